@@ -367,6 +367,7 @@ class MySql(Service):
         image: str = "mysql:8.0.27",
         port: int = 3306,
         volumes: list[str] = ["mydata:/var/lib/mysql-files"],
+        additional_args: list[str] = [],
     ) -> None:
         super().__init__(
             name=name,
@@ -379,6 +380,7 @@ class MySql(Service):
                 "command": [
                     "--default-authentication-plugin=mysql_native_password",
                     "--secure-file-priv=/var/lib/mysql-files",
+                    *additional_args,
                 ],
                 "volumes": volumes,
             },
@@ -541,6 +543,51 @@ class Squid(Service):
         )
 
 
+class Mosquitto(Service):
+    """
+    An MQTT broker that accepts anonymous connections.
+    """
+
+    def __init__(
+        self,
+        name: str = "mosquitto",
+        image: str = "eclipse-mosquitto:2.0.15",
+        port: int = 1883,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "command": ["mosquitto", "-c", "/mosquitto-no-auth.conf"],
+            },
+        )
+
+
+class PubSubEmulator(Service):
+    def __init__(
+        self,
+        name: str = "pubsub-emulator",
+        image: str = "gcr.io/google.com/cloudsdktool/cloud-sdk:425.0.0-emulators",
+        port: int = 8085,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": image,
+                "ports": [port],
+                "command": [
+                    "gcloud",
+                    "beta",
+                    "emulators",
+                    "pubsub",
+                    "start",
+                    f"--host-port=0.0.0.0:{port}",
+                ],
+            },
+        )
+
+
 class Localstack(Service):
     def __init__(
         self,
@@ -61,7 +61,7 @@ use mz_sql::names::{
 use mz_sql::plan::{
     CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
     CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
-    Plan, PlanContext, StatementDesc, StorageHostConfig as PlanStorageHostConfig,
+    Plan, PlanContext, StatementDesc, StorageHostConfig as PlanStorageHostConfig, Webhook,
 };
 use mz_sql::{plan, DEFAULT_SCHEMA};
 use mz_sql_parser::ast::{CreateSinkOption, CreateSourceOption, Statement, WithOptionValue};
@@ -69,7 +69,7 @@ use mz_ssh_util::keys::SshKeyPairSet;
 use mz_stash::{Append, Memory, Postgres, PostgresFactory};
 use mz_storage_client::types::hosts::{StorageHostConfig, StorageHostResourceAllocation};
 use mz_storage_client::types::sinks::{
-    SinkEnvelope, StorageSinkConnection, StorageSinkConnectionBuilder,
+    SinkEnvelope, SinkErrorPolicy, StorageSinkConnection, StorageSinkConnectionBuilder,
 };
 use mz_storage_client::types::sources::{SourceDesc, SourceEnvelope, Timeline};
 use mz_transform::Optimizer;
@@ -548,6 +548,31 @@ impl CatalogState {
             .flatten()
     }
 
+    /// Returns the items that the given item depends on, directly or
+    /// transitively.
+    pub fn transitive_uses(&self, id: GlobalId) -> BTreeSet<GlobalId> {
+        let mut uses = BTreeSet::new();
+        let mut todo = self.get_entry(&id).uses().to_vec();
+        while let Some(id) = todo.pop() {
+            if uses.insert(id) {
+                todo.extend(self.get_entry(&id).uses());
+            }
+        }
+        uses
+    }
+
+    /// Returns the indexes and materialized views exported by the given
+    /// compute instance.
+    pub fn compute_instance_exports(
+        &self,
+        compute_instance: ComputeInstanceId,
+    ) -> impl Iterator<Item = GlobalId> + '_ {
+        self.compute_instances_by_id
+            .get(&compute_instance)
+            .into_iter()
+            .flat_map(|instance| instance.exports.iter().copied())
+    }
+
     /// Associates a name, `GlobalId`, and entry.
     fn insert_item(
         &mut self,
@@ -1489,6 +1514,11 @@ pub enum DataSourceDesc {
     Source,
     /// Receives introspection data from an internal system
     Introspection(IntrospectionType),
+    /// Receives data that is pushed to an HTTP endpoint
+    Webhook(Webhook),
+    /// Receives Arrow record batches that are pushed to its Arrow Flight
+    /// endpoint
+    Flight,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -1508,14 +1538,19 @@ impl Source {
     pub fn size(&self) -> Option<&str> {
         match &self.data_source {
             DataSourceDesc::Ingestion(Ingestion { host_config, .. }) => host_config.size(),
-            DataSourceDesc::Introspection(_) | DataSourceDesc::Source => None,
+            DataSourceDesc::Introspection(_)
+            | DataSourceDesc::Source
+            | DataSourceDesc::Webhook(_)
+            | DataSourceDesc::Flight => None,
         }
     }
 
     /// Returns whether this source ingests data from an external source.
     pub fn is_external(&self) -> bool {
         match self.data_source {
-            DataSourceDesc::Ingestion(_) => true,
+            DataSourceDesc::Ingestion(_) | DataSourceDesc::Webhook(_) | DataSourceDesc::Flight => {
+                true
+            }
             DataSourceDesc::Source | DataSourceDesc::Introspection(_) => false,
         }
     }
@@ -1526,6 +1561,8 @@ impl Source {
             DataSourceDesc::Ingestion(ingestion) => ingestion.desc.name(),
             DataSourceDesc::Source => "subsource",
             DataSourceDesc::Introspection(_) => "source",
+            DataSourceDesc::Webhook(_) => "webhook",
+            DataSourceDesc::Flight => "flight",
         }
     }
 
@@ -1564,6 +1601,7 @@ impl Source {
             },
             DataSourceDesc::Source => None,
             DataSourceDesc::Introspection(_) => None,
+            DataSourceDesc::Webhook(_) | DataSourceDesc::Flight => Some("none"),
         }
     }
 
@@ -1571,7 +1609,10 @@ impl Source {
     pub fn connection_id(&self) -> Option<GlobalId> {
         match &self.data_source {
             DataSourceDesc::Ingestion(ingestion) => ingestion.desc.connection.connection_id(),
-            DataSourceDesc::Source | DataSourceDesc::Introspection(_) => None,
+            DataSourceDesc::Source
+            | DataSourceDesc::Introspection(_)
+            | DataSourceDesc::Webhook(_)
+            | DataSourceDesc::Flight => None,
         }
     }
 }
@@ -1602,11 +1643,13 @@ pub struct Ingestion {
 pub struct Sink {
     pub create_sql: String,
     pub from: GlobalId,
+    pub projection: Option<Vec<usize>>,
     // TODO(benesch): this field duplicates information that could be derived
     // from the connection ID. Too hard to fix at the moment.
     pub connection: StorageSinkConnectionState,
     pub envelope: SinkEnvelope,
     pub with_snapshot: bool,
+    pub error_policy: SinkErrorPolicy,
     pub depends_on: Vec<GlobalId>,
     pub host_config: StorageHostConfig,
 }
@@ -1752,7 +1795,10 @@ impl CatalogItem {
         match &self {
             CatalogItem::Source(source) => match &source.data_source {
                 DataSourceDesc::Ingestion(ingestion) => Ok(Some(&ingestion.desc)),
-                DataSourceDesc::Source | DataSourceDesc::Introspection(_) => Ok(None),
+                DataSourceDesc::Source
+                | DataSourceDesc::Introspection(_)
+                | DataSourceDesc::Webhook(_)
+                | DataSourceDesc::Flight => Ok(None),
             },
             _ => Err(SqlCatalogError::UnexpectedType {
                 name: entry.name().item.to_string(),
@@ -2010,6 +2056,30 @@ impl CatalogEntry {
         matches!(self.item(), CatalogItem::Table(_))
     }
 
+    /// Reports whether this catalog entry is a webhook source, whose data,
+    /// like a table's, is written by the coordinator.
+    pub fn is_webhook_source(&self) -> bool {
+        matches!(
+            self.item(),
+            CatalogItem::Source(Source {
+                data_source: DataSourceDesc::Webhook(_),
+                ..
+            })
+        )
+    }
+
+    /// Reports whether this catalog entry is a Flight source, whose data,
+    /// like a table's, is written by the coordinator.
+    pub fn is_flight_source(&self) -> bool {
+        matches!(
+            self.item(),
+            CatalogItem::Source(Source {
+                data_source: DataSourceDesc::Flight,
+                ..
+            })
+        )
+    }
+
     /// Reports whether this catalog entry is a source.
     pub fn is_source(&self) -> bool {
         matches!(self.item(), CatalogItem::Source(_))
@@ -2995,6 +3065,8 @@ impl<S: Append> Catalog<S> {
             CatalogType::Timestamp => CatalogType::Timestamp,
             CatalogType::TimestampTz => CatalogType::TimestampTz,
             CatalogType::Uuid => CatalogType::Uuid,
+            CatalogType::Inet => CatalogType::Inet,
+            CatalogType::Cidr => CatalogType::Cidr,
             CatalogType::VarChar => CatalogType::VarChar,
             CatalogType::Int2Vector => CatalogType::Int2Vector,
         };
@@ -5502,14 +5574,16 @@ impl<S: Append> Catalog<S> {
                 ..
             }) => CatalogItem::Source(Source {
                 create_sql: source.create_sql,
-                data_source: match source.ingestion {
-                    Some(ingestion) => DataSourceDesc::Ingestion(Ingestion {
+                data_source: match (source.ingestion, source.webhook, source.flight) {
+                    (Some(ingestion), _, _) => DataSourceDesc::Ingestion(Ingestion {
                         desc: ingestion.desc,
                         source_imports: ingestion.source_imports,
                         subsource_exports: ingestion.subsource_exports,
                         host_config: self.resolve_storage_host_config(&host_config)?,
                     }),
-                    None => DataSourceDesc::Source,
+                    (None, Some(webhook), _) => DataSourceDesc::Webhook(webhook),
+                    (None, None, true) => DataSourceDesc::Flight,
+                    (None, None, false) => DataSourceDesc::Source,
                 },
                 desc: source.desc,
                 timeline,
@@ -5554,14 +5628,17 @@ impl<S: Append> Catalog<S> {
             Plan::CreateSink(CreateSinkPlan {
                 sink,
                 with_snapshot,
+                error_policy,
                 host_config,
                 ..
             }) => CatalogItem::Sink(Sink {
                 create_sql: sink.create_sql,
                 from: sink.from,
+                projection: sink.projection,
                 connection: StorageSinkConnectionState::Pending(sink.connection_builder),
                 envelope: sink.envelope,
                 with_snapshot,
+                error_policy,
                 depends_on,
                 host_config: self.resolve_storage_host_config(&host_config)?,
             }),
@@ -6416,7 +6493,10 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
                 DataSourceDesc::Ingestion(ingestion) => {
                     ingestion.subsource_exports.keys().copied().collect()
                 }
-                DataSourceDesc::Source | DataSourceDesc::Introspection(_) => vec![],
+                DataSourceDesc::Source
+                | DataSourceDesc::Introspection(_)
+                | DataSourceDesc::Webhook(_)
+                | DataSourceDesc::Flight => vec![],
             },
             CatalogItem::Table(_)
             | CatalogItem::Log(_)
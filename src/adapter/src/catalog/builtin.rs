@@ -619,6 +619,50 @@ pub const TYPE_UUID_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_INET: BuiltinType<NameReference> = BuiltinType {
+    name: "inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Inet,
+        array_id: None,
+    },
+};
+
+pub const TYPE_INET_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INET.name,
+        },
+        array_id: None,
+    },
+};
+
+pub const TYPE_CIDR: BuiltinType<NameReference> = BuiltinType {
+    name: "cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Cidr,
+        array_id: None,
+    },
+};
+
+pub const TYPE_CIDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_CIDR.name,
+        },
+        array_id: None,
+    },
+};
+
 pub const TYPE_JSONB: BuiltinType<NameReference> = BuiltinType {
     name: "jsonb",
     schema: PG_CATALOG_SCHEMA,
@@ -1766,6 +1810,20 @@ pub static MZ_SOURCE_STATISTICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSourc
     is_retained_metrics_relation: true,
 });
 
+// Like `mz_source_statistics`, this will be replaced with a per-replica table
+// once source/sink multiplexing on a single cluster is supported.
+pub static MZ_SINK_STATISTICS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_sink_statistics",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: Some(IntrospectionType::StorageSinkStatistics),
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("worker_id", ScalarType::UInt64.nullable(false))
+        .with_column("updates_dropped", ScalarType::UInt64.nullable(false))
+        .with_column("write_frontier", ScalarType::MzTimestamp.nullable(true)),
+    is_retained_metrics_relation: true,
+});
+
 pub static MZ_STORAGE_SHARDS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_storage_shards",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2945,6 +3003,10 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_TIMESTAMPTZ_ARRAY),
         Builtin::Type(&TYPE_UUID),
         Builtin::Type(&TYPE_UUID_ARRAY),
+        Builtin::Type(&TYPE_INET),
+        Builtin::Type(&TYPE_INET_ARRAY),
+        Builtin::Type(&TYPE_CIDR),
+        Builtin::Type(&TYPE_CIDR_ARRAY),
         Builtin::Type(&TYPE_VARCHAR),
         Builtin::Type(&TYPE_VARCHAR_ARRAY),
         Builtin::Type(&TYPE_INT2_VECTOR),
@@ -3101,6 +3163,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::Source(&MZ_STORAGE_HOST_METRICS),
         Builtin::Source(&MZ_SOURCE_STATISTICS),
+        Builtin::Source(&MZ_SINK_STATISTICS),
         Builtin::View(&MZ_STORAGE_USAGE),
         Builtin::Table(&MZ_STORAGE_HOST_SIZES),
         Builtin::View(&MZ_SOURCE_UTILIZATION),
@@ -3532,6 +3595,8 @@ mod tests {
                     | ScalarType::VarChar { .. }
                     | ScalarType::Jsonb
                     | ScalarType::Uuid
+                    | ScalarType::Inet
+                    | ScalarType::Cidr
                     | ScalarType::Array(_)
                     | ScalarType::Record { .. }
                     | ScalarType::Oid
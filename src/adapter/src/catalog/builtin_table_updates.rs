@@ -378,10 +378,24 @@ impl CatalogState {
                     mz_storage_client::types::connections::Connection::Postgres { .. } => {
                         "postgres"
                     }
+                    mz_storage_client::types::connections::Connection::MySql { .. } => "mysql",
+                    mz_storage_client::types::connections::Connection::Mqtt { .. } => "mqtt",
+                    mz_storage_client::types::connections::Connection::SqlServer { .. } => {
+                        "sql-server"
+                    }
+                    mz_storage_client::types::connections::Connection::Elasticsearch { .. } => {
+                        "elasticsearch"
+                    }
+                    mz_storage_client::types::connections::Connection::Redis { .. } => "redis",
+                    mz_storage_client::types::connections::Connection::Nats { .. } => "nats",
                     mz_storage_client::types::connections::Connection::Aws(..) => "aws",
                     mz_storage_client::types::connections::Connection::AwsPrivatelink(..) => {
                         "aws-privatelink"
                     }
+                    mz_storage_client::types::connections::Connection::Gcp(..) => "gcp",
+                    mz_storage_client::types::connections::Connection::AzureBlob(..) => {
+                        "azure-blob"
+                    }
                     mz_storage_client::types::connections::Connection::Ssh { .. } => "ssh-tunnel",
                 }),
             ]),
@@ -404,8 +418,16 @@ impl CatalogState {
             }
             mz_storage_client::types::connections::Connection::Csr(_)
             | mz_storage_client::types::connections::Connection::Postgres(_)
+            | mz_storage_client::types::connections::Connection::MySql(_)
+            | mz_storage_client::types::connections::Connection::Mqtt(_)
+            | mz_storage_client::types::connections::Connection::SqlServer(_)
+            | mz_storage_client::types::connections::Connection::Elasticsearch(_)
+            | mz_storage_client::types::connections::Connection::Redis(_)
+            | mz_storage_client::types::connections::Connection::Nats(_)
             | mz_storage_client::types::connections::Connection::Aws(_)
-            | mz_storage_client::types::connections::Connection::AwsPrivatelink(_) => {
+            | mz_storage_client::types::connections::Connection::AwsPrivatelink(_)
+            | mz_storage_client::types::connections::Connection::Gcp(_)
+            | mz_storage_client::types::connections::Connection::AzureBlob(_) => {
                 if let Some(aws_principal_context) = self.aws_principal_context.as_ref() {
                     updates.extend(self.pack_aws_privatelink_connection_update(
                         id,
@@ -583,6 +605,11 @@ impl CatalogState {
                         diff,
                     });
                 }
+                StorageSinkConnection::Object(_)
+                | StorageSinkConnection::Postgres(_)
+                | StorageSinkConnection::Elasticsearch(_)
+                | StorageSinkConnection::Redis(_)
+                | StorageSinkConnection::Nats(_) => (),
             };
             updates.push(BuiltinTableUpdate {
                 id: self.resolve_builtin_table(&MZ_SINKS),
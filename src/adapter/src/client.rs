@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{Display, Formatter};
 use std::future::Future;
 use std::sync::Arc;
@@ -23,8 +23,9 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::id_gen::IdAllocator;
 use mz_ore::task::{AbortOnDropHandle, JoinHandleExt};
 use mz_ore::thread::JoinOnDropHandle;
-use mz_repr::{GlobalId, Row, ScalarType};
+use mz_repr::{GlobalId, RelationDesc, Row, ScalarType};
 use mz_sql::ast::{Raw, Statement};
+use mz_sql::names::PartialObjectName;
 
 use crate::catalog::INTROSPECTION_USER;
 use crate::command::{Canceled, Command, ExecuteResponse, Response, StartupResponse};
@@ -417,6 +418,55 @@ impl SessionClient {
         .await
     }
 
+    /// Appends a set of rows to the named webhook source.
+    ///
+    /// The raw request `body` and `headers` are used to validate the request
+    /// against the source's HMAC configuration, if any, before the rows are
+    /// added to the current transaction.
+    pub async fn append_webhook(
+        &mut self,
+        name: PartialObjectName,
+        body: Vec<u8>,
+        headers: BTreeMap<String, String>,
+        rows: Vec<Row>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        self.send(|tx, session| Command::AppendWebhook {
+            name,
+            body,
+            headers,
+            rows,
+            session,
+            tx,
+        })
+        .await
+    }
+
+    /// Resolves the named Flight source, returning its ID and the schema that
+    /// the record batches written to it must have.
+    pub async fn describe_flight_source(
+        &mut self,
+        name: PartialObjectName,
+    ) -> Result<(GlobalId, RelationDesc), AdapterError> {
+        self.send(|tx, session| Command::DescribeFlightSource { name, session, tx })
+            .await
+    }
+
+    /// Appends a set of rows to the given Flight source, as part of the
+    /// current transaction.
+    pub async fn append_flight(
+        &mut self,
+        id: GlobalId,
+        rows: Vec<Row>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        self.send(|tx, session| Command::AppendFlight {
+            id,
+            rows,
+            session,
+            tx,
+        })
+        .await
+    }
+
     /// Terminates the client session.
     pub async fn terminate(&mut self) {
         let res = self
@@ -467,6 +517,9 @@ impl SessionClient {
                     | Command::CancelRequest { .. }
                     | Command::DumpCatalog { .. }
                     | Command::CopyRows { .. }
+                    | Command::AppendWebhook { .. }
+                    | Command::DescribeFlightSource { .. }
+                    | Command::AppendFlight { .. }
                     | Command::Terminate { .. } => {}
                 };
                 cmd
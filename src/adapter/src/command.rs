@@ -11,6 +11,7 @@
 // https://github.com/rust-lang/rust-clippy/pull/9037 makes it into stable
 #![allow(clippy::extra_unused_lifetimes)]
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
@@ -24,8 +25,9 @@ use tokio::sync::watch;
 
 use mz_ore::str::StrExt;
 use mz_pgcopy::CopyFormatParams;
-use mz_repr::{GlobalId, Row, ScalarType};
+use mz_repr::{GlobalId, RelationDesc, Row, ScalarType};
 use mz_sql::ast::{FetchDirection, ObjectType, Raw, Statement};
+use mz_sql::names::PartialObjectName;
 use mz_sql::plan::ExecuteTimeout;
 
 use crate::client::ConnectionId;
@@ -102,6 +104,28 @@ pub enum Command {
         tx: oneshot::Sender<Response<ExecuteResponse>>,
     },
 
+    AppendWebhook {
+        name: PartialObjectName,
+        body: Vec<u8>,
+        headers: BTreeMap<String, String>,
+        rows: Vec<Row>,
+        session: Session,
+        tx: oneshot::Sender<Response<ExecuteResponse>>,
+    },
+
+    DescribeFlightSource {
+        name: PartialObjectName,
+        session: Session,
+        tx: oneshot::Sender<Response<(GlobalId, RelationDesc)>>,
+    },
+
+    AppendFlight {
+        id: GlobalId,
+        rows: Vec<Row>,
+        session: Session,
+        tx: oneshot::Sender<Response<ExecuteResponse>>,
+    },
+
     Terminate {
         session: Session,
         tx: Option<oneshot::Sender<Response<()>>>,
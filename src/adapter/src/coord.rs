@@ -88,6 +88,7 @@ use uuid::Uuid;
 use mz_build_info::BuildInfo;
 use mz_cloud_resources::{CloudResourceController, VpcEndpointConfig};
 use mz_compute_client::controller::{ComputeInstanceEvent, ComputeInstanceId, ReplicaId};
+use mz_compute_client::protocol::command::ComputeParameter;
 use mz_orchestrator::ServiceProcessMetrics;
 use mz_ore::cast::CastFrom;
 use mz_ore::metrics::MetricsRegistry;
@@ -512,7 +513,7 @@ impl<S: Append + 'static> Coordinator<S> {
             self.controller.compute.create_instance(
                 instance.id,
                 instance.log_indexes.clone(),
-                self.catalog.system_config().max_result_size(),
+                self.compute_config(),
             )?;
             for (replica_id, replica) in instance.replicas_by_id.clone() {
                 let introspection_collections = replica
@@ -651,7 +652,9 @@ impl<S: Append + 'static> Coordinator<S> {
                                 source_status_collection_id,
                             )
                         }
-                        DataSourceDesc::Source => (DataSource::Other, None),
+                        DataSourceDesc::Source
+                        | DataSourceDesc::Webhook(_)
+                        | DataSourceDesc::Flight => (DataSource::Other, None),
                         DataSourceDesc::Introspection(introspection) => {
                             (DataSource::Introspection(*introspection), None)
                         }
@@ -936,7 +939,8 @@ impl<S: Append + 'static> Coordinator<S> {
         builtin_table_updates.extend(self.catalog.state().pack_all_replica_size_updates());
         builtin_table_updates.extend(self.catalog.state().pack_all_storage_host_size_updates());
 
-        // Advance all tables to the current timestamp
+        // Advance all tables, webhook and Flight sources to the current
+        // timestamp
         info!("coordinator init: advancing all tables to current timestamp");
         let WriteTimestamp {
             timestamp: _,
@@ -944,7 +948,9 @@ impl<S: Append + 'static> Coordinator<S> {
         } = self.get_local_write_ts().await;
         let appends = entries
             .iter()
-            .filter(|entry| entry.is_table())
+            .filter(|entry| {
+                entry.is_table() || entry.is_webhook_source() || entry.is_flight_source()
+            })
             .map(|entry| (entry.id(), Vec::new(), advance_to))
             .collect();
         self.controller
@@ -1035,6 +1041,17 @@ impl<S: Append + 'static> Coordinator<S> {
         Ok(())
     }
 
+    /// Returns the compute configuration parameters derived from the system
+    /// configuration.
+    fn compute_config(&self) -> BTreeSet<ComputeParameter> {
+        let config = self.catalog.system_config();
+        [
+            ComputeParameter::MaxResultSize(config.max_result_size()),
+            ComputeParameter::EnableAssertNonNegative(config.enable_assert_non_negative()),
+        ]
+        .into()
+    }
+
     /// Serves the coordinator, receiving commands from users over `cmd_rx`
     /// and feedback from dataflow workers over `feedback_rx`.
     ///
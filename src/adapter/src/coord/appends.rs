@@ -279,8 +279,10 @@ impl<S: Append + 'static> Coordinator<S> {
         for (_, updates) in &mut appends {
             differential_dataflow::consolidation::consolidate(updates);
         }
-        // Add table advancements for all tables.
-        for table in self.catalog.entries().filter(|entry| entry.is_table()) {
+        // Add table advancements for all tables, webhook and Flight sources.
+        for table in self.catalog.entries().filter(|entry| {
+            entry.is_table() || entry.is_webhook_source() || entry.is_flight_source()
+        }) {
             appends.entry(table.id()).or_default();
         }
         let appends = appends
@@ -116,6 +116,33 @@ impl<S: Append + 'static> Coordinator<S> {
                 let _ = tx.send(Response { result, session });
             }
 
+            Command::AppendWebhook {
+                name,
+                body,
+                headers,
+                rows,
+                session,
+                tx,
+            } => {
+                let tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                self.sequence_append_webhook(tx, session, name, body, headers, rows);
+            }
+
+            Command::DescribeFlightSource { name, session, tx } => {
+                let result = self.describe_flight_source(&session, name);
+                let _ = tx.send(Response { result, session });
+            }
+
+            Command::AppendFlight {
+                id,
+                rows,
+                mut session,
+                tx,
+            } => {
+                let result = self.sequence_append_flight(&mut session, id, rows);
+                let _ = tx.send(Response { result, session });
+            }
+
             Command::Terminate { mut session, tx } => {
                 self.handle_terminate(&mut session).await;
                 if let Some(tx) = tx {
@@ -33,11 +33,12 @@ use mz_expr::{
     CollectionPlan, Id, MapFilterProject, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr,
     UnmaterializableFunc, RECURSION_LIMIT,
 };
-use mz_ore::cast::ReinterpretCast;
+use mz_ore::cast::{CastFrom, ReinterpretCast};
 use mz_ore::stack::{maybe_grow, CheckedRecursion, RecursionGuard, RecursionLimitError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::{Datum, GlobalId, Row, Timestamp};
 use mz_stash::Append;
+use mz_transform::plan_limits::PlanLimits;
 
 use crate::catalog::{CatalogItem, CatalogState, DataSourceDesc, MaterializedView, Source, View};
 use crate::coord::ddl::CatalogTxn;
@@ -55,6 +56,13 @@ pub struct DataflowBuilder<'a, T> {
     /// its `storage_mut()` method.
     pub compute: ComputeInstanceRef<'a, T>,
     recursion_guard: RecursionGuard,
+    /// The indexed views that may be read in place of an identical view.
+    ///
+    /// This is only sound when the `as_of` of the dataflow is derived from the
+    /// collections it imports, rather than determined before the dataflow is
+    /// built. It is further restricted to views that the dataflow depends on
+    /// anyway, as the catalog does not record a dependency on the reused view.
+    reusable_views: BTreeSet<GlobalId>,
 }
 
 /// The styles in which an expression can be prepared for use in a dataflow.
@@ -83,6 +91,7 @@ impl<S: Append + 'static> Coordinator<S> {
             catalog: self.catalog.state(),
             compute,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
+            reusable_views: BTreeSet::new(),
         }
     }
 
@@ -205,6 +214,7 @@ impl CatalogTxn<'_, mz_repr::Timestamp> {
             catalog: self.catalog,
             compute,
             recursion_guard: RecursionGuard::with_limit(RECURSION_LIMIT),
+            reusable_views: BTreeSet::new(),
         }
     }
 }
@@ -300,6 +310,15 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         view: &OptimizedMirRelationExpr,
         dataflow: &mut DataflowDesc,
     ) -> Result<(), AdapterError> {
+        if !self.reusable_views.is_empty() {
+            if let Some(equivalent_id) = self.equivalent_indexed_view(*view_id, view) {
+                // Read the existing index rather than building the view again.
+                self.import_into_dataflow(&equivalent_id, dataflow)?;
+                let get = MirRelationExpr::global_get(equivalent_id, view.typ());
+                dataflow.insert_plan(*view_id, OptimizedMirRelationExpr::declare_optimized(get));
+                return Ok(());
+            }
+        }
         for get_id in view.depends_on() {
             self.import_into_dataflow(&get_id, dataflow)?;
         }
@@ -307,6 +326,41 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         Ok(())
     }
 
+    /// Returns a view other than `view_id` that is indexed on this compute
+    /// instance, is one of the `reusable_views`, and whose expression is
+    /// identical to `view`, up to the choice of local identifiers, if one
+    /// exists.
+    fn equivalent_indexed_view(
+        &self,
+        view_id: GlobalId,
+        view: &OptimizedMirRelationExpr,
+    ) -> Option<GlobalId> {
+        let index_oracle = self.index_oracle();
+        let indexed_ids = self
+            .catalog
+            .compute_instance_exports(self.compute.instance_id())
+            .filter_map(
+                |export_id| match self.catalog.get_entry(&export_id).item() {
+                    CatalogItem::Index(index) => Some(index.on),
+                    _ => None,
+                },
+            )
+            .filter(|on_id| *on_id != view_id && self.reusable_views.contains(on_id))
+            .collect::<BTreeSet<_>>();
+        let mut fingerprint = None;
+        indexed_ids
+            .into_iter()
+            .find(|on_id| match self.catalog.get_entry(on_id).item() {
+                CatalogItem::View(other) => {
+                    let fingerprint = *fingerprint.get_or_insert_with(|| view.fingerprint());
+                    other.optimized_expr.fingerprint() == fingerprint
+                        && other.optimized_expr.eq_modulo_local_ids(view)
+                        && index_oracle.indexes_on(*on_id).next().is_some()
+                }
+                _ => false,
+            })
+    }
+
     /// Builds a dataflow description for the index with the specified ID.
     pub fn build_index_dataflow(&mut self, id: GlobalId) -> Result<DataflowDesc, AdapterError> {
         let index_entry = self.catalog.get_entry(&id);
@@ -326,7 +380,13 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
             .clone();
         let name = index_entry.name().to_string();
         let mut dataflow = DataflowDesc::new(name);
-        self.import_into_dataflow(&index.on, &mut dataflow)?;
+        // The `as_of` of an index dataflow is determined by the collections it
+        // imports, so views can be read from the indexes of identical views
+        // that the index depends on.
+        self.reusable_views = self.catalog.transitive_uses(id);
+        let imported = self.import_into_dataflow(&index.on, &mut dataflow);
+        self.reusable_views.clear();
+        imported?;
         for BuildDesc { plan, .. } in &mut dataflow.objects_to_build {
             prep_relation_expr(self.catalog, plan, ExprPrepStyle::Index)?;
         }
@@ -428,12 +488,31 @@ impl<'a> DataflowBuilder<'a, mz_repr::Timestamp> {
         Ok(dataflow)
     }
 
+    /// Checks that the optimized `dataflow` does not exceed the plan limits
+    /// set in the system configuration.
+    ///
+    /// This must only be called when creating new objects, so that lowering a
+    /// limit does not prevent existing objects from being rebuilt on restart.
+    pub fn check_plan_limits(&self, dataflow: &DataflowDesc) -> Result<(), AdapterError> {
+        let config = self.catalog.system_config();
+        let limit = |max: u32| (max > 0).then(|| usize::cast_from(max));
+        let limits = PlanLimits {
+            max_nodes: limit(config.max_plan_nodes()),
+            max_join_inputs: limit(config.max_plan_join_inputs()),
+            max_depth: limit(config.max_plan_depth()),
+        };
+        limits.check_dataflow(dataflow)?;
+        Ok(())
+    }
+
     /// Determine the given source's monotonicity.
     fn monotonic_source(&self, source: &Source) -> bool {
         // TODO(petrosagg): store an inverse mapping of subsource -> source in the catalog so that
         // we can retrieve monotonicity information from the parent source.
         match &source.data_source {
             DataSourceDesc::Ingestion(ingestion) => ingestion.desc.monotonic(),
+            // Webhook and Flight sources are only ever appended to.
+            DataSourceDesc::Webhook(_) | DataSourceDesc::Flight => true,
             DataSourceDesc::Introspection(_) | DataSourceDesc::Source => false,
         }
     }
@@ -683,6 +762,14 @@ fn eval_unmaterializable_func(
             None => coord_bail!("cannot call mz_now in this context"),
             Some(logical_time) => pack(Datum::MzTimestamp(logical_time)),
         },
+        UnmaterializableFunc::MzRandom => match logical_time {
+            None => coord_bail!("cannot call mz_random in this context"),
+            // Seed the value with the timestamp of the query, so that the
+            // result is the same wherever the query is evaluated.
+            Some(logical_time) => pack(Datum::from(mz_expr::func::hash_to_unit_interval(
+                &logical_time.to_bytes(),
+            ))),
+        },
         UnmaterializableFunc::MzSessionId => pack(Datum::from(state.config().session_id)),
         UnmaterializableFunc::MzUptime => {
             let uptime = state.config().start_instant.elapsed();
@@ -484,9 +484,11 @@ impl<S: Append + 'static> Coordinator<S> {
                 ))
                 .unwrap()
                 .into_owned(),
+            projection: sink.projection.clone(),
             connection,
             envelope: Some(sink.envelope),
             as_of,
+            error_policy: sink.error_policy,
             status_id,
             from_storage_metadata: (),
         };
@@ -303,6 +303,9 @@ impl AdapterError {
                     .into(),
             ),
             AdapterError::PlanError(e) => e.hint(),
+            AdapterError::Transform(TransformError::PlanLimitExceeded { .. }) => {
+                Some("Try simplifying the query, or splitting it into several views.".into())
+            }
             _ => None,
         }
     }
@@ -22,7 +22,9 @@ use mz_repr::{
 };
 use mz_sql::plan::{HirRelationExpr, HirScalarExpr};
 
-use super::{AnnotatedPlan, ExplainContext, ExplainSinglePlan, Explainable};
+use super::{AnnotatedPlan, AnnotationsJson, ExplainContext, ExplainSinglePlan, Explainable};
+
+impl AnnotationsJson for HirRelationExpr {}
 
 impl<'a> Explain<'a> for Explainable<'a, HirRelationExpr> {
     type Context = ExplainContext<'a>;
@@ -212,6 +212,7 @@ impl<'a> Displayable<'a, HirRelationExpr> {
                 order_key,
                 limit,
                 offset,
+                expected_group_size,
                 input,
             } => {
                 write!(f, "{}TopK", ctx.indent)?;
@@ -229,6 +230,9 @@ impl<'a> Displayable<'a, HirRelationExpr> {
                 if offset > &0 {
                     write!(f, " offset={}", offset)?
                 }
+                if let Some(expected_group_size) = expected_group_size {
+                    write!(f, " exp_group_size={}", expected_group_size)?;
+                }
                 writeln!(f)?;
                 ctx.indented(|ctx| Displayable::from(input.as_ref()).fmt_text(f, ctx))?;
             }
@@ -393,17 +397,24 @@ impl<'a> DisplayText for Displayable<'a, HirScalarExpr> {
 impl<'a> DisplayText for Displayable<'a, AggregateExpr> {
     fn fmt_text(&self, f: &mut fmt::Formatter<'_>, ctx: &mut ()) -> fmt::Result {
         if self.0.is_count_asterisk() {
-            return write!(f, "count(*)");
-        }
+            write!(f, "count(*)")?;
+        } else {
+            write!(
+                f,
+                "{}({}",
+                self.0.func.clone().into_expr(),
+                if self.0.distinct { "distinct " } else { "" }
+            )?;
 
-        write!(
-            f,
-            "{}({}",
-            self.0.func.clone().into_expr(),
-            if self.0.distinct { "distinct " } else { "" }
-        )?;
+            Displayable::from(self.0.expr.as_ref()).fmt_text(f, ctx)?;
+            write!(f, ")")?;
+        }
 
-        Displayable::from(self.0.expr.as_ref()).fmt_text(f, ctx)?;
-        write!(f, ")")
+        if let Some(filter) = &self.0.filter {
+            write!(f, " filter (where ")?;
+            Displayable::from(filter.as_ref()).fmt_text(f, ctx)?;
+            write!(f, ")")?;
+        }
+        Ok(())
     }
 }
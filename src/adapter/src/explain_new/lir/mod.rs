@@ -17,7 +17,9 @@ use mz_compute_client::plan::Plan;
 use mz_compute_client::types::dataflows::DataflowDescription;
 use mz_repr::explain_new::{Explain, ExplainConfig, ExplainError, UnsupportedFormat};
 
-use super::{AnnotatedPlan, ExplainContext, ExplainMultiPlan, Explainable};
+use super::{AnnotatedPlan, AnnotationsJson, ExplainContext, ExplainMultiPlan, Explainable};
+
+impl AnnotationsJson for Plan {}
 
 impl<'a> Explain<'a> for Explainable<'a, DataflowDescription<Plan>> {
     type Context = ExplainContext<'a>;
@@ -22,7 +22,8 @@ use mz_transform::attribute::{
 };
 
 use super::{
-    AnnotatedPlan, Attributes, ExplainContext, ExplainMultiPlan, ExplainSinglePlan, Explainable,
+    AnnotatedPlan, AnnotationsJson, Attributes, ExplainContext, ExplainMultiPlan,
+    ExplainSinglePlan, Explainable,
 };
 
 impl<'a> Explain<'a> for Explainable<'a, MirRelationExpr> {
@@ -236,6 +237,20 @@ impl<'a> AnnotatedPlan<'a, MirRelationExpr> {
     }
 }
 
+impl AnnotationsJson for MirRelationExpr {
+    fn annotations_json(
+        plan: &AnnotatedPlan<'_, Self>,
+    ) -> serde_json::Result<Option<serde_json::Value>> {
+        let annotations = plan
+            .plan
+            .post_order_vec()
+            .into_iter()
+            .map(|expr| plan.annotations.get(expr).cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+        serde_json::to_value(annotations).map(Some)
+    }
+}
+
 /// Normalize the way inputs of multi-input variants are rendered.
 ///
 /// After the transform is applied, non-trival inputs `$input` of variants with
@@ -417,6 +417,7 @@ impl<'a> Displayable<'a, MirRelationExpr> {
                 limit,
                 offset,
                 monotonic,
+                expected_group_size,
                 input,
             } => {
                 FmtNode {
@@ -437,6 +438,9 @@ impl<'a> Displayable<'a, MirRelationExpr> {
                             write!(f, " offset={}", offset)?
                         }
                         write!(f, " monotonic={}", monotonic)?;
+                        if let Some(expected_group_size) = expected_group_size {
+                            write!(f, " exp_group_size={}", expected_group_size)?;
+                        }
                         self.fmt_attributes(f, ctx)
                     },
                     fmt_children: |f, ctx| {
@@ -701,17 +705,24 @@ impl<'a> DisplayText for Displayable<'a, MirScalarExpr> {
 impl<'a> DisplayText for Displayable<'a, AggregateExpr> {
     fn fmt_text(&self, f: &mut fmt::Formatter<'_>, ctx: &mut ()) -> fmt::Result {
         if self.0.is_count_asterisk() {
-            return write!(f, "count(*)");
-        }
+            write!(f, "count(*)")?;
+        } else {
+            write!(
+                f,
+                "{}({}",
+                self.0.func.clone(),
+                if self.0.distinct { "distinct " } else { "" }
+            )?;
 
-        write!(
-            f,
-            "{}({}",
-            self.0.func.clone(),
-            if self.0.distinct { "distinct " } else { "" }
-        )?;
+            Displayable::from(&self.0.expr).fmt_text(f, ctx)?;
+            write!(f, ")")?;
+        }
 
-        Displayable::from(&self.0.expr).fmt_text(f, ctx)?;
-        write!(f, ")")
+        if let Some(filter) = &self.0.filter {
+            write!(f, " filter (where ")?;
+            Displayable::from(filter).fmt_text(f, ctx)?;
+            write!(f, ")")?;
+        }
+        Ok(())
     }
 }
@@ -26,6 +26,7 @@ use mz_repr::explain_new::{
     separated_text, DisplayJson, DisplayText, ExplainConfig, ExprHumanizer, RenderingContext,
 };
 use mz_repr::GlobalId;
+use serde::Serialize;
 
 use crate::coord::peek::{self, FastPathPlan};
 
@@ -79,15 +80,36 @@ pub(crate) struct AnnotatedPlan<'a, T> {
 }
 
 /// A container for derived attributes.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, Serialize)]
 pub struct Attributes {
+    #[serde(skip_serializing_if = "Option::is_none")]
     non_negative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     subtree_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     arity: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     types: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     keys: Option<String>,
 }
 
+/// Plans whose [`Attributes`] can be rendered in the JSON explain format.
+///
+/// The text format renders the attributes of each node next to the node
+/// itself. As the JSON format renders the plan through its `Serialize`
+/// implementation, the attributes are rendered separately instead, as a list
+/// that enumerates the plan nodes in post-order.
+pub(crate) trait AnnotationsJson: Sized {
+    /// Returns the attributes of the nodes of `plan` in post-order, or `None`
+    /// if attributes are not supported for this type of plan.
+    fn annotations_json(
+        _plan: &AnnotatedPlan<'_, Self>,
+    ) -> serde_json::Result<Option<serde_json::Value>> {
+        Ok(None)
+    }
+}
+
 impl fmt::Display for Attributes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut builder = f.debug_struct("//");
@@ -199,10 +221,27 @@ where
 
 impl<'a, T: 'a> DisplayJson for ExplainSinglePlan<'a, T>
 where
-    T: serde::Serialize,
+    T: serde::Serialize + AnnotationsJson,
 {
     fn to_serde_value(&self) -> serde_json::Result<serde_json::Value> {
-        serde_json::to_value(self.plan.plan)
+        let plan = serde_json::to_value(self.plan.plan)?;
+        match self.annotations_json()? {
+            Some(annotations) => Ok(serde_json::json!({
+                "plan": plan,
+                "annotations": annotations,
+            })),
+            None => Ok(plan),
+        }
+    }
+}
+
+impl<'a, T: AnnotationsJson> ExplainSinglePlan<'a, T> {
+    fn annotations_json(&self) -> serde_json::Result<Option<serde_json::Value>> {
+        if self.context.config.requires_attributes() {
+            T::annotations_json(&self.plan)
+        } else {
+            Ok(None)
+        }
     }
 }
 
@@ -292,20 +331,27 @@ where
 
 impl<'a, T: 'a> DisplayJson for ExplainMultiPlan<'a, T>
 where
-    T: serde::Serialize,
+    T: serde::Serialize + AnnotationsJson,
 {
     fn to_serde_value(&self) -> serde_json::Result<serde_json::Value> {
+        let requires_attributes = self.context.config.requires_attributes();
         let plans = self
             .plans
             .iter()
             .map(|(id, plan)| {
                 // TODO: fix plans with Constants
-                serde_json::json!({
+                let mut json = serde_json::json!({
                     "id": id,
                     "plan": &plan.plan
-                })
+                });
+                if requires_attributes {
+                    if let Some(annotations) = T::annotations_json(plan)? {
+                        json["annotations"] = annotations;
+                    }
+                }
+                Ok(json)
             })
-            .collect::<Vec<_>>();
+            .collect::<serde_json::Result<Vec<_>>>()?;
 
         let sources = self
             .sources
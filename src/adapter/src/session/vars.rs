@@ -308,6 +308,28 @@ pub const MAX_RESULT_SIZE: ServerVar<u32> = ServerVar {
     internal: false,
 };
 
+const MAX_PLAN_NODES: ServerVar<u32> = ServerVar {
+    name: UncasedStr::new("max_plan_nodes"),
+    value: &0,
+    description: "The maximum number of operators in the plan of a single dataflow, or 0 for no limit (Materialize).",
+    internal: false,
+};
+
+const MAX_PLAN_JOIN_INPUTS: ServerVar<u32> = ServerVar {
+    name: UncasedStr::new("max_plan_join_inputs"),
+    value: &0,
+    description:
+        "The maximum number of inputs to a join in a plan, or 0 for no limit (Materialize).",
+    internal: false,
+};
+
+const MAX_PLAN_DEPTH: ServerVar<u32> = ServerVar {
+    name: UncasedStr::new("max_plan_depth"),
+    value: &0,
+    description: "The maximum nesting depth of a plan, or 0 for no limit (Materialize).",
+    internal: false,
+};
+
 /// The logical compaction window for builtin tables and sources that have the
 /// `retained_metrics_relation` flag set.
 ///
@@ -337,6 +359,15 @@ static WINDOW_FUNCTIONS: ServerVar<bool> = ServerVar {
     internal: false,
 };
 
+/// Feature flag indicating whether compute replicas check exported
+/// collections for negative accumulations.
+pub const ENABLE_ASSERT_NON_NEGATIVE: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("enable_assert_non_negative"),
+    value: &false,
+    description: "Feature flag indicating whether compute replicas check exported collections for negative accumulations (Materialize).",
+    internal: true,
+};
+
 /// Boolean flag indicating that the remote configuration was synchronized at
 /// least once with the persistent [SessionVars].
 pub static CONFIG_HAS_SYNCED_ONCE: ServerVar<bool> = ServerVar {
@@ -993,10 +1024,14 @@ pub struct SystemVars {
     max_secrets: SystemVar<u32>,
     max_roles: SystemVar<u32>,
     max_result_size: SystemVar<u32>,
+    max_plan_nodes: SystemVar<u32>,
+    max_plan_join_inputs: SystemVar<u32>,
+    max_plan_depth: SystemVar<u32>,
     allowed_cluster_replica_sizes: SystemVar<Vec<String>>, // TODO: BTreeSet<String> will be better
     window_functions: SystemVar<bool>,
     config_has_synced_once: SystemVar<bool>,
     metrics_retention: SystemVar<Duration>,
+    enable_assert_non_negative: SystemVar<bool>,
 }
 
 impl Default for SystemVars {
@@ -1015,10 +1050,14 @@ impl Default for SystemVars {
             max_secrets: SystemVar::new(&MAX_SECRETS),
             max_roles: SystemVar::new(&MAX_ROLES),
             max_result_size: SystemVar::new(&MAX_RESULT_SIZE),
+            max_plan_nodes: SystemVar::new(&MAX_PLAN_NODES),
+            max_plan_join_inputs: SystemVar::new(&MAX_PLAN_JOIN_INPUTS),
+            max_plan_depth: SystemVar::new(&MAX_PLAN_DEPTH),
             allowed_cluster_replica_sizes: SystemVar::new(&ALLOWED_CLUSTER_REPLICA_SIZES),
             window_functions: SystemVar::new(&WINDOW_FUNCTIONS),
             config_has_synced_once: SystemVar::new(&CONFIG_HAS_SYNCED_ONCE),
             metrics_retention: SystemVar::new(&METRICS_RETENTION),
+            enable_assert_non_negative: SystemVar::new(&ENABLE_ASSERT_NON_NEGATIVE),
         }
     }
 }
@@ -1027,7 +1066,7 @@ impl SystemVars {
     /// Returns an iterator over the configuration parameters and their current
     /// values on disk.
     pub fn iter(&self) -> impl Iterator<Item = &dyn Var> {
-        let vars: [&dyn Var; 17] = [
+        let vars: [&dyn Var; 21] = [
             &self.max_aws_privatelink_connections,
             &self.max_tables,
             &self.max_sources,
@@ -1041,10 +1080,14 @@ impl SystemVars {
             &self.max_secrets,
             &self.max_roles,
             &self.max_result_size,
+            &self.max_plan_nodes,
+            &self.max_plan_join_inputs,
+            &self.max_plan_depth,
             &self.allowed_cluster_replica_sizes,
             &self.window_functions,
             &self.config_has_synced_once,
             &self.metrics_retention,
+            &self.enable_assert_non_negative,
         ];
         vars.into_iter()
     }
@@ -1098,6 +1141,12 @@ impl SystemVars {
             Ok(&self.max_roles)
         } else if name == MAX_RESULT_SIZE.name {
             Ok(&self.max_result_size)
+        } else if name == MAX_PLAN_NODES.name {
+            Ok(&self.max_plan_nodes)
+        } else if name == MAX_PLAN_JOIN_INPUTS.name {
+            Ok(&self.max_plan_join_inputs)
+        } else if name == MAX_PLAN_DEPTH.name {
+            Ok(&self.max_plan_depth)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             Ok(&self.allowed_cluster_replica_sizes)
         } else if name == WINDOW_FUNCTIONS.name {
@@ -1106,6 +1155,8 @@ impl SystemVars {
             Ok(&self.config_has_synced_once)
         } else if name == METRICS_RETENTION.name {
             Ok(&self.metrics_retention)
+        } else if name == ENABLE_ASSERT_NON_NEGATIVE.name {
+            Ok(&self.enable_assert_non_negative)
         } else {
             Err(AdapterError::UnknownParameter(name.into()))
         }
@@ -1147,6 +1198,12 @@ impl SystemVars {
             self.max_roles.is_default(value)
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.is_default(value)
+        } else if name == MAX_PLAN_NODES.name {
+            self.max_plan_nodes.is_default(value)
+        } else if name == MAX_PLAN_JOIN_INPUTS.name {
+            self.max_plan_join_inputs.is_default(value)
+        } else if name == MAX_PLAN_DEPTH.name {
+            self.max_plan_depth.is_default(value)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.is_default(value)
         } else if name == WINDOW_FUNCTIONS.name {
@@ -1155,6 +1212,8 @@ impl SystemVars {
             self.config_has_synced_once.is_default(value)
         } else if name == METRICS_RETENTION.name {
             self.metrics_retention.is_default(value)
+        } else if name == ENABLE_ASSERT_NON_NEGATIVE.name {
+            self.enable_assert_non_negative.is_default(value)
         } else {
             Err(AdapterError::UnknownParameter(name.into()))
         }
@@ -1205,6 +1264,12 @@ impl SystemVars {
             self.max_roles.set(value)
         } else if name == MAX_RESULT_SIZE.name {
             self.max_result_size.set(value)
+        } else if name == MAX_PLAN_NODES.name {
+            self.max_plan_nodes.set(value)
+        } else if name == MAX_PLAN_JOIN_INPUTS.name {
+            self.max_plan_join_inputs.set(value)
+        } else if name == MAX_PLAN_DEPTH.name {
+            self.max_plan_depth.set(value)
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             self.allowed_cluster_replica_sizes.set(value)
         } else if name == WINDOW_FUNCTIONS.name {
@@ -1213,6 +1278,8 @@ impl SystemVars {
             self.config_has_synced_once.set(value)
         } else if name == METRICS_RETENTION.name {
             self.metrics_retention.set(value)
+        } else if name == ENABLE_ASSERT_NON_NEGATIVE.name {
+            self.enable_assert_non_negative.set(value)
         } else {
             Err(AdapterError::UnknownParameter(name.into()))
         }
@@ -1258,6 +1325,12 @@ impl SystemVars {
             Ok(self.max_roles.reset())
         } else if name == MAX_RESULT_SIZE.name {
             Ok(self.max_result_size.reset())
+        } else if name == MAX_PLAN_NODES.name {
+            Ok(self.max_plan_nodes.reset())
+        } else if name == MAX_PLAN_JOIN_INPUTS.name {
+            Ok(self.max_plan_join_inputs.reset())
+        } else if name == MAX_PLAN_DEPTH.name {
+            Ok(self.max_plan_depth.reset())
         } else if name == ALLOWED_CLUSTER_REPLICA_SIZES.name {
             Ok(self.allowed_cluster_replica_sizes.reset())
         } else if name == WINDOW_FUNCTIONS.name {
@@ -1266,6 +1339,8 @@ impl SystemVars {
             Ok(self.config_has_synced_once.reset())
         } else if name == METRICS_RETENTION.name {
             Ok(self.metrics_retention.reset())
+        } else if name == ENABLE_ASSERT_NON_NEGATIVE.name {
+            Ok(self.enable_assert_non_negative.reset())
         } else {
             Err(AdapterError::UnknownParameter(name.into()))
         }
@@ -1336,6 +1411,21 @@ impl SystemVars {
         *self.max_result_size.value()
     }
 
+    /// Returns the value of the `max_plan_nodes` configuration parameter.
+    pub fn max_plan_nodes(&self) -> u32 {
+        *self.max_plan_nodes.value()
+    }
+
+    /// Returns the value of the `max_plan_join_inputs` configuration parameter.
+    pub fn max_plan_join_inputs(&self) -> u32 {
+        *self.max_plan_join_inputs.value()
+    }
+
+    /// Returns the value of the `max_plan_depth` configuration parameter.
+    pub fn max_plan_depth(&self) -> u32 {
+        *self.max_plan_depth.value()
+    }
+
     /// Returns the value of the `allowed_cluster_replica_sizes` configuration parameter.
     pub fn allowed_cluster_replica_sizes(&self) -> &Vec<String> {
         self.allowed_cluster_replica_sizes.value()
@@ -1354,6 +1444,11 @@ impl SystemVars {
     pub fn metrics_retention(&self) -> Duration {
         *self.metrics_retention.value()
     }
+
+    /// Returns the `enable_assert_non_negative` configuration parameter.
+    pub fn enable_assert_non_negative(&self) -> bool {
+        *self.enable_assert_non_negative.value()
+    }
 }
 
 /// A `Var` represents a configuration parameter of an arbitrary type.
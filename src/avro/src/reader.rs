@@ -148,6 +148,12 @@ impl<R: AvroRead> BlockIter<R> {
             inner: Reader::with_schema(reader_schema, inner)?,
         })
     }
+
+    /// Get a reference to the schema with which the blocks' objects must be
+    /// decoded (see [`Reader::schema`]).
+    pub fn schema(&self) -> &Schema {
+        self.inner.schema()
+    }
 }
 
 impl<R: AvroRead> Iterator for BlockIter<R> {
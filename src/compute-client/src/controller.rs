@@ -57,6 +57,7 @@ use mz_repr::{GlobalId, Row};
 use mz_storage_client::controller::{ReadPolicy, StorageController};
 
 use crate::logging::{LogVariant, LogView, LoggingConfig};
+use crate::protocol::command::ComputeParameter;
 use crate::protocol::response::{ComputeResponse, PeekResponse, SubscribeResponse};
 use crate::service::{ComputeClient, ComputeGrpcClient};
 use crate::types::dataflows::DataflowDescription;
@@ -223,11 +224,12 @@ pub struct ComputeReplicaAllocation {
     pub cpu_limit: Option<CpuLimit>,
     /// The number of processes in the replica.
     pub scale: NonZeroUsize,
-    /// The number of worker threads in the replica.
+    /// The number of worker threads in each process of the replica.
     pub workers: NonZeroUsize,
 }
 
 impl ComputeReplicaAllocation {
+    /// Returns the number of worker threads in each process of the replica.
     pub fn workers(&self) -> NonZeroUsize {
         self.workers
     }
@@ -414,7 +416,7 @@ where
         &mut self,
         id: ComputeInstanceId,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
-        max_result_size: u32,
+        config: BTreeSet<ComputeParameter>,
     ) -> Result<(), InstanceExists> {
         if self.instances.contains_key(&id) {
             return Err(InstanceExists(id));
@@ -426,7 +428,7 @@ where
                 id,
                 self.build_info,
                 arranged_logs,
-                max_result_size,
+                config,
                 self.orchestrator.clone(),
                 self.envd_epoch,
             ),
@@ -713,14 +715,13 @@ where
         Ok(())
     }
 
-    /// Update the max size in bytes of any result.
-    pub fn update_max_result_size(
+    /// Update the configuration parameters of a compute instance.
+    pub fn update_configuration(
         &mut self,
         instance_id: ComputeInstanceId,
-        max_result_size: u32,
+        config: BTreeSet<ComputeParameter>,
     ) -> Result<(), InstanceMissing> {
-        self.instance(instance_id)?
-            .update_max_result_size(max_result_size);
+        self.instance(instance_id)?.update_configuration(config);
         Ok(())
     }
 
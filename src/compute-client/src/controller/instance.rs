@@ -164,7 +164,7 @@ where
         instance_id: ComputeInstanceId,
         build_info: &'static BuildInfo,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
-        max_result_size: u32,
+        config: BTreeSet<ComputeParameter>,
         orchestrator: ComputeOrchestrator,
         envd_epoch: NonZeroI64,
     ) -> Self {
@@ -200,8 +200,7 @@ where
         let dummy_logging_config = Default::default();
         instance.send(ComputeCommand::CreateInstance(dummy_logging_config));
 
-        let params = [ComputeParameter::MaxResultSize(max_result_size)].into();
-        instance.send(ComputeCommand::UpdateConfiguration(params));
+        instance.send(ComputeCommand::UpdateConfiguration(config));
 
         instance
     }
@@ -750,11 +749,10 @@ where
         Ok(())
     }
 
-    /// Update the max size in bytes of any result.
-    pub fn update_max_result_size(&mut self, max_result_size: u32) {
-        let params = [ComputeParameter::MaxResultSize(max_result_size)].into();
+    /// Update the configuration parameters of the instance.
+    pub fn update_configuration(&mut self, config: BTreeSet<ComputeParameter>) {
         self.compute
-            .send(ComputeCommand::UpdateConfiguration(params));
+            .send(ComputeCommand::UpdateConfiguration(config));
     }
 
     /// Validate that a collection exists for all identifiers, and error if any do not.
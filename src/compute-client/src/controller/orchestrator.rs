@@ -10,7 +10,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use futures::stream::BoxStream;
 use futures::StreamExt;
 use once_cell::sync::Lazy;
@@ -48,12 +48,18 @@ impl ComputeOrchestrator {
 
     /// Ensure that a service for the given replica exists at the given `location`.
     ///
-    /// Returns a tuple `(compute_addrs, workers, timely_addrs)`, where:
+    /// Returns a tuple `(command_addrs, workers, timely_addrs)`, where:
     ///  * `command_addrs` is the list of addresses on which the replica's processes listen for
     ///    controller connections.
     ///  * `workers` is the number of timely workers per process.
     ///  * `timely_addrs` is the list of addresses used by the timely cluster for inter-process
     ///    communication.
+    ///
+    /// Both lists hold one address per process, in process order: the controller sends the
+    /// `CreateTimely` command for process `i` over `command_addrs[i]`, and that process binds
+    /// `timely_addrs[i]`. Returns an error if the lists do not agree on the number of processes,
+    /// or, for a managed replica, if the service does not have as many processes as its
+    /// allocation's `scale`.
     pub(super) async fn ensure_replica_location(
         &self,
         instance_id: ComputeInstanceId,
@@ -66,9 +72,10 @@ impl ComputeOrchestrator {
                 compute_addrs,
                 workers,
             } => {
-                let server_addrs = addrs.into_iter().collect();
+                let server_addrs: Vec<_> = addrs.into_iter().collect();
                 let workers = workers.get();
-                let worker_addrs = compute_addrs.into_iter().collect();
+                let worker_addrs: Vec<_> = compute_addrs.into_iter().collect();
+                check_process_addrs(replica_id, server_addrs.len(), &server_addrs, &worker_addrs)?;
                 Ok((server_addrs, workers, worker_addrs))
             }
             ComputeReplicaLocation::Managed {
@@ -83,6 +90,12 @@ impl ComputeOrchestrator {
                 let command_addrs = service.addresses("computectl");
                 let workers = allocation.workers.get();
                 let timely_addrs = service.addresses("compute");
+                check_process_addrs(
+                    replica_id,
+                    allocation.scale.get(),
+                    &command_addrs,
+                    &timely_addrs,
+                )?;
                 Ok((command_addrs, workers, timely_addrs))
             }
         }
@@ -232,6 +245,28 @@ impl ComputeOrchestrator {
     }
 }
 
+/// Checks that a replica of `processes` processes has one command address and one timely address
+/// per process.
+///
+/// The controller partitions the replica's commands across one connection per command address, and
+/// gives each process the timely addresses of all processes, so a mismatch would leave timely
+/// waiting forever for processes that do not exist.
+fn check_process_addrs(
+    replica_id: ReplicaId,
+    processes: usize,
+    command_addrs: &[String],
+    timely_addrs: &[String],
+) -> Result<(), anyhow::Error> {
+    if command_addrs.len() != processes || timely_addrs.len() != processes {
+        bail!(
+            "replica {replica_id} has {processes} processes, but {} command addresses and {} timely addresses",
+            command_addrs.len(),
+            timely_addrs.len(),
+        );
+    }
+    Ok(())
+}
+
 /// Deterministically generates replica names based on inputs.
 fn generate_replica_service_name(instance_id: ComputeInstanceId, replica_id: ReplicaId) -> String {
     format!("cluster-{instance_id}-replica-{replica_id}")
@@ -253,3 +288,29 @@ fn parse_replica_service_name(
     let replica_id = caps.get(2).unwrap().as_str().parse().unwrap();
     Ok((instance_id, replica_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("process-{i}:2100")).collect()
+    }
+
+    #[test]
+    fn test_check_process_addrs() {
+        assert!(check_process_addrs(1, 2, &addrs(2), &addrs(2)).is_ok());
+
+        let err = check_process_addrs(1, 2, &addrs(2), &addrs(3)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "replica 1 has 2 processes, but 2 command addresses and 3 timely addresses"
+        );
+
+        let err = check_process_addrs(1, 4, &addrs(2), &addrs(2)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "replica 1 has 4 processes, but 2 command addresses and 2 timely addresses"
+        );
+    }
+}
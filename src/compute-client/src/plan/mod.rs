@@ -1300,7 +1300,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                 monotonic,
                 expected_group_size,
             } => {
-                let input_arity = input.arity();
+                let input_type = input.typ();
                 let output_arity = group_key.len() + aggregates.len();
                 let (input, keys) = Self::from_mir_inner(input, arrangements, debug_info)?;
                 let (input_key, permutation_and_new_arity) = if let Some((
@@ -1317,7 +1317,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     (None, None)
                 };
                 let key_val_plan = KeyValPlan::new(
-                    input_arity,
+                    &input_type.column_types,
                     group_key,
                     aggregates,
                     permutation_and_new_arity,
@@ -1343,6 +1343,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                 limit,
                 offset,
                 monotonic,
+                expected_group_size,
             } => {
                 let arity = input.arity();
                 let (input, keys) = Self::from_mir_inner(input, arrangements, debug_info)?;
@@ -1354,6 +1355,7 @@ This is not expected to cause incorrect results, but could indicate a performanc
                     *limit,
                     arity,
                     *monotonic,
+                    *expected_group_size,
                 );
 
                 // We don't have an MFP here -- install an operator to permute the
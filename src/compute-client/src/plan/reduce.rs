@@ -64,8 +64,11 @@ use mz_expr::permutation_for_arrangement;
 use mz_expr::AggregateExpr;
 use mz_expr::AggregateFunc;
 use mz_expr::MirScalarExpr;
+use mz_expr::VariadicFunc;
+use mz_ore::cast::CastFrom;
 use mz_ore::soft_assert_or_log;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum};
 use proptest::prelude::{any, Arbitrary, BoxedStrategy};
 use proptest::strategy::Strategy;
 use proptest_derive::Arbitrary;
@@ -666,30 +669,10 @@ impl ReducePlan {
                     let monotonic = MonotonicPlan { aggr_funcs, skips };
                     ReducePlan::Hierarchical(HierarchicalPlan::Monotonic(monotonic))
                 } else {
-                    let mut buckets = vec![];
-                    let mut current = 16;
-
-                    // Plan for 4B records in the expected case if the user
-                    // didn't specify a group size.
-                    let limit = expected_group_size.unwrap_or(4_000_000_000);
-
-                    // Distribute buckets in powers of 16, so that we can strike
-                    // a balance between how many inputs each layer gets from
-                    // the preceding layer, while also limiting the number of
-                    // layers.
-                    while current < limit {
-                        // TODO(benesch): fix this dangerous use of `as`.
-                        #[allow(clippy::as_conversions)]
-                        buckets.push(current as u64);
-                        current = current.saturating_mul(16);
-                    }
-                    // We need to store the bucket numbers in decreasing order.
-                    buckets.reverse();
-
                     let bucketed = BucketedPlan {
                         aggr_funcs,
                         skips,
-                        buckets,
+                        buckets: bucketing_of_expected_group_size(expected_group_size),
                     };
 
                     ReducePlan::Hierarchical(HierarchicalPlan::Bucketed(bucketed))
@@ -759,11 +742,13 @@ impl RustType<ProtoKeyValPlan> for KeyValPlan {
 impl KeyValPlan {
     /// Create a new [KeyValPlan] from aggregation arguments.
     pub fn new(
-        input_arity: usize,
+        input_types: &[ColumnType],
         group_key: &[MirScalarExpr],
         aggregates: &[AggregateExpr],
         input_permutation_and_new_arity: Option<(HashMap<usize, usize>, usize)>,
     ) -> Self {
+        let input_arity = input_types.len();
+
         // Form an operator for evaluating key expressions.
         let mut key_mfp = mz_expr::MapFilterProject::new(input_arity)
             .map(group_key.iter().cloned())
@@ -773,8 +758,30 @@ impl KeyValPlan {
         }
 
         // Form an operator for evaluating value expressions.
+        //
+        // Rows rejected by the filter of an aggregate present a value that does not
+        // contribute to the aggregate. Basic aggregates are not guaranteed to ignore
+        // any value, and some of them must see null values. Their rows present the
+        // aggregated value wrapped in a single-element list if accepted, and
+        // `Datum::Null` if rejected, which their rendering skips.
         let mut val_mfp = mz_expr::MapFilterProject::new(input_arity)
-            .map(aggregates.iter().map(|a| a.expr.clone()))
+            .map(aggregates.iter().map(|a| match reduction_type(&a.func) {
+                ReductionType::Basic if a.filter.is_some() => {
+                    let elem_type = a.expr.typ(input_types).scalar_type;
+                    let wrapped = AggregateExpr {
+                        expr: MirScalarExpr::CallVariadic {
+                            func: VariadicFunc::ListCreate { elem_type },
+                            exprs: vec![a.expr.clone()],
+                        },
+                        ..a.clone()
+                    };
+                    wrapped.filtered_expr(Datum::Null, input_types)
+                }
+                ReductionType::Basic => a.expr.clone(),
+                ReductionType::Accumulable | ReductionType::Hierarchical => {
+                    a.filtered_expr(a.func.identity_datum(), input_types)
+                }
+            }))
             .project(input_arity..(input_arity + aggregates.len()));
         if let Some((input_permutation, new_arity)) = input_permutation_and_new_arity {
             val_mfp.permute(input_permutation, new_arity);
@@ -794,6 +801,33 @@ impl KeyValPlan {
     }
 }
 
+/// Determines the buckets of a hierarchical reduction over groups of the
+/// expected size, in decreasing order.
+///
+/// Each layer of a hierarchical reduction reduces the records of a group that
+/// fall into the same bucket, as determined by their hash modulo the bucket
+/// number, before the next layer reduces the results of several buckets.
+pub(crate) fn bucketing_of_expected_group_size(expected_group_size: Option<usize>) -> Vec<u64> {
+    let mut buckets = vec![];
+    let mut current = 16;
+
+    // Plan for 4B records in the expected case if the user
+    // didn't specify a group size.
+    let limit = expected_group_size.unwrap_or(4_000_000_000);
+
+    // Distribute buckets in powers of 16, so that we can strike
+    // a balance between how many inputs each layer gets from
+    // the preceding layer, while also limiting the number of
+    // layers.
+    while current < limit {
+        buckets.push(u64::cast_from(current));
+        current = current.saturating_mul(16);
+    }
+    // We need to store the bucket numbers in decreasing order.
+    buckets.reverse();
+    buckets
+}
+
 /// Transforms a vector containing indexes of needed columns into one containing
 /// the "skips" an iterator over a Row would need to perform to see those values.
 ///
@@ -844,9 +878,17 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::SumFloat64
         | AggregateFunc::SumNumeric
         | AggregateFunc::Count
+        | AggregateFunc::CountDiffs
         | AggregateFunc::Any
         | AggregateFunc::All
-        | AggregateFunc::Dummy => ReductionType::Accumulable,
+        | AggregateFunc::BoolAnd
+        | AggregateFunc::BoolOr
+        | AggregateFunc::BitAnd
+        | AggregateFunc::BitOr
+        | AggregateFunc::BitXor
+        | AggregateFunc::Custom { .. }
+        | AggregateFunc::Dummy
+        | AggregateFunc::ApproxPercentile { .. } => ReductionType::Accumulable,
         AggregateFunc::MaxNumeric
         | AggregateFunc::MaxInt16
         | AggregateFunc::MaxInt32
@@ -862,6 +904,7 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MaxDate
         | AggregateFunc::MaxTimestamp
         | AggregateFunc::MaxTimestampTz
+        | AggregateFunc::MaxRecord
         | AggregateFunc::MinNumeric
         | AggregateFunc::MinInt16
         | AggregateFunc::MinInt32
@@ -876,12 +919,17 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MinString
         | AggregateFunc::MinDate
         | AggregateFunc::MinTimestamp
-        | AggregateFunc::MinTimestampTz => ReductionType::Hierarchical,
+        | AggregateFunc::MinTimestampTz
+        | AggregateFunc::MinRecord
+        | AggregateFunc::HllUnion => ReductionType::Hierarchical,
         AggregateFunc::JsonbAgg { .. }
         | AggregateFunc::JsonbObjectAgg { .. }
         | AggregateFunc::ArrayConcat { .. }
         | AggregateFunc::ListConcat { .. }
         | AggregateFunc::StringAgg { .. }
+        | AggregateFunc::First { .. }
+        | AggregateFunc::Last { .. }
+        | AggregateFunc::PercentileCont { .. }
         | AggregateFunc::RowNumber { .. }
         | AggregateFunc::DenseRank { .. }
         | AggregateFunc::LagLead { .. }
@@ -23,6 +23,8 @@ use serde::{Deserialize, Serialize};
 use mz_expr::ColumnOrder;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
+use crate::plan::reduce::bucketing_of_expected_group_size;
+
 include!(concat!(env!("OUT_DIR"), "/mz_compute_client.plan.top_k.rs"));
 
 /// A plan encapsulating different variants to compute a TopK operation.
@@ -46,6 +48,7 @@ impl TopKPlan {
     /// * `limit` - An optional limit of how many rows should be revealed.
     /// * `arity` - The number of columns in the input and output.
     /// * `monotonic` - `true` if the input is monotonic.
+    /// * `expected_group_size` - A hint for the expected number of rows in each group.
     pub(crate) fn create_from(
         group_key: Vec<usize>,
         order_key: Vec<ColumnOrder>,
@@ -53,6 +56,7 @@ impl TopKPlan {
         limit: Option<usize>,
         arity: usize,
         monotonic: bool,
+        expected_group_size: Option<usize>,
     ) -> Self {
         if monotonic && offset == 0 && limit == Some(1) {
             TopKPlan::MonotonicTop1(MonotonicTop1Plan {
@@ -75,13 +79,20 @@ impl TopKPlan {
                 arity,
             })
         } else {
-            // A plan for all other inputs
+            // A plan for all other inputs. Only TopKs with a limit can thin out their
+            // groups, and so benefit from a hierarchical rendering.
+            let buckets = if limit.is_some() {
+                bucketing_of_expected_group_size(expected_group_size)
+            } else {
+                vec![]
+            };
             TopKPlan::Basic(BasicTopKPlan {
                 group_key,
                 order_key,
                 offset,
                 limit,
                 arity,
+                buckets,
             })
         }
     }
@@ -202,6 +213,20 @@ pub struct BasicTopKPlan {
     pub offset: usize,
     /// The number of columns in the input and output.
     pub arity: usize,
+    /// The buckets of the hierarchical rendering, in decreasing order.
+    ///
+    /// Each layer retains at most `offset + limit` records for each group and
+    /// bucket, where the records are assigned to buckets by their hash modulo
+    /// the bucket number, so that the final layer only needs to consider a
+    /// bounded number of records per group.
+    ///
+    /// The buckets are derived from the expected group size in the same way as
+    /// for hierarchical reductions, that is, in powers of 16 up to the expected
+    /// group size. Without a hint, groups of up to four billion records are
+    /// planned for, which results in seven layers with buckets from `16^7` down
+    /// to `16`. Larger groups are still handled correctly, but their first layer
+    /// has to consider more records per bucket.
+    pub buckets: Vec<u64>,
 }
 
 impl RustType<ProtoBasicTopKPlan> for BasicTopKPlan {
@@ -212,6 +237,7 @@ impl RustType<ProtoBasicTopKPlan> for BasicTopKPlan {
             limit: self.limit.into_proto(),
             offset: self.offset.into_proto(),
             arity: self.arity.into_proto(),
+            buckets: self.buckets.clone(),
         }
     }
 
@@ -222,6 +248,7 @@ impl RustType<ProtoBasicTopKPlan> for BasicTopKPlan {
             limit: proto.limit.into_rust()?,
             offset: proto.offset.into_rust()?,
             arity: proto.arity.into_rust()?,
+            buckets: proto.buckets,
         })
     }
 }
@@ -240,4 +267,23 @@ mod tests {
             assert_eq!(actual.unwrap(), expect);
         }
     }
+
+    #[test]
+    fn basic_top_k_plan_buckets() {
+        fn buckets(limit: Option<usize>, expected_group_size: Option<usize>) -> Vec<u64> {
+            let plan =
+                TopKPlan::create_from(vec![0], vec![], 0, limit, 2, false, expected_group_size);
+            match plan {
+                TopKPlan::Basic(plan) => plan.buckets,
+                plan => panic!("unexpected plan: {:?}", plan),
+            }
+        }
+
+        assert_eq!(buckets(Some(3), Some(1000)), vec![256, 16]);
+        assert_eq!(
+            buckets(Some(3), None),
+            vec![268435456, 16777216, 1048576, 65536, 4096, 256, 16]
+        );
+        assert!(buckets(None, Some(1000)).is_empty());
+    }
 }
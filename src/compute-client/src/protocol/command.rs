@@ -479,6 +479,11 @@ pub enum ComputeParameter {
     /// [`PeekResponse::Error`]: super::response::PeekResponse::Error
     /// [`SubscribeBatch::updates`]: super::response::SubscribeBatch::updates
     MaxResultSize(u32),
+    /// Whether to check exported collections for negative accumulations.
+    ///
+    /// The check renders an additional operator per index and sink export, so it is disabled by
+    /// default.
+    EnableAssertNonNegative(bool),
 }
 
 impl RustType<ProtoComputeParameter> for ComputeParameter {
@@ -488,6 +493,9 @@ impl RustType<ProtoComputeParameter> for ComputeParameter {
         ProtoComputeParameter {
             kind: Some(match self {
                 ComputeParameter::MaxResultSize(size) => Kind::MaxResultSize(*size),
+                ComputeParameter::EnableAssertNonNegative(enabled) => {
+                    Kind::EnableAssertNonNegative(*enabled)
+                }
             }),
         }
     }
@@ -497,6 +505,9 @@ impl RustType<ProtoComputeParameter> for ComputeParameter {
 
         match proto.kind {
             Some(Kind::MaxResultSize(size)) => Ok(ComputeParameter::MaxResultSize(size)),
+            Some(Kind::EnableAssertNonNegative(enabled)) => {
+                Ok(ComputeParameter::EnableAssertNonNegative(enabled))
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoComputeParameter::kind",
             )),
@@ -16,6 +16,7 @@
 use std::collections::HashMap;
 use std::iter;
 
+use anyhow::bail;
 use async_trait::async_trait;
 use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::lattice::Lattice;
@@ -185,9 +186,24 @@ impl<T> PartitionedState<ComputeCommand<T>, ComputeResponse<T>> for PartitionedC
 where
     T: timely::progress::Timestamp + Lattice,
 {
-    fn split_command(&mut self, command: ComputeCommand<T>) -> Vec<Option<ComputeCommand<T>>> {
+    fn split_command(
+        &mut self,
+        command: ComputeCommand<T>,
+    ) -> Result<Vec<Option<ComputeCommand<T>>>, anyhow::Error> {
+        // Each part is a connection to one process of a multi-process replica, and becomes the
+        // process of the same index in the timely cluster.
+        if let ComputeCommand::CreateTimely { config, .. } = &command {
+            if config.addresses.len() != self.parts {
+                bail!(
+                    "timely cluster has {} processes, but the command is split across {} connections",
+                    config.addresses.len(),
+                    self.parts,
+                );
+            }
+        }
+
         self.observe_command(&command);
-        match command {
+        let parts = match command {
             ComputeCommand::CreateTimely { config, epoch } => (0..self.parts)
                 .into_iter()
                 .map(|part| {
@@ -205,7 +221,8 @@ where
                 r[0] = Some(command);
                 r
             }
-        }
+        };
+        Ok(parts)
     }
 
     fn absorb_response(
@@ -364,3 +381,59 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroI64;
+
+    use mz_repr::Timestamp;
+
+    use crate::protocol::command::ComputeStartupEpoch;
+
+    use super::*;
+
+    fn create_timely(processes: usize) -> ComputeCommand<Timestamp> {
+        ComputeCommand::CreateTimely {
+            config: TimelyConfig {
+                workers: 4,
+                addresses: (0..processes)
+                    .map(|i| format!("process-{i}:2102"))
+                    .collect(),
+                ..Default::default()
+            },
+            epoch: ComputeStartupEpoch::new(NonZeroI64::new(1).unwrap(), 1),
+        }
+    }
+
+    #[test]
+    fn test_split_create_timely() {
+        type Pair = (ComputeCommand<Timestamp>, ComputeResponse<Timestamp>);
+        let mut state = <Pair as Partitionable<_, _>>::new(2);
+
+        // Each connection gets the `CreateTimely` command of the process of the same index.
+        let parts = state.split_command(create_timely(2)).unwrap();
+        let processes: Vec<_> = parts
+            .into_iter()
+            .map(|part| match part {
+                Some(ComputeCommand::CreateTimely { config, .. }) => config.process,
+                part => panic!("unexpected command part {part:?}"),
+            })
+            .collect();
+        assert_eq!(processes, vec![0, 1]);
+
+        // Other commands only go to the first process.
+        let parts = state
+            .split_command(ComputeCommand::InitializationComplete)
+            .unwrap();
+        assert_eq!(
+            parts,
+            vec![Some(ComputeCommand::InitializationComplete), None]
+        );
+
+        let err = state.split_command(create_timely(3)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "timely cluster has 3 processes, but the command is split across 2 connections"
+        );
+    }
+}
@@ -88,6 +88,8 @@ pub struct ComputeState {
     pub command_history: ComputeCommandHistory,
     /// Max size in bytes of any result.
     pub max_result_size: u32,
+    /// Whether to check exported collections for negative accumulations.
+    pub enable_assert_non_negative: bool,
     /// Metrics for this replica.
     pub metrics: ComputeMetrics,
 }
@@ -160,6 +162,9 @@ impl<'a, A: Allocate> ActiveComputeState<'a, A> {
                 ComputeParameter::MaxResultSize(size) => {
                     self.compute_state.max_result_size = size;
                 }
+                ComputeParameter::EnableAssertNonNegative(enabled) => {
+                    self.compute_state.enable_assert_non_negative = enabled;
+                }
             }
         }
     }
@@ -106,7 +106,8 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use differential_dataflow::lattice::Lattice;
-use differential_dataflow::AsCollection;
+use differential_dataflow::operators::Reduce;
+use differential_dataflow::{AsCollection, Collection};
 use timely::communication::Allocate;
 use timely::dataflow::operators::to_stream::ToStream;
 use timely::dataflow::operators::InspectCore;
@@ -121,6 +122,7 @@ use timely::PartialOrder;
 use mz_compute_client::plan::Plan;
 use mz_compute_client::types::dataflows::{BuildDesc, DataflowDescription, IndexDesc};
 use mz_expr::Id;
+use mz_ore::soft_panic_or_log;
 use mz_repr::{Diff, GlobalId, Row};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::source::persist_source;
@@ -448,6 +450,36 @@ where
     })
 }
 
+/// Renders an operator that reports negative accumulations in `collection`.
+///
+/// Negative multiplicities in an exported collection indicate a planning error,
+/// e.g. around `Negate` and `Union`, that would otherwise silently corrupt the
+/// results observed by downstream consumers. The operator maintains an
+/// arrangement of the entire collection, and so is only rendered when the
+/// `EnableAssertNonNegative` compute parameter is set.
+pub(crate) fn assert_non_negative<G>(collection: &Collection<G, Row, Diff>, id: GlobalId)
+where
+    G: Scope,
+    G::Timestamp: Lattice,
+{
+    collection.map(|row| (row, ())).reduce_named(
+        "AssertNonNegative",
+        move |row, source, _target: &mut Vec<((), Diff)>| {
+            for (_, count) in source.iter() {
+                if *count < 0 {
+                    // XXX: This reports user data, which we perhaps should not do!
+                    soft_panic_or_log!(
+                        "[customer-data] Negative accumulation in {}: row: {:?}\tcount: {:?}",
+                        id,
+                        row,
+                        count,
+                    );
+                }
+            }
+        },
+    );
+}
+
 // This implementation block allows child timestamps to vary from parent timestamps,
 // but requires the parent timestamp to be `repr::Timestamp`.
 impl<'g, G, T> Context<Child<'g, G, T>, Row>
@@ -549,6 +581,9 @@ where
         // Set up probes to notify on index frontier advancement.
         if let Some(arr) = &arrangement {
             let (collection, _) = arr.as_collection();
+            if compute_state.enable_assert_non_negative {
+                assert_non_negative(&collection, idx_id);
+            }
             let stream = collection.inner;
             for handle in probes.iter_mut() {
                 stream.probe_notify_with(handle);
@@ -620,7 +655,11 @@ where
         // Set up probes to notify on index frontier advancement.
         if let Some(arr) = &arrangement {
             let (collection, _) = arr.as_collection();
-            let stream = collection.leave().inner;
+            let collection = collection.leave();
+            if compute_state.enable_assert_non_negative {
+                assert_non_negative(&collection, idx_id);
+            }
+            let stream = collection.inner;
             for handle in probes.iter_mut() {
                 stream.probe_notify_with(handle);
             }
@@ -910,3 +949,41 @@ impl<T: Timestamp + Lattice> RenderTimestamp for Product<mz_repr::Timestamp, T>
         Product::new(self.outer.saturating_sub(1), self.inner.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use differential_dataflow::input::Input;
+    use mz_ore::assert::SOFT_ASSERTIONS;
+    use mz_repr::Datum;
+
+    use super::*;
+
+    /// Renders `assert_non_negative` on a collection that receives `updates`,
+    /// and runs the dataflow to completion.
+    fn run_assert_non_negative(updates: Vec<(i64, Diff)>) {
+        SOFT_ASSERTIONS.store(true, Ordering::Relaxed);
+        timely::execute_directly(move |worker| {
+            let mut input = worker.dataflow::<u64, _, _>(|scope| {
+                let (input, collection) = scope.new_collection::<Row, Diff>();
+                assert_non_negative(&collection, GlobalId::User(1));
+                input
+            });
+            for (datum, diff) in updates {
+                input.update(Row::pack_slice(&[Datum::Int64(datum)]), diff);
+            }
+        });
+    }
+
+    #[test]
+    fn test_assert_non_negative_passes_retractions() {
+        run_assert_non_negative(vec![(1, 1), (1, -1), (2, 2), (2, -1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Negative accumulation in u1")]
+    fn test_assert_non_negative_reports_negative_accumulations() {
+        run_assert_non_negative(vec![(1, 1), (2, 1), (2, -2)]);
+    }
+}
@@ -31,7 +31,10 @@ use mz_compute_client::plan::reduce::{
     AccumulablePlan, BasicPlan, BucketedPlan, HierarchicalPlan, KeyValPlan, MonotonicPlan,
     ReducePlan, ReductionType,
 };
+use mz_expr::custom_aggregate::CustomAggregateHandle;
+use mz_expr::quantile::QuantileSketch;
 use mz_expr::{AggregateExpr, AggregateFunc};
+use mz_ore::cast::CastFrom;
 use mz_ore::soft_assert_or_log;
 use mz_repr::adt::numeric::{self, Numeric, NumericAgg};
 use mz_repr::{Datum, DatumList, DatumVec, Diff, Row, RowArena};
@@ -479,7 +482,11 @@ where
         func,
         expr: _,
         distinct,
+        filter,
     } = aggr.clone();
+    // Rows rejected by the filter present null values, and accepted rows present
+    // their value wrapped in a single-element list, see `KeyValPlan::new`.
+    let filtered = filter.is_some();
 
     // Extract the value we were asked to aggregate over.
     let mut row_buf = Row::default();
@@ -520,7 +527,22 @@ where
                 let iter = source.iter().flat_map(|(v, w)| {
                     std::iter::repeat(v.iter().next().unwrap()).take(*w as usize)
                 });
-                row_buf.packer().push(func.eval(iter, &RowArena::new()));
+                let temp_storage = RowArena::new();
+                let datum = if filtered {
+                    let mut iter = iter
+                        .filter(|d| !d.is_null())
+                        .map(|d| d.unwrap_list().iter().next().unwrap())
+                        .peekable();
+                    if iter.peek().is_none() {
+                        // No row of the group passed the filter.
+                        func.default()
+                    } else {
+                        func.eval(iter, &temp_storage)
+                    }
+                } else {
+                    func.eval(iter, &temp_storage)
+                };
+                row_buf.packer().push(datum);
                 target.push((row_buf.clone(), 1));
             }
         }
@@ -720,7 +742,7 @@ where
                 for monoid in accum.iter() {
                     use ReductionMonoid::*;
                     match monoid {
-                        Min(row) | Max(row) => row_packer.extend(row.iter()),
+                        Min(row) | Max(row) | HllUnion(row) => row_packer.extend(row.iter()),
                     }
                 }
                 output.push((row_buf.clone(), 1));
@@ -754,6 +776,22 @@ enum Accum {
         /// The number of non-NULL values observed.
         non_nulls: Diff,
     },
+    /// Accumulates integer values bit by bit, for the bitwise aggregations.
+    Bits {
+        /// The number of values observed with each bit set, starting from the
+        /// least significant bit. Empty until a non-NULL value is observed.
+        ones: Vec<Diff>,
+        /// The number of non-NULL values observed.
+        non_nulls: Diff,
+    },
+    /// Accumulates the values of a custom aggregate, see
+    /// [`mz_expr::custom_aggregate`].
+    Custom {
+        /// The custom aggregate.
+        aggregate: CustomAggregateHandle,
+        /// The state of the custom aggregate.
+        state: Row,
+    },
     /// Accumulates float values.
     Float {
         /// Accumulates non-special float values, mapped to a fixed precision i128 domain to
@@ -781,6 +819,8 @@ enum Accum {
         /// Counts non-NULL values
         non_nulls: Diff,
     },
+    /// Accumulates a sketch of the distribution of float values.
+    Quantile(QuantileSketch),
 }
 
 impl Semigroup for Accum {
@@ -788,6 +828,10 @@ impl Semigroup for Accum {
         match self {
             Accum::Bool { trues, falses } => trues.is_zero() && falses.is_zero(),
             Accum::SimpleNumber { accum, non_nulls } => accum.is_zero() && non_nulls.is_zero(),
+            Accum::Bits { ones, non_nulls } => {
+                ones.iter().all(|n| n.is_zero()) && non_nulls.is_zero()
+            }
+            Accum::Custom { aggregate, state } => state == aggregate.zero(),
             Accum::Float {
                 accum,
                 pos_infs,
@@ -814,6 +858,7 @@ impl Semigroup for Accum {
                     && nans.is_zero()
                     && non_nulls.is_zero()
             }
+            Accum::Quantile(sketch) => sketch.is_zero(),
         }
     }
 
@@ -839,6 +884,27 @@ impl Semigroup for Accum {
                 *accum += other_accum;
                 *non_nulls += other_non_nulls;
             }
+            (
+                Accum::Bits { ones, non_nulls },
+                Accum::Bits {
+                    ones: other_ones,
+                    non_nulls: other_non_nulls,
+                },
+            ) => {
+                if ones.len() < other_ones.len() {
+                    ones.resize(other_ones.len(), 0);
+                }
+                for (n, other_n) in ones.iter_mut().zip(other_ones) {
+                    *n += other_n;
+                }
+                *non_nulls += other_non_nulls;
+            }
+            (
+                Accum::Custom { aggregate, state },
+                Accum::Custom {
+                    state: other_state, ..
+                },
+            ) => aggregate.aggregate().merge(state, other_state),
             (
                 Accum::Float {
                     accum,
@@ -912,6 +978,9 @@ impl Semigroup for Accum {
                 *nans += other_nans;
                 *non_nulls += other_non_nulls;
             }
+            (Accum::Quantile(sketch), Accum::Quantile(other_sketch)) => {
+                sketch.plus_equals(other_sketch);
+            }
             (l, r) => unreachable!(
                 "Accumulator::plus_equals called with non-matching variants: {:?} vs {:?}",
                 l, r
@@ -934,6 +1003,38 @@ impl Multiply<Diff> for Accum {
                 accum: accum * i128::from(factor),
                 non_nulls: non_nulls * factor,
             },
+            Accum::Bits { ones, non_nulls } => Accum::Bits {
+                ones: ones.into_iter().map(|n| n * factor).collect(),
+                non_nulls: non_nulls * factor,
+            },
+            Accum::Custom {
+                aggregate: handle,
+                state,
+            } => {
+                let aggregate = handle.aggregate();
+                let mut unit = state;
+                if factor < 0 {
+                    aggregate.negate(&mut unit);
+                }
+                // Merge `unit` into the product `|factor|` times, by repeated
+                // doubling.
+                let mut product = handle.zero().clone();
+                let mut n = factor.unsigned_abs();
+                while n > 0 {
+                    if n & 1 == 1 {
+                        aggregate.merge(&mut product, &unit);
+                    }
+                    n >>= 1;
+                    if n > 0 {
+                        let double = unit.clone();
+                        aggregate.merge(&mut unit, &double);
+                    }
+                }
+                Accum::Custom {
+                    aggregate: handle,
+                    state: product,
+                }
+            }
             Accum::Float {
                 accum,
                 pos_infs,
@@ -977,8 +1078,38 @@ impl Multiply<Diff> for Accum {
                     non_nulls: non_nulls * factor,
                 }
             }
+            Accum::Quantile(sketch) => Accum::Quantile(sketch.multiply(factor)),
+        }
+    }
+}
+
+/// Computes the result of a bitwise aggregation from the number of values
+/// observed with each bit set.
+///
+/// A bit is set in the result of `bit_and` if it is set in every value, of
+/// `bit_or` if it is set in any value, and of `bit_xor` if it is set in an odd
+/// number of values. The width of the result is the number of tracked bits.
+fn finalize_bits(func: &AggregateFunc, ones: &[Diff], non_nulls: Diff) -> Datum<'static> {
+    let mut bits = 0i64;
+    for (i, n) in ones.iter().enumerate() {
+        let set = match func {
+            AggregateFunc::BitAnd => *n == non_nulls,
+            AggregateFunc::BitOr => *n > 0,
+            AggregateFunc::BitXor => n.rem_euclid(2) == 1,
+            _ => unreachable!("{:?} is not a bitwise aggregation", func),
+        };
+        if set {
+            // Sign extend the most significant bit, so that the result fits
+            // into the narrower integer types.
+            bits |= if i + 1 == ones.len() { -1 << i } else { 1 << i };
         }
     }
+    match ones.len() {
+        16 => Datum::Int16(i16::try_from(bits).expect("bits fit in an i16")),
+        32 => Datum::Int32(i32::try_from(bits).expect("bits fit in an i32")),
+        64 => Datum::Int64(bits),
+        width => unreachable!("unexpected bitwise accumulator width {}", width),
+    }
 }
 
 /// Build the dataflow to compute and arrange multiple accumulable aggregations.
@@ -1024,10 +1155,23 @@ where
         full_aggrs
             .iter()
             .map(|f| match f.func {
-                AggregateFunc::Any | AggregateFunc::All => Accum::Bool {
+                AggregateFunc::Any
+                | AggregateFunc::All
+                | AggregateFunc::BoolAnd
+                | AggregateFunc::BoolOr => Accum::Bool {
                     trues: 0,
                     falses: 0,
                 },
+                AggregateFunc::BitAnd | AggregateFunc::BitOr | AggregateFunc::BitXor => {
+                    Accum::Bits {
+                        ones: Vec::new(),
+                        non_nulls: 0,
+                    }
+                }
+                AggregateFunc::Custom { aggregate } => Accum::Custom {
+                    aggregate: aggregate.clone(),
+                    state: aggregate.zero().clone(),
+                },
                 AggregateFunc::SumFloat32 | AggregateFunc::SumFloat64 => Accum::Float {
                     accum: 0,
                     pos_infs: 0,
@@ -1042,6 +1186,9 @@ where
                     nans: 0,
                     non_nulls: 0,
                 },
+                AggregateFunc::ApproxPercentile { .. } => {
+                    Accum::Quantile(QuantileSketch::default())
+                }
                 _ => Accum::SimpleNumber {
                     accum: 0,
                     non_nulls: 0,
@@ -1058,7 +1205,14 @@ where
                 accum: 0, // unused for AggregateFunc::Count
                 non_nulls: if datum.is_null() { 0 } else { 1 },
             },
-            AggregateFunc::Any | AggregateFunc::All => match datum {
+            AggregateFunc::CountDiffs => Accum::SimpleNumber {
+                accum: 0, // unused for AggregateFunc::CountDiffs
+                non_nulls: 1,
+            },
+            AggregateFunc::Any
+            | AggregateFunc::All
+            | AggregateFunc::BoolAnd
+            | AggregateFunc::BoolOr => match datum {
                 Datum::True => Accum::Bool {
                     trues: 1,
                     falses: 0,
@@ -1076,6 +1230,38 @@ where
                     x, dataflow_id
                 ),
             },
+            AggregateFunc::BitAnd | AggregateFunc::BitOr | AggregateFunc::BitXor => {
+                let (n, width) = match datum {
+                    Datum::Int16(n) => (i64::from(n), 16),
+                    Datum::Int32(n) => (i64::from(n), 32),
+                    Datum::Int64(n) => (n, 64),
+                    Datum::Null => {
+                        return Accum::Bits {
+                            ones: Vec::new(),
+                            non_nulls: 0,
+                        }
+                    }
+                    x => panic!(
+                        "Invalid argument to AggregateFunc::{:?}: {:?} in dataflow id {}",
+                        aggr, x, dataflow_id
+                    ),
+                };
+                Accum::Bits {
+                    ones: (0..width).map(|i| (n >> i) & 1).collect(),
+                    non_nulls: 1,
+                }
+            }
+            AggregateFunc::Custom { aggregate } => {
+                let mut state = aggregate.zero().clone();
+                // Like most aggregate functions, custom aggregates skip nulls.
+                if !datum.is_null() {
+                    aggregate.aggregate().insert(&mut state, datum);
+                }
+                Accum::Custom {
+                    aggregate: aggregate.clone(),
+                    state,
+                }
+            }
             AggregateFunc::Dummy => match datum {
                 Datum::Dummy => Accum::SimpleNumber {
                     accum: 0,
@@ -1160,6 +1346,14 @@ where
                     x, dataflow_id
                 ),
             },
+            AggregateFunc::ApproxPercentile { .. } => match datum {
+                Datum::Float64(n) => Accum::Quantile(QuantileSketch::singleton(*n, 1)),
+                Datum::Null => Accum::Quantile(QuantileSketch::default()),
+                x => panic!(
+                    "Invalid argument to AggregateFunc::ApproxPercentile: {:?} in dataflow id {}",
+                    x, dataflow_id
+                ),
+            },
             _ => {
                 // Other accumulations need to disentangle the accumulable
                 // value from its NULL-ness, which is not quite as easily
@@ -1234,24 +1428,53 @@ where
         to_aggregate.push(easy_cases);
     }
 
-    // Next, collect all aggregations that require distinctness.
-    for (accumulable_index, datum_index, aggr) in distinct_aggrs.into_iter() {
+    // Next, collect all aggregations that require distinctness. Rather than rendering one
+    // `distinct` per aggregation, we render a single `distinct` over the values of all of
+    // them, each tagged with its position in the input row. This shares one arrangement
+    // among all distinct aggregations, and aggregations over the same value additionally
+    // share the distinct values themselves.
+    if !distinct_aggrs.is_empty() {
+        let mut datum_indexes = distinct_aggrs
+            .iter()
+            .map(|(_, datum_index, _)| *datum_index)
+            .collect::<Vec<_>>();
+        datum_indexes.sort();
+        datum_indexes.dedup();
+
+        let mut datums = DatumVec::new();
         let mut row_buf = Row::default();
         let collection = collection
-            .map(move |(key, row)| {
-                let value = row.iter().nth(datum_index).unwrap();
-                row_buf.packer().push(value);
-                (key, row_buf.clone())
+            .flat_map(move |(key, row)| {
+                let datums = datums.borrow_with(&row);
+                datum_indexes
+                    .iter()
+                    .map(|datum_index| {
+                        row_buf.packer().extend([
+                            Datum::UInt64(u64::cast_from(*datum_index)),
+                            datums[*datum_index],
+                        ]);
+                        (key.clone(), row_buf.clone())
+                    })
+                    .collect::<Vec<_>>()
             })
             // TODO(#16549): Use explicit arrangement
             .distinct_core()
             .explode_one({
                 let zero_diffs = zero_diffs.clone();
                 move |(key, row)| {
-                    let datum = row.iter().next().unwrap();
+                    let mut datums = row.iter();
+                    let datum_index = usize::cast_from(datums.next().unwrap().unwrap_uint64());
+                    let datum = datums.next().unwrap();
                     let mut diffs = zero_diffs.clone();
-                    diffs.0[accumulable_index] = datum_to_accumulator(datum, &aggr.func);
-                    diffs.1 = 1;
+                    for (accumulable_index, _, aggr) in distinct_aggrs
+                        .iter()
+                        .filter(|(_, index, _)| *index == datum_index)
+                    {
+                        diffs.0[*accumulable_index] = datum_to_accumulator(datum, &aggr.func);
+                        // Each aggregation contributes to the total count, as if it was
+                        // rendered separately.
+                        diffs.1 += 1;
+                    }
                     ((key, ()), diffs)
                 }
             });
@@ -1287,12 +1510,19 @@ where
                         dataflow_id
                     );
 
+                    // Custom aggregates finalize their state, even if it is empty.
+                    if let Accum::Custom { aggregate, state } = accum {
+                        row_packer.extend(aggregate.aggregate().finalize(state).iter());
+                        continue;
+                    }
+
                     // The finished value depends on the aggregation function in a variety of ways.
                     // For all aggregates but count, if only null values were
                     // accumulated, then the output is null.
                     let value = if total > 0
                         && accum.is_zero()
                         && aggr.func != AggregateFunc::Count
+                        && aggr.func != AggregateFunc::CountDiffs
                     {
                         Datum::Null
                     } else {
@@ -1300,6 +1530,10 @@ where
                             (AggregateFunc::Count, Accum::SimpleNumber { non_nulls, .. }) => {
                                 Datum::Int64(*non_nulls)
                             }
+                            // Counts all rows, and may be negative.
+                            (AggregateFunc::CountDiffs, Accum::SimpleNumber { non_nulls, .. }) => {
+                                Datum::Int64(*non_nulls)
+                            }
                             (AggregateFunc::All, Accum::Bool { falses, trues }) => {
                                 // If any false, else if all true, else must be no false and some nulls.
                                 if *falses > 0 {
@@ -1320,6 +1554,18 @@ where
                                     Datum::Null
                                 }
                             }
+                            // Nulls are ignored, and an all-null input was
+                            // handled above.
+                            (AggregateFunc::BoolAnd, Accum::Bool { falses, .. }) => {
+                                Datum::from(*falses == 0)
+                            }
+                            (AggregateFunc::BoolOr, Accum::Bool { trues, .. }) => {
+                                Datum::from(*trues > 0)
+                            }
+                            (
+                                AggregateFunc::BitAnd | AggregateFunc::BitOr | AggregateFunc::BitXor,
+                                Accum::Bits { ones, non_nulls },
+                            ) => finalize_bits(&aggr.func, ones, *non_nulls),
                             (AggregateFunc::Dummy, _) => Datum::Dummy,
                             // If any non-nulls, just report the aggregate.
                             (AggregateFunc::SumInt16, Accum::SimpleNumber { accum, .. })
@@ -1434,6 +1680,10 @@ where
                                     Datum::from(d)
                                 }
                             }
+                            (
+                                AggregateFunc::ApproxPercentile { fraction },
+                                Accum::Quantile(sketch),
+                            ) => Datum::from(sketch.quantile(fraction.0)),
                             _ => panic!(
                                 "Unexpected accumulation (aggr={:?}, accum={:?}) in dataflow id {}",
                                 aggr.func, accum, dataflow_id
@@ -1473,6 +1723,7 @@ pub mod monoids {
     pub enum ReductionMonoid {
         Min(Row),
         Max(Row),
+        HllUnion(Row),
     }
 
     impl Semigroup for ReductionMonoid {
@@ -1508,6 +1759,23 @@ pub mod monoids {
                         lhs.clone_from(rhs);
                     }
                 }
+                (ReductionMonoid::HllUnion(lhs), ReductionMonoid::HllUnion(rhs)) => {
+                    let merged = {
+                        let lhs_val = lhs.unpack_first();
+                        let rhs_val = rhs.unpack_first();
+                        // Datum::Null is the identity, i.e., the empty sketch.
+                        match (lhs_val, rhs_val) {
+                            (_, Datum::Null) => None,
+                            (Datum::Null, _) => Some(rhs_val.unwrap_bytes().to_vec()),
+                            (lhs, rhs) => {
+                                Some(mz_expr::hll::merge(lhs.unwrap_bytes(), rhs.unwrap_bytes()))
+                            }
+                        }
+                    };
+                    if let Some(merged) = merged {
+                        lhs.packer().push(Datum::Bytes(&merged));
+                    }
+                }
                 (lhs, rhs) => {
                     soft_panic_or_log!(
                         "Mismatched monoid variants in reduction! lhs: {:?} rhs: {:?}",
@@ -1541,7 +1809,8 @@ pub mod monoids {
             | AggregateFunc::MaxString
             | AggregateFunc::MaxDate
             | AggregateFunc::MaxTimestamp
-            | AggregateFunc::MaxTimestampTz => Some(ReductionMonoid::Max(row)),
+            | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MaxRecord => Some(ReductionMonoid::Max(row)),
             AggregateFunc::MinNumeric
             | AggregateFunc::MinInt16
             | AggregateFunc::MinInt32
@@ -1556,7 +1825,9 @@ pub mod monoids {
             | AggregateFunc::MinString
             | AggregateFunc::MinDate
             | AggregateFunc::MinTimestamp
-            | AggregateFunc::MinTimestampTz => Some(ReductionMonoid::Min(row)),
+            | AggregateFunc::MinTimestampTz
+            | AggregateFunc::MinRecord => Some(ReductionMonoid::Min(row)),
+            AggregateFunc::HllUnion => Some(ReductionMonoid::HllUnion(row)),
             AggregateFunc::SumInt16
             | AggregateFunc::SumInt32
             | AggregateFunc::SumInt64
@@ -1567,14 +1838,25 @@ pub mod monoids {
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
             | AggregateFunc::Count
+            | AggregateFunc::CountDiffs
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::BoolAnd
+            | AggregateFunc::BoolOr
+            | AggregateFunc::BitAnd
+            | AggregateFunc::BitOr
+            | AggregateFunc::BitXor
+            | AggregateFunc::Custom { .. }
             | AggregateFunc::Dummy
             | AggregateFunc::JsonbAgg { .. }
             | AggregateFunc::JsonbObjectAgg { .. }
             | AggregateFunc::ArrayConcat { .. }
             | AggregateFunc::ListConcat { .. }
             | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::First { .. }
+            | AggregateFunc::Last { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::ApproxPercentile { .. }
             | AggregateFunc::RowNumber { .. }
             | AggregateFunc::DenseRank { .. }
             | AggregateFunc::LagLead { .. }
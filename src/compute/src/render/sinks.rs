@@ -76,6 +76,10 @@ where
         let ok_collection = ok_collection.leave();
         let err_collection = err_collection.leave();
 
+        if compute_state.enable_assert_non_negative {
+            crate::render::assert_non_negative(&ok_collection, sink_id);
+        }
+
         let sink_token = sink_render.render_continuous_sink(
             compute_state,
             sink,
@@ -121,7 +121,10 @@ where
                     offset,
                     limit,
                     arity,
-                }) => build_topk(ok_input, group_key, order_key, offset, limit, arity),
+                    buckets,
+                }) => build_topk(
+                    ok_input, group_key, order_key, offset, limit, arity, buckets,
+                ),
             };
             // Extract the results from the region.
             ok_result.leave_region()
@@ -137,6 +140,7 @@ where
             offset: usize,
             limit: Option<usize>,
             arity: usize,
+            buckets: Vec<u64>,
         ) -> Collection<G, Row, Diff>
         where
             G: Scope,
@@ -157,24 +161,22 @@ where
                     ((group_row, row_hash), row)
                 }
             });
-            // This sequence of numbers defines the shifts that happen to the 64 bit hash
-            // of the record, and has the properties that 1. there are not too many of them,
-            // and 2. each has a modest difference to the next.
+            // The buckets are the moduli applied to the 64 bit hash of the record, in
+            // decreasing order. There are not too many of them, and each is a modest factor
+            // smaller than the previous one.
             //
             // These two properties mean that there should be no reductions on groups that
             // are substantially larger than `offset + limit` (the largest factor should be
-            // bounded by two raised to the difference between subsequent numbers);
+            // bounded by the ratio of subsequent buckets);
             if let Some(limit) = limit {
-                for log_modulus in
-                    [60, 56, 52, 48, 44, 40, 36, 32, 28, 24, 20, 16, 12, 8, 4u64].iter()
-                {
+                for modulus in buckets {
                     // here we do not apply `offset`, but instead restrict ourself with a limit
                     // that includes the offset. We cannot apply `offset` until we perform the
                     // final, complete reduction.
                     collection = build_topk_stage(
                         collection,
                         order_key.clone(),
-                        1u64 << log_modulus,
+                        modulus,
                         0,
                         Some(offset + limit),
                         arity,
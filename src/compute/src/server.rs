@@ -611,6 +611,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     persist_clients: Arc::clone(&self.persist_clients),
                     command_history: ComputeCommandHistory::default(),
                     max_result_size: u32::MAX,
+                    enable_assert_non_negative: false,
                     metrics: self.compute_metrics.clone(),
                 });
             }
@@ -221,6 +221,14 @@ pub struct Args {
         default_value = "127.0.0.1:6878"
     )]
     internal_http_listen_addr: SocketAddr,
+    /// The address on which to listen for Arrow Flight connections to Flight
+    /// sources.
+    ///
+    /// Connections to this address are subject to the same encryption and
+    /// authentication as connections to the HTTP listen address. If not
+    /// specified, the Arrow Flight endpoint is disabled.
+    #[clap(long, value_name = "HOST:PORT", env = "FLIGHT_LISTEN_ADDR")]
+    flight_listen_addr: Option<SocketAddr>,
     /// Enable cross-origin resource sharing (CORS) for HTTP requests from the
     /// specified origin.
     #[structopt(long, env = "CORS_ALLOWED_ORIGIN")]
@@ -752,6 +760,7 @@ fn run(mut args: Args) -> Result<(), anyhow::Error> {
         http_listen_addr: args.http_listen_addr,
         internal_sql_listen_addr: args.internal_sql_listen_addr,
         internal_http_listen_addr: args.internal_http_listen_addr,
+        flight_listen_addr: args.flight_listen_addr,
         tls,
         frontegg,
         cors_allowed_origin,
@@ -52,13 +52,16 @@ use mz_ore::tracing::TracingHandle;
 use crate::server::{ConnectionHandler, Server};
 use crate::BUILD_INFO;
 
+pub use flight::{FlightConfig, FlightServer};
 pub use sql::{SqlResponse, WebSocketAuth, WebSocketResponse};
 
 mod catalog;
+mod flight;
 mod memory;
 mod probe;
 mod root;
 mod sql;
+mod webhook;
 
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
@@ -128,13 +131,24 @@ impl HttpServer {
                     .expose_headers(Any)
                     .max_age(Duration::from_secs(60) * 60),
             );
+        // Webhook requests are authenticated by their signature rather than
+        // by user, so they bypass `http_auth`.
+        let webhook_router = Router::new()
+            .route(
+                "/api/webhook/:database/:schema/:name",
+                routing::post(webhook::handle_webhook),
+            )
+            .with_state(adapter_client.clone());
         let ws_router = Router::new()
             .route("/api/experimental/sql", routing::get(sql::handle_sql_ws))
             .with_state(WsState {
                 frontegg,
                 adapter_client,
             });
-        let router = Router::new().merge(base_router).merge(ws_router);
+        let router = Router::new()
+            .merge(base_router)
+            .merge(ws_router)
+            .merge(webhook_router);
         HttpServer { tls, router }
     }
 
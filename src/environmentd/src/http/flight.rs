@@ -0,0 +1,880 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Arrow Flight endpoint for Flight sources.
+//!
+//! Producers write record batches to a Flight source with a `DoPut` call whose
+//! flight descriptor is the path `[database, schema, source]`. The schema of
+//! the batches is validated against the columns declared by the source, and
+//! each batch is then committed atomically. `GetSchema` returns the schema that
+//! a source expects.
+
+use std::pin::Pin;
+
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::flight::{deserialize_message, deserialize_schemas, serialize_schema_to_result};
+use arrow2::io::ipc::read::Dictionaries;
+use arrow_format::flight::data::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, Result as ActionResult, SchemaResult, Ticket,
+};
+use arrow_format::flight::service::flight_service_server::{FlightService, FlightServiceServer};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::future::TryFutureExt;
+use futures::stream::BoxStream;
+use headers::authorization::{Authorization, Basic, Bearer};
+use headers::HeaderMapExt;
+use hyper_openssl::MaybeHttpsStream;
+use openssl::ssl::Ssl;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_openssl::SslStream;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status, Streaming};
+use tracing::warn;
+
+use mz_adapter::session::EndTransactionAction;
+use mz_adapter::{AdapterError, SessionClient};
+use mz_frontegg_auth::FronteggAuthentication;
+use mz_ore::task;
+use mz_repr::adt::date::Date;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{Datum, GlobalId, RelationDesc, Row, RowPacker, ScalarType};
+use mz_sql::names::PartialObjectName;
+
+use crate::http::{auth, AuthError, AuthedClient, Credentials, TlsConfig};
+use crate::server::{ConnectionHandler, Server};
+
+#[derive(Debug, Clone)]
+pub struct FlightConfig {
+    pub tls: Option<TlsConfig>,
+    pub frontegg: Option<FronteggAuthentication>,
+    pub adapter_client: mz_adapter::Client,
+}
+
+/// Serves the Arrow Flight endpoint of Flight sources.
+pub struct FlightServer {
+    tls: Option<TlsConfig>,
+    service: FlightServiceServer<SourceFlightService>,
+}
+
+impl FlightServer {
+    pub fn new(
+        FlightConfig {
+            tls,
+            frontegg,
+            adapter_client,
+        }: FlightConfig,
+    ) -> FlightServer {
+        let service = FlightServiceServer::new(SourceFlightService {
+            frontegg,
+            adapter_client,
+        });
+        FlightServer { tls, service }
+    }
+}
+
+impl Server for FlightServer {
+    const NAME: &'static str = "flight";
+
+    fn handle_connection(&self, conn: TcpStream) -> ConnectionHandler {
+        let service = self.service.clone();
+        let tls_context = self.tls.as_ref().map(|tls| tls.context.clone());
+        Box::pin(async {
+            let conn = match tls_context {
+                Some(tls_context) => {
+                    let mut ssl_stream = SslStream::new(Ssl::new(&tls_context)?, conn)?;
+                    if let Err(e) = Pin::new(&mut ssl_stream).accept().await {
+                        let _ = ssl_stream.get_mut().shutdown().await;
+                        return Err(e.into());
+                    }
+                    MaybeHttpsStream::Https(ssl_stream)
+                }
+                None => MaybeHttpsStream::Http(conn),
+            };
+            // gRPC is only spoken over HTTP/2.
+            let mut http = hyper::server::conn::Http::new();
+            http.http2_only(true);
+            http.serve_connection(conn, service).err_into().await
+        })
+    }
+}
+
+pub struct SourceFlightService {
+    frontegg: Option<FronteggAuthentication>,
+    adapter_client: mz_adapter::Client,
+}
+
+impl SourceFlightService {
+    /// Authenticates the user named by the `authorization` header of a
+    /// request, in the same manner as the HTTP API, and starts a session for
+    /// them.
+    async fn authenticate(&self, metadata: &MetadataMap) -> Result<SessionClient, Status> {
+        let headers = metadata.clone().into_headers();
+        let creds = match &self.frontegg {
+            // If no Frontegg authentication, we can use the username of the
+            // basic authorization header if present, otherwise the default
+            // HTTP user.
+            None => Credentials::User(
+                headers
+                    .typed_get::<Authorization<Basic>>()
+                    .map(|basic| basic.username().to_string()),
+            ),
+            Some(_) => {
+                if let Some(basic) = headers.typed_get::<Authorization<Basic>>() {
+                    Credentials::Password {
+                        username: basic.username().to_string(),
+                        password: basic.password().to_string(),
+                    }
+                } else if let Some(bearer) = headers.typed_get::<Authorization<Bearer>>() {
+                    Credentials::Token {
+                        token: bearer.token().to_string(),
+                    }
+                } else {
+                    return Err(unauthenticated(AuthError::MissingHttpAuthentication));
+                }
+            }
+        };
+        let user = auth(&self.frontegg, creds).await.map_err(unauthenticated)?;
+        let AuthedClient(client) = AuthedClient::new(&self.adapter_client, user)
+            .await
+            .map_err(adapter_status)?;
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl FlightService for SourceFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoActionStream = BoxStream<'static, Result<ActionResult, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let mut client = self.authenticate(request.metadata()).await?;
+        let name = source_name(Some(request.get_ref()))?;
+        let (_, desc) = client
+            .describe_flight_source(name)
+            .await
+            .map_err(adapter_status)?;
+        let fields = desc
+            .iter()
+            .map(|(name, typ)| {
+                let data_type = arrow_type(&typ.scalar_type).ok_or_else(|| {
+                    Status::internal(format!("column {} has no Arrow type", name.as_str()))
+                })?;
+                Ok(Field::new(name.as_str(), data_type, typ.nullable))
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let schema = Schema::from(fields);
+        Ok(Response::new(serialize_schema_to_result(&schema, None)))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let mut client = self.authenticate(request.metadata()).await?;
+        let mut stream = request.into_inner();
+
+        // The first message names the source and carries the schema of the
+        // record batches that follow.
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("DoPut stream is empty"))?;
+        let name = source_name(first.flight_descriptor.as_ref())?;
+        let (id, desc) = client
+            .describe_flight_source(name)
+            .await
+            .map_err(adapter_status)?;
+        let (schema, ipc_schema) = deserialize_schemas(&first.data_header)
+            .map_err(|e| Status::invalid_argument(format!("invalid Arrow schema: {}", e)))?;
+        let decoders = validate_schema(&schema, &desc).map_err(Status::invalid_argument)?;
+
+        let (tx, rx) = mpsc::channel(1);
+        task::spawn(|| "flight_do_put", async move {
+            let mut dictionaries = Dictionaries::default();
+            let res = async {
+                while let Some(data) = stream.message().await? {
+                    let chunk =
+                        deserialize_message(&data, &schema.fields, &ipc_schema, &mut dictionaries)
+                            .map_err(|e| {
+                                Status::invalid_argument(format!("invalid Arrow message: {}", e))
+                            })?;
+                    // Dictionary batches do not carry any rows.
+                    let chunk = match chunk {
+                        Some(chunk) => chunk,
+                        None => continue,
+                    };
+                    let rows =
+                        decode_chunk(&chunk, &decoders, &desc).map_err(Status::invalid_argument)?;
+                    append(&mut client, id, rows)
+                        .await
+                        .map_err(adapter_status)?;
+                    // Acknowledge the batch once it is committed, echoing its
+                    // application metadata so that producers can correlate
+                    // acknowledgements with batches.
+                    let result = PutResult {
+                        app_metadata: data.app_metadata,
+                    };
+                    if tx.send(Ok(result)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok::<_, Status>(())
+            }
+            .await;
+            if let Err(e) = res {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as Self::DoPutStream
+        ))
+    }
+
+    async fn handshake(
+        &self,
+        _: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented(
+            "Handshake is not supported; authenticate each call with an authorization header",
+        ))
+    }
+
+    async fn list_flights(
+        &self,
+        _: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("ListFlights is not supported"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("GetFlightInfo is not supported"))
+    }
+
+    async fn do_get(&self, _: Request<Ticket>) -> Result<Response<Self::DoGetStream>, Status> {
+        Err(Status::unimplemented("DoGet is not supported"))
+    }
+
+    async fn do_exchange(
+        &self,
+        _: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("DoExchange is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("DoAction is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Err(Status::unimplemented("ListActions is not supported"))
+    }
+}
+
+fn unauthenticated(e: AuthError) -> Status {
+    warn!("Flight request failed authentication: {}", e);
+    // We omit detail from the error message we send to the client, to avoid
+    // giving attackers unnecessary information.
+    Status::unauthenticated("unauthorized")
+}
+
+fn adapter_status(e: AdapterError) -> Status {
+    match e {
+        AdapterError::Unauthorized(_) => Status::permission_denied(e.to_string()),
+        _ => Status::invalid_argument(e.to_string()),
+    }
+}
+
+/// Returns the name of the source that a flight descriptor's path refers to.
+fn source_name(descriptor: Option<&FlightDescriptor>) -> Result<PartialObjectName, Status> {
+    let path = descriptor.map(|d| d.path.as_slice()).unwrap_or_default();
+    let name = match path {
+        [item] => PartialObjectName {
+            database: None,
+            schema: None,
+            item: item.clone(),
+        },
+        [schema, item] => PartialObjectName {
+            database: None,
+            schema: Some(schema.clone()),
+            item: item.clone(),
+        },
+        [database, schema, item] => PartialObjectName {
+            database: Some(database.clone()),
+            schema: Some(schema.clone()),
+            item: item.clone(),
+        },
+        _ => {
+            return Err(Status::invalid_argument(
+                "flight descriptor must be a path of the form [database, schema, source]",
+            ))
+        }
+    };
+    Ok(name)
+}
+
+/// Appends rows to a Flight source in their own transaction.
+async fn append(
+    client: &mut SessionClient,
+    id: GlobalId,
+    rows: Vec<Row>,
+) -> Result<(), AdapterError> {
+    let res = async {
+        client.start_transaction(Some(1)).await?;
+        client.append_flight(id, rows).await?;
+        client.end_transaction(EndTransactionAction::Commit).await
+    }
+    .await;
+    if res.is_err() {
+        let _ = client.end_transaction(EndTransactionAction::Rollback).await;
+    }
+    res.map(|_| ())
+}
+
+/// The Arrow type that producers should use for a column of type `typ`.
+fn arrow_type(typ: &ScalarType) -> Option<DataType> {
+    let data_type = match typ {
+        ScalarType::Bool => DataType::Boolean,
+        ScalarType::Int16 => DataType::Int16,
+        ScalarType::Int32 => DataType::Int32,
+        ScalarType::Int64 => DataType::Int64,
+        ScalarType::Float32 => DataType::Float32,
+        ScalarType::Float64 => DataType::Float64,
+        ScalarType::String | ScalarType::Jsonb => DataType::Utf8,
+        ScalarType::Bytes => DataType::Binary,
+        ScalarType::Date => DataType::Date32,
+        ScalarType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        ScalarType::TimestampTz => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+/// How to decode the values of an Arrow column into datums of a source's
+/// column.
+#[derive(Debug, Clone, Copy)]
+enum ColumnDecoder {
+    Bool,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    String { large: bool },
+    Jsonb { large: bool },
+    Bytes { large: bool },
+    Date,
+    Timestamp(TimeUnit),
+    TimestampTz(TimeUnit),
+}
+
+impl ColumnDecoder {
+    /// Returns the decoder for Arrow values of type `data_type` into datums of
+    /// type `typ`, if they are compatible.
+    fn new(data_type: &DataType, typ: &ScalarType) -> Option<ColumnDecoder> {
+        let decoder = match (data_type, typ) {
+            (DataType::Boolean, ScalarType::Bool) => ColumnDecoder::Bool,
+            (DataType::Int16, ScalarType::Int16) => ColumnDecoder::Int16,
+            (DataType::Int32, ScalarType::Int32) => ColumnDecoder::Int32,
+            (DataType::Int64, ScalarType::Int64) => ColumnDecoder::Int64,
+            (DataType::Float32, ScalarType::Float32) => ColumnDecoder::Float32,
+            (DataType::Float64, ScalarType::Float64) => ColumnDecoder::Float64,
+            (DataType::Utf8, ScalarType::String) => ColumnDecoder::String { large: false },
+            (DataType::LargeUtf8, ScalarType::String) => ColumnDecoder::String { large: true },
+            (DataType::Utf8, ScalarType::Jsonb) => ColumnDecoder::Jsonb { large: false },
+            (DataType::LargeUtf8, ScalarType::Jsonb) => ColumnDecoder::Jsonb { large: true },
+            (DataType::Binary, ScalarType::Bytes) => ColumnDecoder::Bytes { large: false },
+            (DataType::LargeBinary, ScalarType::Bytes) => ColumnDecoder::Bytes { large: true },
+            (DataType::Date32, ScalarType::Date) => ColumnDecoder::Date,
+            (DataType::Timestamp(unit, None), ScalarType::Timestamp) => {
+                ColumnDecoder::Timestamp(*unit)
+            }
+            (DataType::Timestamp(unit, Some(_)), ScalarType::TimestampTz) => {
+                ColumnDecoder::TimestampTz(*unit)
+            }
+            _ => return None,
+        };
+        Some(decoder)
+    }
+
+    /// Packs the non-null value at index `i` of `array`.
+    fn push(&self, array: &dyn Array, i: usize, packer: &mut RowPacker) -> Result<(), String> {
+        match self {
+            ColumnDecoder::Bool => {
+                packer.push(Datum::from(downcast::<BooleanArray>(array).value(i)))
+            }
+            ColumnDecoder::Int16 => packer.push(Datum::Int16(
+                downcast::<PrimitiveArray<i16>>(array).value(i),
+            )),
+            ColumnDecoder::Int32 => packer.push(Datum::Int32(
+                downcast::<PrimitiveArray<i32>>(array).value(i),
+            )),
+            ColumnDecoder::Int64 => packer.push(Datum::Int64(
+                downcast::<PrimitiveArray<i64>>(array).value(i),
+            )),
+            ColumnDecoder::Float32 => packer.push(Datum::Float32(
+                downcast::<PrimitiveArray<f32>>(array).value(i).into(),
+            )),
+            ColumnDecoder::Float64 => packer.push(Datum::Float64(
+                downcast::<PrimitiveArray<f64>>(array).value(i).into(),
+            )),
+            ColumnDecoder::String { large } => {
+                packer.push(Datum::String(utf8_value(array, *large, i)))
+            }
+            ColumnDecoder::Jsonb { large } => JsonbPacker::new(packer)
+                .pack_str(utf8_value(array, *large, i))
+                .map_err(|e| format!("invalid JSON: {}", e))?,
+            ColumnDecoder::Bytes { large } => {
+                let value = if *large {
+                    downcast::<BinaryArray<i64>>(array).value(i)
+                } else {
+                    downcast::<BinaryArray<i32>>(array).value(i)
+                };
+                packer.push(Datum::Bytes(value))
+            }
+            ColumnDecoder::Date => {
+                let days = downcast::<PrimitiveArray<i32>>(array).value(i);
+                let date = Date::from_unix_epoch(days).map_err(|e| e.to_string())?;
+                packer.push(Datum::Date(date))
+            }
+            ColumnDecoder::Timestamp(unit) => {
+                let value = downcast::<PrimitiveArray<i64>>(array).value(i);
+                let ts = CheckedTimestamp::from_timestamplike(timestamp(value, *unit)?)
+                    .map_err(|e| e.to_string())?;
+                packer.push(Datum::Timestamp(ts))
+            }
+            ColumnDecoder::TimestampTz(unit) => {
+                let value = downcast::<PrimitiveArray<i64>>(array).value(i);
+                let ts = DateTime::<Utc>::from_utc(timestamp(value, *unit)?, Utc);
+                let ts = CheckedTimestamp::from_timestamplike(ts).map_err(|e| e.to_string())?;
+                packer.push(Datum::TimestampTz(ts))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> &T {
+    array
+        .as_any()
+        .downcast_ref()
+        .expect("array type validated against schema")
+}
+
+fn utf8_value(array: &dyn Array, large: bool, i: usize) -> &str {
+    if large {
+        downcast::<Utf8Array<i64>>(array).value(i)
+    } else {
+        downcast::<Utf8Array<i32>>(array).value(i)
+    }
+}
+
+/// Converts an Arrow timestamp, which counts `unit`s since the Unix epoch.
+fn timestamp(value: i64, unit: TimeUnit) -> Result<NaiveDateTime, String> {
+    let (units_per_sec, nanos_per_unit) = match unit {
+        TimeUnit::Second => (1, 1_000_000_000),
+        TimeUnit::Millisecond => (1_000, 1_000_000),
+        TimeUnit::Microsecond => (1_000_000, 1_000),
+        TimeUnit::Nanosecond => (1_000_000_000, 1),
+    };
+    let secs = value.div_euclid(units_per_sec);
+    let nanos = u32::try_from(value.rem_euclid(units_per_sec) * nanos_per_unit)
+        .expect("less than one second of nanoseconds");
+    NaiveDateTime::from_timestamp_opt(secs, nanos)
+        .ok_or_else(|| format!("timestamp out of range: {}", value))
+}
+
+/// Validates that record batches with schema `schema` can be written to a
+/// source with description `desc`, returning the decoder for each column.
+///
+/// The fields of the schema must have the names of the source's columns, in
+/// the same order, and types that can be decoded into the types of the
+/// columns. Fields may be nullable even if their column is not, as long as
+/// they do not contain nulls.
+fn validate_schema(schema: &Schema, desc: &RelationDesc) -> Result<Vec<ColumnDecoder>, String> {
+    if schema.fields.len() != desc.arity() {
+        return Err(format!(
+            "record batches have {} columns, but the source has {}",
+            schema.fields.len(),
+            desc.arity()
+        ));
+    }
+    schema
+        .fields
+        .iter()
+        .zip(desc.iter())
+        .map(|(field, (name, typ))| {
+            if field.name != name.as_str() {
+                return Err(format!(
+                    "record batch column {} does not match source column {}",
+                    field.name,
+                    name.as_str()
+                ));
+            }
+            ColumnDecoder::new(&field.data_type, &typ.scalar_type).ok_or_else(|| {
+                format!(
+                    "column {} has Arrow type {:?}, which cannot be written to a column of \
+                     type {}",
+                    name.as_str(),
+                    field.data_type,
+                    mz_pgrepr::Type::from(&typ.scalar_type).name()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Decodes the rows of a record batch whose schema has been validated.
+fn decode_chunk(
+    chunk: &Chunk<Box<dyn Array>>,
+    decoders: &[ColumnDecoder],
+    desc: &RelationDesc,
+) -> Result<Vec<Row>, String> {
+    let arrays = chunk.arrays();
+    let mut rows = Vec::with_capacity(chunk.len());
+    let mut row = Row::default();
+    for i in 0..chunk.len() {
+        let mut packer = row.packer();
+        for ((array, decoder), (name, typ)) in arrays.iter().zip(decoders).zip(desc.iter()) {
+            if array.is_null(i) {
+                if !typ.nullable {
+                    return Err(format!(
+                        "null value in column {} violates not-null constraint",
+                        name.as_str()
+                    ));
+                }
+                packer.push(Datum::Null);
+            } else {
+                decoder
+                    .push(array.as_ref(), i, &mut packer)
+                    .map_err(|e| format!("column {}: {}", name.as_str(), e))?;
+            }
+        }
+        rows.push(row.clone());
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::NaiveDate;
+    use mz_repr::adt::jsonb::Jsonb;
+
+    use super::*;
+
+    /// Decodes a record batch with the given columns into rows of a source
+    /// whose columns have the given types.
+    fn decode(columns: Vec<(Box<dyn Array>, ScalarType, bool)>) -> Result<Vec<Row>, String> {
+        let mut fields = vec![];
+        let mut desc = RelationDesc::empty();
+        let mut arrays = vec![];
+        for (i, (array, typ, nullable)) in columns.into_iter().enumerate() {
+            let name = format!("c{}", i);
+            fields.push(Field::new(&name, array.data_type().clone(), true));
+            desc = desc.with_column(name, typ.nullable(nullable));
+            arrays.push(array);
+        }
+        let decoders = validate_schema(&Schema::from(fields), &desc)?;
+        decode_chunk(&Chunk::new(arrays), &decoders, &desc)
+    }
+
+    /// Decodes a single column, returning its datums as rows of one column.
+    fn decode_column(array: Box<dyn Array>, typ: ScalarType) -> Vec<Row> {
+        decode(vec![(array, typ, true)]).unwrap()
+    }
+
+    fn boxed(array: impl Array + 'static) -> Box<dyn Array> {
+        Box::new(array)
+    }
+
+    fn rows(datums: &[Datum]) -> Vec<Row> {
+        datums.iter().map(|d| Row::pack_slice(&[*d])).collect()
+    }
+
+    fn ts(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32, micros: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_micro_opt(h, min, s, micros)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_bool() {
+        let array = BooleanArray::from([Some(true), Some(false), None]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Bool),
+            rows(&[Datum::True, Datum::False, Datum::Null])
+        );
+    }
+
+    #[test]
+    fn test_decode_ints() {
+        let array = PrimitiveArray::<i16>::from([Some(i16::MIN), None, Some(i16::MAX)]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Int16),
+            rows(&[Datum::Int16(i16::MIN), Datum::Null, Datum::Int16(i16::MAX)])
+        );
+        let array = PrimitiveArray::<i32>::from([Some(i32::MIN), None, Some(i32::MAX)]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Int32),
+            rows(&[Datum::Int32(i32::MIN), Datum::Null, Datum::Int32(i32::MAX)])
+        );
+        let array = PrimitiveArray::<i64>::from([Some(i64::MIN), None, Some(i64::MAX)]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Int64),
+            rows(&[Datum::Int64(i64::MIN), Datum::Null, Datum::Int64(i64::MAX)])
+        );
+    }
+
+    #[test]
+    fn test_decode_floats() {
+        let array = PrimitiveArray::<f32>::from([Some(1.5), None, Some(f32::NAN)]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Float32),
+            rows(&[
+                Datum::Float32(1.5f32.into()),
+                Datum::Null,
+                Datum::Float32(f32::NAN.into())
+            ])
+        );
+        let array = PrimitiveArray::<f64>::from([Some(-0.25), None, Some(f64::INFINITY)]);
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Float64),
+            rows(&[
+                Datum::Float64((-0.25f64).into()),
+                Datum::Null,
+                Datum::Float64(f64::INFINITY.into())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_strings() {
+        let expected = rows(&[Datum::String("a"), Datum::Null, Datum::String("")]);
+        let array = Utf8Array::<i32>::from([Some("a"), None, Some("")]);
+        assert_eq!(decode_column(boxed(array), ScalarType::String), expected);
+        let array = Utf8Array::<i64>::from([Some("a"), None, Some("")]);
+        assert_eq!(decode_column(boxed(array), ScalarType::String), expected);
+    }
+
+    #[test]
+    fn test_decode_jsonb() {
+        let json = Jsonb::from_str(r#"{"a": [1, null]}"#).unwrap().into_row();
+        let expected = vec![json, Row::pack_slice(&[Datum::Null])];
+        let array = Utf8Array::<i32>::from([Some(r#"{"a": [1, null]}"#), None]);
+        assert_eq!(decode_column(boxed(array), ScalarType::Jsonb), expected);
+        let array = Utf8Array::<i64>::from([Some(r#"{"a": [1, null]}"#), None]);
+        assert_eq!(decode_column(boxed(array), ScalarType::Jsonb), expected);
+
+        let array = Utf8Array::<i32>::from([Some("{")]);
+        let err = decode(vec![(boxed(array), ScalarType::Jsonb, true)]).unwrap_err();
+        assert!(err.starts_with("column c0: invalid JSON: "), "{}", err);
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        let expected = rows(&[Datum::Bytes(b"\x00\xff"), Datum::Null]);
+        let array = BinaryArray::<i32>::from([Some(b"\x00\xff".as_ref()), None]);
+        assert_eq!(decode_column(boxed(array), ScalarType::Bytes), expected);
+        let array = BinaryArray::<i64>::from([Some(b"\x00\xff".as_ref()), None]);
+        assert_eq!(decode_column(boxed(array), ScalarType::Bytes), expected);
+    }
+
+    #[test]
+    fn test_decode_date() {
+        let array = PrimitiveArray::<i32>::from([Some(0), Some(-1), Some(19_358), None])
+            .to(DataType::Date32);
+        let date = |y, m, d| {
+            Datum::Date(
+                NaiveDate::from_ymd_opt(y, m, d)
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            )
+        };
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Date),
+            rows(&[
+                date(1970, 1, 1),
+                date(1969, 12, 31),
+                date(2023, 1, 1),
+                Datum::Null
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_timestamps() {
+        // The same instants, 2023-01-01 00:00:01.5 and 1969-12-31 23:59:59.5,
+        // in each unit.
+        let cases = [
+            (TimeUnit::Millisecond, [1_672_531_201_500, -500]),
+            (TimeUnit::Microsecond, [1_672_531_201_500_000, -500_000]),
+            (
+                TimeUnit::Nanosecond,
+                [1_672_531_201_500_000_000, -500_000_000],
+            ),
+        ];
+        let after = ts(2023, 1, 1, 0, 0, 1, 500_000);
+        let before = ts(1969, 12, 31, 23, 59, 59, 500_000);
+        for (unit, values) in cases {
+            let array = PrimitiveArray::<i64>::from([Some(values[0]), Some(values[1]), None])
+                .to(DataType::Timestamp(unit, None));
+            assert_eq!(
+                decode_column(boxed(array), ScalarType::Timestamp),
+                rows(&[
+                    Datum::Timestamp(CheckedTimestamp::from_timestamplike(after).unwrap()),
+                    Datum::Timestamp(CheckedTimestamp::from_timestamplike(before).unwrap()),
+                    Datum::Null
+                ]),
+                "{:?}",
+                unit
+            );
+
+            let array = PrimitiveArray::<i64>::from([Some(values[0]), Some(values[1]), None])
+                .to(DataType::Timestamp(unit, Some("UTC".into())));
+            let tz = |ts| {
+                Datum::TimestampTz(
+                    CheckedTimestamp::from_timestamplike(DateTime::<Utc>::from_utc(ts, Utc))
+                        .unwrap(),
+                )
+            };
+            assert_eq!(
+                decode_column(boxed(array), ScalarType::TimestampTz),
+                rows(&[tz(after), tz(before), Datum::Null]),
+                "{:?}",
+                unit
+            );
+        }
+
+        let array =
+            PrimitiveArray::<i64>::from([Some(-1)]).to(DataType::Timestamp(TimeUnit::Second, None));
+        assert_eq!(
+            decode_column(boxed(array), ScalarType::Timestamp),
+            rows(&[Datum::Timestamp(
+                CheckedTimestamp::from_timestamplike(ts(1969, 12, 31, 23, 59, 59, 0)).unwrap()
+            )])
+        );
+
+        let array = PrimitiveArray::<i64>::from([Some(i64::MAX)])
+            .to(DataType::Timestamp(TimeUnit::Second, None));
+        assert_eq!(
+            decode(vec![(boxed(array), ScalarType::Timestamp, true)]).unwrap_err(),
+            format!("column c0: timestamp out of range: {}", i64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_decode_multiple_columns() {
+        let rows = decode(vec![
+            (
+                boxed(PrimitiveArray::<i64>::from([Some(1), Some(2)])),
+                ScalarType::Int64,
+                false,
+            ),
+            (
+                boxed(Utf8Array::<i32>::from([Some("a"), None])),
+                ScalarType::String,
+                true,
+            ),
+        ])
+        .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack_slice(&[Datum::Int64(1), Datum::String("a")]),
+                Row::pack_slice(&[Datum::Int64(2), Datum::Null]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_null_in_not_null_column() {
+        let array = PrimitiveArray::<i64>::from([Some(1), None]);
+        assert_eq!(
+            decode(vec![(boxed(array), ScalarType::Int64, false)]).unwrap_err(),
+            "null value in column c0 violates not-null constraint"
+        );
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int64.nullable(false))
+            .with_column("b", ScalarType::TimestampTz.nullable(true));
+
+        let schema = Schema::from(vec![Field::new("a", DataType::Int64, false)]);
+        assert_eq!(
+            validate_schema(&schema, &desc).unwrap_err(),
+            "record batches have 1 columns, but the source has 2"
+        );
+
+        let schema = Schema::from(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("c", arrow_type(&ScalarType::TimestampTz).unwrap(), true),
+        ]);
+        assert_eq!(
+            validate_schema(&schema, &desc).unwrap_err(),
+            "record batch column c does not match source column b"
+        );
+
+        // A timestamp without a time zone cannot be written to a column of
+        // type timestamp with time zone.
+        let schema = Schema::from(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Timestamp(TimeUnit::Second, None), true),
+        ]);
+        assert_eq!(
+            validate_schema(&schema, &desc).unwrap_err(),
+            "column b has Arrow type Timestamp(Second, None), which cannot be written to a \
+             column of type timestamp with time zone"
+        );
+
+        // The schema that `GetSchema` reports is always accepted.
+        let schema = Schema::from(
+            desc.iter()
+                .map(|(name, typ)| {
+                    Field::new(
+                        name.as_str(),
+                        arrow_type(&typ.scalar_type).unwrap(),
+                        typ.nullable,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        assert!(validate_schema(&schema, &desc).is_ok());
+    }
+}
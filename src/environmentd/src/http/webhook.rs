@@ -0,0 +1,107 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Webhook source HTTP endpoints.
+
+use std::collections::BTreeMap;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, StatusCode};
+
+use mz_adapter::catalog::SYSTEM_USER;
+use mz_adapter::session::EndTransactionAction;
+use mz_adapter::{AdapterError, SessionClient};
+use mz_repr::adt::jsonb::Jsonb;
+use mz_repr::Row;
+use mz_sql::names::PartialObjectName;
+
+/// Appends the events in the request body to the webhook source
+/// `database.schema.name`.
+///
+/// The body is decoded as a single JSON value, or as one JSON value per line
+/// if the request's content type is `application/x-ndjson`. All events in a
+/// request are committed atomically.
+///
+/// Requests are not authenticated as any user: the coordinator only appends
+/// the events if the request is signed with the source's secret.
+pub async fn handle_webhook(
+    State(adapter_client): State<mz_adapter::Client>,
+    Path((database, schema, name)): Path<(String, String, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let rows = match decode_body(&headers, &body) {
+        Ok(rows) => rows,
+        Err(e) => return Err((StatusCode::BAD_REQUEST, e.to_string())),
+    };
+    let mut client = match webhook_client(&adapter_client).await {
+        Ok(client) => client,
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    };
+    let name = PartialObjectName {
+        database: Some(database),
+        schema: Some(schema),
+        item: name,
+    };
+    // Header names are case insensitive; the `http` crate always yields them
+    // in lowercase, which is how webhook sources record their HMAC header.
+    let headers = headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect::<BTreeMap<_, _>>();
+
+    let res = async {
+        client.start_transaction(Some(1)).await?;
+        client
+            .append_webhook(name, body.to_vec(), headers, rows)
+            .await?;
+        client.end_transaction(EndTransactionAction::Commit).await
+    }
+    .await;
+    match res {
+        Ok(_) => Ok(StatusCode::OK),
+        Err(e) => {
+            let _ = client.end_transaction(EndTransactionAction::Rollback).await;
+            let status = match e {
+                AdapterError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            Err((status, e.to_string()))
+        }
+    }
+}
+
+/// Returns a client whose session only ever appends to webhook sources, which
+/// is why it can belong to the system user.
+async fn webhook_client(
+    adapter_client: &mz_adapter::Client,
+) -> Result<SessionClient, AdapterError> {
+    let adapter_client = adapter_client.new_conn()?;
+    let session = adapter_client.new_session(SYSTEM_USER.clone());
+    let (client, _) = adapter_client.startup(session, false).await?;
+    Ok(client)
+}
+
+fn decode_body(headers: &HeaderMap, body: &[u8]) -> Result<Vec<Row>, anyhow::Error> {
+    let is_ndjson = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(false, |v| v.starts_with("application/x-ndjson"));
+    if is_ndjson {
+        body.split(|b| *b == b'\n')
+            .filter(|line| !line.iter().all(|b| b.is_ascii_whitespace()))
+            .map(|line| Ok(Jsonb::from_slice(line)?.into_row()))
+            .collect()
+    } else {
+        Ok(vec![Jsonb::from_slice(body)?.into_row()])
+    }
+}
@@ -90,7 +90,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{bail, Context};
-use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod};
+use openssl::ssl::{self, AlpnError, SslAcceptor, SslFiletype, SslMethod};
 use tokio::sync::{mpsc, oneshot};
 use tower_http::cors::AllowOrigin;
 
@@ -112,7 +112,9 @@ use mz_sql::catalog::EnvironmentId;
 use mz_stash::Stash;
 use mz_storage_client::types::connections::ConnectionContext;
 
-use crate::http::{HttpConfig, HttpServer, InternalHttpConfig, InternalHttpServer};
+use crate::http::{
+    FlightConfig, FlightServer, HttpConfig, HttpServer, InternalHttpConfig, InternalHttpServer,
+};
 use crate::server::ListenerHandle;
 
 mod http;
@@ -142,6 +144,9 @@ pub struct Config {
     pub internal_sql_listen_addr: SocketAddr,
     /// The IP address and port to serve the metrics registry from.
     pub internal_http_listen_addr: SocketAddr,
+    /// The IP address and port to listen for Arrow Flight connections on, if
+    /// the Arrow Flight endpoint of Flight sources is enabled.
+    pub flight_listen_addr: Option<SocketAddr>,
     /// Origins for which cross-origin resource sharing (CORS) for HTTP requests
     /// is permitted.
     pub cors_allowed_origin: AllowOrigin,
@@ -249,10 +254,10 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
     let stash = mz_stash::Cache::new(stash);
 
     // Validate TLS configuration, if present.
-    let (pgwire_tls, http_tls) = match &config.tls {
-        None => (None, None),
+    let (pgwire_tls, http_tls, flight_tls) = match &config.tls {
+        None => (None, None, None),
         Some(tls_config) => {
-            let context = {
+            let acceptor = || -> Result<_, anyhow::Error> {
                 // Mozilla publishes three presets: old, intermediate, and modern. They
                 // recommend the intermediate preset for general purpose servers, which
                 // is what we use, as it is compatible with nearly every client released
@@ -262,6 +267,15 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
                 let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
                 builder.set_certificate_chain_file(&tls_config.cert)?;
                 builder.set_private_key_file(&tls_config.key, SslFiletype::PEM)?;
+                Ok(builder)
+            };
+            let context = acceptor()?.build().into_context();
+            // gRPC clients require that HTTP/2 be negotiated via ALPN.
+            let flight_context = {
+                let mut builder = acceptor()?;
+                builder.set_alpn_select_callback(|_, client_protos| {
+                    ssl::select_next_proto(b"\x02h2", client_protos).ok_or(AlpnError::NOACK)
+                });
                 builder.build().into_context()
             };
             let pgwire_tls = mz_pgwire::TlsConfig {
@@ -276,7 +290,13 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
                     TlsMode::Require => http::TlsMode::Enable,
                 },
             };
-            (Some(pgwire_tls), Some(http_tls))
+            let flight_tls = http::TlsConfig {
+                context: flight_context,
+                mode: match tls_config.mode {
+                    TlsMode::Require => http::TlsMode::Enable,
+                },
+            };
+            (Some(pgwire_tls), Some(http_tls), Some(flight_tls))
         }
     };
 
@@ -290,6 +310,13 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
         server::listen(config.internal_sql_listen_addr).await?;
     let (internal_http_listener, internal_http_conns) =
         server::listen(config.internal_http_listen_addr).await?;
+    let (flight_listener, flight_conns) = match config.flight_listen_addr {
+        Some(addr) => {
+            let (listener, conns) = server::listen(addr).await?;
+            (Some(listener), Some(conns))
+        }
+        None => (None, None),
+    };
 
     // Start the internal HTTP server.
     //
@@ -440,6 +467,18 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
         server::serve(http_conns, http_server)
     });
 
+    // Launch Arrow Flight server, if enabled.
+    if let Some(flight_conns) = flight_conns {
+        task::spawn(|| "flight_server", {
+            let flight_server = FlightServer::new(FlightConfig {
+                tls: flight_tls,
+                frontegg: config.frontegg.clone(),
+                adapter_client: adapter_client.clone(),
+            });
+            server::serve(flight_conns, flight_server)
+        });
+    }
+
     // Start telemetry reporting loop.
     if let Some(segment_client) = segment_client {
         telemetry::start_reporting(telemetry::Config {
@@ -469,6 +508,7 @@ pub async fn serve(config: Config) -> Result<Server, anyhow::Error> {
         http_listener,
         internal_sql_listener,
         internal_http_listener,
+        flight_listener,
         _adapter_handle: adapter_handle,
     })
 }
@@ -480,6 +520,7 @@ pub struct Server {
     http_listener: ListenerHandle,
     internal_sql_listener: ListenerHandle,
     internal_http_listener: ListenerHandle,
+    flight_listener: Option<ListenerHandle>,
     _adapter_handle: mz_adapter::Handle,
 }
 
@@ -499,4 +540,8 @@ impl Server {
     pub fn internal_http_local_addr(&self) -> SocketAddr {
         self.internal_http_listener.local_addr()
     }
+
+    pub fn flight_local_addr(&self) -> Option<SocketAddr> {
+        self.flight_listener.as_ref().map(|l| l.local_addr())
+    }
 }
@@ -81,6 +81,14 @@ use std::thread;
 use std::time::Duration;
 
 use anyhow::bail;
+use arrow2::array::{Array, PrimitiveArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::DataType;
+use arrow2::io::flight::{deserialize_schemas, serialize_batch, serialize_schema};
+use arrow2::io::ipc::write::{default_ipc_fields, WriteOptions};
+use arrow_format::flight::data::flight_descriptor::DescriptorType;
+use arrow_format::flight::data::{FlightDescriptor, Ticket};
+use arrow_format::flight::service::flight_service_client::FlightServiceClient;
 use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
 use reqwest::Url;
@@ -713,3 +721,76 @@ fn test_default_cluster_sizes() {
         .get(0);
     assert_eq!(builtin_size, "2");
 }
+
+// Tests that record batches written to a Flight source with `DoPut` are
+// committed to the source, and that the Flight endpoint rejects `DoGet`.
+#[test]
+fn test_flight() {
+    let server = util::start_server(util::Config::default().with_flight()).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute("CREATE SOURCE flight_src FROM FLIGHT (id bigint NOT NULL, name text)")
+        .unwrap();
+
+    let addr = server.inner.flight_local_addr().unwrap();
+    server.runtime.block_on(async {
+        let mut flight = FlightServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+        let descriptor = FlightDescriptor {
+            r#type: DescriptorType::Path as i32,
+            cmd: vec![],
+            path: vec!["materialize".into(), "public".into(), "flight_src".into()],
+        };
+
+        let schema = flight
+            .get_schema(descriptor.clone())
+            .await
+            .unwrap()
+            .into_inner();
+        let (schema, _) = deserialize_schemas(&schema.schema).unwrap();
+        let fields = schema
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.data_type.clone(), f.is_nullable))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", DataType::Int64, false),
+                ("name", DataType::Utf8, true)
+            ]
+        );
+
+        let ipc_fields = default_ipc_fields(&schema.fields);
+        let mut first = serialize_schema(&schema, Some(&ipc_fields));
+        first.flight_descriptor = Some(descriptor);
+        let chunk = Chunk::new(vec![
+            Box::new(PrimitiveArray::<i64>::from([Some(1), Some(2)])) as Box<dyn Array>,
+            Box::new(Utf8Array::<i32>::from([Some("one"), None])),
+        ]);
+        let (_, mut batch) =
+            serialize_batch(&chunk, &ipc_fields, &WriteOptions { compression: None }).unwrap();
+        batch.app_metadata = b"batch 1".to_vec();
+        let mut results = flight
+            .do_put(futures::stream::iter(vec![first, batch]))
+            .await
+            .unwrap()
+            .into_inner();
+        let result = results.message().await.unwrap().unwrap();
+        assert_eq!(result.app_metadata, b"batch 1");
+        assert!(results.message().await.unwrap().is_none());
+
+        let err = flight.do_get(Ticket { ticket: vec![] }).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unimplemented);
+    });
+
+    // The batch is committed by the time that it is acknowledged.
+    let rows = client
+        .query("SELECT id, name FROM flight_src ORDER BY id", &[])
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.get::<_, i64>(0), row.get::<_, Option<String>>(1)))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![(1, Some("one".into())), (2, None)]);
+}
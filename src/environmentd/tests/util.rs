@@ -127,6 +127,7 @@ pub struct Config {
     default_cluster_replica_size: String,
     builtin_cluster_replica_size: String,
     propagate_crashes: bool,
+    flight: bool,
 }
 
 impl Default for Config {
@@ -143,6 +144,7 @@ impl Default for Config {
             default_cluster_replica_size: "1".to_string(),
             builtin_cluster_replica_size: "1".to_string(),
             propagate_crashes: false,
+            flight: false,
         }
     }
 }
@@ -215,6 +217,11 @@ impl Config {
         self.propagate_crashes = propagate_crashes;
         self
     }
+
+    pub fn with_flight(mut self) -> Self {
+        self.flight = true;
+        self
+    }
 }
 
 pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
@@ -298,6 +305,9 @@ pub fn start_server(config: Config) -> Result<Server, anyhow::Error> {
         http_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
         internal_sql_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
         internal_http_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        flight_listen_addr: config
+            .flight
+            .then(|| SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)),
         tls: config.tls,
         frontegg: config.frontegg,
         unsafe_mode: config.unsafe_mode,
@@ -0,0 +1,305 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Aggregate functions defined outside of this crate.
+//!
+//! Embedders can add aggregate functions, e.g., domain-specific sketches, by
+//! implementing [`CustomAggregate`] and registering the implementation. A
+//! registered aggregate is planned as [`AggregateFunc::Custom`], which carries
+//! a [`CustomAggregateHandle`] to it, and can be called from SQL under its
+//! name. Embedders should register aggregates through
+//! `mz_sql::func::register_custom_aggregate`, which refuses names of builtin
+//! functions, as the custom aggregate would shadow them.
+//!
+//! Like most aggregate functions, custom aggregates skip `NULL` values, and
+//! produce `NULL` over an empty input without a `GROUP BY` clause. Groups of
+//! only `NULL` values are finalized from their initial state.
+//!
+//! Plans are serialized with the names of their custom aggregates, and are
+//! resolved again when deserialized, so every process that plans or renders
+//! dataflows must register the same aggregates before it does so.
+//!
+//! Custom aggregates are rendered as accumulable reductions: the state of
+//! each group is maintained incrementally, by merging the states of the
+//! values that are added to the group, and the negations of the states of the
+//! values that are removed from it. The states must therefore form an abelian
+//! group:
+//!
+//!   * `merge` is associative and commutative, and `init()` is its identity.
+//!   * Merging a state with its `negate`d copy results in `init()`.
+//!   * Inserting a value into a state is the same as merging the state with
+//!     the state of a group that only contains the value.
+//!
+//! States are compared by their encoding, so equal states must be encoded by
+//! equal rows.
+//!
+//! [`AggregateFunc::Custom`]: crate::AggregateFunc::Custom
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use mz_repr::{ColumnType, Datum, Row};
+
+/// An aggregate function defined outside of this crate.
+///
+/// See the [module documentation](self) for the laws implementations must
+/// satisfy. The callbacks are never passed `Datum::Null`.
+pub trait CustomAggregate: fmt::Debug + Send + Sync + 'static {
+    /// The name of the aggregate, under which it is called from SQL.
+    fn name(&self) -> &str;
+
+    /// The output type of the aggregate for inputs of type `input_type`.
+    fn output_type(&self, input_type: ColumnType) -> ColumnType;
+
+    /// Returns the state of a group that contains no values.
+    fn init(&self) -> Row;
+
+    /// Adds `value` to the group with state `state`.
+    fn insert(&self, state: &mut Row, value: Datum);
+
+    /// Replaces `state` with the state that cancels it out when merged.
+    fn negate(&self, state: &mut Row);
+
+    /// Merges the group with state `other` into the group with state `state`.
+    fn merge(&self, state: &mut Row, other: &Row);
+
+    /// Returns a row containing the single output datum of the group with
+    /// state `state`.
+    fn finalize(&self, state: &Row) -> Row;
+}
+
+/// An error from registering or resolving a custom aggregate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomAggregateError {
+    /// An aggregate with the same name is already registered.
+    AlreadyRegistered(String),
+    /// No aggregate is registered under the name.
+    NotRegistered(String),
+}
+
+impl fmt::Display for CustomAggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CustomAggregateError::AlreadyRegistered(name) => {
+                write!(f, "custom aggregate {} is already registered", name)
+            }
+            CustomAggregateError::NotRegistered(name) => {
+                write!(f, "custom aggregate {} is not registered", name)
+            }
+        }
+    }
+}
+
+impl Error for CustomAggregateError {}
+
+/// The registered custom aggregates, by name.
+static REGISTRY: Lazy<RwLock<BTreeMap<String, Arc<dyn CustomAggregate>>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Registers `aggregate` under its name.
+///
+/// Fails if an aggregate with the same name is already registered.
+pub fn register<A: CustomAggregate>(aggregate: A) -> Result<(), CustomAggregateError> {
+    let mut registry = REGISTRY.write().expect("lock poisoned");
+    let name = aggregate.name().to_string();
+    if registry.contains_key(&name) {
+        return Err(CustomAggregateError::AlreadyRegistered(name));
+    }
+    registry.insert(name, Arc::new(aggregate));
+    Ok(())
+}
+
+/// Returns the names of the registered custom aggregates.
+pub fn registered() -> Vec<String> {
+    REGISTRY
+        .read()
+        .expect("lock poisoned")
+        .keys()
+        .cloned()
+        .collect()
+}
+
+/// A registered custom aggregate, as referred to by plans.
+///
+/// Handles are compared, hashed and serialized by the name of their
+/// aggregate.
+#[derive(Clone)]
+pub struct CustomAggregateHandle {
+    aggregate: Arc<dyn CustomAggregate>,
+    /// The cached result of `aggregate.init()`.
+    zero: Row,
+}
+
+impl CustomAggregateHandle {
+    /// Returns a handle to the custom aggregate registered under `name`.
+    pub fn resolve(name: &str) -> Result<CustomAggregateHandle, CustomAggregateError> {
+        let aggregate = REGISTRY
+            .read()
+            .expect("lock poisoned")
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CustomAggregateError::NotRegistered(name.to_string()))?;
+        let zero = aggregate.init();
+        Ok(CustomAggregateHandle { aggregate, zero })
+    }
+
+    /// The name of the aggregate.
+    pub fn name(&self) -> &str {
+        self.aggregate.name()
+    }
+
+    /// The state of a group that contains no values.
+    pub fn zero(&self) -> &Row {
+        &self.zero
+    }
+
+    /// The aggregate itself.
+    pub fn aggregate(&self) -> &dyn CustomAggregate {
+        &*self.aggregate
+    }
+}
+
+impl fmt::Debug for CustomAggregateHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CustomAggregateHandle")
+            .field(&self.name())
+            .finish()
+    }
+}
+
+impl PartialEq for CustomAggregateHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for CustomAggregateHandle {}
+
+impl PartialOrd for CustomAggregateHandle {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CustomAggregateHandle {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name().cmp(other.name())
+    }
+}
+
+impl Hash for CustomAggregateHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state)
+    }
+}
+
+impl Serialize for CustomAggregateHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomAggregateHandle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        CustomAggregateHandle::resolve(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Once;
+
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    /// Counts the non-null values of a group.
+    #[derive(Debug)]
+    struct TestCount;
+
+    impl TestCount {
+        fn add(&self, state: &mut Row, n: i64) {
+            let count = state.unpack_first().unwrap_int64();
+            *state = Row::pack_slice(&[Datum::Int64(count + n)]);
+        }
+    }
+
+    impl CustomAggregate for TestCount {
+        fn name(&self) -> &str {
+            "test_count"
+        }
+
+        fn output_type(&self, _input_type: ColumnType) -> ColumnType {
+            ScalarType::Int64.nullable(false)
+        }
+
+        fn init(&self) -> Row {
+            Row::pack_slice(&[Datum::Int64(0)])
+        }
+
+        fn insert(&self, state: &mut Row, _value: Datum) {
+            self.add(state, 1)
+        }
+
+        fn negate(&self, state: &mut Row) {
+            let count = state.unpack_first().unwrap_int64();
+            *state = Row::pack_slice(&[Datum::Int64(-count)]);
+        }
+
+        fn merge(&self, state: &mut Row, other: &Row) {
+            self.add(state, other.unpack_first().unwrap_int64())
+        }
+
+        fn finalize(&self, state: &Row) -> Row {
+            state.clone()
+        }
+    }
+
+    /// Returns a handle to [`TestCount`], which is registered on first use.
+    pub(crate) fn test_count() -> CustomAggregateHandle {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| register(TestCount).unwrap());
+        CustomAggregateHandle::resolve("test_count").unwrap()
+    }
+
+    #[test]
+    fn test_registry() {
+        let handle = test_count();
+        assert_eq!(
+            register(TestCount),
+            Err(CustomAggregateError::AlreadyRegistered("test_count".into()))
+        );
+        assert_eq!(
+            CustomAggregateHandle::resolve("test_missing").unwrap_err(),
+            CustomAggregateError::NotRegistered("test_missing".into())
+        );
+
+        let aggregate = handle.aggregate();
+        let mut state = handle.zero().clone();
+        aggregate.insert(&mut state, Datum::True);
+        aggregate.insert(&mut state, Datum::False);
+        let mut other = handle.zero().clone();
+        aggregate.insert(&mut other, Datum::True);
+        aggregate.merge(&mut state, &other);
+        assert_eq!(aggregate.finalize(&state).unpack_first(), Datum::Int64(3));
+
+        let mut negated = state.clone();
+        aggregate.negate(&mut negated);
+        aggregate.merge(&mut state, &negated);
+        assert_eq!(&state, handle.zero());
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! HyperLogLog sketches for approximate distinct counting.
+//!
+//! A sketch is stored as a byte string so that it can travel through
+//! dataflows as an ordinary `bytea` datum. Two sketches are merged by taking
+//! the maximum of each of their registers, which makes merging associative,
+//! commutative and idempotent, and thus suitable for hierarchical reductions.
+//!
+//! Sketches come in two encodings:
+//!
+//!   * A sparse encoding, which is a `0` tag byte followed by
+//!     `(register index, rank)` entries sorted by register index. Each entry is
+//!     a big-endian `u16` followed by a `u8`. Registers that are not present
+//!     are zero.
+//!   * A dense encoding, which is a `1` tag byte followed by one byte per
+//!     register.
+//!
+//! Sketches start out sparse and are converted to the dense encoding once the
+//! sparse encoding would no longer be smaller.
+//!
+//! Sketches are ordinary `bytea` values, so users can hand us arbitrary bytes.
+//! Malformed sketches are merged into a single invalid sketch, which absorbs
+//! any sketch it is merged with, and estimating an invalid sketch is an error.
+//! This keeps merging associative and commutative, which matters because
+//! aggregations cannot report errors from their merge steps.
+
+use std::hash::Hasher;
+
+use mz_ore::cast::{CastFrom, TryCastFrom};
+use twox_hash::XxHash64;
+
+use crate::EvalError;
+
+/// The number of bits of the hash used to select a register.
+const PRECISION: u32 = 12;
+
+/// The number of registers in a sketch.
+const REGISTERS: usize = 1 << PRECISION;
+
+/// The tag byte of a sparse sketch.
+const SPARSE: u8 = 0;
+
+/// The tag byte of a dense sketch.
+const DENSE: u8 = 1;
+
+/// The tag byte of an invalid sketch.
+const INVALID: u8 = 2;
+
+/// The size in bytes of a sparse sketch entry.
+const SPARSE_ENTRY_LEN: usize = 3;
+
+/// The largest rank a register can hold, i.e., one more than the number of
+/// hash bits that remain after selecting a register.
+const MAX_RANK: u8 = 53;
+
+/// Returns a sketch that contains only the value whose encoding is `data`.
+pub fn sketch(data: &[u8]) -> Vec<u8> {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(data);
+    let hash = hasher.finish();
+    let index = u16::try_from(hash >> (64 - PRECISION)).expect("index fits in PRECISION bits");
+    // The rank is the position of the leftmost one bit in the remaining bits.
+    let rest = hash << PRECISION;
+    let rank = u8::try_from(rest.leading_zeros() + 1)
+        .unwrap_or(MAX_RANK)
+        .min(MAX_RANK);
+    let mut buf = Vec::with_capacity(1 + SPARSE_ENTRY_LEN);
+    buf.push(SPARSE);
+    buf.extend_from_slice(&index.to_be_bytes());
+    buf.push(rank);
+    buf
+}
+
+/// Merges two sketches into a sketch that contains the values of both.
+///
+/// The result only depends on the values contained in the sketches, not on
+/// their encodings or on the order in which they were merged. If either
+/// sketch is malformed, the result is the invalid sketch.
+pub fn merge(a: &[u8], b: &[u8]) -> Vec<u8> {
+    if a.first() == Some(&SPARSE) && b.first() == Some(&SPARSE) {
+        let (a, b) = match (sparse_entries(a), sparse_entries(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return vec![INVALID],
+        };
+        let (mut a, mut b) = (a.into_iter().peekable(), b.into_iter().peekable());
+        let mut entries = Vec::new();
+        loop {
+            let entry = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.0 == y.0 => {
+                    let entry = (x.0, x.1.max(y.1));
+                    a.next();
+                    b.next();
+                    entry
+                }
+                (Some(x), Some(y)) if x.0 > y.0 => b.next().unwrap(),
+                (Some(_), _) => a.next().unwrap(),
+                (None, Some(_)) => b.next().unwrap(),
+                (None, None) => break,
+            };
+            entries.push(entry);
+        }
+        encode(entries)
+    } else {
+        let (mut merged, b) = match (registers(a), registers(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return vec![INVALID],
+        };
+        for (register, rank) in merged.iter_mut().zip(b) {
+            *register = (*register).max(rank);
+        }
+        let entries = merged
+            .into_iter()
+            .enumerate()
+            .filter(|(_, rank)| *rank > 0)
+            .map(|(index, rank)| (u16::try_from(index).expect("index fits in a u16"), rank))
+            .collect();
+        encode(entries)
+    }
+}
+
+/// Encodes the non-zero registers of a sketch, sorted by register index, in
+/// whichever encoding is smaller.
+fn encode(entries: Vec<(u16, u8)>) -> Vec<u8> {
+    if entries.len() * SPARSE_ENTRY_LEN < REGISTERS {
+        let mut buf = Vec::with_capacity(1 + entries.len() * SPARSE_ENTRY_LEN);
+        buf.push(SPARSE);
+        for (index, rank) in entries {
+            buf.extend_from_slice(&index.to_be_bytes());
+            buf.push(rank);
+        }
+        buf
+    } else {
+        let mut buf = vec![0; 1 + REGISTERS];
+        buf[0] = DENSE;
+        for (index, rank) in entries {
+            if let Some(register) = buf.get_mut(1 + usize::from(index)) {
+                *register = rank;
+            }
+        }
+        buf
+    }
+}
+
+/// Estimates the number of distinct values contained in a sketch.
+///
+/// Returns an error if the sketch is malformed.
+pub fn estimate(sketch: &[u8]) -> Result<i64, EvalError> {
+    let registers = registers(sketch).ok_or_else(|| {
+        EvalError::InvalidParameterValue("invalid HyperLogLog sketch".to_string())
+    })?;
+    let m = f64::cast_from(u32::try_from(REGISTERS).expect("REGISTERS fits in a u32"));
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-i32::from(*r))).sum();
+    let mut estimate = alpha * m * m / sum;
+    let zeros = registers.iter().filter(|r| **r == 0).count();
+    if estimate <= 2.5 * m && zeros > 0 {
+        // Use linear counting for small cardinalities, where the raw estimate
+        // is known to be biased.
+        let zeros = f64::cast_from(u32::try_from(zeros).expect("zeros fits in a u32"));
+        estimate = m * (m / zeros).ln();
+    }
+    // Sketches where every register holds a large rank can produce estimates
+    // beyond the range of an `i64`.
+    Ok(i64::try_cast_from(estimate.round()).unwrap_or(i64::MAX))
+}
+
+/// Returns the `(register index, rank)` entries of a sparse sketch, or `None`
+/// if the sketch is malformed.
+///
+/// Entries must be sorted by strictly increasing register index, name
+/// registers that exist, and hold non-zero ranks no larger than [`MAX_RANK`].
+fn sparse_entries(sketch: &[u8]) -> Option<Vec<(u16, u8)>> {
+    let body = sketch.get(1..)?;
+    if body.len() % SPARSE_ENTRY_LEN != 0 {
+        return None;
+    }
+    let mut entries = Vec::with_capacity(body.len() / SPARSE_ENTRY_LEN);
+    for entry in body.chunks_exact(SPARSE_ENTRY_LEN) {
+        let (index, rank) = (u16::from_be_bytes([entry[0], entry[1]]), entry[2]);
+        let in_order = entries.last().map_or(true, |(prev, _)| *prev < index);
+        if !in_order || usize::from(index) >= REGISTERS || rank == 0 || rank > MAX_RANK {
+            return None;
+        }
+        entries.push((index, rank));
+    }
+    Some(entries)
+}
+
+/// Returns the registers of a sketch in either encoding, or `None` if the
+/// sketch is malformed.
+fn registers(sketch: &[u8]) -> Option<Vec<u8>> {
+    match sketch.first() {
+        Some(&DENSE) => {
+            let registers = &sketch[1..];
+            if registers.len() != REGISTERS || registers.iter().any(|r| *r > MAX_RANK) {
+                return None;
+            }
+            Some(registers.to_vec())
+        }
+        Some(&SPARSE) => {
+            let mut registers = vec![0; REGISTERS];
+            for (index, rank) in sparse_entries(sketch)? {
+                registers[usize::from(index)] = rank;
+            }
+            Some(registers)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch_range(range: std::ops::Range<u64>) -> Vec<u8> {
+        range
+            .map(|i| sketch(&i.to_le_bytes()))
+            .reduce(|a, b| merge(&a, &b))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_estimate() {
+        for n in [1, 10, 100, 1_000, 10_000] {
+            let estimate = estimate(&sketch_range(0..n)).unwrap();
+            let n = i64::try_from(n).unwrap();
+            // The standard error with 4096 registers is about 1.6%.
+            assert!((estimate - n).abs() <= n / 20 + 1, "{estimate} vs {n}");
+        }
+        // Saturated registers do not overflow the estimate.
+        let mut saturated = vec![MAX_RANK; 1 + REGISTERS];
+        saturated[0] = DENSE;
+        assert_eq!(estimate(&saturated).unwrap(), i64::MAX);
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = sketch_range(0..3_000);
+        let b = sketch_range(2_000..5_000);
+        let merged = merge(&a, &b);
+        assert_eq!(merged, sketch_range(0..5_000));
+        // Merging is idempotent and commutative.
+        assert_eq!(merge(&merged, &a), merged);
+        assert_eq!(merge(&b, &a), merged);
+        assert_eq!(estimate(&merge(&sketch(b"x"), &sketch(b"x"))).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let valid = sketch(b"x");
+        let malformed: &[&[u8]] = &[
+            // Empty.
+            &[],
+            // Unknown tag.
+            &[3],
+            // Truncated sparse entry.
+            &[SPARSE, 0, 1],
+            // Register index out of range.
+            &[SPARSE, 0xff, 0xff, 1],
+            // Rank out of range.
+            &[SPARSE, 0, 1, MAX_RANK + 1],
+            // Zero rank.
+            &[SPARSE, 0, 1, 0],
+            // Unsorted entries.
+            &[SPARSE, 0, 2, 1, 0, 1, 1],
+            // Duplicate entries.
+            &[SPARSE, 0, 1, 1, 0, 1, 1],
+            // Short dense sketch.
+            &[DENSE, 1, 2, 3],
+        ];
+        for sketch in malformed {
+            assert!(estimate(sketch).is_err(), "{sketch:?}");
+            // Invalid sketches absorb whatever they are merged with.
+            assert_eq!(merge(sketch, &valid), [INVALID]);
+            assert_eq!(merge(&valid, sketch), [INVALID]);
+            assert!(estimate(&merge(sketch, &valid)).is_err());
+        }
+    }
+}
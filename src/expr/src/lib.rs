@@ -90,7 +90,10 @@ mod linear;
 mod relation;
 mod scalar;
 
+pub mod custom_aggregate;
 pub mod explain;
+pub mod hll;
+pub mod quantile;
 pub mod virtual_syntax;
 pub mod visit;
 
@@ -107,6 +110,7 @@ pub use linear::{
 pub use relation::func::{AggregateFunc, LagLeadType, TableFunc};
 pub use relation::func::{AnalyzedRegex, CaptureGroupDesc};
 pub use relation::join_input_mapper::JoinInputMapper;
+pub use relation::provenance::ColumnProvenance;
 pub use relation::{
     compare_columns, AggregateExpr, CollectionPlan, ColumnOrder, JoinImplementation,
     MirRelationExpr, ProtoAggregateExpr, RowSetFinishing, WindowFrame, WindowFrameBound,
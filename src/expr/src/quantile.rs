@@ -0,0 +1,221 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sketches of distributions of `f64`s for approximate percentiles.
+//!
+//! The sketch is in the style of DDSketch: finite, non-zero values are counted
+//! in buckets whose bounds grow exponentially, so that any value in a bucket is
+//! within [`ACCURACY`] of the bucket's representative value. The size of a
+//! sketch thus only depends on the range of magnitudes of its values, not on
+//! the number of values.
+//!
+//! A sketch only consists of counts, so sketches are merged by adding their
+//! counts, and values are removed by subtracting them. This makes sketches
+//! suitable for accumulation in the difference field of updates, where
+//! retractions are just negative counts.
+
+use std::collections::BTreeMap;
+use std::iter;
+
+use mz_ore::cast::TryCastFrom;
+use mz_repr::Diff;
+use serde::{Deserialize, Serialize};
+
+/// The relative accuracy of a sketch, i.e., the maximum relative difference
+/// between an estimated value and a value of the requested rank.
+pub const ACCURACY: f64 = 0.01;
+
+/// A sketch of a distribution of `f64`s. See the [module
+/// documentation](self).
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct QuantileSketch {
+    /// The number of values that are `-inf`.
+    neg_infs: Diff,
+    /// The number of negative values, by the bucket of their absolute value.
+    negatives: BTreeMap<i32, Diff>,
+    /// The number of values that are zero.
+    zeros: Diff,
+    /// The number of positive values, by bucket.
+    positives: BTreeMap<i32, Diff>,
+    /// The number of values that are `+inf`.
+    pos_infs: Diff,
+    /// The number of values that are `NaN`, which sorts after all other values.
+    nans: Diff,
+}
+
+impl QuantileSketch {
+    /// Returns a sketch that contains `value` `count` times.
+    pub fn singleton(value: f64, count: Diff) -> QuantileSketch {
+        let mut sketch = QuantileSketch::default();
+        if value.is_nan() {
+            sketch.nans = count;
+        } else if value == f64::INFINITY {
+            sketch.pos_infs = count;
+        } else if value == f64::NEG_INFINITY {
+            sketch.neg_infs = count;
+        } else if value > 0.0 {
+            sketch.positives.insert(bucket(value), count);
+        } else if value < 0.0 {
+            sketch.negatives.insert(bucket(-value), count);
+        } else {
+            sketch.zeros = count;
+        }
+        sketch.normalize();
+        sketch
+    }
+
+    /// Reports whether the sketch contains no values.
+    pub fn is_zero(&self) -> bool {
+        self.neg_infs == 0
+            && self.negatives.is_empty()
+            && self.zeros == 0
+            && self.positives.is_empty()
+            && self.pos_infs == 0
+            && self.nans == 0
+    }
+
+    /// Adds the values of `other` to the sketch.
+    pub fn plus_equals(&mut self, other: &QuantileSketch) {
+        self.neg_infs += other.neg_infs;
+        for (bucket, count) in &other.negatives {
+            *self.negatives.entry(*bucket).or_default() += count;
+        }
+        self.zeros += other.zeros;
+        for (bucket, count) in &other.positives {
+            *self.positives.entry(*bucket).or_default() += count;
+        }
+        self.pos_infs += other.pos_infs;
+        self.nans += other.nans;
+        self.normalize();
+    }
+
+    /// Multiplies the number of occurrences of each value by `factor`.
+    pub fn multiply(mut self, factor: Diff) -> QuantileSketch {
+        self.neg_infs *= factor;
+        for count in self.negatives.values_mut() {
+            *count *= factor;
+        }
+        self.zeros *= factor;
+        for count in self.positives.values_mut() {
+            *count *= factor;
+        }
+        self.pos_infs *= factor;
+        self.nans *= factor;
+        self.normalize();
+        self
+    }
+
+    /// Returns an estimate of the value at position `fraction` of the sorted
+    /// values, or `None` if the sketch contains no values.
+    ///
+    /// `fraction` must be between 0 and 1.
+    pub fn quantile(&self, fraction: f64) -> Option<f64> {
+        let buckets = iter::once((f64::NEG_INFINITY, self.neg_infs))
+            .chain(
+                self.negatives
+                    .iter()
+                    .rev()
+                    .map(|(bucket, count)| (-bucket_value(*bucket), *count)),
+            )
+            .chain(iter::once((0.0, self.zeros)))
+            .chain(
+                self.positives
+                    .iter()
+                    .map(|(bucket, count)| (bucket_value(*bucket), *count)),
+            )
+            .chain(iter::once((f64::INFINITY, self.pos_infs)))
+            .chain(iter::once((f64::NAN, self.nans)));
+        let count: Diff = buckets.clone().map(|(_, count)| count).sum();
+        if count <= 0 {
+            return None;
+        }
+        let last = f64::try_cast_from(count - 1)?;
+        let rank = Diff::try_cast_from((fraction * last).floor())?;
+        let mut seen = 0;
+        for (value, count) in buckets {
+            seen += count;
+            if seen > rank {
+                return Some(value);
+            }
+        }
+        // Only reachable if some counts are negative, which consistent inputs
+        // never produce.
+        None
+    }
+
+    /// Removes buckets whose count is zero, so that equal distributions have
+    /// equal sketches.
+    fn normalize(&mut self) {
+        self.negatives.retain(|_, count| *count != 0);
+        self.positives.retain(|_, count| *count != 0);
+    }
+}
+
+/// The ratio between the bounds of a bucket.
+fn gamma() -> f64 {
+    (1.0 + ACCURACY) / (1.0 - ACCURACY)
+}
+
+/// Returns the bucket of a finite, positive value.
+fn bucket(value: f64) -> i32 {
+    let bucket = (value.ln() / gamma().ln()).ceil();
+    // The logarithm of a finite `f64` is at most about 710 in absolute value,
+    // so the bucket always fits.
+    i32::try_from(i64::try_cast_from(bucket).expect("bucket is integral"))
+        .expect("bucket fits in i32")
+}
+
+/// Returns the representative value of a bucket, which is within
+/// [`ACCURACY`] of every value in the bucket.
+fn bucket_value(bucket: i32) -> f64 {
+    let gamma = gamma();
+    2.0 * gamma.powi(bucket) / (gamma + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch(values: impl IntoIterator<Item = f64>) -> QuantileSketch {
+        let mut sketch = QuantileSketch::default();
+        for value in values {
+            sketch.plus_equals(&QuantileSketch::singleton(value, 1));
+        }
+        sketch
+    }
+
+    #[test]
+    fn test_quantile() {
+        let sketch = sketch((1..=10_000).map(f64::from));
+        for (fraction, expected) in [(0.0, 1.0), (0.5, 5_000.0), (0.9, 9_000.0), (1.0, 10_000.0)] {
+            let estimate = sketch.quantile(fraction).unwrap();
+            assert!(
+                (estimate - expected).abs() <= expected * ACCURACY,
+                "{estimate} vs {expected}"
+            );
+        }
+        let special = sketch([f64::NAN, f64::NEG_INFINITY, 0.0, -1.0, f64::INFINITY]);
+        assert_eq!(special.quantile(0.0), Some(f64::NEG_INFINITY));
+        assert_eq!(special.quantile(0.5), Some(0.0));
+        assert_eq!(special.quantile(0.75), Some(f64::INFINITY));
+        assert!(special.quantile(1.0).unwrap().is_nan());
+        assert_eq!(QuantileSketch::default().quantile(0.5), None);
+    }
+
+    #[test]
+    fn test_retract() {
+        let mut a = sketch([1.0, 2.0, 3.0]);
+        a.plus_equals(&sketch([4.0, 5.0]));
+        a.plus_equals(&QuantileSketch::singleton(5.0, 1).multiply(-1));
+        a.plus_equals(&QuantileSketch::singleton(4.0, -1));
+        assert_eq!(a, sketch([3.0, 2.0, 1.0]));
+        a.plus_equals(&sketch([1.0, 2.0, 3.0]).multiply(-1));
+        assert!(a.is_zero());
+    }
+}
@@ -11,12 +11,12 @@
 //! into canonical form.
 
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use mz_repr::{ColumnType, Datum, ScalarType};
 
 use crate::visit::Visit;
-use crate::{func, MirScalarExpr, UnaryFunc, VariadicFunc};
+use crate::{func, BinaryFunc, MirScalarExpr, UnaryFunc, VariadicFunc};
 
 /// Canonicalize equivalence classes of a join and expressions contained in them.
 ///
@@ -202,7 +202,8 @@ where
 /// predicates in the set. Finally, it sorts and deduplicates the predicates.
 ///
 /// Additionally, it also removes IS NOT NULL predicates if there is another
-/// null rejecting predicate for the same sub-expression.
+/// null rejecting predicate for the same sub-expression, and merges range
+/// predicates that compare the same sub-expression with literals.
 pub fn canonicalize_predicates(predicates: &mut Vec<MirScalarExpr>, column_types: &[ColumnType]) {
     // 1) Reduce each individual predicate.
     predicates.iter_mut().for_each(|p| p.reduce(column_types));
@@ -360,13 +361,149 @@ pub fn canonicalize_predicates(predicates: &mut Vec<MirScalarExpr>, column_types
         // Remove any predicates that have been reduced to "true"
         completed.retain(|p| !p.is_literal_true());
         *predicates = completed;
+        // 6) Merge range predicates on the same sub-expression.
+        merge_range_predicates(predicates, column_types);
     }
 
-    // 6) Sort and dedup predicates.
+    // 7) Sort and dedup predicates.
     predicates.sort_by(compare_predicates);
     predicates.dedup();
 }
 
+/// A bound on the values of an expression imposed by a range predicate.
+#[derive(Clone, Copy)]
+struct RangeBound<'a> {
+    /// The position of the predicate imposing the bound.
+    position: usize,
+    /// The bounding value.
+    value: Datum<'a>,
+    /// Whether `value` itself satisfies the predicate.
+    inclusive: bool,
+    /// Whether the predicate is an equality, which bounds from both sides.
+    equality: bool,
+}
+
+/// Merges predicates of the form `expr <op> literal` (or `literal <op> expr`)
+/// that bound the same `expr`.
+///
+/// Of all lower (resp. upper) bounds on `expr` only the tightest one is
+/// retained, where equalities bound `expr` from both sides. If the tightest
+/// bounds admit no value, the predicates are replaced by a single `false`.
+///
+/// This is sound because the comparison functions are implemented by the
+/// ordering of [`Datum`]s, and because predicates that evaluate to `NULL`
+/// reject rows just like predicates that evaluate to `false`.
+fn merge_range_predicates(predicates: &mut Vec<MirScalarExpr>, column_types: &[ColumnType]) {
+    // For each bounded expression, its tightest lower and upper bound, and the
+    // positions of all predicates that bound it.
+    let mut bounds =
+        BTreeMap::<&MirScalarExpr, (Option<RangeBound>, Option<RangeBound>, Vec<usize>)>::new();
+    for (position, predicate) in predicates.iter().enumerate() {
+        let (func, expr1, expr2) = match predicate {
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => (func, expr1, expr2),
+            _ => continue,
+        };
+        // Normalize to `expr <func> literal`, flipping the comparison if needed.
+        let (func, expr, literal) = match (expr1.is_literal(), expr2.is_literal()) {
+            (false, true) => (func.clone(), &**expr1, &**expr2),
+            (true, false) => match func {
+                BinaryFunc::Lt => (BinaryFunc::Gt, &**expr2, &**expr1),
+                BinaryFunc::Lte => (BinaryFunc::Gte, &**expr2, &**expr1),
+                BinaryFunc::Gt => (BinaryFunc::Lt, &**expr2, &**expr1),
+                BinaryFunc::Gte => (BinaryFunc::Lte, &**expr2, &**expr1),
+                func => (func.clone(), &**expr2, &**expr1),
+            },
+            _ => continue,
+        };
+        let value = match literal {
+            MirScalarExpr::Literal(Ok(row), typ)
+                if typ.scalar_type.base_eq(&expr.typ(column_types).scalar_type) =>
+            {
+                row.unpack_first()
+            }
+            _ => continue,
+        };
+        if value.is_null() {
+            continue;
+        }
+        let (lower, upper) = match func {
+            BinaryFunc::Eq => (true, true),
+            BinaryFunc::Gt | BinaryFunc::Gte => (true, false),
+            BinaryFunc::Lt | BinaryFunc::Lte => (false, true),
+            _ => continue,
+        };
+        let bound = RangeBound {
+            position,
+            value,
+            inclusive: matches!(func, BinaryFunc::Eq | BinaryFunc::Gte | BinaryFunc::Lte),
+            equality: matches!(func, BinaryFunc::Eq),
+        };
+
+        let (tightest_lower, tightest_upper, positions) = bounds.entry(expr).or_default();
+        positions.push(position);
+        if lower {
+            tighten(tightest_lower, bound, Ordering::Greater);
+        }
+        if upper {
+            tighten(tightest_upper, bound, Ordering::Less);
+        }
+    }
+
+    let mut redundant = BTreeSet::new();
+    let mut unsatisfiable = false;
+    for (lower, upper, positions) in bounds.into_values() {
+        if let (Some(lower), Some(upper)) = (lower, upper) {
+            unsatisfiable = match lower.value.cmp(&upper.value) {
+                Ordering::Less => false,
+                Ordering::Equal => !lower.inclusive || !upper.inclusive,
+                Ordering::Greater => true,
+            };
+            if unsatisfiable {
+                break;
+            }
+        }
+        let tightest = [lower, upper].map(|bound| bound.map(|bound| bound.position));
+        redundant.extend(
+            positions
+                .into_iter()
+                .filter(|position| !tightest.contains(&Some(*position))),
+        );
+    }
+
+    if unsatisfiable {
+        *predicates = vec![MirScalarExpr::literal_ok(Datum::False, ScalarType::Bool)];
+        return;
+    }
+    let mut position = 0;
+    predicates.retain(|_| {
+        position += 1;
+        !redundant.contains(&(position - 1))
+    });
+}
+
+/// Replaces `tightest` by `bound` if `bound` is tighter, i.e., if its value
+/// compares as `tighter` to the value of `tightest`.
+///
+/// Among bounds with the same value, exclusive bounds are tighter than
+/// inclusive ones, and equalities are preferred over other inclusive bounds.
+fn tighten<'a>(tightest: &mut Option<RangeBound<'a>>, bound: RangeBound<'a>, tighter: Ordering) {
+    let replace = match tightest {
+        None => true,
+        Some(tightest) => match bound.value.cmp(&tightest.value) {
+            Ordering::Equal => {
+                (tightest.inclusive && !bound.inclusive)
+                    || (tightest.inclusive == bound.inclusive
+                        && bound.equality
+                        && !tightest.equality)
+            }
+            ordering => ordering == tighter,
+        },
+    };
+    if replace {
+        *tightest = Some(bound);
+    }
+}
+
 /// Replace any matching subexpressions in `predicate`, and if `predicate` has
 /// changed, reduce it. Return whether `predicate` has changed.
 fn replace_subexpr_and_reduce(
@@ -25,7 +25,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use mz_lowertest::MzReflect;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::date::Date;
@@ -36,6 +36,9 @@ use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::adt::timestamp::TimestampLike;
 use mz_repr::{ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType};
 
+use crate::custom_aggregate::CustomAggregateHandle;
+use crate::hll;
+use crate::quantile::QuantileSketch;
 use crate::relation::{
     compare_columns, proto_aggregate_func, proto_aggregate_func::ProtoColumnOrders,
     proto_table_func, ColumnOrder, ProtoAggregateFunc, ProtoTableFunc, WindowFrame,
@@ -195,6 +198,17 @@ where
     }
 }
 
+fn max_record<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .max()
+        .unwrap_or(Datum::Null)
+}
+
 fn max_date<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -377,6 +391,17 @@ where
     }
 }
 
+fn min_record<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .min()
+        .unwrap_or(Datum::Null)
+}
+
 fn min_date<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -546,6 +571,15 @@ where
     Datum::from(x)
 }
 
+fn count_diffs<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    // Each row is presented once per unit of its (positive) multiplicity.
+    let x = i64::try_from(datums.into_iter().count()).expect("count fits in an i64");
+    Datum::from(x)
+}
+
 fn any<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -572,11 +606,118 @@ where
         })
 }
 
+/// Folds the non-null integer values with the bitwise operator `f`.
+///
+/// The values are widened to `i64` before applying `f`. Sign extension is
+/// preserved by bitwise operators, so the result always fits back into the
+/// input type.
+fn bitwise<'a, I>(datums: I, f: fn(i64, i64) -> i64) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .reduce(|a, b| match (a, b) {
+            (Datum::Int16(a), Datum::Int16(b)) => Datum::Int16(
+                i16::try_from(f(a.into(), b.into())).expect("bitwise result fits in an i16"),
+            ),
+            (Datum::Int32(a), Datum::Int32(b)) => Datum::Int32(
+                i32::try_from(f(a.into(), b.into())).expect("bitwise result fits in an i32"),
+            ),
+            (Datum::Int64(a), Datum::Int64(b)) => Datum::Int64(f(a, b)),
+            (a, b) => panic!("invalid arguments to bitwise aggregate: {:?}, {:?}", a, b),
+        })
+        .unwrap_or(Datum::Null)
+}
+
+fn custom_aggregate<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    handle: &CustomAggregateHandle,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let aggregate = handle.aggregate();
+    let mut state = handle.zero().clone();
+    for datum in datums.into_iter().filter(|d| !d.is_null()) {
+        aggregate.insert(&mut state, datum);
+    }
+    temp_storage.push_unary_row(aggregate.finalize(&state))
+}
+
+fn hll_union<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut sketches = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| d.unwrap_bytes());
+    match sketches.next() {
+        Some(first) => {
+            let sketch = sketches.fold(first.to_vec(), |acc, sketch| hll::merge(&acc, sketch));
+            Datum::Bytes(temp_storage.push_bytes(sketch))
+        }
+        None => Datum::Null,
+    }
+}
+
+fn percentile_cont<'a, I>(datums: I, fraction: f64) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut values = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| OrderedFloat(d.unwrap_float64()))
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return Datum::Null;
+    }
+    values.sort();
+    // Interpolate linearly between the two values closest to the requested
+    // position, as PostgreSQL does.
+    let last = f64::try_cast_from(u64::cast_from(values.len() - 1)).expect("group fits in f64");
+    let position = fraction * last;
+    let lower = position.floor();
+    let index = |p: f64| usize::cast_from(u64::try_cast_from(p).expect("position is in bounds"));
+    let lower_value = values[index(lower)].0;
+    let upper_value = values[index(position.ceil())].0;
+    Datum::from(lower_value + (upper_value - lower_value) * (position - lower))
+}
+
+fn approx_percentile<'a, I>(datums: I, fraction: f64) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut sketch = QuantileSketch::default();
+    for d in datums {
+        if !d.is_null() {
+            sketch.plus_equals(&QuantileSketch::singleton(d.unwrap_float64(), 1));
+        }
+    }
+    Datum::from(sketch.quantile(fraction))
+}
+
 fn string_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
-    const EMPTY_SEP: &str = "";
+    const EMPTY_SEP: &[u8] = b"";
+
+    // Values and separators are either all strings or all byte strings, so
+    // concatenate them as bytes and remember which of the two to return.
+    let mut is_string = true;
+    let mut as_bytes = |d: Datum<'a>| match d {
+        Datum::String(s) => s.as_bytes(),
+        Datum::Bytes(b) => {
+            is_string = false;
+            b
+        }
+        _ => unreachable!(),
+    };
 
     let datums = order_aggregate_datums(datums, order_by);
     let mut sep_value_pairs = datums.into_iter().filter_map(|d| {
@@ -586,26 +727,48 @@ where
         let mut value_sep = d.unwrap_list().iter();
         match (value_sep.next().unwrap(), value_sep.next().unwrap()) {
             (Datum::Null, _) => None,
-            (Datum::String(val), Datum::Null) => Some((EMPTY_SEP, val)),
-            (Datum::String(val), Datum::String(sep)) => Some((sep, val)),
-            _ => unreachable!(),
+            (val, Datum::Null) => Some((EMPTY_SEP, as_bytes(val))),
+            (val, sep) => Some((as_bytes(sep), as_bytes(val))),
         }
     });
 
-    let mut s = String::default();
+    let mut buf = Vec::new();
     match sep_value_pairs.next() {
         // First value not prefixed by its separator
-        Some((_, value)) => s.push_str(value),
+        Some((_, value)) => buf.extend_from_slice(value),
         // If no non-null values sent, return NULL.
         None => return Datum::Null,
     }
 
     for (sep, value) in sep_value_pairs {
-        s.push_str(sep);
-        s.push_str(value);
+        buf.extend_from_slice(sep);
+        buf.extend_from_slice(value);
     }
 
-    Datum::String(temp_storage.push_string(s))
+    if is_string {
+        let s = String::from_utf8(buf).expect("concatenation of strings is valid UTF-8");
+        Datum::String(temp_storage.push_string(s))
+    } else {
+        Datum::Bytes(temp_storage.push_bytes(buf))
+    }
+}
+
+fn first<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    order_aggregate_datums(datums, order_by)
+        .next()
+        .unwrap_or(Datum::Null)
+}
+
+fn last<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    order_aggregate_datums(datums, order_by)
+        .last()
+        .unwrap_or(Datum::Null)
 }
 
 fn jsonb_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
@@ -673,6 +836,9 @@ where
 
 // Assuming datums is a List, sort them by the 2nd through Nth elements
 // corresponding to order_by, then return the 1st element and computed order by expression.
+//
+// Ties are broken by the remaining order by elements and then by the 1st element,
+// so that the result does not depend on the order of the input.
 fn order_aggregate_datums_with_rank<'a, I>(
     datums: I,
     order_by: &[ColumnOrder],
@@ -692,12 +858,12 @@ where
 
     let mut left_datum_vec = mz_repr::DatumVec::new();
     let mut right_datum_vec = mz_repr::DatumVec::new();
-    let mut sort_by = |left: &(_, Row), right: &(_, Row)| {
-        let left = &left.1;
-        let right = &right.1;
-        let left_datums = left_datum_vec.borrow_with(left);
-        let right_datums = right_datum_vec.borrow_with(right);
-        compare_columns(order_by, &left_datums, &right_datums, || left.cmp(right))
+    let mut sort_by = |left: &(Datum, Row), right: &(Datum, Row)| {
+        let left_datums = left_datum_vec.borrow_with(&left.1);
+        let right_datums = right_datum_vec.borrow_with(&right.1);
+        compare_columns(order_by, &left_datums, &right_datums, || {
+            left.1.cmp(&right.1).then_with(|| left.0.cmp(&right.0))
+        })
     };
     rows.sort_by(&mut sort_by);
     rows.into_iter()
@@ -1102,6 +1268,9 @@ pub enum AggregateFunc {
     MaxDate,
     MaxTimestamp,
     MaxTimestampTz,
+    /// Computes the greatest record, comparing records field by field. Used to
+    /// implement `arg_max`.
+    MaxRecord,
     MinNumeric,
     MinInt16,
     MinInt32,
@@ -1117,6 +1286,9 @@ pub enum AggregateFunc {
     MinDate,
     MinTimestamp,
     MinTimestampTz,
+    /// Computes the least record, comparing records field by field. Used to
+    /// implement `arg_min`.
+    MinRecord,
     SumInt16,
     SumInt32,
     SumInt64,
@@ -1127,8 +1299,25 @@ pub enum AggregateFunc {
     SumFloat64,
     SumNumeric,
     Count,
+    /// Sums the multiplicities of the input rows, including any negative
+    /// multiplicities, which other aggregations reject or hide. Used to
+    /// debug plans that produce unexpected negative multiplicities.
+    CountDiffs,
     Any,
     All,
+    /// Like `All`, but ignores nulls, as the `bool_and` function does.
+    BoolAnd,
+    /// Like `Any`, but ignores nulls, as the `bool_or` function does.
+    BoolOr,
+    /// Computes the bitwise AND of `int2`, `int4` or `int8` values.
+    BitAnd,
+    /// Computes the bitwise OR of `int2`, `int4` or `int8` values.
+    BitOr,
+    /// Computes the bitwise XOR of `int2`, `int4` or `int8` values.
+    BitXor,
+    /// Merges HyperLogLog sketches, as produced by `mz_hll_sketch`, into a
+    /// single sketch. See [`crate::hll`].
+    HllUnion,
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
     /// into a JSON list. The other elements are columns used by `order_by`.
     ///
@@ -1160,6 +1349,27 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Accumulates `Datum::List`s of `ScalarType::Record` into the first field
+    /// of the first record according to `order_by` (the remaining fields are
+    /// used by `order_by`). Used to implement the `first_value` aggregate.
+    First {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `First`, but returns the first field of the last record.
+    Last {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the value at position `fraction` of the sorted values,
+    /// interpolating between adjacent values.
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+    },
+    /// Like `PercentileCont`, but computes an approximation of a value of the
+    /// requested rank from a [`QuantileSketch`], rather than sorting the
+    /// values.
+    ApproxPercentile {
+        fraction: OrderedFloat<f64>,
+    },
     RowNumber {
         order_by: Vec<ColumnOrder>,
     },
@@ -1178,6 +1388,12 @@ pub enum AggregateFunc {
         order_by: Vec<ColumnOrder>,
         window_frame: WindowFrame,
     },
+    /// An aggregate function defined outside of this crate. See
+    /// [`crate::custom_aggregate`].
+    Custom {
+        #[mzreflect(ignore)]
+        aggregate: CustomAggregateHandle,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -1199,7 +1415,7 @@ impl Arbitrary for AggregateFunc {
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
         use proptest::collection::vec;
         use proptest::prelude::any as proptest_any;
-        Union::new(vec![
+        let mut strategies = vec![
             Just(AggregateFunc::MaxNumeric).boxed(),
             Just(AggregateFunc::MaxInt16).boxed(),
             Just(AggregateFunc::MaxInt32).boxed(),
@@ -1215,6 +1431,7 @@ impl Arbitrary for AggregateFunc {
             Just(AggregateFunc::MaxTimestamp).boxed(),
             Just(AggregateFunc::MaxDate).boxed(),
             Just(AggregateFunc::MaxTimestampTz).boxed(),
+            Just(AggregateFunc::MaxRecord).boxed(),
             Just(AggregateFunc::MinNumeric).boxed(),
             Just(AggregateFunc::MinInt16).boxed(),
             Just(AggregateFunc::MinInt32).boxed(),
@@ -1230,6 +1447,7 @@ impl Arbitrary for AggregateFunc {
             Just(AggregateFunc::MinDate).boxed(),
             Just(AggregateFunc::MinTimestamp).boxed(),
             Just(AggregateFunc::MinTimestampTz).boxed(),
+            Just(AggregateFunc::MinRecord).boxed(),
             Just(AggregateFunc::SumInt16).boxed(),
             Just(AggregateFunc::SumInt32).boxed(),
             Just(AggregateFunc::SumInt64).boxed(),
@@ -1240,8 +1458,15 @@ impl Arbitrary for AggregateFunc {
             Just(AggregateFunc::SumFloat64).boxed(),
             Just(AggregateFunc::SumNumeric).boxed(),
             Just(AggregateFunc::Count).boxed(),
+            Just(AggregateFunc::CountDiffs).boxed(),
             Just(AggregateFunc::Any).boxed(),
             Just(AggregateFunc::All).boxed(),
+            Just(AggregateFunc::BoolAnd).boxed(),
+            Just(AggregateFunc::BoolOr).boxed(),
+            Just(AggregateFunc::BitAnd).boxed(),
+            Just(AggregateFunc::BitOr).boxed(),
+            Just(AggregateFunc::BitXor).boxed(),
+            Just(AggregateFunc::HllUnion).boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::JsonbAgg { order_by })
                 .boxed(),
@@ -1257,6 +1482,22 @@ impl Arbitrary for AggregateFunc {
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::StringAgg { order_by })
                 .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::First { order_by })
+                .boxed(),
+            vec(proptest_any::<ColumnOrder>(), 1..4)
+                .prop_map(|order_by| AggregateFunc::Last { order_by })
+                .boxed(),
+            (0.0..=1.0)
+                .prop_map(|fraction| AggregateFunc::PercentileCont {
+                    fraction: OrderedFloat(fraction),
+                })
+                .boxed(),
+            (0.0..=1.0)
+                .prop_map(|fraction| AggregateFunc::ApproxPercentile {
+                    fraction: OrderedFloat(fraction),
+                })
+                .boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::RowNumber { order_by })
                 .boxed(),
@@ -1288,7 +1529,20 @@ impl Arbitrary for AggregateFunc {
                 })
                 .boxed(),
             Just(AggregateFunc::Dummy).boxed(),
-        ])
+        ];
+        // Plans can only refer to registered custom aggregates.
+        let custom = crate::custom_aggregate::registered();
+        if !custom.is_empty() {
+            strategies.push(
+                proptest::sample::select(custom)
+                    .prop_map(|name| AggregateFunc::Custom {
+                        aggregate: CustomAggregateHandle::resolve(&name)
+                            .expect("custom aggregates are never unregistered"),
+                    })
+                    .boxed(),
+            );
+        }
+        Union::new(strategies)
     }
 }
 
@@ -1324,6 +1578,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::MaxDate => Kind::MaxDate(()),
                 AggregateFunc::MaxTimestamp => Kind::MaxTimestamp(()),
                 AggregateFunc::MaxTimestampTz => Kind::MaxTimestampTz(()),
+                AggregateFunc::MaxRecord => Kind::MaxRecord(()),
                 AggregateFunc::MinNumeric => Kind::MinNumeric(()),
                 AggregateFunc::MinInt16 => Kind::MinInt16(()),
                 AggregateFunc::MinInt32 => Kind::MinInt32(()),
@@ -1339,6 +1594,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::MinDate => Kind::MinDate(()),
                 AggregateFunc::MinTimestamp => Kind::MinTimestamp(()),
                 AggregateFunc::MinTimestampTz => Kind::MinTimestampTz(()),
+                AggregateFunc::MinRecord => Kind::MinRecord(()),
                 AggregateFunc::SumInt16 => Kind::SumInt16(()),
                 AggregateFunc::SumInt32 => Kind::SumInt32(()),
                 AggregateFunc::SumInt64 => Kind::SumInt64(()),
@@ -1349,8 +1605,15 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::SumFloat64 => Kind::SumFloat64(()),
                 AggregateFunc::SumNumeric => Kind::SumNumeric(()),
                 AggregateFunc::Count => Kind::Count(()),
+                AggregateFunc::CountDiffs => Kind::CountDiffs(()),
                 AggregateFunc::Any => Kind::Any(()),
                 AggregateFunc::All => Kind::All(()),
+                AggregateFunc::BoolAnd => Kind::BoolAnd(()),
+                AggregateFunc::BoolOr => Kind::BoolOr(()),
+                AggregateFunc::BitAnd => Kind::BitAnd(()),
+                AggregateFunc::BitOr => Kind::BitOr(()),
+                AggregateFunc::BitXor => Kind::BitXor(()),
+                AggregateFunc::HllUnion => Kind::HllUnion(()),
                 AggregateFunc::JsonbAgg { order_by } => Kind::JsonbAgg(order_by.into_proto()),
                 AggregateFunc::JsonbObjectAgg { order_by } => {
                     Kind::JsonbObjectAgg(order_by.into_proto())
@@ -1358,6 +1621,10 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::ArrayConcat { order_by } => Kind::ArrayConcat(order_by.into_proto()),
                 AggregateFunc::ListConcat { order_by } => Kind::ListConcat(order_by.into_proto()),
                 AggregateFunc::StringAgg { order_by } => Kind::StringAgg(order_by.into_proto()),
+                AggregateFunc::First { order_by } => Kind::First(order_by.into_proto()),
+                AggregateFunc::Last { order_by } => Kind::Last(order_by.into_proto()),
+                AggregateFunc::PercentileCont { fraction } => Kind::PercentileCont(fraction.0),
+                AggregateFunc::ApproxPercentile { fraction } => Kind::ApproxPercentile(fraction.0),
                 AggregateFunc::RowNumber { order_by } => Kind::RowNumber(order_by.into_proto()),
                 AggregateFunc::DenseRank { order_by } => Kind::DenseRank(order_by.into_proto()),
                 AggregateFunc::LagLead { order_by, lag_lead } => {
@@ -1387,6 +1654,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     order_by: Some(order_by.into_proto()),
                     window_frame: Some(window_frame.into_proto()),
                 }),
+                AggregateFunc::Custom { aggregate } => Kind::Custom(aggregate.name().into()),
                 AggregateFunc::Dummy => Kind::Dummy(()),
             }),
         }
@@ -1413,6 +1681,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::MaxDate(()) => AggregateFunc::MaxDate,
             Kind::MaxTimestamp(()) => AggregateFunc::MaxTimestamp,
             Kind::MaxTimestampTz(()) => AggregateFunc::MaxTimestampTz,
+            Kind::MaxRecord(()) => AggregateFunc::MaxRecord,
             Kind::MinNumeric(()) => AggregateFunc::MinNumeric,
             Kind::MinInt16(()) => AggregateFunc::MinInt16,
             Kind::MinInt32(()) => AggregateFunc::MinInt32,
@@ -1428,6 +1697,7 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::MinDate(()) => AggregateFunc::MinDate,
             Kind::MinTimestamp(()) => AggregateFunc::MinTimestamp,
             Kind::MinTimestampTz(()) => AggregateFunc::MinTimestampTz,
+            Kind::MinRecord(()) => AggregateFunc::MinRecord,
             Kind::SumInt16(()) => AggregateFunc::SumInt16,
             Kind::SumInt32(()) => AggregateFunc::SumInt32,
             Kind::SumInt64(()) => AggregateFunc::SumInt64,
@@ -1438,8 +1708,15 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::SumFloat64(()) => AggregateFunc::SumFloat64,
             Kind::SumNumeric(()) => AggregateFunc::SumNumeric,
             Kind::Count(()) => AggregateFunc::Count,
+            Kind::CountDiffs(()) => AggregateFunc::CountDiffs,
             Kind::Any(()) => AggregateFunc::Any,
             Kind::All(()) => AggregateFunc::All,
+            Kind::BoolAnd(()) => AggregateFunc::BoolAnd,
+            Kind::BoolOr(()) => AggregateFunc::BoolOr,
+            Kind::BitAnd(()) => AggregateFunc::BitAnd,
+            Kind::BitOr(()) => AggregateFunc::BitOr,
+            Kind::BitXor(()) => AggregateFunc::BitXor,
+            Kind::HllUnion(()) => AggregateFunc::HllUnion,
             Kind::JsonbAgg(order_by) => AggregateFunc::JsonbAgg {
                 order_by: order_by.into_rust()?,
             },
@@ -1455,6 +1732,18 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::StringAgg(order_by) => AggregateFunc::StringAgg {
                 order_by: order_by.into_rust()?,
             },
+            Kind::First(order_by) => AggregateFunc::First {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::Last(order_by) => AggregateFunc::Last {
+                order_by: order_by.into_rust()?,
+            },
+            Kind::PercentileCont(fraction) => AggregateFunc::PercentileCont {
+                fraction: OrderedFloat(fraction),
+            },
+            Kind::ApproxPercentile(fraction) => AggregateFunc::ApproxPercentile {
+                fraction: OrderedFloat(fraction),
+            },
             Kind::RowNumber(order_by) => AggregateFunc::RowNumber {
                 order_by: order_by.into_rust()?,
             },
@@ -1493,6 +1782,14 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                     .window_frame
                     .into_rust_if_some("ProtoWindowFrame::window_frame")?,
             },
+            Kind::Custom(name) => AggregateFunc::Custom {
+                aggregate: CustomAggregateHandle::resolve(&name).map_err(|e| {
+                    TryFromProtoError::UnknownEnumVariant(format!(
+                        "ProtoAggregateFunc::custom: {}",
+                        e
+                    ))
+                })?,
+            },
             Kind::Dummy(()) => AggregateFunc::Dummy,
         })
     }
@@ -1519,6 +1816,7 @@ impl AggregateFunc {
             AggregateFunc::MaxDate => max_date(datums),
             AggregateFunc::MaxTimestamp => max_timestamp(datums),
             AggregateFunc::MaxTimestampTz => max_timestamptz(datums),
+            AggregateFunc::MaxRecord => max_record(datums),
             AggregateFunc::MinNumeric => min_numeric(datums),
             AggregateFunc::MinInt16 => min_int16(datums),
             AggregateFunc::MinInt32 => min_int32(datums),
@@ -1534,6 +1832,7 @@ impl AggregateFunc {
             AggregateFunc::MinDate => min_date(datums),
             AggregateFunc::MinTimestamp => min_timestamp(datums),
             AggregateFunc::MinTimestampTz => min_timestamptz(datums),
+            AggregateFunc::MinRecord => min_record(datums),
             AggregateFunc::SumInt16 => sum_int16(datums),
             AggregateFunc::SumInt32 => sum_int32(datums),
             AggregateFunc::SumInt64 => sum_int64(datums),
@@ -1544,8 +1843,16 @@ impl AggregateFunc {
             AggregateFunc::SumFloat64 => sum_float64(datums),
             AggregateFunc::SumNumeric => sum_numeric(datums),
             AggregateFunc::Count => count(datums),
+            AggregateFunc::CountDiffs => count_diffs(datums),
             AggregateFunc::Any => any(datums),
             AggregateFunc::All => all(datums),
+            // Ignoring nulls, `false` is less than `true`.
+            AggregateFunc::BoolAnd => min_bool(datums),
+            AggregateFunc::BoolOr => max_bool(datums),
+            AggregateFunc::BitAnd => bitwise(datums, |a, b| a & b),
+            AggregateFunc::BitOr => bitwise(datums, |a, b| a | b),
+            AggregateFunc::BitXor => bitwise(datums, |a, b| a ^ b),
+            AggregateFunc::HllUnion => hll_union(datums, temp_storage),
             AggregateFunc::JsonbAgg { order_by } => jsonb_agg(datums, temp_storage, order_by),
             AggregateFunc::JsonbObjectAgg { order_by } => {
                 jsonb_object_agg(datums, temp_storage, order_by)
@@ -1553,6 +1860,10 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => array_concat(datums, temp_storage, order_by),
             AggregateFunc::ListConcat { order_by } => list_concat(datums, temp_storage, order_by),
             AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::First { order_by } => first(datums, order_by),
+            AggregateFunc::Last { order_by } => last(datums, order_by),
+            AggregateFunc::PercentileCont { fraction } => percentile_cont(datums, fraction.0),
+            AggregateFunc::ApproxPercentile { fraction } => approx_percentile(datums, fraction.0),
             AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
             AggregateFunc::DenseRank { order_by } => dense_rank(datums, temp_storage, order_by),
             AggregateFunc::LagLead {
@@ -1567,6 +1878,9 @@ impl AggregateFunc {
                 order_by,
                 window_frame,
             } => last_value(datums, temp_storage, order_by, window_frame),
+            AggregateFunc::Custom { aggregate } => {
+                custom_aggregate(datums, temp_storage, aggregate)
+            }
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -1575,7 +1889,7 @@ impl AggregateFunc {
     /// input relation.
     pub fn default(&self) -> Datum<'static> {
         match self {
-            AggregateFunc::Count => Datum::Int64(0),
+            AggregateFunc::Count | AggregateFunc::CountDiffs => Datum::Int64(0),
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
@@ -1607,8 +1921,11 @@ impl AggregateFunc {
     /// is (without further information) true for aggregations that are not
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        if let AggregateFunc::Custom { aggregate } = self {
+            return aggregate.aggregate().output_type(input_type);
+        }
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::CountDiffs => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
@@ -1630,7 +1947,22 @@ impl AggregateFunc {
                     _ => unreachable!(),
                 }
             }
-            AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
+                // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
+                ScalarType::Record { ref fields, .. } => match &fields[0].1.scalar_type {
+                    // The inner Record is a (value, separator) tuple
+                    ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            AggregateFunc::First { .. } | AggregateFunc::Last { .. } => {
+                match input_type.scalar_type {
+                    // The input is wrapped in a Record with the ORDER BY expressions, so extract it out.
+                    ScalarType::Record { ref fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                }
+            }
             AggregateFunc::RowNumber { .. } => match input_type.scalar_type {
                 ScalarType::Record { ref fields, .. } => ScalarType::List {
                     element_type: Box::new(ScalarType::Record {
@@ -1751,7 +2083,7 @@ impl AggregateFunc {
         // Count never produces null, and other aggregations only produce
         // null in the presence of null inputs.
         let nullable = match self {
-            AggregateFunc::Count => false,
+            AggregateFunc::Count | AggregateFunc::CountDiffs => false,
             // Use the nullability of the underlying column being aggregated, not the Records wrapping it
             AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
                 // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
@@ -1762,6 +2094,13 @@ impl AggregateFunc {
                 },
                 _ => unreachable!(),
             },
+            // The value may be null in any of the records
+            AggregateFunc::First { .. } | AggregateFunc::Last { .. } => {
+                match input_type.scalar_type {
+                    ScalarType::Record { fields, .. } => fields[0].1.nullable,
+                    _ => unreachable!(),
+                }
+            }
             _ => input_type.nullable,
         };
         scalar_type.nullable(nullable)
@@ -1788,6 +2127,7 @@ impl AggregateFunc {
             | AggregateFunc::MaxDate
             | AggregateFunc::MaxTimestamp
             | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MaxRecord
             | AggregateFunc::MinNumeric
             | AggregateFunc::MinInt16
             | AggregateFunc::MinInt32
@@ -1803,6 +2143,7 @@ impl AggregateFunc {
             | AggregateFunc::MinDate
             | AggregateFunc::MinTimestamp
             | AggregateFunc::MinTimestampTz
+            | AggregateFunc::MinRecord
             | AggregateFunc::SumInt16
             | AggregateFunc::SumInt32
             | AggregateFunc::SumInt64
@@ -1812,7 +2153,15 @@ impl AggregateFunc {
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
-            | AggregateFunc::StringAgg { .. } => true,
+            | AggregateFunc::BoolAnd
+            | AggregateFunc::BoolOr
+            | AggregateFunc::BitAnd
+            | AggregateFunc::BitOr
+            | AggregateFunc::BitXor
+            | AggregateFunc::HllUnion
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::ApproxPercentile { .. } => true,
             // Count is never null
             AggregateFunc::Count => false,
             _ => false,
@@ -2020,6 +2369,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::MaxDate => f.write_str("max"),
             AggregateFunc::MaxTimestamp => f.write_str("max"),
             AggregateFunc::MaxTimestampTz => f.write_str("max"),
+            AggregateFunc::MaxRecord => f.write_str("max"),
             AggregateFunc::MinNumeric => f.write_str("min"),
             AggregateFunc::MinInt16 => f.write_str("min"),
             AggregateFunc::MinInt32 => f.write_str("min"),
@@ -2035,6 +2385,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::MinDate => f.write_str("min"),
             AggregateFunc::MinTimestamp => f.write_str("min"),
             AggregateFunc::MinTimestampTz => f.write_str("min"),
+            AggregateFunc::MinRecord => f.write_str("min"),
             AggregateFunc::SumInt16 => f.write_str("sum"),
             AggregateFunc::SumInt32 => f.write_str("sum"),
             AggregateFunc::SumInt64 => f.write_str("sum"),
@@ -2045,13 +2396,24 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::SumFloat64 => f.write_str("sum"),
             AggregateFunc::SumNumeric => f.write_str("sum"),
             AggregateFunc::Count => f.write_str("count"),
+            AggregateFunc::CountDiffs => f.write_str("mz_count_diffs"),
             AggregateFunc::Any => f.write_str("any"),
             AggregateFunc::All => f.write_str("all"),
+            AggregateFunc::BoolAnd => f.write_str("bool_and"),
+            AggregateFunc::BoolOr => f.write_str("bool_or"),
+            AggregateFunc::BitAnd => f.write_str("bit_and"),
+            AggregateFunc::BitOr => f.write_str("bit_or"),
+            AggregateFunc::BitXor => f.write_str("bit_xor"),
+            AggregateFunc::HllUnion => f.write_str("mz_hll_union"),
             AggregateFunc::JsonbAgg { .. } => f.write_str("jsonb_agg"),
             AggregateFunc::JsonbObjectAgg { .. } => f.write_str("jsonb_object_agg"),
             AggregateFunc::ArrayConcat { .. } => f.write_str("array_agg"),
             AggregateFunc::ListConcat { .. } => f.write_str("list_agg"),
             AggregateFunc::StringAgg { .. } => f.write_str("string_agg"),
+            AggregateFunc::First { .. } => f.write_str("mz_first_value"),
+            AggregateFunc::Last { .. } => f.write_str("mz_last_value"),
+            AggregateFunc::PercentileCont { .. } => f.write_str("percentile_cont"),
+            AggregateFunc::ApproxPercentile { .. } => f.write_str("approx_percentile"),
             AggregateFunc::RowNumber { .. } => f.write_str("row_number"),
             AggregateFunc::DenseRank { .. } => f.write_str("dense_rank"),
             AggregateFunc::LagLead {
@@ -2064,6 +2426,7 @@ impl fmt::Display for AggregateFunc {
             } => f.write_str("lead"),
             AggregateFunc::FirstValue { .. } => f.write_str("first_value"),
             AggregateFunc::LastValue { .. } => f.write_str("last_value"),
+            AggregateFunc::Custom { aggregate } => f.write_str(aggregate.name()),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -2530,10 +2893,27 @@ impl fmt::Display for TableFunc {
 
 #[cfg(test)]
 mod tests {
-    use super::{AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc};
-    use mz_proto::protobuf_roundtrip;
+    use super::{
+        proto_aggregate_func, AggregateFunc, ProtoAggregateFunc, ProtoTableFunc, TableFunc,
+    };
+    use crate::custom_aggregate::tests::test_count;
+    use mz_proto::{protobuf_roundtrip, RustType};
     use proptest::prelude::*;
 
+    #[test]
+    fn custom_aggregate_protobuf_roundtrip() {
+        let expect = AggregateFunc::Custom {
+            aggregate: test_count(),
+        };
+        let actual = protobuf_roundtrip::<_, ProtoAggregateFunc>(&expect);
+        assert_eq!(actual.unwrap(), expect);
+
+        let unregistered = ProtoAggregateFunc {
+            kind: Some(proto_aggregate_func::Kind::Custom("test_missing".into())),
+        };
+        assert!(AggregateFunc::from_proto(unregistered).is_err());
+    }
+
     proptest! {
        #[test]
         fn aggregate_func_protobuf_roundtrip(expect in any::<AggregateFunc>() ) {
@@ -0,0 +1,157 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Rendering of [`MirRelationExpr`]s as Graphviz graphs.
+//!
+//! The output is a graph in the DOT language, meant for visual inspection of
+//! plans, e.g. by piping it into `dot -Tsvg`, or for attaching to bug reports.
+
+use std::fmt::Write;
+
+use mz_ore::str::{bracketed, separated};
+use mz_repr::RelationType;
+
+use crate::MirRelationExpr;
+
+impl MirRelationExpr {
+    /// Renders this expression as a Graphviz graph in the DOT language.
+    ///
+    /// Each operator is rendered as a node labeled with the operator type,
+    /// its arity, and its unique keys. Edges point from each input to the
+    /// operator that consumes it.
+    pub fn to_graphviz(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "digraph {{").unwrap();
+        writeln!(out, "    node [shape=box];").unwrap();
+
+        // The nodes of `post_order_vec` are in post-order, so the inputs of each
+        // node are on top of the stack (in order) by the time we visit it.
+        let mut stack: Vec<(usize, RelationType)> = Vec::new();
+        for (index, expr) in self.post_order_vec().into_iter().enumerate() {
+            let inputs = stack.split_off(stack.len() - expr.num_inputs());
+            let input_types = inputs
+                .iter()
+                .map(|(_, typ)| typ.clone())
+                .collect::<Vec<_>>();
+            let typ = expr.typ_with_input_types(&input_types);
+
+            let keys = typ
+                .keys
+                .iter()
+                .map(|key| bracketed("[", "]", separated(", ", key)).to_string());
+            let label = format!(
+                "{}\\narity: {}\\nkeys: {}",
+                escape(&operator_label(expr)),
+                typ.arity(),
+                bracketed("(", ")", separated(", ", keys)),
+            );
+            writeln!(out, "    n{} [label=\"{}\"];", index, label).unwrap();
+            for (input, _) in inputs {
+                writeln!(out, "    n{} -> n{};", input, index).unwrap();
+            }
+
+            stack.push((index, typ));
+        }
+
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+/// Describes the operator at the root of `expr`, excluding its inputs.
+fn operator_label(expr: &MirRelationExpr) -> String {
+    use MirRelationExpr::*;
+
+    match expr {
+        Constant { rows, .. } => match rows {
+            Ok(rows) => format!("Constant ({} rows)", rows.len()),
+            Err(e) => format!("Constant (error: {})", e),
+        },
+        Get { id, .. } => format!("Get {}", id),
+        Let { id, .. } => format!("Let {}", id),
+        LetRec { ids, .. } => format!("LetRec {}", separated(", ", ids)),
+        Project { outputs, .. } => format!("Project ({})", separated(", ", outputs)),
+        Map { scalars, .. } => format!("Map ({})", separated(", ", scalars)),
+        FlatMap { func, exprs, .. } => format!("FlatMap {}({})", func, separated(", ", exprs)),
+        Filter { predicates, .. } => format!("Filter ({})", separated(", ", predicates)),
+        Join { implementation, .. } => match implementation.name() {
+            Some(name) => format!("Join ({})", name),
+            None => "Join".to_string(),
+        },
+        Reduce {
+            group_key,
+            aggregates,
+            ..
+        } => {
+            if aggregates.is_empty() {
+                format!("Distinct ({})", separated(", ", group_key))
+            } else {
+                format!(
+                    "Reduce ({}) ({})",
+                    separated(", ", group_key),
+                    separated(", ", aggregates)
+                )
+            }
+        }
+        TopK {
+            group_key, limit, ..
+        } => match limit {
+            Some(limit) => format!("TopK ({}) limit={}", separated(", ", group_key), limit),
+            None => format!("TopK ({})", separated(", ", group_key)),
+        },
+        Negate { .. } => "Negate".to_string(),
+        Threshold { .. } => "Threshold".to_string(),
+        Union { .. } => "Union".to_string(),
+        ArrangeBy { keys, .. } => format!(
+            "ArrangeBy {}",
+            separated(
+                " ",
+                keys.iter()
+                    .map(|key| bracketed("(", ")", separated(", ", key)).to_string())
+            )
+        ),
+    }
+}
+
+/// Escapes `s` for use in a double-quoted DOT string.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{GlobalId, ScalarType};
+
+    use super::*;
+
+    #[test]
+    fn test_to_graphviz() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(false),
+            ScalarType::String.nullable(true),
+        ])
+        .with_key(vec![0]);
+        let expr = MirRelationExpr::global_get(GlobalId::User(1), typ)
+            .threshold()
+            .project(vec![1]);
+
+        assert_eq!(
+            expr.to_graphviz(),
+            "digraph {
+    node [shape=box];
+    n0 [label=\"Get u1\\narity: 2\\nkeys: ([0])\"];
+    n1 [label=\"Threshold\\narity: 2\\nkeys: ([0])\"];
+    n0 -> n1;
+    n2 [label=\"Project (1)\\narity: 1\\nkeys: ()\"];
+    n1 -> n2;
+}
+"
+        );
+    }
+}
@@ -99,7 +99,7 @@ impl JoinInputMapper {
     /// can remain unique.
     pub fn global_keys<'a, I>(
         &self,
-        mut local_keys: I,
+        local_keys: I,
         equivalences: &[Vec<MirScalarExpr>],
     ) -> Vec<Vec<usize>>
     where
@@ -110,54 +110,63 @@ impl JoinInputMapper {
         // a uniqueness constraint whose columns are used in join
         // constraints with relations prior in the sequence.
         //
-        // Currently, we only:
-        // 1. test for whether the uniqueness constraints for the first input will hold
-        // 2. try one sequence, namely the inputs in order
-        // 3. check that the column themselves are used in the join constraints
-        //    Technically uniqueness constraint would still hold if a 1-to-1
-        //    expression on a unique key is used in the join constraint.
-
-        // for inputs `1..self.total_inputs()`, store a set of columns from that
-        // input that exist in join constraints that have expressions belonging to
-        // earlier inputs.
-        let mut column_with_prior_bound_by_input = vec![HashSet::new(); self.total_inputs() - 1];
-        for equivalence in equivalences {
-            // do a scan to find the first input represented in the constraint
-            let min_bound_input = equivalence
-                .iter()
-                .flat_map(|expr| self.lookup_inputs(expr).max())
-                .min();
-            if let Some(min_bound_input) = min_bound_input {
-                for expr in equivalence {
-                    // then store all columns in the constraint that don't come
-                    // from the first input
-                    if let MirScalarExpr::Column(c) = expr {
-                        let (col, input) = self.map_column_to_local(*c);
-                        if input > min_bound_input {
-                            column_with_prior_bound_by_input[input - 1].insert(col);
-                        }
+        // For each input, we greedily grow such a sequence starting with that
+        // input, adding any input that has a uniqueness constraint whose columns
+        // are bound by the inputs already in the sequence (or by literals). If
+        // the sequence grows to contain all inputs, the keys of the starting
+        // input remain unique.
+        //
+        // Currently, we only check that the columns themselves are used in the
+        // join constraints. Technically uniqueness constraint would still hold
+        // if a 1-to-1 expression on a unique key is used in the join constraint.
+        let local_keys = local_keys.collect::<Vec<_>>();
+        let mut result = Vec::new();
+        for start in 0..self.total_inputs() {
+            let mut in_sequence = vec![false; self.total_inputs()];
+            in_sequence[start] = true;
+            let mut progress = true;
+            while progress {
+                progress = false;
+                for input in 0..self.total_inputs() {
+                    if !in_sequence[input]
+                        && local_keys[input].iter().any(|key| {
+                            key.iter().all(|k| {
+                                self.is_bound_by_inputs(*k, input, &in_sequence, equivalences)
+                            })
+                        })
+                    {
+                        in_sequence[input] = true;
+                        progress = true;
                     }
                 }
             }
-        }
-
-        if self.total_inputs() > 0 {
-            let first_input_keys = local_keys.next().unwrap().clone();
-            // for inputs `1..self.total_inputs()`, checks the keys belong to each
-            // input against the storage of columns that exist in join constraints
-            // that have expressions belonging to earlier inputs.
-            let remains_unique = local_keys.enumerate().all(|(index, keys)| {
-                keys.iter().any(|ks| {
-                    ks.iter()
-                        .all(|k| column_with_prior_bound_by_input[index].contains(k))
-                })
-            });
-
-            if remains_unique {
-                return first_input_keys;
+            if in_sequence.iter().all(|b| *b) {
+                result.extend(local_keys[start].iter().map(|key| {
+                    key.iter()
+                        .map(|k| self.map_column_to_global(*k, start))
+                        .collect::<Vec<_>>()
+                }));
             }
         }
-        vec![]
+        result
+    }
+
+    /// Returns whether the local `column` of input `index` is equated with an
+    /// expression that only references inputs for which `inputs` is true.
+    fn is_bound_by_inputs(
+        &self,
+        column: usize,
+        index: usize,
+        inputs: &[bool],
+        equivalences: &[Vec<MirScalarExpr>],
+    ) -> bool {
+        let column = MirScalarExpr::Column(self.map_column_to_global(column, index));
+        equivalences.iter().any(|equivalence| {
+            equivalence.contains(&column)
+                && equivalence
+                    .iter()
+                    .any(|expr| expr != &column && self.lookup_inputs(expr).all(|i| inputs[i]))
+        })
     }
 
     /// returns the arity for a particular input
@@ -582,4 +591,37 @@ mod tests {
             input_mapper.try_localize_to_input_with_bound_expr(&mut mutab, 1, &equivalences),
         )
     }
+
+    #[test]
+    fn global_keys_test() {
+        let input_mapper = JoinInputMapper::new_from_input_arities([2, 2, 1].into_iter());
+        let keys = vec![vec![vec![0]], vec![vec![0]], vec![]];
+
+        // The first input is joined on the key of the second input, whose key
+        // is not preserved, and the third input has no keys.
+        let equivalences = vec![vec![MirScalarExpr::Column(1), MirScalarExpr::Column(2)]];
+        assert_eq!(
+            input_mapper.global_keys(keys.iter(), &equivalences),
+            Vec::<Vec<usize>>::new(),
+        );
+
+        // Pinning the third input to a single row preserves the key of the
+        // first input.
+        let keys = vec![vec![vec![0]], vec![vec![0]], vec![vec![]]];
+        assert_eq!(
+            input_mapper.global_keys(keys.iter(), &equivalences),
+            vec![vec![0]],
+        );
+
+        // Binding the foreign key of the second input to the key of the first
+        // input preserves the keys of both inputs.
+        let equivalences = vec![
+            vec![MirScalarExpr::Column(1), MirScalarExpr::Column(2)],
+            vec![MirScalarExpr::Column(0), MirScalarExpr::Column(3)],
+        ];
+        assert_eq!(
+            input_mapper.global_keys(keys.iter(), &equivalences),
+            vec![vec![0], vec![2]],
+        );
+    }
 }
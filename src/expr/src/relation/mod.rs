@@ -10,7 +10,7 @@
 #![warn(missing_docs)]
 
 use std::cmp::{max, Ordering};
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt;
 use std::num::NonZeroUsize;
 
@@ -42,7 +42,9 @@ use self::func::{AggregateFunc, LagLeadType, TableFunc};
 
 pub mod canonicalize;
 pub mod func;
+mod graphviz;
 pub mod join_input_mapper;
+pub mod provenance;
 
 include!(concat!(env!("OUT_DIR"), "/mz_expr.relation.rs"));
 
@@ -242,6 +244,9 @@ pub enum MirRelationExpr {
         /// True iff the input is known to monotonically increase (only addition of records).
         #[serde(default)]
         monotonic: bool,
+        /// User hint: expected number of values per group key. Used to optimize physical rendering.
+        #[serde(default)]
+        expected_group_size: Option<usize>,
     },
     /// Return a dataflow where the row counts are negated
     ///
@@ -428,9 +433,23 @@ impl MirRelationExpr {
                 }
                 result
             }
-            // Iterating and cloning types inside the flat_map() avoids allocating Vec<>,
-            // as clones are directly added to column_types Vec<>.
-            Join { .. } => input_types.flat_map(|cols| cols.to_owned()).collect(),
+            Join { equivalences, .. } => {
+                // Iterating and cloning types inside the flat_map() avoids allocating Vec<>,
+                // as clones are directly added to column_types Vec<>.
+                let mut result: Vec<_> = input_types.flat_map(|cols| cols.to_owned()).collect();
+                // Augment non-nullability of columns that are equated with a
+                // non-nullable expression, as they must take its (non-null) value.
+                for equivalence in equivalences {
+                    if equivalence.iter().any(|expr| !expr.typ(&result).nullable) {
+                        for expr in equivalence {
+                            if let MirScalarExpr::Column(c) = expr {
+                                result[*c].nullable = false;
+                            }
+                        }
+                    }
+                }
+                result
+            }
             Reduce {
                 group_key,
                 aggregates,
@@ -1204,6 +1223,39 @@ impl MirRelationExpr {
         }
     }
 
+    /// Reduce the collection like [`MirRelationExpr::reduce`], but ensure that
+    /// each row of `keys` is present as a group in the output.
+    ///
+    /// Groups of `keys` that receive no input records are populated with the
+    /// default value of each aggregate (e.g. `0` for `COUNT`, `NULL` for `SUM`).
+    /// The columns of `keys` must correspond to the columns of `group_key`, in
+    /// order. Planners choose the key universe: scalar aggregates use the outer
+    /// relation of a (possibly trivial) correlated context, whereas grouped
+    /// aggregates may supply any relation that enumerates the groups that
+    /// should always be reported.
+    pub fn reduce_or_default(
+        self,
+        id_gen: &mut IdGen,
+        keys: MirRelationExpr,
+        group_key: Vec<usize>,
+        aggregates: Vec<AggregateExpr>,
+        expected_group_size: Option<usize>,
+    ) -> Self {
+        assert_eq!(keys.arity(), group_key.len());
+        let input_type = self.typ();
+        let default = aggregates
+            .iter()
+            .map(|agg| {
+                (
+                    agg.func.default(),
+                    agg.typ(&input_type.column_types).scalar_type,
+                )
+            })
+            .collect();
+        let reduced = self.reduce(group_key, aggregates, expected_group_size);
+        keys.lookup(id_gen, reduced, default)
+    }
+
     /// Perform a key-wise reduction order by and limit.
     ///
     /// The `group_key` argument indicates columns in the input collection that should
@@ -1216,6 +1268,7 @@ impl MirRelationExpr {
         order_key: Vec<ColumnOrder>,
         limit: Option<usize>,
         offset: usize,
+        expected_group_size: Option<usize>,
     ) -> Self {
         MirRelationExpr::TopK {
             input: Box::new(self),
@@ -1224,6 +1277,7 @@ impl MirRelationExpr {
             limit,
             offset,
             monotonic: false,
+            expected_group_size,
         }
     }
 
@@ -1549,7 +1603,9 @@ impl MirRelationExpr {
                     f(s)?;
                 }
                 for agg in aggregates {
-                    f(&mut agg.expr)?;
+                    for expr in agg.exprs_mut() {
+                        f(expr)?;
+                    }
                 }
             }
             Constant { .. }
@@ -1637,7 +1693,9 @@ impl MirRelationExpr {
                     f(s)?;
                 }
                 for agg in aggregates {
-                    f(&agg.expr)?;
+                    for expr in agg.exprs() {
+                        f(expr)?;
+                    }
                 }
             }
             Constant { .. }
@@ -1705,6 +1763,88 @@ impl MirRelationExpr {
         dfs(self, &mut size, &mut max_depth, 1);
         (size, max_depth)
     }
+
+    /// Computes a structural hash of the expression that does not depend on
+    /// the particular `LocalId`s chosen for `Let` and `LetRec` bindings.
+    ///
+    /// Two expressions that differ only in the names of their local bindings
+    /// have the same fingerprint, which allows callers to detect that a newly
+    /// planned collection is identical to an existing one. Distinct
+    /// fingerprints imply distinct expressions, but equal fingerprints should
+    /// be confirmed with [`MirRelationExpr::eq_modulo_local_ids`] before
+    /// reusing work.
+    ///
+    /// The fingerprint is only stable within a single build of Materialize,
+    /// and must not be persisted.
+    pub fn fingerprint(&self) -> u64 {
+        mz_ore::hash::hash(&self.with_canonical_local_ids())
+    }
+
+    /// Reports whether `self` and `other` are identical up to the choice of
+    /// `LocalId`s for their `Let` and `LetRec` bindings.
+    pub fn eq_modulo_local_ids(&self, other: &MirRelationExpr) -> bool {
+        self.with_canonical_local_ids() == other.with_canonical_local_ids()
+    }
+
+    /// Returns a copy of `self` where local bindings are renumbered from zero
+    /// in the order in which they are encountered.
+    fn with_canonical_local_ids(&self) -> MirRelationExpr {
+        fn renumber(
+            expr: &mut MirRelationExpr,
+            remap: &mut BTreeMap<LocalId, LocalId>,
+            next_id: &mut u64,
+        ) {
+            mz_ore::stack::maybe_grow(|| match expr {
+                MirRelationExpr::Let { id, value, body } => {
+                    renumber(value, remap, next_id);
+                    let old_id = *id;
+                    *id = LocalId::new(*next_id);
+                    *next_id += 1;
+                    let shadowed = remap.insert(old_id, *id);
+                    renumber(body, remap, next_id);
+                    match shadowed {
+                        Some(shadowed) => remap.insert(old_id, shadowed),
+                        None => remap.remove(&old_id),
+                    };
+                }
+                MirRelationExpr::LetRec { ids, values, body } => {
+                    let mut shadowed = Vec::with_capacity(ids.len());
+                    for id in ids.iter_mut() {
+                        let old_id = *id;
+                        *id = LocalId::new(*next_id);
+                        *next_id += 1;
+                        shadowed.push((old_id, remap.insert(old_id, *id)));
+                    }
+                    for value in values.iter_mut() {
+                        renumber(value, remap, next_id);
+                    }
+                    renumber(body, remap, next_id);
+                    for (old_id, shadowed) in shadowed.into_iter().rev() {
+                        match shadowed {
+                            Some(shadowed) => remap.insert(old_id, shadowed),
+                            None => remap.remove(&old_id),
+                        };
+                    }
+                }
+                MirRelationExpr::Get {
+                    id: Id::Local(id), ..
+                } => {
+                    if let Some(new_id) = remap.get(&*id) {
+                        *id = *new_id;
+                    }
+                }
+                _ => {
+                    for child in expr.children_mut() {
+                        renumber(child, remap, next_id);
+                    }
+                }
+            })
+        }
+
+        let mut expr = self.clone();
+        renumber(&mut expr, &mut BTreeMap::new(), &mut 0);
+        expr
+    }
 }
 
 // Temporary implementation for working with `LetRec`.
@@ -1999,6 +2139,13 @@ pub struct AggregateExpr {
     /// Should the aggregation be applied only to distinct results in each group.
     #[serde(default)]
     pub distinct: bool,
+    /// An optional predicate that restricts the rows that are fed to `func`.
+    ///
+    /// Rows for which the predicate does not evaluate to true do not
+    /// contribute to the aggregation. If no rows of a group contribute, the
+    /// aggregation produces [`AggregateFunc::default`].
+    #[serde(default)]
+    pub filter: Option<MirScalarExpr>,
 }
 
 impl RustType<ProtoAggregateExpr> for AggregateExpr {
@@ -2007,6 +2154,7 @@ impl RustType<ProtoAggregateExpr> for AggregateExpr {
             func: Some(self.func.into_proto()),
             expr: Some(self.expr.into_proto()),
             distinct: self.distinct,
+            filter: self.filter.into_proto(),
         }
     }
 
@@ -2015,6 +2163,7 @@ impl RustType<ProtoAggregateExpr> for AggregateExpr {
             func: proto.func.into_rust_if_some("ProtoAggregateExpr::func")?,
             expr: proto.expr.into_rust_if_some("ProtoAggregateExpr::expr")?,
             distinct: proto.distinct,
+            filter: proto.filter.into_rust()?,
         })
     }
 }
@@ -2022,11 +2171,53 @@ impl RustType<ProtoAggregateExpr> for AggregateExpr {
 impl AggregateExpr {
     /// Computes the type of this `AggregateExpr`.
     pub fn typ(&self, column_types: &[ColumnType]) -> ColumnType {
-        self.func.output_type(self.expr.typ(column_types))
+        let mut typ = self.func.output_type(self.expr.typ(column_types));
+        // If the filter rejects all rows of a group, the result is the default.
+        if self.filter.is_some() && self.func.default().is_null() {
+            typ.nullable = true;
+        }
+        typ
+    }
+
+    /// Iterates over the scalar expressions of this `AggregateExpr`: the
+    /// aggregated expression, followed by the filter, if any.
+    pub fn exprs(&self) -> impl Iterator<Item = &MirScalarExpr> {
+        std::iter::once(&self.expr).chain(self.filter.as_ref())
+    }
+
+    /// Mutably iterates over the scalar expressions of this `AggregateExpr`:
+    /// the aggregated expression, followed by the filter, if any.
+    pub fn exprs_mut(&mut self) -> impl Iterator<Item = &mut MirScalarExpr> {
+        std::iter::once(&mut self.expr).chain(self.filter.as_mut())
+    }
+
+    /// Reports the input columns referenced by the aggregated expression or
+    /// by the filter.
+    pub fn support(&self) -> HashSet<usize> {
+        self.exprs().flat_map(|e| e.support()).collect()
+    }
+
+    /// Returns the aggregated expression with the filter folded into it.
+    ///
+    /// Rows rejected by the filter are mapped to `rejected` instead. The
+    /// expressions are typed against `column_types`.
+    pub fn filtered_expr(&self, rejected: Datum, column_types: &[ColumnType]) -> MirScalarExpr {
+        match &self.filter {
+            None => self.expr.clone(),
+            Some(filter) => {
+                let typ = self.expr.typ(column_types).scalar_type;
+                filter
+                    .clone()
+                    .if_then_else(self.expr.clone(), MirScalarExpr::literal_ok(rejected, typ))
+            }
+        }
     }
 
     /// Returns whether the expression has a constant result.
     pub fn is_constant(&self) -> bool {
+        if self.filter.is_some() {
+            return false;
+        }
         match self.func {
             AggregateFunc::MaxInt16
             | AggregateFunc::MaxInt32
@@ -2042,6 +2233,7 @@ impl AggregateExpr {
             | AggregateFunc::MaxDate
             | AggregateFunc::MaxTimestamp
             | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MaxRecord
             | AggregateFunc::MinInt16
             | AggregateFunc::MinInt32
             | AggregateFunc::MinInt64
@@ -2056,8 +2248,14 @@ impl AggregateExpr {
             | AggregateFunc::MinDate
             | AggregateFunc::MinTimestamp
             | AggregateFunc::MinTimestampTz
+            | AggregateFunc::MinRecord
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::BoolAnd
+            | AggregateFunc::BoolOr
+            | AggregateFunc::BitAnd
+            | AggregateFunc::BitOr
+            | AggregateFunc::HllUnion
             | AggregateFunc::Dummy => self.expr.is_literal(),
             AggregateFunc::Count => self.expr.is_literal_null(),
             _ => self.expr.is_literal_err(),
@@ -2065,7 +2263,20 @@ impl AggregateExpr {
     }
 
     /// Extracts unique input from aggregate type
+    ///
+    /// Must only be called if [`AggregateExpr::supports_on_unique`] holds.
     pub fn on_unique(&self, input_type: &[ColumnType]) -> MirScalarExpr {
+        if let Some(filter) = &self.filter {
+            // A rejected row leaves the aggregation without input.
+            let unfiltered = AggregateExpr {
+                filter: None,
+                ..self.clone()
+            };
+            return filter.clone().if_then_else(
+                unfiltered.on_unique(input_type),
+                MirScalarExpr::literal_ok(self.func.default(), self.typ(input_type).scalar_type),
+            );
+        }
         match &self.func {
             // Count is one if non-null, and zero if null.
             AggregateFunc::Count => self
@@ -2145,6 +2356,12 @@ impl AggregateExpr {
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)))
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
+            // First and Last take a single level of records and output the value
+            AggregateFunc::First { .. } | AggregateFunc::Last { .. } => self
+                .expr
+                .clone()
+                .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
+
             // ListConcat and ArrayConcat take a single level of records and output a list containing exactly 1 element
             AggregateFunc::ListConcat { .. } | AggregateFunc::ArrayConcat { .. } => self
                 .expr
@@ -2405,6 +2622,7 @@ impl AggregateExpr {
             | AggregateFunc::MaxDate
             | AggregateFunc::MaxTimestamp
             | AggregateFunc::MaxTimestampTz
+            | AggregateFunc::MaxRecord
             | AggregateFunc::MinNumeric
             | AggregateFunc::MinInt16
             | AggregateFunc::MinInt32
@@ -2420,15 +2638,40 @@ impl AggregateExpr {
             | AggregateFunc::MinDate
             | AggregateFunc::MinTimestamp
             | AggregateFunc::MinTimestampTz
+            | AggregateFunc::MinRecord
             | AggregateFunc::SumFloat32
             | AggregateFunc::SumFloat64
             | AggregateFunc::SumNumeric
             | AggregateFunc::Any
             | AggregateFunc::All
+            | AggregateFunc::BoolAnd
+            | AggregateFunc::BoolOr
+            | AggregateFunc::BitAnd
+            | AggregateFunc::BitOr
+            | AggregateFunc::BitXor
+            | AggregateFunc::HllUnion
             | AggregateFunc::Dummy => self.expr.clone(),
+
+            AggregateFunc::CountDiffs => unreachable!("mz_count_diffs has no scalar equivalent"),
+            AggregateFunc::Custom { aggregate } => unreachable!(
+                "custom aggregate {} has no scalar equivalent",
+                aggregate.name()
+            ),
         }
     }
 
+    /// Returns whether [`AggregateExpr::on_unique`] can express the result of
+    /// the aggregation over a single row.
+    pub fn supports_on_unique(&self) -> bool {
+        // `CountDiffs` must not assume that unique rows have a multiplicity
+        // of one, as it is meant to detect rows for which this is not the
+        // case.
+        !matches!(
+            self.func,
+            AggregateFunc::CountDiffs | AggregateFunc::Custom { .. }
+        )
+    }
+
     /// Returns whether the expression is COUNT(*) or not.  Note that
     /// when we define the count builtin in sql::func, we convert
     /// COUNT(*) to COUNT(true), making it indistinguishable from
@@ -2461,7 +2704,11 @@ impl fmt::Display for AggregateExpr {
             self.func,
             if self.distinct { "distinct " } else { "" },
             self.expr
-        )
+        )?;
+        if let Some(filter) = &self.filter {
+            write!(f, " filter (where {})", filter)?;
+        }
+        Ok(())
     }
 }
 
@@ -3006,6 +3253,31 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_join_equivalence_nullability() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(false),
+            ScalarType::Int64.nullable(true),
+        ]);
+        let a = MirRelationExpr::global_get(GlobalId::User(1), typ.clone());
+        let b = MirRelationExpr::global_get(GlobalId::User(2), typ);
+
+        // Columns equated with a non-nullable column are non-nullable.
+        let join = MirRelationExpr::join(vec![a.clone(), b.clone()], vec![vec![(0, 0), (1, 1)]]);
+        let nullable = |expr: &MirRelationExpr| {
+            expr.typ()
+                .column_types
+                .iter()
+                .map(|t| t.nullable)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(nullable(&join), vec![false, true, false, false]);
+
+        // Equating only nullable columns leaves them nullable.
+        let join = MirRelationExpr::join(vec![a, b], vec![vec![(0, 1), (1, 1)]]);
+        assert_eq!(nullable(&join), vec![false, true, false, true]);
+    }
+
     proptest! {
         #[test]
         fn column_order_protobuf_roundtrip(expect in any::<ColumnOrder>()) {
@@ -3079,4 +3351,81 @@ mod tests {
 
         assert_eq!(act, exp);
     }
+
+    #[test]
+    fn test_fingerprint_ignores_local_ids() {
+        let typ = RelationType::new(vec![ScalarType::Int64.nullable(false)]);
+        let source = MirRelationExpr::global_get(GlobalId::User(1), typ.clone());
+        let bind = |id: u64, value: MirRelationExpr| {
+            let id = LocalId::new(id);
+            let get = MirRelationExpr::Get {
+                id: Id::Local(id),
+                typ: typ.clone(),
+            };
+            MirRelationExpr::Let {
+                id,
+                value: Box::new(value),
+                body: Box::new(get.clone().union(get)),
+            }
+        };
+
+        let a = bind(3, source.clone());
+        let b = bind(17, source.clone());
+        assert_ne!(a, b);
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert!(a.eq_modulo_local_ids(&b));
+
+        let c = bind(3, source.negate());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert!(!a.eq_modulo_local_ids(&c));
+    }
+
+    #[test]
+    fn test_reduce_or_default() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(false),
+            ScalarType::Int64.nullable(true),
+        ]);
+        let input = MirRelationExpr::global_get(GlobalId::User(1), typ);
+        let keys = MirRelationExpr::global_get(
+            GlobalId::User(2),
+            RelationType::new(vec![ScalarType::Int64.nullable(false)]),
+        );
+        let aggregates = vec![
+            AggregateExpr {
+                func: AggregateFunc::Count,
+                expr: MirScalarExpr::Column(1),
+                distinct: false,
+                filter: None,
+            },
+            AggregateExpr {
+                func: AggregateFunc::MaxInt64,
+                expr: MirScalarExpr::Column(1),
+                distinct: false,
+                filter: None,
+            },
+        ];
+        let mut id_gen = IdGen::default();
+        let reduced = input.reduce_or_default(&mut id_gen, keys, vec![0], aggregates, None);
+        assert_eq!(
+            reduced.typ().column_types,
+            vec![
+                ScalarType::Int64.nullable(false),
+                ScalarType::Int64.nullable(false),
+                ScalarType::Int64.nullable(true),
+            ]
+        );
+
+        // Groups without input records report a count of zero and a null maximum.
+        let mut defaults = Vec::new();
+        reduced.visit_pre(|expr| {
+            if let MirRelationExpr::Constant { rows: Ok(rows), .. } = expr {
+                defaults.extend(rows.iter().map(|(row, _diff)| row.clone()));
+            }
+        });
+        assert_eq!(
+            defaults,
+            vec![Row::pack_slice(&[Datum::Int64(0), Datum::Null])]
+        );
+    }
 }
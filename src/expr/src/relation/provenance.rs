@@ -0,0 +1,276 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracking the provenance of output columns through relation expressions.
+//!
+//! The [`ColumnProvenance`] of an output column describes how the column is
+//! derived from the columns of the collections the expression reads, including
+//! the scalar expressions, table functions, and aggregates applied along the
+//! way. It is meant to support lineage tooling, e.g. to determine which field
+//! of a source feeds a particular column of a sink.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    AggregateExpr, Id, JoinInputMapper, LocalId, MirRelationExpr, MirScalarExpr, TableFunc,
+};
+
+/// Describes how a single column of a relation is derived.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ColumnProvenance {
+    /// The column is read from a column of a collection that is not defined
+    /// by the expression itself.
+    ///
+    /// Local identifiers are only reported when they refer to a recursive
+    /// binding, or to a binding that is not in scope.
+    Get {
+        /// The identifier of the collection.
+        id: Id,
+        /// The column of the collection.
+        column: usize,
+    },
+    /// The column is a column of a constant collection.
+    Constant,
+    /// The column is the result of a scalar expression.
+    Scalar {
+        /// The scalar expression, in terms of the columns of its input.
+        expr: MirScalarExpr,
+        /// The provenance of each input column referenced by `expr`.
+        inputs: BTreeMap<usize, ColumnProvenance>,
+    },
+    /// The column is one of the outputs of a table function.
+    TableFunc {
+        /// The table function.
+        func: TableFunc,
+        /// The arguments of the table function.
+        exprs: Vec<MirScalarExpr>,
+        /// The provenance of each input column referenced by `exprs`.
+        inputs: BTreeMap<usize, ColumnProvenance>,
+    },
+    /// The column is the result of an aggregation.
+    Aggregate {
+        /// The aggregate expression, in terms of the columns of its input.
+        aggregate: AggregateExpr,
+        /// The provenance of each input column referenced by `aggregate`.
+        inputs: BTreeMap<usize, ColumnProvenance>,
+    },
+    /// The column is the union of the corresponding columns of several inputs.
+    Union(Vec<ColumnProvenance>),
+}
+
+impl ColumnProvenance {
+    /// Reports the collection columns from which the column is derived.
+    pub fn sources(&self) -> BTreeSet<(Id, usize)> {
+        let mut sources = BTreeSet::new();
+        self.sources_into(&mut sources);
+        sources
+    }
+
+    fn sources_into(&self, sources: &mut BTreeSet<(Id, usize)>) {
+        match self {
+            ColumnProvenance::Get { id, column } => {
+                sources.insert((*id, *column));
+            }
+            ColumnProvenance::Constant => {}
+            ColumnProvenance::Scalar { inputs, .. }
+            | ColumnProvenance::TableFunc { inputs, .. }
+            | ColumnProvenance::Aggregate { inputs, .. } => {
+                for input in inputs.values() {
+                    input.sources_into(sources);
+                }
+            }
+            ColumnProvenance::Union(inputs) => {
+                for input in inputs {
+                    input.sources_into(sources);
+                }
+            }
+        }
+    }
+}
+
+impl MirRelationExpr {
+    /// Reports the provenance of the output column `column`.
+    ///
+    /// The provenance is traced through `Let` bindings, but not through the
+    /// bindings of a `LetRec`, which are reported as [`ColumnProvenance::Get`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `column` is not less than the arity of `self`.
+    pub fn column_provenance(&self, column: usize) -> ColumnProvenance {
+        provenance(self, column, &BTreeMap::new())
+    }
+}
+
+fn provenance<'a>(
+    expr: &'a MirRelationExpr,
+    column: usize,
+    bindings: &BTreeMap<LocalId, &'a MirRelationExpr>,
+) -> ColumnProvenance {
+    mz_ore::stack::maybe_grow(|| match expr {
+        MirRelationExpr::Constant { .. } => ColumnProvenance::Constant,
+        MirRelationExpr::Get { id, typ } => {
+            assert!(column < typ.arity(), "column {} out of bounds", column);
+            match id {
+                Id::Local(local_id) if bindings.contains_key(local_id) => {
+                    provenance(bindings[local_id], column, bindings)
+                }
+                _ => ColumnProvenance::Get { id: *id, column },
+            }
+        }
+        MirRelationExpr::Let { id, value, body } => {
+            let mut bindings = bindings.clone();
+            bindings.insert(*id, &**value);
+            provenance(body, column, &bindings)
+        }
+        MirRelationExpr::LetRec { ids, body, .. } => {
+            // Recursive bindings are opaque, and shadow any outer bindings.
+            let mut bindings = bindings.clone();
+            for id in ids {
+                bindings.remove(id);
+            }
+            provenance(body, column, &bindings)
+        }
+        MirRelationExpr::Project { input, outputs } => provenance(input, outputs[column], bindings),
+        MirRelationExpr::Map { input, scalars } => {
+            let arity = input.arity();
+            if column < arity {
+                provenance(input, column, bindings)
+            } else {
+                // Scalars may reference prior scalars, which are columns of `expr` itself.
+                let scalar = &scalars[column - arity];
+                ColumnProvenance::Scalar {
+                    expr: scalar.clone(),
+                    inputs: support_provenance(expr, scalar.support(), bindings),
+                }
+            }
+        }
+        MirRelationExpr::FlatMap { input, func, exprs } => {
+            let arity = input.arity();
+            if column < arity {
+                provenance(input, column, bindings)
+            } else {
+                let support = exprs.iter().flat_map(|e| e.support());
+                ColumnProvenance::TableFunc {
+                    func: func.clone(),
+                    exprs: exprs.clone(),
+                    inputs: support_provenance(input, support, bindings),
+                }
+            }
+        }
+        MirRelationExpr::Filter { input, .. }
+        | MirRelationExpr::TopK { input, .. }
+        | MirRelationExpr::Negate { input }
+        | MirRelationExpr::Threshold { input }
+        | MirRelationExpr::ArrangeBy { input, .. } => provenance(input, column, bindings),
+        MirRelationExpr::Join { inputs, .. } => {
+            let input_mapper = JoinInputMapper::new(inputs);
+            let (local_column, index) = input_mapper.map_column_to_local(column);
+            provenance(&inputs[index], local_column, bindings)
+        }
+        MirRelationExpr::Reduce {
+            input,
+            group_key,
+            aggregates,
+            ..
+        } => {
+            if column < group_key.len() {
+                let key = &group_key[column];
+                match key {
+                    MirScalarExpr::Column(c) => provenance(input, *c, bindings),
+                    _ => ColumnProvenance::Scalar {
+                        expr: key.clone(),
+                        inputs: support_provenance(input, key.support(), bindings),
+                    },
+                }
+            } else {
+                let aggregate = &aggregates[column - group_key.len()];
+                ColumnProvenance::Aggregate {
+                    aggregate: aggregate.clone(),
+                    inputs: support_provenance(input, aggregate.support(), bindings),
+                }
+            }
+        }
+        MirRelationExpr::Union { base, inputs } => ColumnProvenance::Union(
+            std::iter::once(&**base)
+                .chain(inputs)
+                .map(|input| provenance(input, column, bindings))
+                .collect(),
+        ),
+    })
+}
+
+/// Reports the provenance of each column in `support`, as columns of `expr`.
+fn support_provenance<'a, I>(
+    expr: &'a MirRelationExpr,
+    support: I,
+    bindings: &BTreeMap<LocalId, &'a MirRelationExpr>,
+) -> BTreeMap<usize, ColumnProvenance>
+where
+    I: IntoIterator<Item = usize>,
+{
+    support
+        .into_iter()
+        .map(|c| (c, provenance(expr, c, bindings)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{GlobalId, RelationType, ScalarType};
+
+    use crate::{AggregateFunc, BinaryFunc};
+
+    use super::*;
+
+    #[test]
+    fn test_column_provenance() {
+        let typ = RelationType::new(vec![
+            ScalarType::Int64.nullable(false),
+            ScalarType::Int64.nullable(false),
+        ]);
+        let a = MirRelationExpr::global_get(GlobalId::User(1), typ.clone());
+        let b = MirRelationExpr::global_get(GlobalId::User(2), typ);
+
+        let sum = MirScalarExpr::CallBinary {
+            func: BinaryFunc::AddInt64,
+            expr1: Box::new(MirScalarExpr::Column(1)),
+            expr2: Box::new(MirScalarExpr::Column(2)),
+        };
+        let expr = MirRelationExpr::join(vec![a, b], vec![vec![(0, 0), (1, 0)]])
+            .map(vec![sum])
+            .reduce(
+                vec![0],
+                vec![AggregateExpr {
+                    func: AggregateFunc::SumInt64,
+                    expr: MirScalarExpr::Column(4),
+                    distinct: false,
+                    filter: None,
+                }],
+                None,
+            )
+            .project(vec![1, 0]);
+
+        assert_eq!(
+            expr.column_provenance(1).sources(),
+            BTreeSet::from([(Id::Global(GlobalId::User(1)), 0)])
+        );
+        assert_eq!(
+            expr.column_provenance(0).sources(),
+            BTreeSet::from([
+                (Id::Global(GlobalId::User(1)), 1),
+                (Id::Global(GlobalId::User(2)), 0),
+            ])
+        );
+        assert!(matches!(
+            expr.column_provenance(0),
+            ColumnProvenance::Aggregate { .. }
+        ));
+    }
+}
@@ -10,6 +10,7 @@
 use std::cmp::{self, Ordering};
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
+use std::hash::Hasher;
 use std::iter;
 use std::ops::Deref;
 use std::str;
@@ -18,7 +19,7 @@ use std::str::FromStr;
 use ::encoding::label::encoding_from_whatwg_label;
 use ::encoding::DecoderTrap;
 use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
-use dec::OrderedDecimal;
+use dec::{Context, OrderedDecimal, Rounding};
 use fallible_iterator::FallibleIterator;
 use hmac::{Hmac, Mac};
 use itertools::Itertools;
@@ -30,10 +31,12 @@ use regex::RegexBuilder;
 use serde::{Deserialize, Serialize};
 use sha1::Sha1;
 use sha2::{Sha224, Sha256, Sha384, Sha512};
+use twox_hash::XxHash64;
 
 use mz_lowertest::MzReflect;
 use mz_ore::cast;
 use mz_ore::cast::CastFrom;
+use mz_ore::cast::TryCastFrom;
 use mz_ore::fmt::FormatBuffer;
 use mz_ore::option::OptionExt;
 use mz_ore::result::ResultExt;
@@ -46,7 +49,7 @@ use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::numeric::{self, DecimalLike, Numeric, NumericMaxScale};
 use mz_repr::adt::range::{self, Range, RangeBound, RangeOps};
-use mz_repr::adt::regex::any_regex;
+use mz_repr::adt::regex::{any_regex, Regex};
 use mz_repr::adt::timestamp::{CheckedTimestamp, TimestampLike};
 use mz_repr::chrono::any_naive_datetime;
 use mz_repr::{strconv, ColumnName, ColumnType, Datum, DatumType, Row, RowArena, ScalarType};
@@ -76,6 +79,7 @@ pub enum UnmaterializableFunc {
     CurrentUser,
     MzEnvironmentId,
     MzNow,
+    MzRandom,
     MzSessionId,
     MzUptime,
     MzVersion,
@@ -100,6 +104,7 @@ impl UnmaterializableFunc {
             UnmaterializableFunc::CurrentUser => ScalarType::String.nullable(false),
             UnmaterializableFunc::MzEnvironmentId => ScalarType::String.nullable(false),
             UnmaterializableFunc::MzNow => ScalarType::MzTimestamp.nullable(false),
+            UnmaterializableFunc::MzRandom => ScalarType::Float64.nullable(false),
             UnmaterializableFunc::MzSessionId => ScalarType::Uuid.nullable(false),
             UnmaterializableFunc::MzUptime => ScalarType::Interval.nullable(true),
             UnmaterializableFunc::MzVersion => ScalarType::String.nullable(false),
@@ -123,6 +128,7 @@ impl fmt::Display for UnmaterializableFunc {
             UnmaterializableFunc::CurrentUser => f.write_str("current_user"),
             UnmaterializableFunc::MzEnvironmentId => f.write_str("mz_environment_id"),
             UnmaterializableFunc::MzNow => f.write_str("mz_now"),
+            UnmaterializableFunc::MzRandom => f.write_str("mz_random"),
             UnmaterializableFunc::MzSessionId => f.write_str("mz_session_id"),
             UnmaterializableFunc::MzUptime => f.write_str("mz_uptime"),
             UnmaterializableFunc::MzVersion => f.write_str("mz_version"),
@@ -145,6 +151,7 @@ impl RustType<ProtoUnmaterializableFunc> for UnmaterializableFunc {
             UnmaterializableFunc::CurrentUser => CurrentUser(()),
             UnmaterializableFunc::MzEnvironmentId => MzEnvironmentId(()),
             UnmaterializableFunc::MzNow => MzNow(()),
+            UnmaterializableFunc::MzRandom => MzRandom(()),
             UnmaterializableFunc::MzSessionId => MzSessionId(()),
             UnmaterializableFunc::MzUptime => MzUptime(()),
             UnmaterializableFunc::MzVersion => MzVersion(()),
@@ -169,6 +176,7 @@ impl RustType<ProtoUnmaterializableFunc> for UnmaterializableFunc {
                 CurrentUser(()) => Ok(UnmaterializableFunc::CurrentUser),
                 MzEnvironmentId(()) => Ok(UnmaterializableFunc::MzEnvironmentId),
                 MzNow(()) => Ok(UnmaterializableFunc::MzNow),
+                MzRandom(()) => Ok(UnmaterializableFunc::MzRandom),
                 MzSessionId(()) => Ok(UnmaterializableFunc::MzSessionId),
                 MzUptime(()) => Ok(UnmaterializableFunc::MzUptime),
                 MzVersion(()) => Ok(UnmaterializableFunc::MzVersion),
@@ -359,9 +367,29 @@ fn add_time_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
 }
 
 fn round_numeric_binary<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    round_numeric_binary_with(a, b, numeric::cx_datum())
+}
+
+fn trunc_numeric_binary<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut cx = numeric::cx_datum();
+    cx.set_rounding(Rounding::Down);
+    round_numeric_binary_with(a, b, cx)
+}
+
+fn round_numeric_half_even_binary<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut cx = numeric::cx_datum();
+    cx.set_rounding(Rounding::HalfEven);
+    round_numeric_binary_with(a, b, cx)
+}
+
+/// Rounds `a` to `b` decimal places, using the rounding mode of `cx`.
+fn round_numeric_binary_with<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    mut cx: Context<numeric::Numeric>,
+) -> Result<Datum<'a>, EvalError> {
     let mut a = a.unwrap_numeric().0;
     let mut b = b.unwrap_int32();
-    let mut cx = numeric::cx_datum();
     let a_exp = a.exponent();
     if a_exp > 0 && b > 0 || a_exp < 0 && -a_exp < b {
         // This condition indicates:
@@ -935,7 +963,10 @@ fn div_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int16() / b))
+        a.unwrap_int16()
+            .checked_div(b)
+            .map(Datum::from)
+            .ok_or(EvalError::Int16OutOfRange)
     }
 }
 
@@ -944,7 +975,10 @@ fn div_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int32() / b))
+        a.unwrap_int32()
+            .checked_div(b)
+            .map(Datum::from)
+            .ok_or(EvalError::Int32OutOfRange)
     }
 }
 
@@ -953,7 +987,10 @@ fn div_int64<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int64() / b))
+        a.unwrap_int64()
+            .checked_div(b)
+            .map(Datum::from)
+            .ok_or(EvalError::Int64OutOfRange)
     }
 }
 
@@ -1057,7 +1094,9 @@ fn mod_int16<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int16() % b))
+        // The remainder of dividing the minimum value by -1 is 0, even though
+        // the quotient overflows.
+        Ok(Datum::from(a.unwrap_int16().wrapping_rem(b)))
     }
 }
 
@@ -1066,7 +1105,7 @@ fn mod_int32<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int32() % b))
+        Ok(Datum::from(a.unwrap_int32().wrapping_rem(b)))
     }
 }
 
@@ -1075,7 +1114,7 @@ fn mod_int64<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     if b == 0 {
         Err(EvalError::DivisionByZero)
     } else {
-        Ok(Datum::from(a.unwrap_int64() % b))
+        Ok(Datum::from(a.unwrap_int64().wrapping_rem(b)))
     }
 }
 
@@ -1195,6 +1234,38 @@ fn log_base_numeric<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalErr
     }
 }
 
+fn atan2<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let a = a.unwrap_float64();
+    let b = b.unwrap_float64();
+    Datum::from(a.atan2(b))
+}
+
+/// Maps `data` to a pseudo-random value in the range `[0, 1)`.
+///
+/// The value depends only on `data`, so it is the same in every process. It is
+/// not guaranteed to be the same across versions, as the callers hash encodings
+/// that may change between versions.
+pub fn hash_to_unit_interval(data: &[u8]) -> f64 {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(data);
+    // Keep the 53 most significant bits, which is the precision of an `f64`.
+    let bits = f64::try_cast_from(hasher.finish() >> 11).expect("53 bits fit in an f64");
+    bits / 2f64.powi(53)
+}
+
+fn hash_sample<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let row = Row::pack_slice(&[a]);
+    Datum::from(hash_to_unit_interval(row.data()) < b.unwrap_float64())
+}
+
+fn inet_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains(&b.unwrap_inet()))
+}
+
+fn inet_contains_or_equals<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains_or_equals(&b.unwrap_inet()))
+}
+
 fn power<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let a = a.unwrap_float64();
     let b = b.unwrap_float64();
@@ -1309,7 +1380,7 @@ fn jsonb_get_int64<'a>(
                 usize::cast_from(u64::try_from(i).expect("known to be positive"))
             } else {
                 // index backwards from the end
-                let i = usize::cast_from(u64::try_from(i.abs()).expect("known to be positive"));
+                let i = usize::cast_from(i.unsigned_abs());
                 (list.iter().count()).wrapping_sub(i)
             };
             match list.iter().nth(i) {
@@ -1543,7 +1614,7 @@ fn jsonb_delete_int64<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowArena
                 usize::cast_from(u64::try_from(i).expect("known to be positive"))
             } else {
                 // index backwards from the end
-                let i = usize::cast_from(u64::try_from(i.abs()).expect("known to be positive"));
+                let i = usize::cast_from(i.unsigned_abs());
                 (list.iter().count()).wrapping_sub(i)
             };
             let elems = list
@@ -1572,6 +1643,101 @@ fn jsonb_delete_string<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     }
 }
 
+fn jsonb_delete_path<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if !matches!(a, Datum::List(_) | Datum::Map(_)) {
+        return Err(EvalError::InvalidParameterValue(
+            "cannot delete path in scalar".into(),
+        ));
+    }
+    let path = b.unwrap_array().elements().iter().collect::<Vec<_>>();
+    jsonb_delete_path_inner(a, &path, 1, temp_storage)
+}
+
+/// Deletes `path` from `json`, where `position` is the (1-based) position of
+/// the first element of `path` in the original path.
+fn jsonb_delete_path_inner<'a>(
+    json: Datum<'a>,
+    path: &[Datum<'a>],
+    position: usize,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let (key, rest) = match path.split_first() {
+        Some((key, rest)) => (key, rest),
+        None => return Ok(json),
+    };
+    let key = match key {
+        Datum::String(s) => *s,
+        Datum::Null => {
+            return Err(EvalError::InvalidParameterValue(format!(
+                "path element at position {} is null",
+                position
+            )))
+        }
+        _ => unreachable!("keys in jsonb_delete_path known to be strings"),
+    };
+    match json {
+        Datum::Map(map) => {
+            if !map.iter().any(|(k, _v)| k == key) {
+                return Ok(json);
+            }
+            let mut pairs = Vec::new();
+            for (k, v) in map.iter() {
+                if k != key {
+                    pairs.push((k, v));
+                } else if !rest.is_empty() {
+                    pairs.push((
+                        k,
+                        jsonb_delete_path_inner(v, rest, position + 1, temp_storage)?,
+                    ));
+                }
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_dict(pairs)))
+        }
+        Datum::List(list) => {
+            let i = match strconv::parse_int64(key) {
+                Ok(i) => i,
+                Err(_) => {
+                    return Err(EvalError::InvalidParameterValue(format!(
+                        "path element at position {} is not an integer: \"{}\"",
+                        position, key
+                    )))
+                }
+            };
+            let len = list.iter().count();
+            let i = if i >= 0 {
+                usize::cast_from(u64::try_from(i).expect("known to be positive"))
+            } else {
+                // index backwards from the end
+                let i = usize::cast_from(i.unsigned_abs());
+                len.wrapping_sub(i)
+            };
+            if i >= len {
+                return Ok(json);
+            }
+            let mut elems = Vec::new();
+            for (i2, e) in list.iter().enumerate() {
+                if i2 != i {
+                    elems.push(e);
+                } else if !rest.is_empty() {
+                    elems.push(jsonb_delete_path_inner(
+                        e,
+                        rest,
+                        position + 1,
+                        temp_storage,
+                    )?);
+                }
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_list(elems)))
+        }
+        // Scalars nested within the document are left unchanged.
+        _ => Ok(json),
+    }
+}
+
 fn date_part_interval<'a, D>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError>
 where
     D: DecimalLike + Into<Datum<'static>>,
@@ -1830,6 +1996,8 @@ pub enum BinaryFunc {
     ModFloat64,
     ModNumeric,
     RoundNumeric,
+    RoundNumericHalfEven,
+    TruncNumeric,
     Eq,
     NotEq,
     Lt,
@@ -1870,6 +2038,7 @@ pub enum BinaryFunc {
     JsonbContainsJsonb,
     JsonbDeleteInt64,
     JsonbDeleteString,
+    JsonbDeletePath,
     MapContainsKey,
     MapGetValue,
     MapGetValues,
@@ -1898,14 +2067,20 @@ pub enum BinaryFunc {
     ListRemove,
     DigestString,
     DigestBytes,
+    UuidGenerateV5,
     MzRenderTypmod,
     Encode,
     Decode,
     LogNumeric,
     Power,
+    Atan2,
+    InetContains,
+    InetContainsOrEquals,
+    HashSample,
     PowerNumeric,
     GetByte,
     RangeContainsElem { elem_type: ScalarType, rev: bool },
+    RegexpReplace { regex: Regex, limit: usize },
 }
 
 impl BinaryFunc {
@@ -2167,6 +2342,7 @@ impl BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => Ok(eager!(jsonb_contains_jsonb)),
             BinaryFunc::JsonbDeleteInt64 => Ok(eager!(jsonb_delete_int64, temp_storage)),
             BinaryFunc::JsonbDeleteString => Ok(eager!(jsonb_delete_string, temp_storage)),
+            BinaryFunc::JsonbDeletePath => eager!(jsonb_delete_path, temp_storage),
             BinaryFunc::MapContainsKey => Ok(eager!(map_contains_key)),
             BinaryFunc::MapGetValue => Ok(eager!(map_get_value)),
             BinaryFunc::MapGetValues => Ok(eager!(map_get_values, temp_storage)),
@@ -2174,6 +2350,8 @@ impl BinaryFunc {
             BinaryFunc::MapContainsAnyKeys => Ok(eager!(map_contains_any_keys)),
             BinaryFunc::MapContainsMap => Ok(eager!(map_contains_map)),
             BinaryFunc::RoundNumeric => eager!(round_numeric_binary),
+            BinaryFunc::RoundNumericHalfEven => eager!(round_numeric_half_even_binary),
+            BinaryFunc::TruncNumeric => eager!(trunc_numeric_binary),
             BinaryFunc::ConvertFrom => eager!(convert_from),
             BinaryFunc::Encode => eager!(encode, temp_storage),
             BinaryFunc::Decode => eager!(decode, temp_storage),
@@ -2185,6 +2363,9 @@ impl BinaryFunc {
             BinaryFunc::TrimTrailing => Ok(eager!(trim_trailing)),
             BinaryFunc::EncodedBytesCharLength => eager!(encoded_bytes_char_length),
             BinaryFunc::ListLengthMax { max_layer } => eager!(list_length_max, *max_layer),
+            BinaryFunc::RegexpReplace { regex, limit } => {
+                Ok(eager!(regexp_replace_static, regex, *limit, temp_storage))
+            }
             BinaryFunc::ArrayLength => eager!(array_length),
             BinaryFunc::ArrayContains => Ok(eager!(array_contains)),
             BinaryFunc::ArrayLower => Ok(eager!(array_lower)),
@@ -2197,9 +2378,14 @@ impl BinaryFunc {
             BinaryFunc::ListRemove => Ok(eager!(list_remove, temp_storage)),
             BinaryFunc::DigestString => eager!(digest_string, temp_storage),
             BinaryFunc::DigestBytes => eager!(digest_bytes, temp_storage),
+            BinaryFunc::UuidGenerateV5 => Ok(eager!(uuid_generate_v5)),
             BinaryFunc::MzRenderTypmod => eager!(mz_render_typmod, temp_storage),
             BinaryFunc::LogNumeric => eager!(log_base_numeric),
             BinaryFunc::Power => eager!(power),
+            BinaryFunc::Atan2 => Ok(eager!(atan2)),
+            BinaryFunc::InetContains => Ok(eager!(inet_contains)),
+            BinaryFunc::InetContainsOrEquals => Ok(eager!(inet_contains_or_equals)),
+            BinaryFunc::HashSample => Ok(eager!(hash_sample)),
             BinaryFunc::PowerNumeric => eager!(power_numeric),
             BinaryFunc::RepeatString => eager!(repeat_string, temp_storage),
             BinaryFunc::GetByte => eager!(get_byte),
@@ -2325,7 +2511,8 @@ impl BinaryFunc {
             | JsonbGetPath { stringify: false }
             | JsonbConcat
             | JsonbDeleteInt64
-            | JsonbDeleteString => ScalarType::Jsonb.nullable(true),
+            | JsonbDeleteString
+            | JsonbDeletePath => ScalarType::Jsonb.nullable(true),
 
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
@@ -2345,6 +2532,8 @@ impl BinaryFunc {
 
             ListLengthMax { .. } => ScalarType::Int32.nullable(true),
 
+            RegexpReplace { .. } => ScalarType::String.nullable(in_nullable),
+
             ArrayArrayConcat | ArrayRemove | ListListConcat | ListElementConcat | ListRemove => {
                 input1_type.scalar_type.without_modifiers().nullable(true)
             }
@@ -2352,14 +2541,18 @@ impl BinaryFunc {
             ElementListConcat => input2_type.scalar_type.without_modifiers().nullable(true),
 
             DigestString | DigestBytes => ScalarType::Bytes.nullable(true),
+            UuidGenerateV5 => ScalarType::Uuid.nullable(in_nullable),
             Position => ScalarType::Int32.nullable(in_nullable),
             Encode => ScalarType::String.nullable(in_nullable),
             Decode => ScalarType::Bytes.nullable(in_nullable),
-            Power => ScalarType::Float64.nullable(in_nullable),
+            Power | Atan2 => ScalarType::Float64.nullable(in_nullable),
+            InetContains | InetContainsOrEquals | HashSample => {
+                ScalarType::Bool.nullable(in_nullable)
+            }
             RepeatString => input1_type.scalar_type.nullable(in_nullable),
 
             AddNumeric | DivNumeric | LogNumeric | ModNumeric | MulNumeric | PowerNumeric
-            | RoundNumeric | SubNumeric => {
+            | RoundNumeric | RoundNumericHalfEven | SubNumeric | TruncNumeric => {
                 ScalarType::Numeric { max_scale: None }.nullable(in_nullable)
             }
 
@@ -2602,6 +2795,7 @@ impl BinaryFunc {
             | JsonbContainsString
             | JsonbDeleteInt64
             | JsonbDeleteString
+            | JsonbDeletePath
             | MapContainsKey
             | MapGetValue
             | MapGetValues
@@ -2619,6 +2813,8 @@ impl BinaryFunc {
             | ListListConcat
             | ListElementConcat
             | ElementListConcat
+            | InetContains
+            | InetContainsOrEquals
             | RangeContainsElem { .. } => true,
             ToCharTimestamp
             | ToCharTimestampTz
@@ -2643,6 +2839,8 @@ impl BinaryFunc {
             | TimezoneIntervalTimestampTz
             | TimezoneIntervalTime
             | RoundNumeric
+            | RoundNumericHalfEven
+            | TruncNumeric
             | ConvertFrom
             | Left
             | Position
@@ -2652,13 +2850,17 @@ impl BinaryFunc {
             | TrimTrailing
             | EncodedBytesCharLength
             | ListLengthMax { .. }
+            | RegexpReplace { .. }
             | DigestString
             | DigestBytes
+            | UuidGenerateV5
             | MzRenderTypmod
             | Encode
             | Decode
             | LogNumeric
             | Power
+            | Atan2
+            | HashSample
             | PowerNumeric
             | RepeatString
             | ArrayRemove
@@ -2843,10 +3045,13 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb | BinaryFunc::MapContainsMap => f.write_str("@>"),
             BinaryFunc::JsonbDeleteInt64 => f.write_str("-"),
             BinaryFunc::JsonbDeleteString => f.write_str("-"),
+            BinaryFunc::JsonbDeletePath => f.write_str("#-"),
             BinaryFunc::MapGetValue | BinaryFunc::MapGetValues => f.write_str("->"),
             BinaryFunc::MapContainsAllKeys => f.write_str("?&"),
             BinaryFunc::MapContainsAnyKeys => f.write_str("?|"),
             BinaryFunc::RoundNumeric => f.write_str("round"),
+            BinaryFunc::RoundNumericHalfEven => f.write_str("round_half_even"),
+            BinaryFunc::TruncNumeric => f.write_str("trunc"),
             BinaryFunc::ConvertFrom => f.write_str("convert_from"),
             BinaryFunc::Left => f.write_str("left"),
             BinaryFunc::Position => f.write_str("position"),
@@ -2856,6 +3061,9 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::TrimTrailing => f.write_str("rtrim"),
             BinaryFunc::EncodedBytesCharLength => f.write_str("length"),
             BinaryFunc::ListLengthMax { .. } => f.write_str("list_length_max"),
+            BinaryFunc::RegexpReplace { regex, limit } => {
+                write!(f, "regexp_replace[{}, limit={}]", regex.as_str(), limit)
+            }
             BinaryFunc::ArrayContains => f.write_str("array_contains"),
             BinaryFunc::ArrayLength => f.write_str("array_length"),
             BinaryFunc::ArrayLower => f.write_str("array_lower"),
@@ -2867,11 +3075,16 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ElementListConcat => f.write_str("||"),
             BinaryFunc::ListRemove => f.write_str("list_remove"),
             BinaryFunc::DigestString | BinaryFunc::DigestBytes => f.write_str("digest"),
+            BinaryFunc::UuidGenerateV5 => f.write_str("uuid_generate_v5"),
             BinaryFunc::MzRenderTypmod => f.write_str("mz_render_typmod"),
             BinaryFunc::Encode => f.write_str("encode"),
             BinaryFunc::Decode => f.write_str("decode"),
             BinaryFunc::LogNumeric => f.write_str("log"),
             BinaryFunc::Power => f.write_str("power"),
+            BinaryFunc::Atan2 => f.write_str("atan2"),
+            BinaryFunc::InetContains => f.write_str(">>"),
+            BinaryFunc::InetContainsOrEquals => f.write_str(">>="),
+            BinaryFunc::HashSample => f.write_str("hash_sample"),
             BinaryFunc::PowerNumeric => f.write_str("power_numeric"),
             BinaryFunc::RepeatString => f.write_str("repeat"),
             BinaryFunc::GetByte => f.write_str("get_byte"),
@@ -2988,6 +3201,8 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::ModFloat64).boxed(),
             Just(BinaryFunc::ModNumeric).boxed(),
             Just(BinaryFunc::RoundNumeric).boxed(),
+            Just(BinaryFunc::RoundNumericHalfEven).boxed(),
+            Just(BinaryFunc::TruncNumeric).boxed(),
             Just(BinaryFunc::Eq).boxed(),
             Just(BinaryFunc::NotEq).boxed(),
             Just(BinaryFunc::Lt).boxed(),
@@ -3040,6 +3255,7 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::JsonbContainsJsonb).boxed(),
             Just(BinaryFunc::JsonbDeleteInt64).boxed(),
             Just(BinaryFunc::JsonbDeleteString).boxed(),
+            Just(BinaryFunc::JsonbDeletePath).boxed(),
             Just(BinaryFunc::MapContainsKey).boxed(),
             Just(BinaryFunc::MapGetValue).boxed(),
             Just(BinaryFunc::MapGetValues).boxed(),
@@ -3070,15 +3286,23 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::ListRemove).boxed(),
             Just(BinaryFunc::DigestString).boxed(),
             Just(BinaryFunc::DigestBytes).boxed(),
+            Just(BinaryFunc::UuidGenerateV5).boxed(),
             Just(BinaryFunc::MzRenderTypmod).boxed(),
             Just(BinaryFunc::Encode).boxed(),
             Just(BinaryFunc::Decode).boxed(),
             Just(BinaryFunc::LogNumeric).boxed(),
             Just(BinaryFunc::Power).boxed(),
+            Just(BinaryFunc::Atan2).boxed(),
+            Just(BinaryFunc::InetContains).boxed(),
+            Just(BinaryFunc::InetContainsOrEquals).boxed(),
+            Just(BinaryFunc::HashSample).boxed(),
             Just(BinaryFunc::PowerNumeric).boxed(),
             (bool::arbitrary(), mz_repr::arb_range_type())
                 .prop_map(|(rev, elem_type)| BinaryFunc::RangeContainsElem { elem_type, rev })
                 .boxed(),
+            (any_regex(), usize::arbitrary())
+                .prop_map(|(regex, limit)| BinaryFunc::RegexpReplace { regex, limit })
+                .boxed(),
         ])
     }
 }
@@ -3180,6 +3404,8 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::ModFloat64 => ModFloat64(()),
             BinaryFunc::ModNumeric => ModNumeric(()),
             BinaryFunc::RoundNumeric => RoundNumeric(()),
+            BinaryFunc::RoundNumericHalfEven => RoundNumericHalfEven(()),
+            BinaryFunc::TruncNumeric => TruncNumeric(()),
             BinaryFunc::Eq => Eq(()),
             BinaryFunc::NotEq => NotEq(()),
             BinaryFunc::Lt => Lt(()),
@@ -3220,6 +3446,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => JsonbContainsJsonb(()),
             BinaryFunc::JsonbDeleteInt64 => JsonbDeleteInt64(()),
             BinaryFunc::JsonbDeleteString => JsonbDeleteString(()),
+            BinaryFunc::JsonbDeletePath => JsonbDeletePath(()),
             BinaryFunc::MapContainsKey => MapContainsKey(()),
             BinaryFunc::MapGetValue => MapGetValue(()),
             BinaryFunc::MapGetValues => MapGetValues(()),
@@ -3236,6 +3463,12 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::TrimTrailing => TrimTrailing(()),
             BinaryFunc::EncodedBytesCharLength => EncodedBytesCharLength(()),
             BinaryFunc::ListLengthMax { max_layer } => ListLengthMax(max_layer.into_proto()),
+            BinaryFunc::RegexpReplace { regex, limit } => {
+                RegexpReplace(crate::scalar::proto_binary_func::ProtoRegexpReplace {
+                    regex: Some(regex.into_proto()),
+                    limit: limit.into_proto(),
+                })
+            }
             BinaryFunc::ArrayContains => ArrayContains(()),
             BinaryFunc::ArrayLength => ArrayLength(()),
             BinaryFunc::ArrayLower => ArrayLower(()),
@@ -3248,11 +3481,16 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::ListRemove => ListRemove(()),
             BinaryFunc::DigestString => DigestString(()),
             BinaryFunc::DigestBytes => DigestBytes(()),
+            BinaryFunc::UuidGenerateV5 => UuidGenerateV5(()),
             BinaryFunc::MzRenderTypmod => MzRenderTypmod(()),
             BinaryFunc::Encode => Encode(()),
             BinaryFunc::Decode => Decode(()),
             BinaryFunc::LogNumeric => LogNumeric(()),
             BinaryFunc::Power => Power(()),
+            BinaryFunc::Atan2 => Atan2(()),
+            BinaryFunc::InetContains => InetContains(()),
+            BinaryFunc::InetContainsOrEquals => InetContainsOrEquals(()),
+            BinaryFunc::HashSample => HashSample(()),
             BinaryFunc::PowerNumeric => PowerNumeric(()),
             BinaryFunc::GetByte => GetByte(()),
             BinaryFunc::RangeContainsElem { elem_type, rev } => RangeContainsElem(
@@ -3362,6 +3600,8 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 ModFloat64(()) => Ok(BinaryFunc::ModFloat64),
                 ModNumeric(()) => Ok(BinaryFunc::ModNumeric),
                 RoundNumeric(()) => Ok(BinaryFunc::RoundNumeric),
+                RoundNumericHalfEven(()) => Ok(BinaryFunc::RoundNumericHalfEven),
+                TruncNumeric(()) => Ok(BinaryFunc::TruncNumeric),
                 Eq(()) => Ok(BinaryFunc::Eq),
                 NotEq(()) => Ok(BinaryFunc::NotEq),
                 Lt(()) => Ok(BinaryFunc::Lt),
@@ -3406,6 +3646,7 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 JsonbContainsJsonb(()) => Ok(BinaryFunc::JsonbContainsJsonb),
                 JsonbDeleteInt64(()) => Ok(BinaryFunc::JsonbDeleteInt64),
                 JsonbDeleteString(()) => Ok(BinaryFunc::JsonbDeleteString),
+                JsonbDeletePath(()) => Ok(BinaryFunc::JsonbDeletePath),
                 MapContainsKey(()) => Ok(BinaryFunc::MapContainsKey),
                 MapGetValue(()) => Ok(BinaryFunc::MapGetValue),
                 MapGetValues(()) => Ok(BinaryFunc::MapGetValues),
@@ -3424,6 +3665,10 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 ListLengthMax(max_layer) => Ok(BinaryFunc::ListLengthMax {
                     max_layer: max_layer.into_rust()?,
                 }),
+                RegexpReplace(inner) => Ok(BinaryFunc::RegexpReplace {
+                    regex: inner.regex.into_rust_if_some("ProtoRegexpReplace::regex")?,
+                    limit: inner.limit.into_rust()?,
+                }),
                 ArrayContains(()) => Ok(BinaryFunc::ArrayContains),
                 ArrayLength(()) => Ok(BinaryFunc::ArrayLength),
                 ArrayLower(()) => Ok(BinaryFunc::ArrayLower),
@@ -3436,11 +3681,16 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 ListRemove(()) => Ok(BinaryFunc::ListRemove),
                 DigestString(()) => Ok(BinaryFunc::DigestString),
                 DigestBytes(()) => Ok(BinaryFunc::DigestBytes),
+                UuidGenerateV5(()) => Ok(BinaryFunc::UuidGenerateV5),
                 MzRenderTypmod(()) => Ok(BinaryFunc::MzRenderTypmod),
                 Encode(()) => Ok(BinaryFunc::Encode),
                 Decode(()) => Ok(BinaryFunc::Decode),
                 LogNumeric(()) => Ok(BinaryFunc::LogNumeric),
                 Power(()) => Ok(BinaryFunc::Power),
+                Atan2(()) => Ok(BinaryFunc::Atan2),
+                InetContains(()) => Ok(BinaryFunc::InetContains),
+                InetContainsOrEquals(()) => Ok(BinaryFunc::InetContainsOrEquals),
+                HashSample(()) => Ok(BinaryFunc::HashSample),
                 PowerNumeric(()) => Ok(BinaryFunc::PowerNumeric),
                 GetByte(()) => Ok(BinaryFunc::GetByte),
                 RangeContainsElem(inner) => Ok(BinaryFunc::RangeContainsElem {
@@ -3609,6 +3859,7 @@ derive_unary!(
     NegInterval,
     SqrtFloat64,
     SqrtNumeric,
+    ScaleNumeric,
     CbrtFloat64,
     AbsInt16,
     AbsInt32,
@@ -3749,6 +4000,8 @@ derive_unary!(
     CastStringToInterval,
     CastStringToNumeric,
     CastStringToUuid,
+    CastStringToInet,
+    CastStringToCidr,
     CastStringToChar,
     PadChar,
     CastStringToVarChar,
@@ -3783,6 +4036,12 @@ derive_unary!(
     CastJsonbToNumeric,
     CastJsonbToBool,
     CastUuidToString,
+    CastInetToString,
+    CastCidrToString,
+    CastCidrToInet,
+    CastInetToCidr,
+    InetHost,
+    InetMasklen,
     CastRecordToString,
     CastRecord1ToRecord2,
     CastArrayToString,
@@ -3844,6 +4103,9 @@ derive_unary!(
     MapLength,
     Upper,
     Lower,
+    Initcap,
+    Xxhash64String,
+    Xxhash64Bytes,
     Cos,
     Acos,
     Cosh,
@@ -3870,7 +4132,10 @@ derive_unary!(
     RescaleNumeric,
     PgColumnSize,
     MzRowSize,
+    MzHllSketch,
+    MzHllEstimate,
     MzTypeName,
+    IsInList,
     StepMzTimestamp,
     RangeLower,
     RangeUpper,
@@ -3935,6 +4200,7 @@ impl Arbitrary for UnaryFunc {
             NegInterval::arbitrary().prop_map_into().boxed(),
             SqrtFloat64::arbitrary().prop_map_into().boxed(),
             SqrtNumeric::arbitrary().prop_map_into().boxed(),
+            ScaleNumeric::arbitrary().prop_map_into().boxed(),
             CbrtFloat64::arbitrary().prop_map_into().boxed(),
             AbsInt16::arbitrary().prop_map_into().boxed(),
             AbsInt32::arbitrary().prop_map_into().boxed(),
@@ -4112,6 +4378,8 @@ impl Arbitrary for UnaryFunc {
             CastStringToInterval::arbitrary().prop_map_into().boxed(),
             CastStringToNumeric::arbitrary().prop_map_into().boxed(),
             CastStringToUuid::arbitrary().prop_map_into().boxed(),
+            CastStringToInet::arbitrary().prop_map_into().boxed(),
+            CastStringToCidr::arbitrary().prop_map_into().boxed(),
             CastStringToChar::arbitrary().prop_map_into().boxed(),
             PadChar::arbitrary().prop_map_into().boxed(),
             CastStringToVarChar::arbitrary().prop_map_into().boxed(),
@@ -4152,6 +4420,12 @@ impl Arbitrary for UnaryFunc {
             CastJsonbToNumeric::arbitrary().prop_map_into().boxed(),
             CastJsonbToBool::arbitrary().prop_map_into().boxed(),
             CastUuidToString::arbitrary().prop_map_into().boxed(),
+            CastInetToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToInet::arbitrary().prop_map_into().boxed(),
+            CastInetToCidr::arbitrary().prop_map_into().boxed(),
+            InetHost::arbitrary().prop_map_into().boxed(),
+            InetMasklen::arbitrary().prop_map_into().boxed(),
             CastRecordToString::arbitrary().prop_map_into().boxed(),
             (
                 any::<ScalarType>(),
@@ -4236,6 +4510,9 @@ impl Arbitrary for UnaryFunc {
             MapLength::arbitrary().prop_map_into().boxed(),
             Upper::arbitrary().prop_map_into().boxed(),
             Lower::arbitrary().prop_map_into().boxed(),
+            Initcap::arbitrary().prop_map_into().boxed(),
+            Xxhash64String::arbitrary().prop_map_into().boxed(),
+            Xxhash64Bytes::arbitrary().prop_map_into().boxed(),
             Cos::arbitrary().prop_map_into().boxed(),
             Acos::arbitrary().prop_map_into().boxed(),
             Cosh::arbitrary().prop_map_into().boxed(),
@@ -4262,7 +4539,12 @@ impl Arbitrary for UnaryFunc {
             RescaleNumeric::arbitrary().prop_map_into().boxed(),
             PgColumnSize::arbitrary().prop_map_into().boxed(),
             MzRowSize::arbitrary().prop_map_into().boxed(),
+            MzHllSketch::arbitrary().prop_map_into().boxed(),
+            MzHllEstimate::arbitrary().prop_map_into().boxed(),
             MzTypeName::arbitrary().prop_map_into().boxed(),
+            prop::collection::vec(any::<Row>(), 0..8)
+                .prop_map(|values| UnaryFunc::IsInList(IsInList { values }))
+                .boxed(),
             RangeLower::arbitrary().prop_map_into().boxed(),
             RangeUpper::arbitrary().prop_map_into().boxed(),
             RangeEmpty::arbitrary().prop_map_into().boxed(),
@@ -4298,6 +4580,7 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::NegInterval(_) => NegInterval(()),
             UnaryFunc::SqrtFloat64(_) => SqrtFloat64(()),
             UnaryFunc::SqrtNumeric(_) => SqrtNumeric(()),
+            UnaryFunc::ScaleNumeric(_) => ScaleNumeric(()),
             UnaryFunc::CbrtFloat64(_) => CbrtFloat64(()),
             UnaryFunc::AbsInt16(_) => AbsInt16(()),
             UnaryFunc::AbsInt32(_) => AbsInt32(()),
@@ -4449,6 +4732,8 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastStringToInterval(_) => CastStringToInterval(()),
             UnaryFunc::CastStringToNumeric(func) => CastStringToNumeric(func.0.into_proto()),
             UnaryFunc::CastStringToUuid(_) => CastStringToUuid(()),
+            UnaryFunc::CastStringToInet(_) => CastStringToInet(()),
+            UnaryFunc::CastStringToCidr(_) => CastStringToCidr(()),
             UnaryFunc::CastStringToChar(func) => CastStringToChar(ProtoCastStringToChar {
                 length: func.length.into_proto(),
                 fail_on_len: func.fail_on_len,
@@ -4491,6 +4776,12 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastJsonbToNumeric(func) => CastJsonbToNumeric(func.0.into_proto()),
             UnaryFunc::CastJsonbToBool(_) => CastJsonbToBool(()),
             UnaryFunc::CastUuidToString(_) => CastUuidToString(()),
+            UnaryFunc::CastInetToString(_) => CastInetToString(()),
+            UnaryFunc::CastCidrToString(_) => CastCidrToString(()),
+            UnaryFunc::CastCidrToInet(_) => CastCidrToInet(()),
+            UnaryFunc::CastInetToCidr(_) => CastInetToCidr(()),
+            UnaryFunc::InetHost(_) => InetHost(()),
+            UnaryFunc::InetMasklen(_) => InetMasklen(()),
             UnaryFunc::CastRecordToString(func) => CastRecordToString(func.ty.into_proto()),
             UnaryFunc::CastRecord1ToRecord2(inner) => {
                 CastRecord1ToRecord2(ProtoCastRecord1ToRecord2 {
@@ -4565,6 +4856,9 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::MapLength(_) => MapLength(()),
             UnaryFunc::Upper(_) => Upper(()),
             UnaryFunc::Lower(_) => Lower(()),
+            UnaryFunc::Initcap(_) => Initcap(()),
+            UnaryFunc::Xxhash64String(_) => Xxhash64String(()),
+            UnaryFunc::Xxhash64Bytes(_) => Xxhash64Bytes(()),
             UnaryFunc::Cos(_) => Cos(()),
             UnaryFunc::Acos(_) => Acos(()),
             UnaryFunc::Cosh(_) => Cosh(()),
@@ -4591,7 +4885,12 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::RescaleNumeric(func) => RescaleNumeric(func.0.into_proto()),
             UnaryFunc::PgColumnSize(_) => PgColumnSize(()),
             UnaryFunc::MzRowSize(_) => MzRowSize(()),
+            UnaryFunc::MzHllSketch(_) => MzHllSketch(()),
+            UnaryFunc::MzHllEstimate(_) => MzHllEstimate(()),
             UnaryFunc::MzTypeName(_) => MzTypeName(()),
+            UnaryFunc::IsInList(func) => IsInList(ProtoIsInList {
+                values: func.values.into_proto(),
+            }),
             UnaryFunc::CastMzTimestampToString(_) => CastMzTimestampToString(()),
             UnaryFunc::CastStringToMzTimestamp(_) => CastStringToMzTimestamp(()),
             UnaryFunc::CastUint64ToMzTimestamp(_) => CastUint64ToMzTimestamp(()),
@@ -4636,6 +4935,7 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 NegInterval(()) => Ok(impls::NegInterval.into()),
                 SqrtFloat64(()) => Ok(impls::SqrtFloat64.into()),
                 SqrtNumeric(()) => Ok(impls::SqrtNumeric.into()),
+                ScaleNumeric(()) => Ok(impls::ScaleNumeric.into()),
                 CbrtFloat64(()) => Ok(impls::CbrtFloat64.into()),
                 AbsInt16(()) => Ok(impls::AbsInt16.into()),
                 AbsInt32(()) => Ok(impls::AbsInt32.into()),
@@ -4817,6 +5117,8 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                     Ok(impls::CastStringToNumeric(max_scale.into_rust()?).into())
                 }
                 CastStringToUuid(()) => Ok(impls::CastStringToUuid.into()),
+                CastStringToInet(()) => Ok(impls::CastStringToInet.into()),
+                CastStringToCidr(()) => Ok(impls::CastStringToCidr.into()),
                 CastStringToChar(func) => Ok(impls::CastStringToChar {
                     length: func.length.into_rust()?,
                     fail_on_len: func.fail_on_len,
@@ -4864,6 +5166,12 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 }
                 CastJsonbToBool(()) => Ok(impls::CastJsonbToBool.into()),
                 CastUuidToString(()) => Ok(impls::CastUuidToString.into()),
+                CastInetToString(()) => Ok(impls::CastInetToString.into()),
+                CastCidrToString(()) => Ok(impls::CastCidrToString.into()),
+                CastCidrToInet(()) => Ok(impls::CastCidrToInet.into()),
+                CastInetToCidr(()) => Ok(impls::CastInetToCidr.into()),
+                InetHost(()) => Ok(impls::InetHost.into()),
+                InetMasklen(()) => Ok(impls::InetMasklen.into()),
                 CastRecordToString(ty) => Ok(impls::CastRecordToString {
                     ty: ty.into_rust()?,
                 }
@@ -4968,6 +5276,9 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 MapLength(()) => Ok(impls::MapLength.into()),
                 Upper(()) => Ok(impls::Upper.into()),
                 Lower(()) => Ok(impls::Lower.into()),
+                Initcap(()) => Ok(impls::Initcap.into()),
+                Xxhash64String(()) => Ok(impls::Xxhash64String.into()),
+                Xxhash64Bytes(()) => Ok(impls::Xxhash64Bytes.into()),
                 Cos(()) => Ok(impls::Cos.into()),
                 Acos(()) => Ok(impls::Acos.into()),
                 Cosh(()) => Ok(impls::Cosh.into()),
@@ -4996,7 +5307,13 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                 }
                 PgColumnSize(()) => Ok(impls::PgColumnSize.into()),
                 MzRowSize(()) => Ok(impls::MzRowSize.into()),
+                MzHllSketch(()) => Ok(impls::MzHllSketch.into()),
+                MzHllEstimate(()) => Ok(impls::MzHllEstimate.into()),
                 MzTypeName(()) => Ok(impls::MzTypeName.into()),
+                IsInList(inner) => Ok(impls::IsInList {
+                    values: inner.values.into_rust()?,
+                }
+                .into()),
 
                 CastMzTimestampToString(()) => Ok(impls::CastMzTimestampToString.into()),
                 CastStringToMzTimestamp(()) => Ok(impls::CastStringToMzTimestamp.into()),
@@ -5102,6 +5419,25 @@ fn text_concat_variadic<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) ->
 fn pad_leading<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    pad(datums, temp_storage, true)
+}
+
+fn pad_trailing<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    pad(datums, temp_storage, false)
+}
+
+/// Pads the string in `datums[0]` to the length in `datums[1]` with the
+/// characters of `datums[2]`, or spaces if absent, at the start of the string if
+/// `leading` is set and at its end otherwise. Strings longer than the length are
+/// truncated.
+fn pad<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+    leading: bool,
 ) -> Result<Datum<'a>, EvalError> {
     let string = datums[0].unwrap_str();
 
@@ -5128,9 +5464,12 @@ fn pad_leading<'a>(
     let mut buf = String::with_capacity(len);
     if len == end_char {
         buf.push_str(&string[0..end_char_byte_offset]);
-    } else {
+    } else if leading {
         buf.extend(pad_string.chars().cycle().take(len - end_char));
         buf.push_str(string);
+    } else {
+        buf.push_str(string);
+        buf.extend(pad_string.chars().cycle().take(len - end_char));
     }
 
     Ok(Datum::String(temp_storage.push_string(buf)))
@@ -5186,6 +5525,33 @@ fn substr<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
     }
 }
 
+fn substr_bytes<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let b: &'a [u8] = datums[0].unwrap_bytes();
+
+    // Provided positions begin at 1, not 0.
+    let start = datums[1].unwrap_int64();
+    let end = if datums.len() == 3 {
+        let len = datums[2].unwrap_int64();
+        if len < 0 {
+            return Err(EvalError::InvalidParameterValue(
+                "negative substring length not allowed".to_owned(),
+            ));
+        }
+        start.saturating_add(len)
+    } else {
+        i64::MAX
+    };
+    let to_index = |pos: i64| {
+        usize::try_from(cmp::max(pos.saturating_sub(1), 0))
+            .map_or(b.len(), |i| cmp::min(i, b.len()))
+    };
+    let (start, end) = (to_index(start), to_index(end));
+    if end <= start {
+        return Ok(Datum::Bytes(&[]));
+    }
+    Ok(Datum::Bytes(&b[start..end]))
+}
+
 fn split_part<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
     let string = datums[0].unwrap_str();
     let delimiter = datums[1].unwrap_str();
@@ -5306,6 +5672,86 @@ fn regexp_match_static<'a>(
     Ok(temp_storage.push_unary_row(row))
 }
 
+fn regexp_replace_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let source = datums[0].unwrap_str();
+    let needle = datums[1].unwrap_str();
+    let replacement = datums[2].unwrap_str();
+    let flags = match datums.get(3) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let (limit, flags) = regexp_replace_parse_flags(flags);
+    let regex = build_regex(needle, &flags)?;
+    Ok(regexp_replace_static(
+        Datum::String(source),
+        Datum::String(replacement),
+        &regex,
+        limit,
+        temp_storage,
+    ))
+}
+
+/// Computes `regexp_replace` for a pattern that was compiled ahead of time.
+///
+/// A `limit` of zero replaces every match.
+pub fn regexp_replace_static<'a>(
+    source: Datum<'a>,
+    replacement: Datum<'a>,
+    regex: &regex::Regex,
+    limit: usize,
+    temp_storage: &'a RowArena,
+) -> Datum<'a> {
+    let source = source.unwrap_str();
+    let replacement = regexp_replacement(replacement.unwrap_str());
+    let replaced = regex.replacen(source, limit, replacement.as_str());
+    Datum::String(temp_storage.push_string(replaced.into_owned()))
+}
+
+/// Splits the `g` flag, which determines how many matches `regexp_replace`
+/// replaces, from the flags that determine how the regex is built.
+pub fn regexp_replace_parse_flags(flags: &str) -> (usize, String) {
+    if flags.contains('g') {
+        (0, flags.replace('g', ""))
+    } else {
+        (1, flags.to_owned())
+    }
+}
+
+/// Translates a PostgreSQL regex replacement string, which refers to capture
+/// groups as `\1` through `\9` and to the entire match as `\&`, into the
+/// syntax understood by [`regex::Regex::replace`].
+fn regexp_replacement(replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => out.push_str("$$"),
+            '\\' => match chars.peek() {
+                Some(&d @ '1'..='9') => {
+                    out.push_str("${");
+                    out.push(d);
+                    out.push('}');
+                    chars.next();
+                }
+                Some('&') => {
+                    out.push_str("${0}");
+                    chars.next();
+                }
+                Some('\\') => {
+                    out.push('\\');
+                    chars.next();
+                }
+                _ => out.push('\\'),
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError> {
     let mut regex = RegexBuilder::new(needle);
     for f in flags.chars() {
@@ -5408,6 +5854,21 @@ fn replace<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     )
 }
 
+fn translate<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
+    let from = datums[1].unwrap_str().chars().collect::<Vec<_>>();
+    let to = datums[2].unwrap_str().chars().collect::<Vec<_>>();
+    // Characters in `from` without a counterpart in `to` are removed.
+    let translated = datums[0]
+        .unwrap_str()
+        .chars()
+        .filter_map(|c| match from.iter().position(|f| *f == c) {
+            Some(i) => to.get(i).copied(),
+            None => Some(c),
+        })
+        .collect::<String>();
+    Datum::String(temp_storage.push_string(translated))
+}
+
 fn jsonb_build_array<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     if datums.iter().any(|datum| datum.is_null()) {
         // the inputs should all be valid jsonb types, but a casting error might produce a Datum::Null that needs to be propagated
@@ -5572,6 +6033,8 @@ where
         }
         Jsonb => Ok(strconv::format_jsonb(buf, JsonbRef::from_datum(d))),
         Uuid => Ok(strconv::format_uuid(buf, d.unwrap_uuid())),
+        Inet => Ok(strconv::format_inet(buf, d.unwrap_inet())),
+        Cidr => Ok(strconv::format_cidr(buf, d.unwrap_inet())),
         Record { fields, .. } => {
             let mut fields = fields.iter();
             strconv::format_record(buf, &d.unwrap_list(), |buf, d| {
@@ -5754,6 +6217,31 @@ fn create_range<'a>(
     }))
 }
 
+fn make_date<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let year = datums[0].unwrap_int64();
+    let month = datums[1].unwrap_int64();
+    let day = datums[2].unwrap_int64();
+    let out_of_range =
+        || EvalError::DateFieldValueOutOfRange(format!("{}-{:02}-{:02}", year, month, day));
+
+    // Like PostgreSQL, negative years are years BC, and there is no year zero.
+    let astronomical_year = match year {
+        0 => return Err(out_of_range()),
+        year if year < 0 => year + 1,
+        year => year,
+    };
+    let date = match (
+        i32::try_from(astronomical_year),
+        u32::try_from(month),
+        u32::try_from(day),
+    ) {
+        (Ok(year), Ok(month), Ok(day)) => NaiveDate::from_ymd_opt(year, month, day),
+        _ => None,
+    }
+    .ok_or_else(out_of_range)?;
+    Ok(Date::try_from(date)?.into())
+}
+
 // TODO(benesch): remove potentially dangerous usage of `as`.
 #[allow(clippy::as_conversions)]
 fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
@@ -6205,6 +6693,16 @@ fn digest_inner<'a>(
     Ok(Datum::Bytes(temp_storage.push_bytes(bytes)))
 }
 
+fn uuid_generate_v5<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let mut hasher = Sha1::new();
+    hasher.update(a.unwrap_uuid().as_bytes());
+    hasher.update(b.unwrap_str().as_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Datum::Uuid(uuid::Builder::from_sha1_bytes(bytes).into_uuid())
+}
+
 fn mz_render_typmod<'a>(
     oid: Datum<'a>,
     typmod: Datum<'a>,
@@ -6228,10 +6726,14 @@ pub enum VariadicFunc {
     Greatest,
     Least,
     Concat,
+    MakeDate,
     MakeTimestamp,
     PadLeading,
+    PadTrailing,
     Substr,
+    SubstrBytes,
     Replace,
+    Translate,
     JsonbBuildArray,
     JsonbBuildObject,
     ArrayCreate {
@@ -6257,6 +6759,7 @@ pub enum VariadicFunc {
     ListSliceLinear,
     SplitPart,
     RegexpMatch,
+    RegexpReplace,
     HmacString,
     HmacBytes,
     ErrorIfNull,
@@ -6293,10 +6796,14 @@ impl VariadicFunc {
             VariadicFunc::Greatest => greatest(datums, temp_storage, exprs),
             VariadicFunc::Least => least(datums, temp_storage, exprs),
             VariadicFunc::Concat => Ok(eager!(text_concat_variadic, temp_storage)),
+            VariadicFunc::MakeDate => eager!(make_date),
             VariadicFunc::MakeTimestamp => eager!(make_timestamp),
             VariadicFunc::PadLeading => eager!(pad_leading, temp_storage),
+            VariadicFunc::PadTrailing => eager!(pad_trailing, temp_storage),
             VariadicFunc::Substr => eager!(substr),
+            VariadicFunc::SubstrBytes => eager!(substr_bytes),
             VariadicFunc::Replace => Ok(eager!(replace, temp_storage)),
+            VariadicFunc::Translate => Ok(eager!(translate, temp_storage)),
             VariadicFunc::JsonbBuildArray => Ok(eager!(jsonb_build_array, temp_storage)),
             VariadicFunc::JsonbBuildObject => Ok(eager!(jsonb_build_object, temp_storage)),
             VariadicFunc::ArrayCreate {
@@ -6315,6 +6822,7 @@ impl VariadicFunc {
             VariadicFunc::ListSliceLinear => Ok(eager!(list_slice_linear, temp_storage)),
             VariadicFunc::SplitPart => eager!(split_part),
             VariadicFunc::RegexpMatch => eager!(regexp_match_dynamic, temp_storage),
+            VariadicFunc::RegexpReplace => eager!(regexp_replace_dynamic, temp_storage),
             VariadicFunc::HmacString => eager!(hmac_string, temp_storage),
             VariadicFunc::HmacBytes => eager!(hmac_bytes, temp_storage),
             VariadicFunc::ErrorIfNull => error_if_null(datums, temp_storage, exprs),
@@ -6343,10 +6851,14 @@ impl VariadicFunc {
             | VariadicFunc::And
             | VariadicFunc::Or => true,
 
-            VariadicFunc::MakeTimestamp
+            VariadicFunc::MakeDate
+            | VariadicFunc::MakeTimestamp
             | VariadicFunc::PadLeading
+            | VariadicFunc::PadTrailing
             | VariadicFunc::Substr
+            | VariadicFunc::SubstrBytes
             | VariadicFunc::Replace
+            | VariadicFunc::Translate
             | VariadicFunc::JsonbBuildArray
             | VariadicFunc::JsonbBuildObject
             | VariadicFunc::ArrayCreate { elem_type: _ }
@@ -6358,6 +6870,7 @@ impl VariadicFunc {
             | VariadicFunc::ListSliceLinear
             | VariadicFunc::SplitPart
             | VariadicFunc::RegexpMatch
+            | VariadicFunc::RegexpReplace
             | VariadicFunc::HmacString
             | VariadicFunc::HmacBytes
             | VariadicFunc::ErrorIfNull
@@ -6376,10 +6889,12 @@ impl VariadicFunc {
                 .reduce(|l, r| l.union(&r).unwrap())
                 .unwrap(),
             Concat => ScalarType::String.nullable(true),
+            MakeDate => ScalarType::Date.nullable(true),
             MakeTimestamp => ScalarType::Timestamp.nullable(true),
-            PadLeading => ScalarType::String.nullable(true),
+            PadLeading | PadTrailing => ScalarType::String.nullable(true),
             Substr => ScalarType::String.nullable(true),
-            Replace => ScalarType::String.nullable(true),
+            SubstrBytes => ScalarType::Bytes.nullable(true),
+            Replace | Translate => ScalarType::String.nullable(true),
             JsonbBuildArray | JsonbBuildObject => ScalarType::Jsonb.nullable(true),
             ArrayCreate { elem_type } => {
                 debug_assert!(
@@ -6427,6 +6942,7 @@ impl VariadicFunc {
             .nullable(false),
             SplitPart => ScalarType::String.nullable(in_nullable),
             RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpReplace => ScalarType::String.nullable(true),
             HmacString | HmacBytes => ScalarType::Bytes.nullable(true),
             ErrorIfNull => input_types[0].scalar_type.clone().nullable(false),
             DateBinTimestamp => ScalarType::Timestamp.nullable(true),
@@ -6525,10 +7041,13 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::Greatest => f.write_str("greatest"),
             VariadicFunc::Least => f.write_str("least"),
             VariadicFunc::Concat => f.write_str("concat"),
+            VariadicFunc::MakeDate => f.write_str("makedate"),
             VariadicFunc::MakeTimestamp => f.write_str("makets"),
             VariadicFunc::PadLeading => f.write_str("lpad"),
-            VariadicFunc::Substr => f.write_str("substr"),
+            VariadicFunc::PadTrailing => f.write_str("rpad"),
+            VariadicFunc::Substr | VariadicFunc::SubstrBytes => f.write_str("substr"),
             VariadicFunc::Replace => f.write_str("replace"),
+            VariadicFunc::Translate => f.write_str("translate"),
             VariadicFunc::JsonbBuildArray => f.write_str("jsonb_build_array"),
             VariadicFunc::JsonbBuildObject => f.write_str("jsonb_build_object"),
             VariadicFunc::ArrayCreate { .. } => f.write_str("array_create"),
@@ -6540,6 +7059,7 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::ListSliceLinear => f.write_str("list_slice_linear"),
             VariadicFunc::SplitPart => f.write_str("split_string"),
             VariadicFunc::RegexpMatch => f.write_str("regexp_match"),
+            VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
             VariadicFunc::HmacString | VariadicFunc::HmacBytes => f.write_str("hmac"),
             VariadicFunc::ErrorIfNull => f.write_str("error_if_null"),
             VariadicFunc::DateBinTimestamp => f.write_str("timestamp_bin"),
@@ -6576,10 +7096,14 @@ impl Arbitrary for VariadicFunc {
             Just(VariadicFunc::Greatest).boxed(),
             Just(VariadicFunc::Least).boxed(),
             Just(VariadicFunc::Concat).boxed(),
+            Just(VariadicFunc::MakeDate).boxed(),
             Just(VariadicFunc::MakeTimestamp).boxed(),
             Just(VariadicFunc::PadLeading).boxed(),
+            Just(VariadicFunc::PadTrailing).boxed(),
             Just(VariadicFunc::Substr).boxed(),
+            Just(VariadicFunc::SubstrBytes).boxed(),
             Just(VariadicFunc::Replace).boxed(),
+            Just(VariadicFunc::Translate).boxed(),
             Just(VariadicFunc::JsonbBuildArray).boxed(),
             Just(VariadicFunc::JsonbBuildObject).boxed(),
             ScalarType::arbitrary()
@@ -6601,6 +7125,7 @@ impl Arbitrary for VariadicFunc {
             Just(VariadicFunc::ListSliceLinear).boxed(),
             Just(VariadicFunc::SplitPart).boxed(),
             Just(VariadicFunc::RegexpMatch).boxed(),
+            Just(VariadicFunc::RegexpReplace).boxed(),
             Just(VariadicFunc::HmacString).boxed(),
             Just(VariadicFunc::HmacBytes).boxed(),
             Just(VariadicFunc::ErrorIfNull).boxed(),
@@ -6624,10 +7149,14 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::Greatest => Greatest(()),
             VariadicFunc::Least => Least(()),
             VariadicFunc::Concat => Concat(()),
+            VariadicFunc::MakeDate => MakeDate(()),
             VariadicFunc::MakeTimestamp => MakeTimestamp(()),
             VariadicFunc::PadLeading => PadLeading(()),
+            VariadicFunc::PadTrailing => PadTrailing(()),
             VariadicFunc::Substr => Substr(()),
+            VariadicFunc::SubstrBytes => SubstrBytes(()),
             VariadicFunc::Replace => Replace(()),
+            VariadicFunc::Translate => Translate(()),
             VariadicFunc::JsonbBuildArray => JsonbBuildArray(()),
             VariadicFunc::JsonbBuildObject => JsonbBuildObject(()),
             VariadicFunc::ArrayCreate { elem_type } => ArrayCreate(elem_type.into_proto()),
@@ -6641,6 +7170,7 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::ListSliceLinear => ListSliceLinear(()),
             VariadicFunc::SplitPart => SplitPart(()),
             VariadicFunc::RegexpMatch => RegexpMatch(()),
+            VariadicFunc::RegexpReplace => RegexpReplace(()),
             VariadicFunc::HmacString => HmacString(()),
             VariadicFunc::HmacBytes => HmacBytes(()),
             VariadicFunc::ErrorIfNull => ErrorIfNull(()),
@@ -6662,10 +7192,14 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 Greatest(()) => Ok(VariadicFunc::Greatest),
                 Least(()) => Ok(VariadicFunc::Least),
                 Concat(()) => Ok(VariadicFunc::Concat),
+                MakeDate(()) => Ok(VariadicFunc::MakeDate),
                 MakeTimestamp(()) => Ok(VariadicFunc::MakeTimestamp),
                 PadLeading(()) => Ok(VariadicFunc::PadLeading),
+                PadTrailing(()) => Ok(VariadicFunc::PadTrailing),
                 Substr(()) => Ok(VariadicFunc::Substr),
+                SubstrBytes(()) => Ok(VariadicFunc::SubstrBytes),
                 Replace(()) => Ok(VariadicFunc::Replace),
+                Translate(()) => Ok(VariadicFunc::Translate),
                 JsonbBuildArray(()) => Ok(VariadicFunc::JsonbBuildArray),
                 JsonbBuildObject(()) => Ok(VariadicFunc::JsonbBuildObject),
                 ArrayCreate(elem_type) => Ok(VariadicFunc::ArrayCreate {
@@ -6687,6 +7221,7 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 ListSliceLinear(()) => Ok(VariadicFunc::ListSliceLinear),
                 SplitPart(()) => Ok(VariadicFunc::SplitPart),
                 RegexpMatch(()) => Ok(VariadicFunc::RegexpMatch),
+                RegexpReplace(()) => Ok(VariadicFunc::RegexpReplace),
                 HmacString(()) => Ok(VariadicFunc::HmacString),
                 HmacBytes(()) => Ok(VariadicFunc::HmacBytes),
                 ErrorIfNull(()) => Ok(VariadicFunc::ErrorIfNull),
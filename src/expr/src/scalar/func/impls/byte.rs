@@ -7,7 +7,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::hash::Hasher;
+
+use mz_ore::cast::ReinterpretCast;
 use mz_repr::strconv;
+use twox_hash::XxHash64;
 
 use crate::EvalError;
 
@@ -35,3 +39,21 @@ sqlfunc!(
         i32::try_from(a.len()).or(Err(EvalError::Int32OutOfRange))
     }
 );
+
+sqlfunc!(
+    #[sqlname = "xxhash64"]
+    fn xxhash64_bytes<'a>(a: &'a [u8]) -> i64 {
+        // Use a fixed seed, so that the hash is stable across processes and
+        // versions and thus suitable for sharding.
+        let mut hasher = XxHash64::with_seed(0);
+        hasher.write(a);
+        i64::reinterpret_cast(hasher.finish())
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "mz_hll_estimate"]
+    fn mz_hll_estimate<'a>(a: Option<&'a [u8]>) -> Result<i64, EvalError> {
+        a.map(crate::hll::estimate).unwrap_or(Ok(0))
+    }
+);
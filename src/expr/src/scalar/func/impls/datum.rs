@@ -7,8 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_repr::{Datum, DatumList};
+use std::fmt;
 
+use mz_lowertest::MzReflect;
+use mz_ore::str::separated;
+use mz_repr::{ColumnType, Datum, DatumList, Row, ScalarType};
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
 use crate::EvalError;
 
 sqlfunc!(
@@ -52,3 +58,76 @@ sqlfunc!(
         i32::try_from(sz).or(Err(EvalError::Int32OutOfRange))
     }
 );
+
+sqlfunc!(
+    #[sqlname = "mz_hll_sketch"]
+    fn mz_hll_sketch<'a>(a: Datum<'a>) -> Option<Vec<u8>> {
+        match a {
+            Datum::Null => None,
+            datum => Some(crate::hll::sketch(Row::pack_slice(&[datum]).data())),
+        }
+    }
+);
+
+/// Tests whether a datum is equal to any of a list of non-null values.
+///
+/// This is equivalent to a disjunction of equalities, but looks the datum up
+/// in time logarithmic in the length of the list.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct IsInList {
+    /// The values to test against, each packed into its own row, sorted and
+    /// deduplicated.
+    pub values: Vec<Row>,
+}
+
+impl IsInList {
+    /// Constructs a test against the given non-null `values`.
+    pub fn new<'a, I>(values: I) -> IsInList
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        let mut values: Vec<Row> = values
+            .into_iter()
+            .inspect(|datum| assert!(!datum.is_null(), "IN list values must not be null"))
+            .map(|datum| Row::pack_slice(&[datum]))
+            .collect();
+        values.sort_by(|a, b| a.unpack_first().cmp(&b.unpack_first()));
+        values.dedup_by(|a, b| a.unpack_first() == b.unpack_first());
+        IsInList { values }
+    }
+}
+
+impl<'a> EagerUnaryFunc<'a> for IsInList {
+    type Input = Datum<'a>;
+    type Output = Option<bool>;
+
+    fn call(&self, a: Datum<'a>) -> Option<bool> {
+        if a.is_null() {
+            return None;
+        }
+        let found = self
+            .values
+            .binary_search_by(|value| value.unpack_first().cmp(&a))
+            .is_ok();
+        Some(found)
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Bool.nullable(input.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for IsInList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values = self.values.iter().map(|value| value.unpack_first());
+        write!(f, "IN ({})", separated(", ", values))
+    }
+}
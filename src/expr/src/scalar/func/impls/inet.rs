@@ -0,0 +1,87 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_lowertest::MzReflect;
+use mz_repr::adt::inet::Inet;
+use mz_repr::{strconv, ColumnType, ScalarType};
+
+use crate::scalar::func::EagerUnaryFunc;
+
+sqlfunc!(
+    #[sqlname = "inet_to_text"]
+    #[preserves_uniqueness = true]
+    fn cast_inet_to_string(a: Inet) -> String {
+        let mut buf = String::new();
+        strconv::format_inet(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "cidr_to_text"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastStringToCidr)]
+    fn cast_cidr_to_string(a: Inet) -> String {
+        let mut buf = String::new();
+        strconv::format_cidr(&mut buf, a);
+        buf
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "cidr_to_inet"]
+    #[preserves_uniqueness = true]
+    #[inverse = to_unary!(super::CastInetToCidr)]
+    fn cast_cidr_to_inet(a: Inet) -> Inet {
+        a
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "host"]
+    fn inet_host(a: Inet) -> String {
+        a.addr().to_string()
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "masklen"]
+    fn inet_masklen(a: Inet) -> i32 {
+        i32::from(a.prefix_len())
+    }
+);
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastInetToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastInetToCidr {
+    type Input = Inet;
+    type Output = Inet;
+
+    fn call(&self, a: Inet) -> Inet {
+        a.network()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+}
+
+impl fmt::Display for CastInetToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("inet_to_cidr")
+    }
+}
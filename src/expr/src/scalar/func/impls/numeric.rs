@@ -166,6 +166,13 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "scale"]
+    fn scale_numeric(a: Numeric) -> i32 {
+        i32::from(numeric::get_scale(&a))
+    }
+);
+
 sqlfunc!(
     #[sqlname = "numeric_to_smallint"]
     #[preserves_uniqueness = false]
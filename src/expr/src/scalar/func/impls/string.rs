@@ -23,6 +23,7 @@ use mz_ore::str::StrExt;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char::{format_str_trim, Char};
 use mz_repr::adt::date::Date;
+use mz_repr::adt::inet::Inet;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
@@ -235,6 +236,43 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "text_to_inet"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastInetToString)]
+    fn cast_string_to_inet<'a>(a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_inet(a).err_into()
+    }
+);
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToCidr {
+    type Input = &'a str;
+    type Output = Result<Inet, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_cidr(a).err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastCidrToString)
+    }
+}
+
+impl fmt::Display for CastStringToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_cidr")
+    }
+}
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
 pub struct CastStringToArray {
     // Target array's type.
@@ -760,6 +798,30 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    fn initcap<'a>(a: &'a str) -> String {
+        // Words are sequences of alphanumeric characters.
+        let mut out = String::with_capacity(a.len());
+        let mut in_word = false;
+        for c in a.chars() {
+            if in_word {
+                out.extend(c.to_lowercase());
+            } else {
+                out.extend(c.to_uppercase());
+            }
+            in_word = c.is_alphanumeric();
+        }
+        out
+    }
+);
+
+sqlfunc!(
+    #[sqlname = "xxhash64"]
+    fn xxhash64_string<'a>(a: &'a str) -> i64 {
+        super::xxhash64_bytes(a.as_bytes())
+    }
+);
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
 pub struct IsLikeMatch(pub like_pattern::Matcher);
 
@@ -778,7 +840,8 @@ impl<'a> EagerUnaryFunc<'a> for IsLikeMatch {
 
 impl fmt::Display for IsLikeMatch {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} ~~", self.0.pattern.quoted())
+        let op = if self.0.case_insensitive { "~~*" } else { "~~" };
+        write!(f, "{} {}", self.0.pattern.quoted(), op)
     }
 }
 
@@ -1137,6 +1137,34 @@ impl MirScalarExpr {
                                     e.typ(column_types).scalar_type,
                                 ),
                             };
+                        } else if *func == VariadicFunc::RegexpReplace
+                            && exprs[1].is_literal()
+                            && exprs.get(3).map_or(true, |e| e.is_literal())
+                        {
+                            let needle = exprs[1].as_literal_str().unwrap();
+                            let flags = match exprs.len() {
+                                4 => exprs[3].as_literal_str().unwrap(),
+                                _ => "",
+                            };
+                            let (limit, flags) = func::regexp_replace_parse_flags(flags);
+                            *e = match func::build_regex(needle, &flags) {
+                                Ok(regex) => {
+                                    let mut exprs = mem::take(exprs);
+                                    let replacement = exprs.swap_remove(2);
+                                    let source = exprs.swap_remove(0);
+                                    source.call_binary(
+                                        replacement,
+                                        BinaryFunc::RegexpReplace {
+                                            regex: Regex(regex),
+                                            limit,
+                                        },
+                                    )
+                                }
+                                Err(err) => MirScalarExpr::literal(
+                                    Err(err),
+                                    e.typ(column_types).scalar_type,
+                                ),
+                            };
                         } else if *func == VariadicFunc::ListIndex && is_list_create_call(&exprs[0])
                         {
                             // We are looking for ListIndex(ListCreate, literal), and eliminate
@@ -1211,6 +1239,47 @@ impl MirScalarExpr {
                                 }
                                 _ => {}
                             }
+                        } else if matches!(
+                            &**els,
+                            MirScalarExpr::If { cond: cond2, then: then2, .. }
+                                if **then2 == **then && !cond2.could_error()
+                        ) {
+                            // Merges adjacent arms of a CASE with the same result:
+                            // `if a then x else (if b then x else y)` --> `if (a OR b) then x else y`
+                            // A NULL `a` falls through to `b` in both forms. `b` is only
+                            // evaluated when `a` is not true in the original form, so we
+                            // only merge if `b` cannot error.
+                            if let MirScalarExpr::If {
+                                cond: cond2,
+                                els: els2,
+                                ..
+                            } = &mut **els
+                            {
+                                *e = cond
+                                    .take()
+                                    .or(cond2.take())
+                                    .if_then_else(then.take(), els2.take());
+                            }
+                        } else if matches!(
+                            &**then,
+                            MirScalarExpr::If { cond: cond2, els: els2, .. }
+                                if **els2 == **els && !cond2.could_error()
+                        ) {
+                            // `if a then (if b then x else y) else y` --> `if (a AND b) then x else y`
+                            // A NULL `a` or `b` selects `y` in both forms. As above, we only
+                            // merge if `b`, which is only evaluated when `a` is true, cannot
+                            // error.
+                            if let MirScalarExpr::If {
+                                cond: cond2,
+                                then: then2,
+                                ..
+                            } = &mut **then
+                            {
+                                *e = cond
+                                    .take()
+                                    .and(cond2.take())
+                                    .if_then_else(then2.take(), els.take());
+                            }
                         }
                     }
                 },
@@ -1841,6 +1910,8 @@ impl fmt::Display for MirScalarExpr {
                 }
                 if let Some(is) = func.is() {
                     write!(f, "({}) IS {}", expr, is)?;
+                } else if let UnaryFunc::IsInList(_) = func {
+                    write!(f, "({}) {}", expr, func)?;
                 } else {
                     write!(f, "{}({})", func, expr)?;
                 }
@@ -2193,6 +2264,7 @@ pub enum EvalError {
     TimestampCannotBeNan,
     TimestampOutOfRange,
     DateOutOfRange,
+    DateFieldValueOutOfRange(String),
     CharOutOfRange,
     IndexOutOfRange {
         provided: i32,
@@ -2288,6 +2360,9 @@ impl fmt::Display for EvalError {
             EvalError::TimestampCannotBeNan => f.write_str("timestamp cannot be NaN"),
             EvalError::TimestampOutOfRange => f.write_str("timestamp out of range"),
             EvalError::DateOutOfRange => f.write_str("date out of range"),
+            EvalError::DateFieldValueOutOfRange(date) => {
+                write!(f, "date field value out of range: {}", date)
+            }
             EvalError::CharOutOfRange => f.write_str("\"char\" out of range"),
             EvalError::IndexOutOfRange {
                 provided,
@@ -2526,6 +2601,7 @@ impl RustType<ProtoEvalError> for EvalError {
             EvalError::TimestampCannotBeNan => TimestampCannotBeNan(()),
             EvalError::TimestampOutOfRange => TimestampOutOfRange(()),
             EvalError::DateOutOfRange => DateOutOfRange(()),
+            EvalError::DateFieldValueOutOfRange(v) => DateFieldValueOutOfRange(v.clone()),
             EvalError::CharOutOfRange => CharOutOfRange(()),
             EvalError::IndexOutOfRange {
                 provided,
@@ -2636,6 +2712,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 TimestampCannotBeNan(()) => Ok(EvalError::TimestampCannotBeNan),
                 TimestampOutOfRange(()) => Ok(EvalError::TimestampOutOfRange),
                 DateOutOfRange(()) => Ok(EvalError::DateOutOfRange),
+                DateFieldValueOutOfRange(v) => Ok(EvalError::DateFieldValueOutOfRange(v)),
                 CharOutOfRange(()) => Ok(EvalError::CharOutOfRange),
                 IndexOutOfRange(v) => Ok(EvalError::IndexOutOfRange {
                     provided: v.provided,
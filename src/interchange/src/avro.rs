@@ -21,7 +21,9 @@ pub use self::encode::{
     encode_datums_as_avro, encode_debezium_transaction_unchecked, get_debezium_transaction_schema,
     AvroEncoder, AvroSchemaGenerator,
 };
-pub use self::schema::{parse_schema, schema_to_relationdesc, ConfluentAvroResolver};
+pub use self::schema::{
+    parse_schema, schema_to_relationdesc, ConfluentAvroResolver, IncompatibleSchemaError,
+};
 
 fn is_null(schema: &SchemaPieceOrNamed) -> bool {
     matches!(schema, SchemaPieceOrNamed::Piece(SchemaPiece::Null))
@@ -75,6 +77,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn nested_record() -> anyhow::Result<()> {
+        let schema = r#"{
+            "type": "record",
+            "name": "test",
+            "fields": [
+                { "name": "f1", "type": "int" },
+                {
+                    "name": "f2",
+                    "type": {
+                        "type": "record",
+                        "name": "inner",
+                        "fields": [
+                            { "name": "a", "type": "long" },
+                            { "name": "b", "type": ["null", "string"] }
+                        ]
+                    }
+                },
+                {
+                    "name": "f3",
+                    "type": [
+                        "null",
+                        {
+                            "type": "record",
+                            "name": "optional_inner",
+                            "fields": [{ "name": "c", "type": "boolean" }]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let desc = schema_to_relationdesc(parse_schema(schema)?)?;
+        let expected_desc = RelationDesc::empty()
+            .with_column("f1", ScalarType::Int32.nullable(false))
+            .with_column(
+                "f2",
+                ScalarType::Record {
+                    fields: vec![
+                        ("a".into(), ScalarType::Int64.nullable(false)),
+                        ("b".into(), ScalarType::String.nullable(true)),
+                    ],
+                    custom_id: None,
+                }
+                .nullable(false),
+            )
+            .with_column(
+                "f3",
+                ScalarType::Record {
+                    fields: vec![("c".into(), ScalarType::Bool.nullable(false))],
+                    custom_id: None,
+                }
+                .nullable(true),
+            );
+
+        assert_eq!(desc, expected_desc);
+        Ok(())
+    }
+
     #[test]
     /// Test that primitive Avro Schema types are allow Datums to be correctly
     /// serialized into Avro Values.
@@ -23,8 +23,8 @@ use uuid::Uuid;
 use mz_avro::error::{DecodeError, Error as AvroError};
 use mz_avro::{
     define_unexpected, give_value, AvroArrayAccess, AvroDecode, AvroDeserializer, AvroMapAccess,
-    AvroRead, AvroRecordAccess, GeneralDeserializer, StatefulAvroDecodable, ValueDecoder,
-    ValueOrReader,
+    AvroRead, AvroRecordAccess, BlockIter, GeneralDeserializer, StatefulAvroDecodable,
+    ValueDecoder, ValueOrReader,
 };
 use mz_ore::result::ResultExt;
 use mz_repr::adt::jsonb::JsonbPacker;
@@ -44,9 +44,11 @@ pub struct Decoder<C> {
 
 #[cfg(test)]
 mod tests {
+    use mz_avro::types::Value;
+    use mz_avro::Writer;
     use mz_repr::{Datum, Row};
 
-    use crate::avro::Decoder;
+    use crate::avro::{parse_schema, Decoder};
 
     #[tokio::test]
     async fn test_error_followed_by_success() {
@@ -68,6 +70,41 @@ mod tests {
             Row::pack([Datum::Int32(0), Datum::Int32(0)])
         );
     }
+
+    #[tokio::test]
+    async fn test_decode_ocf() {
+        let writer_schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [{"name": "f1", "type": "int"}, {"name": "f2", "type": "string"}]
+}"#;
+        let reader_schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [{"name": "f1", "type": "int"}]
+}"#;
+        let mut writer = Writer::new(parse_schema(writer_schema).unwrap(), vec![]);
+        for (f1, f2) in [(1, "a"), (2, "b")] {
+            writer
+                .append(Value::Record(vec![
+                    ("f1".into(), Value::Int(f1)),
+                    ("f2".into(), Value::String(f2.into())),
+                ]))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+        let file = writer.into_inner();
+
+        let mut decoder =
+            Decoder::<Box<mz_ccsr::Client>>::new(reader_schema, None, "Test".to_string(), false)
+                .unwrap();
+        assert_eq!(
+            decoder.decode_ocf(&file).unwrap(),
+            vec![Row::pack([Datum::Int32(1)]), Row::pack([Datum::Int32(2)])]
+        );
+        // Files without an Object Container File header are rejected.
+        assert!(decoder.decode_ocf(&[0, 0]).is_err());
+    }
 }
 
 impl<C: Deref<Target = mz_ccsr::Client>> Decoder<C> {
@@ -126,6 +163,42 @@ impl<C: Deref<Target = mz_ccsr::Client>> Decoder<C> {
         );
         Ok(self.row_buf.clone())
     }
+
+    /// Decodes the records of the Avro Object Container File `bytes` into
+    /// `Row`s.
+    ///
+    /// The writer's schema is read from the header of the file, and resolved
+    /// against the reader's schema.
+    pub fn decode_ocf(&mut self, bytes: &[u8]) -> anyhow::Result<Vec<Row>> {
+        let blocks = BlockIter::with_schema(self.csr_avro.reader_schema(), bytes)
+            .context("unable to read Avro object container file header")?;
+        let schema = blocks.schema().clone();
+        let mut rows = vec![];
+        for block in blocks {
+            let block = block.context("unable to read Avro object container file block")?;
+            let mut block_bytes = &block.bytes[..];
+            for _ in 0..block.len {
+                let mut packer = self.row_buf.packer();
+                let dec = AvroFlatDecoder {
+                    packer: &mut packer,
+                    buf: &mut self.buf1,
+                    is_top: true,
+                };
+                let dsr = GeneralDeserializer {
+                    schema: schema.top_node(),
+                };
+                dsr.deserialize(&mut block_bytes, dec)
+                    .with_context(|| format!("unable to decode row {}", rows.len()))?;
+                rows.push(self.row_buf.clone());
+            }
+        }
+        trace!(
+            "[customer-data] Decoded {} rows from Avro object container file in {}",
+            rows.len(),
+            self.debug_name
+        );
+        Ok(rows)
+    }
 }
 
 pub struct AvroStringDecoder<'a> {
@@ -21,7 +21,7 @@ use mz_avro::Schema;
 use mz_ore::cast::CastFrom;
 use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::numeric::{self, NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
-use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+use mz_repr::{strconv, ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 
 use crate::encode::{column_names_and_types, Encode, TypedDatum};
 use crate::envelopes::{self, ENVELOPE_CUSTOM_NAMES};
@@ -345,6 +345,16 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                 }
                 ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
                 ScalarType::Uuid => Value::Uuid(datum.unwrap_uuid()),
+                ScalarType::Inet => {
+                    let mut buf = String::new();
+                    strconv::format_inet(&mut buf, datum.unwrap_inet());
+                    Value::String(buf)
+                }
+                ScalarType::Cidr => {
+                    let mut buf = String::new();
+                    strconv::format_cidr(&mut buf, datum.unwrap_inet());
+                    Value::String(buf)
+                }
                 ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
                     let list = match ty {
                         ScalarType::Array(_) | ScalarType::Int2Vector => {
@@ -312,6 +312,11 @@ impl<C: Deref<Target = mz_ccsr::Client>> ConfluentAvroResolver<C> {
         })
     }
 
+    /// Returns the schema that records are expected to be decoded with.
+    pub fn reader_schema(&self) -> &Schema {
+        &self.reader_schema
+    }
+
     pub async fn resolve<'a, 'b>(
         &'a mut self,
         mut bytes: &'b [u8],
@@ -424,6 +429,24 @@ impl<C: Deref<Target = mz_ccsr::Client>> SchemaCache<C> {
                 v.insert(result)
             }
         };
-        entry.as_ref().map_err(|e| anyhow::Error::new(e.clone()))
+        entry
+            .as_ref()
+            .map_err(|e| anyhow::Error::new(IncompatibleSchemaError(e.clone())))
+    }
+}
+
+/// The error returned when a writer schema from the schema registry cannot be
+/// parsed or resolved against the reader schema.
+///
+/// Unlike failures to fetch a schema, this error is permanent for a given
+/// schema ID.
+#[derive(Debug, Clone)]
+pub struct IncompatibleSchemaError(AvroError);
+
+impl fmt::Display for IncompatibleSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
+
+impl std::error::Error for IncompatibleSchemaError {}
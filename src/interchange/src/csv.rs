@@ -0,0 +1,191 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use bytes::BytesMut;
+
+use mz_repr::{RelationDesc, RelationType, Row};
+
+use crate::encode::Encode;
+
+/// Manages encoding of rows as CSV records.
+///
+/// Each row is encoded as a single record without a line terminator. Datums
+/// are formatted as they are in the text format of the PostgreSQL wire
+/// protocol. A null datum is encoded as the null string, unquoted, which is
+/// empty by default. Fields that are equal to the null string or that contain
+/// the delimiter, the quote character or a line break are quoted, so that
+/// values that spell the null string remain distinguishable from nulls.
+pub struct CsvEncoder {
+    key_type: Option<RelationType>,
+    value_type: RelationType,
+    delimiter: u8,
+    quote: u8,
+    null: String,
+}
+
+impl CsvEncoder {
+    /// Returns an encoder for rows with the given key and value descriptions.
+    ///
+    /// `null` must not contain the delimiter, the quote character or a line
+    /// break.
+    pub fn new(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        delimiter: u8,
+        quote: u8,
+        null: String,
+    ) -> Self {
+        CsvEncoder {
+            key_type: key_desc.map(|desc| desc.typ().clone()),
+            value_type: value_desc.typ().clone(),
+            delimiter,
+            quote,
+            null,
+        }
+    }
+
+    pub fn encode_row(&self, row: Row, typ: &RelationType) -> Vec<u8> {
+        let mut out = vec![];
+        let mut buf = BytesMut::new();
+        for (i, field) in mz_pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+            if i > 0 {
+                out.push(self.delimiter);
+            }
+            match field {
+                None => out.extend(self.null.as_bytes()),
+                Some(field) => {
+                    buf.clear();
+                    field.encode_text(&mut buf);
+                    self.push_field(&mut out, &buf);
+                }
+            }
+        }
+        out
+    }
+
+    /// Encodes a header record that holds the given field names.
+    pub fn encode_header(&self, names: &[String]) -> Vec<u8> {
+        let mut out = vec![];
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                out.push(self.delimiter);
+            }
+            self.push_field(&mut out, name.as_bytes());
+        }
+        out
+    }
+
+    /// Appends a non-null field, quoting it if necessary.
+    fn push_field(&self, out: &mut Vec<u8>, field: &[u8]) {
+        let needs_quotes = field == self.null.as_bytes()
+            || field
+                .iter()
+                .any(|b| *b == self.delimiter || *b == self.quote || *b == b'\n' || *b == b'\r');
+        if needs_quotes {
+            out.push(self.quote);
+            for b in field {
+                if *b == self.quote {
+                    out.push(self.quote);
+                }
+                out.push(*b);
+            }
+            out.push(self.quote);
+        } else {
+            out.extend(field);
+        }
+    }
+}
+
+impl Encode for CsvEncoder {
+    fn get_format_name(&self) -> &str {
+        "csv"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_row(row, self.key_type.as_ref().expect("key type must exist"))
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_row(row, &self.value_type)
+    }
+}
+
+impl fmt::Debug for CsvEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CsvEncoder")
+            .field("delimiter", &char::from(self.delimiter))
+            .field("quote", &char::from(self.quote))
+            .field("null", &self.null)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, ScalarType};
+
+    use super::*;
+
+    fn csv_encoder(null: &str) -> CsvEncoder {
+        let desc = RelationDesc::empty()
+            .with_column("a", ScalarType::Int32.nullable(true))
+            .with_column("b", ScalarType::String.nullable(true));
+        CsvEncoder::new(None, desc, b',', b'"', null.into())
+    }
+
+    fn encode(encoder: &CsvEncoder, datums: &[Datum]) -> String {
+        let row = Row::pack_slice(datums);
+        String::from_utf8(encoder.encode_value_unchecked(row)).unwrap()
+    }
+
+    #[test]
+    fn test_encode_row() {
+        let encoder = csv_encoder("");
+        let cases = [
+            (vec![Datum::Int32(1), Datum::String("a")], "1,a"),
+            (vec![Datum::Null, Datum::Null], ","),
+            (vec![Datum::Int32(1), Datum::String("")], r#"1,"""#),
+            (vec![Datum::Int32(1), Datum::String("a,b")], r#"1,"a,b""#),
+            (
+                vec![Datum::Int32(1), Datum::String(r#"say "hi""#)],
+                r#"1,"say ""hi""""#,
+            ),
+            (vec![Datum::Int32(1), Datum::String("a\nb")], "1,\"a\nb\""),
+        ];
+        for (datums, expected) in cases {
+            assert_eq!(encode(&encoder, &datums), expected, "{:?}", datums);
+        }
+    }
+
+    #[test]
+    fn test_encode_row_null_string() {
+        let encoder = csv_encoder("NULL");
+        let cases = [
+            (vec![Datum::Null, Datum::Null], "NULL,NULL"),
+            (vec![Datum::Int32(1), Datum::String("")], "1,"),
+            (vec![Datum::Int32(1), Datum::String("NULL")], r#"1,"NULL""#),
+            (vec![Datum::Int32(1), Datum::String("null")], "1,null"),
+        ];
+        for (datums, expected) in cases {
+            assert_eq!(encode(&encoder, &datums), expected, "{:?}", datums);
+        }
+    }
+
+    #[test]
+    fn test_encode_header() {
+        let encoder = csv_encoder("");
+        let names = ["a".to_string(), "b c".into(), "d,e".into(), "".into()];
+        assert_eq!(
+            String::from_utf8(encoder.encode_header(&names)).unwrap(),
+            r#"a,b c,"d,e","""#
+        );
+    }
+}
@@ -15,7 +15,7 @@ use serde_json::{json, Map};
 use mz_repr::adt::char;
 use mz_repr::adt::jsonb::JsonbRef;
 use mz_repr::adt::numeric::{NUMERIC_AGG_MAX_PRECISION, NUMERIC_DATUM_MAX_PRECISION};
-use mz_repr::{ColumnName, ColumnType, Datum, GlobalId, RelationDesc, ScalarType};
+use mz_repr::{strconv, ColumnName, ColumnType, Datum, GlobalId, RelationDesc, ScalarType};
 
 use crate::encode::{column_names_and_types, Encode, TypedDatum};
 use crate::envelopes;
@@ -154,6 +154,16 @@ impl ToJson for TypedDatum<'_> {
                 }
                 ScalarType::Jsonb => JsonbRef::from_datum(datum).to_serde_json(),
                 ScalarType::Uuid => json!(datum.unwrap_uuid()),
+                ScalarType::Inet => {
+                    let mut buf = String::new();
+                    strconv::format_inet(&mut buf, datum.unwrap_inet());
+                    serde_json::Value::String(buf)
+                }
+                ScalarType::Cidr => {
+                    let mut buf = String::new();
+                    strconv::format_cidr(&mut buf, datum.unwrap_inet());
+                    serde_json::Value::String(buf)
+                }
                 ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
                     let list = match typ.scalar_type {
                         ScalarType::Array(_) | ScalarType::Int2Vector => {
@@ -270,6 +280,7 @@ fn build_row_schema_field(
             "type": "string",
             "logicalType": "uuid",
         }),
+        ScalarType::Inet | ScalarType::Cidr => json!("string"),
         ty @ (ScalarType::Array(..) | ScalarType::Int2Vector | ScalarType::List { .. }) => {
             let inner = build_row_schema_field(
                 type_namer,
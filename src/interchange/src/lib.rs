@@ -80,6 +80,7 @@
 
 pub mod avro;
 mod confluent;
+pub mod csv;
 pub mod encode;
 pub mod envelopes;
 pub mod json;
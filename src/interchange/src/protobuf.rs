@@ -39,7 +39,7 @@ impl DecodedDescriptors {
             )
         })?;
         let mut seen_messages = HashSet::new();
-        seen_messages.insert(message_descriptor.name().to_owned());
+        seen_messages.insert(message_descriptor.full_name().to_owned());
         let mut columns = vec![];
         for field in message_descriptor.fields() {
             let name = ColumnName::from(field.name());
@@ -151,17 +151,19 @@ fn derive_inner_type(
         Kind::Bytes => Ok(ScalarType::Bytes.nullable(false)),
         Kind::Enum(_) => Ok(ScalarType::String.nullable(false)),
         Kind::Message(m) => {
-            if seen_messages.contains(m.name()) {
+            // Track messages by their fully qualified name, as distinct
+            // nested messages may share the same short name.
+            if seen_messages.contains(m.full_name()) {
                 bail!("Recursive types are not supported: {}", m.name());
             }
-            seen_messages.insert(m.name().to_owned());
+            seen_messages.insert(m.full_name().to_owned());
             let mut fields = Vec::with_capacity(m.fields().len());
             for field in m.fields() {
                 let column_name = ColumnName::from(field.name());
                 let column_type = derive_column_type(seen_messages, &field)?;
                 fields.push((column_name, column_type))
             }
-            seen_messages.remove(m.name());
+            seen_messages.remove(m.full_name());
             let ty = ScalarType::Record {
                 fields,
                 custom_id: None,
@@ -96,6 +96,7 @@ pub use format::Format;
 pub use types::{
     Type, TypeConversionError, TypeFromOidError, ANYCOMPATIBLELIST, ANYCOMPATIBLEMAP, LIST, MAP,
 };
+pub use value::inet::Inet;
 pub use value::interval::Interval;
 pub use value::jsonb::Jsonb;
 pub use value::numeric::Numeric;
@@ -27,12 +27,16 @@ pub const TYPE_BYTEA_ARRAY_OID: u32 = 1001;
 pub const TYPE_BYTEA_OID: u32 = 17;
 pub const TYPE_CHAR_ARRAY_OID: u32 = 1002;
 pub const TYPE_CHAR_OID: u32 = 18;
+pub const TYPE_CIDR_ARRAY_OID: u32 = 651;
+pub const TYPE_CIDR_OID: u32 = 650;
 pub const TYPE_DATE_ARRAY_OID: u32 = 1182;
 pub const TYPE_DATE_OID: u32 = 1082;
 pub const TYPE_FLOAT4_ARRAY_OID: u32 = 1021;
 pub const TYPE_FLOAT4_OID: u32 = 700;
 pub const TYPE_FLOAT8_ARRAY_OID: u32 = 1022;
 pub const TYPE_FLOAT8_OID: u32 = 701;
+pub const TYPE_INET_ARRAY_OID: u32 = 1041;
+pub const TYPE_INET_OID: u32 = 869;
 pub const TYPE_INT2_ARRAY_OID: u32 = 1005;
 pub const TYPE_INT2_OID: u32 = 21;
 pub const TYPE_INT2_VECTOR_ARRAY_OID: u32 = 1006;
@@ -274,3 +278,30 @@ pub const FUNC_MZ_NOW_OID: u32 = 16_560;
 pub const FUNC_MAX_MZ_TIMESTAMP_OID: u32 = 16_561;
 pub const FUNC_MIN_MZ_TIMESTAMP_OID: u32 = 16_562;
 pub const FUNC_DATE_FROM_TEXT: u32 = 16_563;
+pub const FUNC_UUID_GENERATE_V5: u32 = 16_564;
+pub const FUNC_XXHASH64_STRING_OID: u32 = 16_565;
+pub const FUNC_XXHASH64_BYTES_OID: u32 = 16_566;
+pub const FUNC_CEILING_F32_OID: u32 = 16_567;
+pub const FUNC_MZ_TIME_BUCKET_UNIX_EPOCH_TS_OID: u32 = 16_568;
+pub const FUNC_MZ_TIME_BUCKET_UNIX_EPOCH_TSTZ_OID: u32 = 16_569;
+pub const FUNC_MZ_TIME_BUCKET_TS_OID: u32 = 16_570;
+pub const FUNC_MZ_TIME_BUCKET_TSTZ_OID: u32 = 16_571;
+pub const FUNC_MZ_HASH_SAMPLE_OID: u32 = 16_572;
+pub const FUNC_MZ_RANDOM_OID: u32 = 16_573;
+pub const FUNC_APPROX_COUNT_DISTINCT_OID: u32 = 16_574;
+pub const FUNC_MZ_HLL_SKETCH_OID: u32 = 16_575;
+pub const FUNC_MZ_HLL_UNION_OID: u32 = 16_576;
+pub const FUNC_MZ_HLL_ESTIMATE_OID: u32 = 16_577;
+pub const FUNC_APPROX_PERCENTILE_OID: u32 = 16_578;
+pub const FUNC_MEDIAN_OID: u32 = 16_579;
+pub const FUNC_PERCENTILE_CONT_OID: u32 = 16_580;
+pub const FUNC_ARG_MAX_OID: u32 = 16_581;
+pub const FUNC_ARG_MIN_OID: u32 = 16_582;
+pub const FUNC_MZ_MAX_RECORD_OID: u32 = 16_583;
+pub const FUNC_MZ_MIN_RECORD_OID: u32 = 16_584;
+pub const FUNC_MZ_FIRST_VALUE_OID: u32 = 16_585;
+pub const FUNC_MZ_LAST_VALUE_OID: u32 = 16_586;
+pub const FUNC_MZ_CUSTOM_AGGREGATE_OID: u32 = 16_587;
+pub const FUNC_MZ_COUNT_DIFFS_OID: u32 = 16_588;
+pub const FUNC_ROUND_HALF_EVEN_OID: u32 = 16_589;
+pub const FUNC_ROUND_HALF_EVEN_SCALE_OID: u32 = 16_590;
@@ -135,6 +135,10 @@ pub enum Type {
     },
     /// A universally unique identifier.
     Uuid,
+    /// An IPv4 or IPv6 host address with an optional network prefix.
+    Inet,
+    /// An IPv4 or IPv6 network.
+    Cidr,
     /// A function name.
     RegProc,
     /// A type name.
@@ -511,6 +515,8 @@ impl Type {
             postgres_types::Type::TIMESTAMP => Type::Timestamp { precision: None },
             postgres_types::Type::TIMESTAMPTZ => Type::TimestampTz { precision: None },
             postgres_types::Type::UUID => Type::Uuid,
+            postgres_types::Type::INET => Type::Inet,
+            postgres_types::Type::CIDR => Type::Cidr,
             postgres_types::Type::REGCLASS => Type::RegClass,
             postgres_types::Type::REGPROC => Type::RegProc,
             postgres_types::Type::REGTYPE => Type::RegType,
@@ -548,6 +554,8 @@ impl Type {
                 Type::Array(Box::new(Type::TimestampTz { precision: None }))
             }
             postgres_types::Type::UUID_ARRAY => Type::Array(Box::new(Type::Uuid)),
+            postgres_types::Type::INET_ARRAY => Type::Array(Box::new(Type::Inet)),
+            postgres_types::Type::CIDR_ARRAY => Type::Array(Box::new(Type::Cidr)),
             postgres_types::Type::VARCHAR_ARRAY => {
                 Type::Array(Box::new(Type::VarChar { max_length: None }))
             }
@@ -670,6 +678,8 @@ impl Type {
                 Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP_ARRAY,
                 Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
+                Type::Inet => &postgres_types::Type::INET_ARRAY,
+                Type::Cidr => &postgres_types::Type::CIDR_ARRAY,
                 Type::RegClass => &postgres_types::Type::REGCLASS_ARRAY,
                 Type::RegProc => &postgres_types::Type::REGPROC_ARRAY,
                 Type::RegType => &postgres_types::Type::REGTYPE_ARRAY,
@@ -712,6 +722,8 @@ impl Type {
             Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ,
             Type::Uuid => &postgres_types::Type::UUID,
+            Type::Inet => &postgres_types::Type::INET,
+            Type::Cidr => &postgres_types::Type::CIDR,
             Type::RegClass => &postgres_types::Type::REGCLASS,
             Type::RegProc => &postgres_types::Type::REGPROC,
             Type::RegType => &postgres_types::Type::REGTYPE,
@@ -843,6 +855,8 @@ impl Type {
             | Type::Timestamp { precision: None }
             | Type::TimestampTz { precision: None }
             | Type::Uuid
+            | Type::Inet
+            | Type::Cidr
             | Type::MzTimestamp
             | Type::VarChar { max_length: None }
             | Type::Range { .. } => None,
@@ -882,6 +896,8 @@ impl Type {
             Type::Timestamp { .. } => 8,
             Type::TimestampTz { .. } => 8,
             Type::Uuid => 16,
+            Type::Inet => -1,
+            Type::Cidr => -1,
             Type::RegClass => 4,
             Type::RegProc => 4,
             Type::RegType => 4,
@@ -1005,6 +1021,8 @@ impl TryFrom<&Type> for ScalarType {
                 Err(TypeConversionError::UnsupportedType(typ.clone()))
             }
             Type::Uuid => Ok(ScalarType::Uuid),
+            Type::Inet => Ok(ScalarType::Inet),
+            Type::Cidr => Ok(ScalarType::Cidr),
             Type::RegClass => Ok(ScalarType::RegClass),
             Type::RegProc => Ok(ScalarType::RegProc),
             Type::RegType => Ok(ScalarType::RegType),
@@ -1147,6 +1165,8 @@ impl From<&ScalarType> for Type {
             ScalarType::Timestamp => Type::Timestamp { precision: None },
             ScalarType::TimestampTz => Type::TimestampTz { precision: None },
             ScalarType::Uuid => Type::Uuid,
+            ScalarType::Inet => Type::Inet,
+            ScalarType::Cidr => Type::Cidr,
             ScalarType::Numeric { max_scale } => Type::Numeric {
                 constraints: Some(NumericConstraints {
                     max_precision: i32::from(NUMERIC_DATUM_MAX_PRECISION),
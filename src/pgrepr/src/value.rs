@@ -28,8 +28,9 @@ use mz_repr::strconv::{self, Nestable};
 use mz_repr::{Datum, RelationType, Row, RowArena, ScalarType};
 
 use crate::types::{UINT2, UINT4, UINT8};
-use crate::{Format, Interval, Jsonb, Numeric, Type, UInt2, UInt4, UInt8};
+use crate::{Format, Inet, Interval, Jsonb, Numeric, Type, UInt2, UInt4, UInt8};
 
+pub mod inet;
 pub mod interval;
 pub mod jsonb;
 pub mod numeric;
@@ -98,6 +99,10 @@ pub enum Value {
     VarChar(String),
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// An IPv4 or IPv6 host address with an optional network prefix.
+    Inet(Inet),
+    /// An IPv4 or IPv6 network.
+    Cidr(Inet),
     /// A small int vector.
     Int2Vector {
         /// The elements of the vector.
@@ -149,6 +154,8 @@ impl Value {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
             (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
+            (Datum::Inet(i), ScalarType::Inet) => Some(Value::Inet(Inet(i))),
+            (Datum::Inet(i), ScalarType::Cidr) => Some(Value::Cidr(Inet(i))),
             (Datum::Array(array), ScalarType::Array(elem_type)) => {
                 let dims = array.dims().into_iter().collect();
                 let elements = array
@@ -273,6 +280,7 @@ impl Value {
             Value::BpChar(s) => Datum::String(buf.push_string(s.trim_end().into())),
             Value::VarChar(s) => Datum::String(buf.push_string(s)),
             Value::Uuid(u) => Datum::Uuid(u),
+            Value::Inet(i) | Value::Cidr(i) => Datum::Inet(i.0),
             Value::Numeric(n) => Datum::Numeric(n.0),
             Value::MzTimestamp(t) => Datum::MzTimestamp(t),
             Value::Range(range) => {
@@ -357,6 +365,8 @@ impl Value {
             Value::Timestamp(ts) => strconv::format_timestamp(buf, ts),
             Value::TimestampTz(ts) => strconv::format_timestamptz(buf, ts),
             Value::Uuid(u) => strconv::format_uuid(buf, *u),
+            Value::Inet(i) => strconv::format_inet(buf, i.0),
+            Value::Cidr(i) => strconv::format_cidr(buf, i.0),
             Value::Numeric(d) => strconv::format_numeric(buf, &d.0),
             Value::MzTimestamp(t) => strconv::format_mz_timestamp(buf, *t),
             Value::Range(range) => strconv::format_range(buf, range, |buf, elem| match elem {
@@ -468,6 +478,8 @@ impl Value {
             Value::Timestamp(ts) => ts.to_sql(&PgType::TIMESTAMP, buf),
             Value::TimestampTz(ts) => ts.to_sql(&PgType::TIMESTAMPTZ, buf),
             Value::Uuid(u) => u.to_sql(&PgType::UUID, buf),
+            Value::Inet(i) => i.to_sql(&PgType::INET, buf),
+            Value::Cidr(i) => i.to_sql(&PgType::CIDR, buf),
             Value::Numeric(a) => a.to_sql(&PgType::NUMERIC, buf),
             Value::MzTimestamp(t) => t.to_string().to_sql(&PgType::TEXT, buf),
             Value::Range(_) => Err("binary encodings of range types not yet implemented".into()),
@@ -562,6 +574,8 @@ impl Value {
             Type::Timestamp { .. } => Value::Timestamp(strconv::parse_timestamp(s)?),
             Type::TimestampTz { .. } => Value::TimestampTz(strconv::parse_timestamptz(s)?),
             Type::Uuid => Value::Uuid(Uuid::parse_str(s)?),
+            Type::Inet => Value::Inet(Inet(strconv::parse_inet(s)?)),
+            Type::Cidr => Value::Cidr(Inet(strconv::parse_cidr(s)?)),
             Type::MzTimestamp => Value::MzTimestamp(strconv::parse_mz_timestamp(s)?),
             Type::Range { element_type } => Value::Range(strconv::parse_range(s, |elem_text| {
                 Value::decode_text(element_type, elem_text.as_bytes()).map(Box::new)
@@ -618,6 +632,8 @@ impl Value {
                 )?))
             }
             Type::Uuid => Uuid::from_sql(ty.inner(), raw).map(Value::Uuid),
+            Type::Inet => Inet::from_sql(ty.inner(), raw).map(Value::Inet),
+            Type::Cidr => Inet::from_sql(ty.inner(), raw).map(Value::Cidr),
             Type::MzTimestamp => {
                 let s = String::from_sql(ty.inner(), raw)?;
                 let t: mz_repr::Timestamp = s.parse()?;
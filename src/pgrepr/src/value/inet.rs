@@ -0,0 +1,96 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use byteorder::ReadBytesExt;
+use bytes::{BufMut, BytesMut};
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use mz_repr::adt::inet::Inet as ReprInet;
+
+/// The address family of IPv4 addresses in the PostgreSQL binary format.
+const PGSQL_AF_INET: u8 = 2;
+/// The address family of IPv6 addresses in the PostgreSQL binary format.
+const PGSQL_AF_INET6: u8 = 3;
+
+/// A wrapper for the `repr` crate's [`Inet`](mz_repr::adt::inet::Inet) type
+/// that can be serialized to and deserialized from the PostgreSQL binary
+/// format of the `inet` and `cidr` types.
+#[derive(Debug, Clone)]
+pub struct Inet(pub ReprInet);
+
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ToSql for Inet {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        // Postgres represents network addresses as the address family, the
+        // prefix length, a flag indicating whether the value is a `cidr`, the
+        // number of address bytes, and finally the address bytes.
+        //
+        // Postgres implementation: https://github.com/postgres/postgres/blob/REL_15_1/src/backend/utils/adt/network.c#L265
+        let is_cidr = u8::from(*ty == Type::CIDR);
+        match self.0.addr() {
+            IpAddr::V4(addr) => {
+                out.put_u8(PGSQL_AF_INET);
+                out.put_u8(self.0.prefix_len());
+                out.put_u8(is_cidr);
+                out.put_u8(4);
+                out.put_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                out.put_u8(PGSQL_AF_INET6);
+                out.put_u8(self.0.prefix_len());
+                out.put_u8(is_cidr);
+                out.put_u8(16);
+                out.put_slice(&addr.octets());
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Inet {
+    fn from_sql(ty: &Type, mut raw: &'a [u8]) -> Result<Inet, Box<dyn Error + Sync + Send>> {
+        let family = raw.read_u8()?;
+        let prefix_len = raw.read_u8()?;
+        let _is_cidr = raw.read_u8()?;
+        let nb = raw.read_u8()?;
+        let addr = match (family, nb, raw) {
+            (PGSQL_AF_INET, 4, raw) => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(raw)?)),
+            (PGSQL_AF_INET6, 16, raw) => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(raw)?)),
+            _ => return Err(format!("invalid {} address family: {}", ty.name(), family).into()),
+        };
+        let inet = ReprInet::new(addr, prefix_len)?;
+        if *ty == Type::CIDR && !inet.is_network() {
+            return Err("invalid cidr value: value has bits set to right of mask".into());
+        }
+        Ok(Inet(inet))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+}
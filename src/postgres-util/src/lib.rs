@@ -307,6 +307,12 @@ pub async fn drop_replication_slots(config: Config, slots: &[&str]) -> Result<()
     Ok(())
 }
 
+/// Quotes a PostgreSQL identifier, so that it may be interpolated into a
+/// query regardless of its case or of the characters it contains.
+pub fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
 /// Configures an optional tunnel for use when connecting to a PostgreSQL
 /// database.
 #[derive(Debug, PartialEq, Clone)]
@@ -0,0 +1,238 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Network address types.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// An IPv4 or IPv6 host address together with the length of the prefix that
+/// identifies its network, e.g. `192.168.0.1/24`.
+///
+/// This is the representation of both the `inet` and the `cidr` type. Values
+/// of the `cidr` type are additionally guaranteed to be networks, i.e., to have
+/// no bits set to the right of the prefix; see [`Inet::is_network`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Inet {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl Inet {
+    /// Constructs a new address with the given prefix length.
+    ///
+    /// Returns an error if `prefix_len` exceeds the number of bits in `addr`.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Inet, InvalidInetError> {
+        if prefix_len > max_prefix_len(addr) {
+            return Err(InvalidInetError::PrefixLenOutOfRange);
+        }
+        Ok(Inet { addr, prefix_len })
+    }
+
+    /// Constructs a new address whose prefix covers all of its bits, i.e., a
+    /// single host.
+    pub fn host(addr: IpAddr) -> Inet {
+        Inet {
+            addr,
+            prefix_len: max_prefix_len(addr),
+        }
+    }
+
+    /// Returns the address, including any bits to the right of the prefix.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the network prefix in bits.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the number of bits in the address, i.e., 32 for IPv4 addresses
+    /// and 128 for IPv6 addresses.
+    pub fn max_prefix_len(&self) -> u8 {
+        max_prefix_len(self.addr)
+    }
+
+    /// Returns the network that contains this address, i.e., this address
+    /// with all bits to the right of the prefix cleared.
+    pub fn network(&self) -> Inet {
+        let bits = self.bits() & mask(self.prefix_len);
+        let addr = match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(
+                u32::try_from(bits >> 96).expect("IPv4 addresses have 32 bits"),
+            )),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        };
+        Inet {
+            addr,
+            prefix_len: self.prefix_len,
+        }
+    }
+
+    /// Reports whether this address has no bits set to the right of its
+    /// prefix, as required of values of the `cidr` type.
+    pub fn is_network(&self) -> bool {
+        self.network().addr == self.addr
+    }
+
+    /// Reports whether the network of this address strictly contains `other`,
+    /// i.e., whether `other` has a longer prefix and lies within this network.
+    ///
+    /// This is PostgreSQL's `>>` operator.
+    pub fn contains(&self, other: &Inet) -> bool {
+        self.prefix_len < other.prefix_len && self.contains_or_equals(other)
+    }
+
+    /// Reports whether the network of this address contains or equals
+    /// `other`.
+    ///
+    /// This is PostgreSQL's `>>=` operator.
+    pub fn contains_or_equals(&self, other: &Inet) -> bool {
+        self.is_ipv4() == other.is_ipv4()
+            && self.prefix_len <= other.prefix_len
+            && (self.bits() ^ other.bits()) & mask(self.prefix_len) == 0
+    }
+
+    fn is_ipv4(&self) -> bool {
+        matches!(self.addr, IpAddr::V4(_))
+    }
+
+    /// Returns the bits of the address, aligned to the most significant bit.
+    fn bits(&self) -> u128 {
+        match self.addr {
+            IpAddr::V4(addr) => u128::from(u32::from(addr)) << 96,
+            IpAddr::V6(addr) => u128::from(addr),
+        }
+    }
+}
+
+/// Addresses are ordered like in PostgreSQL: IPv4 addresses before IPv6
+/// addresses, then by the bits of their common prefix, then by prefix length,
+/// and finally by all of their bits.
+impl Ord for Inet {
+    fn cmp(&self, other: &Inet) -> Ordering {
+        let common = mask(std::cmp::min(self.prefix_len, other.prefix_len));
+        other
+            .is_ipv4()
+            .cmp(&self.is_ipv4())
+            .then_with(|| (self.bits() & common).cmp(&(other.bits() & common)))
+            .then_with(|| self.prefix_len.cmp(&other.prefix_len))
+            .then_with(|| self.bits().cmp(&other.bits()))
+    }
+}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Inet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats the address with its prefix length, like a `cidr` value.
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+/// An error while constructing an [`Inet`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum InvalidInetError {
+    /// The prefix length exceeds the number of bits in the address.
+    PrefixLenOutOfRange,
+}
+
+impl fmt::Display for InvalidInetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidInetError::PrefixLenOutOfRange => {
+                f.write_str("network prefix length out of range")
+            }
+        }
+    }
+}
+
+impl Error for InvalidInetError {}
+
+fn max_prefix_len(addr: IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// Returns a mask of the `prefix_len` most significant bits.
+fn mask(prefix_len: u8) -> u128 {
+    match prefix_len {
+        0 => 0,
+        n => !0 << (128 - u32::from(n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inet(s: &str, prefix_len: u8) -> Inet {
+        Inet::new(s.parse().unwrap(), prefix_len).unwrap()
+    }
+
+    #[test]
+    fn test_inet_new() {
+        assert!(Inet::new("10.0.0.1".parse().unwrap(), 32).is_ok());
+        assert!(Inet::new("10.0.0.1".parse().unwrap(), 33).is_err());
+        assert!(Inet::new("::1".parse().unwrap(), 128).is_ok());
+        assert!(Inet::new("::1".parse().unwrap(), 129).is_err());
+    }
+
+    #[test]
+    fn test_inet_network() {
+        assert_eq!(inet("10.1.2.3", 8).network(), inet("10.0.0.0", 8));
+        assert_eq!(inet("10.1.2.3", 0).network(), inet("0.0.0.0", 0));
+        assert_eq!(inet("2001:db8::1", 32).network(), inet("2001:db8::", 32));
+        assert!(inet("10.0.0.0", 8).is_network());
+        assert!(!inet("10.0.0.1", 8).is_network());
+    }
+
+    #[test]
+    fn test_inet_contains() {
+        let net = inet("10.0.0.0", 8);
+        assert!(net.contains(&inet("10.1.2.3", 32)));
+        assert!(net.contains(&inet("10.1.0.0", 16)));
+        assert!(!net.contains(&net));
+        assert!(net.contains_or_equals(&net));
+        assert!(!net.contains(&inet("11.0.0.1", 32)));
+        assert!(!inet("10.1.0.0", 16).contains(&net));
+        assert!(!inet("::", 0).contains(&inet("10.0.0.1", 32)));
+    }
+
+    #[test]
+    fn test_inet_ord() {
+        let mut addrs = vec![
+            inet("::1", 128),
+            inet("10.0.0.5", 32),
+            inet("10.1.0.0", 8),
+            inet("9.255.255.255", 32),
+            inet("10.0.0.0", 8),
+        ];
+        addrs.sort();
+        assert_eq!(
+            addrs,
+            vec![
+                inet("9.255.255.255", 32),
+                inet("10.0.0.0", 8),
+                inet("10.1.0.0", 8),
+                inet("10.0.0.5", 32),
+                inet("::1", 128),
+            ]
+        );
+    }
+}
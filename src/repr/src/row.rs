@@ -13,6 +13,7 @@ use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::mem::{size_of, transmute};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str;
 
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc};
@@ -33,6 +34,7 @@ use crate::adt::array::{
     Array, ArrayDimension, ArrayDimensions, InvalidArrayError, MAX_ARRAY_DIMENSIONS,
 };
 use crate::adt::date::Date;
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
@@ -400,6 +402,7 @@ enum Tag {
     UInt64,
     MzTimestamp,
     Range,
+    Inet,
 }
 
 // --------------------------------------------------------------------------------
@@ -566,6 +569,18 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
         | Tag::StringLong
         | Tag::StringHuge => read_lengthed_datum(data, offset, tag),
         Tag::Uuid => Datum::Uuid(Uuid::from_bytes(read_byte_array(data, offset))),
+        Tag::Inet => {
+            // See the comment in `push_datum` for details on the encoding of
+            // network addresses.
+            let family = read_byte(data, offset);
+            let prefix_len = read_byte(data, offset);
+            let addr = match family {
+                4 => IpAddr::V4(Ipv4Addr::from(read_byte_array::<4>(data, offset))),
+                6 => IpAddr::V6(Ipv6Addr::from(read_byte_array::<16>(data, offset))),
+                _ => panic!("unexpected inet family {}", family),
+            };
+            Datum::Inet(Inet::new(addr, prefix_len).expect("unexpected inet prefix length"))
+        }
         Tag::Array => {
             // See the comment in `Row::push_array` for details on the encoding
             // of arrays.
@@ -813,6 +828,23 @@ where
             data.push(Tag::Uuid.into());
             data.extend_from_slice(u.as_bytes());
         }
+        Datum::Inet(inet) => {
+            // Network addresses are encoded as the address family (4 or 6),
+            // the prefix length, and the 4 or 16 bytes of the address.
+            data.push(Tag::Inet.into());
+            match inet.addr() {
+                IpAddr::V4(addr) => {
+                    data.push(4);
+                    data.push(inet.prefix_len());
+                    data.extend_from_slice(&addr.octets());
+                }
+                IpAddr::V6(addr) => {
+                    data.push(6);
+                    data.push(inet.prefix_len());
+                    data.extend_from_slice(&addr.octets());
+                }
+            }
+        }
         Datum::Array(array) => {
             // See the comment in `Row::push_array` for details on the encoding
             // of arrays.
@@ -971,6 +1003,10 @@ pub fn datum_size(datum: &Datum) -> usize {
             1 + bytes_for_length + string.len()
         }
         Datum::Uuid(_) => 1 + size_of::<uuid::Bytes>(),
+        Datum::Inet(inet) => match inet.addr() {
+            IpAddr::V4(_) => 3 + 4,
+            IpAddr::V6(_) => 3 + 16,
+        },
         Datum::Array(array) => {
             1 + size_of::<u8>()
                 + array.dims.data.len()
@@ -11,6 +11,8 @@
 //!
 //! See row.proto for details.
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
 use bytes::BufMut;
 use chrono::Timelike;
 use dec::Decimal;
@@ -21,13 +23,14 @@ use mz_ore::cast::CastFrom;
 use mz_proto::{ProtoType, RustType, TryFromProtoError};
 
 use crate::adt::array::ArrayDimension;
+use crate::adt::inet::Inet;
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{Range, RangeInner, RangeLowerBound, RangeUpperBound};
 use crate::chrono::ProtoNaiveTime;
 use crate::row::proto_datum::DatumType;
 use crate::row::{
     ProtoArray, ProtoArrayDimension, ProtoDatum, ProtoDatumOther, ProtoDict, ProtoDictElement,
-    ProtoNumeric, ProtoRange, ProtoRangeInner, ProtoRow,
+    ProtoInet, ProtoNumeric, ProtoRange, ProtoRangeInner, ProtoRow,
 };
 use crate::{Datum, Row, RowPacker};
 
@@ -130,6 +133,13 @@ impl<'a> From<Datum<'a>> for ProtoDatum {
             }
             Datum::JsonNull => DatumType::Other(ProtoDatumOther::JsonNull.into()),
             Datum::Uuid(x) => DatumType::Uuid(x.as_bytes().to_vec()),
+            Datum::Inet(x) => DatumType::Inet(ProtoInet {
+                addr: match x.addr() {
+                    IpAddr::V4(addr) => addr.octets().to_vec(),
+                    IpAddr::V6(addr) => addr.octets().to_vec(),
+                },
+                prefix_len: x.prefix_len().into(),
+            }),
             Datum::MzTimestamp(x) => DatumType::MzTimestamp(x.into()),
             Datum::Dummy => DatumType::Other(ProtoDatumOther::Dummy.into()),
             Datum::Null => DatumType::Other(ProtoDatumOther::Null.into()),
@@ -198,6 +208,21 @@ impl RowPacker<'_> {
                 let u = Uuid::from_slice(x).map_err(|err| err.to_string())?;
                 self.push(Datum::Uuid(u));
             }
+            Some(DatumType::Inet(x)) => {
+                let addr = match x.addr.len() {
+                    4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(&x.addr[..]).unwrap())),
+                    16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(&x.addr[..]).unwrap())),
+                    len => return Err(format!("inet field stored with {} address bytes", len)),
+                };
+                let prefix_len = u8::try_from(x.prefix_len).map_err(|_| {
+                    format!(
+                        "inet field stored with out of range prefix length: {}",
+                        x.prefix_len
+                    )
+                })?;
+                let inet = Inet::new(addr, prefix_len).map_err(|err| err.to_string())?;
+                self.push(Datum::Inet(inet));
+            }
             Some(DatumType::Date(x)) => self.push(Datum::Date(x.clone().into_rust()?)),
             Some(DatumType::Time(x)) => self.push(Datum::Time(x.clone().into_rust()?)),
             Some(DatumType::Timestamp(x)) => self.push(Datum::Timestamp(x.clone().into_rust()?)),
@@ -327,10 +352,13 @@ impl RustType<ProtoRow> for Row {
 
 #[cfg(test)]
 mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
     use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
     use uuid::Uuid;
 
     use crate::adt::array::ArrayDimension;
+    use crate::adt::inet::Inet;
     use crate::adt::interval::Interval;
     use crate::adt::numeric::Numeric;
     use crate::adt::timestamp::CheckedTimestamp;
@@ -388,6 +416,8 @@ mod tests {
             Datum::from(Numeric::nan()),
             Datum::JsonNull,
             Datum::Uuid(Uuid::from_u128(30)),
+            Datum::Inet(Inet::new(Ipv4Addr::new(10, 0, 0, 1).into(), 8).unwrap()),
+            Datum::Inet(Inet::host(Ipv6Addr::LOCALHOST.into())),
             Datum::Dummy,
             Datum::Null,
         ]);
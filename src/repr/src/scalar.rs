@@ -14,6 +14,7 @@
 use std::fmt::{self, Debug, Write};
 use std::hash::Hash;
 use std::iter;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ops::Add;
 
 use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
@@ -33,6 +34,7 @@ use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use crate::adt::array::{Array, ArrayDimension};
 use crate::adt::char::{Char, CharLength};
 use crate::adt::date::Date;
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
@@ -123,6 +125,8 @@ pub enum Datum<'a> {
     JsonNull,
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// An IPv4 or IPv6 address with a network prefix.
+    Inet(Inet),
     MzTimestamp(crate::Timestamp),
     /// A range of values, e.g. [-1, 1).
     Range(Range<DatumNested<'a>>),
@@ -658,6 +662,19 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the network address value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Inet`].
+    #[track_caller]
+    pub fn unwrap_inet(&self) -> Inet {
+        match self {
+            Datum::Inet(i) => *i,
+            _ => panic!("Datum::unwrap_inet called on {:?}", self),
+        }
+    }
+
     /// Unwraps the array value within this datum.
     ///
     /// # Panics
@@ -809,6 +826,9 @@ impl<'a> Datum<'a> {
                     (Datum::String(_), _) => false,
                     (Datum::Uuid(_), ScalarType::Uuid) => true,
                     (Datum::Uuid(_), _) => false,
+                    (Datum::Inet(_), ScalarType::Inet) => true,
+                    (Datum::Inet(inet), ScalarType::Cidr) => inet.is_network(),
+                    (Datum::Inet(_), _) => false,
                     (Datum::Array(array), ScalarType::Array(t)) => {
                         array.elements.iter().all(|e| match e {
                             Datum::Null => true,
@@ -1034,6 +1054,11 @@ impl<'a> From<Uuid> for Datum<'a> {
         Datum::Uuid(uuid)
     }
 }
+impl<'a> From<Inet> for Datum<'a> {
+    fn from(inet: Inet) -> Datum<'a> {
+        Datum::Inet(inet)
+    }
+}
 impl<'a> From<crate::Timestamp> for Datum<'a> {
     fn from(ts: crate::Timestamp) -> Datum<'a> {
         Datum::MzTimestamp(ts)
@@ -1111,6 +1136,7 @@ impl fmt::Display for Datum<'_> {
                 f.write_str("\"")
             }
             Datum::Uuid(u) => write!(f, "{}", u),
+            Datum::Inet(i) => write!(f, "{}", i),
             Datum::Array(array) => {
                 f.write_str("{")?;
                 write_delimited(f, ", ", &array.elements, |f, e| write!(f, "{}", e))?;
@@ -1220,6 +1246,11 @@ pub enum ScalarType {
     Jsonb,
     /// The type of [`Datum::Uuid`].
     Uuid,
+    /// The type of [`Datum::Inet`].
+    Inet,
+    /// Stored as [`Datum::Inet`], but restricted to networks, i.e., to
+    /// addresses without bits set to the right of the prefix.
+    Cidr,
     /// The type of [`Datum::Array`].
     ///
     /// Elements within the array are of the specified type. It is illegal for
@@ -1314,6 +1345,8 @@ impl RustType<ProtoScalarType> for ScalarType {
                 ScalarType::String => String(()),
                 ScalarType::Jsonb => Jsonb(()),
                 ScalarType::Uuid => Uuid(()),
+                ScalarType::Inet => Inet(()),
+                ScalarType::Cidr => Cidr(()),
                 ScalarType::Oid => Oid(()),
                 ScalarType::RegProc => RegProc(()),
                 ScalarType::RegType => RegType(()),
@@ -1382,6 +1415,8 @@ impl RustType<ProtoScalarType> for ScalarType {
             String(()) => Ok(ScalarType::String),
             Jsonb(()) => Ok(ScalarType::Jsonb),
             Uuid(()) => Ok(ScalarType::Uuid),
+            Inet(()) => Ok(ScalarType::Inet),
+            Cidr(()) => Ok(ScalarType::Cidr),
             Oid(()) => Ok(ScalarType::Oid),
             RegProc(()) => Ok(ScalarType::RegProc),
             RegType(()) => Ok(ScalarType::RegType),
@@ -1541,6 +1576,7 @@ impl_datum_type_copy!(NaiveTime, Time);
 impl_datum_type_copy!(CheckedTimestamp<NaiveDateTime>, Timestamp);
 impl_datum_type_copy!(CheckedTimestamp<DateTime<Utc>>, TimestampTz);
 impl_datum_type_copy!(Uuid, Uuid);
+impl_datum_type_copy!(Inet, Inet);
 impl_datum_type_copy!('a, &'a str, String);
 impl_datum_type_copy!('a, &'a [u8], Bytes);
 impl_datum_type_copy!(crate::Timestamp, MzTimestamp);
@@ -2542,6 +2578,22 @@ impl<'a> ScalarType {
                 Datum::Uuid(Uuid::from_u128(u128::MAX)),
             ])
         });
+        static INET: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::host(IpAddr::V4(Ipv4Addr::UNSPECIFIED))),
+                Datum::Inet(Inet::host(IpAddr::V4(Ipv4Addr::BROADCAST))),
+                Datum::Inet(Inet::host(IpAddr::V6(Ipv6Addr::UNSPECIFIED))),
+                Datum::Inet(Inet::host(IpAddr::V6(Ipv6Addr::from(u128::MAX)))),
+            ])
+        });
+        static CIDR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0).unwrap()),
+                Datum::Inet(Inet::host(IpAddr::V4(Ipv4Addr::BROADCAST))),
+                Datum::Inet(Inet::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0).unwrap()),
+                Datum::Inet(Inet::host(IpAddr::V6(Ipv6Addr::from(u128::MAX)))),
+            ])
+        });
         static ARRAY: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
         static LIST: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
         static RECORD: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
@@ -2582,6 +2634,8 @@ impl<'a> ScalarType {
             ScalarType::VarChar { .. } => (*STRING).iter(),
             ScalarType::Jsonb => (*JSONB).iter(),
             ScalarType::Uuid => (*UUID).iter(),
+            ScalarType::Inet => (*INET).iter(),
+            ScalarType::Cidr => (*CIDR).iter(),
             ScalarType::Array(_) => (*ARRAY).iter(),
             ScalarType::List { .. } => (*LIST).iter(),
             ScalarType::Record { .. } => (*RECORD).iter(),
@@ -2630,6 +2684,8 @@ impl<'a> ScalarType {
             ScalarType::VarChar { max_length: None },
             ScalarType::Jsonb,
             ScalarType::Uuid,
+            ScalarType::Inet,
+            ScalarType::Cidr,
             ScalarType::Oid,
             ScalarType::RegProc,
             ScalarType::RegType,
@@ -2693,6 +2749,8 @@ impl Arbitrary for ScalarType {
                 .boxed(),
             Just(ScalarType::Jsonb).boxed(),
             Just(ScalarType::Uuid).boxed(),
+            Just(ScalarType::Inet).boxed(),
+            Just(ScalarType::Cidr).boxed(),
             Just(ScalarType::Oid).boxed(),
             Just(ScalarType::RegProc).boxed(),
             Just(ScalarType::RegType).boxed(),
@@ -53,6 +53,7 @@ use mz_proto::{RustType, TryFromProtoError};
 use crate::adt::array::ArrayDimension;
 use crate::adt::date::Date;
 use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
@@ -723,6 +724,57 @@ where
     Nestable::Yes
 }
 
+/// Parses an `inet` value of the form `address[/prefix_len]`.
+///
+/// If the prefix length is omitted, the value describes a single host.
+pub fn parse_inet(s: &str) -> Result<Inet, ParseError> {
+    parse_inet_inner(s).ok_or_else(|| ParseError::invalid_input_syntax("inet", s))
+}
+
+fn parse_inet_inner(s: &str) -> Option<Inet> {
+    let s = s.trim();
+    match s.split_once('/') {
+        None => Some(Inet::host(s.parse().ok()?)),
+        Some((addr, prefix_len)) => Inet::new(addr.parse().ok()?, prefix_len.parse().ok()?).ok(),
+    }
+}
+
+/// Parses a `cidr` value of the form `address[/prefix_len]`.
+///
+/// Unlike [`parse_inet`], this rejects addresses with bits set to the right of
+/// the prefix.
+pub fn parse_cidr(s: &str) -> Result<Inet, ParseError> {
+    let inet = parse_inet_inner(s).ok_or_else(|| ParseError::invalid_input_syntax("cidr", s))?;
+    if !inet.is_network() {
+        return Err(ParseError::invalid_input_syntax("cidr", s)
+            .with_details("value has bits set to right of mask"));
+    }
+    Ok(inet)
+}
+
+/// Formats an `inet` value, omitting the prefix length if the value describes
+/// a single host.
+pub fn format_inet<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    if inet.prefix_len() == inet.max_prefix_len() {
+        write!(buf, "{}", inet.addr());
+    } else {
+        write!(buf, "{}", inet);
+    }
+    Nestable::Yes
+}
+
+/// Formats a `cidr` value, which always includes the prefix length.
+pub fn format_cidr<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", inet);
+    Nestable::Yes
+}
+
 fn format_nanos_to_micros<F>(buf: &mut F, nanos: u32)
 where
     F: FormatBuffer,
@@ -1842,6 +1894,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_format_inet() {
+        let cases = [
+            ("10.0.0.1", "10.0.0.1", "10.0.0.1/32"),
+            (" 10.0.0.1/8 ", "10.0.0.1/8", "10.0.0.1/8"),
+            ("10.0.0.0/8", "10.0.0.0/8", "10.0.0.0/8"),
+            ("::1", "::1", "::1/128"),
+            ("2001:db8::/32", "2001:db8::/32", "2001:db8::/32"),
+        ];
+        for (input, expect_inet, expect_cidr) in cases {
+            let inet = parse_inet(input).unwrap();
+            let mut buf = String::new();
+            format_inet(&mut buf, inet);
+            assert_eq!(buf, expect_inet);
+            let mut buf = String::new();
+            format_cidr(&mut buf, inet);
+            assert_eq!(buf, expect_cidr);
+        }
+
+        for input in [
+            "",
+            "10.0.0",
+            "10.0.0.1/",
+            "10.0.0.1/33",
+            "::1/129",
+            "10.0.0.1/-1",
+        ] {
+            assert!(parse_inet(input).is_err(), "{} should not parse", input);
+        }
+        assert!(parse_cidr("10.0.0.0/8").is_ok());
+        assert_eq!(
+            parse_cidr("10.0.0.1/8").unwrap_err().to_string(),
+            "invalid input syntax for type cidr: value has bits set to right of mask: \"10.0.0.1/8\""
+        );
+    }
+
     #[test]
     fn test_format_nanos_to_micros() {
         let cases: Vec<(u32, &str)> = vec![
@@ -123,7 +123,7 @@ where
     R: fmt::Debug + Send,
 {
     async fn send(&mut self, cmd: C) -> Result<(), anyhow::Error> {
-        let cmd_parts = self.state.split_command(cmd);
+        let cmd_parts = self.state.split_command(cmd)?;
         for (shard, cmd_part) in self.parts.iter_mut().zip(cmd_parts) {
             if let Some(cmd) = cmd_part {
                 shard.send(cmd).await?;
@@ -170,7 +170,9 @@ pub trait Partitionable<C, R> {
 /// amalgamates responses from multiple partitions.
 pub trait PartitionedState<C, R>: fmt::Debug + Send {
     /// Splits a command into multiple partitions.
-    fn split_command(&mut self, command: C) -> Vec<Option<C>>;
+    ///
+    /// Returns an error if the command cannot be split across the partitions.
+    fn split_command(&mut self, command: C) -> Result<Vec<Option<C>>, anyhow::Error>;
 
     /// Absorbs a response from a single partition.
     ///
@@ -139,6 +139,7 @@ impl_display_t!(ProtobufSchema);
 pub enum CsrConfigOptionName {
     AvroKeyFullname,
     AvroValueFullname,
+    IncompatibleSchemaPolicy,
 }
 
 impl AstDisplay for CsrConfigOptionName {
@@ -146,6 +147,7 @@ impl AstDisplay for CsrConfigOptionName {
         f.write_str(match self {
             CsrConfigOptionName::AvroKeyFullname => "AVRO KEY FULLNAME",
             CsrConfigOptionName::AvroValueFullname => "AVRO VALUE FULLNAME",
+            CsrConfigOptionName::IncompatibleSchemaPolicy => "INCOMPATIBLE SCHEMA POLICY",
         })
     }
 }
@@ -333,15 +335,63 @@ pub enum Format<T: AstInfo> {
     Bytes,
     Avro(AvroSchema<T>),
     Protobuf(ProtobufSchema<T>),
-    Regex(String),
+    Regex {
+        regex: String,
+        /// The types of named capture groups whose values are not text.
+        column_types: Vec<RegexColumnType<T>>,
+        on_mismatch: RegexMismatch,
+    },
     Csv {
-        columns: CsvColumns,
+        /// The columns of the data. Sinks only specify them to write a header.
+        columns: Option<CsvColumns>,
         delimiter: char,
+        quote: char,
+        /// The string that encodes nulls, which only sinks specify.
+        null: Option<String>,
     },
     Json,
     Text,
 }
 
+/// `name type`: the type of the values of a named capture group of a regex
+/// format.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RegexColumnType<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for RegexColumnType<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(RegexColumnType);
+
+/// What a regex format does with values that do not match its regex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegexMismatch {
+    /// `ON MISMATCH SKIP`: the value is ignored.
+    Skip,
+    /// `ON MISMATCH NULL`: the value is decoded as a row of nulls.
+    Null,
+    /// `ON MISMATCH ERROR`: the value is decoded as an error.
+    Error,
+}
+
+impl AstDisplay for RegexMismatch {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            RegexMismatch::Skip => f.write_str("SKIP"),
+            RegexMismatch::Null => f.write_str("NULL"),
+            RegexMismatch::Error => f.write_str("ERROR"),
+        }
+    }
+}
+impl_display!(RegexMismatch);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CsvColumns {
     /// `WITH count COLUMNS`
@@ -467,20 +517,51 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_str("PROTOBUF ");
                 f.write_node(inner);
             }
-            Self::Regex(regex) => {
+            Self::Regex {
+                regex,
+                column_types,
+                on_mismatch,
+            } => {
                 f.write_str("REGEX '");
                 f.write_node(&display::escape_single_quote_string(regex));
                 f.write_str("'");
+                if !column_types.is_empty() {
+                    f.write_str(" TYPES (");
+                    f.write_node(&display::comma_separated(column_types));
+                    f.write_str(")");
+                }
+                if *on_mismatch != RegexMismatch::Skip {
+                    f.write_str(" ON MISMATCH ");
+                    f.write_node(on_mismatch);
+                }
             }
-            Self::Csv { columns, delimiter } => {
-                f.write_str("CSV WITH ");
-                f.write_node(columns);
+            Self::Csv {
+                columns,
+                delimiter,
+                quote,
+                null,
+            } => {
+                f.write_str("CSV");
+                if let Some(columns) = columns {
+                    f.write_str(" WITH ");
+                    f.write_node(columns);
+                }
 
                 if *delimiter != ',' {
                     f.write_str(" DELIMITED BY '");
                     f.write_node(&display::escape_single_quote_string(&delimiter.to_string()));
                     f.write_str("'");
                 }
+                if *quote != '"' {
+                    f.write_str(" QUOTE '");
+                    f.write_node(&display::escape_single_quote_string(&quote.to_string()));
+                    f.write_str("'");
+                }
+                if let Some(null) = null {
+                    f.write_str(" NULL '");
+                    f.write_node(&display::escape_single_quote_string(null));
+                    f.write_str("'");
+                }
             }
             Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
@@ -695,6 +776,328 @@ impl<T: AstInfo> AstDisplay for PostgresConnectionOption<T> {
 }
 impl_display_t!(PostgresConnectionOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MySqlConnectionOptionName {
+    Host,
+    Password,
+    Port,
+    SslCertificateAuthority,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for MySqlConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MySqlConnectionOptionName::Host => "HOST",
+            MySqlConnectionOptionName::Password => "PASSWORD",
+            MySqlConnectionOptionName::Port => "PORT",
+            MySqlConnectionOptionName::SslCertificateAuthority => "SSL CERTIFICATE AUTHORITY",
+            MySqlConnectionOptionName::SslMode => "SSL MODE",
+            MySqlConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(MySqlConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... MYSQL`.
+pub struct MySqlConnectionOption<T: AstInfo> {
+    pub name: MySqlConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MySqlConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MySqlConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SqlServerConnectionOptionName {
+    Database,
+    Host,
+    Password,
+    Port,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for SqlServerConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConnectionOptionName::Database => "DATABASE",
+            SqlServerConnectionOptionName::Host => "HOST",
+            SqlServerConnectionOptionName::Password => "PASSWORD",
+            SqlServerConnectionOptionName::Port => "PORT",
+            SqlServerConnectionOptionName::SslMode => "SSL MODE",
+            SqlServerConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(SqlServerConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... SQL SERVER`.
+pub struct SqlServerConnectionOption<T: AstInfo> {
+    pub name: SqlServerConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqlServerConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqlServerConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MqttConnectionOptionName {
+    Host,
+    Password,
+    Port,
+    SslCertificateAuthority,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for MqttConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MqttConnectionOptionName::Host => "HOST",
+            MqttConnectionOptionName::Password => "PASSWORD",
+            MqttConnectionOptionName::Port => "PORT",
+            MqttConnectionOptionName::SslCertificateAuthority => "SSL CERTIFICATE AUTHORITY",
+            MqttConnectionOptionName::SslMode => "SSL MODE",
+            MqttConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(MqttConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... MQTT`.
+pub struct MqttConnectionOption<T: AstInfo> {
+    pub name: MqttConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MqttConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MqttConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GcpConnectionOptionName {
+    Credentials,
+    Endpoint,
+    Project,
+}
+
+impl AstDisplay for GcpConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            GcpConnectionOptionName::Credentials => "CREDENTIALS",
+            GcpConnectionOptionName::Endpoint => "ENDPOINT",
+            GcpConnectionOptionName::Project => "PROJECT",
+        })
+    }
+}
+impl_display!(GcpConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... GCP`.
+pub struct GcpConnectionOption<T: AstInfo> {
+    pub name: GcpConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for GcpConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(GcpConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AzureBlobConnectionOptionName {
+    AccountKey,
+    AccountName,
+    Endpoint,
+    SasToken,
+}
+
+impl AstDisplay for AzureBlobConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            AzureBlobConnectionOptionName::AccountKey => "ACCOUNT KEY",
+            AzureBlobConnectionOptionName::AccountName => "ACCOUNT NAME",
+            AzureBlobConnectionOptionName::Endpoint => "ENDPOINT",
+            AzureBlobConnectionOptionName::SasToken => "SAS TOKEN",
+        })
+    }
+}
+impl_display!(AzureBlobConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... AZURE BLOB`.
+pub struct AzureBlobConnectionOption<T: AstInfo> {
+    pub name: AzureBlobConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for AzureBlobConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(AzureBlobConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ElasticsearchConnectionOptionName {
+    Password,
+    SslCertificateAuthority,
+    Url,
+    User,
+}
+
+impl AstDisplay for ElasticsearchConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            ElasticsearchConnectionOptionName::Password => "PASSWORD",
+            ElasticsearchConnectionOptionName::SslCertificateAuthority => {
+                "SSL CERTIFICATE AUTHORITY"
+            }
+            ElasticsearchConnectionOptionName::Url => "URL",
+            ElasticsearchConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(ElasticsearchConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... ELASTICSEARCH`.
+pub struct ElasticsearchConnectionOption<T: AstInfo> {
+    pub name: ElasticsearchConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ElasticsearchConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(ElasticsearchConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RedisConnectionOptionName {
+    Database,
+    Host,
+    Password,
+    Port,
+    SslMode,
+    User,
+}
+
+impl AstDisplay for RedisConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            RedisConnectionOptionName::Database => "DATABASE",
+            RedisConnectionOptionName::Host => "HOST",
+            RedisConnectionOptionName::Password => "PASSWORD",
+            RedisConnectionOptionName::Port => "PORT",
+            RedisConnectionOptionName::SslMode => "SSL MODE",
+            RedisConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(RedisConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... REDIS`.
+pub struct RedisConnectionOption<T: AstInfo> {
+    pub name: RedisConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for RedisConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(RedisConnectionOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NatsConnectionOptionName {
+    Credentials,
+    Password,
+    Token,
+    Url,
+    User,
+}
+
+impl AstDisplay for NatsConnectionOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            NatsConnectionOptionName::Credentials => "CREDENTIALS",
+            NatsConnectionOptionName::Password => "PASSWORD",
+            NatsConnectionOptionName::Token => "TOKEN",
+            NatsConnectionOptionName::Url => "URL",
+            NatsConnectionOptionName::User => "USER",
+        })
+    }
+}
+impl_display!(NatsConnectionOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `CREATE CONNECTION ... NATS`.
+pub struct NatsConnectionOption<T: AstInfo> {
+    pub name: NatsConnectionOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for NatsConnectionOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(NatsConnectionOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AwsConnectionOptionName {
     AccessKeyId,
@@ -824,6 +1227,30 @@ pub enum CreateConnection<T: AstInfo> {
     Postgres {
         with_options: Vec<PostgresConnectionOption<T>>,
     },
+    MySql {
+        with_options: Vec<MySqlConnectionOption<T>>,
+    },
+    Mqtt {
+        with_options: Vec<MqttConnectionOption<T>>,
+    },
+    Elasticsearch {
+        with_options: Vec<ElasticsearchConnectionOption<T>>,
+    },
+    Redis {
+        with_options: Vec<RedisConnectionOption<T>>,
+    },
+    Nats {
+        with_options: Vec<NatsConnectionOption<T>>,
+    },
+    SqlServer {
+        with_options: Vec<SqlServerConnectionOption<T>>,
+    },
+    Gcp {
+        with_options: Vec<GcpConnectionOption<T>>,
+    },
+    AzureBlob {
+        with_options: Vec<AzureBlobConnectionOption<T>>,
+    },
     Ssh {
         with_options: Vec<SshConnectionOption<T>>,
     },
@@ -847,13 +1274,53 @@ impl<T: AstInfo> AstDisplay for CreateConnection<T> {
                 f.write_node(&display::comma_separated(with_options));
                 f.write_str(")");
             }
-            Self::Aws { with_options } => {
-                f.write_str("AWS (");
+            Self::MySql { with_options } => {
+                f.write_str("MYSQL (");
                 f.write_node(&display::comma_separated(with_options));
                 f.write_str(")");
             }
-            Self::AwsPrivatelink { with_options } => {
-                f.write_str("AWS PRIVATELINK (");
+            Self::Mqtt { with_options } => {
+                f.write_str("MQTT (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::Elasticsearch { with_options } => {
+                f.write_str("ELASTICSEARCH (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::Redis { with_options } => {
+                f.write_str("REDIS (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::Nats { with_options } => {
+                f.write_str("NATS (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::SqlServer { with_options } => {
+                f.write_str("SQL SERVER (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::Gcp { with_options } => {
+                f.write_str("GCP (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::AzureBlob { with_options } => {
+                f.write_str("AZURE BLOB (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::Aws { with_options } => {
+                f.write_str("AWS (");
+                f.write_node(&display::comma_separated(with_options));
+                f.write_str(")");
+            }
+            Self::AwsPrivatelink { with_options } => {
+                f.write_str("AWS PRIVATELINK (");
                 f.write_node(&display::comma_separated(with_options));
                 f.write_str(")");
             }
@@ -871,6 +1338,8 @@ impl_display_t!(CreateConnection);
 pub enum KafkaConfigOptionName {
     Acks,
     ClientId,
+    ConsistencyTopic,
+    DeadLetterTopic,
     EnableIdempotence,
     FetchMessageMaxBytes,
     GroupIdPrefix,
@@ -881,6 +1350,7 @@ pub enum KafkaConfigOptionName {
     StartTimestamp,
     StartOffset,
     PartitionCount,
+    PartitionStrategy,
     ReplicationFactor,
     RetentionMs,
     RetentionBytes,
@@ -891,6 +1361,8 @@ impl AstDisplay for KafkaConfigOptionName {
         f.write_str(match self {
             KafkaConfigOptionName::Acks => "ACKS",
             KafkaConfigOptionName::ClientId => "CLIENT ID",
+            KafkaConfigOptionName::ConsistencyTopic => "CONSISTENCY TOPIC",
+            KafkaConfigOptionName::DeadLetterTopic => "DEAD LETTER TOPIC",
             KafkaConfigOptionName::EnableIdempotence => "ENABLE IDEMPOTENCE",
             KafkaConfigOptionName::FetchMessageMaxBytes => "FETCH MESSAGE MAX BYTES",
             KafkaConfigOptionName::GroupIdPrefix => "GROUP ID PREFIX",
@@ -903,6 +1375,7 @@ impl AstDisplay for KafkaConfigOptionName {
             KafkaConfigOptionName::StartOffset => "START OFFSET",
             KafkaConfigOptionName::StartTimestamp => "START TIMESTAMP",
             KafkaConfigOptionName::PartitionCount => "PARTITION COUNT",
+            KafkaConfigOptionName::PartitionStrategy => "PARTITION STRATEGY",
             KafkaConfigOptionName::ReplicationFactor => "REPLICATION FACTOR",
             KafkaConfigOptionName::RetentionBytes => "RETENTION BYTES",
             KafkaConfigOptionName::RetentionMs => "RETENTION MS",
@@ -993,6 +1466,177 @@ impl<T: AstInfo> AstDisplay for PgConfigOption<T> {
 }
 impl_display_t!(PgConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MySqlConfigOptionName {
+    /// Hex encoded string of binary serialization of `dataflow_types::MySqlSourceDetails`
+    Details,
+}
+
+impl AstDisplay for MySqlConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MySqlConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(MySqlConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `FROM MYSQL CONNECTION ...` clause.
+pub struct MySqlConfigOption<T: AstInfo> {
+    pub name: MySqlConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MySqlConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MySqlConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SqlServerConfigOptionName {
+    /// Hex encoded string of binary serialization of `dataflow_types::SqlServerSourceDetails`
+    Details,
+}
+
+impl AstDisplay for SqlServerConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(SqlServerConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `FROM SQL SERVER CONNECTION ...` clause.
+pub struct SqlServerConfigOption<T: AstInfo> {
+    pub name: SqlServerConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqlServerConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqlServerConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MqttConfigOptionName {
+    /// The quality of service level at which to subscribe
+    Qos,
+    /// The topic filters to subscribe to
+    TopicFilters,
+}
+
+impl AstDisplay for MqttConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MqttConfigOptionName::Qos => "QOS",
+            MqttConfigOptionName::TopicFilters => "TOPIC FILTERS",
+        })
+    }
+}
+impl_display!(MqttConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `FROM MQTT CONNECTION ...` clause.
+pub struct MqttConfigOption<T: AstInfo> {
+    pub name: MqttConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MqttConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MqttConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PubSubConfigOptionName {
+    /// The subscription from which to pull messages
+    Subscription,
+}
+
+impl AstDisplay for PubSubConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PubSubConfigOptionName::Subscription => "SUBSCRIPTION",
+        })
+    }
+}
+impl_display!(PubSubConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `FROM PUBSUB CONNECTION ...` clause.
+pub struct PubSubConfigOption<T: AstInfo> {
+    pub name: PubSubConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for PubSubConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(PubSubConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WebhookOptionName {
+    /// The secret with which requests are signed.
+    HmacSecret,
+    /// The header that carries the signature of a request.
+    HmacHeader,
+}
+
+impl AstDisplay for WebhookOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            WebhookOptionName::HmacSecret => "HMAC SECRET",
+            WebhookOptionName::HmacHeader => "HMAC HEADER",
+        })
+    }
+}
+impl_display!(WebhookOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in a `FROM WEBHOOK` clause.
+pub struct WebhookOption<T: AstInfo> {
+    pub name: WebhookOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for WebhookOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(WebhookOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceConnection<T: AstInfo> {
     Kafka(KafkaSourceConnection<T>),
@@ -1015,6 +1659,26 @@ pub enum CreateSourceConnection<T: AstInfo> {
         connection: T::ObjectName,
         options: Vec<PgConfigOption<T>>,
     },
+    MySql {
+        /// The MySQL connection.
+        connection: T::ObjectName,
+        options: Vec<MySqlConfigOption<T>>,
+    },
+    Mqtt {
+        /// The MQTT connection.
+        connection: T::ObjectName,
+        options: Vec<MqttConfigOption<T>>,
+    },
+    PubSub {
+        /// The GCP connection.
+        connection: T::ObjectName,
+        options: Vec<PubSubConfigOption<T>>,
+    },
+    SqlServer {
+        /// The SQL Server connection.
+        connection: T::ObjectName,
+        options: Vec<SqlServerConfigOption<T>>,
+    },
     LoadGenerator {
         generator: LoadGenerator,
         options: Vec<LoadGeneratorOption<T>>,
@@ -1022,6 +1686,20 @@ pub enum CreateSourceConnection<T: AstInfo> {
     TestScript {
         desc_json: String,
     },
+    File {
+        /// The path of the file on the host that runs the source.
+        path: String,
+        /// Whether to keep reading data that is appended to the file.
+        tail: bool,
+    },
+    Webhook {
+        options: Vec<WebhookOption<T>>,
+    },
+    Flight {
+        /// The columns that the record batches written to the source must
+        /// have.
+        columns: Vec<ColumnDef<T>>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
@@ -1074,6 +1752,54 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::MySql {
+                connection,
+                options,
+            } => {
+                f.write_str("MYSQL CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Mqtt {
+                connection,
+                options,
+            } => {
+                f.write_str("MQTT CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::PubSub {
+                connection,
+                options,
+            } => {
+                f.write_str("PUBSUB CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::SqlServer {
+                connection,
+                options,
+            } => {
+                f.write_str("SQL SERVER CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::LoadGenerator { generator, options } => {
                 f.write_str("LOAD GENERATOR ");
                 f.write_node(generator);
@@ -1089,6 +1815,27 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                 f.write_str(&display::escape_single_quote_string(desc_json));
                 f.write_str("'");
             }
+            CreateSourceConnection::File { path, tail } => {
+                f.write_str("FILE '");
+                f.write_str(&display::escape_single_quote_string(path));
+                f.write_str("'");
+                if *tail {
+                    f.write_str(" TAIL");
+                }
+            }
+            CreateSourceConnection::Webhook { options } => {
+                f.write_str("WEBHOOK");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::Flight { columns } => {
+                f.write_str("FLIGHT (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
         }
     }
 }
@@ -1154,6 +1901,35 @@ pub enum CreateSinkConnection<T: AstInfo> {
         connection: KafkaConnection<T>,
         key: Option<KafkaSinkKey>,
     },
+    File {
+        path: String,
+        options: Vec<ObjectSinkOption<T>>,
+    },
+    ObjectStore {
+        store: ObjectStoreKind,
+        connection: T::ItemName,
+        options: Vec<ObjectSinkOption<T>>,
+    },
+    Postgres {
+        connection: T::ItemName,
+        options: Vec<PostgresSinkOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    Elasticsearch {
+        connection: T::ItemName,
+        options: Vec<ElasticsearchSinkOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    Redis {
+        connection: T::ItemName,
+        options: Vec<RedisSinkOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
+    Nats {
+        connection: T::ItemName,
+        options: Vec<NatsSinkOption<T>>,
+        key: Option<KafkaSinkKey>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
@@ -1166,11 +1942,308 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                     f.write_node(key);
                 }
             }
+            CreateSinkConnection::File { path, options } => {
+                f.write_str("FILE '");
+                f.write_str(&display::escape_single_quote_string(path));
+                f.write_str("'");
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSinkConnection::ObjectStore {
+                store,
+                connection,
+                options,
+            } => {
+                f.write_node(store);
+                f.write_str(" CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSinkConnection::Postgres {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("POSTGRES CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Elasticsearch {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("ELASTICSEARCH CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Redis {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("REDIS CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Nats {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("NATS CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
         }
     }
 }
 impl_display_t!(CreateSinkConnection);
 
+/// The object store to which an object sink writes its files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectStoreKind {
+    /// Amazon S3, reached through an AWS connection.
+    S3,
+    /// Google Cloud Storage, reached through a GCP connection.
+    Gcs,
+    /// Azure Blob Storage, reached through an Azure Blob connection.
+    AzureBlob,
+}
+
+impl AstDisplay for ObjectStoreKind {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            ObjectStoreKind::S3 => "S3",
+            ObjectStoreKind::Gcs => "GCS",
+            ObjectStoreKind::AzureBlob => "AZURE BLOB",
+        })
+    }
+}
+impl_display!(ObjectStoreKind);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ObjectSinkOptionName {
+    /// The bucket to which the files are written.
+    Bucket,
+    /// The Azure Blob container to which the files are written.
+    Container,
+    /// The size past which a file is completed.
+    MaxFileSize,
+    /// The prefix of the keys of the files.
+    Prefix,
+    /// How long a file may stay open before it is completed.
+    RotationInterval,
+}
+
+impl AstDisplay for ObjectSinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            ObjectSinkOptionName::Bucket => "BUCKET",
+            ObjectSinkOptionName::Container => "CONTAINER",
+            ObjectSinkOptionName::MaxFileSize => "MAX FILE SIZE",
+            ObjectSinkOptionName::Prefix => "PREFIX",
+            ObjectSinkOptionName::RotationInterval => "ROTATION INTERVAL",
+        })
+    }
+}
+impl_display!(ObjectSinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in an `INTO {FILE|S3|GCS|AZURE BLOB} ...` clause.
+pub struct ObjectSinkOption<T: AstInfo> {
+    pub name: ObjectSinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ObjectSinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(ObjectSinkOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PostgresSinkOptionName {
+    /// The schema of the table that the sink maintains.
+    Schema,
+    /// The name of the table that the sink maintains.
+    Table,
+}
+
+impl AstDisplay for PostgresSinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PostgresSinkOptionName::Schema => "SCHEMA",
+            PostgresSinkOptionName::Table => "TABLE",
+        })
+    }
+}
+impl_display!(PostgresSinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in an `INTO POSTGRES ...` clause.
+pub struct PostgresSinkOption<T: AstInfo> {
+    pub name: PostgresSinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for PostgresSinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(PostgresSinkOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ElasticsearchSinkOptionName {
+    /// The name of the index that the sink maintains.
+    Index,
+}
+
+impl AstDisplay for ElasticsearchSinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            ElasticsearchSinkOptionName::Index => "INDEX",
+        })
+    }
+}
+impl_display!(ElasticsearchSinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in an `INTO ELASTICSEARCH ...` clause.
+pub struct ElasticsearchSinkOption<T: AstInfo> {
+    pub name: ElasticsearchSinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for ElasticsearchSinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(ElasticsearchSinkOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RedisSinkOptionName {
+    /// The prefix of the Redis keys that the sink writes.
+    KeyPrefix,
+}
+
+impl AstDisplay for RedisSinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            RedisSinkOptionName::KeyPrefix => "KEY PREFIX",
+        })
+    }
+}
+impl_display!(RedisSinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in an `INTO REDIS ...` clause.
+pub struct RedisSinkOption<T: AstInfo> {
+    pub name: RedisSinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for RedisSinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(RedisSinkOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NatsSinkOptionName {
+    /// Whether messages carry the header with which JetStream discards
+    /// duplicate messages.
+    DeduplicationHeaders,
+    /// The JetStream stream that captures the subject.
+    Stream,
+    /// The subject to which messages are published.
+    Subject,
+}
+
+impl AstDisplay for NatsSinkOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            NatsSinkOptionName::DeduplicationHeaders => "DEDUPLICATION HEADERS",
+            NatsSinkOptionName::Stream => "STREAM",
+            NatsSinkOptionName::Subject => "SUBJECT",
+        })
+    }
+}
+impl_display!(NatsSinkOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An option in an `INTO NATS ...` clause.
+pub struct NatsSinkOption<T: AstInfo> {
+    pub name: NatsSinkOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for NatsSinkOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(NatsSinkOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KafkaSinkKey {
     pub key_columns: Vec<Ident>,
@@ -1335,6 +2408,8 @@ impl_display!(KeyConstraint);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceOptionName {
     IgnoreKeys,
+    MaxBytesInFlight,
+    MaxMessagesPerSecond,
     Remote,
     Size,
     Timeline,
@@ -1345,6 +2420,8 @@ impl AstDisplay for CreateSourceOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str(match self {
             CreateSourceOptionName::IgnoreKeys => "IGNORE KEYS",
+            CreateSourceOptionName::MaxBytesInFlight => "MAX BYTES IN FLIGHT",
+            CreateSourceOptionName::MaxMessagesPerSecond => "MAX MESSAGES PER SECOND",
             CreateSourceOptionName::Remote => "REMOTE",
             CreateSourceOptionName::Size => "SIZE",
             CreateSourceOptionName::Timeline => "TIMELINE",
@@ -585,6 +585,7 @@ impl<T: AstInfo> Expr<T> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         })
     }
 
@@ -796,6 +797,10 @@ pub struct Function<T: AstInfo> {
     pub over: Option<WindowSpec<T>>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    // ordered-set aggregate functions specify the values they aggregate in a
+    // `WITHIN GROUP (ORDER BY ...)` clause, e.g.
+    // `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`
+    pub within_group: Vec<OrderByExpr<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for Function<T> {
@@ -807,6 +812,11 @@ impl<T: AstInfo> AstDisplay for Function<T> {
         }
         f.write_node(&self.args);
         f.write_str(")");
+        if !self.within_group.is_empty() {
+            f.write_str(" WITHIN GROUP (ORDER BY ");
+            f.write_node(&display::comma_separated(&self.within_group));
+            f.write_str(")");
+        }
         if let Some(filter) = &self.filter {
             f.write_str(" FILTER (WHERE ");
             f.write_node(&filter);
@@ -553,6 +553,10 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub include_metadata: Vec<SourceIncludeMetadata>,
     pub format: CreateSourceFormat<T>,
     pub envelope: Option<Envelope>,
+    /// The columns to retain, as specified by a `COLUMNS` clause.
+    pub projection: Vec<Ident>,
+    /// The rows to retain, as specified by a `WHERE` clause.
+    pub filter: Option<Expr<T>>,
     pub if_not_exists: bool,
     pub key_constraint: Option<KeyConstraint>,
     pub with_options: Vec<CreateSourceOption<T>>,
@@ -593,6 +597,17 @@ impl<T: AstInfo> AstDisplay for CreateSourceStatement<T> {
             f.write_node(envelope);
         }
 
+        if !self.projection.is_empty() {
+            f.write_str(" COLUMNS (");
+            f.write_node(&display::comma_separated(&self.projection));
+            f.write_str(")");
+        }
+
+        if let Some(filter) = &self.filter {
+            f.write_str(" WHERE ");
+            f.write_node(filter);
+        }
+
         if let Some(subsources) = &self.subsources {
             f.write_str(" ");
             f.write_node(subsources);
@@ -677,6 +692,7 @@ impl_display_t!(CreateSubsourceStatement);
 /// An option in a `CREATE SINK` statement.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSinkOptionName {
+    ErrorPolicy,
     Remote,
     Size,
     Snapshot,
@@ -685,6 +701,9 @@ pub enum CreateSinkOptionName {
 impl AstDisplay for CreateSinkOptionName {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
+            CreateSinkOptionName::ErrorPolicy => {
+                f.write_str("ERROR POLICY");
+            }
             CreateSinkOptionName::Remote => {
                 f.write_str("REMOTE");
             }
@@ -720,6 +739,9 @@ pub struct CreateSinkStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub if_not_exists: bool,
     pub from: T::ObjectName,
+    /// The columns of `from` to sink, as specified by a `COLUMNS` clause, or
+    /// all of them if empty.
+    pub projection: Vec<Ident>,
     pub connection: CreateSinkConnection<T>,
     pub format: Option<Format<T>>,
     pub envelope: Option<Envelope>,
@@ -735,6 +757,11 @@ impl<T: AstInfo> AstDisplay for CreateSinkStatement<T> {
         f.write_node(&self.name);
         f.write_str(" FROM ");
         f.write_node(&self.from);
+        if !self.projection.is_empty() {
+            f.write_str(" COLUMNS (");
+            f.write_node(&display::comma_separated(&self.projection));
+            f.write_str(")");
+        }
         f.write_str(" INTO ");
         f.write_node(&self.connection);
         if let Some(format) = &self.format {
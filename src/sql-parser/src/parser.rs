@@ -587,6 +587,34 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let within_group_pos = self.peek_pos();
+        let within_group = if self.parse_keywords(&[WITHIN, GROUP]) {
+            match &args {
+                FunctionArgs::Args { order_by, .. } if order_by.is_empty() => (),
+                _ => {
+                    return parser_err!(
+                        self,
+                        within_group_pos,
+                        "cannot use multiple ORDER BY clauses with WITHIN GROUP"
+                    )
+                }
+            }
+            if distinct {
+                return parser_err!(
+                    self,
+                    within_group_pos,
+                    "cannot use DISTINCT with WITHIN GROUP"
+                );
+            }
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            let within_group = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            within_group
+        } else {
+            vec![]
+        };
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -633,6 +661,7 @@ impl<'a> Parser<'a> {
             filter,
             over,
             distinct,
+            within_group,
         }))
     }
 
@@ -765,6 +794,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -828,6 +858,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -846,6 +877,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1072,6 +1104,7 @@ impl<'a> Parser<'a> {
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: vec![],
                     }))
                 }
                 COLLATE => Ok(Expr::Collate {
@@ -1196,6 +1229,7 @@ impl<'a> Parser<'a> {
             filter: None,
             over: None,
             distinct: false,
+            within_group: vec![],
         }))
     }
 
@@ -1690,28 +1724,69 @@ impl<'a> Parser<'a> {
             Format::Protobuf(self.parse_protobuf_schema()?)
         } else if self.parse_keyword(REGEX) {
             let regex = self.parse_literal_string()?;
-            Format::Regex(regex)
-        } else if self.parse_keyword(CSV) {
-            self.expect_keyword(WITH)?;
-            let columns = if self.parse_keyword(HEADER) || self.parse_keyword(HEADERS) {
-                CsvColumns::Header {
-                    names: self.parse_parenthesized_column_list(Mandatory)?,
+            let column_types = if self.parse_keyword(TYPES) {
+                self.expect_token(&Token::LParen)?;
+                let column_types = self.parse_comma_separated(|parser| {
+                    Ok(RegexColumnType {
+                        name: parser.parse_identifier()?,
+                        data_type: parser.parse_data_type()?,
+                    })
+                })?;
+                self.expect_token(&Token::RParen)?;
+                column_types
+            } else {
+                vec![]
+            };
+            let on_mismatch = if self.parse_keywords(&[ON, MISMATCH]) {
+                match self.expect_one_of_keywords(&[SKIP, NULL, ERROR])? {
+                    SKIP => RegexMismatch::Skip,
+                    NULL => RegexMismatch::Null,
+                    ERROR => RegexMismatch::Error,
+                    _ => unreachable!(),
                 }
+            } else {
+                RegexMismatch::Skip
+            };
+            Format::Regex {
+                regex,
+                column_types,
+                on_mismatch,
+            }
+        } else if self.parse_keyword(CSV) {
+            // Sinks only specify the columns to write a header, whose names
+            // default to those of the sinked columns.
+            let columns = if !self.parse_keyword(WITH) {
+                None
+            } else if self.parse_keyword(HEADER) || self.parse_keyword(HEADERS) {
+                Some(CsvColumns::Header {
+                    names: self.parse_parenthesized_column_list(Optional)?,
+                })
             } else {
                 let n_cols = usize::cast_from(self.parse_literal_uint()?);
                 self.expect_keyword(COLUMNS)?;
-                CsvColumns::Count(n_cols)
+                Some(CsvColumns::Count(n_cols))
             };
             let delimiter = if self.parse_keywords(&[DELIMITED, BY]) {
-                let s = self.parse_literal_string()?;
-                match s.len() {
-                    1 => Ok(s.chars().next().unwrap()),
-                    _ => self.expected(self.peek_pos(), "one-character string", self.peek_token()),
-                }?
+                self.parse_csv_char()?
             } else {
                 ','
             };
-            Format::Csv { columns, delimiter }
+            let quote = if self.parse_keyword(QUOTE) {
+                self.parse_csv_char()?
+            } else {
+                '"'
+            };
+            let null = if self.parse_keyword(NULL) {
+                Some(self.parse_literal_string()?)
+            } else {
+                None
+            };
+            Format::Csv {
+                columns,
+                delimiter,
+                quote,
+                null,
+            }
         } else if self.parse_keyword(JSON) {
             Format::Json
         } else if self.parse_keyword(TEXT) {
@@ -1767,6 +1842,16 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses a one-character string, as used for the delimiter and quote
+    /// character of the CSV format.
+    fn parse_csv_char(&mut self) -> Result<char, ParserError> {
+        let s = self.parse_literal_string()?;
+        match s.len() {
+            1 => Ok(s.chars().next().unwrap()),
+            _ => self.expected(self.peek_pos(), "one-character string", self.peek_token()),
+        }
+    }
+
     fn parse_protobuf_schema(&mut self) -> Result<ProtobufSchema<Raw>, ParserError> {
         if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
             let csr_connection = self.parse_csr_connection_proto()?;
@@ -1810,7 +1895,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_csr_config_option(&mut self) -> Result<CsrConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[AVRO])? {
+        let name = match self.expect_one_of_keywords(&[AVRO, INCOMPATIBLE])? {
             AVRO => {
                 let name = match self.expect_one_of_keywords(&[KEY, VALUE])? {
                     KEY => CsrConfigOptionName::AvroKeyFullname,
@@ -1820,6 +1905,10 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(FULLNAME)?;
                 name
             }
+            INCOMPATIBLE => {
+                self.expect_keywords(&[SCHEMA, POLICY])?;
+                CsrConfigOptionName::IncompatibleSchemaPolicy
+            }
             _ => unreachable!(),
         };
         Ok(CsrConfigOption {
@@ -1959,9 +2048,21 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection = match self
-            .expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH])?
-        {
+        let connection = match self.expect_one_of_keywords(&[
+            AWS,
+            KAFKA,
+            CONFLUENT,
+            POSTGRES,
+            MYSQL,
+            MQTT,
+            SQL,
+            GCP,
+            AZURE,
+            SSH,
+            ELASTICSEARCH,
+            REDIS,
+            NATS,
+        ])? {
             AWS => {
                 if self.parse_keyword(PRIVATELINK) {
                     if expect_paren {
@@ -2004,6 +2105,72 @@ impl<'a> Parser<'a> {
                     self.parse_comma_separated(Parser::parse_postgres_connection_option)?;
                 CreateConnection::Postgres { with_options }
             }
+            MYSQL => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_mysql_connection_option)?;
+                CreateConnection::MySql { with_options }
+            }
+            MQTT => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_mqtt_connection_option)?;
+                CreateConnection::Mqtt { with_options }
+            }
+            ELASTICSEARCH => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_elasticsearch_connection_option)?;
+                CreateConnection::Elasticsearch { with_options }
+            }
+            REDIS => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_redis_connection_option)?;
+                CreateConnection::Redis { with_options }
+            }
+            NATS => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_nats_connection_option)?;
+                CreateConnection::Nats { with_options }
+            }
+            SQL => {
+                self.expect_keyword(SERVER)?;
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_sql_server_connection_option)?;
+                CreateConnection::SqlServer { with_options }
+            }
+            GCP => {
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_gcp_connection_option)?;
+                CreateConnection::Gcp { with_options }
+            }
+            AZURE => {
+                self.expect_keyword(BLOB)?;
+                if expect_paren {
+                    self.expect_token(&Token::LParen)?;
+                }
+                let with_options =
+                    self.parse_comma_separated(Parser::parse_azure_blob_connection_option)?;
+                CreateConnection::AzureBlob { with_options }
+            }
             SSH => {
                 self.expect_keyword(TUNNEL)?;
                 if expect_paren {
@@ -2152,6 +2319,8 @@ impl<'a> Parser<'a> {
         let name = match self.expect_one_of_keywords(&[
             ACKS,
             CLIENT,
+            CONSISTENCY,
+            DEAD,
             ENABLE,
             FETCH,
             GROUP,
@@ -2169,6 +2338,14 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(ID)?;
                 KafkaConfigOptionName::ClientId
             }
+            CONSISTENCY => {
+                self.expect_keyword(TOPIC)?;
+                KafkaConfigOptionName::ConsistencyTopic
+            }
+            DEAD => {
+                self.expect_keywords(&[LETTER, TOPIC])?;
+                KafkaConfigOptionName::DeadLetterTopic
+            }
             ENABLE => {
                 self.expect_keyword(IDEMPOTENCE)?;
                 KafkaConfigOptionName::EnableIdempotence
@@ -2185,10 +2362,11 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(LEVEL)?;
                 KafkaConfigOptionName::IsolationLevel
             }
-            PARTITION => {
-                self.expect_keyword(COUNT)?;
-                KafkaConfigOptionName::PartitionCount
-            }
+            PARTITION => match self.expect_one_of_keywords(&[COUNT, STRATEGY])? {
+                COUNT => KafkaConfigOptionName::PartitionCount,
+                STRATEGY => KafkaConfigOptionName::PartitionStrategy,
+                _ => unreachable!(),
+            },
             REPLICATION => {
                 self.expect_keyword(FACTOR)?;
                 KafkaConfigOptionName::ReplicationFactor
@@ -2308,6 +2486,172 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_mysql_connection_option(&mut self) -> Result<MySqlConnectionOption<Raw>, ParserError> {
+        let name =
+            match self.expect_one_of_keywords(&[HOST, PASSWORD, PORT, SSL, USER, USERNAME])? {
+                HOST => MySqlConnectionOptionName::Host,
+                PASSWORD => MySqlConnectionOptionName::Password,
+                PORT => MySqlConnectionOptionName::Port,
+                SSL => match self.expect_one_of_keywords(&[CERTIFICATE, MODE])? {
+                    CERTIFICATE => {
+                        self.expect_keyword(AUTHORITY)?;
+                        MySqlConnectionOptionName::SslCertificateAuthority
+                    }
+                    MODE => MySqlConnectionOptionName::SslMode,
+                    _ => unreachable!(),
+                },
+                USER | USERNAME => MySqlConnectionOptionName::User,
+                _ => unreachable!(),
+            };
+        Ok(MySqlConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_mqtt_connection_option(&mut self) -> Result<MqttConnectionOption<Raw>, ParserError> {
+        let name =
+            match self.expect_one_of_keywords(&[HOST, PASSWORD, PORT, SSL, USER, USERNAME])? {
+                HOST => MqttConnectionOptionName::Host,
+                PASSWORD => MqttConnectionOptionName::Password,
+                PORT => MqttConnectionOptionName::Port,
+                SSL => match self.expect_one_of_keywords(&[CERTIFICATE, MODE])? {
+                    CERTIFICATE => {
+                        self.expect_keyword(AUTHORITY)?;
+                        MqttConnectionOptionName::SslCertificateAuthority
+                    }
+                    MODE => MqttConnectionOptionName::SslMode,
+                    _ => unreachable!(),
+                },
+                USER | USERNAME => MqttConnectionOptionName::User,
+                _ => unreachable!(),
+            };
+        Ok(MqttConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sql_server_connection_option(
+        &mut self,
+    ) -> Result<SqlServerConnectionOption<Raw>, ParserError> {
+        let name = match self
+            .expect_one_of_keywords(&[DATABASE, HOST, PASSWORD, PORT, SSL, USER, USERNAME])?
+        {
+            DATABASE => SqlServerConnectionOptionName::Database,
+            HOST => SqlServerConnectionOptionName::Host,
+            PASSWORD => SqlServerConnectionOptionName::Password,
+            PORT => SqlServerConnectionOptionName::Port,
+            SSL => {
+                self.expect_keyword(MODE)?;
+                SqlServerConnectionOptionName::SslMode
+            }
+            USER | USERNAME => SqlServerConnectionOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(SqlServerConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_gcp_connection_option(&mut self) -> Result<GcpConnectionOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[CREDENTIALS, ENDPOINT, PROJECT])? {
+            CREDENTIALS => GcpConnectionOptionName::Credentials,
+            ENDPOINT => GcpConnectionOptionName::Endpoint,
+            PROJECT => GcpConnectionOptionName::Project,
+            _ => unreachable!(),
+        };
+        Ok(GcpConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_elasticsearch_connection_option(
+        &mut self,
+    ) -> Result<ElasticsearchConnectionOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PASSWORD, SSL, URL, USER, USERNAME])? {
+            PASSWORD => ElasticsearchConnectionOptionName::Password,
+            SSL => {
+                self.expect_keywords(&[CERTIFICATE, AUTHORITY])?;
+                ElasticsearchConnectionOptionName::SslCertificateAuthority
+            }
+            URL => ElasticsearchConnectionOptionName::Url,
+            USER | USERNAME => ElasticsearchConnectionOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(ElasticsearchConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_redis_connection_option(&mut self) -> Result<RedisConnectionOption<Raw>, ParserError> {
+        let name = match self
+            .expect_one_of_keywords(&[DATABASE, HOST, PASSWORD, PORT, SSL, USER, USERNAME])?
+        {
+            DATABASE => RedisConnectionOptionName::Database,
+            HOST => RedisConnectionOptionName::Host,
+            PASSWORD => RedisConnectionOptionName::Password,
+            PORT => RedisConnectionOptionName::Port,
+            SSL => {
+                self.expect_keyword(MODE)?;
+                RedisConnectionOptionName::SslMode
+            }
+            USER | USERNAME => RedisConnectionOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(RedisConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_azure_blob_connection_option(
+        &mut self,
+    ) -> Result<AzureBlobConnectionOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[ACCOUNT, ENDPOINT, SAS])? {
+            ACCOUNT => match self.expect_one_of_keywords(&[KEY, NAME])? {
+                KEY => AzureBlobConnectionOptionName::AccountKey,
+                NAME => AzureBlobConnectionOptionName::AccountName,
+                _ => unreachable!(),
+            },
+            ENDPOINT => AzureBlobConnectionOptionName::Endpoint,
+            SAS => {
+                self.expect_keyword(TOKEN)?;
+                AzureBlobConnectionOptionName::SasToken
+            }
+            _ => unreachable!(),
+        };
+        Ok(AzureBlobConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_nats_connection_option(&mut self) -> Result<NatsConnectionOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[
+            CREDENTIALS,
+            PASSWORD,
+            TOKEN,
+            URL,
+            USER,
+            USERNAME,
+        ])? {
+            CREDENTIALS => NatsConnectionOptionName::Credentials,
+            PASSWORD => NatsConnectionOptionName::Password,
+            TOKEN => NatsConnectionOptionName::Token,
+            URL => NatsConnectionOptionName::Url,
+            USER | USERNAME => NatsConnectionOptionName::User,
+            _ => unreachable!(),
+        };
+        Ok(NatsConnectionOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_aws_connection_option(&mut self) -> Result<AwsConnectionOption<Raw>, ParserError> {
         let name =
             match self.expect_one_of_keywords(&[ACCESS, ENDPOINT, REGION, ROLE, SECRET, TOKEN])? {
@@ -2409,6 +2753,18 @@ impl<'a> Parser<'a> {
             None
         };
 
+        let projection = if self.parse_keyword(COLUMNS) {
+            self.parse_parenthesized_column_list(Mandatory)?
+        } else {
+            vec![]
+        };
+
+        let filter = if self.parse_keyword(WHERE) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
         let subsources = if self.parse_keywords(&[FOR, TABLES]) {
             self.expect_token(&Token::LParen)?;
             let subsources = self.parse_comma_separated(Parser::parse_subsource_references)?;
@@ -2437,6 +2793,8 @@ impl<'a> Parser<'a> {
             format,
             include_metadata,
             envelope,
+            projection,
+            filter,
             if_not_exists,
             key_constraint,
             with_options,
@@ -2502,11 +2860,22 @@ impl<'a> Parser<'a> {
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
         let name =
-            match self.expect_one_of_keywords(&[IGNORE, REMOTE, SIZE, TIMELINE, TIMESTAMP])? {
+            match self.expect_one_of_keywords(&[IGNORE, MAX, REMOTE, SIZE, TIMELINE, TIMESTAMP])? {
                 IGNORE => {
                     self.expect_keyword(KEYS)?;
                     CreateSourceOptionName::IgnoreKeys
                 }
+                MAX => match self.expect_one_of_keywords(&[BYTES, MESSAGES])? {
+                    BYTES => {
+                        self.expect_keywords(&[IN, FLIGHT])?;
+                        CreateSourceOptionName::MaxBytesInFlight
+                    }
+                    MESSAGES => {
+                        self.expect_keywords(&[PER, SECOND])?;
+                        CreateSourceOptionName::MaxMessagesPerSecond
+                    }
+                    _ => unreachable!(),
+                },
                 REMOTE => CreateSourceOptionName::Remote,
                 SIZE => CreateSourceOptionName::Size,
                 TIMELINE => CreateSourceOptionName::Timeline,
@@ -2534,6 +2903,11 @@ impl<'a> Parser<'a> {
         let name = self.parse_object_name()?;
         self.expect_keyword(FROM)?;
         let from = self.parse_raw_name()?;
+        let projection = if self.parse_keyword(COLUMNS) {
+            self.parse_parenthesized_column_list(Mandatory)?
+        } else {
+            vec![]
+        };
         self.expect_keyword(INTO)?;
         let connection = self.parse_create_sink_connection()?;
         let format = if self.parse_keyword(FORMAT) {
@@ -2559,6 +2933,7 @@ impl<'a> Parser<'a> {
         Ok(Statement::CreateSink(CreateSinkStatement {
             name,
             from,
+            projection,
             connection,
             format,
             envelope,
@@ -2569,7 +2944,11 @@ impl<'a> Parser<'a> {
 
     /// Parse the name of a CREATE SINK optional parameter
     fn parse_create_sink_option_name(&mut self) -> Result<CreateSinkOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[REMOTE, SIZE, SNAPSHOT])? {
+        let name = match self.expect_one_of_keywords(&[ERROR, REMOTE, SIZE, SNAPSHOT])? {
+            ERROR => {
+                self.expect_keyword(POLICY)?;
+                CreateSinkOptionName::ErrorPolicy
+            }
             SIZE => CreateSinkOptionName::Size,
             SNAPSHOT => CreateSinkOptionName::Snapshot,
             REMOTE => CreateSinkOptionName::Remote,
@@ -2589,7 +2968,10 @@ impl<'a> Parser<'a> {
     fn parse_create_source_connection(
         &mut self,
     ) -> Result<CreateSourceConnection<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, KINESIS, S3, POSTGRES, LOAD, TEST])? {
+        match self.expect_one_of_keywords(&[
+            KAFKA, KINESIS, S3, POSTGRES, MYSQL, MQTT, SQL, PUBSUB, LOAD, TEST, FILE, WEBHOOK,
+            FLIGHT,
+        ])? {
             POSTGRES => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -2607,6 +2989,75 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
+            MYSQL => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_mysql_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::MySql {
+                    connection,
+                    options,
+                })
+            }
+            MQTT => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_mqtt_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::Mqtt {
+                    connection,
+                    options,
+                })
+            }
+            SQL => {
+                self.expect_keywords(&[SERVER, CONNECTION])?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_sql_server_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::SqlServer {
+                    connection,
+                    options,
+                })
+            }
+            PUBSUB => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_pubsub_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::PubSub {
+                    connection,
+                    options,
+                })
+            }
             KAFKA => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_kafka_connection_reference()?;
@@ -2709,10 +3160,50 @@ impl<'a> Parser<'a> {
                     desc_json: self.parse_literal_string()?,
                 })
             }
+            FILE => {
+                let path = self.parse_literal_string()?;
+                let tail = self.parse_keyword(TAIL);
+                Ok(CreateSourceConnection::File { path, tail })
+            }
+            WEBHOOK => {
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_webhook_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                Ok(CreateSourceConnection::Webhook { options })
+            }
+            FLIGHT => {
+                let pos = self.peek_pos();
+                let (columns, constraints) = self.parse_columns(Mandatory)?;
+                if !constraints.is_empty() {
+                    return parser_err!(
+                        self,
+                        pos,
+                        "FLIGHT sources do not support table constraints"
+                    );
+                }
+                Ok(CreateSourceConnection::Flight { columns })
+            }
             _ => unreachable!(),
         }
     }
 
+    fn parse_webhook_option(&mut self) -> Result<WebhookOption<Raw>, ParserError> {
+        self.expect_keyword(HMAC)?;
+        let name = match self.expect_one_of_keywords(&[SECRET, HEADER])? {
+            SECRET => WebhookOptionName::HmacSecret,
+            HEADER => WebhookOptionName::HmacHeader,
+            _ => unreachable!(),
+        };
+        Ok(WebhookOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_pg_connection_option(&mut self) -> Result<PgConfigOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, TEXT])? {
             DETAILS => PgConfigOptionName::Details,
@@ -2746,6 +3237,56 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_mysql_config_option(&mut self) -> Result<MySqlConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => MySqlConfigOptionName::Details,
+            _ => unreachable!(),
+        };
+        Ok(MySqlConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sql_server_config_option(
+        &mut self,
+    ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => SqlServerConfigOptionName::Details,
+            _ => unreachable!(),
+        };
+        Ok(SqlServerConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_mqtt_config_option(&mut self) -> Result<MqttConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[QOS, TOPIC])? {
+            QOS => MqttConfigOptionName::Qos,
+            TOPIC => {
+                self.expect_keyword(FILTERS)?;
+                MqttConfigOptionName::TopicFilters
+            }
+            _ => unreachable!(),
+        };
+        Ok(MqttConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_pubsub_config_option(&mut self) -> Result<PubSubConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[SUBSCRIPTION])? {
+            SUBSCRIPTION => PubSubConfigOptionName::Subscription,
+            _ => unreachable!(),
+        };
+        Ok(PubSubConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[SCALE, TICK])? {
             SCALE => {
@@ -2767,11 +3308,212 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
-        self.expect_keyword(KAFKA)?;
+        match self.expect_one_of_keywords(&[
+            KAFKA,
+            FILE,
+            POSTGRES,
+            S3,
+            GCS,
+            AZURE,
+            ELASTICSEARCH,
+            REDIS,
+            NATS,
+        ])? {
+            KAFKA => self.parse_kafka_sink_connection(),
+            FILE => {
+                let path = self.parse_literal_string()?;
+                let options = self.parse_object_sink_options()?;
+                Ok(CreateSinkConnection::File { path, options })
+            }
+            kw @ (S3 | GCS | AZURE) => {
+                let store = match kw {
+                    S3 => ObjectStoreKind::S3,
+                    GCS => ObjectStoreKind::Gcs,
+                    AZURE => {
+                        self.expect_keyword(BLOB)?;
+                        ObjectStoreKind::AzureBlob
+                    }
+                    _ => unreachable!(),
+                };
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+                let options = self.parse_object_sink_options()?;
+                Ok(CreateSinkConnection::ObjectStore {
+                    store,
+                    connection,
+                    options,
+                })
+            }
+            POSTGRES => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_postgres_sink_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                let key = self.parse_sink_key()?;
+                Ok(CreateSinkConnection::Postgres {
+                    connection,
+                    options,
+                    key,
+                })
+            }
+            ELASTICSEARCH => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_elasticsearch_sink_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                let key = self.parse_sink_key()?;
+                Ok(CreateSinkConnection::Elasticsearch {
+                    connection,
+                    options,
+                    key,
+                })
+            }
+            REDIS => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_redis_sink_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                let key = self.parse_sink_key()?;
+                Ok(CreateSinkConnection::Redis {
+                    connection,
+                    options,
+                    key,
+                })
+            }
+            NATS => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_nats_sink_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+                let key = self.parse_sink_key()?;
+                Ok(CreateSinkConnection::Nats {
+                    connection,
+                    options,
+                    key,
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_postgres_sink_option(&mut self) -> Result<PostgresSinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[SCHEMA, TABLE])? {
+            SCHEMA => PostgresSinkOptionName::Schema,
+            TABLE => PostgresSinkOptionName::Table,
+            _ => unreachable!(),
+        };
+        Ok(PostgresSinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_elasticsearch_sink_option(
+        &mut self,
+    ) -> Result<ElasticsearchSinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[INDEX])? {
+            INDEX => ElasticsearchSinkOptionName::Index,
+            _ => unreachable!(),
+        };
+        Ok(ElasticsearchSinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_redis_sink_option(&mut self) -> Result<RedisSinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[KEY])? {
+            KEY => {
+                self.expect_keyword(PREFIX)?;
+                RedisSinkOptionName::KeyPrefix
+            }
+            _ => unreachable!(),
+        };
+        Ok(RedisSinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_nats_sink_option(&mut self) -> Result<NatsSinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DEDUPLICATION, STREAM, SUBJECT])? {
+            DEDUPLICATION => {
+                self.expect_keyword(HEADERS)?;
+                NatsSinkOptionName::DeduplicationHeaders
+            }
+            STREAM => NatsSinkOptionName::Stream,
+            SUBJECT => NatsSinkOptionName::Subject,
+            _ => unreachable!(),
+        };
+        Ok(NatsSinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    /// Parses the optional parenthesized options of a sink that writes files.
+    fn parse_object_sink_options(&mut self) -> Result<Vec<ObjectSinkOption<Raw>>, ParserError> {
+        if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_object_sink_option)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(options)
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn parse_object_sink_option(&mut self) -> Result<ObjectSinkOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[BUCKET, CONTAINER, MAX, PREFIX, ROTATION])? {
+            BUCKET => ObjectSinkOptionName::Bucket,
+            CONTAINER => ObjectSinkOptionName::Container,
+            MAX => {
+                self.expect_keywords(&[FILE, SIZE])?;
+                ObjectSinkOptionName::MaxFileSize
+            }
+            PREFIX => ObjectSinkOptionName::Prefix,
+            ROTATION => {
+                self.expect_keyword(INTERVAL)?;
+                ObjectSinkOptionName::RotationInterval
+            }
+            _ => unreachable!(),
+        };
+        Ok(ObjectSinkOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_kafka_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
         self.expect_keyword(CONNECTION)?;
 
         let connection = self.parse_kafka_connection_reference()?;
+        let key = self.parse_sink_key()?;
+        Ok(CreateSinkConnection::Kafka { connection, key })
+    }
 
+    /// Parses the optional `KEY (...) [NOT ENFORCED]` clause of a sink.
+    fn parse_sink_key(&mut self) -> Result<Option<KafkaSinkKey>, ParserError> {
         // one token of lookahead:
         // * `KEY (` means we're parsing a list of columns for the key
         // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
@@ -2793,7 +3535,7 @@ impl<'a> Parser<'a> {
             } else {
                 None
             };
-        Ok(CreateSinkConnection::Kafka { connection, key })
+        Ok(key)
     }
 
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
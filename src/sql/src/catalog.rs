@@ -495,6 +495,8 @@ pub enum CatalogType<T: TypeReference> {
     Uuid,
     VarChar,
     Int2Vector,
+    Inet,
+    Cidr,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
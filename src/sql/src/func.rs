@@ -17,7 +17,9 @@ use std::fmt;
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
+use ordered_float::OrderedFloat;
 
+use mz_expr::custom_aggregate::{self, CustomAggregate, CustomAggregateHandle};
 use mz_expr::func;
 use mz_ore::collections::CollectionExt;
 use mz_pgrepr::oid;
@@ -90,6 +92,7 @@ impl TypeCategory {
             | ScalarType::RegProc
             | ScalarType::RegType
             | ScalarType::Numeric { .. } => Self::Numeric,
+            ScalarType::Inet | ScalarType::Cidr => Self::NetworkAddress,
             ScalarType::Interval => Self::Timespan,
             ScalarType::List { .. } => Self::List,
             ScalarType::PgLegacyChar
@@ -157,6 +160,7 @@ impl TypeCategory {
             | CatalogType::RegProc
             | CatalogType::RegType
             | CatalogType::Numeric { .. } => Self::Numeric,
+            CatalogType::Inet | CatalogType::Cidr => Self::NetworkAddress,
             CatalogType::Interval => Self::Timespan,
             CatalogType::List { .. } => Self::List,
             CatalogType::PgLegacyChar
@@ -185,13 +189,13 @@ impl TypeCategory {
             | Self::Enum
             | Self::Geometric
             | Self::List
-            | Self::NetworkAddress
             | Self::Pseudo
             | Self::Range
             | Self::Unknown
             | Self::UserDefined => None,
             Self::Boolean => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz),
+            Self::NetworkAddress => Some(ScalarType::Inet),
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
@@ -871,6 +875,8 @@ impl From<ScalarBaseType> for ParamType {
             PgLegacyChar => ScalarType::PgLegacyChar,
             Jsonb => ScalarType::Jsonb,
             Uuid => ScalarType::Uuid,
+            Inet => ScalarType::Inet,
+            Cidr => ScalarType::Cidr,
             Oid => ScalarType::Oid,
             RegClass => ScalarType::RegClass,
             RegProc => ScalarType::RegProc,
@@ -1827,6 +1833,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(Float32) => UnaryFunc::AbsFloat32(func::AbsFloat32), 1394;
             params!(Float64) => UnaryFunc::AbsFloat64(func::AbsFloat64), 1395;
         },
+        "array_append" => Scalar {
+            params!(ArrayAnyCompatible, AnyCompatible) => Operation::binary(|ecx, lhs, rhs| {
+                let rhs = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ArrayCreate { elem_type: ecx.scalar_type(&rhs) },
+                    exprs: vec![rhs],
+                };
+                Ok(lhs.call_binary(rhs, BinaryFunc::ArrayArrayConcat))
+            }) => ArrayAnyCompatible, 378;
+        },
         "array_cat" => Scalar {
             params!(ArrayAnyCompatible, ArrayAnyCompatible) => Operation::binary(|_ecx, lhs, rhs| {
                 Ok(lhs.call_binary(rhs, BinaryFunc::ArrayArrayConcat))
@@ -1842,6 +1857,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "array_lower" => Scalar {
             params!(ArrayAny, Int64) => BinaryFunc::ArrayLower => Int32, 2091;
         },
+        "array_prepend" => Scalar {
+            params!(AnyCompatible, ArrayAnyCompatible) => Operation::binary(|ecx, lhs, rhs| {
+                let lhs = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::ArrayCreate { elem_type: ecx.scalar_type(&lhs) },
+                    exprs: vec![lhs],
+                };
+                Ok(lhs.call_binary(rhs, BinaryFunc::ArrayArrayConcat))
+            }) => ArrayAnyCompatible, 379;
+        },
         "array_remove" => Scalar {
             params!(ArrayAnyCompatible, AnyCompatible) => BinaryFunc::ArrayRemove => ArrayAnyCompatible, 3167;
         },
@@ -1882,6 +1906,11 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(Float64) => UnaryFunc::CeilFloat64(func::CeilFloat64), 2308;
             params!(Numeric) => UnaryFunc::CeilNumeric(func::CeilNumeric), 1711;
         },
+        "ceiling" => Scalar {
+            params!(Float32) => UnaryFunc::CeilFloat32(func::CeilFloat32), oid::FUNC_CEILING_F32_OID;
+            params!(Float64) => UnaryFunc::CeilFloat64(func::CeilFloat64), 2320;
+            params!(Numeric) => UnaryFunc::CeilNumeric(func::CeilNumeric), 2167;
+        },
         "char_length" => Scalar {
             params!(String) => UnaryFunc::CharLength(func::CharLength), 1381;
         },
@@ -1923,6 +1952,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "cot" => Scalar {
             params!(Float64) => UnaryFunc::Cot(func::Cot), 1607;
         },
+        "corr" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("corr")) => Float64, 2829;
+        },
+        "covar_pop" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_pop")) => Float64, 2827;
+        },
+        "covar_samp" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_samp")) => Float64, 2828;
+        },
         "current_schema" => Scalar {
             // TODO: this should be name
             params!() => sql_impl_func("pg_catalog.current_schemas(false)[1]") => String, 1402;
@@ -2030,6 +2068,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(String, String, String) => VariadicFunc::HmacString, 44156;
             params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes, 44157;
         },
+        "host" => Scalar {
+            params!(Inet) => UnaryFunc::InetHost(func::InetHost), 699;
+        },
         "int4range" => Scalar {
             params!(Int32, Int32) => Operation::variadic(|_ecx, mut exprs| {
                 exprs.push(HirScalarExpr::literal(Datum::String("[)"), ScalarType::String));
@@ -2132,6 +2173,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(Numeric) => UnaryFunc::Log10Numeric(func::Log10Numeric), 1741;
             params!(Numeric, Numeric) => BinaryFunc::LogNumeric, 1736;
         },
+        "initcap" => Scalar {
+            params!(String) => UnaryFunc::Initcap(func::Initcap), 872;
+        },
         "lower" => Scalar {
             params!(String) => UnaryFunc::Lower(func::Lower), 870;
             params!(RangeAny) => UnaryFunc::RangeLower(func::RangeLower) => AnyElement, 3848;
@@ -2150,9 +2194,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(String) => UnaryFunc::TrimLeadingWhitespace(func::TrimLeadingWhitespace), 881;
             params!(String, String) => BinaryFunc::TrimLeading, 875;
         },
+        "make_date" => Scalar {
+            params!(Int64, Int64, Int64) => VariadicFunc::MakeDate, 3846;
+        },
         "make_timestamp" => Scalar {
             params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp, 3461;
         },
+        "masklen" => Scalar {
+            params!(Inet) => UnaryFunc::InetMasklen(func::InetMasklen), 697;
+        },
         "md5" => Scalar {
             params!(String) => Operation::unary(move |_ecx, input| {
                 let algorithm = HirScalarExpr::literal(Datum::String("md5"), ScalarType::String);
@@ -2354,6 +2404,11 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "position" => Scalar {
             params!(String, String) => BinaryFunc::Position, 849;
         },
+        "pi" => Scalar {
+            params!() => Operation::nullary(|_ecx| {
+                Ok(HirScalarExpr::literal(Datum::from(std::f64::consts::PI), ScalarType::Float64))
+            }) => Float64, 1610;
+        },
         "pow" => Scalar {
             params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("pow")) => Float64, 1346;
         },
@@ -2371,12 +2426,20 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             params!(String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3396;
             params!(String, String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3397;
         },
+        "regexp_replace" => Scalar {
+            params!(String, String, String) => VariadicFunc::RegexpReplace, 2284;
+            params!(String, String, String, String) => VariadicFunc::RegexpReplace, 2285;
+        },
         "replace" => Scalar {
             params!(String, String, String) => VariadicFunc::Replace, 2087;
         },
         "right" => Scalar {
             params!(String, Int32) => BinaryFunc::Right, 3061;
         },
+        "rpad" => Scalar {
+            params!(String, Int64) => VariadicFunc::PadTrailing, 880;
+            params!(String, Int64, String) => VariadicFunc::PadTrailing, 874;
+        },
         "round" => Scalar {
             params!(Float32) => UnaryFunc::RoundFloat32(func::RoundFloat32), oid::FUNC_ROUND_F32_OID;
             params!(Float64) => UnaryFunc::RoundFloat64(func::RoundFloat64), 1342;
@@ -2399,6 +2462,14 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "sha512" => Scalar {
             params!(Bytes) => digest("sha512") => Bytes, 3422;
         },
+        "sign" => Scalar {
+            params!(Float64) => sql_impl_func(
+                "CASE WHEN $1 = 'NaN' THEN $1 WHEN $1 > 0 THEN 1 WHEN $1 < 0 THEN -1 ELSE 0 END"
+            ) => Float64, 2310;
+            params!(Numeric) => sql_impl_func(
+                "CASE WHEN $1 = 'NaN' THEN $1 WHEN $1 > 0 THEN 1 WHEN $1 < 0 THEN -1 ELSE 0 END"
+            ) => Numeric, 1706;
+        },
         "sin" => Scalar {
             params!(Float64) => UnaryFunc::Sin(func::Sin), 1604;
         },
@@ -2447,10 +2518,17 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "substr" => Scalar {
             params!(String, Int64) => VariadicFunc::Substr, 883;
             params!(String, Int64, Int64) => VariadicFunc::Substr, 877;
+            params!(Bytes, Int64) => VariadicFunc::SubstrBytes, 2086;
+            params!(Bytes, Int64, Int64) => VariadicFunc::SubstrBytes, 2085;
         },
         "substring" => Scalar {
             params!(String, Int64) => VariadicFunc::Substr, 937;
             params!(String, Int64, Int64) => VariadicFunc::Substr, 936;
+            params!(Bytes, Int64) => VariadicFunc::SubstrBytes, 2013;
+            params!(Bytes, Int64, Int64) => VariadicFunc::SubstrBytes, 2012;
+        },
+        "scale" => Scalar {
+            params!(Numeric) => UnaryFunc::ScaleNumeric(func::ScaleNumeric), 3281;
         },
         "sqrt" => Scalar {
             params!(Float64) => UnaryFunc::SqrtFloat64(func::SqrtFloat64), 1344;
@@ -2462,6 +2540,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "atan" => Scalar {
             params!(Float64) => UnaryFunc::Atan(func::Atan), 1602;
         },
+        "atan2" => Scalar {
+            params!(Float64, Float64) => BinaryFunc::Atan2, 1603;
+        },
         "tanh" => Scalar {
             params!(Float64) => UnaryFunc::Tanh(func::Tanh), 2464;
         },
@@ -2513,10 +2594,14 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "to_timestamp" => Scalar {
             params!(Float64) => UnaryFunc::ToTimestamp(func::ToTimestamp), 1158;
         },
+        "translate" => Scalar {
+            params!(String, String, String) => VariadicFunc::Translate, 878;
+        },
         "trunc" => Scalar {
             params!(Float32) => UnaryFunc::TruncFloat32(func::TruncFloat32), oid::FUNC_TRUNC_F32_OID;
             params!(Float64) => UnaryFunc::TruncFloat64(func::TruncFloat64), 1343;
             params!(Numeric) => UnaryFunc::TruncNumeric(func::TruncNumeric), 1710;
+            params!(Numeric, Int32) => BinaryFunc::TruncNumeric, 1709;
         },
         "upper" => Scalar {
             params!(String) => UnaryFunc::Upper(func::Upper), 871;
@@ -2528,6 +2613,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "upper_inf" => Scalar {
             params!(RangeAny) => UnaryFunc::RangeUpperInf(func::RangeUpperInf) => Bool, 3854;
         },
+        "uuid_generate_v5" => Scalar {
+            params!(Uuid, String) => BinaryFunc::UuidGenerateV5, oid::FUNC_UUID_GENERATE_V5;
+        },
         "variance" => Scalar {
             params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => Float64, 2151;
             params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => Float64, 2152;
@@ -2579,11 +2667,26 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             }) => ArrayAny, 2335;
             params!(ArrayAny) => Operation::unary(|_ecx, _e| bail_unsupported!("array_agg on arrays")) => ArrayAny, 4053;
         },
+        "bit_and" => Aggregate {
+            params!(Int16) => AggregateFunc::BitAnd, 2236;
+            params!(Int32) => AggregateFunc::BitAnd, 2238;
+            params!(Int64) => AggregateFunc::BitAnd, 2240;
+        },
+        "bit_or" => Aggregate {
+            params!(Int16) => AggregateFunc::BitOr, 2237;
+            params!(Int32) => AggregateFunc::BitOr, 2239;
+            params!(Int64) => AggregateFunc::BitOr, 2241;
+        },
+        "bit_xor" => Aggregate {
+            params!(Int16) => AggregateFunc::BitXor, 6164;
+            params!(Int32) => AggregateFunc::BitXor, 6165;
+            params!(Int64) => AggregateFunc::BitXor, 6166;
+        },
         "bool_and" => Aggregate {
-            params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("bool_and")) => Bool, 2517;
+            params!(Bool) => AggregateFunc::BoolAnd, 2517;
         },
         "bool_or" => Aggregate {
-            params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("bool_or")) => Bool, 2518;
+            params!(Bool) => AggregateFunc::BoolOr, 2518;
         },
         "count" => Aggregate {
             params!() => Operation::nullary(|_ecx| {
@@ -2592,6 +2695,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             }), 2803;
             params!(Any) => AggregateFunc::Count => Int32, 2147;
         },
+        "every" => Aggregate {
+            params!(Bool) => AggregateFunc::BoolAnd, 2519;
+        },
         "max" => Aggregate {
             params!(Bool) => AggregateFunc::MaxBool, oid::FUNC_MAX_BOOL_OID;
             params!(Int16) => AggregateFunc::MaxInt16, 2117;
@@ -2683,7 +2789,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 };
                 Ok((e, AggregateFunc::StringAgg { order_by }))
             }), 3538;
-            params!(Bytes, Bytes) => Operation::binary(|_ecx, _l, _r| bail_unsupported!("string_agg")) => Bytes, 3545;
+            params!(Bytes, Bytes) => Operation::binary_ordered(|_ecx, value, sep, order_by| {
+                let e = HirScalarExpr::CallVariadic {
+                    func: VariadicFunc::RecordCreate {
+                        field_names: vec![ColumnName::from("value"), ColumnName::from("sep")],
+                    },
+                    exprs: vec![value, sep],
+                };
+                Ok((e, AggregateFunc::StringAgg { order_by }))
+            }), 3545;
         },
         "sum" => Aggregate {
             params!(Int16) => AggregateFunc::SumInt16, 2109;
@@ -2991,12 +3105,27 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 })
             }) => ReturnType::set_of(RecordAny), oid::FUNC_CSV_EXTRACT_OID;
         },
+        "approx_count_distinct" => Scalar {
+            params!(Any) => Operation::nullary(|_ecx| catalog_name_only!("approx_count_distinct")) => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+        },
+        "approx_percentile" => Aggregate {
+            params!(Float64, Float64) => percentile(|fraction| AggregateFunc::ApproxPercentile { fraction }) => Float64, oid::FUNC_APPROX_PERCENTILE_OID;
+        },
+        "arg_max" => Scalar {
+            params!(Any, Any) => Operation::nullary(|_ecx| catalog_name_only!("arg_max")) => Any, oid::FUNC_ARG_MAX_OID;
+        },
+        "arg_min" => Scalar {
+            params!(Any, Any) => Operation::nullary(|_ecx| catalog_name_only!("arg_min")) => Any, oid::FUNC_ARG_MIN_OID;
+        },
         "concat_agg" => Aggregate {
             params!(Any) => Operation::unary(|_ecx, _e| bail_unsupported!("concat_agg")) => String, oid::FUNC_CONCAT_AGG_OID;
         },
         "current_timestamp" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp, oid::FUNC_CURRENT_TIMESTAMP_OID;
         },
+        "hash_sample" => Scalar {
+            params!(Any, Float64) => BinaryFunc::HashSample => Bool, oid::FUNC_MZ_HASH_SAMPLE_OID;
+        },
         "list_agg" => Aggregate {
             params!(Any) => Operation::unary_ordered(|ecx, e, order_by| {
                 if let ScalarType::Char {.. }  = ecx.scalar_type(&e) {
@@ -3050,6 +3179,9 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "map_length" => Scalar {
             params![MapAny] => UnaryFunc::MapLength(func::MapLength) => Int32, oid::FUNC_MAP_LENGTH_OID;
         },
+        "median" => Scalar {
+            params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("median")) => Float64, oid::FUNC_MEDIAN_OID;
+        },
         "mz_environment_id" => Scalar {
             params!() => UnmaterializableFunc::MzEnvironmentId, oid::FUNC_MZ_ENVIRONMENT_ID_OID;
         },
@@ -3068,6 +3200,9 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
         "mz_version_num" => Scalar {
             params!() => UnmaterializableFunc::MzVersionNum, oid::FUNC_MZ_VERSION_NUM_OID;
         },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => percentile(|fraction| AggregateFunc::PercentileCont { fraction }) => Float64, oid::FUNC_PERCENTILE_CONT_OID;
+        },
         "regexp_extract" => Table {
             params!(String, String) => Operation::binary(move |_ecx, regex, haystack| {
                 let regex = match regex.into_literal_string() {
@@ -3101,6 +3236,25 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
                 })
             }), oid::FUNC_REPEAT_OID;
         },
+        "round_half_even" => Scalar {
+            params!(Numeric) => Operation::unary(|_ecx, e| {
+                Ok(e.call_binary(
+                    HirScalarExpr::literal(Datum::Int32(0), ScalarType::Int32),
+                    BinaryFunc::RoundNumericHalfEven,
+                ))
+            }) => Numeric, oid::FUNC_ROUND_HALF_EVEN_OID;
+            params!(Numeric, Int32) => BinaryFunc::RoundNumericHalfEven => Numeric, oid::FUNC_ROUND_HALF_EVEN_SCALE_OID;
+        },
+        "time_bucket" => Scalar {
+            params!(Interval, Timestamp) => Operation::binary(|_ecx, stride, source| {
+                Ok(stride.call_binary(source, BinaryFunc::DateBinTimestamp))
+            }), oid::FUNC_MZ_TIME_BUCKET_UNIX_EPOCH_TS_OID;
+            params!(Interval, TimestampTz) => Operation::binary(|_ecx, stride, source| {
+                Ok(stride.call_binary(source, BinaryFunc::DateBinTimestampTz))
+            }), oid::FUNC_MZ_TIME_BUCKET_UNIX_EPOCH_TSTZ_OID;
+            params!(Interval, Timestamp, Timestamp) => VariadicFunc::DateBinTimestamp, oid::FUNC_MZ_TIME_BUCKET_TS_OID;
+            params!(Interval, TimestampTz, TimestampTz) => VariadicFunc::DateBinTimestampTz, oid::FUNC_MZ_TIME_BUCKET_TSTZ_OID;
+        },
         "unnest" => Table {
             vec![ArrayAny] => Operation::unary(move |ecx, e| {
                 let el_typ = ecx.scalar_type(&e).unwrap_array_element_type().clone();
@@ -3126,6 +3280,10 @@ pub static MZ_CATALOG_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(||
             }) =>
                 // This return type should be equivalent to "ListElementAny", but this would be its sole use.
                 ReturnType::set_of(Any), oid::FUNC_UNNEST_LIST_OID;
+        },
+        "xxhash64" => Scalar {
+            params!(String) => UnaryFunc::Xxhash64String(func::Xxhash64String), oid::FUNC_XXHASH64_STRING_OID;
+            params!(Bytes) => UnaryFunc::Xxhash64Bytes(func::Xxhash64Bytes), oid::FUNC_XXHASH64_BYTES_OID;
         }
     }
 });
@@ -3174,6 +3332,54 @@ pub static MZ_INTERNAL_BUILTINS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|
             // message is the second argument.
             params!(Any, String) => VariadicFunc::ErrorIfNull => Any, oid::FUNC_MZ_ERROR_IF_NULL_OID;
         },
+        "mz_hll_estimate" => Scalar {
+            params!(Bytes) => UnaryFunc::MzHllEstimate(func::MzHllEstimate), oid::FUNC_MZ_HLL_ESTIMATE_OID;
+        },
+        "mz_hll_sketch" => Scalar {
+            params!(Any) => UnaryFunc::MzHllSketch(func::MzHllSketch) => Bytes, oid::FUNC_MZ_HLL_SKETCH_OID;
+        },
+        "mz_hll_union" => Aggregate {
+            params!(Bytes) => AggregateFunc::HllUnion, oid::FUNC_MZ_HLL_UNION_OID;
+        },
+        "mz_max_record" => Aggregate {
+            params!(RecordAny) => AggregateFunc::MaxRecord => RecordAny, oid::FUNC_MZ_MAX_RECORD_OID;
+        },
+        "mz_count_diffs" => Aggregate {
+            params!() => Operation::nullary(|_ecx| {
+                Ok((HirScalarExpr::literal_true(), AggregateFunc::CountDiffs))
+            }) => Int64, oid::FUNC_MZ_COUNT_DIFFS_OID;
+        },
+        "mz_custom_aggregate" => Aggregate {
+            params!(String, Any) => Operation::binary(|_ecx, name, value| {
+                let name = match name.into_literal_string() {
+                    Some(name) => name,
+                    None => sql_bail!("custom aggregate name must be a non-null constant"),
+                };
+                let aggregate = match CustomAggregateHandle::resolve(&name) {
+                    Ok(aggregate) => aggregate,
+                    Err(e) => sql_bail!("{}", e),
+                };
+                Ok((value, AggregateFunc::Custom { aggregate }))
+            }) => Any, oid::FUNC_MZ_CUSTOM_AGGREGATE_OID;
+        },
+        "mz_first_value" => Aggregate {
+            params!(Any) => Operation::unary_ordered(|_ecx, e, order_by| {
+                Ok((e, AggregateFunc::First { order_by }))
+            }) => Any, oid::FUNC_MZ_FIRST_VALUE_OID;
+        },
+        "mz_last_value" => Aggregate {
+            params!(Any) => Operation::unary_ordered(|_ecx, e, order_by| {
+                Ok((e, AggregateFunc::Last { order_by }))
+            }) => Any, oid::FUNC_MZ_LAST_VALUE_OID;
+        },
+        "mz_min_record" => Aggregate {
+            params!(RecordAny) => AggregateFunc::MinRecord => RecordAny, oid::FUNC_MZ_MIN_RECORD_OID;
+        },
+        "mz_random" => Scalar {
+            // Unlike PostgreSQL's `random`, this returns the same value for
+            // every row of a query, as it is seeded by the query's timestamp.
+            params!() => UnmaterializableFunc::MzRandom, oid::FUNC_MZ_RANDOM_OID;
+        },
         "mz_render_typmod" => Scalar {
             params!(Oid, Int32) => BinaryFunc::MzRenderTypmod, oid::FUNC_MZ_RENDER_TYPMOD_OID;
         },
@@ -3202,6 +3408,55 @@ fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     })
 }
 
+/// Reports whether `name` is the name of a builtin function in any schema.
+pub fn is_builtin_function(name: &str) -> bool {
+    [
+        &*PG_CATALOG_BUILTINS,
+        &*INFORMATION_SCHEMA_BUILTINS,
+        &*MZ_CATALOG_BUILTINS,
+        &*MZ_INTERNAL_BUILTINS,
+    ]
+    .iter()
+    .any(|builtins| builtins.contains_key(name))
+}
+
+/// Registers a custom aggregate function, which can then be called from SQL
+/// under its name. See [`mz_expr::custom_aggregate`].
+///
+/// Fails if the name is that of a builtin function, which the custom
+/// aggregate would shadow, or of an already registered custom aggregate.
+pub fn register_custom_aggregate<A: CustomAggregate>(aggregate: A) -> Result<(), PlanError> {
+    if is_builtin_function(aggregate.name()) {
+        sql_bail!(
+            "cannot register custom aggregate {}: a builtin function with that name exists",
+            aggregate.name()
+        );
+    }
+    custom_aggregate::register(aggregate).map_err(|e| sql_err!("{}", e))
+}
+
+/// The names of the ordered-set aggregates, which aggregate the values
+/// specified in a `WITHIN GROUP (ORDER BY ...)` clause. See [`percentile`].
+pub const ORDERED_SET_AGGREGATES: &[&str] = &["approx_percentile", "percentile_cont"];
+
+/// Plans an ordered-set aggregate that computes the value at a given fraction
+/// of the sorted values of a group. Its arguments are the value, which comes
+/// from the `WITHIN GROUP` clause, and the fraction.
+fn percentile(
+    func: fn(OrderedFloat<f64>) -> AggregateFunc,
+) -> Operation<(HirScalarExpr, AggregateFunc)> {
+    Operation::binary(move |_ecx, value, fraction| {
+        // The fraction is part of the aggregate function rather than its
+        // input, so it must be a constant.
+        let fraction = match fraction.into_literal_float64() {
+            Some(f) if (0.0..=1.0).contains(&f) => f,
+            Some(f) => sql_bail!("percentile value {} is not between 0 and 1", f),
+            None => sql_bail!("percentile value must be a non-null constant"),
+        };
+        Ok((value, func(OrderedFloat(fraction))))
+    })
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
@@ -3390,6 +3645,9 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt16, UInt32) => BitShiftLeftUInt16, oid::FUNC_SHIFT_LEFT_UINT16;
             params!(UInt32, UInt32) => BitShiftLeftUInt32, oid::FUNC_SHIFT_LEFT_UINT32;
             params!(UInt64, UInt32) => BitShiftLeftUInt64, oid::FUNC_SHIFT_LEFT_UINT64;
+            params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, InetContains))
+            }) => Bool, 931;
         },
         ">>" => Scalar {
             params!(Int16, Int32) => BitShiftRightInt16, 1879;
@@ -3398,6 +3656,15 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt16, UInt32) => BitShiftRightUInt16, oid::FUNC_SHIFT_RIGHT_UINT16;
             params!(UInt32, UInt32) => BitShiftRightUInt32, oid::FUNC_SHIFT_RIGHT_UINT32;
             params!(UInt64, UInt32) => BitShiftRightUInt64, oid::FUNC_SHIFT_RIGHT_UINT64;
+            params!(Inet, Inet) => InetContains, 933;
+        },
+        "<<=" => Scalar {
+            params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                Ok(rhs.call_binary(lhs, InetContainsOrEquals))
+            }) => Bool, 932;
+        },
+        ">>=" => Scalar {
+            params!(Inet, Inet) => InetContainsOrEquals, 934;
         },
 
         // ILIKE
@@ -3552,6 +3819,9 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
         "#>>" => Scalar {
             params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String))) => JsonbGetPath { stringify: true }, 3206;
         },
+        "#-" => Scalar {
+            params!(Jsonb, ScalarType::Array(Box::new(ScalarType::String))) => JsonbDeletePath, 3287;
+        },
         "@>" => Scalar {
             params!(Jsonb, Jsonb) => JsonbContainsJsonb, 3246;
             params!(Jsonb, String) => Operation::binary(|_ecx, lhs, rhs| {
@@ -3623,6 +3893,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lt, 2062;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lt, 1322;
             params!(Uuid, Uuid) => BinaryFunc::Lt, 2974;
+            params!(Inet, Inet) => BinaryFunc::Lt, 1203;
             params!(Interval, Interval) => BinaryFunc::Lt, 1332;
             params!(Bytes, Bytes) => BinaryFunc::Lt, 1957;
             params!(String, String) => BinaryFunc::Lt, 664;
@@ -3651,6 +3922,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lte, 2063;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lte, 1323;
             params!(Uuid, Uuid) => BinaryFunc::Lte, 2976;
+            params!(Inet, Inet) => BinaryFunc::Lte, 1204;
             params!(Interval, Interval) => BinaryFunc::Lte, 1333;
             params!(Bytes, Bytes) => BinaryFunc::Lte, 1958;
             params!(String, String) => BinaryFunc::Lte, 665;
@@ -3679,6 +3951,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gt, 2064;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gt, 1324;
             params!(Uuid, Uuid) => BinaryFunc::Gt, 2975;
+            params!(Inet, Inet) => BinaryFunc::Gt, 1205;
             params!(Interval, Interval) => BinaryFunc::Gt, 1334;
             params!(Bytes, Bytes) => BinaryFunc::Gt, 1959;
             params!(String, String) => BinaryFunc::Gt, 666;
@@ -3707,6 +3980,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gte, 2065;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gte, 1325;
             params!(Uuid, Uuid) => BinaryFunc::Gte, 2977;
+            params!(Inet, Inet) => BinaryFunc::Gte, 1206;
             params!(Interval, Interval) => BinaryFunc::Gte, 1335;
             params!(Bytes, Bytes) => BinaryFunc::Gte, 1960;
             params!(String, String) => BinaryFunc::Gte, 667;
@@ -3744,6 +4018,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Eq, 2060;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Eq, 1320;
             params!(Uuid, Uuid) => BinaryFunc::Eq, 2972;
+            params!(Inet, Inet) => BinaryFunc::Eq, 1201;
             params!(Interval, Interval) => BinaryFunc::Eq, 1330;
             params!(Bytes, Bytes) => BinaryFunc::Eq, 1955;
             params!(String, String) => BinaryFunc::Eq, 98;
@@ -3773,6 +4048,7 @@ static OP_IMPLS: Lazy<HashMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::NotEq, 2061;
             params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq, 1321;
             params!(Uuid, Uuid) => BinaryFunc::NotEq, 2973;
+            params!(Inet, Inet) => BinaryFunc::NotEq, 1202;
             params!(Interval, Interval) => BinaryFunc::NotEq, 1331;
             params!(Bytes, Bytes) => BinaryFunc::NotEq, 1956;
             params!(String, String) => BinaryFunc::NotEq, 531;
@@ -3804,3 +4080,46 @@ pub fn resolve_op(op: &str) -> Result<&'static [FuncImpl<HirScalarExpr>], PlanEr
         None => bail_unsupported!(format!("[{}]", op)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Named(&'static str);
+
+    impl CustomAggregate for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn output_type(&self, input_type: ColumnType) -> ColumnType {
+            input_type
+        }
+
+        fn init(&self) -> Row {
+            Row::default()
+        }
+
+        fn insert(&self, _state: &mut Row, _value: Datum) {}
+
+        fn negate(&self, _state: &mut Row) {}
+
+        fn merge(&self, _state: &mut Row, _other: &Row) {}
+
+        fn finalize(&self, _state: &Row) -> Row {
+            Row::pack_slice(&[Datum::Null])
+        }
+    }
+
+    #[test]
+    fn test_register_custom_aggregate() {
+        for name in ["sum", "median", "mz_hll_union"] {
+            assert!(register_custom_aggregate(Named(name)).is_err());
+            assert!(CustomAggregateHandle::resolve(name).is_err());
+        }
+        register_custom_aggregate(Named("test_sql_noop")).unwrap();
+        assert!(register_custom_aggregate(Named("test_sql_noop")).is_err());
+        assert!(CustomAggregateHandle::resolve("test_sql_noop").is_ok());
+    }
+}
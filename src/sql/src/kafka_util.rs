@@ -47,6 +47,8 @@ pub fn validate_options_for_context<T: AstInfo>(
         let limited_to_context = match name {
             Acks => None,
             ClientId => None,
+            ConsistencyTopic => Some(Sink),
+            DeadLetterTopic => Some(Source),
             EnableIdempotence => None,
             FetchMessageMaxBytes => None,
             GroupIdPrefix => None,
@@ -57,6 +59,7 @@ pub fn validate_options_for_context<T: AstInfo>(
             StartTimestamp => Some(Source),
             StartOffset => Some(Source),
             PartitionCount => Some(Sink),
+            PartitionStrategy => Some(Sink),
             ReplicationFactor => Some(Sink),
             RetentionBytes => Some(Sink),
             RetentionMs => Some(Sink),
@@ -80,6 +83,8 @@ generate_extracted_config!(
     KafkaConfigOption,
     (Acks, String),
     (ClientId, String),
+    (ConsistencyTopic, String),
+    (DeadLetterTopic, String),
     (EnableIdempotence, bool),
     (FetchMessageMaxBytes, i32),
     (GroupIdPrefix, String),
@@ -94,6 +99,7 @@ generate_extracted_config!(
     (StartTimestamp, i64),
     (StartOffset, Vec<i64>),
     (PartitionCount, i32, Default(-1)),
+    (PartitionStrategy, String),
     (ReplicationFactor, i32, Default(-1)),
     (RetentionBytes, i64),
     (RetentionMs, i64)
@@ -258,7 +264,8 @@ pub async fn create_consumer(
 ///   before now (e.g. `-10` means 10 millis ago)
 ///
 /// If `START TIMESTAMP` has not been configured, an empty Option is
-/// returned.
+/// returned. A `START OFFSET` option is instead validated against the
+/// partitions of `topic`.
 pub async fn lookup_start_offsets<C>(
     consumer: Arc<BaseConsumer<C>>,
     topic: &str,
@@ -270,7 +277,10 @@ where
 {
     let time_offset = match offsets {
         KafkaStartOffsetType::StartTimestamp(time) => time,
-        _ => return Ok(None),
+        KafkaStartOffsetType::StartOffset(offsets) => {
+            validate_start_offsets(consumer, topic, offsets.len()).await?;
+            return Ok(None);
+        }
     };
 
     let time_offset = if time_offset < 0 {
@@ -341,6 +351,46 @@ where
     .map_err(|e| sql_err!("{}", e))?
 }
 
+/// Validates that a `START OFFSET` option with `num_offsets` offsets does not
+/// provide offsets for partitions of `topic` that do not exist.
+///
+/// Partitions that are added later would otherwise be read from the provided
+/// offsets rather than from their beginning, silently skipping records.
+async fn validate_start_offsets<C>(
+    consumer: Arc<BaseConsumer<C>>,
+    topic: &str,
+    num_offsets: usize,
+) -> Result<(), PlanError>
+where
+    C: ConsumerContext + 'static,
+{
+    task::spawn_blocking(|| format!("kafka_validate_start_offsets:{topic}"), {
+        let topic = topic.to_string();
+        move || {
+            let num_partitions = mz_kafka_util::client::get_partitions(
+                consumer.as_ref().client(),
+                &topic,
+                Duration::from_secs(10),
+            )
+            .map_err(|e| sql_err!("{}", e))?
+            .len();
+
+            if num_offsets > num_partitions {
+                sql_bail!(
+                    "START OFFSET specified {} offsets, but topic {} only has {} partitions",
+                    num_offsets,
+                    topic,
+                    num_partitions,
+                );
+            }
+
+            Ok(())
+        }
+    })
+    .await
+    .map_err(|e| sql_err!("{}", e))?
+}
+
 // Kafka supports bulk lookup of watermarks, but it is not exposed in rdkafka.
 // If that ever changes, we will want to first collect all pids that have no
 // offset for a given timestamp and then do a single request (instead of doing
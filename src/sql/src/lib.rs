@@ -167,6 +167,7 @@ pub mod ast;
 pub mod catalog;
 pub mod func;
 pub mod kafka_util;
+pub mod mysql_util;
 pub mod names;
 #[macro_use]
 pub mod normalize;
@@ -174,3 +175,4 @@ pub mod parse;
 pub mod plan;
 pub mod pure;
 pub mod query_model;
+pub mod sql_server_util;
@@ -0,0 +1,153 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Provides convenience functions for working with MySQL from the `sql` package.
+
+use mz_sql_parser::ast::UnresolvedObjectName;
+use mz_storage_client::types::sources::mysql::MySqlTableDesc;
+
+use crate::normalize;
+use crate::plan::PlanError;
+
+/// The largest precision of a MySQL `decimal` column that fits in a
+/// Materialize `numeric`.
+const MAX_DECIMAL_PRECISION: u32 = 39;
+
+/// Returns the Materialize type to which values of the MySQL column type
+/// `column_type` are cast, or `None` if the MySQL type is not supported.
+///
+/// `column_type` is the full, lowercase column type as reported by
+/// `information_schema.columns`, e.g. `int unsigned` or `decimal(10,2)`.
+pub fn scalar_type(column_type: &str) -> Option<mz_pgrepr::Type> {
+    let (base, modifier) = match column_type.find('(') {
+        Some(i) => {
+            let end = column_type[i..].find(')')? + i;
+            (&column_type[..i], Some(&column_type[i + 1..end]))
+        }
+        None => (
+            column_type.split_whitespace().next().unwrap_or(column_type),
+            None,
+        ),
+    };
+    let unsigned = column_type.contains("unsigned");
+    let ty = match (base, unsigned) {
+        // `tinyint(1)` is the conventional spelling of `bool`, but MySQL
+        // permits it to hold any `tinyint`, so it is not special cased.
+        ("tinyint" | "smallint", false) => mz_pgrepr::Type::Int2,
+        ("tinyint" | "smallint", true) => mz_pgrepr::Type::UInt2,
+        ("mediumint" | "int" | "integer", false) => mz_pgrepr::Type::Int4,
+        ("mediumint" | "int" | "integer", true) => mz_pgrepr::Type::UInt4,
+        ("bigint", false) => mz_pgrepr::Type::Int8,
+        ("bigint", true) => mz_pgrepr::Type::UInt8,
+        ("float", _) => mz_pgrepr::Type::Float4,
+        ("double" | "real", _) => mz_pgrepr::Type::Float8,
+        ("decimal" | "numeric", _) => {
+            let precision = match modifier {
+                Some(modifier) => modifier.split(',').next()?.trim().parse().ok()?,
+                None => 10,
+            };
+            if precision > MAX_DECIMAL_PRECISION {
+                return None;
+            }
+            mz_pgrepr::Type::Numeric { constraints: None }
+        }
+        (
+            "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum" | "set",
+            _,
+        ) => mz_pgrepr::Type::Text,
+        ("binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob", _) => {
+            mz_pgrepr::Type::Bytea
+        }
+        ("date", _) => mz_pgrepr::Type::Date,
+        ("datetime", _) => mz_pgrepr::Type::Timestamp { precision: None },
+        ("timestamp", _) => mz_pgrepr::Type::TimestampTz { precision: None },
+        ("time", _) => mz_pgrepr::Type::Time { precision: None },
+        ("year", _) => mz_pgrepr::Type::Int2,
+        ("json", _) => mz_pgrepr::Type::Jsonb,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Resolves a reference to an upstream MySQL table, which may be qualified
+/// by its schema, against the tables available on the server.
+///
+/// Returns the schema-qualified name of the table along with its description.
+pub fn resolve_table<'a>(
+    tables: &'a [MySqlTableDesc],
+    reference: UnresolvedObjectName,
+) -> Result<(UnresolvedObjectName, &'a MySqlTableDesc), PlanError> {
+    let name = normalize::unresolved_object_name(reference)?;
+    if name.database.is_some() {
+        sql_bail!("MySQL table references must be of the form schema.table, but got {name}");
+    }
+    let mut candidates = tables.iter().filter(|t| {
+        t.name == name.item
+            && match &name.schema {
+                Some(schema) => &t.schema_name == schema,
+                None => true,
+            }
+    });
+    let table = match (candidates.next(), candidates.next()) {
+        (Some(table), None) => table,
+        (None, _) => sql_bail!("table {name} not found in source"),
+        (Some(_), Some(_)) => {
+            sql_bail!("table {name} is ambiguous, consider specifying the schema")
+        }
+    };
+    Ok((
+        UnresolvedObjectName::qualified(&[&table.schema_name, &table.name]),
+        table,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_pgrepr::Type;
+
+    use super::*;
+
+    #[test]
+    fn test_scalar_type() {
+        let cases = [
+            ("tinyint", Some(Type::Int2)),
+            ("tinyint(1)", Some(Type::Int2)),
+            ("tinyint unsigned", Some(Type::UInt2)),
+            ("smallint", Some(Type::Int2)),
+            ("mediumint unsigned", Some(Type::UInt4)),
+            ("int", Some(Type::Int4)),
+            ("int(11) unsigned", Some(Type::UInt4)),
+            ("bigint", Some(Type::Int8)),
+            ("bigint unsigned", Some(Type::UInt8)),
+            ("float", Some(Type::Float4)),
+            ("double", Some(Type::Float8)),
+            ("decimal", Some(Type::Numeric { constraints: None })),
+            ("decimal(10,2)", Some(Type::Numeric { constraints: None })),
+            ("decimal(39,0)", Some(Type::Numeric { constraints: None })),
+            ("decimal(40,0)", None),
+            ("varchar(255)", Some(Type::Text)),
+            ("longtext", Some(Type::Text)),
+            ("enum('a','b')", Some(Type::Text)),
+            ("set('a','b')", Some(Type::Text)),
+            ("varbinary(16)", Some(Type::Bytea)),
+            ("blob", Some(Type::Bytea)),
+            ("date", Some(Type::Date)),
+            ("datetime(6)", Some(Type::Timestamp { precision: None })),
+            ("timestamp", Some(Type::TimestampTz { precision: None })),
+            ("time", Some(Type::Time { precision: None })),
+            ("year", Some(Type::Int2)),
+            ("json", Some(Type::Jsonb)),
+            ("geometry", None),
+            ("bit(8)", None),
+        ];
+        for (column_type, expected) in cases {
+            assert_eq!(scalar_type(column_type), expected, "{}", column_type);
+        }
+    }
+}
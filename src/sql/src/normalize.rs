@@ -302,6 +302,8 @@ pub fn create_statement(
             format: _,
             include_metadata: _,
             envelope: _,
+            projection: _,
+            filter,
             if_not_exists,
             key_constraint: _,
             with_options: _,
@@ -309,6 +311,13 @@ pub fn create_statement(
         }) => {
             *name = allocate_name(name)?;
             *if_not_exists = false;
+            if let Some(filter) = filter {
+                let mut normalizer = QueryNormalizer::new(scx);
+                normalizer.visit_expr_mut(filter);
+                if let Some(err) = normalizer.err {
+                    return Err(err);
+                }
+            }
         }
 
         Statement::CreateSubsource(CreateSubsourceStatement {
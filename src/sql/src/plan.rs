@@ -44,7 +44,9 @@ use mz_ore::now::{self, NOW_ZERO};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain_new::{ExplainConfig, ExplainFormat};
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType};
-use mz_storage_client::types::sinks::{SinkEnvelope, StorageSinkConnectionBuilder};
+use mz_storage_client::types::sinks::{
+    SinkEnvelope, SinkErrorPolicy, StorageSinkConnectionBuilder,
+};
 use mz_storage_client::types::sources::{SourceDesc, Timeline};
 
 use crate::ast::{
@@ -367,6 +369,7 @@ pub struct CreateSinkPlan {
     pub name: QualifiedObjectName,
     pub sink: Sink,
     pub with_snapshot: bool,
+    pub error_policy: SinkErrorPolicy,
     pub if_not_exists: bool,
     pub host_config: StorageHostConfig,
 }
@@ -658,9 +661,30 @@ pub struct Table {
 pub struct Source {
     pub create_sql: String,
     pub ingestion: Option<Ingestion>,
+    pub webhook: Option<Webhook>,
+    /// Whether the source's data is written to it as Arrow record batches
+    /// over Arrow Flight.
+    pub flight: bool,
     pub desc: RelationDesc,
 }
 
+/// A source whose data is pushed to it over HTTP.
+#[derive(Clone, Debug, Serialize)]
+pub struct Webhook {
+    /// How requests are authenticated.
+    pub validation: WebhookValidation,
+}
+
+/// Requests to webhook sources are authenticated by their signature alone.
+#[derive(Clone, Debug, Serialize)]
+pub struct WebhookValidation {
+    /// The secret with which requests are signed.
+    pub secret_id: GlobalId,
+    /// The lowercase name of the header that carries the hex-encoded
+    /// HMAC-SHA256 signature of a request's body.
+    pub header: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct Ingestion {
     pub desc: SourceDesc,
@@ -684,6 +708,8 @@ pub struct Secret {
 pub struct Sink {
     pub create_sql: String,
     pub from: GlobalId,
+    /// The indices of the columns of `from` to sink, if not all of them.
+    pub projection: Option<Vec<usize>>,
     pub connection_builder: StorageSinkConnectionBuilder,
     pub envelope: SinkEnvelope,
 }
@@ -100,6 +100,12 @@ pub enum PlanError {
     UnrecognizedTypeInPostgresSource {
         cols: Vec<(String, Oid)>,
     },
+    UnrecognizedTypeInMySqlSource {
+        cols: Vec<(String, String)>,
+    },
+    UnrecognizedTypeInSqlServerSource {
+        cols: Vec<(String, String)>,
+    },
     FetchingCsrSchemaFailed {
         schema_lookup: String,
         cause: Arc<dyn Error + Send + Sync>,
@@ -343,6 +349,20 @@ impl fmt::Display for PlanError {
                     )
                 )
             },
+            Self::UnrecognizedTypeInMySqlSource { cols }
+            | Self::UnrecognizedTypeInSqlServerSource { cols } => {
+                let mut cols = cols.to_owned();
+                cols.sort();
+
+                write!(
+                    f,
+                    "the following columns contain unsupported types:\n{}",
+                    itertools::join(
+                        cols.into_iter().map(|(col, ty)| format!("{} ({})", col, ty)),
+                        "\n"
+                    )
+                )
+            },
             Self::FetchingCsrSchemaFailed { schema_lookup, .. } => {
                 write!(f, "failed to fetch schema {schema_lookup} from schema registry")
             }
@@ -20,6 +20,7 @@
 
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::sync::Arc;
 
 use mz_expr::explain::Indices;
 use mz_expr::{Id, LocalId, RowSetFinishing};
@@ -204,6 +205,7 @@ impl<'a> Explanation<'a> {
                 Reduce { aggregates, .. } => {
                     for agg in aggregates {
                         scalars.push(&agg.expr);
+                        scalars.extend(agg.filter.as_deref());
                     }
                 }
             }
@@ -276,18 +278,19 @@ impl<'a> Explanation<'a> {
 
     fn explain_types_internal(
         &mut self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         params: &BTreeMap<usize, ScalarType>,
     ) {
         for node in &mut self.nodes {
             // TODO(jamii) `typ` is itself recursive, so this is quadratic :(
             let typ = node.expr.typ(outers, params);
             let mut outers = outers.to_vec();
-            outers.insert(0, typ);
+            outers.insert(0, Arc::new(typ));
             for subquery in &mut node.subqueries {
                 subquery.explain_types_internal(&outers, params);
             }
-            node.typ = Some(outers.into_first());
+            let typ = outers.into_first();
+            node.typ = Some(Arc::try_unwrap(typ).unwrap_or_else(|typ| (*typ).clone()));
         }
     }
 
@@ -581,7 +584,13 @@ impl<'a> Explanation<'a> {
             write!(f, "distinct ")?;
         }
         self.fmt_scalar_expr(f, &expr.expr)?;
-        write!(f, ")")
+        write!(f, ")")?;
+        if let Some(filter) = &expr.filter {
+            write!(f, " filter (where ")?;
+            self.fmt_scalar_expr(f, filter)?;
+            write!(f, ")")?;
+        }
+        Ok(())
     }
 
     /// Retrieves the chain ID for the specified expression.
@@ -15,13 +15,16 @@
 use std::collections::BTreeMap;
 use std::fmt;
 use std::mem;
+use std::sync::Arc;
 
 use itertools::Itertools;
 use mz_expr::visit::Visit;
 use mz_expr::visit::VisitChildren;
 use mz_ore::stack::RecursionLimitError;
+use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
+use mz_expr::custom_aggregate::CustomAggregateHandle;
 use mz_expr::func;
 use mz_expr::virtual_syntax::{AlgExcept, Except, IR};
 use mz_ore::collections::CollectionExt;
@@ -169,6 +172,8 @@ pub enum HirRelationExpr {
         limit: Option<usize>,
         /// Number of records to skip
         offset: usize,
+        /// User hint: expected number of values per group key. Used to optimize physical rendering.
+        expected_group_size: Option<usize>,
     },
     Negate {
         input: Box<HirRelationExpr>,
@@ -372,7 +377,7 @@ impl WindowExprType {
 
     fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> ColumnType {
@@ -460,7 +465,7 @@ impl ScalarWindowExpr {
 
     fn typ(
         &self,
-        _outers: &[RelationType],
+        _outers: &[Arc<RelationType>],
         _inner: &RelationType,
         _params: &BTreeMap<usize, ScalarType>,
     ) -> ColumnType {
@@ -522,7 +527,7 @@ impl ValueWindowExpr {
 
     fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> ColumnType {
@@ -679,9 +684,13 @@ pub trait AbstractExpr {
     type Type: AbstractColumnType;
 
     /// Computes the type of the expression.
+    ///
+    /// The types of the outer relations are shared behind `Arc`s, so that
+    /// nested subqueries need not copy the (potentially very wide) types of
+    /// all of their enclosing relations.
     fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> Self::Type;
@@ -692,7 +701,7 @@ impl AbstractExpr for CoercibleScalarExpr {
 
     fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> Self::Type {
@@ -812,6 +821,7 @@ pub struct AggregateExpr {
     pub func: AggregateFunc,
     pub expr: Box<HirScalarExpr>,
     pub distinct: bool,
+    pub filter: Option<Box<HirScalarExpr>>,
 }
 
 /// Aggregate functions analogous to `mz_expr::AggregateFunc`, but whose
@@ -838,6 +848,7 @@ pub enum AggregateFunc {
     MaxDate,
     MaxTimestamp,
     MaxTimestampTz,
+    MaxRecord,
     MinNumeric,
     MinInt16,
     MinInt32,
@@ -853,6 +864,7 @@ pub enum AggregateFunc {
     MinDate,
     MinTimestamp,
     MinTimestampTz,
+    MinRecord,
     SumInt16,
     SumInt32,
     SumInt64,
@@ -863,8 +875,16 @@ pub enum AggregateFunc {
     SumFloat64,
     SumNumeric,
     Count,
+    CountDiffs,
     Any,
     All,
+    BoolAnd,
+    BoolOr,
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// Merges HyperLogLog sketches into a single sketch.
+    HllUnion,
     /// Accumulates `Datum::List`s whose first element is a JSON-typed `Datum`s
     /// into a JSON list. The other elements are columns used by `order_by`.
     ///
@@ -892,6 +912,26 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Returns the first field of the first `Datum::Record` according to
+    /// `order_by`. The other fields are columns used by `order_by`.
+    First {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `First`, but returns the first field of the last record.
+    Last {
+        order_by: Vec<ColumnOrder>,
+    },
+    PercentileCont {
+        fraction: OrderedFloat<f64>,
+    },
+    ApproxPercentile {
+        fraction: OrderedFloat<f64>,
+    },
+    /// An aggregate function registered by an embedder. See
+    /// [`mz_expr::custom_aggregate`].
+    Custom {
+        aggregate: CustomAggregateHandle,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -918,6 +958,7 @@ impl AggregateFunc {
             AggregateFunc::MaxDate => mz_expr::AggregateFunc::MaxDate,
             AggregateFunc::MaxTimestamp => mz_expr::AggregateFunc::MaxTimestamp,
             AggregateFunc::MaxTimestampTz => mz_expr::AggregateFunc::MaxTimestampTz,
+            AggregateFunc::MaxRecord => mz_expr::AggregateFunc::MaxRecord,
             AggregateFunc::MinNumeric => mz_expr::AggregateFunc::MinNumeric,
             AggregateFunc::MinInt16 => mz_expr::AggregateFunc::MinInt16,
             AggregateFunc::MinInt32 => mz_expr::AggregateFunc::MinInt32,
@@ -933,6 +974,7 @@ impl AggregateFunc {
             AggregateFunc::MinDate => mz_expr::AggregateFunc::MinDate,
             AggregateFunc::MinTimestamp => mz_expr::AggregateFunc::MinTimestamp,
             AggregateFunc::MinTimestampTz => mz_expr::AggregateFunc::MinTimestampTz,
+            AggregateFunc::MinRecord => mz_expr::AggregateFunc::MinRecord,
             AggregateFunc::SumInt16 => mz_expr::AggregateFunc::SumInt16,
             AggregateFunc::SumInt32 => mz_expr::AggregateFunc::SumInt32,
             AggregateFunc::SumInt64 => mz_expr::AggregateFunc::SumInt64,
@@ -943,8 +985,15 @@ impl AggregateFunc {
             AggregateFunc::SumFloat64 => mz_expr::AggregateFunc::SumFloat64,
             AggregateFunc::SumNumeric => mz_expr::AggregateFunc::SumNumeric,
             AggregateFunc::Count => mz_expr::AggregateFunc::Count,
+            AggregateFunc::CountDiffs => mz_expr::AggregateFunc::CountDiffs,
             AggregateFunc::Any => mz_expr::AggregateFunc::Any,
             AggregateFunc::All => mz_expr::AggregateFunc::All,
+            AggregateFunc::BoolAnd => mz_expr::AggregateFunc::BoolAnd,
+            AggregateFunc::BoolOr => mz_expr::AggregateFunc::BoolOr,
+            AggregateFunc::BitAnd => mz_expr::AggregateFunc::BitAnd,
+            AggregateFunc::BitOr => mz_expr::AggregateFunc::BitOr,
+            AggregateFunc::BitXor => mz_expr::AggregateFunc::BitXor,
+            AggregateFunc::HllUnion => mz_expr::AggregateFunc::HllUnion,
             AggregateFunc::JsonbAgg { order_by } => mz_expr::AggregateFunc::JsonbAgg { order_by },
             AggregateFunc::JsonbObjectAgg { order_by } => {
                 mz_expr::AggregateFunc::JsonbObjectAgg { order_by }
@@ -956,6 +1005,15 @@ impl AggregateFunc {
                 mz_expr::AggregateFunc::ListConcat { order_by }
             }
             AggregateFunc::StringAgg { order_by } => mz_expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::First { order_by } => mz_expr::AggregateFunc::First { order_by },
+            AggregateFunc::Last { order_by } => mz_expr::AggregateFunc::Last { order_by },
+            AggregateFunc::PercentileCont { fraction } => {
+                mz_expr::AggregateFunc::PercentileCont { fraction }
+            }
+            AggregateFunc::ApproxPercentile { fraction } => {
+                mz_expr::AggregateFunc::ApproxPercentile { fraction }
+            }
+            AggregateFunc::Custom { aggregate } => mz_expr::AggregateFunc::Custom { aggregate },
             AggregateFunc::Dummy => mz_expr::AggregateFunc::Dummy,
         }
     }
@@ -979,13 +1037,28 @@ impl AggregateFunc {
     /// is (without further information) true for aggregations that are not
     /// counts.
     pub fn output_type(&self, input_type: ColumnType) -> ColumnType {
+        if let AggregateFunc::Custom { aggregate } = self {
+            return aggregate.aggregate().output_type(input_type);
+        }
         let scalar_type = match self {
-            AggregateFunc::Count => ScalarType::Int64,
+            AggregateFunc::Count | AggregateFunc::CountDiffs => ScalarType::Int64,
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
+            AggregateFunc::BoolAnd | AggregateFunc::BoolOr => ScalarType::Bool,
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
-            AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
+                // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
+                ScalarType::Record { fields, .. } => match &fields[0].1.scalar_type {
+                    // The inner Record is a (value, separator) tuple
+                    ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::ApproxPercentile { .. } => {
+                ScalarType::Float64
+            }
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
@@ -994,7 +1067,10 @@ impl AggregateFunc {
             AggregateFunc::SumUInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
             },
-            AggregateFunc::ArrayConcat { .. } | AggregateFunc::ListConcat { .. } => {
+            AggregateFunc::ArrayConcat { .. }
+            | AggregateFunc::ListConcat { .. }
+            | AggregateFunc::First { .. }
+            | AggregateFunc::Last { .. } => {
                 match input_type.scalar_type {
                     // The input is wrapped in a Record if there's an ORDER BY, so extract it out.
                     ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
@@ -1004,7 +1080,7 @@ impl AggregateFunc {
             _ => input_type.scalar_type,
         };
         // max/min/sum return null on empty sets
-        let nullable = !matches!(self, AggregateFunc::Count);
+        let nullable = !matches!(self, AggregateFunc::Count | AggregateFunc::CountDiffs);
         scalar_type.nullable(nullable)
     }
 
@@ -1017,6 +1093,8 @@ impl AggregateFunc {
                 | ArrayConcat { .. }
                 | ListConcat { .. }
                 | StringAgg { .. }
+                | First { .. }
+                | Last { .. }
         )
     }
 }
@@ -1024,7 +1102,7 @@ impl AggregateFunc {
 impl HirRelationExpr {
     pub fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         params: &BTreeMap<usize, ScalarType>,
     ) -> RelationType {
         stack::maybe_grow(|| match self {
@@ -1063,7 +1141,7 @@ impl HirRelationExpr {
                     t.nullable(nullable)
                 });
                 let mut outers = outers.to_vec();
-                outers.insert(0, RelationType::new(lt.clone().collect()));
+                outers.insert(0, Arc::new(RelationType::new(lt.clone().collect())));
                 let rt = right
                     .typ(&outers, params)
                     .column_types
@@ -1219,6 +1297,7 @@ impl HirRelationExpr {
         order_key: Vec<ColumnOrder>,
         limit: Option<usize>,
         offset: usize,
+        expected_group_size: Option<usize>,
     ) -> Self {
         HirRelationExpr::TopK {
             input: Box::new(self),
@@ -1226,6 +1305,7 @@ impl HirRelationExpr {
             order_key,
             limit,
             offset,
+            expected_group_size,
         }
     }
 
@@ -1247,10 +1327,21 @@ impl HirRelationExpr {
         }
     }
 
+    /// Constructs the union of `self` and `other`.
+    ///
+    /// If `self` is itself a union, `other` is appended to its inputs, so that
+    /// chains of `UNION ALL` produce a single n-ary union rather than a deep
+    /// tree of binary unions.
     pub fn union(self, other: Self) -> Self {
-        HirRelationExpr::Union {
-            base: Box::new(self),
-            inputs: vec![other],
+        match self {
+            HirRelationExpr::Union { base, mut inputs } => {
+                inputs.push(other);
+                HirRelationExpr::Union { base, inputs }
+            }
+            _ => HirRelationExpr::Union {
+                base: Box::new(self),
+                inputs: vec![other],
+            },
         }
     }
 
@@ -1504,6 +1595,9 @@ impl HirRelationExpr {
                 HirRelationExpr::Reduce { aggregates, .. } => {
                     for aggregate in aggregates {
                         f(&aggregate.expr, depth)?;
+                        if let Some(filter) = &aggregate.filter {
+                            f(filter, depth)?;
+                        }
                     }
                 }
                 HirRelationExpr::Union { .. }
@@ -1553,6 +1647,9 @@ impl HirRelationExpr {
                 HirRelationExpr::Reduce { aggregates, .. } => {
                     for aggregate in aggregates.iter_mut() {
                         f(&mut aggregate.expr, depth)?;
+                        if let Some(filter) = &mut aggregate.filter {
+                            f(filter, depth)?;
+                        }
                     }
                 }
                 HirRelationExpr::Union { .. }
@@ -1648,6 +1745,7 @@ impl HirRelationExpr {
                     order_key: finishing.order_by,
                     limit: finishing.limit,
                     offset: finishing.offset,
+                    expected_group_size: None,
                 }),
                 outputs: finishing.project,
             }
@@ -1724,6 +1822,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1806,6 +1905,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1888,6 +1988,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -1971,6 +2072,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input }
             | Threshold { input } => {
@@ -2042,6 +2144,9 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
             } => {
                 for aggregate in aggregates {
                     f(aggregate.expr.as_ref());
+                    if let Some(filter) = &aggregate.filter {
+                        f(filter.as_ref());
+                    }
                 }
             }
             Distinct { input: _ }
@@ -2051,6 +2156,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2112,6 +2218,9 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
             } => {
                 for aggregate in aggregates {
                     f(aggregate.expr.as_mut());
+                    if let Some(filter) = &mut aggregate.filter {
+                        f(filter.as_mut());
+                    }
                 }
             }
             Distinct { input: _ }
@@ -2121,6 +2230,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2183,6 +2293,9 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
             } => {
                 for aggregate in aggregates {
                     f(aggregate.expr.as_ref())?;
+                    if let Some(filter) = &aggregate.filter {
+                        f(filter.as_ref())?;
+                    }
                 }
             }
             Distinct { input: _ }
@@ -2192,6 +2305,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2255,6 +2369,9 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
             } => {
                 for aggregate in aggregates {
                     f(aggregate.expr.as_mut())?;
+                    if let Some(filter) = &mut aggregate.filter {
+                        f(filter.as_mut())?;
+                    }
                 }
             }
             Distinct { input: _ }
@@ -2264,6 +2381,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 order_key: _,
                 limit: _,
                 offset: _,
+                expected_group_size: _,
             }
             | Negate { input: _ }
             | Threshold { input: _ }
@@ -2718,6 +2836,25 @@ impl HirScalarExpr {
         })
     }
 
+    /// Attempts to simplify this expression to a literal 64-bit float.
+    ///
+    /// Returns `None` if this expression cannot be simplified, e.g. because it
+    /// contains non-literal values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this expression does not have type [`ScalarType::Float64`].
+    pub fn into_literal_float64(self) -> Option<f64> {
+        self.simplify_to_literal().and_then(|row| {
+            let datum = row.unpack_first();
+            if datum.is_null() {
+                None
+            } else {
+                Some(datum.unwrap_float64())
+            }
+        })
+    }
+
     /// Attempts to simplify this expression to a literal string.
     ///
     /// Returns `None` if this expression cannot be simplified, e.g. because it
@@ -2857,7 +2994,7 @@ impl AbstractExpr for HirScalarExpr {
 
     fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> Self::Type {
@@ -2890,7 +3027,7 @@ impl AbstractExpr for HirScalarExpr {
             HirScalarExpr::Exists(_) => ScalarType::Bool.nullable(true),
             HirScalarExpr::Select(expr) => {
                 let mut outers = outers.to_vec();
-                outers.insert(0, inner.clone());
+                outers.insert(0, Arc::new(inner.clone()));
                 expr.typ(&outers, params)
                     .column_types
                     .into_element()
@@ -2905,12 +3042,16 @@ impl AggregateExpr {
     /// Replaces any parameter references in the expression with the
     /// corresponding datum from `parameters`.
     pub fn bind_parameters(&mut self, params: &Params) -> Result<(), PlanError> {
-        self.expr.bind_parameters(params)
+        self.expr.bind_parameters(params)?;
+        if let Some(filter) = &mut self.filter {
+            filter.bind_parameters(params)?;
+        }
+        Ok(())
     }
 
     pub fn typ(
         &self,
-        outers: &[RelationType],
+        outers: &[Arc<RelationType>],
         inner: &RelationType,
         params: &BTreeMap<usize, ScalarType>,
     ) -> ColumnType {
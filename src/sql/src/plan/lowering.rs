@@ -31,6 +31,14 @@
 //! performed, they should include not only the indicated keys but also all
 //! of the outer columns.
 //!
+//! The same machinery lowers `LATERAL` joins, whose right input may refer to
+//! the columns of the left input: such a join is a correlated join, whose right
+//! input is applied to the left input by `branch` exactly like a subquery,
+//! with the join predicate applied as a filter afterwards. Correlated scalar
+//! (`SELECT`) and existential (`EXISTS`) subqueries are lowered by
+//! `apply_scalar_subquery` and `apply_existential_subquery` respectively, so
+//! no correlated construct survives in the resulting `MirRelationExpr`.
+//!
 //! The decorrelation transformation is initialized with an empty outer
 //! relation, but it seems entirely appropriate to decorrelate queries that
 //! contain "holes" from prepared statements, as if the query was a subquery
@@ -666,25 +674,20 @@ impl HirRelationExpr {
                         .into_iter()
                         .map(|aggregate| aggregate.applied_to(id_gen, col_map, cte_map, &mut input))
                         .collect::<Vec<_>>();
-                    let input_type = input.typ();
-                    let default = applied_aggregates
-                        .iter()
-                        .map(|agg| {
-                            (
-                                agg.func.default(),
-                                agg.typ(&input_type.column_types).scalar_type,
-                            )
-                        })
-                        .collect();
                     // NOTE we don't need to remove any extra columns from aggregate.applied_to above because the reduce will do that anyway
-                    let mut reduced =
-                        input.reduce(applied_group_key, applied_aggregates, expected_group_size);
-
-                    // Introduce default values in the case the group key is empty.
                     if group_key.is_empty() {
-                        reduced = get_outer.lookup(id_gen, reduced, default);
+                        // Introduce default values in the case the group key is empty,
+                        // using the outer relation as the universe of groups.
+                        input.reduce_or_default(
+                            id_gen,
+                            get_outer,
+                            applied_group_key,
+                            applied_aggregates,
+                            expected_group_size,
+                        )
+                    } else {
+                        input.reduce(applied_group_key, applied_aggregates, expected_group_size)
                     }
-                    reduced
                 }
                 Distinct { input } => {
                     // Distinct is uncomplicated.
@@ -698,6 +701,7 @@ impl HirRelationExpr {
                     order_key,
                     limit,
                     offset,
+                    expected_group_size,
                 } => {
                     // TopK is uncomplicated, except that we must group by the columns of `get_outer` as well.
                     let input = input.applied_to(id_gen, get_outer.clone(), col_map, cte_map);
@@ -712,7 +716,13 @@ impl HirRelationExpr {
                             nulls_last: column_order.nulls_last,
                         })
                         .collect();
-                    input.top_k(applied_group_key, applied_order_key, limit, offset)
+                    input.top_k(
+                        applied_group_key,
+                        applied_order_key,
+                        limit,
+                        offset,
+                        expected_group_size,
+                    )
                 }
                 Negate { input } => {
                     // Negate is uncomplicated.
@@ -1038,6 +1048,7 @@ impl HirScalarExpr {
                                                 func,
                                                 expr: agg_input,
                                                 distinct: false,
+                                                filter: None,
                                             };
                                             let mut reduce = to_reduce
                                                 .reduce(
@@ -1253,6 +1264,7 @@ impl HirScalarExpr {
                                                 func,
                                                 expr: agg_input,
                                                 distinct: false,
+                                                filter: None,
                                             };
 
                                             // Actually call reduce with the window function
@@ -1698,6 +1710,7 @@ fn apply_scalar_subquery(
                         func: mz_expr::AggregateFunc::Count,
                         expr: mz_expr::MirScalarExpr::literal_ok(Datum::True, ScalarType::Bool),
                         distinct: false,
+                        filter: None,
                     }],
                     None,
                 );
@@ -1764,12 +1777,14 @@ impl AggregateExpr {
             func,
             expr,
             distinct,
+            filter,
         } = self;
 
         mz_expr::AggregateExpr {
             func: func.into_expr(),
             expr: expr.applied_to(id_gen, col_map, cte_map, inner, &None),
             distinct,
+            filter: filter.map(|filter| filter.applied_to(id_gen, col_map, cte_map, inner, &None)),
         }
     }
 }
@@ -38,6 +38,7 @@ use std::convert::{TryFrom, TryInto};
 
 use std::iter;
 use std::mem;
+use std::sync::Arc;
 
 use itertools::Itertools;
 use uuid::Uuid;
@@ -988,6 +989,39 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the `WHERE` clause of a `CREATE SOURCE` statement, which filters the
+/// rows of a source described by `on_desc`.
+pub fn plan_source_filter(
+    scx: &StatementContext,
+    on_desc: &RelationDesc,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, on_desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "WHERE clause in CREATE SOURCE",
+        scope: &scope,
+        relation_type: on_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+    let mut expr = expr.lower_uncorrelated()?;
+    // The filter is evaluated once, as data is ingested, so its result must
+    // not depend on when it is evaluated.
+    if expr.contains_temporal() || expr.contains_unmaterializable() {
+        sql_bail!(
+            "WHERE clause in CREATE SOURCE must not call mz_now() or unmaterializable functions"
+        );
+    }
+    expr.reduce(&on_desc.typ().column_types);
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1236,6 +1270,7 @@ pub fn plan_nested_query(
             order_key: finishing.order_by,
             limit: finishing.limit,
             offset: finishing.offset,
+            expected_group_size: None,
         };
     }
     Ok((expr.project(finishing.project), scope))
@@ -2031,6 +2066,7 @@ fn plan_view_select(
                     group_key: distinct_key,
                     limit: Some(1),
                     offset: 0,
+                    expected_group_size: expected_group_size.map(usize::cast_from),
                 }
             }
         }
@@ -3774,6 +3810,7 @@ where
             order_key: finishing.order_by.clone(),
             limit: finishing.limit,
             offset: finishing.offset,
+            expected_group_size: None,
         };
     }
 
@@ -3840,6 +3877,7 @@ where
                     exprs: aggregation_exprs,
                 }),
                 distinct: false,
+                filter: None,
             }],
             None,
         )
@@ -4093,6 +4131,7 @@ fn plan_aggregate(
         filter,
         over,
         distinct,
+        within_group,
     }: &Function<Aug>,
 ) -> Result<AggregateExpr, PlanError> {
     // Normal aggregate functions, like `sum`, expect as input a single expression
@@ -4119,6 +4158,22 @@ fn plan_aggregate(
 
     let name = normalize::unresolved_object_name(name.clone())?;
 
+    // Ordered-set aggregates, like `percentile_cont`, aggregate the values
+    // specified in their `WITHIN GROUP (ORDER BY ...)` clause, while their
+    // direct arguments are constant across each group.
+    let ordered_set = func::ORDERED_SET_AGGREGATES.contains(&name.item.as_str());
+    if ordered_set && within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            name
+        );
+    } else if !ordered_set && !within_group.is_empty() {
+        sql_bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            name
+        );
+    }
+
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
     // generalized function selection framework. The rule is simple: the user
     // must type `count(*)`, but the function selection framework sees an empty
@@ -4141,38 +4196,55 @@ fn plan_aggregate(
         }
     };
 
+    // The implementations of ordered-set aggregates expect the aggregated
+    // values before the direct arguments.
+    let args = if ordered_set {
+        let mut aggregated = vec![];
+        for order_by in within_group {
+            if order_by.asc == Some(false) {
+                bail_unsupported!("DESC in WITHIN GROUP");
+            }
+            aggregated.push(&order_by.expr);
+        }
+        let mut aggregated = plan_exprs(ecx, &aggregated)?;
+        aggregated.extend(args);
+        aggregated
+    } else {
+        args
+    };
+
     let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
 
     let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(&name), impls, args, col_orders)?;
-    if let Some(filter) = &filter {
-        // If a filter is present, as in
-        //
-        //     <agg>(<expr>) FILTER (WHERE <cond>)
-        //
-        // we plan it by essentially rewriting the expression to
-        //
-        //     <agg>(CASE WHEN <cond> THEN <expr> ELSE <identity>)
-        //
-        // where <identity> is the identity input for <agg>.
-        let cond = plan_expr(&ecx.with_name("FILTER"), filter)?.type_as(ecx, &ScalarType::Bool)?;
-        let expr_typ = ecx.scalar_type(&expr);
-        expr = HirScalarExpr::If {
-            cond: Box::new(cond),
-            then: Box::new(expr),
-            els: Box::new(HirScalarExpr::literal(func.identity_datum(), expr_typ)),
-        };
-    }
+
+    // If a filter is present, as in
+    //
+    //     <agg>(<expr>) FILTER (WHERE <cond>)
+    //
+    // we plan <cond> alongside <expr>, and only the rows for which <cond>
+    // holds contribute to <agg>. If no rows of a group satisfy <cond>, <agg>
+    // produces the same result as for an empty input.
+    let filter = match filter {
+        Some(filter) => {
+            let cond =
+                plan_expr(&ecx.with_name("FILTER"), filter)?.type_as(ecx, &ScalarType::Bool)?;
+            Some(Box::new(cond))
+        }
+        None => None,
+    };
 
     let mut seen_outer = false;
     let mut seen_inner = false;
-    #[allow(deprecated)]
-    expr.visit_columns(0, &mut |depth, col| {
-        if depth == 0 && col.level == 0 {
-            seen_inner = true;
-        } else if col.level > depth {
-            seen_outer = true;
-        }
-    });
+    for e in iter::once(&expr).chain(filter.as_deref()) {
+        #[allow(deprecated)]
+        e.visit_columns(0, &mut |depth, col| {
+            if depth == 0 && col.level == 0 {
+                seen_inner = true;
+            } else if col.level > depth {
+                seen_outer = true;
+            }
+        });
+    }
     if seen_outer && !seen_inner {
         bail_unsupported!(
             3720,
@@ -4198,6 +4270,7 @@ fn plan_aggregate(
         func,
         expr: Box::new(expr),
         distinct: *distinct,
+        filter,
     })
 }
 
@@ -4306,6 +4379,7 @@ fn plan_function<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<HirScalarExpr, PlanError> {
     let unresolved_name = normalize::unresolved_object_name(name.clone())?;
@@ -4390,6 +4464,12 @@ fn plan_function<'a>(
             name
         );
     }
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
+    }
 
     let scalar_args = match &args {
         FunctionArgs::Star => {
@@ -4603,6 +4683,7 @@ fn validate_window_function_plan<'a>(
         filter,
         over,
         distinct,
+        within_group,
     }: &'a Function<Aug>,
 ) -> Result<
     (
@@ -4630,6 +4711,13 @@ fn validate_window_function_plan<'a>(
         bail_unsupported!("FILTER in non-aggregate window functions");
     }
 
+    if !within_group.is_empty() {
+        sql_bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            name
+        );
+    }
+
     let window_spec = match over.as_ref() {
         Some(over) => over,
         None => sql_bail!("window function {} requires an OVER clause", name),
@@ -4948,6 +5036,8 @@ fn scalar_type_from_catalog(
                 CatalogType::Timestamp => Ok(ScalarType::Timestamp),
                 CatalogType::TimestampTz => Ok(ScalarType::TimestampTz),
                 CatalogType::Uuid => Ok(ScalarType::Uuid),
+                CatalogType::Inet => Ok(ScalarType::Inet),
+                CatalogType::Cidr => Ok(ScalarType::Cidr),
                 CatalogType::Int2Vector => Ok(ScalarType::Int2Vector),
                 CatalogType::Numeric => unreachable!("handled above"),
                 CatalogType::Char => unreachable!("handled above"),
@@ -5023,6 +5113,7 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     filter,
                     over: _,
                     distinct: _,
+                    within_group,
                 } = func;
                 if let Some(filter) = filter {
                     self.visit_expr_mut(filter);
@@ -5033,6 +5124,9 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                     .push("aggregate function calls");
 
                 self.visit_function_args_mut(args);
+                for order_by in within_group {
+                    self.visit_order_by_expr_mut(order_by);
+                }
 
                 self.within_aggregate = old_within_aggregate;
                 self.table_disallowed_context.pop();
@@ -5087,15 +5181,18 @@ impl<'a> VisitMut<'_, Aug> for AggregateTableFuncVisitor<'a> {
                 filter: None,
                 over: None,
                 distinct: false,
+                within_group,
             } = func
             {
-                let func = TableFunction { name, args };
-                // Identical table functions can be de-duplicated.
-                let id = self
-                    .tables
-                    .entry(func)
-                    .or_insert_with(|| format!("table_func_{}", Uuid::new_v4()));
-                *expr = Expr::Identifier(vec![Ident::from(id.clone())]);
+                if within_group.is_empty() {
+                    let func = TableFunction { name, args };
+                    // Identical table functions can be de-duplicated.
+                    let id = self
+                        .tables
+                        .entry(func)
+                        .or_insert_with(|| format!("table_func_{}", Uuid::new_v4()));
+                    *expr = Expr::Identifier(vec![Ident::from(id.clone())]);
+                }
             }
         }
         if let Some(context) = disallowed_context {
@@ -5145,7 +5242,9 @@ pub struct QueryContext<'a> {
     /// The scopes of the outer relation expression.
     pub outer_scopes: Vec<Scope>,
     /// The type of the outer relation expressions.
-    pub outer_relation_types: Vec<RelationType>,
+    ///
+    /// The types are shared with the contexts of the enclosing queries.
+    pub outer_relation_types: Vec<Arc<RelationType>>,
     /// CTEs for this query, mapping their assigned LocalIds to their definition.
     pub ctes: HashMap<LocalId, CteDesc>,
     pub recursion_guard: RecursionGuard,
@@ -5178,7 +5277,7 @@ impl<'a> QueryContext<'a> {
     fn derived_context(&self, scope: Scope, relation_type: RelationType) -> QueryContext<'a> {
         let ctes = self.ctes.clone();
         let outer_scopes = iter::once(scope).chain(self.outer_scopes.clone()).collect();
-        let outer_relation_types = iter::once(relation_type)
+        let outer_relation_types = iter::once(Arc::new(relation_type))
             .chain(self.outer_relation_types.clone())
             .collect();
 
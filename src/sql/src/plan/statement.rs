@@ -42,7 +42,7 @@ mod scl;
 pub(crate) mod show;
 mod tcl;
 
-pub(crate) use ddl::PgConfigOptionExtracted;
+pub(crate) use ddl::{PgConfigOptionExtracted, PubSubConfigOptionExtracted};
 
 /// Describes the output of a SQL statement.
 #[derive(Debug, Clone, Eq, PartialEq)]
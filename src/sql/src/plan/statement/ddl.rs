@@ -12,10 +12,12 @@
 //! This module houses the handlers for statements that modify the catalog, like
 //! `ALTER`, `CREATE`, and `DROP`.
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Write;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
+use std::time::Duration;
 
 use aws_arn::ResourceName as AmazonResourceName;
 use globset::GlobBuilder;
@@ -25,7 +27,7 @@ use regex::Regex;
 use tracing::warn;
 
 use mz_compute_client::controller::DEFAULT_COMPUTE_REPLICA_LOGGING_INTERVAL_MICROS;
-use mz_expr::CollectionPlan;
+use mz_expr::{CollectionPlan, MapFilterProject};
 use mz_interchange::avro::AvroSchemaGenerator;
 use mz_ore::cast::{self, TryCastFrom};
 use mz_ore::collections::CollectionExt;
@@ -45,24 +47,34 @@ use mz_sql_parser::ast::{
 use mz_storage_client::types::connections::aws::{
     AwsAssumeRole, AwsConfig, AwsCredentials, SerdeUri,
 };
+use mz_storage_client::types::connections::azure::{AzureBlobConnection, AzureBlobCredentials};
 use mz_storage_client::types::connections::{
     AwsPrivatelink, AwsPrivatelinkConnection, Connection, CsrConnectionHttpAuth, KafkaConnection,
-    KafkaSecurity, KafkaTlsConfig, SaslConfig, SshTunnel, StringOrSecret, TlsIdentity, Tunnel,
+    KafkaSecurity, KafkaTlsConfig, MySqlSslMode, SaslConfig, SqlServerSslMode, SshTunnel,
+    StringOrSecret, TlsIdentity, Tunnel,
 };
 use mz_storage_client::types::sinks::{
+    elasticsearch_field_mapping, postgres_sink_column_type, ElasticsearchSinkConnection,
     KafkaConsistencyConfig, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention,
-    KafkaSinkFormat, SinkEnvelope, StorageSinkConnectionBuilder,
+    KafkaSinkCsvFormat, KafkaSinkFormat, KafkaSinkPartitionStrategy, NatsSinkConnection,
+    ObjectSinkConnection, ObjectSinkFormat, ObjectStoreConnection, PostgresSinkConnection,
+    RedisSinkConnection, RedisSinkValueFormat, SinkEnvelope, SinkErrorPolicy,
+    StorageSinkConnectionBuilder,
 };
 use mz_storage_client::types::sources::encoding::{
     included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, DataEncodingInner,
-    ProtobufEncoding, RegexEncoding, SourceDataEncoding, SourceDataEncodingInner,
+    ProtobufEncoding, RegexEncoding, RegexMismatchPolicy, SourceDataEncoding,
+    SourceDataEncodingInner,
 };
 use mz_storage_client::types::sources::{
-    GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection, KeyEnvelope,
-    KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
-    PostgresSourceConnection, PostgresSourcePublicationDetails,
-    ProtoPostgresSourcePublicationDetails, S3SourceConnection, SourceDesc, SourceEnvelope,
-    TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope, UpsertStyle,
+    FileSourceConnection, GenericSourceConnection, IncludedColumnPos, KafkaSourceConnection,
+    KeyEnvelope, KinesisSourceConnection, LoadGenerator, LoadGeneratorSourceConnection,
+    MqttSourceConnection, MySqlSourceConnection, MySqlSourceDetails, PostgresSourceConnection,
+    PostgresSourcePublicationDetails, ProtoMySqlSourceDetails,
+    ProtoPostgresSourcePublicationDetails, ProtoSqlServerSourceDetails, PubSubSourceConnection,
+    S3SourceConnection, SourceDesc, SourceEnvelope, SourceRateLimits, SqlServerSourceConnection,
+    SqlServerSourceDetails, TestScriptSourceConnection, Timeline, UnplannedSourceEnvelope,
+    UpsertStyle,
 };
 
 use crate::ast::display::AstDisplay;
@@ -70,25 +82,36 @@ use crate::ast::{
     AlterConnectionStatement, AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement,
     AlterSecretStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, AwsConnectionOption,
     AwsConnectionOptionName, AwsPrivatelinkConnectionOption, AwsPrivatelinkConnectionOptionName,
-    ClusterOption, ClusterOptionName, ColumnOption, Compression, CreateClusterReplicaStatement,
-    CreateClusterStatement, CreateConnection, CreateConnectionStatement, CreateDatabaseStatement,
-    CreateIndexStatement, CreateMaterializedViewStatement, CreateReferencedSubsources,
-    CreateRoleOption, CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement,
-    CreateSinkConnection, CreateSinkOption, CreateSinkOptionName, CreateSinkStatement,
-    CreateSourceConnection, CreateSourceFormat, CreateSourceOption, CreateSourceOptionName,
-    CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
-    CreateTypeStatement, CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrConnectionAvro, CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf,
-    CsrSeedProtobuf, CsvColumns, DbzMode, DropClusterReplicasStatement, DropClustersStatement,
-    DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement, Envelope,
-    Expr, Format, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaBroker,
+    AzureBlobConnectionOption, AzureBlobConnectionOptionName, ClusterOption, ClusterOptionName,
+    ColumnOption, Compression, CreateClusterReplicaStatement, CreateClusterStatement,
+    CreateConnection, CreateConnectionStatement, CreateDatabaseStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateReferencedSubsources, CreateRoleOption,
+    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
+    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
+    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
+    CreateSubsourceStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
+    CreateViewStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection, CsrConnectionAvro,
+    CsrConnectionOption, CsrConnectionOptionName, CsrConnectionProtobuf, CsrSeedProtobuf,
+    CsvColumns, DbzMode, DropClusterReplicasStatement, DropClustersStatement,
+    DropDatabaseStatement, DropObjectsStatement, DropRolesStatement, DropSchemaStatement,
+    ElasticsearchConnectionOption, ElasticsearchConnectionOptionName, ElasticsearchSinkOption,
+    ElasticsearchSinkOptionName, Envelope, Expr, Format, GcpConnectionOption,
+    GcpConnectionOptionName, Ident, IfExistsBehavior, IndexOption, IndexOptionName, KafkaBroker,
     KafkaBrokerAwsPrivatelinkOption, KafkaBrokerAwsPrivatelinkOptionName, KafkaBrokerTunnel,
     KafkaConfigOptionName, KafkaConnectionOption, KafkaConnectionOptionName, KeyConstraint,
-    LoadGeneratorOption, LoadGeneratorOptionName, ObjectType, PgConfigOption, PgConfigOptionName,
-    PostgresConnectionOption, PostgresConnectionOptionName, ProtobufSchema, QualifiedReplica,
-    ReplicaDefinition, ReplicaOption, ReplicaOptionName, SourceIncludeMetadata,
-    SourceIncludeMetadataType, SshConnectionOptionName, Statement, TableConstraint,
-    UnresolvedDatabaseName, Value, ViewDefinition,
+    LoadGeneratorOption, LoadGeneratorOptionName, MqttConfigOption, MqttConfigOptionName,
+    MqttConnectionOption, MqttConnectionOptionName, MySqlConfigOption, MySqlConfigOptionName,
+    MySqlConnectionOption, MySqlConnectionOptionName, NatsConnectionOption,
+    NatsConnectionOptionName, NatsSinkOption, NatsSinkOptionName, ObjectSinkOption,
+    ObjectSinkOptionName, ObjectStoreKind, ObjectType, PgConfigOption, PgConfigOptionName,
+    PostgresConnectionOption, PostgresConnectionOptionName, PostgresSinkOption,
+    PostgresSinkOptionName, ProtobufSchema, PubSubConfigOption, PubSubConfigOptionName,
+    QualifiedReplica, RedisConnectionOption, RedisConnectionOptionName, RedisSinkOption,
+    RedisSinkOptionName, RegexColumnType, RegexMismatch, ReplicaDefinition, ReplicaOption,
+    ReplicaOptionName, SourceIncludeMetadata, SourceIncludeMetadataType, SqlServerConfigOption,
+    SqlServerConfigOptionName, SqlServerConnectionOption, SqlServerConnectionOptionName,
+    SshConnectionOptionName, Statement, TableConstraint, UnresolvedDatabaseName, Value,
+    ViewDefinition, WebhookOption, WebhookOptionName,
 };
 use crate::catalog::{
     CatalogComputeInstance, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails,
@@ -116,7 +139,8 @@ use crate::plan::{
     CreateTablePlan, CreateTypePlan, CreateViewPlan, DropComputeInstancesPlan,
     DropComputeReplicasPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
     FullObjectName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan, QueryContext,
-    RotateKeysPlan, Secret, Sink, Source, StorageHostConfig, Table, Type, View,
+    RotateKeysPlan, Secret, Sink, Source, StorageHostConfig, Table, Type, View, Webhook,
+    WebhookValidation,
 };
 
 pub fn describe_create_database(
@@ -323,6 +347,8 @@ pub fn describe_create_subsource(
 generate_extracted_config!(
     CreateSourceOption,
     (IgnoreKeys, bool),
+    (MaxBytesInFlight, u64),
+    (MaxMessagesPerSecond, u64),
     (Remote, String),
     (Size, String),
     (Timeline, String),
@@ -336,15 +362,35 @@ generate_extracted_config!(
     (TextColumns, Vec::<UnresolvedObjectName>, Default(vec![]))
 );
 
+generate_extracted_config!(MySqlConfigOption, (Details, String));
+
+generate_extracted_config!(SqlServerConfigOption, (Details, String));
+
+generate_extracted_config!(
+    MqttConfigOption,
+    (Qos, u16, Default(1_u16)),
+    (TopicFilters, Vec::<String>, Default(vec![]))
+);
+
+generate_extracted_config!(PubSubConfigOption, (Subscription, String));
+
 pub fn plan_create_source(
     scx: &StatementContext,
     stmt: CreateSourceStatement<Aug>,
 ) -> Result<Plan, PlanError> {
+    match &stmt.connection {
+        CreateSourceConnection::Webhook { .. } => return plan_create_webhook_source(scx, stmt),
+        CreateSourceConnection::Flight { .. } => return plan_create_flight_source(scx, stmt),
+        _ => {}
+    }
+
     let CreateSourceStatement {
         name,
         col_names,
         connection,
         envelope,
+        projection,
+        filter,
         if_not_exists,
         format,
         key_constraint,
@@ -355,7 +401,20 @@ pub fn plan_create_source(
 
     let envelope = envelope.clone().unwrap_or(Envelope::None);
 
-    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[CreateSourceOptionName::Size];
+    if !projection.is_empty() || filter.is_some() {
+        if !matches!(connection, CreateSourceConnection::Kafka(_)) {
+            bail_unsupported!("COLUMNS and WHERE with non-Kafka sources");
+        }
+        if !matches!(envelope, Envelope::None) {
+            sql_bail!("COLUMNS and WHERE require ENVELOPE NONE");
+        }
+    }
+
+    const SAFE_WITH_OPTIONS: &[CreateSourceOptionName] = &[
+        CreateSourceOptionName::MaxBytesInFlight,
+        CreateSourceOptionName::MaxMessagesPerSecond,
+        CreateSourceOptionName::Size,
+    ];
 
     if with_options
         .iter()
@@ -375,11 +434,29 @@ pub fn plan_create_source(
         // TODO(guswynn): should this be `bail_unsupported!`?
         sql_bail!("INCLUDE HEADERS with non-Kafka sources not supported");
     }
-    if !matches!(connection, CreateSourceConnection::Kafka { .. }) && !include_metadata.is_empty() {
+    // The key of an MQTT message is the topic it was published to, and the key
+    // of a Pub/Sub message is its ordering key.
+    if matches!(connection, CreateSourceConnection::Mqtt { .. }) {
+        if include_metadata
+            .iter()
+            .any(|sic| sic.ty != SourceIncludeMetadataType::Key)
+        {
+            bail_unsupported!("INCLUDE metadata other than KEY with MQTT sources");
+        }
+    } else if matches!(connection, CreateSourceConnection::PubSub { .. }) {
+        if include_metadata
+            .iter()
+            .any(|sic| sic.ty != SourceIncludeMetadataType::Key)
+        {
+            bail_unsupported!("INCLUDE metadata other than KEY with Pub/Sub sources");
+        }
+    } else if !matches!(connection, CreateSourceConnection::Kafka { .. })
+        && !include_metadata.is_empty()
+    {
         bail_unsupported!("INCLUDE metadata with non-Kafka sources");
     }
 
-    let (mut external_connection, encoding, available_subsources) = match connection {
+    let (mut external_connection, mut encoding, available_subsources) = match connection {
         CreateSourceConnection::Kafka(mz_sql_parser::ast::KafkaSourceConnection {
             connection:
                 mz_sql_parser::ast::KafkaConnection {
@@ -400,6 +477,7 @@ pub fn plan_create_source(
                 opt.name != KafkaConfigOptionName::StartOffset
                     && opt.name != KafkaConfigOptionName::StartTimestamp
                     && opt.name != KafkaConfigOptionName::Topic
+                    && opt.name != KafkaConfigOptionName::DeadLetterTopic
             }) {
                 scx.require_unsafe_mode(&format!("KAFKA CONNECTION option {}", opt.name))?;
             }
@@ -422,6 +500,10 @@ pub fn plan_create_source(
                 .topic
                 .expect("validated exists during purification");
             let group_id_prefix = extracted_options.group_id_prefix;
+            let dead_letter_topic = extracted_options.dead_letter_topic;
+            if dead_letter_topic.as_ref() == Some(&topic) {
+                sql_bail!("DEAD LETTER TOPIC must differ from TOPIC");
+            }
 
             let mut start_offsets = HashMap::new();
             match optional_start_offset {
@@ -451,6 +533,7 @@ pub fn plan_create_source(
                 topic,
                 start_offsets,
                 group_id_prefix,
+                dead_letter_topic,
                 environment_id: scx.catalog.config().environment_id.to_string(),
                 include_timestamp: None,
                 include_partition: None,
@@ -571,6 +654,20 @@ pub fn plan_create_source(
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 sql_bail!("S3 sources do not support key decoding");
             }
+            // Objects are decoded as Avro Object Container Files, which embed
+            // the schema they were written with.
+            if let SourceDataEncoding::Single(DataEncoding {
+                inner: DataEncodingInner::Avro(AvroEncoding { csr_connection, .. }),
+                ..
+            }) = &encoding
+            {
+                if csr_connection.is_some() {
+                    sql_bail!(
+                        "S3 sources do not support schema registry connections; \
+                         specify the reader schema with FORMAT AVRO USING SCHEMA"
+                    );
+                }
+            }
             let connection = GenericSourceConnection::S3(S3SourceConnection {
                 connection_id: connection_item.id(),
                 key_sources: converted_sources,
@@ -798,6 +895,337 @@ pub fn plan_create_source(
             ));
             (connection, encoding, Some(available_subsources))
         }
+        CreateSourceConnection::MySql {
+            connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::MySql(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a mysql connection", connection_item.name()),
+            };
+            let MySqlConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: MySQL source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoMySqlSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details = MySqlSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            // Register the available subsources
+            let mut available_subsources = BTreeMap::new();
+
+            // As with Postgres sources, the MySQL source reader produces text
+            // encoded rows, which these expressions cast to the columns' target
+            // types.
+            let mut table_casts = HashMap::new();
+
+            for (i, table) in details.tables.iter().enumerate() {
+                let mut cast_scx = scx.clone();
+                cast_scx.param_types = Default::default();
+                let cast_qcx = QueryContext::root(&cast_scx, QueryLifetime::Static);
+                let mut column_types = vec![];
+                for column in table.columns.iter() {
+                    column_types.push(ColumnType {
+                        nullable: column.nullable,
+                        scalar_type: ScalarType::String,
+                    });
+                }
+
+                let cast_ecx = ExprContext {
+                    qcx: &cast_qcx,
+                    name: "plan_mysql_source_cast",
+                    scope: &Scope::empty(),
+                    relation_type: &RelationType {
+                        column_types,
+                        keys: vec![],
+                    },
+                    allow_aggregates: false,
+                    allow_subqueries: false,
+                    allow_windows: false,
+                };
+
+                let mut column_casts = vec![];
+                for (i, column) in table.columns.iter().enumerate() {
+                    let ty = match crate::mysql_util::scalar_type(&column.column_type) {
+                        Some(ty) => ty,
+                        // If this reference survived purification, we do not
+                        // expect it to be from a table that the user will
+                        // consume, i.e. expect this table to be filtered out
+                        // of table casts.
+                        None => {
+                            column_casts.push(
+                                HirScalarExpr::CallVariadic {
+                                    func: mz_expr::VariadicFunc::ErrorIfNull,
+                                    exprs: vec![
+                                        HirScalarExpr::literal_null(ScalarType::String),
+                                        HirScalarExpr::literal(
+                                            mz_repr::Datum::from(
+                                                format!("Unsupported type {}", column.column_type)
+                                                    .as_str(),
+                                            ),
+                                            ScalarType::String,
+                                        ),
+                                    ],
+                                }
+                                .lower_uncorrelated()
+                                .expect("no correlation"),
+                            );
+                            continue;
+                        }
+                    };
+
+                    let data_type = scx.resolve_type(ty)?;
+                    let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
+
+                    let col_expr = HirScalarExpr::Column(ColumnRef {
+                        level: 0,
+                        column: i,
+                    });
+
+                    let cast_expr = plan_cast(
+                        &cast_ecx,
+                        CastContext::Explicit,
+                        col_expr,
+                        &scalar_type,
+                    )?
+                    .lower_uncorrelated()
+                    .expect(
+                        "lower_uncorrelated should not fail given that there is no correlation \
+                            in the input col_expr",
+                    );
+                    column_casts.push(cast_expr);
+                }
+                let r = table_casts.insert(i + 1, column_casts);
+                assert!(r.is_none(), "cannot have table defined multiple times");
+
+                // MySQL has no level of namespacing above schemas, so tables
+                // are referenced as `schema.table`.
+                let name = FullObjectName {
+                    database: RawDatabaseSpecifier::Ambient,
+                    schema: table.schema_name.clone(),
+                    item: table.name.clone(),
+                };
+
+                // The zero-th output is the main output
+                available_subsources.insert(name, i + 1);
+            }
+
+            let connection = GenericSourceConnection::MySql(MySqlSourceConnection {
+                connection,
+                connection_id: connection_item.id(),
+                table_casts,
+                details,
+            });
+            // The MySQL source only outputs data to its subsources. The catalog
+            // object representing the source itself is just an empty relation
+            // with no columns
+            let encoding = SourceDataEncoding::Single(DataEncoding::new(
+                DataEncodingInner::RowCodec(RelationDesc::empty()),
+            ));
+            (connection, encoding, Some(available_subsources))
+        }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a sql server connection", connection_item.name()),
+            };
+            let SqlServerConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: SQL Server source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoSqlServerSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                SqlServerSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            // Register the available subsources
+            let mut available_subsources = BTreeMap::new();
+
+            // As with Postgres sources, the SQL Server source reader produces
+            // text encoded rows, which these expressions cast to the columns'
+            // target types.
+            let mut table_casts = HashMap::new();
+
+            for (i, table) in details.tables.iter().enumerate() {
+                let mut cast_scx = scx.clone();
+                cast_scx.param_types = Default::default();
+                let cast_qcx = QueryContext::root(&cast_scx, QueryLifetime::Static);
+                let mut column_types = vec![];
+                for column in table.columns.iter() {
+                    column_types.push(ColumnType {
+                        nullable: column.nullable,
+                        scalar_type: ScalarType::String,
+                    });
+                }
+
+                let cast_ecx = ExprContext {
+                    qcx: &cast_qcx,
+                    name: "plan_sql_server_source_cast",
+                    scope: &Scope::empty(),
+                    relation_type: &RelationType {
+                        column_types,
+                        keys: vec![],
+                    },
+                    allow_aggregates: false,
+                    allow_subqueries: false,
+                    allow_windows: false,
+                };
+
+                let mut column_casts = vec![];
+                for (i, column) in table.columns.iter().enumerate() {
+                    let ty = match crate::sql_server_util::scalar_type(&column.data_type) {
+                        Some(ty) => ty,
+                        // If this reference survived purification, we do not
+                        // expect it to be from a table that the user will
+                        // consume, i.e. expect this table to be filtered out
+                        // of table casts.
+                        None => {
+                            column_casts.push(
+                                HirScalarExpr::CallVariadic {
+                                    func: mz_expr::VariadicFunc::ErrorIfNull,
+                                    exprs: vec![
+                                        HirScalarExpr::literal_null(ScalarType::String),
+                                        HirScalarExpr::literal(
+                                            mz_repr::Datum::from(
+                                                format!("Unsupported type {}", column.data_type)
+                                                    .as_str(),
+                                            ),
+                                            ScalarType::String,
+                                        ),
+                                    ],
+                                }
+                                .lower_uncorrelated()
+                                .expect("no correlation"),
+                            );
+                            continue;
+                        }
+                    };
+
+                    let data_type = scx.resolve_type(ty)?;
+                    let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
+
+                    let col_expr = HirScalarExpr::Column(ColumnRef {
+                        level: 0,
+                        column: i,
+                    });
+
+                    let cast_expr = plan_cast(
+                        &cast_ecx,
+                        CastContext::Explicit,
+                        col_expr,
+                        &scalar_type,
+                    )?
+                    .lower_uncorrelated()
+                    .expect(
+                        "lower_uncorrelated should not fail given that there is no correlation \
+                            in the input col_expr",
+                    );
+                    column_casts.push(cast_expr);
+                }
+                let r = table_casts.insert(i + 1, column_casts);
+                assert!(r.is_none(), "cannot have table defined multiple times");
+
+                // Each SQL Server connection is to a single database, so
+                // tables are referenced as `schema.table`.
+                let name = FullObjectName {
+                    database: RawDatabaseSpecifier::Ambient,
+                    schema: table.schema_name.clone(),
+                    item: table.name.clone(),
+                };
+
+                // The zero-th output is the main output
+                available_subsources.insert(name, i + 1);
+            }
+
+            let connection = GenericSourceConnection::SqlServer(SqlServerSourceConnection {
+                connection,
+                connection_id: connection_item.id(),
+                table_casts,
+                details,
+            });
+            // The SQL Server source only outputs data to its subsources. The catalog
+            // object representing the source itself is just an empty relation
+            // with no columns
+            let encoding = SourceDataEncoding::Single(DataEncoding::new(
+                DataEncodingInner::RowCodec(RelationDesc::empty()),
+            ));
+            (connection, encoding, Some(available_subsources))
+        }
+        CreateSourceConnection::Mqtt {
+            connection: mqtt_connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(mqtt_connection)?;
+            let mqtt_connection = match connection_item.connection()? {
+                Connection::Mqtt(connection) => connection.clone(),
+                _ => sql_bail!("{} is not an MQTT connection", connection_item.name()),
+            };
+            let MqttConfigOptionExtracted {
+                qos,
+                topic_filters,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            if topic_filters.is_empty() {
+                sql_bail!("TOPIC FILTERS option is required");
+            }
+            for topic_filter in &topic_filters {
+                if !is_valid_mqtt_topic_filter(topic_filter) {
+                    sql_bail!("invalid MQTT topic filter {}", topic_filter.quoted());
+                }
+            }
+            let qos = match u8::try_from(qos) {
+                Ok(qos @ 0..=2) => qos,
+                _ => sql_bail!("QOS must be 0, 1, or 2, got {}", qos),
+            };
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            let connection = GenericSourceConnection::Mqtt(MqttSourceConnection {
+                connection_id: connection_item.id(),
+                connection: mqtt_connection,
+                topic_filters,
+                qos,
+                environment_id: scx.catalog.config().environment_id.to_string(),
+            });
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::PubSub {
+            connection: gcp_connection,
+            options,
+        } => {
+            let connection_item = scx.get_item_by_resolved_name(gcp_connection)?;
+            let gcp_connection = match connection_item.connection()? {
+                Connection::Gcp(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a GCP connection", connection_item.name()),
+            };
+            let PubSubConfigOptionExtracted {
+                subscription,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            let subscription =
+                subscription.ok_or_else(|| sql_err!("SUBSCRIPTION option is required"))?;
+            if !is_valid_pubsub_subscription(&subscription) {
+                sql_bail!("invalid Pub/Sub subscription {}", subscription.quoted());
+            }
+
+            let encoding = get_encoding(scx, format, &envelope, Some(connection))?;
+            let connection = GenericSourceConnection::PubSub(PubSubSourceConnection {
+                connection_id: connection_item.id(),
+                connection: gcp_connection,
+                subscription,
+            });
+            (connection, encoding, None)
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let (load_generator, available_subsources) =
                 load_generator_ast_to_generator(generator, options)?;
@@ -832,6 +1260,38 @@ pub fn plan_create_source(
             let encoding = get_encoding(scx, format, &envelope, None)?;
             (connection, encoding, None)
         }
+        CreateSourceConnection::File { path, tail } => {
+            scx.require_unsafe_mode("CREATE SOURCE ... FROM FILE")?;
+            let connection = GenericSourceConnection::File(FileSourceConnection {
+                path: path.clone(),
+                tail: *tail,
+            });
+            let encoding = get_encoding(scx, format, &envelope, None)?;
+            // Avro files are decoded as a whole, as Avro Object Container
+            // Files, which embed the schema they were written with.
+            if let SourceDataEncoding::Single(DataEncoding {
+                inner: DataEncodingInner::Avro(AvroEncoding { csr_connection, .. }),
+                ..
+            }) = &encoding
+            {
+                if csr_connection.is_some() {
+                    sql_bail!(
+                        "FILE sources do not support schema registry connections; \
+                         specify the reader schema with FORMAT AVRO USING SCHEMA"
+                    );
+                }
+                if *tail {
+                    sql_bail!("FILE sources with FORMAT AVRO do not support TAIL");
+                }
+            }
+            (connection, encoding, None)
+        }
+        CreateSourceConnection::Webhook { .. } => {
+            unreachable!("webhook sources are planned by plan_create_webhook_source")
+        }
+        CreateSourceConnection::Flight { .. } => {
+            unreachable!("Flight sources are planned by plan_create_flight_source")
+        }
     };
 
     let (available_subsources, requested_subsources) = match (available_subsources, subsources) {
@@ -903,7 +1363,9 @@ pub fn plan_create_source(
         subsource_exports.insert(target_id, *idx);
     }
 
-    if let GenericSourceConnection::Postgres(PostgresSourceConnection { table_casts, .. }) =
+    if let GenericSourceConnection::Postgres(PostgresSourceConnection { table_casts, .. })
+    | GenericSourceConnection::MySql(MySqlSourceConnection { table_casts, .. })
+    | GenericSourceConnection::SqlServer(SqlServerSourceConnection { table_casts, .. }) =
         &mut external_connection
     {
         // Now that we know which subsources sources we want, we can remove all
@@ -914,6 +1376,24 @@ pub fn plan_create_source(
         table_casts.retain(|pos, _| used_pos.contains(pos));
     }
 
+    // Remove the fields that the projection discards from an Avro value's
+    // reader schema, so that they are skipped rather than decoded. Column
+    // names only match field names if the columns have not been renamed.
+    if !projection.is_empty() && col_names.is_empty() {
+        let value = match &mut encoding {
+            SourceDataEncoding::Single(value) | SourceDataEncoding::KeyValue { value, .. } => value,
+        };
+        if let DataEncodingInner::Avro(AvroEncoding { schema, .. }) = &mut value.inner {
+            let fields = projection
+                .iter()
+                .map(|c| normalize::column_name(c.clone()))
+                .collect::<HashSet<_>>();
+            if let Some(pruned) = prune_avro_record_fields(schema, &fields) {
+                *schema = pruned;
+            }
+        }
+    }
+
     let (key_desc, value_desc) = encoding.desc()?;
 
     let mut key_envelope = get_key_envelope(include_metadata, &envelope, &encoding)?;
@@ -975,9 +1455,22 @@ pub fn plan_create_source(
         timeline,
         timestamp_interval,
         ignore_keys,
+        max_bytes_in_flight,
+        max_messages_per_second,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
+    if max_bytes_in_flight == Some(0) {
+        sql_bail!("MAX BYTES IN FLIGHT must be greater than 0");
+    }
+    if max_messages_per_second == Some(0) {
+        sql_bail!("MAX MESSAGES PER SECOND must be greater than 0");
+    }
+    let rate_limits = SourceRateLimits {
+        max_messages_per_second,
+        max_bytes_in_flight,
+    };
+
     if ignore_keys.unwrap_or(false) {
         desc = desc.without_keys();
     }
@@ -989,6 +1482,45 @@ pub fn plan_create_source(
         sql_bail!("column {} specified more than once", dup.as_str().quoted());
     }
 
+    // Apply the user-specified projection and filter, which storage
+    // evaluates as each row is decoded.
+    let mfp = if !projection.is_empty() || filter.is_some() {
+        let mut mfp = MapFilterProject::new(desc.arity());
+        if !projection.is_empty() {
+            let indices = projection
+                .iter()
+                .map(|col| {
+                    let col = normalize::column_name(col.clone());
+                    match desc.get_by_name(&col) {
+                        Some((idx, _)) if desc.get_unambiguous_name(idx).is_some() => Ok(idx),
+                        Some(_) => sql_bail!("Ambiguous column in COLUMNS: {}", col),
+                        None => sql_bail!("No such column in COLUMNS: {}", col),
+                    }
+                })
+                .collect::<Result<Vec<_>, PlanError>>()?;
+            if let Some(dup) = indices.iter().duplicates().next() {
+                sql_bail!(
+                    "column {} specified more than once in COLUMNS",
+                    desc.get_name(*dup).as_str().quoted()
+                );
+            }
+            desc = RelationDesc::from_names_and_types(indices.iter().map(|idx| {
+                (
+                    desc.get_name(*idx).clone(),
+                    desc.typ().column_types[*idx].clone(),
+                )
+            }));
+            mfp = mfp.project(indices);
+        }
+        if let Some(filter) = filter {
+            let predicate = query::plan_source_filter(scx, &desc, filter.clone())?;
+            mfp = mfp.filter([predicate]);
+        }
+        Some(mfp)
+    } else {
+        None
+    };
+
     // Apply user-specified key constraint
     if let Some(KeyConstraint::PrimaryKeyNotEnforced { columns }) = key_constraint.clone() {
         // Don't remove this without addressing
@@ -1041,6 +1573,8 @@ pub fn plan_create_source(
         envelope: envelope.clone(),
         metadata_columns: metadata_column_types,
         timestamp_interval,
+        mfp,
+        rate_limits,
     };
 
     let if_not_exists = *if_not_exists;
@@ -1071,6 +1605,8 @@ pub fn plan_create_source(
             source_imports: HashSet::new(),
             subsource_exports,
         }),
+        webhook: None,
+        flight: false,
         desc,
     };
 
@@ -1083,6 +1619,226 @@ pub fn plan_create_source(
     }))
 }
 
+/// Removes the fields of the Avro record `schema` whose names are not in
+/// `fields`.
+///
+/// Returns `None` if `schema` is not a record, or if removing the fields would
+/// leave a schema that does not parse, e.g. because a retained field refers to
+/// a named type defined by a removed field.
+fn prune_avro_record_fields(schema: &str, fields: &HashSet<ColumnName>) -> Option<String> {
+    let mut value: serde_json::Value = serde_json::from_str(schema).ok()?;
+    value.get_mut("fields")?.as_array_mut()?.retain(|field| {
+        match field.get("name").and_then(|name| name.as_str()) {
+            Some(name) => fields.contains(&ColumnName::from(name)),
+            None => true,
+        }
+    });
+    let pruned = value.to_string();
+    mz_interchange::avro::parse_schema(&pruned).ok()?;
+    Some(pruned)
+}
+
+generate_extracted_config!(
+    WebhookOption,
+    (HmacSecret, with_options::Secret),
+    (HmacHeader, String)
+);
+
+/// The header that carries request signatures if `HMAC HEADER` is not
+/// specified.
+const DEFAULT_WEBHOOK_HMAC_HEADER: &str = "x-signature";
+
+fn plan_create_webhook_source(
+    scx: &StatementContext,
+    stmt: CreateSourceStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let CreateSourceStatement {
+        name,
+        col_names,
+        connection,
+        envelope,
+        projection,
+        filter,
+        if_not_exists,
+        format,
+        key_constraint,
+        include_metadata,
+        with_options,
+        subsources,
+    } = &stmt;
+
+    let options = match connection {
+        CreateSourceConnection::Webhook { options } => options,
+        _ => unreachable!("plan_create_webhook_source called on a non-webhook source"),
+    };
+    if !col_names.is_empty()
+        || !matches!(format, CreateSourceFormat::None)
+        || envelope.is_some()
+        || !projection.is_empty()
+        || filter.is_some()
+        || key_constraint.is_some()
+        || !include_metadata.is_empty()
+        || !with_options.is_empty()
+        || subsources.is_some()
+    {
+        sql_bail!(
+            "webhook sources do not support column names, FORMAT, ENVELOPE, INCLUDE, \
+             COLUMNS, WHERE, key constraints, WITH options, or subsources"
+        );
+    }
+
+    let WebhookOptionExtracted {
+        hmac_secret,
+        hmac_header,
+        ..
+    } = options.clone().try_into()?;
+    // Requests are not authenticated as any user, so the signature is the
+    // only thing that keeps others from writing to the source.
+    let validation = match hmac_secret {
+        Some(secret) => WebhookValidation {
+            secret_id: secret.into(),
+            header: hmac_header
+                .unwrap_or_else(|| DEFAULT_WEBHOOK_HMAC_HEADER.into())
+                .to_lowercase(),
+        },
+        None => sql_bail!("webhook sources require HMAC SECRET"),
+    };
+
+    // Each request, or each line of a newline-delimited request, is appended
+    // as a single JSON value.
+    let desc = RelationDesc::empty().with_column("body", ScalarType::Jsonb.nullable(false));
+
+    let if_not_exists = *if_not_exists;
+    let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name.clone())?)?;
+    let create_sql = normalize::create_statement(scx, Statement::CreateSource(stmt))?;
+
+    let source = Source {
+        create_sql,
+        ingestion: None,
+        webhook: Some(Webhook { validation }),
+        flight: false,
+        desc,
+    };
+
+    Ok(Plan::CreateSource(CreateSourcePlan {
+        name,
+        source,
+        if_not_exists,
+        // Requests are appended at the time they are received.
+        timeline: Timeline::EpochMilliseconds,
+        host_config: StorageHostConfig::Undefined,
+    }))
+}
+
+fn plan_create_flight_source(
+    scx: &StatementContext,
+    stmt: CreateSourceStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let CreateSourceStatement {
+        name,
+        col_names,
+        connection,
+        envelope,
+        projection,
+        filter,
+        if_not_exists,
+        format,
+        key_constraint,
+        include_metadata,
+        with_options,
+        subsources,
+    } = &stmt;
+
+    let columns = match connection {
+        CreateSourceConnection::Flight { columns } => columns,
+        _ => unreachable!("plan_create_flight_source called on a non-Flight source"),
+    };
+    if !col_names.is_empty()
+        || !matches!(format, CreateSourceFormat::None)
+        || envelope.is_some()
+        || !projection.is_empty()
+        || filter.is_some()
+        || key_constraint.is_some()
+        || !include_metadata.is_empty()
+        || !with_options.is_empty()
+        || subsources.is_some()
+    {
+        sql_bail!(
+            "Flight sources do not support column names, FORMAT, ENVELOPE, INCLUDE, \
+             COLUMNS, WHERE, key constraints, WITH options, or subsources"
+        );
+    }
+
+    let names: Vec<_> = columns
+        .iter()
+        .map(|c| normalize::column_name(c.name.clone()))
+        .collect();
+    if names.is_empty() {
+        sql_bail!("Flight sources must declare at least one column");
+    }
+    if let Some(dup) = names.iter().duplicates().next() {
+        sql_bail!("column {} specified more than once", dup.as_str().quoted());
+    }
+
+    // The declared columns are the schema against which the record batches
+    // written to the source are validated.
+    let mut column_types = Vec::with_capacity(columns.len());
+    for c in columns {
+        let ty = query::scalar_type_from_sql(scx, &c.data_type)?;
+        match ty {
+            ScalarType::Bool
+            | ScalarType::Int16
+            | ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::Float32
+            | ScalarType::Float64
+            | ScalarType::String
+            | ScalarType::Bytes
+            | ScalarType::Jsonb
+            | ScalarType::Date
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz => (),
+            _ => bail_unsupported!(format!(
+                "Flight source column of type {}",
+                scx.humanize_scalar_type(&ty)
+            )),
+        }
+        let mut nullable = true;
+        for option in &c.options {
+            match &option.option {
+                ColumnOption::NotNull => nullable = false,
+                ColumnOption::Null => nullable = true,
+                other => {
+                    bail_unsupported!(format!("Flight source with column constraint: {}", other))
+                }
+            }
+        }
+        column_types.push(ty.nullable(nullable));
+    }
+    let desc = RelationDesc::new(RelationType::new(column_types), names);
+
+    let if_not_exists = *if_not_exists;
+    let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name.clone())?)?;
+    let create_sql = normalize::create_statement(scx, Statement::CreateSource(stmt))?;
+
+    let source = Source {
+        create_sql,
+        ingestion: None,
+        webhook: None,
+        flight: true,
+        desc,
+    };
+
+    Ok(Plan::CreateSource(CreateSourcePlan {
+        name,
+        source,
+        if_not_exists,
+        // Record batches are appended at the time they are received.
+        timeline: Timeline::EpochMilliseconds,
+        host_config: StorageHostConfig::Undefined,
+    }))
+}
+
 pub fn plan_create_subsource(
     scx: &StatementContext,
     stmt: CreateSubsourceStatement<Aug>,
@@ -1176,6 +1932,8 @@ pub fn plan_create_subsource(
     let source = Source {
         create_sql,
         ingestion: None,
+        webhook: None,
+        flight: false,
         desc,
     };
 
@@ -1315,6 +2073,25 @@ fn get_encoding(
         && matches!(envelope, Envelope::None);
     let encoding = encoding.into_source_data_encoding(force_nullable_keys);
 
+    // Skipped messages would be indistinguishable from deletions in envelopes
+    // that interpret missing values.
+    let skips_incompatible_schemas = match &encoding {
+        SourceDataEncoding::Single(DataEncoding { inner, .. })
+        | SourceDataEncoding::KeyValue {
+            value: DataEncoding { inner, .. },
+            ..
+        } => matches!(
+            inner,
+            DataEncodingInner::Avro(AvroEncoding {
+                skip_incompatible_schemas: true,
+                ..
+            })
+        ),
+    };
+    if skips_incompatible_schemas && !matches!(envelope, Envelope::None) {
+        sql_bail!("INCOMPATIBLE SCHEMA POLICY 'skip' requires ENVELOPE NONE");
+    }
+
     let requires_keyvalue = matches!(
         envelope,
         Envelope::Debezium(DbzMode::Plain) | Envelope::Upsert
@@ -1347,6 +2124,7 @@ pub struct Schema {
     pub value_schema: String,
     pub csr_connection: Option<mz_storage_client::types::connections::CsrConnection>,
     pub confluent_wire_format: bool,
+    pub skip_incompatible_schemas: bool,
 }
 
 fn get_encoding_inner(
@@ -1362,6 +2140,7 @@ fn get_encoding_inner(
                 value_schema,
                 csr_connection,
                 confluent_wire_format,
+                skip_incompatible_schemas,
             } = match schema {
                 // TODO(jldlaughlin): we need a way to pass in primary key information
                 // when building a source from a string or file.
@@ -1379,6 +2158,7 @@ fn get_encoding_inner(
                         value_schema: schema.clone(),
                         csr_connection: None,
                         confluent_wire_format,
+                        skip_incompatible_schemas: false,
                     }
                 }
                 AvroSchema::Csr {
@@ -1397,6 +2177,18 @@ fn get_encoding_inner(
                             sql_bail!("{} is not a schema registry connection", item.name())
                         }
                     };
+                    let CsrConfigOptionExtracted {
+                        incompatible_schema_policy,
+                        ..
+                    } = connection.options.clone().try_into()?;
+                    let skip_incompatible_schemas = match incompatible_schema_policy.as_deref() {
+                        None | Some("error") => false,
+                        Some("skip") => true,
+                        Some(policy) => sql_bail!(
+                            "invalid INCOMPATIBLE SCHEMA POLICY {}: must be 'error' or 'skip'",
+                            policy.quoted()
+                        ),
+                    };
 
                     if let Some(seed) = seed {
                         Schema {
@@ -1404,6 +2196,7 @@ fn get_encoding_inner(
                             value_schema: seed.value_schema.clone(),
                             csr_connection: Some(csr_connection),
                             confluent_wire_format: true,
+                            skip_incompatible_schemas,
                         }
                     } else {
                         unreachable!("CSR seed resolution should already have been called: Avro")
@@ -1417,11 +2210,13 @@ fn get_encoding_inner(
                         schema: key_schema,
                         csr_connection: csr_connection.clone(),
                         confluent_wire_format,
+                        skip_incompatible_schemas: false,
                     }),
                     value: DataEncodingInner::Avro(AvroEncoding {
                         schema: value_schema,
                         csr_connection,
                         confluent_wire_format,
+                        skip_incompatible_schemas,
                     }),
                 });
             } else {
@@ -1429,6 +2224,7 @@ fn get_encoding_inner(
                     schema: value_schema,
                     csr_connection,
                     confluent_wire_format,
+                    skip_incompatible_schemas,
                 })
             }
         }
@@ -1490,15 +2286,75 @@ fn get_encoding_inner(
                 })
             }
         },
-        Format::Regex(regex) => {
+        Format::Regex {
+            regex,
+            column_types: types,
+            on_mismatch,
+        } => {
             let regex = Regex::new(regex).map_err(|e| sql_err!("parsing regex: {e}"))?;
+            // Capture groups are decoded as text unless a type is specified
+            // for them. The first capture group is the entire match, which
+            // does not become a column.
+            let mut column_types = vec![ScalarType::String; regex.captures_len() - 1];
+            let mut typed = BTreeSet::new();
+            for RegexColumnType { name, data_type } in types {
+                let i = match regex.capture_names().position(|n| n == Some(name.as_str())) {
+                    Some(i) => i,
+                    None => sql_bail!(
+                        "regex has no capture group named {}",
+                        name.as_str().quoted()
+                    ),
+                };
+                if !typed.insert(i) {
+                    sql_bail!(
+                        "type of capture group {} specified more than once",
+                        name.as_str().quoted()
+                    );
+                }
+                let ty = query::scalar_type_from_sql(scx, data_type)?;
+                match ty {
+                    ScalarType::Bool
+                    | ScalarType::Int16
+                    | ScalarType::Int32
+                    | ScalarType::Int64
+                    | ScalarType::Float32
+                    | ScalarType::Float64
+                    | ScalarType::Numeric { .. }
+                    | ScalarType::String
+                    | ScalarType::Jsonb
+                    | ScalarType::Date
+                    | ScalarType::Timestamp
+                    | ScalarType::TimestampTz => (),
+                    _ => bail_unsupported!(format!(
+                        "regex capture group of type {}",
+                        scx.humanize_scalar_type(&ty)
+                    )),
+                }
+                column_types[i - 1] = ty;
+            }
+            let on_mismatch = match on_mismatch {
+                RegexMismatch::Skip => RegexMismatchPolicy::Skip,
+                RegexMismatch::Null => RegexMismatchPolicy::Null,
+                RegexMismatch::Error => RegexMismatchPolicy::Error,
+            };
             DataEncodingInner::Regex(RegexEncoding {
                 regex: mz_repr::adt::regex::Regex(regex),
+                column_types,
+                on_mismatch,
             })
         }
-        Format::Csv { columns, delimiter } => {
+        Format::Csv {
+            columns,
+            delimiter,
+            quote,
+            null,
+        } => {
+            if null.is_some() {
+                sql_bail!("CSV sources do not support NULL");
+            }
             let columns = match columns {
-                CsvColumns::Header { names } => {
+                None => sql_bail!("CSV sources require WITH HEADER or WITH n COLUMNS"),
+                Some(CsvColumns::Header { names }) => {
                     if names.is_empty() {
                         sql_bail!("[internal error] column spec should get names in purify")
                     }
@@ -1506,19 +2362,112 @@ fn get_encoding_inner(
                         names: names.iter().cloned().map(|n| n.into_string()).collect(),
                     }
                 }
-                CsvColumns::Count(n) => ColumnSpec::Count(*n),
+                Some(CsvColumns::Count(n)) => ColumnSpec::Count(*n),
             };
+            let (delimiter, quote) = plan_csv_delimiter_and_quote(*delimiter, *quote)?;
             DataEncodingInner::Csv(CsvEncoding {
                 columns,
-                delimiter: u8::try_from(*delimiter)
-                    .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?,
+                delimiter,
+                quote,
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json => DataEncodingInner::Json,
         Format::Text => DataEncodingInner::Text,
     }))
 }
 
+/// Converts the delimiter and quote character of a CSV format to the bytes
+/// that encode them.
+fn plan_csv_delimiter_and_quote(delimiter: char, quote: char) -> Result<(u8, u8), PlanError> {
+    let delimiter = u8::try_from(delimiter)
+        .map_err(|_| sql_err!("CSV delimiter must be an ASCII character"))?;
+    let quote =
+        u8::try_from(quote).map_err(|_| sql_err!("CSV quote must be an ASCII character"))?;
+    if delimiter == quote {
+        sql_bail!("CSV delimiter and quote must be different characters");
+    }
+    Ok((delimiter, quote))
+}
+
+/// Plans the CSV format of a sink.
+///
+/// Only sinks that write many records to one file can write a header, so only
+/// they pass the `header_desc` whose column names the header defaults to.
+fn plan_csv_sink_format(
+    columns: Option<CsvColumns>,
+    delimiter: char,
+    quote: char,
+    null: Option<String>,
+    header_desc: Option<&RelationDesc>,
+) -> Result<KafkaSinkCsvFormat, PlanError> {
+    let (delimiter, quote) = plan_csv_delimiter_and_quote(delimiter, quote)?;
+    let header = match (columns, header_desc) {
+        (None, _) => None,
+        (Some(CsvColumns::Count(_)), _) => sql_bail!("CSV sinks do not support WITH n COLUMNS"),
+        (Some(CsvColumns::Header { .. }), None) => {
+            sql_bail!("CSV WITH HEADER is only supported for file sinks")
+        }
+        (Some(CsvColumns::Header { names }), Some(desc)) if names.is_empty() => {
+            Some(desc.iter_names().map(|n| n.as_str().to_string()).collect())
+        }
+        (Some(CsvColumns::Header { names }), Some(desc)) => {
+            if names.len() != desc.arity() {
+                sql_bail!(
+                    "CSV WITH HEADER specifies {} columns, but the sink has {}",
+                    names.len(),
+                    desc.arity()
+                );
+            }
+            Some(names.into_iter().map(|n| n.into_string()).collect())
+        }
+    };
+    let null = null.unwrap_or_default();
+    if null
+        .bytes()
+        .any(|b| b == delimiter || b == quote || b == b'\n' || b == b'\r')
+    {
+        sql_bail!("CSV NULL string must not contain the delimiter, the quote or a line break");
+    }
+    Ok(KafkaSinkCsvFormat {
+        delimiter,
+        quote,
+        null,
+        header,
+    })
+}
+
+/// Reports whether `filter` is a valid MQTT topic filter: a non-empty string
+/// whose `+` wildcards each occupy a whole level, and whose `#` wildcard, if
+/// any, occupies the whole last level.
+fn is_valid_mqtt_topic_filter(filter: &str) -> bool {
+    if filter.is_empty() || filter.contains('\0') {
+        return false;
+    }
+    let mut levels = filter.split('/').peekable();
+    while let Some(level) = levels.next() {
+        let is_last = levels.peek().is_none();
+        match level {
+            "+" => {}
+            "#" if is_last => {}
+            _ if level.contains(['+', '#']) => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Reports whether `subscription` is a valid Pub/Sub subscription ID: between 3
+/// and 255 characters that start with a letter and contain only letters,
+/// digits, and `-_.~+%`, and that do not start with `goog`.
+fn is_valid_pubsub_subscription(subscription: &str) -> bool {
+    (3..=255).contains(&subscription.len())
+        && subscription.starts_with(|c: char| c.is_ascii_alphabetic())
+        && !subscription.starts_with("goog")
+        && subscription
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.~+%".contains(c))
+}
+
 /// Extract the key envelope, if it is requested
 fn get_key_envelope(
     included_items: &[SourceIncludeMetadata],
@@ -1539,7 +2488,24 @@ fn get_key_envelope(
                 Ok(KeyEnvelope::Named(name.as_str().to_string()))
             }
             (None, SourceDataEncoding::KeyValue { key, .. }) => get_unnamed_key_envelope(key),
-            (_, SourceDataEncoding::Single(_)) => {
+            (_, SourceDataEncoding::Single(value)) => {
+                // Schema registry formats look up the key schema themselves,
+                // and only produce a bare format if there is none.
+                let uses_csr = match &value.inner {
+                    DataEncodingInner::Avro(AvroEncoding { csr_connection, .. }) => {
+                        csr_connection.is_some()
+                    }
+                    DataEncodingInner::Protobuf(ProtobufEncoding {
+                        confluent_wire_format,
+                        ..
+                    }) => *confluent_wire_format,
+                    _ => false,
+                };
+                if uses_csr {
+                    sql_bail!(
+                        "INCLUDE KEY requires a key schema, but none was found in the schema registry"
+                    );
+                }
                 // `kd.alias` == `None` means `INCLUDE KEY`
                 // `kd.alias` == `Some(_) means INCLUDE KEY AS ___`
                 // These both make sense with the same error message
@@ -1564,7 +2530,7 @@ fn get_unnamed_key_envelope(key: &DataEncoding) -> Result<KeyEnvelope, PlanError
         DataEncodingInner::RowCodec(_) => {
             sql_bail!("{} sources cannot use INCLUDE KEY", key.op_name())
         }
-        DataEncodingInner::Bytes | DataEncodingInner::Text => false,
+        DataEncodingInner::Bytes | DataEncodingInner::Text | DataEncodingInner::Json => false,
         DataEncodingInner::Avro(_)
         | DataEncodingInner::Csv(_)
         | DataEncodingInner::Protobuf(_)
@@ -1766,6 +2732,7 @@ pub fn describe_create_sink(
 
 generate_extracted_config!(
     CreateSinkOption,
+    (ErrorPolicy, String),
     (Remote, String),
     (Size, String),
     (Snapshot, bool)
@@ -1779,6 +2746,7 @@ pub fn plan_create_sink(
     let CreateSinkStatement {
         name,
         from,
+        projection,
         connection,
         format,
         envelope,
@@ -1786,8 +2754,11 @@ pub fn plan_create_sink(
         with_options,
     } = stmt;
 
-    const SAFE_WITH_OPTIONS: &[CreateSinkOptionName] =
-        &[CreateSinkOptionName::Size, CreateSinkOptionName::Snapshot];
+    const SAFE_WITH_OPTIONS: &[CreateSinkOptionName] = &[
+        CreateSinkOptionName::ErrorPolicy,
+        CreateSinkOptionName::Size,
+        CreateSinkOptionName::Snapshot,
+    ];
 
     if with_options
         .iter()
@@ -1809,9 +2780,55 @@ pub fn plan_create_sink(
     let name = scx.allocate_qualified_name(normalize::unresolved_object_name(name)?)?;
     let from = scx.get_item_by_resolved_name(&from)?;
 
-    let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
+    let mut desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
+
+    // Restrict the sink to the columns named in the `COLUMNS` clause, if any.
+    // Keys of `from` survive only if all of their columns are retained.
+    let projection = if !projection.is_empty() {
+        let indices = projection
+            .iter()
+            .map(|col| {
+                let col = normalize::column_name(col.clone());
+                match desc.get_by_name(&col) {
+                    Some((idx, _)) if desc.get_unambiguous_name(idx).is_some() => Ok(idx),
+                    Some(_) => sql_bail!("Ambiguous column in COLUMNS: {}", col),
+                    None => sql_bail!("No such column in COLUMNS: {}", col),
+                }
+            })
+            .collect::<Result<Vec<_>, PlanError>>()?;
+        if let Some(dup) = indices.iter().duplicates().next() {
+            sql_bail!(
+                "column {} specified more than once in COLUMNS",
+                desc.get_name(*dup).as_str().quoted()
+            );
+        }
+        let mut projected = RelationDesc::from_names_and_types(indices.iter().map(|idx| {
+            (
+                desc.get_name(*idx).clone(),
+                desc.typ().column_types[*idx].clone(),
+            )
+        }));
+        for key in &desc.typ().keys {
+            let key = key
+                .iter()
+                .map(|col| indices.iter().position(|idx| idx == col))
+                .collect::<Option<Vec<_>>>();
+            if let Some(key) = key {
+                projected = projected.with_key(key);
+            }
+        }
+        desc = Cow::Owned(projected);
+        Some(indices)
+    } else {
+        None
+    };
+
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. }
+        | CreateSinkConnection::Postgres { key, .. }
+        | CreateSinkConnection::Elasticsearch { key, .. }
+        | CreateSinkConnection::Redis { key, .. }
+        | CreateSinkConnection::Nats { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -1856,6 +2873,14 @@ pub fn plan_create_sink(
                 None
             }
         }
+        CreateSinkConnection::File { .. } | CreateSinkConnection::ObjectStore { .. } => {
+            // A file has no notion of a key, so each of its records must carry
+            // both the old and the new state of the row that it updates.
+            if envelope != SinkEnvelope::Debezium {
+                sql_bail!("sinks that write files require ENVELOPE DEBEZIUM");
+            }
+            None
+        }
     };
 
     // pick the first valid natural relation key, if any
@@ -1886,9 +2911,239 @@ pub fn plan_create_sink(
             desc.into_owned(),
             envelope,
         )?,
+        CreateSinkConnection::File { path, options } => {
+            scx.require_unsafe_mode("CREATE SINK ... INTO FILE")?;
+            let options: ObjectSinkOptionExtracted = options.try_into()?;
+            if options.bucket.is_some() || options.container.is_some() || options.prefix.is_some() {
+                sql_bail!("FILE sinks do not support BUCKET, CONTAINER or PREFIX");
+            }
+            object_sink_builder(
+                ObjectStoreConnection::Directory { path },
+                options,
+                format,
+                relation_key_indices,
+                desc.into_owned(),
+            )?
+        }
+        CreateSinkConnection::ObjectStore {
+            store,
+            connection,
+            options,
+        } => {
+            scx.require_unsafe_mode(&format!("CREATE SINK ... INTO {}", store))?;
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let options: ObjectSinkOptionExtracted = options.try_into()?;
+            let prefix = options.prefix.clone().unwrap_or_default();
+            let store = match store {
+                ObjectStoreKind::S3 | ObjectStoreKind::Gcs => {
+                    if options.container.is_some() {
+                        sql_bail!("{} sinks do not support CONTAINER", store);
+                    }
+                    let bucket = options
+                        .bucket
+                        .clone()
+                        .ok_or_else(|| sql_err!("{} sinks must specify BUCKET", store))?;
+                    match (store, item.connection()?) {
+                        (ObjectStoreKind::S3, Connection::Aws(aws)) => ObjectStoreConnection::S3 {
+                            connection_id: item.id(),
+                            connection: aws.clone(),
+                            bucket,
+                            prefix,
+                        },
+                        (ObjectStoreKind::S3, _) => {
+                            sql_bail!("{} is not an AWS connection", item.name())
+                        }
+                        (_, Connection::Gcp(gcp)) => ObjectStoreConnection::Gcs {
+                            connection_id: item.id(),
+                            connection: gcp.clone(),
+                            bucket,
+                            prefix,
+                        },
+                        (_, _) => sql_bail!("{} is not a GCP connection", item.name()),
+                    }
+                }
+                ObjectStoreKind::AzureBlob => {
+                    if options.bucket.is_some() {
+                        sql_bail!("AZURE BLOB sinks do not support BUCKET");
+                    }
+                    let connection = match item.connection()? {
+                        Connection::AzureBlob(connection) => connection.clone(),
+                        _ => sql_bail!("{} is not an Azure Blob connection", item.name()),
+                    };
+                    ObjectStoreConnection::AzureBlob {
+                        connection_id: item.id(),
+                        connection,
+                        container: options
+                            .container
+                            .clone()
+                            .ok_or_else(|| sql_err!("AZURE BLOB sinks must specify CONTAINER"))?,
+                        prefix,
+                    }
+                }
+            };
+            object_sink_builder(
+                store,
+                options,
+                format,
+                relation_key_indices,
+                desc.into_owned(),
+            )?
+        }
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            ..
+        } => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let connection = match item.connection()? {
+                Connection::Postgres(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a postgres connection", item.name()),
+            };
+            if envelope != SinkEnvelope::Upsert {
+                sql_bail!("POSTGRES sinks require ENVELOPE UPSERT");
+            }
+            if format.is_some() {
+                sql_bail!("POSTGRES sinks do not support FORMAT");
+            }
+            for (name, typ) in desc.iter() {
+                if postgres_sink_column_type(&typ.scalar_type).is_none() {
+                    bail_unsupported!(format!(
+                        "column {} of type {} in POSTGRES sinks",
+                        name,
+                        scx.humanize_scalar_type(&typ.scalar_type)
+                    ));
+                }
+            }
+            let options: PostgresSinkOptionExtracted = options.try_into()?;
+            StorageSinkConnectionBuilder::Postgres(PostgresSinkConnection {
+                connection_id: item.id(),
+                connection,
+                schema: options.schema,
+                table: options
+                    .table
+                    .ok_or_else(|| sql_err!("POSTGRES sinks must specify TABLE"))?,
+                key_desc_and_indices: key_desc_and_indices.expect("upsert sinks have a key"),
+                value_desc: desc.into_owned(),
+            })
+        }
+        CreateSinkConnection::Elasticsearch {
+            connection,
+            options,
+            ..
+        } => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let connection = match item.connection()? {
+                Connection::Elasticsearch(connection) => connection.clone(),
+                _ => sql_bail!("{} is not an Elasticsearch connection", item.name()),
+            };
+            if envelope != SinkEnvelope::Upsert {
+                sql_bail!("ELASTICSEARCH sinks require ENVELOPE UPSERT");
+            }
+            if format.is_some() {
+                sql_bail!("ELASTICSEARCH sinks do not support FORMAT");
+            }
+            for (name, typ) in desc.iter() {
+                if elasticsearch_field_mapping(&typ.scalar_type).is_none() {
+                    bail_unsupported!(format!(
+                        "column {} of type {} in ELASTICSEARCH sinks",
+                        name,
+                        scx.humanize_scalar_type(&typ.scalar_type)
+                    ));
+                }
+            }
+            let options: ElasticsearchSinkOptionExtracted = options.try_into()?;
+            let index = options
+                .index
+                .ok_or_else(|| sql_err!("ELASTICSEARCH sinks must specify INDEX"))?;
+            if !is_valid_elasticsearch_index(&index)
+                || index == ElasticsearchSinkConnection::PROGRESS_INDEX
+            {
+                sql_bail!("invalid Elasticsearch index name {}", index.quoted());
+            }
+            StorageSinkConnectionBuilder::Elasticsearch(ElasticsearchSinkConnection {
+                connection_id: item.id(),
+                connection,
+                index,
+                key_desc_and_indices: key_desc_and_indices.expect("upsert sinks have a key"),
+                value_desc: desc.into_owned(),
+            })
+        }
+        CreateSinkConnection::Redis {
+            connection,
+            options,
+            ..
+        } => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let connection = match item.connection()? {
+                Connection::Redis(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a Redis connection", item.name()),
+            };
+            if envelope != SinkEnvelope::Upsert {
+                sql_bail!("REDIS sinks require ENVELOPE UPSERT");
+            }
+            let value_format = match format {
+                None => RedisSinkValueFormat::Hash,
+                Some(Format::Json) => RedisSinkValueFormat::Json,
+                Some(_) => sql_bail!("REDIS sinks only support FORMAT JSON"),
+            };
+            let options: RedisSinkOptionExtracted = options.try_into()?;
+            StorageSinkConnectionBuilder::Redis(RedisSinkConnection {
+                connection_id: item.id(),
+                connection,
+                key_prefix: options.key_prefix,
+                value_format,
+                key_desc_and_indices: key_desc_and_indices.expect("upsert sinks have a key"),
+                value_desc: desc.into_owned(),
+            })
+        }
+        CreateSinkConnection::Nats {
+            connection,
+            options,
+            ..
+        } => {
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let connection = match item.connection()? {
+                Connection::Nats(connection) => connection.clone(),
+                _ => sql_bail!("{} is not a NATS connection", item.name()),
+            };
+            let csv_format = match format {
+                Some(Format::Json) => None,
+                Some(Format::Csv {
+                    columns,
+                    delimiter,
+                    quote,
+                    null,
+                }) => {
+                    if envelope == SinkEnvelope::Debezium {
+                        sql_bail!("CSV sinks do not support ENVELOPE DEBEZIUM");
+                    }
+                    Some(plan_csv_sink_format(columns, delimiter, quote, null, None)?)
+                }
+                Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
+                None => bail_unsupported!("sink without format"),
+            };
+            let NatsSinkOptionExtracted {
+                deduplication_headers,
+                stream,
+                subject,
+                seen: _,
+            } = options.try_into()?;
+            StorageSinkConnectionBuilder::Nats(NatsSinkConnection {
+                connection_id: item.id(),
+                connection,
+                stream: stream.ok_or_else(|| sql_err!("NATS sinks must specify STREAM"))?,
+                subject: subject.ok_or_else(|| sql_err!("NATS sinks must specify SUBJECT"))?,
+                deduplication_headers,
+                csv_format,
+                key_desc_and_indices,
+                relation_key_indices,
+                value_desc: desc.into_owned(),
+            })
+        }
     };
 
     let CreateSinkOptionExtracted {
+        error_policy,
         remote,
         size,
         snapshot,
@@ -1900,15 +3155,34 @@ pub fn plan_create_sink(
     // WITH SNAPSHOT defaults to true
     let with_snapshot = snapshot.unwrap_or(true);
 
+    let error_policy = match error_policy.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("retry") => SinkErrorPolicy::Retry,
+        Some("pause") => SinkErrorPolicy::Pause,
+        Some("drop") => SinkErrorPolicy::Drop,
+        Some(policy) => sql_bail!(
+            "invalid ERROR POLICY {}: must be one of 'retry', 'pause' or 'drop'",
+            policy.quoted()
+        ),
+    };
+    // The updates in a file cannot be discarded once other updates have been
+    // written after them.
+    if error_policy == SinkErrorPolicy::Drop
+        && matches!(connection_builder, StorageSinkConnectionBuilder::Object(_))
+    {
+        sql_bail!("FILE and object store sinks do not support ERROR POLICY 'drop'");
+    }
+
     Ok(Plan::CreateSink(CreateSinkPlan {
         name,
         sink: Sink {
             create_sql,
             from: from.id(),
+            projection,
             connection_builder,
             envelope,
         },
         with_snapshot,
+        error_policy,
         if_not_exists,
         host_config,
     }))
@@ -1965,7 +3239,8 @@ fn key_constraint_err(desc: &RelationDesc, user_keys: &[ColumnName]) -> PlanErro
 generate_extracted_config!(
     CsrConfigOption,
     (AvroKeyFullname, String),
-    (AvroValueFullname, String)
+    (AvroValueFullname, String),
+    (IncompatibleSchemaPolicy, String)
 );
 
 fn kafka_sink_builder(
@@ -1990,10 +3265,17 @@ fn kafka_sink_builder(
     if with_options
         .iter()
         .any(|mz_sql_parser::ast::KafkaConfigOption { name, .. }| {
-            !matches!(name, KafkaConfigOptionName::Topic)
+            !matches!(
+                name,
+                KafkaConfigOptionName::Topic
+                    | KafkaConfigOptionName::PartitionStrategy
+                    | KafkaConfigOptionName::ConsistencyTopic
+            )
         })
     {
-        scx.require_unsafe_mode("KAFKA CONNECTION options besides TOPIC")?;
+        scx.require_unsafe_mode(
+            "KAFKA CONNECTION options besides TOPIC, PARTITION STRATEGY and CONSISTENCY TOPIC",
+        )?;
     }
 
     kafka_util::validate_options_for_context(
@@ -2011,9 +3293,11 @@ fn kafka_sink_builder(
     let KafkaConfigOptionExtracted {
         topic,
         partition_count,
+        partition_strategy,
         replication_factor,
         retention_ms,
         retention_bytes,
+        consistency_topic,
         ..
     } = extracted_options;
 
@@ -2053,9 +3337,14 @@ fn kafka_sink_builder(
             let CsrConfigOptionExtracted {
                 avro_key_fullname,
                 avro_value_fullname,
+                incompatible_schema_policy,
                 ..
             } = options.try_into()?;
 
+            if incompatible_schema_policy.is_some() {
+                sql_bail!("INCOMPATIBLE SCHEMA POLICY is not supported for sinks");
+            }
+
             if key_desc_and_indices.is_none() && avro_key_fullname.is_some() {
                 sql_bail!("Cannot specify AVRO KEY FULLNAME without a corresponding KEY field");
             }
@@ -2087,16 +3376,44 @@ fn kafka_sink_builder(
             }
         }
         Some(Format::Json) => KafkaSinkFormat::Json,
+        Some(Format::Csv {
+            columns,
+            delimiter,
+            quote,
+            null,
+        }) => {
+            // A CSV record has no way to nest the before and after states of
+            // an update.
+            if envelope == SinkEnvelope::Debezium {
+                sql_bail!("CSV sinks do not support ENVELOPE DEBEZIUM");
+            }
+            KafkaSinkFormat::Csv(plan_csv_sink_format(columns, delimiter, quote, null, None)?)
+        }
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
 
+    let progress_topic = connection.progress_topic.clone().unwrap_or_else(|| {
+        scx.catalog
+            .config()
+            .default_kafka_sink_progress_topic(connection_id)
+    });
+
+    if let Some(consistency_topic) = &consistency_topic {
+        if *consistency_topic == topic_name {
+            sql_bail!("CONSISTENCY TOPIC must differ from TOPIC");
+        }
+        // The progress topic holds records that only the sink understands.
+        if *consistency_topic == progress_topic {
+            sql_bail!(
+                "CONSISTENCY TOPIC must differ from the progress topic of connection {}",
+                item.name()
+            );
+        }
+    }
+
     let consistency_config = KafkaConsistencyConfig::Progress {
-        topic: connection.progress_topic.clone().unwrap_or_else(|| {
-            scx.catalog
-                .config()
-                .default_kafka_sink_progress_topic(connection_id)
-        }),
+        topic: progress_topic,
     };
 
     if partition_count == 0 || partition_count < -1 {
@@ -2105,6 +3422,21 @@ fn kafka_sink_builder(
         );
     }
 
+    let partition_strategy = match partition_strategy.map(|s| s.to_lowercase()).as_deref() {
+        None | Some("hash") => KafkaSinkPartitionStrategy::Hash,
+        Some("murmur2") => {
+            if key_desc_and_indices.is_none() {
+                sql_bail!("PARTITION STRATEGY 'murmur2' requires a KEY");
+            }
+            KafkaSinkPartitionStrategy::Murmur2
+        }
+        Some("round_robin") => KafkaSinkPartitionStrategy::RoundRobin,
+        Some(strategy) => sql_bail!(
+            "invalid PARTITION STRATEGY {}: must be one of 'hash', 'murmur2' or 'round_robin'",
+            strategy.quoted()
+        ),
+    };
+
     if replication_factor == 0 || replication_factor < -1 {
         sql_bail!(
             "REPLICATION FACTOR for sink topics must be a positive integer or -1 for broker default"
@@ -2131,6 +3463,7 @@ fn kafka_sink_builder(
             format,
             topic_name,
             consistency_config,
+            consistency_topic,
             partition_count,
             replication_factor,
             fuel: 10000,
@@ -2138,10 +3471,106 @@ fn kafka_sink_builder(
             key_desc_and_indices,
             value_desc,
             retention,
+            partition_strategy,
         },
     ))
 }
 
+generate_extracted_config!(
+    ObjectSinkOption,
+    (Bucket, String),
+    (Container, String),
+    (MaxFileSize, u64, Default(64 << 20)),
+    (Prefix, String),
+    (RotationInterval, Interval)
+);
+
+generate_extracted_config!(
+    PostgresSinkOption,
+    (Schema, String, Default("public".to_string())),
+    (Table, String)
+);
+
+generate_extracted_config!(ElasticsearchSinkOption, (Index, String));
+
+generate_extracted_config!(RedisSinkOption, (KeyPrefix, String, Default(String::new())));
+
+generate_extracted_config!(
+    NatsSinkOption,
+    (DeduplicationHeaders, bool, Default(true)),
+    (Stream, String),
+    (Subject, String)
+);
+
+/// Reports whether `index` is a valid name for an Elasticsearch index: a
+/// non-empty, lowercase name of at most 255 bytes that is not `.` or `..`,
+/// does not start with `-`, `_` or `+`, and contains none of the characters
+/// that Elasticsearch reserves.
+fn is_valid_elasticsearch_index(index: &str) -> bool {
+    !index.is_empty()
+        && index.len() <= 255
+        && index != "."
+        && index != ".."
+        && !index.starts_with(['-', '_', '+'])
+        && !index.chars().any(|c| {
+            c.is_uppercase()
+                || matches!(
+                    c,
+                    '\\' | '/' | '*' | '?' | '"' | '<' | '>' | '|' | ' ' | ',' | '#' | ':'
+                )
+        })
+}
+
+/// How long the files of an object sink stay open if the sink does not specify
+/// a ROTATION INTERVAL.
+const DEFAULT_OBJECT_SINK_ROTATION_INTERVAL: Duration = Duration::from_secs(60);
+
+fn object_sink_builder(
+    store: ObjectStoreConnection,
+    options: ObjectSinkOptionExtracted,
+    format: Option<Format<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    value_desc: RelationDesc,
+) -> Result<StorageSinkConnectionBuilder, PlanError> {
+    let format = match format {
+        Some(Format::Json) => ObjectSinkFormat::Json,
+        Some(Format::Csv {
+            columns,
+            delimiter,
+            quote,
+            null,
+        }) => ObjectSinkFormat::Csv(plan_csv_sink_format(
+            columns,
+            delimiter,
+            quote,
+            null,
+            Some(&value_desc),
+        )?),
+        Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
+        None => bail_unsupported!("sink without format"),
+    };
+
+    if options.max_file_size == 0 {
+        sql_bail!("MAX FILE SIZE must be greater than zero");
+    }
+    let rotation_interval = match options.rotation_interval {
+        Some(interval) => interval.duration()?,
+        None => DEFAULT_OBJECT_SINK_ROTATION_INTERVAL,
+    };
+    if rotation_interval.is_zero() {
+        sql_bail!("ROTATION INTERVAL must be greater than zero");
+    }
+
+    Ok(StorageSinkConnectionBuilder::Object(ObjectSinkConnection {
+        store,
+        relation_key_indices,
+        value_desc,
+        format,
+        max_file_size: options.max_file_size,
+        rotation_interval,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -2939,6 +4368,319 @@ impl PostgresConnectionOptionExtracted {
     }
 }
 
+generate_extracted_config!(
+    MySqlConnectionOption,
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16, Default(3306_u16)),
+    (SslCertificateAuthority, StringOrSecret),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl MySqlConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::MySqlConnection, PlanError> {
+        let tls_mode = match self.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => MySqlSslMode::Disabled,
+            Some("required") => MySqlSslMode::Required,
+            Some("verify_ca") | Some("verify-ca") => MySqlSslMode::VerifyCa,
+            Some("verify_identity") | Some("verify-identity") => MySqlSslMode::VerifyIdentity,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+        if tls_mode == MySqlSslMode::Disabled && self.ssl_certificate_authority.is_some() {
+            sql_bail!("invalid CONNECTION: SSL CERTIFICATE AUTHORITY requires an SSL MODE");
+        }
+
+        Ok(mz_storage_client::types::connections::MySqlConnection {
+            host: self
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            port: self.port,
+            user: self
+                .user
+                .ok_or_else(|| sql_err!("USER option is required"))?,
+            password: self.password.map(|password| password.into()),
+            tls_mode,
+            tls_root_cert: self.ssl_certificate_authority,
+        })
+    }
+}
+
+generate_extracted_config!(
+    MqttConnectionOption,
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16),
+    (SslCertificateAuthority, StringOrSecret),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl MqttConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::MqttConnection, PlanError> {
+        let tls = match self.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => false,
+            Some("verify_identity") | Some("verify-identity") => true,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+        if !tls && self.ssl_certificate_authority.is_some() {
+            sql_bail!("invalid CONNECTION: SSL CERTIFICATE AUTHORITY requires an SSL MODE");
+        }
+        if self.user.is_none() && self.password.is_some() {
+            sql_bail!("invalid CONNECTION: PASSWORD requires USER");
+        }
+
+        Ok(mz_storage_client::types::connections::MqttConnection {
+            host: self
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            // Brokers conventionally listen for TLS connections on 8883.
+            port: self.port.unwrap_or(if tls { 8883 } else { 1883 }),
+            user: self.user,
+            password: self.password.map(|password| password.into()),
+            tls,
+            tls_root_cert: self.ssl_certificate_authority,
+        })
+    }
+}
+
+generate_extracted_config!(
+    ElasticsearchConnectionOption,
+    (Password, with_options::Secret),
+    (SslCertificateAuthority, StringOrSecret),
+    (Url, String),
+    (User, StringOrSecret)
+);
+
+impl ElasticsearchConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::ElasticsearchConnection, PlanError> {
+        let url: reqwest::Url = match self.url {
+            Some(url) => url
+                .parse()
+                .map_err(|e| sql_err!("parsing Elasticsearch url: {e}"))?,
+            None => sql_bail!("invalid CONNECTION: must specify URL"),
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            sql_bail!("invalid CONNECTION: URL must use http or https");
+        }
+        if url.scheme() != "https" && self.ssl_certificate_authority.is_some() {
+            sql_bail!("invalid CONNECTION: SSL CERTIFICATE AUTHORITY requires an https URL");
+        }
+        if self.user.is_none() && self.password.is_some() {
+            sql_bail!("invalid CONNECTION: PASSWORD requires USER");
+        }
+
+        Ok(
+            mz_storage_client::types::connections::ElasticsearchConnection {
+                url: url.to_string(),
+                user: self.user,
+                password: self.password.map(|password| password.into()),
+                tls_root_cert: self.ssl_certificate_authority,
+            },
+        )
+    }
+}
+
+generate_extracted_config!(
+    SqlServerConnectionOption,
+    (Database, String),
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16, Default(1433_u16)),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl SqlServerConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::SqlServerConnection, PlanError> {
+        let tls_mode = match self.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => SqlServerSslMode::Disabled,
+            Some("required") => SqlServerSslMode::Required,
+            Some("verify_identity") | Some("verify-identity") => SqlServerSslMode::VerifyIdentity,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+
+        Ok(mz_storage_client::types::connections::SqlServerConnection {
+            host: self
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            port: self.port,
+            database: self
+                .database
+                .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+            user: self
+                .user
+                .ok_or_else(|| sql_err!("USER option is required"))?,
+            password: self.password.map(|password| password.into()),
+            tls_mode,
+        })
+    }
+}
+
+generate_extracted_config!(
+    RedisConnectionOption,
+    (Database, i64, Default(0_i64)),
+    (Host, String),
+    (Password, with_options::Secret),
+    (Port, u16, Default(6379_u16)),
+    (SslMode, String),
+    (User, StringOrSecret)
+);
+
+impl RedisConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::RedisConnection, PlanError> {
+        let tls = match self.ssl_mode.as_ref().map(|m| m.as_str()) {
+            None | Some("disabled") => false,
+            Some("verify_identity") | Some("verify-identity") => true,
+            Some(m) => sql_bail!("invalid CONNECTION: unknown SSL MODE {}", m.quoted()),
+        };
+        if self.database < 0 {
+            sql_bail!("invalid CONNECTION: DATABASE must be non-negative");
+        }
+
+        Ok(mz_storage_client::types::connections::RedisConnection {
+            host: self
+                .host
+                .ok_or_else(|| sql_err!("HOST option is required"))?,
+            port: self.port,
+            database: self.database,
+            // Unlike most other services, Redis accepts a password without a
+            // user, which authenticates as the default user.
+            user: self.user,
+            password: self.password.map(|password| password.into()),
+            tls,
+        })
+    }
+}
+
+generate_extracted_config!(
+    NatsConnectionOption,
+    (Credentials, with_options::Secret),
+    (Password, with_options::Secret),
+    (Token, with_options::Secret),
+    (Url, String),
+    (User, StringOrSecret)
+);
+
+impl NatsConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::NatsConnection, PlanError> {
+        if self.password.is_some() && self.user.is_none() {
+            sql_bail!("invalid CONNECTION: PASSWORD requires USER");
+        }
+        let methods = [
+            self.user.is_some(),
+            self.token.is_some(),
+            self.credentials.is_some(),
+        ];
+        if methods.into_iter().filter(|m| *m).count() > 1 {
+            sql_bail!("invalid CONNECTION: at most one of USER, TOKEN or CREDENTIALS may be set");
+        }
+
+        Ok(mz_storage_client::types::connections::NatsConnection {
+            url: self.url.ok_or_else(|| sql_err!("URL option is required"))?,
+            user: self.user,
+            password: self.password.map(|password| password.into()),
+            token: self.token.map(|token| token.into()),
+            credentials: self.credentials.map(|credentials| credentials.into()),
+        })
+    }
+}
+
+generate_extracted_config!(
+    GcpConnectionOption,
+    (Credentials, with_options::Secret),
+    (Endpoint, String),
+    (Project, String)
+);
+
+impl GcpConnectionOptionExtracted {
+    fn to_connection(
+        self,
+    ) -> Result<mz_storage_client::types::connections::gcp::GcpConnection, PlanError> {
+        let endpoint = match self.endpoint {
+            Some(endpoint) => {
+                let endpoint = http::Uri::from_str(&endpoint)
+                    .map_err(|e| PlanError::Unstructured(e.to_string()))?;
+                Some(SerdeUri(endpoint))
+            }
+            None => None,
+        };
+        // Only the Pub/Sub emulator accepts unauthenticated requests.
+        if self.credentials.is_none() && endpoint.is_none() {
+            sql_bail!("invalid CONNECTION: CREDENTIALS option is required");
+        }
+
+        Ok(mz_storage_client::types::connections::gcp::GcpConnection {
+            project: self
+                .project
+                .ok_or_else(|| sql_err!("PROJECT option is required"))?,
+            credentials: self.credentials.map(|credentials| credentials.into()),
+            endpoint,
+        })
+    }
+}
+
+generate_extracted_config!(
+    AzureBlobConnectionOption,
+    (AccountKey, with_options::Secret),
+    (AccountName, String),
+    (Endpoint, String),
+    (SasToken, with_options::Secret)
+);
+
+impl AzureBlobConnectionOptionExtracted {
+    fn to_connection(self) -> Result<AzureBlobConnection, PlanError> {
+        let account = self
+            .account_name
+            .ok_or_else(|| sql_err!("ACCOUNT NAME option is required"))?;
+        if !is_valid_azure_storage_account(&account) {
+            sql_bail!("invalid Azure storage account name {}", account.quoted());
+        }
+        let credentials = match (self.account_key, self.sas_token) {
+            (Some(key), None) => AzureBlobCredentials::AccountKey(key.into()),
+            (None, Some(token)) => AzureBlobCredentials::SasToken(token.into()),
+            _ => sql_bail!(
+                "invalid CONNECTION: exactly one of ACCOUNT KEY or SAS TOKEN must be specified"
+            ),
+        };
+        let endpoint = match self.endpoint {
+            Some(endpoint) => {
+                let endpoint = http::Uri::from_str(&endpoint)
+                    .map_err(|e| PlanError::Unstructured(e.to_string()))?;
+                Some(SerdeUri(endpoint))
+            }
+            None => None,
+        };
+
+        Ok(AzureBlobConnection {
+            account,
+            credentials,
+            endpoint,
+        })
+    }
+}
+
+/// Reports whether `account` is a valid name for an Azure storage account:
+/// between 3 and 24 lowercase letters and digits.
+fn is_valid_azure_storage_account(account: &str) -> bool {
+    (3..=24).contains(&account.len())
+        && account
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
 generate_extracted_config!(
     SshConnectionOption,
     (Host, String),
@@ -3052,6 +4794,38 @@ pub fn plan_create_connection(
             let c = PostgresConnectionOptionExtracted::try_from(with_options)?;
             Connection::Postgres(c.to_connection(scx)?)
         }
+        CreateConnection::MySql { with_options } => {
+            let c = MySqlConnectionOptionExtracted::try_from(with_options)?;
+            Connection::MySql(c.to_connection()?)
+        }
+        CreateConnection::Mqtt { with_options } => {
+            let c = MqttConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Mqtt(c.to_connection()?)
+        }
+        CreateConnection::Elasticsearch { with_options } => {
+            let c = ElasticsearchConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Elasticsearch(c.to_connection()?)
+        }
+        CreateConnection::SqlServer { with_options } => {
+            let c = SqlServerConnectionOptionExtracted::try_from(with_options)?;
+            Connection::SqlServer(c.to_connection()?)
+        }
+        CreateConnection::Redis { with_options } => {
+            let c = RedisConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Redis(c.to_connection()?)
+        }
+        CreateConnection::Nats { with_options } => {
+            let c = NatsConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Nats(c.to_connection()?)
+        }
+        CreateConnection::Gcp { with_options } => {
+            let c = GcpConnectionOptionExtracted::try_from(with_options)?;
+            Connection::Gcp(c.to_connection()?)
+        }
+        CreateConnection::AzureBlob { with_options } => {
+            let c = AzureBlobConnectionOptionExtracted::try_from(with_options)?;
+            Connection::AzureBlob(c.to_connection()?)
+        }
         CreateConnection::Aws { with_options } => {
             let c = AwsConnectionOptionExtracted::try_from(with_options)?;
             let connection = AwsConfig::try_from(c)?;
@@ -3702,6 +5476,7 @@ pub fn plan_alter_sink(
     match action {
         AlterSinkAction::SetOptions(options) => {
             let CreateSinkOptionExtracted {
+                error_policy,
                 remote: remote_opt,
                 size: size_opt,
                 snapshot,
@@ -3717,6 +5492,9 @@ pub fn plan_alter_sink(
             if let Some(_) = snapshot {
                 sql_bail!("Cannot modify the SNAPSHOT of a SINK.");
             }
+            if let Some(_) = error_policy {
+                sql_bail!("Cannot modify the ERROR POLICY of a SINK.");
+            }
         }
         AlterSinkAction::ResetOptions(reset) => {
             for name in reset {
@@ -3730,6 +5508,9 @@ pub fn plan_alter_sink(
                     CreateSinkOptionName::Snapshot => {
                         sql_bail!("Cannot modify the SNAPSHOT of a SINK.");
                     }
+                    CreateSinkOptionName::ErrorPolicy => {
+                        sql_bail!("Cannot modify the ERROR POLICY of a SINK.");
+                    }
                 }
             }
         }
@@ -3792,6 +5573,8 @@ pub fn plan_alter_source(
                 timeline: timeline_opt,
                 timestamp_interval: timestamp_interval_opt,
                 ignore_keys: ignore_keys_opt,
+                max_bytes_in_flight: max_bytes_in_flight_opt,
+                max_messages_per_second: max_messages_per_second_opt,
             } = CreateSourceOptionExtracted::try_from(options)?;
 
             if let Some(value) = remote_opt {
@@ -3809,6 +5592,12 @@ pub fn plan_alter_source(
             if let Some(_) = ignore_keys_opt {
                 sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
             }
+            if let Some(_) = max_bytes_in_flight_opt {
+                sql_bail!("Cannot modify the MAX BYTES IN FLIGHT of a SOURCE.");
+            }
+            if let Some(_) = max_messages_per_second_opt {
+                sql_bail!("Cannot modify the MAX MESSAGES PER SECOND of a SOURCE.");
+            }
         }
         AlterSourceAction::ResetOptions(reset) => {
             for name in reset {
@@ -3828,6 +5617,12 @@ pub fn plan_alter_source(
                     CreateSourceOptionName::IgnoreKeys => {
                         sql_bail!("Cannot modify the IGNORE KEYS property of a SOURCE.");
                     }
+                    CreateSourceOptionName::MaxBytesInFlight => {
+                        sql_bail!("Cannot modify the MAX BYTES IN FLIGHT of a SOURCE.");
+                    }
+                    CreateSourceOptionName::MaxMessagesPerSecond => {
+                        sql_bail!("Cannot modify the MAX MESSAGES PER SECOND of a SOURCE.");
+                    }
                 }
             }
         }
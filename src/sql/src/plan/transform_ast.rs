@@ -15,14 +15,17 @@
 
 use uuid::Uuid;
 
-use crate::names::Aug;
+use crate::names::{Aug, ResolvedDataType};
+use mz_expr::custom_aggregate::CustomAggregateHandle;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    Expr, Function, FunctionArgs, Ident, Op, OrderByExpr, Query, Select, SelectItem, TableAlias,
-    TableFactor, TableFunction, TableWithJoins, UnresolvedObjectName,
+    Expr, Function, FunctionArgs, Ident, IsExprConstruct, Op, OrderByExpr, Query, Select,
+    SelectItem, TableAlias, TableFactor, TableFunction, TableWithJoins, UnresolvedObjectName,
+    Value,
 };
 
+use crate::func;
 use crate::normalize;
 use crate::plan::{PlanError, StatementContext};
 
@@ -73,6 +76,9 @@ where
 //   * Rewrites the suite of standard deviation and variance functions in a
 //     manner similar to `avg`.
 //
+//   * Rewrites the covariance and correlation functions in a manner similar
+//     to the variance functions.
+//
 // TODO(sploiselle): rewrite these in terms of func::sql_op!
 struct FuncRewriter<'a> {
     scx: &'a StatementContext<'a>,
@@ -114,6 +120,7 @@ impl<'a> FuncRewriter<'a> {
             filter,
             over: None,
             distinct,
+            within_group: vec![],
         })
     }
 
@@ -199,15 +206,161 @@ impl<'a> FuncRewriter<'a> {
         Self::plan_variance(expr, filter, distinct, sample).call_unary(vec!["sqrt"])
     }
 
+    fn plan_approx_count_distinct(
+        expr: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+    ) -> Expr<Aug> {
+        // Rather than retaining every distinct value, as count(DISTINCT x)
+        // must, each value is turned into a HyperLogLog sketch and the sketches
+        // are merged by a hierarchical reduction. The estimate is computed
+        // from the merged sketch:
+        //
+        //     mz_hll_estimate(mz_hll_union(mz_hll_sketch(x)))
+        //
+        let sketch = expr.call_unary(vec!["mz_internal", "mz_hll_sketch"]);
+        Self::plan_agg(
+            UnresolvedObjectName::qualified(&["mz_internal", "mz_hll_union"]),
+            sketch,
+            vec![],
+            filter,
+            distinct,
+        )
+        .call_unary(vec!["mz_internal", "mz_hll_estimate"])
+    }
+
+    fn plan_arg_max(
+        value: Expr<Aug>,
+        key: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        max: bool,
+    ) -> Expr<Aug> {
+        // Records are compared field by field, so the greatest (least) record
+        // whose first field is the key holds the value associated with the
+        // greatest (least) key. Unlike a self-join with max(key), this is a
+        // hierarchical reduction that only retains the winning records. The
+        // code below converts arg_max(value, key) into
+        //
+        //     (mz_max_record(CASE WHEN key IS NULL THEN NULL ELSE ROW(key, value) END)).f2
+        //
+        // Ties between equal keys are broken by the values.
+        let record = Expr::Case {
+            operand: None,
+            conditions: vec![Expr::IsExpr {
+                expr: Box::new(key.clone()),
+                construct: IsExprConstruct::Null,
+                negated: false,
+            }],
+            results: vec![Expr::null()],
+            else_result: Some(Box::new(Expr::Row {
+                exprs: vec![key, value],
+            })),
+        };
+        let name = if max {
+            "mz_max_record"
+        } else {
+            "mz_min_record"
+        };
+        Expr::FieldAccess {
+            expr: Box::new(Self::plan_agg(
+                UnresolvedObjectName::qualified(&["mz_internal", name]),
+                record,
+                vec![],
+                filter,
+                distinct,
+            )),
+            field: Ident::new("f2"),
+        }
+    }
+
+    fn plan_covariance(
+        y: Expr<Aug>,
+        x: Expr<Aug>,
+        float8: ResolvedDataType,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        kind: CovarianceKind,
+    ) -> Expr<Aug> {
+        // Like the variance calculation above, this uses the "textbook"
+        // algorithm so that it can be decomposed into sums and counts.
+        //
+        // Only the rows in which both `y` and `x` are non-null contribute to
+        // the result, so each input is nulled out when the other is null. The
+        // code below then converts covar_samp(y, x) into
+        //
+        //     (sum(x*y) - sum(x) * sum(y) / count(x)) / (count(x) - 1)
+        //
+        // covar_pop(y, x) into
+        //
+        //     (sum(x*y) - sum(x) * sum(y) / count(x)) / count(x)
+        //
+        // and corr(y, x) into
+        //
+        //     (sum(x*y) - sum(x) * sum(y) / count(x))
+        //         / sqrt((sum(x²) - sum(x)² / count(x)) * (sum(y²) - sum(y)² / count(x)))
+        //
+        let null_if_null = |expr: Expr<Aug>, other: Expr<Aug>| Expr::Case {
+            operand: None,
+            conditions: vec![Expr::IsExpr {
+                expr: Box::new(other),
+                construct: IsExprConstruct::Null,
+                negated: false,
+            }],
+            results: vec![Expr::null()],
+            else_result: Some(Box::new(Expr::Cast {
+                expr: Box::new(expr),
+                data_type: float8.clone(),
+            })),
+        };
+        let (y, x) = (null_if_null(y.clone(), x.clone()), null_if_null(x, y));
+        let sum = |expr: Expr<Aug>| {
+            Self::plan_agg(
+                UnresolvedObjectName::qualified(&["pg_catalog", "sum"]),
+                expr,
+                vec![],
+                filter.clone(),
+                distinct,
+            )
+        };
+        let count = Self::plan_agg(
+            UnresolvedObjectName::qualified(&["pg_catalog", "count"]),
+            x.clone(),
+            vec![],
+            filter.clone(),
+            distinct,
+        );
+        // The sum of the products of the deviations of `a` and `b` from their
+        // means.
+        let sum_of_products = |a: Expr<Aug>, b: Expr<Aug>| {
+            sum(a.clone().multiply(b.clone()))
+                .minus(Self::plan_divide(sum(a).multiply(sum(b)), count.clone()))
+        };
+        match kind {
+            CovarianceKind::Population => Self::plan_divide(sum_of_products(x, y), count),
+            CovarianceKind::Sample => Self::plan_divide(
+                sum_of_products(x, y),
+                count.clone().minus(Expr::number("1")),
+            ),
+            CovarianceKind::Correlation => Self::plan_divide(
+                sum_of_products(x.clone(), y.clone()),
+                sum_of_products(x.clone(), x)
+                    .multiply(sum_of_products(y.clone(), y))
+                    .call_unary(vec!["sqrt"]),
+            ),
+        }
+    }
+
     fn rewrite_expr(&mut self, expr: &Expr<Aug>) -> Option<(Ident, Expr<Aug>)> {
         match expr {
             Expr::Function(Function {
                 name,
-                args: FunctionArgs::Args { args, order_by: _ },
+                args: FunctionArgs::Args { args, order_by },
                 filter,
                 distinct,
                 over: None,
-            }) => {
+                within_group,
+            }) if within_group.is_empty() => {
                 let name = normalize::unresolved_object_name(name.clone()).ok()?;
                 if let Some(database) = &name.database {
                     // If a database name is provided, we need only verify that
@@ -230,13 +383,82 @@ impl<'a> FuncRewriter<'a> {
                         "var_pop" => Self::plan_variance(arg, filter, distinct, false),
                         "stddev" | "stddev_samp" => Self::plan_stddev(arg, filter, distinct, true),
                         "stddev_pop" => Self::plan_stddev(arg, filter, distinct, false),
+                        "approx_count_distinct" => {
+                            Self::plan_approx_count_distinct(arg, filter, distinct)
+                        }
+                        // Without an OVER clause, these are aggregates that
+                        // pick the value of the first or last row according
+                        // to the ORDER BY clause in their arguments.
+                        "first_value" | "last_value" => Self::plan_agg(
+                            UnresolvedObjectName::qualified(&[
+                                "mz_internal",
+                                &format!("mz_{}", name.item),
+                            ]),
+                            arg,
+                            order_by.clone(),
+                            filter,
+                            distinct,
+                        ),
+                        "median" => Expr::Function(Function {
+                            name: UnresolvedObjectName::qualified(&[
+                                "mz_catalog",
+                                "percentile_cont",
+                            ]),
+                            args: FunctionArgs::args(vec![Expr::number("0.5")]),
+                            filter,
+                            over: None,
+                            distinct,
+                            within_group: vec![OrderByExpr {
+                                expr: arg,
+                                asc: None,
+                                nulls_last: None,
+                            }],
+                        }),
+                        // Aggregates registered by embedders are called by
+                        // name through `mz_custom_aggregate`. They never
+                        // shadow builtin functions.
+                        item if !func::is_builtin_function(item)
+                            && CustomAggregateHandle::resolve(item).is_ok() =>
+                        {
+                            Expr::Function(Function {
+                                name: UnresolvedObjectName::qualified(&[
+                                    "mz_internal",
+                                    "mz_custom_aggregate",
+                                ]),
+                                args: FunctionArgs::Args {
+                                    args: vec![Expr::Value(Value::String(item.into())), arg],
+                                    order_by: vec![],
+                                },
+                                filter,
+                                over: None,
+                                distinct,
+                                within_group: vec![],
+                            })
+                        }
                         _ => return None,
                     }
                 } else if args.len() == 2 {
                     let (lhs, rhs) = (args[0].clone(), args[1].clone());
                     match name.item.as_str() {
                         "mod" => lhs.modulo(rhs),
+                        "arg_max" => Self::plan_arg_max(lhs, rhs, filter, distinct, true),
+                        "arg_min" => Self::plan_arg_max(lhs, rhs, filter, distinct, false),
                         "pow" => Expr::call(vec!["pg_catalog", "power"], vec![lhs, rhs]),
+                        "covar_pop" | "covar_samp" | "corr" => {
+                            let float8 = match self.scx.resolve_type(mz_pgrepr::Type::Float8) {
+                                Ok(float8) => float8,
+                                Err(e) => {
+                                    self.status = Err(e);
+                                    return None;
+                                }
+                            };
+                            let kind = match name.item.as_str() {
+                                "covar_pop" => CovarianceKind::Population,
+                                "covar_samp" => CovarianceKind::Sample,
+                                _ => CovarianceKind::Correlation,
+                            };
+                            Self::plan_covariance(lhs, rhs, float8, filter, distinct, kind)
+                        }
                         _ => return None,
                     }
                 } else {
@@ -269,6 +491,17 @@ impl<'a> FuncRewriter<'a> {
     }
 }
 
+/// The statistic computed by [`FuncRewriter::plan_covariance`].
+#[derive(Clone, Copy)]
+enum CovarianceKind {
+    /// The population covariance, `covar_pop`.
+    Population,
+    /// The sample covariance, `covar_samp`.
+    Sample,
+    /// The correlation coefficient, `corr`.
+    Correlation,
+}
+
 impl<'ast> VisitMut<'ast, Aug> for FuncRewriter<'_> {
     fn visit_select_item_mut(&mut self, item: &'ast mut SelectItem<Aug>) {
         if let SelectItem::Expr { expr, alias: None } = item {
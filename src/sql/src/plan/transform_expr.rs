@@ -11,6 +11,7 @@
 
 use std::collections::BTreeMap;
 use std::mem;
+use std::sync::Arc;
 
 use once_cell::sync::Lazy;
 
@@ -156,32 +157,32 @@ pub fn split_subquery_predicates(expr: &mut HirRelationExpr) {
 /// See Section 3.5 of "Execution Strategies for SQL Subqueries" by
 /// M. Elhemali, et al.
 pub fn try_simplify_quantified_comparisons(expr: &mut HirRelationExpr) {
-    fn walk_relation(expr: &mut HirRelationExpr, outers: &[RelationType]) {
+    fn walk_relation(expr: &mut HirRelationExpr, outers: &[Arc<RelationType>]) {
         match expr {
             HirRelationExpr::Map { scalars, input } => {
                 walk_relation(input, outers);
                 let mut outers = outers.to_vec();
-                outers.insert(0, input.typ(&outers, &NO_PARAMS));
+                outers.insert(0, Arc::new(input.typ(&outers, &NO_PARAMS)));
                 for scalar in scalars {
                     walk_scalar(scalar, &outers, false);
                     let (inner, outers) = outers
                         .split_first_mut()
                         .expect("outers known to have at least one element");
                     let scalar_type = scalar.typ(outers, inner, &NO_PARAMS);
-                    inner.column_types.push(scalar_type);
+                    Arc::make_mut(inner).column_types.push(scalar_type);
                 }
             }
             HirRelationExpr::Filter { predicates, input } => {
                 walk_relation(input, outers);
                 let mut outers = outers.to_vec();
-                outers.insert(0, input.typ(&outers, &NO_PARAMS));
+                outers.insert(0, Arc::new(input.typ(&outers, &NO_PARAMS)));
                 for pred in predicates {
                     walk_scalar(pred, &outers, true);
                 }
             }
             HirRelationExpr::CallTable { exprs, .. } => {
                 let mut outers = outers.to_vec();
-                outers.insert(0, RelationType::empty());
+                outers.insert(0, Arc::new(RelationType::empty()));
                 for scalar in exprs {
                     walk_scalar(scalar, &outers, false);
                 }
@@ -189,7 +190,7 @@ pub fn try_simplify_quantified_comparisons(expr: &mut HirRelationExpr) {
             HirRelationExpr::Join { left, right, .. } => {
                 walk_relation(left, outers);
                 let mut outers = outers.to_vec();
-                outers.insert(0, left.typ(&outers, &NO_PARAMS));
+                outers.insert(0, Arc::new(left.typ(&outers, &NO_PARAMS)));
                 walk_relation(right, &outers);
             }
             expr => {
@@ -202,7 +203,7 @@ pub fn try_simplify_quantified_comparisons(expr: &mut HirRelationExpr) {
         }
     }
 
-    fn walk_scalar(expr: &mut HirScalarExpr, outers: &[RelationType], mut in_filter: bool) {
+    fn walk_scalar(expr: &mut HirScalarExpr, outers: &[Arc<RelationType>], mut in_filter: bool) {
         #[allow(deprecated)]
         expr.visit_mut_pre(&mut |e| match e {
             HirScalarExpr::Exists(input) => walk_relation(input, outers),
@@ -221,7 +222,10 @@ pub fn try_simplify_quantified_comparisons(expr: &mut HirRelationExpr) {
                         aggregates,
                         input,
                         expected_group_size: _,
-                    } if group_key.is_empty() && aggregates.len() == 1 => {
+                    } if group_key.is_empty()
+                        && aggregates.len() == 1
+                        && aggregates[0].filter.is_none() =>
+                    {
                         let agg = &mut aggregates[0];
                         (&agg.func, &mut agg.expr, input)
                     }
@@ -274,7 +278,7 @@ pub fn try_simplify_quantified_comparisons(expr: &mut HirRelationExpr) {
 static NO_PARAMS: Lazy<BTreeMap<usize, ScalarType>> = Lazy::new(BTreeMap::new);
 
 fn column_type(
-    outers: &[RelationType],
+    outers: &[Arc<RelationType>],
     inner: &HirRelationExpr,
     expr: &HirScalarExpr,
 ) -> ColumnType {
@@ -456,6 +456,8 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         (String, Bytes) => Explicit: CastStringToBytes(func::CastStringToBytes),
         (String, Jsonb) => Explicit: CastStringToJsonb(func::CastStringToJsonb),
         (String, Uuid) => Explicit: CastStringToUuid(func::CastStringToUuid),
+        (String, Inet) => Explicit: CastStringToInet(func::CastStringToInet),
+        (String, Cidr) => Explicit: CastStringToCidr(func::CastStringToCidr),
         (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
             let return_ty = to_type.clone();
             let to_el_type = to_type.unwrap_array_element_type();
@@ -623,6 +625,14 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
         // UUID
         (Uuid, String) => Assignment: CastUuidToString(func::CastUuidToString),
 
+        // INET
+        (Inet, String) => Assignment: CastInetToString(func::CastInetToString),
+        (Inet, Cidr) => Assignment: CastInetToCidr(func::CastInetToCidr),
+
+        // CIDR
+        (Cidr, String) => Assignment: CastCidrToString(func::CastCidrToString),
+        (Cidr, Inet) => Implicit: CastCidrToInet(func::CastCidrToInet),
+
         // Numeric
         (Numeric, Numeric) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
             let scale = to_type.unwrap_numeric_max_scale();
@@ -649,6 +659,26 @@ static VALID_CASTS: Lazy<HashMap<(ScalarBaseType, ScalarBaseType), CastImpl>> =
     }
 });
 
+/// Reports the [`CastContext`] required to cast directly from values of base
+/// type `from` to values of base type `to`, or `None` if no such cast exists.
+///
+/// Casts that are valid in a given context are also valid in any more
+/// permissive context, e.g. an `Implicit` cast may also be applied explicitly.
+/// Note that the cast templates for some base types, like lists, may still
+/// reject specific pairs of concrete types; use [`can_cast`] to determine
+/// whether a cast between two [`ScalarType`]s is possible.
+pub fn get_cast_context(from: ScalarBaseType, to: ScalarBaseType) -> Option<CastContext> {
+    VALID_CASTS.get(&(from, to)).map(|imp| imp.context)
+}
+
+/// Returns the matrix of direct casts between base types, as
+/// `(from, to, context)` triples in no particular order.
+pub fn valid_casts() -> impl Iterator<Item = (ScalarBaseType, ScalarBaseType, CastContext)> {
+    VALID_CASTS
+        .iter()
+        .map(|((from, to), imp)| (*from, *to, imp.context))
+}
+
 /// Get casts directly between two [`ScalarType`]s, with control over the
 /// allowed [`CastContext`].
 fn get_cast(
@@ -983,3 +1013,26 @@ pub fn can_cast(
     }
     get_cast(ecx, ccx, cast_from, cast_to).is_some()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_cast_context() {
+        use ScalarBaseType::*;
+
+        assert_eq!(get_cast_context(Int32, Int64), Some(CastContext::Implicit));
+        assert_eq!(
+            get_cast_context(Int64, Int32),
+            Some(CastContext::Assignment)
+        );
+        assert_eq!(get_cast_context(Jsonb, Int32), Some(CastContext::Explicit));
+        assert_eq!(get_cast_context(Bytes, Interval), None);
+
+        // Every cast in the matrix must be reported by the lookup.
+        for (from, to, ccx) in valid_casts() {
+            assert_eq!(get_cast_context(from, to), Some(ccx));
+        }
+    }
+}
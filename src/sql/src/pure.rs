@@ -36,12 +36,15 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     ColumnDef, ColumnOption, ColumnOptionDef, CsrConnection, CsrSeedAvro, CsrSeedProtobuf,
     CsrSeedProtobufSchema, DbzMode, DeferredObjectName, Envelope, Ident, KafkaConfigOption,
-    KafkaConfigOptionName, KafkaConnection, KafkaSourceConnection, PgConfigOption,
-    PgConfigOptionName, ReaderSchemaSelectionStrategy, TableConstraint, UnresolvedObjectName,
+    KafkaConfigOptionName, KafkaConnection, KafkaSourceConnection, MySqlConfigOption,
+    MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, ReaderSchemaSelectionStrategy,
+    SqlServerConfigOption, SqlServerConfigOptionName, TableConstraint, UnresolvedObjectName,
 };
 use mz_storage_client::types::connections::aws::AwsConfig;
 use mz_storage_client::types::connections::{Connection, ConnectionContext};
-use mz_storage_client::types::sources::PostgresSourcePublicationDetails;
+use mz_storage_client::types::sources::{
+    MySqlSourceDetails, PostgresSourcePublicationDetails, SqlServerSourceDetails,
+};
 
 use crate::ast::{
     AvroSchema, CreateReferencedSubsources, CreateSourceConnection, CreateSourceFormat,
@@ -51,11 +54,13 @@ use crate::ast::{
 use crate::catalog::{ErsatzCatalog, SessionCatalog};
 use crate::kafka_util;
 use crate::kafka_util::KafkaConfigOptionExtracted;
+use crate::mysql_util;
 use crate::names::{Aug, RawDatabaseSpecifier, ResolvedObjectName};
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::statement::ddl::load_generator_ast_to_generator;
 use crate::plan::StatementContext;
+use crate::sql_server_util;
 
 fn subsource_gen<'a, T>(
     selected_subsources: &mut Vec<CreateSourceSubsource<Aug>>,
@@ -205,6 +210,48 @@ pub async fn purify_create_source(
         CreateSourceConnection::TestScript { desc_json: _ } => {
             // TODO: verify valid json and valid schema
         }
+        CreateSourceConnection::File { .. } => {}
+        CreateSourceConnection::Mqtt { .. } => {}
+        CreateSourceConnection::PubSub {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &*catalog);
+            let gcp = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::Gcp(gcp) => gcp.clone(),
+                    _ => sql_bail!("{} is not a GCP connection", item.name()),
+                }
+            };
+            let crate::plan::statement::PubSubConfigOptionExtracted { subscription, .. } =
+                options.clone().try_into()?;
+            let subscription =
+                subscription.ok_or_else(|| sql_err!("SUBSCRIPTION option is required"))?;
+
+            let mut client = gcp
+                .pubsub_client(&*connection_context.secrets_reader)
+                .await?;
+            let subscription_config = match client.get_subscription(&subscription).await {
+                Ok(config) => config,
+                Err(e) => sql_bail!(
+                    "failed to fetch Pub/Sub subscription {}: {:#}",
+                    subscription.quoted(),
+                    e
+                ),
+            };
+            // Upserts are only applied in the order in which they were published
+            // if Pub/Sub delivers the messages that share a key in that order.
+            if matches!(envelope, Some(Envelope::Upsert))
+                && !subscription_config.enable_message_ordering
+            {
+                sql_bail!(
+                    "ENVELOPE UPSERT requires a Pub/Sub subscription with message ordering enabled"
+                );
+            }
+        }
+        CreateSourceConnection::Webhook { .. } => {}
+        CreateSourceConnection::Flight { .. } => {}
         CreateSourceConnection::S3 { connection, .. } => {
             let scx = StatementContext::new(None, &*catalog);
             let aws = {
@@ -483,6 +530,277 @@ pub async fn purify_create_source(
                 )))),
             })
         }
+        CreateSourceConnection::MySql {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &*catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::MySql(connection) => connection.clone(),
+                    _ => sql_bail!("{} is not a mysql connection", item.name()),
+                }
+            };
+
+            // verify that we can connect upstream, that the server is
+            // configured for replication, and snapshot the table metadata
+            let mut conn = connection
+                .connect(&*connection_context.secrets_reader)
+                .await
+                .map_err(|e| sql_err!("failed to connect to MySQL server: {}", e))?;
+            mz_storage_client::types::sources::mysql::validate_replication_settings(&mut conn)
+                .await?;
+            let tables = mz_storage_client::types::sources::mysql::table_info(&mut conn).await?;
+            let _ = conn.disconnect().await;
+
+            let mut validated_requested_subsources = vec![];
+            match requested_subsources {
+                Some(CreateReferencedSubsources::All) => {
+                    for table in &tables {
+                        let upstream_name =
+                            UnresolvedObjectName::qualified(&[&table.schema_name, &table.name]);
+                        let subsource_name = UnresolvedObjectName::unqualified(&table.name);
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                Some(CreateReferencedSubsources::Subset(subsources)) => {
+                    for subsource in subsources {
+                        let subsource_name = match &subsource.subsource {
+                            Some(DeferredObjectName::Deferred(name)) => name.clone(),
+                            Some(DeferredObjectName::Named(..)) => {
+                                sql_bail!("Cannot manually ID qualify subsources")
+                            }
+                            None => UnresolvedObjectName::unqualified(
+                                &normalize::unresolved_object_name(subsource.reference.clone())?
+                                    .item,
+                            ),
+                        };
+                        let (upstream_name, table) =
+                            mysql_util::resolve_table(&tables, subsource.reference.clone())?;
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                None => {}
+            };
+
+            // Aggregate all unrecognized types.
+            let mut unsupported_cols = vec![];
+
+            let mut targeted_subsources = vec![];
+            for (i, (upstream_name, subsource_name, table)) in
+                validated_requested_subsources.into_iter().enumerate()
+            {
+                // Figure out the schema of the subsource
+                let mut columns = vec![];
+                for c in table.columns.iter() {
+                    let name = Ident::new(c.name.clone());
+                    let ty = match mysql_util::scalar_type(&c.column_type) {
+                        Some(ty) => ty,
+                        None => {
+                            let mut full_name = upstream_name.0.clone();
+                            full_name.push(name);
+                            unsupported_cols.push((
+                                UnresolvedObjectName(full_name).to_ast_string(),
+                                c.column_type.clone(),
+                            ));
+                            continue;
+                        }
+                    };
+                    let data_type = scx.resolve_type(ty)?;
+
+                    columns.push(ColumnDef {
+                        name,
+                        data_type,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
+
+                // Create the targeted AST node for the original CREATE SOURCE statement
+                let transient_id = GlobalId::Transient(u64::cast_from(i));
+                let partial_subsource_name =
+                    normalize::unresolved_object_name(subsource_name.clone())?;
+                let qualified_subsource_name =
+                    scx.allocate_qualified_name(partial_subsource_name.clone())?;
+                let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
+                targeted_subsources.push(CreateSourceSubsource {
+                    reference: upstream_name,
+                    subsource: Some(DeferredObjectName::Named(ResolvedObjectName::Object {
+                        id: transient_id,
+                        qualifiers: qualified_subsource_name.qualifiers,
+                        full_name: full_subsource_name,
+                        print_id: true,
+                    })),
+                });
+
+                // Create the subsource statement
+                let subsource = CreateSubsourceStatement {
+                    name: subsource_name,
+                    columns,
+                    constraints: vec![],
+                    if_not_exists: false,
+                };
+                subsources.push((transient_id, subsource));
+            }
+
+            if !unsupported_cols.is_empty() {
+                return Err(PlanError::UnrecognizedTypeInMySqlSource {
+                    cols: unsupported_cols,
+                });
+            }
+
+            *requested_subsources = Some(CreateReferencedSubsources::Subset(targeted_subsources));
+
+            // Remove any old detail references
+            options
+                .retain(|MySqlConfigOption { name, .. }| name != &MySqlConfigOptionName::Details);
+            // The server ID identifies the source as a replica to the upstream
+            // server, and must not collide with any of its other replicas.
+            let server_id = Uuid::new_v4().as_u128() as u32 | 1;
+            let details = MySqlSourceDetails { tables, server_id };
+            options.push(MySqlConfigOption {
+                name: MySqlConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &*catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                match item.connection()? {
+                    Connection::SqlServer(connection) => connection.clone(),
+                    _ => sql_bail!("{} is not a sql server connection", item.name()),
+                }
+            };
+
+            // verify that we can connect upstream, that the database has
+            // change data capture enabled, and snapshot the table metadata
+            let mut client = connection
+                .connect(&*connection_context.secrets_reader)
+                .await
+                .map_err(|e| sql_err!("failed to connect to SQL Server: {}", e))?;
+            mz_storage_client::types::sources::sql_server::validate_cdc_settings(&mut client)
+                .await?;
+            let tables =
+                mz_storage_client::types::sources::sql_server::table_info(&mut client).await?;
+            let _ = client.close().await;
+
+            let mut validated_requested_subsources = vec![];
+            match requested_subsources {
+                Some(CreateReferencedSubsources::All) => {
+                    for table in &tables {
+                        let upstream_name =
+                            UnresolvedObjectName::qualified(&[&table.schema_name, &table.name]);
+                        let subsource_name = UnresolvedObjectName::unqualified(&table.name);
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                Some(CreateReferencedSubsources::Subset(subsources)) => {
+                    for subsource in subsources {
+                        let subsource_name = match &subsource.subsource {
+                            Some(DeferredObjectName::Deferred(name)) => name.clone(),
+                            Some(DeferredObjectName::Named(..)) => {
+                                sql_bail!("Cannot manually ID qualify subsources")
+                            }
+                            None => UnresolvedObjectName::unqualified(
+                                &normalize::unresolved_object_name(subsource.reference.clone())?
+                                    .item,
+                            ),
+                        };
+                        let (upstream_name, table) =
+                            sql_server_util::resolve_table(&tables, subsource.reference.clone())?;
+                        validated_requested_subsources.push((upstream_name, subsource_name, table));
+                    }
+                }
+                None => {}
+            };
+
+            // Aggregate all unrecognized types.
+            let mut unsupported_cols = vec![];
+
+            let mut targeted_subsources = vec![];
+            for (i, (upstream_name, subsource_name, table)) in
+                validated_requested_subsources.into_iter().enumerate()
+            {
+                // Figure out the schema of the subsource
+                let mut columns = vec![];
+                for c in table.columns.iter() {
+                    let name = Ident::new(c.name.clone());
+                    let ty = match sql_server_util::scalar_type(&c.data_type) {
+                        Some(ty) => ty,
+                        None => {
+                            let mut full_name = upstream_name.0.clone();
+                            full_name.push(name);
+                            unsupported_cols.push((
+                                UnresolvedObjectName(full_name).to_ast_string(),
+                                c.data_type.clone(),
+                            ));
+                            continue;
+                        }
+                    };
+                    let data_type = scx.resolve_type(ty)?;
+
+                    columns.push(ColumnDef {
+                        name,
+                        data_type,
+                        collation: None,
+                        options: vec![],
+                    });
+                }
+
+                // Create the targeted AST node for the original CREATE SOURCE statement
+                let transient_id = GlobalId::Transient(u64::cast_from(i));
+                let partial_subsource_name =
+                    normalize::unresolved_object_name(subsource_name.clone())?;
+                let qualified_subsource_name =
+                    scx.allocate_qualified_name(partial_subsource_name.clone())?;
+                let full_subsource_name = scx.allocate_full_name(partial_subsource_name)?;
+                targeted_subsources.push(CreateSourceSubsource {
+                    reference: upstream_name,
+                    subsource: Some(DeferredObjectName::Named(ResolvedObjectName::Object {
+                        id: transient_id,
+                        qualifiers: qualified_subsource_name.qualifiers,
+                        full_name: full_subsource_name,
+                        print_id: true,
+                    })),
+                });
+
+                // Create the subsource statement
+                let subsource = CreateSubsourceStatement {
+                    name: subsource_name,
+                    columns,
+                    constraints: vec![],
+                    if_not_exists: false,
+                };
+                subsources.push((transient_id, subsource));
+            }
+
+            if !unsupported_cols.is_empty() {
+                return Err(PlanError::UnrecognizedTypeInSqlServerSource {
+                    cols: unsupported_cols,
+                });
+            }
+
+            *requested_subsources = Some(CreateReferencedSubsources::Subset(targeted_subsources));
+
+            // Remove any old detail references
+            options.retain(|SqlServerConfigOption { name, .. }| {
+                name != &SqlServerConfigOptionName::Details
+            });
+            let details = SqlServerSourceDetails { tables };
+            options.push(SqlServerConfigOption {
+                name: SqlServerConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let scx = StatementContext::new(None, &*catalog);
 
@@ -698,9 +1016,11 @@ async fn purify_source_format_single(
         },
         Format::Csv {
             delimiter: _,
+            quote: _,
+            null: _,
             ref mut columns,
         } => {
-            if let CsvColumns::Header { names } = columns {
+            if let Some(CsvColumns::Header { names }) = columns {
                 match connection {
                     CreateSourceConnection::S3 { .. } => {
                         if names.is_empty() {
@@ -711,7 +1031,7 @@ async fn purify_source_format_single(
                 }
             }
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Bytes | Format::Regex { .. } | Format::Json | Format::Text => (),
     }
     Ok(())
 }
@@ -319,6 +319,10 @@ impl FromHir {
                         .add_column(select_box_col_ref);
                 }
                 for aggregate in aggregates.into_iter() {
+                    // Filtered aggregates are not representable in a grouping box.
+                    if let Some(filter) = aggregate.filter {
+                        return Err(QGMError::from(UnsupportedHirScalarExpr { scalar: *filter }));
+                    }
                     // Any computed expression passed as an argument of an aggregate
                     // function is computed by the input select box.
                     let input_expr = self.generate_expr(*aggregate.expr, select_id)?;
@@ -238,6 +238,7 @@ impl<'a> Lowerer<'a> {
                                 func: func.clone(),
                                 expr: Self::lower_expression(expr, &column_map).unwrap(),
                                 distinct: *distinct,
+                                filter: None,
                             })
                         } else {
                             None
@@ -616,6 +617,7 @@ impl<'a> Lowerer<'a> {
                             func: mz_expr::AggregateFunc::Count,
                             expr: mz_expr::MirScalarExpr::literal_ok(Datum::True, ScalarType::Bool),
                             distinct: false,
+                            filter: None,
                         }],
                         None,
                     );
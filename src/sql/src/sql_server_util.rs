@@ -0,0 +1,132 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Provides convenience functions for working with SQL Server from the `sql`
+//! package.
+
+use mz_sql_parser::ast::UnresolvedObjectName;
+use mz_storage_client::types::sources::sql_server::SqlServerTableDesc;
+
+use crate::normalize;
+use crate::plan::PlanError;
+
+/// Returns the Materialize type to which values of the SQL Server system type
+/// `data_type` are cast, or `None` if the SQL Server type is not supported.
+///
+/// `data_type` is the lowercase name of the type as reported by `sys.types`,
+/// e.g. `int` or `nvarchar`.
+pub fn scalar_type(data_type: &str) -> Option<mz_pgrepr::Type> {
+    let ty = match data_type {
+        "bit" => mz_pgrepr::Type::Bool,
+        // `tinyint` is unsigned, so only fits in a wider type.
+        "tinyint" | "smallint" => mz_pgrepr::Type::Int2,
+        "int" => mz_pgrepr::Type::Int4,
+        "bigint" => mz_pgrepr::Type::Int8,
+        "real" => mz_pgrepr::Type::Float4,
+        "float" => mz_pgrepr::Type::Float8,
+        // SQL Server's precision is at most 38, so every `decimal` fits in a
+        // Materialize `numeric`.
+        "decimal" | "numeric" | "money" | "smallmoney" => {
+            mz_pgrepr::Type::Numeric { constraints: None }
+        }
+        "char" | "varchar" | "text" | "nchar" | "nvarchar" | "ntext" | "xml" => {
+            mz_pgrepr::Type::Text
+        }
+        "binary" | "varbinary" | "image" | "timestamp" => mz_pgrepr::Type::Bytea,
+        "date" => mz_pgrepr::Type::Date,
+        "time" => mz_pgrepr::Type::Time { precision: None },
+        "datetime" | "datetime2" | "smalldatetime" => {
+            mz_pgrepr::Type::Timestamp { precision: None }
+        }
+        "datetimeoffset" => mz_pgrepr::Type::TimestampTz { precision: None },
+        "uniqueidentifier" => mz_pgrepr::Type::Uuid,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Resolves a reference to an upstream SQL Server table, which may be
+/// qualified by its schema, against the tables that have change data capture
+/// enabled.
+///
+/// Returns the schema-qualified name of the table along with its description.
+pub fn resolve_table<'a>(
+    tables: &'a [SqlServerTableDesc],
+    reference: UnresolvedObjectName,
+) -> Result<(UnresolvedObjectName, &'a SqlServerTableDesc), PlanError> {
+    let name = normalize::unresolved_object_name(reference)?;
+    if name.database.is_some() {
+        sql_bail!("SQL Server table references must be of the form schema.table, but got {name}");
+    }
+    let mut candidates = tables.iter().filter(|t| {
+        t.name == name.item
+            && match &name.schema {
+                Some(schema) => &t.schema_name == schema,
+                None => true,
+            }
+    });
+    let table = match (candidates.next(), candidates.next()) {
+        (Some(table), None) => table,
+        (None, _) => sql_bail!(
+            "table {name} not found in source, or does not have change data capture enabled"
+        ),
+        (Some(_), Some(_)) => {
+            sql_bail!("table {name} is ambiguous, consider specifying the schema")
+        }
+    };
+    Ok((
+        UnresolvedObjectName::qualified(&[&table.schema_name, &table.name]),
+        table,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_pgrepr::Type;
+
+    use super::*;
+
+    #[test]
+    fn test_scalar_type() {
+        let cases = [
+            ("bit", Some(Type::Bool)),
+            ("tinyint", Some(Type::Int2)),
+            ("smallint", Some(Type::Int2)),
+            ("int", Some(Type::Int4)),
+            ("bigint", Some(Type::Int8)),
+            ("real", Some(Type::Float4)),
+            ("float", Some(Type::Float8)),
+            ("decimal", Some(Type::Numeric { constraints: None })),
+            ("money", Some(Type::Numeric { constraints: None })),
+            ("smallmoney", Some(Type::Numeric { constraints: None })),
+            ("nvarchar", Some(Type::Text)),
+            ("ntext", Some(Type::Text)),
+            ("xml", Some(Type::Text)),
+            ("varbinary", Some(Type::Bytea)),
+            ("timestamp", Some(Type::Bytea)),
+            ("date", Some(Type::Date)),
+            ("time", Some(Type::Time { precision: None })),
+            ("datetime", Some(Type::Timestamp { precision: None })),
+            ("datetime2", Some(Type::Timestamp { precision: None })),
+            ("smalldatetime", Some(Type::Timestamp { precision: None })),
+            (
+                "datetimeoffset",
+                Some(Type::TimestampTz { precision: None }),
+            ),
+            ("uniqueidentifier", Some(Type::Uuid)),
+            ("geography", None),
+            ("hierarchyid", None),
+            ("sql_variant", None),
+            ("INT", None),
+        ];
+        for (data_type, expected) in cases {
+            assert_eq!(scalar_type(data_type), expected, "{}", data_type);
+        }
+    }
+}
@@ -70,7 +70,7 @@ use mz_ore::thread::{JoinHandleExt, JoinOnDropHandle};
 use mz_ore::tracing::TracingHandle;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistConfig, PersistLocation};
-use mz_pgrepr::{oid, Interval, Jsonb, Numeric, UInt2, UInt4, UInt8, Value};
+use mz_pgrepr::{oid, Inet, Interval, Jsonb, Numeric, UInt2, UInt4, UInt8, Value};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::numeric;
 use mz_repr::ColumnName;
@@ -388,6 +388,8 @@ impl<'a> FromSql<'a> for Slt {
                 DateTime::<Utc>::from_sql(ty, raw)?.try_into()?,
             )),
             PgType::UUID => Self(Value::Uuid(Uuid::from_sql(ty, raw)?)),
+            PgType::INET => Self(Value::Inet(Inet::from_sql(ty, raw)?)),
+            PgType::CIDR => Self(Value::Cidr(Inet::from_sql(ty, raw)?)),
             PgType::RECORD => {
                 let num_fields = read_be_i32(&mut raw)?;
                 let mut tuple = vec![];
@@ -484,6 +486,8 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::TIMESTAMP
                 | PgType::TIMESTAMPTZ
                 | PgType::UUID
+                | PgType::INET
+                | PgType::CIDR
         )
     }
 }
@@ -841,6 +845,7 @@ impl RunnerInner {
             http_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
             internal_sql_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
             internal_http_listen_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+            flight_listen_addr: None,
             tls: None,
             frontegg: None,
             cors_allowed_origin: AllowOrigin::list([]),
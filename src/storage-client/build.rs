@@ -108,10 +108,14 @@ fn main() {
                 "storage-client/src/client.proto",
                 "storage-client/src/types/errors.proto",
                 "storage-client/src/types/connections/aws.proto",
+                "storage-client/src/types/connections/azure.proto",
+                "storage-client/src/types/connections/gcp.proto",
                 "storage-client/src/types/hosts.proto",
                 "storage-client/src/types/sinks.proto",
                 "storage-client/src/types/sources.proto",
                 "storage-client/src/types/sources/encoding.proto",
+                "storage-client/src/types/sources/mysql.proto",
+                "storage-client/src/types/sources/sql_server.proto",
             ],
             &[".."],
         )
@@ -294,6 +294,19 @@ pub struct SourceStatisticsUpdate {
     pub bytes_received: u64,
 }
 
+/// This structure represents a full set up updates for the `mz_sink_statistics`
+/// table, for a specific sink-worker pair, in the same way as
+/// [`SourceStatisticsUpdate`] does for sources.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SinkStatisticsUpdate {
+    pub id: GlobalId,
+    pub worker_id: usize,
+    pub updates_dropped: u64,
+    /// The write frontier of the sink, if the worker is the one that writes
+    /// to the sink's external system and has not finished writing.
+    pub write_frontier: Option<mz_repr::Timestamp>,
+}
+
 /// Responses that the storage nature of a worker/dataflow can provide back to the coordinator.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum StorageResponse<T = mz_repr::Timestamp> {
@@ -305,14 +318,15 @@ pub enum StorageResponse<T = mz_repr::Timestamp> {
     /// Punctuation indicates that no more responses will be transmitted for the specified ids
     DroppedIds(Vec<GlobalId>),
 
-    /// A list of statistics updates, currently only for sources.
-    StatisticsUpdates(Vec<SourceStatisticsUpdate>),
+    /// A list of statistics updates for sources, and one for sinks.
+    StatisticsUpdates(Vec<SourceStatisticsUpdate>, Vec<SinkStatisticsUpdate>),
 }
 
 impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
     fn into_proto(&self) -> ProtoStorageResponse {
         use proto_storage_response::{
-            Kind::*, ProtoDroppedIds, ProtoSourceStatisticsUpdate, ProtoStatisticsUpdates,
+            Kind::*, ProtoDroppedIds, ProtoSinkStatisticsUpdate, ProtoSourceStatisticsUpdate,
+            ProtoStatisticsUpdates,
         };
         ProtoStorageResponse {
             kind: Some(match self {
@@ -320,20 +334,31 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
                 StorageResponse::DroppedIds(ids) => DroppedIds(ProtoDroppedIds {
                     ids: ids.into_proto(),
                 }),
-                StorageResponse::StatisticsUpdates(stats) => Stats(ProtoStatisticsUpdates {
-                    source_updates: stats
-                        .iter()
-                        .map(|update| ProtoSourceStatisticsUpdate {
-                            id: Some(update.id.into_proto()),
-                            worker_id: u64::cast_from(update.worker_id),
-                            snapshot_committed: update.snapshot_committed,
-                            messages_received: update.messages_received,
-                            updates_staged: update.updates_staged,
-                            updates_committed: update.updates_committed,
-                            bytes_received: update.bytes_received,
-                        })
-                        .collect(),
-                }),
+                StorageResponse::StatisticsUpdates(source_stats, sink_stats) => {
+                    Stats(ProtoStatisticsUpdates {
+                        source_updates: source_stats
+                            .iter()
+                            .map(|update| ProtoSourceStatisticsUpdate {
+                                id: Some(update.id.into_proto()),
+                                worker_id: u64::cast_from(update.worker_id),
+                                snapshot_committed: update.snapshot_committed,
+                                messages_received: update.messages_received,
+                                updates_staged: update.updates_staged,
+                                updates_committed: update.updates_committed,
+                                bytes_received: update.bytes_received,
+                            })
+                            .collect(),
+                        sink_updates: sink_stats
+                            .iter()
+                            .map(|update| ProtoSinkStatisticsUpdate {
+                                id: Some(update.id.into_proto()),
+                                worker_id: u64::cast_from(update.worker_id),
+                                updates_dropped: update.updates_dropped,
+                                write_frontier: update.write_frontier.map(u64::from),
+                            })
+                            .collect(),
+                    })
+                }
             }),
         }
     }
@@ -365,6 +390,20 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
                         })
                     })
                     .collect::<Result<Vec<_>, TryFromProtoError>>()?,
+                stats
+                    .sink_updates
+                    .into_iter()
+                    .map(|update| {
+                        Ok(SinkStatisticsUpdate {
+                            id: update.id.into_rust_if_some(
+                                "ProtoStorageResponse::stats::sink_updates::id",
+                            )?,
+                            worker_id: usize::cast_from(update.worker_id),
+                            updates_dropped: update.updates_dropped,
+                            write_frontier: update.write_frontier.map(mz_repr::Timestamp::from),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, TryFromProtoError>>()?,
             )),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStorageResponse::kind",
@@ -459,10 +498,13 @@ impl<T> PartitionedState<StorageCommand<T>, StorageResponse<T>> for PartitionedS
 where
     T: timely::progress::Timestamp + Lattice,
 {
-    fn split_command(&mut self, command: StorageCommand<T>) -> Vec<Option<StorageCommand<T>>> {
+    fn split_command(
+        &mut self,
+        command: StorageCommand<T>,
+    ) -> Result<Vec<Option<StorageCommand<T>>>, anyhow::Error> {
         self.observe_command(&command);
 
-        vec![Some(command); usize::cast_from(self.parts)]
+        Ok(vec![Some(command); usize::cast_from(self.parts)])
     }
 
     fn absorb_response(
@@ -527,11 +569,14 @@ where
                     Some(Ok(StorageResponse::DroppedIds(new_drops)))
                 }
             }
-            StorageResponse::StatisticsUpdates(stats) => {
+            StorageResponse::StatisticsUpdates(source_stats, sink_stats) => {
                 // Just forward it along; the `worker_id` should have been set in `storage_state`.
                 // We _could_ consolidate across worker_id's, here, but each worker only produces
                 // responses periodically, so we avoid that complexity.
-                Some(Ok(StorageResponse::StatisticsUpdates(stats)))
+                Some(Ok(StorageResponse::StatisticsUpdates(
+                    source_stats,
+                    sink_stats,
+                )))
             }
         }
     }
@@ -55,7 +55,7 @@ use mz_stash::{self, PostgresFactory, StashError, TypedCollection};
 
 use crate::client::{
     CreateSinkCommand, CreateSourceCommand, ProtoStorageCommand, ProtoStorageResponse,
-    SourceStatisticsUpdate, StorageCommand, StorageResponse, Update,
+    SinkStatisticsUpdate, SourceStatisticsUpdate, StorageCommand, StorageResponse, Update,
 };
 use crate::controller::hosts::{StorageHosts, StorageHostsConfig};
 use crate::healthcheck;
@@ -110,9 +110,10 @@ pub enum IntrospectionType {
     ShardMapping,
     StorageHostMetrics,
 
-    // Note that this single-shard introspection source will be changed to per-replica,
+    // Note that these single-shard introspection sources will be changed to per-replica,
     // once we allow multiplexing multiple sources/sinks on a single cluster.
     StorageSourceStatistics,
+    StorageSinkStatistics,
 }
 
 /// Describes how data is written to the collection.
@@ -215,6 +216,24 @@ pub trait StorageController: Debug + Send {
         collections: Vec<(GlobalId, StorageHostConfig)>,
     ) -> Result<(), StorageError>;
 
+    /// Stops ingesting data into the identified collections, without dropping
+    /// them.
+    ///
+    /// Paused collections remain readable, but their uppers do not advance
+    /// until they are resumed with `resume_ingestions`. Pausing an ingestion
+    /// that is already paused has no effect.
+    ///
+    /// Pause state is only held in memory, so it is lost when `environmentd`
+    /// restarts, after which all ingestions run again.
+    async fn pause_ingestions(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError>;
+
+    /// Resumes ingesting data into the identified collections, which must have
+    /// been paused with `pause_ingestions`.
+    ///
+    /// Ingestion resumes where it left off. Resuming an ingestion that is not
+    /// paused has no effect.
+    async fn resume_ingestions(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
@@ -602,6 +621,8 @@ pub struct StorageControllerState<
     /// Commands that should be send to storage instances during the next call
     /// to `StorageController::process`.
     pending_compaction_commands: Vec<(GlobalId, Antichain<T>)>,
+    /// Ingestions that are paused, and so have no storage host.
+    paused_ingestions: PausedIngestions,
 
     /// Interface for managed collections
     pub(super) collection_manager: collection_mgmt::CollectionManager,
@@ -620,6 +641,8 @@ pub struct StorageControllerState<
     /// and its contents are entirely driven by `StorageResponse::StatisticsUpdates`'s.
     source_statistics:
         Arc<std::sync::Mutex<HashMap<GlobalId, HashMap<usize, SourceStatisticsUpdate>>>>,
+    /// Consolidated sink statistics, maintained like `source_statistics`.
+    sink_statistics: Arc<std::sync::Mutex<HashMap<GlobalId, HashMap<usize, SinkStatisticsUpdate>>>>,
 }
 
 /// A storage controller for a storage instance.
@@ -644,6 +667,8 @@ pub enum StorageError {
     SourceIdReused(GlobalId),
     /// The source identifier is not present.
     IdentifierMissing(GlobalId),
+    /// The identified collection is not ingested from an external source.
+    IdentifierNotIngestion(GlobalId),
     /// The update contained in the appended batch was at a timestamp equal or beyond the batch's upper
     UpdateBeyondUpper(GlobalId),
     /// The read was at a timestamp before the collection's since
@@ -663,6 +688,7 @@ impl Error for StorageError {
         match self {
             Self::SourceIdReused(_) => None,
             Self::IdentifierMissing(_) => None,
+            Self::IdentifierNotIngestion(_) => None,
             Self::UpdateBeyondUpper(_) => None,
             Self::ReadBeforeSince(_) => None,
             Self::InvalidUppers(_) => None,
@@ -682,6 +708,12 @@ impl fmt::Display for StorageError {
                 "source identifier was re-created after having been dropped: {id}"
             ),
             Self::IdentifierMissing(id) => write!(f, "collection identifier is not present: {id}"),
+            Self::IdentifierNotIngestion(id) => {
+                write!(
+                    f,
+                    "collection is not ingested from an external source: {id}"
+                )
+            }
             Self::UpdateBeyondUpper(id) => {
                 write!(
                     f,
@@ -711,6 +743,90 @@ impl From<anyhow::Error> for StorageError {
     }
 }
 
+/// The ingestions that are paused, and so have no storage host.
+///
+/// Pause state is only held in memory: it is lost when `environmentd` restarts,
+/// at which point all ingestions run again.
+#[derive(Debug, Default)]
+struct PausedIngestions(BTreeSet<GlobalId>);
+
+impl PausedIngestions {
+    /// Reports whether the ingestion `id` is paused.
+    fn is_paused(&self, id: GlobalId) -> bool {
+        self.0.contains(&id)
+    }
+
+    /// Pauses the ingestion `id`. Returns whether it was running, in which
+    /// case its storage host must be deprovisioned.
+    fn pause(&mut self, id: GlobalId) -> bool {
+        self.0.insert(id)
+    }
+
+    /// Resumes the ingestion `id`. Returns whether it was paused, in which
+    /// case it must be started again.
+    fn resume(&mut self, id: GlobalId) -> bool {
+        self.0.remove(&id)
+    }
+
+    /// Forgets the dropped ingestion `id`. Returns whether it was paused, in
+    /// which case there is no storage host to tell about the drop.
+    fn remove_dropped(&mut self, id: GlobalId) -> bool {
+        self.0.remove(&id)
+    }
+}
+
+/// Returns the command that starts the ingestion `id` from the resumption
+/// frontier of its collections, whose metadata is looked up with `metadata`.
+async fn create_source_command<T, F>(
+    id: GlobalId,
+    ingestion: IngestionDescription,
+    metadata: F,
+    persist: &Mutex<PersistClientCache>,
+) -> Result<CreateSourceCommand<T>, StorageError>
+where
+    T: Timestamp + Lattice + Codec64,
+    F: Fn(GlobalId) -> Result<CollectionMetadata, StorageError>,
+{
+    // Each ingestion is augmented with the collection metadata.
+    let mut source_imports = BTreeMap::new();
+    for (id, _) in ingestion.source_imports {
+        source_imports.insert(id, metadata(id)?);
+    }
+
+    // The ingestion metadata is simply the collection metadata of the collection with
+    // the associated ingestion
+    let ingestion_metadata = metadata(id)?;
+
+    let mut source_exports = BTreeMap::new();
+    for (id, export) in ingestion.source_exports {
+        source_exports.insert(
+            id,
+            SourceExport {
+                storage_metadata: metadata(id)?,
+                output_index: export.output_index,
+            },
+        );
+    }
+
+    let desc = IngestionDescription {
+        source_imports,
+        source_exports,
+        ingestion_metadata,
+        // The rest of the fields are identical
+        desc: ingestion.desc,
+        host_config: ingestion.host_config,
+    };
+    let mut persist_clients = persist.lock().await;
+    let mut state = desc.initialize_state(&mut persist_clients).await;
+    let resume_upper = desc.calculate_resumption_frontier(&mut state).await;
+
+    Ok(CreateSourceCommand {
+        id,
+        description: desc,
+        resume_upper,
+    })
+}
+
 impl From<StashError> for StorageError {
     fn from(error: StashError) -> Self {
         Self::IOError(error)
@@ -760,12 +876,14 @@ impl<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + TimestampManipulatio
             stashed_response: None,
             pending_host_deprovisions: BTreeSet::new(),
             pending_compaction_commands: Vec::new(),
+            paused_ingestions: PausedIngestions::default(),
             collection_manager,
             introspection_ids: HashMap::new(),
             introspection_tokens: HashMap::new(),
             now,
             envd_epoch,
             source_statistics: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            sink_statistics: Arc::new(std::sync::Mutex::new(HashMap::new())),
         }
     }
 }
@@ -951,51 +1069,8 @@ where
             self.register_shard_mapping(id).await;
 
             match description.data_source {
-                DataSource::Ingestion(ingestion) => {
-                    // Each ingestion is augmented with the collection metadata.
-                    let mut source_imports = BTreeMap::new();
-                    for (id, _) in ingestion.source_imports {
-                        let metadata = self.collection(id)?.collection_metadata.clone();
-                        source_imports.insert(id, metadata);
-                    }
-
-                    // The ingestion metadata is simply the collection metadata of the collection with
-                    // the associated ingestion
-                    let ingestion_metadata = self.collection(id)?.collection_metadata.clone();
-
-                    let mut source_exports = BTreeMap::new();
-                    for (id, export) in ingestion.source_exports {
-                        let storage_metadata = self.collection(id)?.collection_metadata.clone();
-                        source_exports.insert(
-                            id,
-                            SourceExport {
-                                storage_metadata,
-                                output_index: export.output_index,
-                            },
-                        );
-                    }
-
-                    let desc = IngestionDescription {
-                        source_imports,
-                        source_exports,
-                        ingestion_metadata,
-                        // The rest of the fields are identical
-                        desc: ingestion.desc,
-                        host_config: ingestion.host_config,
-                    };
-                    let mut persist_clients = self.persist.lock().await;
-                    let mut state = desc.initialize_state(&mut persist_clients).await;
-                    let resume_upper = desc.calculate_resumption_frontier(&mut state).await;
-
-                    // Provision a storage host for the ingestion.
-                    let client = self.hosts.provision(id, desc.host_config.clone()).await?;
-                    let augmented_ingestion = CreateSourceCommand {
-                        id,
-                        description: desc,
-                        resume_upper,
-                    };
-
-                    client.send(StorageCommand::CreateSources(vec![augmented_ingestion]));
+                DataSource::Ingestion(_) => {
+                    self.run_ingestion(id).await?;
                 }
                 DataSource::Introspection(i) => {
                     let prev = self.state.introspection_ids.insert(i, id);
@@ -1060,6 +1135,21 @@ where
                             // dropped, so that the internal task will stop.
                             self.state.introspection_tokens.insert(id, scraper_token);
                         }
+                        IntrospectionType::StorageSinkStatistics => {
+                            // Set the collection to empty.
+                            self.reconcile_managed_collection(id, vec![]).await;
+
+                            let scraper_token = statistics::spawn_statistics_scraper(
+                                id.clone(),
+                                // These do a shallow copy.
+                                self.state.collection_manager.clone(),
+                                Arc::clone(&self.state.sink_statistics),
+                            );
+
+                            // Make sure this is dropped when the controller is
+                            // dropped, so that the internal task will stop.
+                            self.state.introspection_tokens.insert(id, scraper_token);
+                        }
                         IntrospectionType::SourceStatusHistory
                         | IntrospectionType::SinkStatusHistory => {
                             // nothing to do: these collections are append only
@@ -1078,11 +1168,55 @@ where
         collections: Vec<(GlobalId, StorageHostConfig)>,
     ) -> Result<(), StorageError> {
         for (id, config) in collections {
+            // Record the new configuration, so that resuming a paused
+            // ingestion provisions a storage host with it.
+            if let Ok(collection) = self.collection_mut(id) {
+                if let DataSource::Ingestion(ingestion) = &mut collection.description.data_source {
+                    ingestion.host_config = config.clone();
+                }
+            }
+            if self.state.paused_ingestions.is_paused(id) {
+                continue;
+            }
             let _ = self.hosts.provision(id, config).await?;
         }
         Ok(())
     }
 
+    async fn pause_ingestions(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError> {
+        for id in identifiers.iter() {
+            if !matches!(
+                self.collection(*id)?.description.data_source,
+                DataSource::Ingestion(_)
+            ) {
+                return Err(StorageError::IdentifierNotIngestion(*id));
+            }
+        }
+
+        let status_id = self.state.introspection_ids[&IntrospectionType::SourceStatusHistory];
+        for id in identifiers {
+            if !self.state.paused_ingestions.pause(id) {
+                continue;
+            }
+            // Deprovisioning the storage host stops the ingestion; the data
+            // it has written stays in persist.
+            self.hosts.deprovision(id).await?;
+            let status_row = healthcheck::pack_status_row(id, "paused", None, (self.state.now)());
+            self.append_to_managed_collection(status_id, vec![(status_row, 1)])
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn resume_ingestions(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError> {
+        for id in identifiers {
+            if self.state.paused_ingestions.resume(id) {
+                self.run_ingestion(id).await?;
+            }
+        }
+        Ok(())
+    }
+
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError> {
         self.state
             .exports
@@ -1197,9 +1331,11 @@ where
                 description: StorageSinkDesc {
                     from: from_id,
                     from_desc: description.sink.from_desc,
+                    projection: description.sink.projection,
                     connection: description.sink.connection,
                     envelope: description.sink.envelope,
                     as_of,
+                    error_policy: description.sink.error_policy,
                     status_id,
                     from_storage_metadata,
                 },
@@ -1460,15 +1596,25 @@ where
                 // TODO(petrosagg): It looks like the storage controller never cleans up GlobalIds
                 // from its state. It should probably be done as a reaction to this response.
             }
-            Some(StorageResponse::StatisticsUpdates(source_stats)) => {
-                // Note we only hold the lock while moving some plain-old-data around here.
-                let mut shared_stats = self.state.source_statistics.lock().expect("poisoned");
-
-                for stat in source_stats {
-                    let shared_stats = shared_stats.entry(stat.id).or_default();
-                    // We just write the whole object, as the update from storage represents the
-                    // current values.
-                    shared_stats.insert(stat.worker_id, stat);
+            Some(StorageResponse::StatisticsUpdates(source_stats, sink_stats)) => {
+                // Note we only hold the locks while moving some plain-old-data around here.
+                {
+                    let mut shared_stats = self.state.source_statistics.lock().expect("poisoned");
+
+                    for stat in source_stats {
+                        let shared_stats = shared_stats.entry(stat.id).or_default();
+                        // We just write the whole object, as the update from storage represents the
+                        // current values.
+                        shared_stats.insert(stat.worker_id, stat);
+                    }
+                }
+                {
+                    let mut shared_stats = self.state.sink_statistics.lock().expect("poisoned");
+
+                    for stat in sink_stats {
+                        let shared_stats = shared_stats.entry(stat.id).or_default();
+                        shared_stats.insert(stat.worker_id, stat);
+                    }
                 }
             }
         }
@@ -1476,6 +1622,15 @@ where
         // TODO(aljoscha): We could consolidate these before sending to
         // instances, but this seems fine for now.
         for (id, frontier) in self.state.pending_compaction_commands.drain(..) {
+            // A paused ingestion has no storage host to instruct, but its
+            // destruction must still be recorded.
+            if frontier.is_empty() && self.state.paused_ingestions.remove_dropped(id) {
+                let status_id =
+                    self.state.introspection_ids[&IntrospectionType::SourceStatusHistory];
+                self.state.pending_host_deprovisions.insert((id, status_id));
+                continue;
+            }
+
             // TODO(petrosagg): make this a strict check
             if let Some(client) = self.hosts.client(id) {
                 client.send(StorageCommand::AllowCompaction(vec![(
@@ -1708,6 +1863,30 @@ where
         }
     }
 
+    /// Provisions a storage host for the ingestion `id` and instructs it to
+    /// start ingesting from the collection's resumption frontier.
+    async fn run_ingestion(&mut self, id: GlobalId) -> Result<(), StorageError> {
+        let ingestion = match &self.collection(id)?.description.data_source {
+            DataSource::Ingestion(ingestion) => ingestion.clone(),
+            _ => return Err(StorageError::IdentifierNotIngestion(id)),
+        };
+        let command = create_source_command(
+            id,
+            ingestion,
+            |id| Ok(self.collection(id)?.collection_metadata.clone()),
+            &self.persist,
+        )
+        .await?;
+
+        // Provision a storage host for the ingestion.
+        let client = self
+            .hosts
+            .provision(id, command.description.host_config.clone())
+            .await?;
+        client.send(StorageCommand::CreateSources(vec![command]));
+        Ok(())
+    }
+
     /// Append `updates` to the `data_shard` associated with `global_id`
     /// effective as of the system time.
     ///
@@ -2027,6 +2206,19 @@ impl<T: Timestamp> ExportState<T> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_ore::now::SYSTEM_TIME;
+    use mz_persist_client::PersistConfig;
+
+    use crate::types::sources::encoding::{DataEncoding, DataEncodingInner, SourceDataEncoding};
+    use crate::types::sources::{
+        GenericSourceConnection, KeyEnvelope, NoneEnvelope, SourceDesc, SourceEnvelope,
+        SourceRateLimits, TestScriptSourceConnection,
+    };
+
     use super::*;
 
     #[test]
@@ -2035,4 +2227,128 @@ mod tests {
         let write_frontier = Antichain::from_elem(mz_repr::Timestamp::from(5));
         assert_eq!(policy.frontier(write_frontier.borrow()), write_frontier);
     }
+
+    #[test]
+    fn pause_resume() {
+        let id = GlobalId::User(1);
+        let mut paused = PausedIngestions::default();
+        assert!(!paused.is_paused(id));
+
+        // Only the first pause stops the ingestion.
+        assert!(paused.pause(id));
+        assert!(!paused.pause(id));
+        assert!(paused.is_paused(id));
+
+        // Only the first resume starts it again.
+        assert!(paused.resume(id));
+        assert!(!paused.resume(id));
+        assert!(!paused.is_paused(id));
+
+        // Resuming a running ingestion has no effect.
+        assert!(!paused.resume(GlobalId::User(2)));
+    }
+
+    #[test]
+    fn drop_while_paused() {
+        let id = GlobalId::User(1);
+        let mut paused = PausedIngestions::default();
+
+        // A running ingestion is dropped through its storage host.
+        assert!(!paused.remove_dropped(id));
+
+        // A paused ingestion has no storage host, and cannot be resumed once
+        // it is dropped.
+        assert!(paused.pause(id));
+        assert!(paused.remove_dropped(id));
+        assert!(!paused.is_paused(id));
+        assert!(!paused.resume(id));
+    }
+
+    #[tokio::test]
+    async fn resume_from_frontier() {
+        let persist = Mutex::new(PersistClientCache::new(
+            PersistConfig::new(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
+            &MetricsRegistry::new(),
+        ));
+        let id = GlobalId::User(1);
+        let metadata = CollectionMetadata {
+            persist_location: PersistLocation {
+                blob_uri: "mem://".to_owned(),
+                consensus_uri: "mem://".to_owned(),
+            },
+            remap_shard: ShardId::new(),
+            data_shard: ShardId::new(),
+            status_shard: None,
+        };
+        let ingestion = IngestionDescription {
+            desc: SourceDesc {
+                connection: GenericSourceConnection::TestScript(TestScriptSourceConnection {
+                    desc_json: String::new(),
+                }),
+                encoding: SourceDataEncoding::Single(DataEncoding::new(DataEncodingInner::Bytes)),
+                envelope: SourceEnvelope::None(NoneEnvelope {
+                    key_envelope: KeyEnvelope::None,
+                    key_arity: 0,
+                }),
+                metadata_columns: vec![],
+                timestamp_interval: Duration::from_secs(1),
+                mfp: None,
+                rate_limits: SourceRateLimits::default(),
+            },
+            source_imports: BTreeMap::new(),
+            ingestion_metadata: (),
+            source_exports: BTreeMap::from([(
+                id,
+                SourceExport {
+                    output_index: 0,
+                    storage_metadata: (),
+                },
+            )]),
+            host_config: StorageHostConfig::Remote {
+                addr: "localhost:2100".into(),
+            },
+        };
+        let lookup = |_| Ok::<_, StorageError>(metadata.clone());
+
+        // A new ingestion starts from the beginning.
+        let command: CreateSourceCommand<mz_repr::Timestamp> =
+            create_source_command(id, ingestion.clone(), lookup, &persist)
+                .await
+                .unwrap();
+        assert_eq!(
+            command.resume_upper,
+            Antichain::from_elem(mz_repr::Timestamp::minimum())
+        );
+
+        // Ingest up to 5 before pausing.
+        let client = persist
+            .lock()
+            .await
+            .open(metadata.persist_location.clone())
+            .await
+            .unwrap();
+        for shard in [metadata.data_shard, metadata.remap_shard] {
+            let mut write = client
+                .open_writer::<SourceData, (), mz_repr::Timestamp, Diff>(shard, "test")
+                .await
+                .unwrap();
+            let empty: &[((SourceData, ()), mz_repr::Timestamp, Diff)] = &[];
+            write
+                .compare_and_append(
+                    empty,
+                    Antichain::from_elem(0.into()),
+                    Antichain::from_elem(5.into()),
+                )
+                .await
+                .expect("invalid usage")
+                .expect("upper mismatch");
+        }
+
+        // A resumed ingestion continues where it left off.
+        let command: CreateSourceCommand<mz_repr::Timestamp> =
+            create_source_command(id, ingestion, lookup, &persist)
+                .await
+                .unwrap();
+        assert_eq!(command.resume_upper, Antichain::from_elem(5.into()));
+    }
 }
@@ -13,15 +13,19 @@ use std::time::Duration;
 use anyhow::{anyhow, Context};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
 use rdkafka::ClientContext;
+use tracing::warn;
 
 use mz_kafka_util::client::MzClientContext;
 use mz_ore::collections::CollectionExt;
+use mz_ore::retry::{Retry, RetryResult};
+use mz_postgres_util::quote_identifier;
 
-use crate::types::connections::ConnectionContext;
+use crate::types::connections::{ConnectionContext, ElasticsearchClient};
 use crate::types::sinks::{
-    KafkaConsistencyConfig, KafkaSinkConnection, KafkaSinkConnectionBuilder,
-    KafkaSinkConnectionRetention, KafkaSinkFormat, KafkaSinkProgressConnection,
-    PublishedSchemaInfo, StorageSinkConnection, StorageSinkConnectionBuilder,
+    postgres_sink_column_type, ElasticsearchSinkConnection, KafkaConsistencyConfig,
+    KafkaSinkConnection, KafkaSinkConnectionBuilder, KafkaSinkConnectionRetention, KafkaSinkFormat,
+    KafkaSinkProgressConnection, NatsSinkConnection, PostgresSinkConnection, PublishedSchemaInfo,
+    RedisSinkConnection, StorageSinkConnection, StorageSinkConnectionBuilder,
 };
 
 /// Build a sink connection.
@@ -33,6 +37,13 @@ pub async fn build_sink_connection(
 ) -> Result<StorageSinkConnection, anyhow::Error> {
     match builder {
         StorageSinkConnectionBuilder::Kafka(k) => build_kafka(k, connection_context).await,
+        StorageSinkConnectionBuilder::Object(o) => Ok(StorageSinkConnection::Object(o)),
+        StorageSinkConnectionBuilder::Postgres(p) => build_postgres(p, connection_context).await,
+        StorageSinkConnectionBuilder::Elasticsearch(e) => {
+            build_elasticsearch(e, connection_context).await
+        }
+        StorageSinkConnectionBuilder::Redis(r) => build_redis(r, connection_context).await,
+        StorageSinkConnectionBuilder::Nats(n) => build_nats(n, connection_context).await,
     }
 }
 
@@ -166,21 +177,20 @@ async fn publish_kafka_schemas(
     value_schema: &str,
     value_schema_type: mz_ccsr::SchemaType,
 ) -> Result<(Option<i32>, i32), anyhow::Error> {
-    let value_schema_id = ccsr
-        .publish_schema(
-            &format!("{}-value", topic),
-            value_schema,
-            value_schema_type,
-            &[],
-        )
-        .await
-        .context("unable to publish value schema to registry in kafka sink")?;
+    let value_schema_id = publish_schema(
+        ccsr,
+        &format!("{}-value", topic),
+        value_schema,
+        value_schema_type,
+    )
+    .await
+    .context("unable to publish value schema to registry in kafka sink")?;
 
     let key_schema_id = if let Some(key_schema) = key_schema {
         let key_schema_type =
             key_schema_type.ok_or_else(|| anyhow!("expected schema type for key schema"))?;
         Some(
-            ccsr.publish_schema(&format!("{}-key", topic), key_schema, key_schema_type, &[])
+            publish_schema(ccsr, &format!("{}-key", topic), key_schema, key_schema_type)
                 .await
                 .context("unable to publish key schema to registry in kafka sink")?,
         )
@@ -191,6 +201,39 @@ async fn publish_kafka_schemas(
     Ok((key_schema_id, value_schema_id))
 }
 
+/// Publishes a schema under `subject`, retrying transient failures.
+///
+/// Errors that indicate the schema itself was rejected are returned
+/// immediately, since retrying them cannot succeed.
+async fn publish_schema(
+    ccsr: &mz_ccsr::Client,
+    subject: &str,
+    schema: &str,
+    schema_type: mz_ccsr::SchemaType,
+) -> Result<i32, mz_ccsr::PublishError> {
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|state| async move {
+            match ccsr.publish_schema(subject, schema, schema_type, &[]).await {
+                Ok(id) => RetryResult::Ok(id),
+                Err(
+                    e @ (mz_ccsr::PublishError::IncompatibleSchema
+                    | mz_ccsr::PublishError::InvalidSchema { .. }),
+                ) => RetryResult::FatalErr(e),
+                Err(e) => {
+                    if let Some(timeout) = state.next_backoff {
+                        warn!(
+                            "transient failure publishing schema for subject {}: {}, retrying in {:?}",
+                            subject, e, timeout
+                        );
+                    }
+                    RetryResult::RetryableErr(e)
+                }
+            }
+        })
+        .await
+}
+
 async fn build_kafka(
     builder: KafkaSinkConnectionBuilder,
     connection_context: ConnectionContext,
@@ -211,6 +254,10 @@ async fn build_kafka(
     .await
     .context("error registering kafka topic for sink")?;
 
+    let csv_format = match &builder.format {
+        KafkaSinkFormat::Csv(csv_format) => Some(csv_format.clone()),
+        KafkaSinkFormat::Avro { .. } | KafkaSinkFormat::Json => None,
+    };
     let published_schema_info = match builder.format {
         KafkaSinkFormat::Avro {
             key_schema,
@@ -236,7 +283,7 @@ async fn build_kafka(
                 value_schema_id,
             })
         }
-        KafkaSinkFormat::Json => None,
+        KafkaSinkFormat::Json | KafkaSinkFormat::Csv(_) => None,
     };
 
     let progress = match builder.consistency_config {
@@ -255,6 +302,22 @@ async fn build_kafka(
         }
     };
 
+    if let Some(consistency_topic) = &builder.consistency_topic {
+        // Consumers read the consistency topic in order, so it has a single
+        // partition.
+        ensure_kafka_topic(
+            &client,
+            consistency_topic,
+            1,
+            builder.replication_factor,
+            KafkaSinkConnectionRetention::default(),
+        )
+        .await
+        .with_context(|| {
+            format!("error registering kafka consistency topic {consistency_topic}")
+        })?;
+    }
+
     Ok(StorageSinkConnection::Kafka(KafkaSinkConnection {
         connection: builder.connection,
         connection_id: builder.connection_id,
@@ -263,7 +326,166 @@ async fn build_kafka(
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
         published_schema_info,
+        csv_format,
         progress,
+        consistency_topic: builder.consistency_topic,
         fuel: builder.fuel,
+        partition_strategy: builder.partition_strategy,
     }))
 }
+
+/// Creates the table that a Postgres sink maintains and the table in which it
+/// records its progress, unless they already exist.
+///
+/// An existing table is used as is, so long as the sink's upserts and deletes
+/// apply to it.
+async fn build_postgres(
+    connection: PostgresSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let client = connection
+        .connection
+        .config(&*connection_context.secrets_reader)
+        .await?
+        .connect("postgres_sink_setup")
+        .await
+        .context("connecting to PostgreSQL for sink")?;
+
+    let mut columns = vec![];
+    for (name, typ) in connection.value_desc.iter() {
+        let pg_type = postgres_sink_column_type(&typ.scalar_type).ok_or_else(|| {
+            anyhow!(
+                "column {} has type {:?}, which cannot be sinked to PostgreSQL",
+                name,
+                typ.scalar_type
+            )
+        })?;
+        let not_null = if typ.nullable { "" } else { " NOT NULL" };
+        columns.push(format!(
+            "{} {}{}",
+            quote_identifier(name.as_str()),
+            pg_type,
+            not_null
+        ));
+    }
+    let key = connection
+        .key_desc_and_indices
+        .0
+        .iter_names()
+        .map(|name| quote_identifier(name.as_str()))
+        .collect::<Vec<_>>();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} ({}, PRIMARY KEY ({}))",
+            connection.qualified_table(),
+            columns.join(", "),
+            key.join(", ")
+        ))
+        .await
+        .context("error creating table for sink")?;
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (sink_id text PRIMARY KEY, ts bigint NOT NULL)",
+            connection.qualified_progress_table()
+        ))
+        .await
+        .context("error creating progress table for sink")?;
+
+    Ok(StorageSinkConnection::Postgres(connection))
+}
+
+/// Creates the index that an Elasticsearch sink maintains, with a mapping
+/// generated from the sinked relation, and the index in which it records its
+/// progress, unless they already exist.
+///
+/// An existing index is used as is, so long as it accepts the sink's
+/// documents.
+async fn build_elasticsearch(
+    connection: ElasticsearchSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let client = connection
+        .connection
+        .client(&*connection_context.secrets_reader)
+        .await?;
+    ensure_elasticsearch_index(&client, &connection.index, connection.mapping()?)
+        .await
+        .context("error creating index for sink")?;
+    ensure_elasticsearch_index(
+        &client,
+        ElasticsearchSinkConnection::PROGRESS_INDEX,
+        serde_json::json!({
+            "properties": { "ts": { "type": "long" } },
+        }),
+    )
+    .await
+    .context("error creating progress index for sink")?;
+
+    Ok(StorageSinkConnection::Elasticsearch(connection))
+}
+
+/// Creates `index` with the given mapping, unless it already exists.
+async fn ensure_elasticsearch_index(
+    client: &ElasticsearchClient,
+    index: &str,
+    mapping: serde_json::Value,
+) -> Result<(), anyhow::Error> {
+    let response = client
+        .request(reqwest::Method::HEAD, index)
+        .send()
+        .await
+        .context("connecting to Elasticsearch")?;
+    if response.status().is_success() {
+        return Ok(());
+    }
+    if response.status() != reqwest::StatusCode::NOT_FOUND {
+        return Err(anyhow!("unexpected response {}", response.status()));
+    }
+    let response = client
+        .request(reqwest::Method::PUT, index)
+        .json(&serde_json::json!({ "mappings": mapping }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(anyhow!("{}: {}", status, body));
+    }
+    Ok(())
+}
+
+/// Checks that the server to which a Redis sink writes is reachable.
+async fn build_redis(
+    connection: RedisSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let mut conn = connection
+        .connection
+        .connect(&*connection_context.secrets_reader)
+        .await
+        .context("connecting to Redis for sink")?;
+    redis::cmd("PING")
+        .query_async::<_, ()>(&mut conn)
+        .await
+        .context("connecting to Redis for sink")?;
+
+    Ok(StorageSinkConnection::Redis(connection))
+}
+
+/// Checks that the JetStream stream to which a NATS sink publishes exists.
+async fn build_nats(
+    connection: NatsSinkConnection,
+    connection_context: ConnectionContext,
+) -> Result<StorageSinkConnection, anyhow::Error> {
+    let client = connection
+        .connection
+        .connect(&*connection_context.secrets_reader)
+        .await
+        .context("connecting to NATS for sink")?;
+    async_nats::jetstream::new(client)
+        .get_stream(&connection.stream)
+        .await
+        .map_err(|e| anyhow!("looking up NATS stream {}: {}", connection.stream, e))?;
+
+    Ok(StorageSinkConnection::Nats(connection))
+}
@@ -10,7 +10,9 @@
 //! Connection types.
 
 use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -24,6 +26,7 @@ use serde::{Deserialize, Serialize};
 use tokio::net;
 use tokio::sync::oneshot::channel;
 use tokio_postgres::config::SslMode;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 use url::Url;
 
 use mz_ccsr::tls::{Certificate, Identity};
@@ -38,8 +41,12 @@ use mz_ssh_util::keys::SshKeyPairSet;
 use mz_ssh_util::tunnel::SshTunnelConfig;
 
 use crate::types::connections::aws::AwsConfig;
+use crate::types::connections::azure::AzureBlobConnection;
+use crate::types::connections::gcp::GcpConnection;
 
 pub mod aws;
+pub mod azure;
+pub mod gcp;
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -157,9 +164,17 @@ pub enum Connection {
     Kafka(KafkaConnection),
     Csr(CsrConnection),
     Postgres(PostgresConnection),
+    MySql(MySqlConnection),
+    Mqtt(MqttConnection),
+    Elasticsearch(ElasticsearchConnection),
+    Redis(RedisConnection),
+    Nats(NatsConnection),
+    SqlServer(SqlServerConnection),
     Ssh(SshConnection),
     Aws(AwsConfig),
     AwsPrivatelink(AwsPrivatelinkConnection),
+    Gcp(GcpConnection),
+    AzureBlob(AzureBlobConnection),
 }
 
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -310,12 +325,11 @@ impl KafkaConnection {
             connection_context.librdkafka_log_level,
         );
         for (k, v) in options {
-            config.set(
-                k,
-                v.get_string(&*connection_context.secrets_reader)
-                    .await
-                    .expect("reading kafka secret unexpectedly failed"),
-            );
+            let value = v
+                .get_string(&*connection_context.secrets_reader)
+                .await
+                .with_context(|| format!("reading kafka option {}", k))?;
+            config.set(k, value);
         }
         for (k, v) in extra_options {
             config.set(*k, v);
@@ -843,6 +857,555 @@ impl Arbitrary for PostgresConnection {
     }
 }
 
+/// A connection to a MySQL server.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MySqlConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to use TLS for encryption, verification of the server's
+    /// identity, or both.
+    pub tls_mode: MySqlSslMode,
+    /// An optional root TLS certificate in PEM format, to verify the server's
+    /// identity.
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+/// How a [`MySqlConnection`] uses TLS.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MySqlSslMode {
+    /// Do not use TLS.
+    Disabled,
+    /// Use TLS, but do not verify the server's certificate.
+    Required,
+    /// Use TLS, and verify that the server's certificate is signed by a
+    /// trusted certificate authority.
+    VerifyCa,
+    /// Like `VerifyCa`, and additionally verify that the server's certificate
+    /// matches its hostname.
+    VerifyIdentity,
+}
+
+impl MySqlConnection {
+    /// Opens a connection to the MySQL server.
+    pub async fn connect(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<mysql_async::Conn, anyhow::Error> {
+        let mut opts = mysql_async::OptsBuilder::default()
+            .ip_or_hostname(self.host.clone())
+            .tcp_port(self.port)
+            .user(Some(self.user.get_string(secrets_reader).await?));
+        if let Some(password) = self.password {
+            let password = secrets_reader.read_string(password).await?;
+            opts = opts.pass(Some(password));
+        }
+        let mut ssl_opts = match self.tls_mode {
+            MySqlSslMode::Disabled => None,
+            MySqlSslMode::Required => Some(
+                mysql_async::SslOpts::default()
+                    .with_danger_accept_invalid_certs(true)
+                    .with_danger_skip_domain_validation(true),
+            ),
+            MySqlSslMode::VerifyCa => {
+                Some(mysql_async::SslOpts::default().with_danger_skip_domain_validation(true))
+            }
+            MySqlSslMode::VerifyIdentity => Some(mysql_async::SslOpts::default()),
+        };
+        if let (Some(ssl_opts), Some(tls_root_cert)) = (&mut ssl_opts, &self.tls_root_cert) {
+            // The MySQL client only reads root certificates from files. Name
+            // the file after its contents, so that reconnecting reuses it.
+            let tls_root_cert = tls_root_cert.get_string(secrets_reader).await?;
+            let mut hasher = DefaultHasher::new();
+            tls_root_cert.hash(&mut hasher);
+            let path =
+                std::env::temp_dir().join(format!("mysql-root-cert-{:016x}.pem", hasher.finish()));
+            tokio::fs::write(&path, tls_root_cert).await?;
+            *ssl_opts = ssl_opts.clone().with_root_cert_path(Some(path));
+        }
+        let opts = opts.ssl_opts(ssl_opts);
+        Ok(mysql_async::Conn::new(opts).await?)
+    }
+}
+
+impl RustType<ProtoMySqlConnection> for MySqlConnection {
+    fn into_proto(&self) -> ProtoMySqlConnection {
+        ProtoMySqlConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            tls_mode: Some(self.tls_mode.into_proto()),
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            user: proto.user.into_rust_if_some("ProtoMySqlConnection::user")?,
+            password: proto.password.into_rust()?,
+            tls_mode: proto
+                .tls_mode
+                .into_rust_if_some("ProtoMySqlConnection::tls_mode")?,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoMySqlSslMode> for MySqlSslMode {
+    fn into_proto(&self) -> ProtoMySqlSslMode {
+        use proto_my_sql_ssl_mode::Kind;
+        ProtoMySqlSslMode {
+            kind: Some(match self {
+                MySqlSslMode::Disabled => Kind::Disabled(()),
+                MySqlSslMode::Required => Kind::Required(()),
+                MySqlSslMode::VerifyCa => Kind::VerifyCa(()),
+                MySqlSslMode::VerifyIdentity => Kind::VerifyIdentity(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlSslMode) -> Result<Self, TryFromProtoError> {
+        use proto_my_sql_ssl_mode::Kind;
+        Ok(match proto.kind {
+            None => return Err(TryFromProtoError::missing_field("ProtoMySqlSslMode::kind")),
+            Some(Kind::Disabled(())) => MySqlSslMode::Disabled,
+            Some(Kind::Required(())) => MySqlSslMode::Required,
+            Some(Kind::VerifyCa(())) => MySqlSslMode::VerifyCa,
+            Some(Kind::VerifyIdentity(())) => MySqlSslMode::VerifyIdentity,
+        })
+    }
+}
+
+/// A connection to an MQTT broker.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MqttConnection {
+    /// The hostname of the broker.
+    pub host: String,
+    /// The port of the broker.
+    pub port: u16,
+    /// An optional username for authentication.
+    pub user: Option<StringOrSecret>,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to connect using TLS, verifying the broker's identity.
+    pub tls: bool,
+    /// An optional root TLS certificate in PEM format, to verify the broker's
+    /// identity.
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+impl MqttConnection {
+    /// Returns the options for an MQTT client that identifies itself to the
+    /// broker as `client_id`.
+    pub async fn options(
+        &self,
+        client_id: String,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<rumqttc::MqttOptions, anyhow::Error> {
+        let mut options = rumqttc::MqttOptions::new(client_id, self.host.clone(), self.port);
+        if let Some(user) = &self.user {
+            let user = user.get_string(secrets_reader).await?;
+            let password = match self.password {
+                Some(password) => secrets_reader.read_string(password).await?,
+                None => String::new(),
+            };
+            options.set_credentials(user, password);
+        }
+        if self.tls {
+            let tls = match &self.tls_root_cert {
+                Some(tls_root_cert) => rumqttc::TlsConfiguration::SimpleNative {
+                    ca: tls_root_cert.get_string(secrets_reader).await?.into_bytes(),
+                    client_auth: None,
+                },
+                None => rumqttc::TlsConfiguration::Native,
+            };
+            options.set_transport(rumqttc::Transport::tls_with_config(tls));
+        }
+        Ok(options)
+    }
+}
+
+impl RustType<ProtoMqttConnection> for MqttConnection {
+    fn into_proto(&self) -> ProtoMqttConnection {
+        ProtoMqttConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            tls: self.tls,
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMqttConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MqttConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            tls: proto.tls,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
+/// A connection to a Microsoft SQL Server database.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SqlServerConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The name of the database to connect to.
+    pub database: String,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to use TLS for encryption, verification of the server's
+    /// identity, or both.
+    pub tls_mode: SqlServerSslMode,
+}
+
+/// How a [`SqlServerConnection`] uses TLS.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SqlServerSslMode {
+    /// Only encrypt the login packet, which carries the user's credentials.
+    Disabled,
+    /// Use TLS, but do not verify the server's certificate.
+    Required,
+    /// Use TLS, and verify that the server's certificate is signed by a
+    /// trusted certificate authority and matches its hostname.
+    VerifyIdentity,
+}
+
+impl SqlServerConnection {
+    /// Opens a connection to the SQL Server database.
+    pub async fn connect(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<tiberius::Client<Compat<net::TcpStream>>, anyhow::Error> {
+        let mut config = tiberius::Config::new();
+        config.host(&self.host);
+        config.port(self.port);
+        config.database(&self.database);
+        let user = self.user.get_string(secrets_reader).await?;
+        let password = match self.password {
+            Some(password) => secrets_reader.read_string(password).await?,
+            None => String::new(),
+        };
+        config.authentication(tiberius::AuthMethod::sql_server(user, password));
+        match self.tls_mode {
+            SqlServerSslMode::Disabled => config.encryption(tiberius::EncryptionLevel::Off),
+            SqlServerSslMode::Required => {
+                config.encryption(tiberius::EncryptionLevel::Required);
+                config.trust_cert();
+            }
+            SqlServerSslMode::VerifyIdentity => {
+                config.encryption(tiberius::EncryptionLevel::Required)
+            }
+        }
+
+        let tcp = net::TcpStream::connect(config.get_addr()).await?;
+        tcp.set_nodelay(true)?;
+        // The client expects a stream that implements the `futures` crate's
+        // I/O traits rather than Tokio's.
+        Ok(tiberius::Client::connect(config, tcp.compat_write()).await?)
+    }
+}
+
+impl RustType<ProtoSqlServerConnection> for SqlServerConnection {
+    fn into_proto(&self) -> ProtoSqlServerConnection {
+        ProtoSqlServerConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            tls_mode: Some(self.tls_mode.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSqlServerConnection::user")?,
+            password: proto.password.into_rust()?,
+            tls_mode: proto
+                .tls_mode
+                .into_rust_if_some("ProtoSqlServerConnection::tls_mode")?,
+        })
+    }
+}
+
+impl RustType<ProtoSqlServerSslMode> for SqlServerSslMode {
+    fn into_proto(&self) -> ProtoSqlServerSslMode {
+        use proto_sql_server_ssl_mode::Kind;
+        ProtoSqlServerSslMode {
+            kind: Some(match self {
+                SqlServerSslMode::Disabled => Kind::Disabled(()),
+                SqlServerSslMode::Required => Kind::Required(()),
+                SqlServerSslMode::VerifyIdentity => Kind::VerifyIdentity(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSslMode) -> Result<Self, TryFromProtoError> {
+        use proto_sql_server_ssl_mode::Kind;
+        Ok(match proto.kind {
+            None => {
+                return Err(TryFromProtoError::missing_field(
+                    "ProtoSqlServerSslMode::kind",
+                ))
+            }
+            Some(Kind::Disabled(())) => SqlServerSslMode::Disabled,
+            Some(Kind::Required(())) => SqlServerSslMode::Required,
+            Some(Kind::VerifyIdentity(())) => SqlServerSslMode::VerifyIdentity,
+        })
+    }
+}
+
+/// A connection to an Elasticsearch or OpenSearch cluster.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ElasticsearchConnection {
+    /// The base URL of the cluster's REST API.
+    pub url: String,
+    /// An optional username for basic authentication.
+    pub user: Option<StringOrSecret>,
+    /// An optional password for basic authentication.
+    pub password: Option<GlobalId>,
+    /// An optional root TLS certificate in PEM format, to verify the
+    /// cluster's identity.
+    pub tls_root_cert: Option<StringOrSecret>,
+}
+
+impl ElasticsearchConnection {
+    /// Returns a client for the cluster's REST API.
+    pub async fn client(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<ElasticsearchClient, anyhow::Error> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(tls_root_cert) = &self.tls_root_cert {
+            let pem = tls_root_cert.get_string(secrets_reader).await?;
+            builder = builder.add_root_certificate(reqwest::Certificate::from_pem(pem.as_bytes())?);
+        }
+        let credentials = match &self.user {
+            Some(user) => {
+                let password = match self.password {
+                    Some(password) => Some(secrets_reader.read_string(password).await?),
+                    None => None,
+                };
+                Some((user.get_string(secrets_reader).await?, password))
+            }
+            None => None,
+        };
+        Ok(ElasticsearchClient {
+            client: builder.build()?,
+            url: self.url.trim_end_matches('/').to_string(),
+            credentials,
+        })
+    }
+}
+
+impl RustType<ProtoElasticsearchConnection> for ElasticsearchConnection {
+    fn into_proto(&self) -> ProtoElasticsearchConnection {
+        ProtoElasticsearchConnection {
+            url: self.url.clone(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            tls_root_cert: self.tls_root_cert.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoElasticsearchConnection) -> Result<Self, TryFromProtoError> {
+        Ok(ElasticsearchConnection {
+            url: proto.url,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            tls_root_cert: proto.tls_root_cert.into_rust()?,
+        })
+    }
+}
+
+/// A client for the REST API of an Elasticsearch or OpenSearch cluster.
+#[derive(Clone, Debug)]
+pub struct ElasticsearchClient {
+    client: reqwest::Client,
+    /// The base URL of the REST API, without a trailing slash.
+    url: String,
+    credentials: Option<(String, Option<String>)>,
+}
+
+impl ElasticsearchClient {
+    /// Returns a request for `path`, relative to the base URL of the REST API.
+    pub fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/{}", self.url, path));
+        match &self.credentials {
+            Some((user, password)) => request.basic_auth(user, password.as_ref()),
+            None => request,
+        }
+    }
+}
+
+/// A connection to a Redis server.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct RedisConnection {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The number of the logical database to select.
+    pub database: i64,
+    /// An optional username for authentication.
+    pub user: Option<StringOrSecret>,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// Whether to connect using TLS, verifying the server's identity.
+    pub tls: bool,
+}
+
+impl RedisConnection {
+    /// Connects to the server, returning a connection that may be cloned to
+    /// issue commands concurrently.
+    pub async fn connect(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<redis::aio::MultiplexedConnection, anyhow::Error> {
+        let addr = if self.tls {
+            redis::ConnectionAddr::TcpTls {
+                host: self.host.clone(),
+                port: self.port,
+                insecure: false,
+            }
+        } else {
+            redis::ConnectionAddr::Tcp(self.host.clone(), self.port)
+        };
+        let username = match &self.user {
+            Some(user) => Some(user.get_string(secrets_reader).await?),
+            None => None,
+        };
+        let password = match self.password {
+            Some(password) => Some(secrets_reader.read_string(password).await?),
+            None => None,
+        };
+        let client = redis::Client::open(redis::ConnectionInfo {
+            addr,
+            redis: redis::RedisConnectionInfo {
+                db: self.database,
+                username,
+                password,
+            },
+        })?;
+        Ok(client.get_multiplexed_tokio_connection().await?)
+    }
+}
+
+impl RustType<ProtoRedisConnection> for RedisConnection {
+    fn into_proto(&self) -> ProtoRedisConnection {
+        ProtoRedisConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database,
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            tls: self.tls,
+        }
+    }
+
+    fn from_proto(proto: ProtoRedisConnection) -> Result<Self, TryFromProtoError> {
+        Ok(RedisConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            tls: proto.tls,
+        })
+    }
+}
+
+/// A connection to a NATS server.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct NatsConnection {
+    /// The URL of the server, like `nats://nats.example.com:4222`. A `tls://`
+    /// URL requires TLS.
+    pub url: String,
+    /// An optional username for authentication.
+    pub user: Option<StringOrSecret>,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// An optional token for authentication.
+    pub token: Option<GlobalId>,
+    /// The contents of an optional credentials file, which holds the JWT and
+    /// NKey seed of a user.
+    pub credentials: Option<GlobalId>,
+}
+
+impl NatsConnection {
+    /// Connects to the server.
+    pub async fn connect(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+    ) -> Result<async_nats::Client, anyhow::Error> {
+        let mut options = async_nats::ConnectOptions::new();
+        if let Some(user) = &self.user {
+            let user = user.get_string(secrets_reader).await?;
+            let password = match self.password {
+                Some(password) => secrets_reader.read_string(password).await?,
+                None => String::new(),
+            };
+            options = options.user_and_password(user, password);
+        }
+        if let Some(token) = self.token {
+            options = options.token(secrets_reader.read_string(token).await?);
+        }
+        if let Some(credentials) = self.credentials {
+            let credentials = secrets_reader.read_string(credentials).await?;
+            options = options
+                .credentials(&credentials)
+                .context("parsing NATS credentials")?;
+        }
+        Ok(options.connect(self.url.as_str()).await?)
+    }
+}
+
+impl RustType<ProtoNatsConnection> for NatsConnection {
+    fn into_proto(&self) -> ProtoNatsConnection {
+        ProtoNatsConnection {
+            url: self.url.clone(),
+            user: self.user.into_proto(),
+            password: self.password.into_proto(),
+            token: self.token.into_proto(),
+            credentials: self.credentials.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoNatsConnection) -> Result<Self, TryFromProtoError> {
+        Ok(NatsConnection {
+            url: proto.url,
+            user: proto.user.into_rust()?,
+            password: proto.password.into_rust()?,
+            token: proto.token.into_rust()?,
+            credentials: proto.credentials.into_rust()?,
+        })
+    }
+}
+
 /// Specifies how to tunnel a connection.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Tunnel {
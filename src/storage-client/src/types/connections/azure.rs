@@ -0,0 +1,554 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Azure Blob Storage configuration for sinks.
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context};
+use hmac::{Hmac, Mac};
+use proptest_derive::Arbitrary;
+use reqwest::{Method, Url};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::types::connections::aws::SerdeUri;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_client.types.connections.azure.rs"
+));
+
+/// The version of the Blob service REST API that requests ask for.
+const API_VERSION: &str = "2021-08-06";
+
+/// A connection to the Blob service of an Azure storage account.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct AzureBlobConnection {
+    /// The name of the storage account.
+    pub account: String,
+    /// How requests are authorized.
+    pub credentials: AzureBlobCredentials,
+    /// The custom endpoint of the Blob service, if any. Otherwise, the
+    /// service is reached at `https://<account>.blob.core.windows.net`.
+    pub endpoint: Option<SerdeUri>,
+}
+
+/// The secret with which requests to the Blob service are authorized.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AzureBlobCredentials {
+    /// An access key of the storage account, with which requests are signed.
+    AccountKey(GlobalId),
+    /// A shared access signature, which is appended to the URL of requests.
+    SasToken(GlobalId),
+}
+
+impl RustType<ProtoAzureBlobConnection> for AzureBlobConnection {
+    fn into_proto(&self) -> ProtoAzureBlobConnection {
+        ProtoAzureBlobConnection {
+            account: self.account.clone(),
+            credentials: Some(self.credentials.into_proto()),
+            endpoint: self.endpoint.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoAzureBlobConnection) -> Result<Self, TryFromProtoError> {
+        Ok(AzureBlobConnection {
+            account: proto.account,
+            credentials: proto
+                .credentials
+                .into_rust_if_some("ProtoAzureBlobConnection::credentials")?,
+            endpoint: proto.endpoint.into_rust()?,
+        })
+    }
+}
+
+impl RustType<ProtoAzureBlobCredentials> for AzureBlobCredentials {
+    fn into_proto(&self) -> ProtoAzureBlobCredentials {
+        use proto_azure_blob_credentials::Kind;
+        ProtoAzureBlobCredentials {
+            kind: Some(match self {
+                AzureBlobCredentials::AccountKey(id) => Kind::AccountKey(id.into_proto()),
+                AzureBlobCredentials::SasToken(id) => Kind::SasToken(id.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoAzureBlobCredentials) -> Result<Self, TryFromProtoError> {
+        use proto_azure_blob_credentials::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoAzureBlobCredentials::kind"))?;
+        Ok(match kind {
+            Kind::AccountKey(id) => AzureBlobCredentials::AccountKey(id.into_rust()?),
+            Kind::SasToken(id) => AzureBlobCredentials::SasToken(id.into_rust()?),
+        })
+    }
+}
+
+impl AzureBlobConnection {
+    /// Returns a client for the Blob service of the storage account.
+    pub async fn blob_client(
+        &self,
+        secrets_reader: &dyn SecretsReader,
+    ) -> Result<AzureBlobClient, anyhow::Error> {
+        let auth = match self.credentials {
+            AzureBlobCredentials::AccountKey(id) => {
+                let key = secrets_reader.read_string(id).await?;
+                let key = base64::decode(key.trim())
+                    .context("decoding Azure storage account key from ACCOUNT KEY")?;
+                Auth::SharedKey(key)
+            }
+            AzureBlobCredentials::SasToken(id) => {
+                let token = secrets_reader.read_string(id).await?;
+                Auth::Sas(token.trim().trim_start_matches('?').to_string())
+            }
+        };
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint.0.to_string(),
+            None => format!("https://{}.blob.core.windows.net", self.account),
+        };
+        Ok(AzureBlobClient {
+            http: reqwest::Client::new(),
+            endpoint: Url::parse(&endpoint).context("parsing Azure Blob service endpoint")?,
+            account: self.account.clone(),
+            auth,
+        })
+    }
+}
+
+/// How an [`AzureBlobClient`] authorizes its requests.
+enum Auth {
+    /// The decoded account key with which requests are signed.
+    SharedKey(Vec<u8>),
+    /// The query string of a shared access signature.
+    Sas(String),
+}
+
+/// A client for the containers of a storage account's Blob service.
+pub struct AzureBlobClient {
+    http: reqwest::Client,
+    endpoint: Url,
+    account: String,
+    auth: Auth,
+}
+
+impl AzureBlobClient {
+    /// Returns the names of the blobs in `container` whose names start with
+    /// `prefix`.
+    pub async fn list_blobs(
+        &self,
+        container: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let mut names = vec![];
+        let mut marker = None;
+        loop {
+            let mut url = self.url(container, None);
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "list")
+                .append_pair("prefix", prefix);
+            if let Some(marker) = &marker {
+                url.query_pairs_mut().append_pair("marker", marker);
+            }
+            let response = self.send(Method::GET, url, None).await?;
+            let (page, next_marker) = parse_list_blobs(&response.text().await?)?;
+            names.extend(page);
+            marker = next_marker;
+            if marker.is_none() {
+                return Ok(names);
+            }
+        }
+    }
+
+    /// Uploads `contents` as the block blob `name` in `container`, replacing
+    /// any blob of that name.
+    pub async fn put_blob(
+        &self,
+        container: &str,
+        name: &str,
+        contents: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let url = self.url(container, Some(name));
+        self.send(Method::PUT, url, Some(contents)).await?;
+        Ok(())
+    }
+
+    /// Returns the URL of `container`, or of the blob `name` within it.
+    fn url(&self, container: &str, name: Option<&str>) -> Url {
+        let mut url = self.endpoint.clone();
+        {
+            let mut segments = url.path_segments_mut().expect("endpoint is a base URL");
+            segments.pop_if_empty().push(container);
+            if let Some(name) = name {
+                segments.extend(name.split('/'));
+            }
+        }
+        url
+    }
+
+    /// Sends an authorized request, returning an error that includes the
+    /// response body if it is unsuccessful. A request with a body uploads a
+    /// block blob.
+    async fn send(
+        &self,
+        method: Method,
+        mut url: Url,
+        body: Option<Vec<u8>>,
+    ) -> Result<reqwest::Response, anyhow::Error> {
+        let mut headers = vec![
+            ("x-ms-date", httpdate::fmt_http_date(SystemTime::now())),
+            ("x-ms-version", API_VERSION.to_string()),
+        ];
+        let content_type = match body {
+            Some(_) => {
+                headers.push(("x-ms-blob-type", "BlockBlob".to_string()));
+                "application/octet-stream"
+            }
+            None => "",
+        };
+        let content_length = body.as_ref().map_or(0, |body| body.len());
+
+        let authorization =
+            self.authorize(&method, &mut url, &headers, content_length, content_type)?;
+
+        let mut request = self.http.request(method, url);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if let Some(authorization) = authorization {
+            request = request.header(reqwest::header::AUTHORIZATION, authorization);
+        }
+        if let Some(body) = body {
+            request = request
+                .header(reqwest::header::CONTENT_TYPE, content_type)
+                .body(body);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Azure Blob request failed with {}: {}",
+                status,
+                body
+            ));
+        }
+        Ok(response)
+    }
+
+    /// Authorizes a request, returning the value of its Authorization header
+    /// if it is signed. A shared access signature is instead appended to the
+    /// query string of `url`.
+    fn authorize(
+        &self,
+        method: &Method,
+        url: &mut Url,
+        headers: &[(&str, String)],
+        content_length: usize,
+        content_type: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        match &self.auth {
+            Auth::SharedKey(key) => {
+                let string_to_sign =
+                    self.string_to_sign(method, url, headers, content_length, content_type);
+                let mut mac = Hmac::<Sha256>::new_from_slice(key)?;
+                mac.update(string_to_sign.as_bytes());
+                Ok(Some(format!(
+                    "SharedKey {}:{}",
+                    self.account,
+                    base64::encode(mac.finalize().into_bytes())
+                )))
+            }
+            Auth::Sas(token) => {
+                url.query_pairs_mut()
+                    .extend_pairs(url::form_urlencoded::parse(token.as_bytes()));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the string whose HMAC is the Shared Key signature of a request.
+    ///
+    /// See: <https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key>
+    fn string_to_sign(
+        &self,
+        method: &Method,
+        url: &Url,
+        headers: &[(&str, String)],
+        content_length: usize,
+        content_type: &str,
+    ) -> String {
+        let content_length = match content_length {
+            0 => String::new(),
+            n => n.to_string(),
+        };
+
+        let mut canonicalized_headers = headers.to_vec();
+        canonicalized_headers.sort();
+        let canonicalized_headers: String = canonicalized_headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+
+        let mut params: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (name, value) in url.query_pairs() {
+            params
+                .entry(name.to_lowercase())
+                .or_default()
+                .push(value.into_owned());
+        }
+        let mut canonicalized_resource = format!("/{}{}", self.account, url.path());
+        for (name, mut values) in params {
+            values.sort();
+            canonicalized_resource.push_str(&format!("\n{}:{}", name, values.join(",")));
+        }
+
+        // The empty lines are the standard headers that requests never set:
+        // Content-Encoding, Content-Language, Content-MD5, Date (superseded by
+        // x-ms-date), and the conditional and range headers.
+        format!(
+            "{}\n\n\n{}\n\n{}\n\n\n\n\n\n\n{}{}",
+            method, content_length, content_type, canonicalized_headers, canonicalized_resource
+        )
+    }
+}
+
+/// Parses the names of the blobs and the continuation marker, if any, out of
+/// the XML body of a List Blobs response.
+fn parse_list_blobs(xml: &str) -> Result<(Vec<String>, Option<String>), anyhow::Error> {
+    let mut path = vec![];
+    let mut names = vec![];
+    let mut next_marker = None;
+    for token in Tokenizer::from(xml) {
+        match token.context("parsing List Blobs response")? {
+            Token::ElementStart { local, .. } => path.push(local.as_str()),
+            Token::ElementEnd {
+                end: ElementEnd::Close(..) | ElementEnd::Empty,
+                ..
+            } => {
+                path.pop();
+            }
+            Token::Text { text } => match path.as_slice() {
+                [.., "Blob", "Name"] => names.push(unescape_xml(text.as_str())),
+                [.., "NextMarker"] => next_marker = Some(unescape_xml(text.as_str())),
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+    Ok((names, next_marker))
+}
+
+/// Replaces the predefined XML entities in `text`.
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_secrets::{InMemorySecretsController, SecretsController};
+
+    use super::*;
+
+    const DATE: &str = "Sat, 17 Oct 2026 00:00:00 GMT";
+
+    fn client(endpoint: &str, auth: Auth) -> AzureBlobClient {
+        AzureBlobClient {
+            http: reqwest::Client::new(),
+            endpoint: Url::parse(endpoint).unwrap(),
+            account: "acct".into(),
+            auth,
+        }
+    }
+
+    fn headers() -> Vec<(&'static str, String)> {
+        vec![
+            ("x-ms-date", DATE.to_string()),
+            ("x-ms-version", API_VERSION.to_string()),
+        ]
+    }
+
+    fn list_url(client: &AzureBlobClient) -> Url {
+        let mut url = client.url("lake", None);
+        url.query_pairs_mut()
+            .append_pair("restype", "container")
+            .append_pair("comp", "list")
+            .append_pair("prefix", "out/");
+        url
+    }
+
+    #[test]
+    fn test_url() {
+        let c = client(
+            "https://acct.blob.core.windows.net",
+            Auth::Sas(String::new()),
+        );
+        assert_eq!(
+            c.url("lake", None).as_str(),
+            "https://acct.blob.core.windows.net/lake"
+        );
+        // Each component of a blob name is a path segment, with the
+        // characters that have a meaning in URLs escaped.
+        assert_eq!(
+            c.url("lake", Some("out/a b?c#d%e.csv")).as_str(),
+            "https://acct.blob.core.windows.net/lake/out/a%20b%3Fc%23d%25e.csv"
+        );
+
+        // Emulators serve the account under a path of the endpoint.
+        for endpoint in [
+            "http://127.0.0.1:10000/devstoreaccount1",
+            "http://127.0.0.1:10000/devstoreaccount1/",
+        ] {
+            let c = client(endpoint, Auth::Sas(String::new()));
+            assert_eq!(
+                c.url("lake", Some("x.csv")).as_str(),
+                "http://127.0.0.1:10000/devstoreaccount1/lake/x.csv"
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_to_sign() {
+        let c = client(
+            "https://acct.blob.core.windows.net",
+            Auth::SharedKey(vec![]),
+        );
+
+        // Query parameters are sorted and decoded, and the content length of a
+        // request without a body is empty.
+        let url = list_url(&c);
+        assert_eq!(
+            c.string_to_sign(&Method::GET, &url, &headers(), 0, ""),
+            format!(
+                "GET\n\n\n\n\n\n\n\n\n\n\n\nx-ms-date:{DATE}\nx-ms-version:{API_VERSION}\n\
+                 /acct/lake\ncomp:list\nprefix:out/\nrestype:container"
+            )
+        );
+
+        // Headers are sorted, and the path stays escaped.
+        let url = c.url("lake", Some("out/a b.csv"));
+        let mut headers = headers();
+        headers.push(("x-ms-blob-type", "BlockBlob".into()));
+        assert_eq!(
+            c.string_to_sign(&Method::PUT, &url, &headers, 5, "application/octet-stream"),
+            format!(
+                "PUT\n\n\n5\n\napplication/octet-stream\n\n\n\n\n\n\n\
+                 x-ms-blob-type:BlockBlob\nx-ms-date:{DATE}\nx-ms-version:{API_VERSION}\n\
+                 /acct/lake/out/a%20b.csv"
+            )
+        );
+    }
+
+    #[test]
+    fn test_authorize_shared_key() {
+        let c = client(
+            "https://acct.blob.core.windows.net",
+            Auth::SharedKey(b"key".to_vec()),
+        );
+
+        let mut url = list_url(&c);
+        let original = url.clone();
+        let authorization = c
+            .authorize(&Method::GET, &mut url, &headers(), 0, "")
+            .unwrap();
+        assert_eq!(
+            authorization.as_deref(),
+            Some("SharedKey acct:UpiL06sL+sCG1lfhMycz/+Jt2Bb2pl2DIueJN2Oi2HU=")
+        );
+        assert_eq!(url, original);
+
+        let mut url = c.url("lake", Some("out/a b.csv"));
+        let mut headers = headers();
+        headers.push(("x-ms-blob-type", "BlockBlob".into()));
+        let authorization = c
+            .authorize(
+                &Method::PUT,
+                &mut url,
+                &headers,
+                5,
+                "application/octet-stream",
+            )
+            .unwrap();
+        assert_eq!(
+            authorization.as_deref(),
+            Some("SharedKey acct:Eks+c1uOFJKvrop+LfsflUMPnud94Mro2D7pnrSiUCA=")
+        );
+    }
+
+    #[test]
+    fn test_authorize_sas() {
+        let c = client(
+            "https://acct.blob.core.windows.net",
+            Auth::Sas("sv=2021-08-06&sig=a%2Bb%3D".into()),
+        );
+        let mut url = c.url("lake", None);
+        url.query_pairs_mut().append_pair("restype", "container");
+        let authorization = c
+            .authorize(&Method::GET, &mut url, &headers(), 0, "")
+            .unwrap();
+        assert_eq!(authorization, None);
+        assert_eq!(
+            url.as_str(),
+            "https://acct.blob.core.windows.net/lake?restype=container&sv=2021-08-06&sig=a%2Bb%3D"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blob_client_credentials() {
+        let secrets = InMemorySecretsController::new();
+        let key_id = GlobalId::User(1);
+        let sas_id = GlobalId::User(2);
+        secrets.ensure(key_id, b" a2V5\n").await.unwrap();
+        secrets
+            .ensure(sas_id, b"?sv=2021-08-06&sig=x\n")
+            .await
+            .unwrap();
+
+        let connection = AzureBlobConnection {
+            account: "acct".into(),
+            credentials: AzureBlobCredentials::AccountKey(key_id),
+            endpoint: None,
+        };
+        let c = connection.blob_client(&secrets).await.unwrap();
+        assert_eq!(c.endpoint.as_str(), "https://acct.blob.core.windows.net/");
+        match c.auth {
+            Auth::SharedKey(key) => assert_eq!(key, b"key"),
+            Auth::Sas(_) => panic!("expected a shared key"),
+        }
+
+        let connection = AzureBlobConnection {
+            account: "devstoreaccount1".into(),
+            credentials: AzureBlobCredentials::SasToken(sas_id),
+            endpoint: Some(SerdeUri(
+                "http://127.0.0.1:10000/devstoreaccount1".parse().unwrap(),
+            )),
+        };
+        let c = connection.blob_client(&secrets).await.unwrap();
+        assert_eq!(
+            c.endpoint.as_str(),
+            "http://127.0.0.1:10000/devstoreaccount1"
+        );
+        match c.auth {
+            Auth::Sas(token) => assert_eq!(token, "sv=2021-08-06&sig=x"),
+            Auth::SharedKey(_) => panic!("expected a shared access signature"),
+        }
+    }
+}
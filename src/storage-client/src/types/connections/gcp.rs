@@ -0,0 +1,624 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Google Cloud Platform configuration for sources and sinks.
+//!
+//! A [`GcpConnection`] provides clients for the Pub/Sub API, from which Pub/Sub
+//! sources read, and for the Cloud Storage API, to which object sinks write.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{ProtoType, RustType, TryFromProtoError};
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+
+use crate::types::connections::aws::SerdeUri;
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_client.types.connections.gcp.rs"
+));
+
+/// The endpoint of the Pub/Sub API.
+const PUBSUB_ENDPOINT: &str = "https://pubsub.googleapis.com";
+
+/// The OAuth scope that grants access to the Pub/Sub API.
+const PUBSUB_SCOPE: &str = "https://www.googleapis.com/auth/pubsub";
+
+/// The endpoint of the Cloud Storage JSON API.
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// The OAuth scope that grants read and write access to Cloud Storage.
+const GCS_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.read_write";
+
+/// How long the access tokens that the client requests remain valid.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// How long before an access token expires the client replaces it.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// A connection to a Google Cloud Platform project.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct GcpConnection {
+    /// The ID of the project.
+    pub project: String,
+    /// The secret containing the JSON key of the service account with which to
+    /// authenticate. Requests are unauthenticated if absent, which only
+    /// emulators accept.
+    pub credentials: Option<GlobalId>,
+    /// The custom endpoint of the API that the connection is used with, if
+    /// any.
+    pub endpoint: Option<SerdeUri>,
+}
+
+impl RustType<ProtoGcpConnection> for GcpConnection {
+    fn into_proto(&self) -> ProtoGcpConnection {
+        ProtoGcpConnection {
+            project: self.project.clone(),
+            credentials: self.credentials.into_proto(),
+            endpoint: self.endpoint.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoGcpConnection) -> Result<Self, TryFromProtoError> {
+        Ok(GcpConnection {
+            project: proto.project,
+            credentials: proto.credentials.into_rust()?,
+            endpoint: proto.endpoint.into_rust()?,
+        })
+    }
+}
+
+impl GcpConnection {
+    /// Returns a client for the Pub/Sub API of the project.
+    pub async fn pubsub_client(
+        &self,
+        secrets_reader: &dyn SecretsReader,
+    ) -> Result<PubSubClient, anyhow::Error> {
+        let http = reqwest::Client::new();
+        Ok(PubSubClient {
+            endpoint: self.endpoint_or(PUBSUB_ENDPOINT),
+            project: self.project.clone(),
+            tokens: self
+                .access_tokens(http.clone(), PUBSUB_SCOPE, secrets_reader)
+                .await?,
+            http,
+        })
+    }
+
+    /// Returns a client for the Cloud Storage API.
+    pub async fn gcs_client(
+        &self,
+        secrets_reader: &dyn SecretsReader,
+    ) -> Result<GcsClient, anyhow::Error> {
+        let http = reqwest::Client::new();
+        Ok(GcsClient {
+            endpoint: self.endpoint_or(GCS_ENDPOINT),
+            tokens: self
+                .access_tokens(http.clone(), GCS_SCOPE, secrets_reader)
+                .await?,
+            http,
+        })
+    }
+
+    fn endpoint_or(&self, default: &str) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.0.to_string().trim_end_matches('/').to_string(),
+            None => default.into(),
+        }
+    }
+
+    async fn access_tokens(
+        &self,
+        http: reqwest::Client,
+        scope: &'static str,
+        secrets_reader: &dyn SecretsReader,
+    ) -> Result<AccessTokens, anyhow::Error> {
+        let key = match self.credentials {
+            Some(credentials) => {
+                let key = secrets_reader.read_string(credentials).await?;
+                Some(
+                    serde_json::from_str(&key)
+                        .context("parsing GCP service account key from CREDENTIALS")?,
+                )
+            }
+            None => None,
+        };
+        Ok(AccessTokens {
+            http,
+            scope,
+            key,
+            token: None,
+        })
+    }
+}
+
+/// The fields of a service account's JSON key that are needed to request
+/// access tokens.
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Serialize)]
+struct TokenClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// A Pub/Sub subscription.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+    /// The topic from which the subscription receives messages.
+    pub topic: String,
+    /// Whether messages that share an ordering key are delivered in the order
+    /// in which they were published.
+    #[serde(default)]
+    pub enable_message_ordering: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullResponse {
+    #[serde(default)]
+    received_messages: Vec<ReceivedMessage>,
+}
+
+/// A message pulled from a subscription.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedMessage {
+    /// The ID with which to acknowledge the message, or to modify its ack
+    /// deadline.
+    pub ack_id: String,
+    pub message: PubSubMessage,
+}
+
+/// A message published to a topic.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PubSubMessage {
+    /// The base64-encoded payload of the message.
+    #[serde(default)]
+    pub data: String,
+    /// The key that orders the message with respect to the other messages
+    /// that share it, if any.
+    #[serde(default)]
+    pub ordering_key: String,
+}
+
+impl PubSubMessage {
+    /// Decodes the payload of the message.
+    pub fn payload(&self) -> Result<Vec<u8>, anyhow::Error> {
+        base64::decode(&self.data).context("decoding Pub/Sub message data")
+    }
+}
+
+/// A client for the subscriptions of a project's Pub/Sub API.
+pub struct PubSubClient {
+    http: reqwest::Client,
+    endpoint: String,
+    project: String,
+    tokens: AccessTokens,
+}
+
+impl PubSubClient {
+    /// Fetches the configuration of a subscription.
+    pub async fn get_subscription(
+        &mut self,
+        subscription: &str,
+    ) -> Result<Subscription, anyhow::Error> {
+        let url = self.subscription_url(subscription, "");
+        let response = self.send(self.http.get(url)).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Pulls up to `max_messages` messages from a subscription, waiting for
+    /// messages to become available if there are none.
+    pub async fn pull(
+        &mut self,
+        subscription: &str,
+        max_messages: usize,
+    ) -> Result<Vec<ReceivedMessage>, anyhow::Error> {
+        let url = self.subscription_url(subscription, ":pull");
+        let body = serde_json::json!({ "maxMessages": max_messages });
+        let response = self.send(self.http.post(url).json(&body)).await?;
+        let response: PullResponse = response.json().await?;
+        Ok(response.received_messages)
+    }
+
+    /// Acknowledges messages, so that they are not redelivered.
+    pub async fn acknowledge(
+        &mut self,
+        subscription: &str,
+        ack_ids: &[String],
+    ) -> Result<(), anyhow::Error> {
+        let url = self.subscription_url(subscription, ":acknowledge");
+        let body = serde_json::json!({ "ackIds": ack_ids });
+        self.send(self.http.post(url).json(&body)).await?;
+        Ok(())
+    }
+
+    /// Sets the deadline by which messages must be acknowledged before they
+    /// are redelivered to `deadline` from now.
+    pub async fn modify_ack_deadline(
+        &mut self,
+        subscription: &str,
+        ack_ids: &[String],
+        deadline: Duration,
+    ) -> Result<(), anyhow::Error> {
+        let url = self.subscription_url(subscription, ":modifyAckDeadline");
+        let body = serde_json::json!({
+            "ackIds": ack_ids,
+            "ackDeadlineSeconds": deadline.as_secs(),
+        });
+        self.send(self.http.post(url).json(&body)).await?;
+        Ok(())
+    }
+
+    fn subscription_url(&self, subscription: &str, method: &str) -> String {
+        format!(
+            "{}/v1/projects/{}/subscriptions/{}{}",
+            self.endpoint, self.project, subscription, method
+        )
+    }
+
+    /// Sends an authenticated request, returning an error that includes the
+    /// response body if it is unsuccessful.
+    async fn send(
+        &mut self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, anyhow::Error> {
+        self.tokens.send("Pub/Sub", request).await
+    }
+}
+
+#[derive(Deserialize)]
+struct ListObjectsResponse {
+    #[serde(default)]
+    items: Vec<ObjectResource>,
+    #[serde(rename = "nextPageToken")]
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ObjectResource {
+    name: String,
+}
+
+/// A client for the buckets of the Cloud Storage JSON API.
+pub struct GcsClient {
+    http: reqwest::Client,
+    endpoint: String,
+    tokens: AccessTokens,
+}
+
+impl GcsClient {
+    /// Returns the names of the objects in `bucket` whose names start with
+    /// `prefix`.
+    pub async fn list_objects(
+        &mut self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let mut names = vec![];
+        let mut page_token = None;
+        loop {
+            let request = self.list_objects_request(bucket, prefix, page_token);
+            let response = self.tokens.send("Cloud Storage", request).await?;
+            let response: ListObjectsResponse = response.json().await?;
+            names.extend(response.items.into_iter().map(|object| object.name));
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                return Ok(names);
+            }
+        }
+    }
+
+    /// Uploads `contents` as the object `name` in `bucket`, replacing any
+    /// object of that name.
+    pub async fn upload_object(
+        &mut self,
+        bucket: &str,
+        name: &str,
+        contents: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let request = self.upload_object_request(bucket, name, contents);
+        self.tokens.send("Cloud Storage", request).await?;
+        Ok(())
+    }
+
+    /// Returns the request for the page of the objects in `bucket` that
+    /// `page_token` identifies, or for the first page if there is none.
+    fn list_objects_request(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        page_token: Option<String>,
+    ) -> reqwest::RequestBuilder {
+        let url = format!("{}/storage/v1/b/{}/o", self.endpoint, bucket);
+        let mut query = vec![("prefix", prefix.to_string())];
+        if let Some(page_token) = page_token {
+            query.push(("pageToken", page_token));
+        }
+        self.http.get(url).query(&query)
+    }
+
+    /// Returns the request that uploads `contents` as the object `name` in
+    /// `bucket`.
+    fn upload_object_request(
+        &self,
+        bucket: &str,
+        name: &str,
+        contents: Vec<u8>,
+    ) -> reqwest::RequestBuilder {
+        let url = format!("{}/upload/storage/v1/b/{}/o", self.endpoint, bucket);
+        self.http
+            .post(url)
+            .query(&[("uploadType", "media"), ("name", name)])
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(contents)
+    }
+}
+
+/// The access tokens with which a client authenticates as a service account.
+struct AccessTokens {
+    http: reqwest::Client,
+    /// The OAuth scope that the tokens grant.
+    scope: &'static str,
+    key: Option<ServiceAccountKey>,
+    /// The current access token, and the time at which it expires.
+    token: Option<(String, SystemTime)>,
+}
+
+impl AccessTokens {
+    /// Sends a request to the named API, authenticated if the connection has
+    /// credentials, returning an error that includes the response body if it
+    /// is unsuccessful.
+    async fn send(
+        &mut self,
+        api: &str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, anyhow::Error> {
+        let response = self.authorize(request).await?.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("{} request failed with {}: {}", api, status, body));
+        }
+        Ok(response)
+    }
+
+    /// Adds the bearer token with which to authenticate to `request`, if the
+    /// connection has credentials.
+    async fn authorize(
+        &mut self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, anyhow::Error> {
+        Ok(match self.access_token().await? {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+
+    /// Returns an access token, exchanging a token signed with the service
+    /// account's key for a new one if the current one is about to expire.
+    async fn access_token(&mut self) -> Result<Option<String>, anyhow::Error> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        let now = SystemTime::now();
+        if let Some((token, expires_at)) = &self.token {
+            if now + TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(Some(token.clone()));
+            }
+        }
+
+        let iat = now.duration_since(UNIX_EPOCH)?.as_secs();
+        let claims = TokenClaims {
+            iss: &key.client_email,
+            scope: self.scope,
+            aud: &key.token_uri,
+            iat,
+            exp: iat + TOKEN_LIFETIME.as_secs(),
+        };
+        let assertion = jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+            &claims,
+            &jsonwebtoken::EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+                .context("parsing GCP service account private key")?,
+        )?;
+        let response = self
+            .http
+            .post(&key.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &assertion),
+            ])
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "requesting GCP access token failed with {}: {}",
+                status,
+                body
+            ));
+        }
+        let response: TokenResponse = response.json().await?;
+        self.token = Some((response.access_token.clone(), now + TOKEN_LIFETIME));
+        Ok(Some(response.access_token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(endpoint: Option<&str>) -> GcpConnection {
+        GcpConnection {
+            project: "proj".into(),
+            credentials: None,
+            endpoint: endpoint.map(|endpoint| SerdeUri(endpoint.parse().unwrap())),
+        }
+    }
+
+    fn access_tokens(key: Option<ServiceAccountKey>) -> AccessTokens {
+        AccessTokens {
+            http: reqwest::Client::new(),
+            scope: GCS_SCOPE,
+            key,
+            token: None,
+        }
+    }
+
+    fn service_account_key() -> ServiceAccountKey {
+        ServiceAccountKey {
+            client_email: "sink@proj.iam.gserviceaccount.com".into(),
+            private_key: "not a key".into(),
+            token_uri: "http://127.0.0.1:1/token".into(),
+        }
+    }
+
+    #[test]
+    fn test_endpoint_or() {
+        assert_eq!(connection(None).endpoint_or(GCS_ENDPOINT), GCS_ENDPOINT);
+        for endpoint in ["http://localhost:8085", "http://localhost:8085/"] {
+            assert_eq!(
+                connection(Some(endpoint)).endpoint_or(PUBSUB_ENDPOINT),
+                "http://localhost:8085"
+            );
+        }
+    }
+
+    #[test]
+    fn test_subscription_url() {
+        let client = PubSubClient {
+            http: reqwest::Client::new(),
+            endpoint: PUBSUB_ENDPOINT.into(),
+            project: "proj".into(),
+            tokens: access_tokens(None),
+        };
+        assert_eq!(
+            client.subscription_url("sub", ""),
+            "https://pubsub.googleapis.com/v1/projects/proj/subscriptions/sub"
+        );
+        assert_eq!(
+            client.subscription_url("sub", ":pull"),
+            "https://pubsub.googleapis.com/v1/projects/proj/subscriptions/sub:pull"
+        );
+    }
+
+    #[test]
+    fn test_gcs_requests() {
+        let client = GcsClient {
+            http: reqwest::Client::new(),
+            endpoint: GCS_ENDPOINT.into(),
+            tokens: access_tokens(None),
+        };
+
+        let request = client
+            .list_objects_request("lake", "out/a b", None)
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::GET);
+        assert_eq!(
+            request.url().as_str(),
+            "https://storage.googleapis.com/storage/v1/b/lake/o?prefix=out%2Fa+b"
+        );
+
+        let request = client
+            .list_objects_request("lake", "", Some("next&page".into()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://storage.googleapis.com/storage/v1/b/lake/o?prefix=&pageToken=next%26page"
+        );
+
+        // Object names are passed as a query parameter, so the slashes that
+        // separate their components are escaped.
+        let request = client
+            .upload_object_request("lake", "out/u1-1.csv", b"a,b".to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(
+            request.url().as_str(),
+            "https://storage.googleapis.com/upload/storage/v1/b/lake/o?uploadType=media&name=out%2Fu1-1.csv"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::CONTENT_TYPE],
+            "application/octet-stream"
+        );
+        assert_eq!(
+            request.body().and_then(|body| body.as_bytes()),
+            Some(&b"a,b"[..])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authorize() {
+        let http = reqwest::Client::new();
+
+        // Without credentials, requests are unauthenticated.
+        let mut tokens = access_tokens(None);
+        let request = tokens
+            .authorize(http.get(GCS_ENDPOINT))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(request
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .is_none());
+
+        // A token that remains valid for longer than the refresh margin is
+        // reused.
+        let mut tokens = access_tokens(Some(service_account_key()));
+        tokens.token = Some((
+            "cached".into(),
+            SystemTime::now() + TOKEN_REFRESH_MARGIN * 2,
+        ));
+        let request = tokens
+            .authorize(http.get(GCS_ENDPOINT))
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.headers()[reqwest::header::AUTHORIZATION],
+            "Bearer cached"
+        );
+
+        // A token that is about to expire is replaced, which requires signing
+        // a new assertion with the service account's private key.
+        tokens.token = Some(("stale".into(), SystemTime::now() + TOKEN_REFRESH_MARGIN / 2));
+        let err = tokens.authorize(http.get(GCS_ENDPOINT)).await.unwrap_err();
+        assert_eq!(err.to_string(), "parsing GCP service account private key");
+    }
+}
@@ -10,6 +10,7 @@
 //! Types and traits related to reporting changing collections out of `dataflow`.
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use mz_persist_client::ShardId;
 use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
@@ -19,10 +20,16 @@ use timely::progress::frontier::Antichain;
 use timely::PartialOrder;
 
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
-use mz_repr::{GlobalId, RelationDesc};
+use mz_repr::{GlobalId, RelationDesc, ScalarType};
 
 use crate::controller::CollectionMetadata;
-use crate::types::connections::{CsrConnection, KafkaConnection};
+use crate::types::connections::aws::AwsConfig;
+use crate::types::connections::azure::AzureBlobConnection;
+use crate::types::connections::gcp::GcpConnection;
+use crate::types::connections::{
+    CsrConnection, ElasticsearchConnection, KafkaConnection, NatsConnection, PostgresConnection,
+    RedisConnection,
+};
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -34,9 +41,13 @@ include!(concat!(
 pub struct StorageSinkDesc<S: StorageSinkDescFillState, T = mz_repr::Timestamp> {
     pub from: GlobalId,
     pub from_desc: RelationDesc,
+    /// The indices of the columns of `from` to write to the sink, if not all
+    /// of them.
+    pub projection: Option<Vec<usize>>,
     pub connection: StorageSinkConnection,
     pub envelope: Option<SinkEnvelope>,
     pub as_of: SinkAsOf<T>,
+    pub error_policy: SinkErrorPolicy,
     pub status_id: Option<<S as StorageSinkDescFillState>::StatusId>,
     pub from_storage_metadata: <S as StorageSinkDescFillState>::StorageMetadata,
 }
@@ -68,9 +79,11 @@ impl Arbitrary for StorageSinkDesc<MetadataFilled, mz_repr::Timestamp> {
         (
             any::<GlobalId>(),
             any::<RelationDesc>(),
+            any::<Option<Vec<usize>>>(),
             any::<StorageSinkConnection>(),
             any::<Option<SinkEnvelope>>(),
             any::<SinkAsOf<mz_repr::Timestamp>>(),
+            any::<SinkErrorPolicy>(),
             any::<Option<ShardId>>(),
             any::<CollectionMetadata>(),
         )
@@ -78,18 +91,22 @@ impl Arbitrary for StorageSinkDesc<MetadataFilled, mz_repr::Timestamp> {
                 |(
                     from,
                     from_desc,
+                    projection,
                     connection,
                     envelope,
                     as_of,
+                    error_policy,
                     status_id,
                     from_storage_metadata,
                 )| {
                     StorageSinkDesc {
                         from,
                         from_desc,
+                        projection,
                         connection,
                         envelope,
                         as_of,
+                        error_policy,
                         status_id,
                         from_storage_metadata,
                     }
@@ -105,8 +122,10 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<MetadataFilled, mz_repr:
             connection: Some(self.connection.into_proto()),
             from: Some(self.from.into_proto()),
             from_desc: Some(self.from_desc.into_proto()),
+            projection: self.projection.into_proto(),
             envelope: self.envelope.into_proto(),
             as_of: Some(self.as_of.into_proto()),
+            error_policy: Some(self.error_policy.into_proto()),
             status_id: self.status_id.into_proto(),
             from_storage_metadata: Some(self.from_storage_metadata.into_proto()),
         }
@@ -118,6 +137,7 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<MetadataFilled, mz_repr:
             from_desc: proto
                 .from_desc
                 .into_rust_if_some("ProtoStorageSinkDesc::from_desc")?,
+            projection: proto.projection.into_rust()?,
             connection: proto
                 .connection
                 .into_rust_if_some("ProtoStorageSinkDesc::connection")?,
@@ -125,6 +145,9 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<MetadataFilled, mz_repr:
             as_of: proto
                 .as_of
                 .into_rust_if_some("ProtoStorageSinkDesc::as_of")?,
+            error_policy: proto
+                .error_policy
+                .into_rust_if_some("ProtoStorageSinkDesc::error_policy")?,
             status_id: proto.status_id.into_rust()?,
             from_storage_metadata: proto
                 .from_storage_metadata
@@ -133,6 +156,18 @@ impl RustType<ProtoStorageSinkDesc> for StorageSinkDesc<MetadataFilled, mz_repr:
     }
 }
 
+impl RustType<ProtoSinkProjection> for Vec<usize> {
+    fn into_proto(&self) -> ProtoSinkProjection {
+        ProtoSinkProjection {
+            columns: self.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkProjection) -> Result<Self, TryFromProtoError> {
+        proto.columns.into_rust()
+    }
+}
+
 #[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SinkEnvelope {
     Debezium,
@@ -162,6 +197,55 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
     }
 }
 
+/// What a sink does when a write to its external system fails persistently.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SinkErrorPolicy {
+    /// Reports the sink as stalled and restarts it, retrying the write until
+    /// it succeeds.
+    Retry,
+    /// Reports the sink as paused and stops writing, holding back the sink's
+    /// write frontier until the sink is restarted.
+    Pause,
+    /// Discards the updates that could not be written, counting them in the
+    /// sink's statistics, and carries on with later updates.
+    Drop,
+}
+
+impl SinkErrorPolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SinkErrorPolicy::Retry => "retry",
+            SinkErrorPolicy::Pause => "pause",
+            SinkErrorPolicy::Drop => "drop",
+        }
+    }
+}
+
+impl RustType<ProtoSinkErrorPolicy> for SinkErrorPolicy {
+    fn into_proto(&self) -> ProtoSinkErrorPolicy {
+        use proto_sink_error_policy::Kind;
+        ProtoSinkErrorPolicy {
+            kind: Some(match self {
+                SinkErrorPolicy::Retry => Kind::Retry(()),
+                SinkErrorPolicy::Pause => Kind::Pause(()),
+                SinkErrorPolicy::Drop => Kind::Drop(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoSinkErrorPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_sink_error_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoSinkErrorPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Retry(()) => SinkErrorPolicy::Retry,
+            Kind::Pause(()) => SinkErrorPolicy::Pause,
+            Kind::Drop(()) => SinkErrorPolicy::Drop,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SinkAsOf<T = mz_repr::Timestamp> {
     pub frontier: Antichain<T>,
@@ -220,6 +304,11 @@ impl RustType<ProtoSinkAsOf> for SinkAsOf<mz_repr::Timestamp> {
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum StorageSinkConnection {
     Kafka(KafkaSinkConnection),
+    Object(ObjectSinkConnection),
+    Postgres(PostgresSinkConnection),
+    Elasticsearch(ElasticsearchSinkConnection),
+    Redis(RedisSinkConnection),
+    Nats(NatsSinkConnection),
 }
 
 impl StorageSinkConnection {
@@ -228,6 +317,13 @@ impl StorageSinkConnection {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Object(object) => object.store.connection_id(),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Elasticsearch(ElasticsearchSinkConnection { connection_id, .. }) => {
+                Some(*connection_id)
+            }
+            Redis(RedisSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Nats(NatsSinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 
@@ -235,6 +331,11 @@ impl StorageSinkConnection {
     pub fn name(&self) -> &'static str {
         match self {
             StorageSinkConnection::Kafka(_) => "kafka",
+            StorageSinkConnection::Object(object) => object.store.name(),
+            StorageSinkConnection::Postgres(_) => "postgres",
+            StorageSinkConnection::Elasticsearch(_) => "elasticsearch",
+            StorageSinkConnection::Redis(_) => "redis",
+            StorageSinkConnection::Nats(_) => "nats",
         }
     }
 }
@@ -245,6 +346,13 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 StorageSinkConnection::Kafka(kafka) => Kind::Kafka(kafka.into_proto()),
+                StorageSinkConnection::Object(object) => Kind::Object(object.into_proto()),
+                StorageSinkConnection::Postgres(postgres) => Kind::Postgres(postgres.into_proto()),
+                StorageSinkConnection::Elasticsearch(elasticsearch) => {
+                    Kind::Elasticsearch(elasticsearch.into_proto())
+                }
+                StorageSinkConnection::Redis(redis) => Kind::Redis(redis.into_proto()),
+                StorageSinkConnection::Nats(nats) => Kind::Nats(nats.into_proto()),
             }),
         }
     }
@@ -256,6 +364,13 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoStorageSinkConnection::kind"))?;
         Ok(match kind {
             Kind::Kafka(kafka) => StorageSinkConnection::Kafka(kafka.into_rust()?),
+            Kind::Object(object) => StorageSinkConnection::Object(object.into_rust()?),
+            Kind::Postgres(postgres) => StorageSinkConnection::Postgres(postgres.into_rust()?),
+            Kind::Elasticsearch(elasticsearch) => {
+                StorageSinkConnection::Elasticsearch(elasticsearch.into_rust()?)
+            }
+            Kind::Redis(redis) => StorageSinkConnection::Redis(redis.into_rust()?),
+            Kind::Nats(nats) => StorageSinkConnection::Nats(nats.into_rust()?),
         })
     }
 }
@@ -286,10 +401,18 @@ pub struct KafkaSinkConnection {
     pub relation_key_indices: Option<Vec<usize>>,
     pub value_desc: RelationDesc,
     pub published_schema_info: Option<PublishedSchemaInfo>,
+    /// The CSV dialect in which messages are encoded, if they are encoded as
+    /// CSV records rather than as JSON. Only present for sinks that did not
+    /// publish schemas.
+    pub csv_format: Option<KafkaSinkCsvFormat>,
     pub progress: KafkaSinkProgressConnection,
+    /// The topic to which the sink reports the timestamps through which its
+    /// topic is complete, if any.
+    pub consistency_topic: Option<String>,
     // Maximum number of records the sink will attempt to send each time it is
     // invoked
     pub fuel: usize,
+    pub partition_strategy: KafkaSinkPartitionStrategy,
 }
 
 proptest::prop_compose! {
@@ -301,8 +424,11 @@ proptest::prop_compose! {
         relation_key_indices in any::<Option<Vec<usize>>>(),
         value_desc in any::<RelationDesc>(),
         published_schema_info in any::<Option<PublishedSchemaInfo>>(),
+        csv_format in any::<Option<KafkaSinkCsvFormat>>(),
         progress in any::<KafkaSinkProgressConnection>(),
+        consistency_topic in any::<Option<String>>(),
         fuel in any::<usize>(),
+        partition_strategy in any::<KafkaSinkPartitionStrategy>(),
     ) -> KafkaSinkConnection {
         KafkaSinkConnection {
             connection,
@@ -312,8 +438,11 @@ proptest::prop_compose! {
             relation_key_indices,
             value_desc,
             published_schema_info,
+            csv_format,
             progress,
+            consistency_topic,
             fuel,
+            partition_strategy,
         }
     }
 }
@@ -371,8 +500,11 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
             relation_key_indices: self.relation_key_indices.into_proto(),
             value_desc: Some(self.value_desc.into_proto()),
             published_schema_info: self.published_schema_info.into_proto(),
+            csv_format: self.csv_format.into_proto(),
             progress: Some(self.progress.into_proto()),
+            consistency_topic: self.consistency_topic.clone(),
             fuel: self.fuel.into_proto(),
+            partition_strategy: Some(self.partition_strategy.into_proto()),
         }
     }
 
@@ -391,10 +523,56 @@ impl RustType<ProtoKafkaSinkConnection> for KafkaSinkConnection {
                 .value_desc
                 .into_rust_if_some("ProtoKafkaSinkConnection::addrs")?,
             published_schema_info: proto.published_schema_info.into_rust()?,
+            csv_format: proto.csv_format.into_rust()?,
             progress: proto
                 .progress
                 .into_rust_if_some("ProtoKafkaSinkConnection::progress")?,
+            consistency_topic: proto.consistency_topic,
             fuel: proto.fuel.into_rust()?,
+            partition_strategy: proto
+                .partition_strategy
+                .into_rust_if_some("ProtoKafkaSinkConnection::partition_strategy")?,
+        })
+    }
+}
+
+/// How a Kafka sink chooses the partition of the topic to which it writes each
+/// message.
+#[derive(Arbitrary, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum KafkaSinkPartitionStrategy {
+    /// librdkafka's default partitioner, which hashes the message key with
+    /// CRC32, and chooses a random partition for messages without a key.
+    Hash,
+    /// The partitioner of the Java client, which hashes the message key with
+    /// murmur2, so that messages are partitioned like those that Java
+    /// producers write to other topics.
+    Murmur2,
+    /// Messages are written to each partition in turn, regardless of their
+    /// key.
+    RoundRobin,
+}
+
+impl RustType<ProtoKafkaSinkPartitionStrategy> for KafkaSinkPartitionStrategy {
+    fn into_proto(&self) -> ProtoKafkaSinkPartitionStrategy {
+        use proto_kafka_sink_partition_strategy::Kind;
+        ProtoKafkaSinkPartitionStrategy {
+            kind: Some(match self {
+                KafkaSinkPartitionStrategy::Hash => Kind::Hash(()),
+                KafkaSinkPartitionStrategy::Murmur2 => Kind::Murmur2(()),
+                KafkaSinkPartitionStrategy::RoundRobin => Kind::RoundRobin(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkPartitionStrategy) -> Result<Self, TryFromProtoError> {
+        use proto_kafka_sink_partition_strategy::Kind;
+        let kind = proto.kind.ok_or_else(|| {
+            TryFromProtoError::missing_field("ProtoKafkaSinkPartitionStrategy::kind")
+        })?;
+        Ok(match kind {
+            Kind::Hash(()) => KafkaSinkPartitionStrategy::Hash,
+            Kind::Murmur2(()) => KafkaSinkPartitionStrategy::Murmur2,
+            Kind::RoundRobin(()) => KafkaSinkPartitionStrategy::RoundRobin,
         })
     }
 }
@@ -422,9 +600,663 @@ impl RustType<ProtoPublishedSchemaInfo> for PublishedSchemaInfo {
     }
 }
 
+/// The CSV dialect of a sink that encodes its messages as CSV records.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KafkaSinkCsvFormat {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// The string that encodes nulls.
+    pub null: String,
+    /// The field names of the header record that begins each file, if files
+    /// begin with one. Only object sinks write headers.
+    pub header: Option<Vec<String>>,
+}
+
+impl RustType<ProtoKafkaSinkCsvFormat> for KafkaSinkCsvFormat {
+    fn into_proto(&self) -> ProtoKafkaSinkCsvFormat {
+        ProtoKafkaSinkCsvFormat {
+            delimiter: self.delimiter.into_proto(),
+            quote: self.quote.into_proto(),
+            null: self.null.clone(),
+            header: self
+                .header
+                .as_ref()
+                .map(|names| proto_kafka_sink_csv_format::ProtoHeader {
+                    names: names.clone(),
+                }),
+        }
+    }
+
+    fn from_proto(proto: ProtoKafkaSinkCsvFormat) -> Result<Self, TryFromProtoError> {
+        Ok(KafkaSinkCsvFormat {
+            delimiter: proto.delimiter.into_rust()?,
+            quote: proto.quote.into_rust()?,
+            null: proto.null,
+            header: proto.header.map(|header| header.names),
+        })
+    }
+}
+
+/// A sink that writes the updates to a collection to a sequence of files.
+///
+/// Updates are appended to an in-progress file, which is completed once it
+/// exceeds `max_file_size` or has been open for `rotation_interval`. Files are
+/// only ever completed at timestamp boundaries, and a completed file appears
+/// atomically under its final name, so the completed files of a sink contain
+/// every update up to some timestamp exactly once.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ObjectSinkConnection {
+    pub store: ObjectStoreConnection,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+    pub format: ObjectSinkFormat,
+    /// The size, in bytes, past which a file is completed.
+    pub max_file_size: u64,
+    /// How long a file may stay open before it is completed.
+    pub rotation_interval: Duration,
+}
+
+impl RustType<ProtoObjectSinkConnection> for ObjectSinkConnection {
+    fn into_proto(&self) -> ProtoObjectSinkConnection {
+        ProtoObjectSinkConnection {
+            store: Some(self.store.into_proto()),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+            format: Some(self.format.into_proto()),
+            max_file_size: self.max_file_size,
+            rotation_interval: Some(self.rotation_interval.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoObjectSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(ObjectSinkConnection {
+            store: proto
+                .store
+                .into_rust_if_some("ProtoObjectSinkConnection::store")?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoObjectSinkConnection::value_desc")?,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoObjectSinkConnection::format")?,
+            max_file_size: proto.max_file_size,
+            rotation_interval: proto
+                .rotation_interval
+                .into_rust_if_some("ProtoObjectSinkConnection::rotation_interval")?,
+        })
+    }
+}
+
+/// The place to which an object sink writes its files.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ObjectStoreConnection {
+    /// A directory on the filesystem of the process that runs the sink.
+    Directory { path: String },
+    /// A prefix of the keys in an S3 bucket.
+    S3 {
+        connection_id: GlobalId,
+        connection: AwsConfig,
+        bucket: String,
+        prefix: String,
+    },
+    /// A prefix of the object names in a Google Cloud Storage bucket.
+    Gcs {
+        connection_id: GlobalId,
+        connection: GcpConnection,
+        bucket: String,
+        prefix: String,
+    },
+    /// A prefix of the blob names in an Azure Blob Storage container.
+    AzureBlob {
+        connection_id: GlobalId,
+        connection: AzureBlobConnection,
+        container: String,
+        prefix: String,
+    },
+}
+
+impl ObjectStoreConnection {
+    /// Returns the ID of the connection with which the store is accessed, if
+    /// any.
+    pub fn connection_id(&self) -> Option<GlobalId> {
+        match self {
+            ObjectStoreConnection::Directory { .. } => None,
+            ObjectStoreConnection::S3 { connection_id, .. }
+            | ObjectStoreConnection::Gcs { connection_id, .. }
+            | ObjectStoreConnection::AzureBlob { connection_id, .. } => Some(*connection_id),
+        }
+    }
+
+    /// Returns the name of the store.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectStoreConnection::Directory { .. } => "file",
+            ObjectStoreConnection::S3 { .. } => "s3",
+            ObjectStoreConnection::Gcs { .. } => "gcs",
+            ObjectStoreConnection::AzureBlob { .. } => "azure-blob",
+        }
+    }
+}
+
+impl RustType<ProtoObjectStoreConnection> for ObjectStoreConnection {
+    fn into_proto(&self) -> ProtoObjectStoreConnection {
+        use proto_object_store_connection::{Kind, ProtoAzureBlob, ProtoGcs, ProtoS3};
+        ProtoObjectStoreConnection {
+            kind: Some(match self {
+                ObjectStoreConnection::Directory { path } => Kind::Directory(path.clone()),
+                ObjectStoreConnection::S3 {
+                    connection_id,
+                    connection,
+                    bucket,
+                    prefix,
+                } => Kind::S3(ProtoS3 {
+                    connection_id: Some(connection_id.into_proto()),
+                    connection: Some(connection.into_proto()),
+                    bucket: bucket.clone(),
+                    prefix: prefix.clone(),
+                }),
+                ObjectStoreConnection::Gcs {
+                    connection_id,
+                    connection,
+                    bucket,
+                    prefix,
+                } => Kind::Gcs(ProtoGcs {
+                    connection_id: Some(connection_id.into_proto()),
+                    connection: Some(connection.into_proto()),
+                    bucket: bucket.clone(),
+                    prefix: prefix.clone(),
+                }),
+                ObjectStoreConnection::AzureBlob {
+                    connection_id,
+                    connection,
+                    container,
+                    prefix,
+                } => Kind::AzureBlob(ProtoAzureBlob {
+                    connection_id: Some(connection_id.into_proto()),
+                    connection: Some(connection.into_proto()),
+                    container: container.clone(),
+                    prefix: prefix.clone(),
+                }),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoObjectStoreConnection) -> Result<Self, TryFromProtoError> {
+        use proto_object_store_connection::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoObjectStoreConnection::kind"))?;
+        Ok(match kind {
+            Kind::Directory(path) => ObjectStoreConnection::Directory { path },
+            Kind::S3(s3) => ObjectStoreConnection::S3 {
+                connection_id: s3
+                    .connection_id
+                    .into_rust_if_some("ProtoS3::connection_id")?,
+                connection: s3.connection.into_rust_if_some("ProtoS3::connection")?,
+                bucket: s3.bucket,
+                prefix: s3.prefix,
+            },
+            Kind::Gcs(gcs) => ObjectStoreConnection::Gcs {
+                connection_id: gcs
+                    .connection_id
+                    .into_rust_if_some("ProtoGcs::connection_id")?,
+                connection: gcs.connection.into_rust_if_some("ProtoGcs::connection")?,
+                bucket: gcs.bucket,
+                prefix: gcs.prefix,
+            },
+            Kind::AzureBlob(azure) => ObjectStoreConnection::AzureBlob {
+                connection_id: azure
+                    .connection_id
+                    .into_rust_if_some("ProtoAzureBlob::connection_id")?,
+                connection: azure
+                    .connection
+                    .into_rust_if_some("ProtoAzureBlob::connection")?,
+                container: azure.container,
+                prefix: azure.prefix,
+            },
+        })
+    }
+}
+
+/// The format of the files that an object sink writes.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ObjectSinkFormat {
+    /// One JSON object per line, holding the `before` and `after` states of
+    /// an update.
+    Json,
+    /// One CSV record per changed row, whose first field is the change in the
+    /// row's multiplicity.
+    Csv(KafkaSinkCsvFormat),
+}
+
+impl RustType<ProtoObjectSinkFormat> for ObjectSinkFormat {
+    fn into_proto(&self) -> ProtoObjectSinkFormat {
+        use proto_object_sink_format::Kind;
+        ProtoObjectSinkFormat {
+            kind: Some(match self {
+                ObjectSinkFormat::Json => Kind::Json(()),
+                ObjectSinkFormat::Csv(csv) => Kind::Csv(csv.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoObjectSinkFormat) -> Result<Self, TryFromProtoError> {
+        use proto_object_sink_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoObjectSinkFormat::kind"))?;
+        Ok(match kind {
+            Kind::Json(()) => ObjectSinkFormat::Json,
+            Kind::Csv(csv) => ObjectSinkFormat::Csv(csv.into_rust()?),
+        })
+    }
+}
+
+/// A sink that maintains a table in a PostgreSQL database whose contents
+/// match the sinked collection.
+///
+/// The updates at each timestamp are applied to the table as upserts and
+/// deletes keyed by `key_desc_and_indices`, in transactions that also record
+/// the latest timestamp that the table reflects in a progress table. The
+/// progress table lives in the same schema as the sinked table.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSinkConnection {
+    pub connection_id: GlobalId,
+    pub connection: PostgresConnection,
+    /// The schema of the sinked table.
+    pub schema: String,
+    /// The name of the sinked table.
+    pub table: String,
+    /// The columns on whose values rows of the table are upserted.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    pub value_desc: RelationDesc,
+}
+
+impl PostgresSinkConnection {
+    /// The name of the table, in the sinked table's schema, in which sinks
+    /// record their progress.
+    pub const PROGRESS_TABLE: &'static str = "mz_sink_progress";
+
+    /// Returns the quoted, schema-qualified name of the sinked table.
+    pub fn qualified_table(&self) -> String {
+        format!(
+            "{}.{}",
+            mz_postgres_util::quote_identifier(&self.schema),
+            mz_postgres_util::quote_identifier(&self.table)
+        )
+    }
+
+    /// Returns the quoted, schema-qualified name of the progress table.
+    pub fn qualified_progress_table(&self) -> String {
+        format!(
+            "{}.{}",
+            mz_postgres_util::quote_identifier(&self.schema),
+            mz_postgres_util::quote_identifier(Self::PROGRESS_TABLE)
+        )
+    }
+}
+
+impl RustType<ProtoPostgresSinkConnection> for PostgresSinkConnection {
+    fn into_proto(&self) -> ProtoPostgresSinkConnection {
+        ProtoPostgresSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPostgresSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PostgresSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection")?,
+            schema: proto.schema,
+            table: proto.table,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoPostgresSinkConnection::key_desc_and_indices")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoPostgresSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// Returns the PostgreSQL type of the columns in which a Postgres sink
+/// stores values of `typ`, or `None` if such values cannot be sinked to
+/// PostgreSQL.
+pub fn postgres_sink_column_type(typ: &ScalarType) -> Option<String> {
+    let name = match typ {
+        ScalarType::Bool => "boolean",
+        ScalarType::Int16 => "smallint",
+        ScalarType::Int32 => "integer",
+        ScalarType::Int64 => "bigint",
+        ScalarType::Float32 => "real",
+        ScalarType::Float64 => "double precision",
+        ScalarType::Numeric { .. } => "numeric",
+        ScalarType::Date => "date",
+        ScalarType::Time => "time",
+        ScalarType::Timestamp => "timestamp",
+        ScalarType::TimestampTz => "timestamptz",
+        ScalarType::Interval => "interval",
+        ScalarType::Bytes => "bytea",
+        ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => "text",
+        ScalarType::Jsonb => "jsonb",
+        ScalarType::Uuid => "uuid",
+        ScalarType::Array(element_type) => {
+            return postgres_sink_column_type(element_type).map(|name| format!("{}[]", name))
+        }
+        _ => return None,
+    };
+    Some(name.into())
+}
+
+/// A sink that maintains an index in an Elasticsearch or OpenSearch cluster
+/// whose documents match the rows of the sinked collection.
+///
+/// Each row is indexed as a document whose ID is derived from the values of
+/// its key columns, and the updates at each timestamp are applied with bulk
+/// requests that index or delete those documents. Once they are applied, the
+/// latest timestamp that the index reflects is recorded in a progress index.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ElasticsearchSinkConnection {
+    pub connection_id: GlobalId,
+    pub connection: ElasticsearchConnection,
+    /// The name of the sinked index.
+    pub index: String,
+    /// The columns from whose values the IDs of documents are derived.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    pub value_desc: RelationDesc,
+}
+
+impl ElasticsearchSinkConnection {
+    /// The name of the index in which sinks record their progress.
+    pub const PROGRESS_INDEX: &'static str = "mz_sink_progress";
+
+    /// Returns the mapping of the sinked index, with a field for each column
+    /// of the sinked collection.
+    pub fn mapping(&self) -> Result<serde_json::Value, anyhow::Error> {
+        let mut properties = serde_json::Map::new();
+        for (name, typ) in self.value_desc.iter() {
+            let mapping = elasticsearch_field_mapping(&typ.scalar_type).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "column {} has type {:?}, which cannot be sinked to Elasticsearch",
+                    name,
+                    typ.scalar_type
+                )
+            })?;
+            properties.insert(name.to_string(), mapping);
+        }
+        Ok(serde_json::json!({ "properties": properties }))
+    }
+}
+
+impl RustType<ProtoElasticsearchSinkConnection> for ElasticsearchSinkConnection {
+    fn into_proto(&self) -> ProtoElasticsearchSinkConnection {
+        ProtoElasticsearchSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            index: self.index.clone(),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoElasticsearchSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(ElasticsearchSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoElasticsearchSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoElasticsearchSinkConnection::connection")?,
+            index: proto.index,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoElasticsearchSinkConnection::key_desc_and_indices")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoElasticsearchSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// Returns the mapping of the fields in which an Elasticsearch sink indexes
+/// values of `typ`, as they are encoded by the JSON sink format, or `None` if
+/// such values cannot be sinked to Elasticsearch.
+pub fn elasticsearch_field_mapping(typ: &ScalarType) -> Option<serde_json::Value> {
+    let field_type = match typ {
+        ScalarType::Bool => "boolean",
+        ScalarType::Int16 => "short",
+        ScalarType::Int32 | ScalarType::UInt16 => "integer",
+        ScalarType::Int64 | ScalarType::UInt32 => "long",
+        ScalarType::Float32 => "float",
+        // Numeric values are encoded as strings, which Elasticsearch coerces.
+        ScalarType::Float64 | ScalarType::Numeric { .. } => "double",
+        // Dates are encoded in ISO 8601 format, and timestamps as milliseconds
+        // since the Unix epoch, both of which the default date format accepts.
+        ScalarType::Date | ScalarType::Timestamp | ScalarType::TimestampTz => "date",
+        ScalarType::Time | ScalarType::Interval | ScalarType::Uuid => "keyword",
+        // Index text for full-text search, as well as for exact matches and
+        // aggregations, as Elasticsearch does for strings it maps dynamically.
+        ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+            return Some(serde_json::json!({
+                "type": "text",
+                "fields": { "keyword": { "type": "keyword", "ignore_above": 256 } },
+            }))
+        }
+        ScalarType::Jsonb => "object",
+        // Any field may hold an array of values.
+        ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
+            return elasticsearch_field_mapping(element_type)
+        }
+        ScalarType::Record { fields, .. } => {
+            let mut properties = serde_json::Map::new();
+            for (name, typ) in fields {
+                properties.insert(
+                    name.to_string(),
+                    elasticsearch_field_mapping(&typ.scalar_type)?,
+                );
+            }
+            return Some(serde_json::json!({ "properties": properties }));
+        }
+        _ => return None,
+    };
+    Some(serde_json::json!({ "type": field_type }))
+}
+
+/// A sink that writes the value of each key of the sinked collection to a
+/// Redis key, and deletes the Redis keys of deleted keys.
+///
+/// The updates at all of the timestamps that a frontier closes are applied in
+/// a single `MULTI` transaction, which also records the latest timestamp that
+/// the Redis keys reflect in the [`RedisSinkConnection::PROGRESS_KEY`] hash.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RedisSinkConnection {
+    pub connection_id: GlobalId,
+    pub connection: RedisConnection,
+    /// The prefix of the Redis key of every value.
+    pub key_prefix: String,
+    pub value_format: RedisSinkValueFormat,
+    /// The columns from whose values the Redis keys are derived.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    pub value_desc: RelationDesc,
+}
+
+impl RedisSinkConnection {
+    /// The Redis key of the hash in which sinks record their progress, keyed
+    /// by the ID of the sink.
+    pub const PROGRESS_KEY: &'static str = "mz_sink_progress";
+}
+
+impl RustType<ProtoRedisSinkConnection> for RedisSinkConnection {
+    fn into_proto(&self) -> ProtoRedisSinkConnection {
+        ProtoRedisSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            key_prefix: self.key_prefix.clone(),
+            value_format: Some(self.value_format.into_proto()),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoRedisSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(RedisSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoRedisSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoRedisSinkConnection::connection")?,
+            key_prefix: proto.key_prefix,
+            value_format: proto
+                .value_format
+                .into_rust_if_some("ProtoRedisSinkConnection::value_format")?,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoRedisSinkConnection::key_desc_and_indices")?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoRedisSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// How a Redis sink stores the value of a key.
+#[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RedisSinkValueFormat {
+    /// A hash with a field for each non-null column, holding the column's
+    /// value in the text format of the PostgreSQL wire protocol.
+    Hash,
+    /// A string holding a JSON object with a field for each column.
+    Json,
+}
+
+impl RustType<ProtoRedisSinkValueFormat> for RedisSinkValueFormat {
+    fn into_proto(&self) -> ProtoRedisSinkValueFormat {
+        use proto_redis_sink_value_format::Kind;
+        ProtoRedisSinkValueFormat {
+            kind: Some(match self {
+                RedisSinkValueFormat::Hash => Kind::Hash(()),
+                RedisSinkValueFormat::Json => Kind::Json(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoRedisSinkValueFormat) -> Result<Self, TryFromProtoError> {
+        use proto_redis_sink_value_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoRedisSinkValueFormat::kind"))?;
+        Ok(match kind {
+            Kind::Hash(()) => RedisSinkValueFormat::Hash,
+            Kind::Json(()) => RedisSinkValueFormat::Json,
+        })
+    }
+}
+
+/// A sink that publishes a message for each update to a subject that a NATS
+/// JetStream stream captures.
+///
+/// Every message carries the timestamp of its update in the
+/// [`NatsSinkConnection::TIMESTAMP_HEADER`] header, from which a restarted
+/// sink determines the updates that it has already published. With
+/// `deduplication_headers`, every message also carries an ID that is unique
+/// to its update, so the stream discards the messages that a restarted sink
+/// publishes again.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NatsSinkConnection {
+    pub connection_id: GlobalId,
+    pub connection: NatsConnection,
+    /// The JetStream stream that captures `subject`.
+    pub stream: String,
+    /// The subject to which messages are published.
+    pub subject: String,
+    /// Whether messages carry the `Nats-Msg-Id` header, with which the stream
+    /// discards duplicate messages.
+    pub deduplication_headers: bool,
+    /// The CSV format of message payloads, if they are not encoded as JSON.
+    pub csv_format: Option<KafkaSinkCsvFormat>,
+    /// The columns whose encoded values messages carry in the
+    /// [`NatsSinkConnection::KEY_HEADER`] header.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl NatsSinkConnection {
+    /// The header that holds the timestamp of the update that a message
+    /// carries.
+    pub const TIMESTAMP_HEADER: &'static str = "Materialize-Timestamp";
+    /// The header that holds the encoded key of the update that a message
+    /// carries.
+    pub const KEY_HEADER: &'static str = "Materialize-Key";
+}
+
+impl RustType<ProtoNatsSinkConnection> for NatsSinkConnection {
+    fn into_proto(&self) -> ProtoNatsSinkConnection {
+        ProtoNatsSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            stream: self.stream.clone(),
+            subject: self.subject.clone(),
+            deduplication_headers: self.deduplication_headers,
+            csv_format: self.csv_format.into_proto(),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoNatsSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(NatsSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoNatsSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoNatsSinkConnection::connection")?,
+            stream: proto.stream,
+            subject: proto.subject,
+            deduplication_headers: proto.deduplication_headers,
+            csv_format: proto.csv_format.into_rust()?,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoNatsSinkConnection::value_desc")?,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnectionBuilder {
     Kafka(KafkaSinkConnectionBuilder),
+    /// Object sinks need no external setup, so they are fully described when
+    /// they are planned.
+    Object(ObjectSinkConnection),
+    /// Postgres sinks create their tables when they are built, but need no
+    /// other information to do so.
+    Postgres(PostgresSinkConnection),
+    /// Elasticsearch sinks create their indexes when they are built, but need
+    /// no other information to do so.
+    Elasticsearch(ElasticsearchSinkConnection),
+    /// Redis sinks need no external setup, but check that the server is
+    /// reachable when they are built.
+    Redis(RedisSinkConnection),
+    /// NATS sinks need no external setup, but check that their stream exists
+    /// when they are built.
+    Nats(NatsSinkConnection),
 }
 
 impl StorageSinkConnectionBuilder {
@@ -433,6 +1265,13 @@ impl StorageSinkConnectionBuilder {
         use StorageSinkConnectionBuilder::*;
         match self {
             Kafka(KafkaSinkConnectionBuilder { connection_id, .. }) => Some(*connection_id),
+            Object(object) => object.store.connection_id(),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Elasticsearch(ElasticsearchSinkConnection { connection_id, .. }) => {
+                Some(*connection_id)
+            }
+            Redis(RedisSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Nats(NatsSinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 
@@ -441,6 +1280,11 @@ impl StorageSinkConnectionBuilder {
         use StorageSinkConnectionBuilder::*;
         match self {
             Kafka(_) => "kafka",
+            Object(object) => object.store.name(),
+            Postgres(_) => "postgres",
+            Elasticsearch(_) => "elasticsearch",
+            Redis(_) => "redis",
+            Nats(_) => "nats",
         }
     }
 }
@@ -462,10 +1306,13 @@ pub struct KafkaSinkConnectionBuilder {
     pub value_desc: RelationDesc,
     pub topic_name: String,
     pub consistency_config: KafkaConsistencyConfig,
+    /// The user-specified topic to which the sink reports its progress.
+    pub consistency_topic: Option<String>,
     pub partition_count: i32,
     pub replication_factor: i32,
     pub fuel: usize,
     pub retention: KafkaSinkConnectionRetention,
+    pub partition_strategy: KafkaSinkPartitionStrategy,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -482,4 +1329,5 @@ pub enum KafkaSinkFormat {
         csr_connection: CsrConnection,
     },
     Json,
+    Csv(KafkaSinkCsvFormat),
 }
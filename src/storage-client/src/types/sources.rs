@@ -33,7 +33,7 @@ use timely::progress::{PathSummary, Timestamp};
 use timely::scheduling::ActivateOnDrop;
 use uuid::Uuid;
 
-use mz_expr::{MirScalarExpr, PartitionId};
+use mz_expr::{MapFilterProject, MirScalarExpr, PartitionId};
 use mz_ore::now::NowFn;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::write::WriteHandle;
@@ -45,7 +45,10 @@ use mz_timely_util::order::{Interval, Partitioned, RangeBound};
 
 use crate::controller::{CollectionMetadata, ResumptionFrontierCalculator};
 use crate::types::connections::aws::AwsConfig;
-use crate::types::connections::{KafkaConnection, PostgresConnection};
+use crate::types::connections::gcp::GcpConnection;
+use crate::types::connections::{
+    KafkaConnection, MqttConnection, MySqlConnection, PostgresConnection, SqlServerConnection,
+};
 use crate::types::errors::DataflowError;
 use crate::types::hosts::StorageHostConfig;
 use crate::util::antichain::OffsetAntichain;
@@ -55,6 +58,8 @@ use proto_ingestion_description::{ProtoSourceExport, ProtoSourceImport};
 use proto_load_generator_source_connection::Generator as ProtoGenerator;
 
 pub mod encoding;
+pub mod mysql;
+pub mod sql_server;
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -1334,6 +1339,9 @@ pub struct KafkaSourceConnection {
     // Map from partition -> starting offset
     pub start_offsets: HashMap<i32, i64>,
     pub group_id_prefix: Option<String>,
+    /// If present, messages that fail to decode are written to this topic
+    /// instead of the source's error collection.
+    pub dead_letter_topic: Option<String>,
     pub environment_id: String,
     /// If present, include the timestamp as an output column of the source with the given name
     pub include_timestamp: Option<IncludedColumnPos>,
@@ -1363,6 +1371,7 @@ impl Arbitrary for KafkaSourceConnection {
             any::<String>(),
             proptest::collection::hash_map(any::<i32>(), any::<i64>(), 1..4),
             any::<Option<String>>(),
+            any::<Option<String>>(),
             any::<String>(),
             any::<Option<IncludedColumnPos>>(),
             any::<Option<IncludedColumnPos>>(),
@@ -1377,6 +1386,7 @@ impl Arbitrary for KafkaSourceConnection {
                     topic,
                     start_offsets,
                     group_id_prefix,
+                    dead_letter_topic,
                     environment_id,
                     include_timestamp,
                     include_partition,
@@ -1389,6 +1399,7 @@ impl Arbitrary for KafkaSourceConnection {
                     topic,
                     start_offsets,
                     group_id_prefix,
+                    dead_letter_topic,
                     environment_id,
                     include_timestamp,
                     include_partition,
@@ -1409,6 +1420,7 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             topic: self.topic.clone(),
             start_offsets: self.start_offsets.clone(),
             group_id_prefix: self.group_id_prefix.clone(),
+            dead_letter_topic: self.dead_letter_topic.clone(),
             environment_id: None,
             environment_name: Some(self.environment_id.into_proto()),
             include_timestamp: self.include_timestamp.into_proto(),
@@ -1430,6 +1442,7 @@ impl RustType<ProtoKafkaSourceConnection> for KafkaSourceConnection {
             topic: proto.topic,
             start_offsets: proto.start_offsets,
             group_id_prefix: proto.group_id_prefix,
+            dead_letter_topic: proto.dead_letter_topic,
             environment_id: match (proto.environment_id, proto.environment_name) {
                 (_, Some(name)) => name,
                 (u128, _) => {
@@ -1486,6 +1499,12 @@ pub struct SourceDesc {
     pub envelope: SourceEnvelope,
     pub metadata_columns: Vec<IncludedColumnSource>,
     pub timestamp_interval: Duration,
+    /// A projection and filter to apply to each decoded row, as specified by
+    /// the source's `COLUMNS` and `WHERE` clauses.
+    ///
+    /// Only supported with `ENVELOPE NONE`.
+    pub mfp: Option<MapFilterProject>,
+    pub rate_limits: SourceRateLimits,
 }
 
 impl Arbitrary for SourceDesc {
@@ -1499,14 +1518,26 @@ impl Arbitrary for SourceDesc {
             any::<SourceEnvelope>(),
             any::<Vec<IncludedColumnSource>>(),
             any::<Duration>(),
+            any::<Option<MapFilterProject>>(),
+            any::<SourceRateLimits>(),
         )
             .prop_map(
-                |(connection, encoding, envelope, metadata_columns, timestamp_interval)| Self {
+                |(
+                    connection,
+                    encoding,
+                    envelope,
+                    metadata_columns,
+                    timestamp_interval,
+                    mfp,
+                    rate_limits,
+                )| Self {
                     connection,
                     encoding,
                     envelope,
                     metadata_columns,
                     timestamp_interval,
+                    mfp,
+                    rate_limits,
                 },
             )
             .boxed()
@@ -1521,6 +1552,8 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             envelope: Some(self.envelope.into_proto()),
             metadata_columns: self.metadata_columns.into_proto(),
             timestamp_interval: Some(self.timestamp_interval.into_proto()),
+            mfp: self.mfp.into_proto(),
+            rate_limits: Some(self.rate_limits.into_proto()),
         }
     }
 
@@ -1539,6 +1572,38 @@ impl RustType<ProtoSourceDesc> for SourceDesc {
             timestamp_interval: proto
                 .timestamp_interval
                 .into_rust_if_some("ProtoSourceDesc::timestamp_interval")?,
+            mfp: proto.mfp.into_rust()?,
+            rate_limits: proto
+                .rate_limits
+                .into_rust_if_some("ProtoSourceDesc::rate_limits")?,
+        })
+    }
+}
+
+/// Limits on how quickly a source reads from its upstream system.
+///
+/// Each limit applies to the source as a whole, and is divided evenly among
+/// the workers that read from the source.
+#[derive(Arbitrary, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SourceRateLimits {
+    /// The maximum number of messages to read per second.
+    pub max_messages_per_second: Option<u64>,
+    /// The maximum number of bytes to read before they are emitted downstream.
+    pub max_bytes_in_flight: Option<u64>,
+}
+
+impl RustType<ProtoSourceRateLimits> for SourceRateLimits {
+    fn into_proto(&self) -> ProtoSourceRateLimits {
+        ProtoSourceRateLimits {
+            max_messages_per_second: self.max_messages_per_second,
+            max_bytes_in_flight: self.max_bytes_in_flight,
+        }
+    }
+
+    fn from_proto(proto: ProtoSourceRateLimits) -> Result<Self, TryFromProtoError> {
+        Ok(SourceRateLimits {
+            max_messages_per_second: proto.max_messages_per_second,
+            max_bytes_in_flight: proto.max_bytes_in_flight,
         })
     }
 }
@@ -1557,6 +1622,16 @@ impl SourceDesc {
                 connection: GenericSourceConnection::Postgres(_),
                 ..
             } => false,
+            // MySQL can produce retractions (deletes)
+            SourceDesc {
+                connection: GenericSourceConnection::MySql(_),
+                ..
+            } => false,
+            // SQL Server can produce retractions (deletes)
+            SourceDesc {
+                connection: GenericSourceConnection::SqlServer(_),
+                ..
+            } => false,
             // Loadgen can produce retractions (deletes)
             SourceDesc {
                 connection: GenericSourceConnection::LoadGenerator(_),
@@ -1575,7 +1650,10 @@ impl SourceDesc {
                     GenericSourceConnection::S3(_)
                     | GenericSourceConnection::Kafka(_)
                     | GenericSourceConnection::Kinesis(_)
-                    | GenericSourceConnection::TestScript(_),
+                    | GenericSourceConnection::Mqtt(_)
+                    | GenericSourceConnection::PubSub(_)
+                    | GenericSourceConnection::TestScript(_)
+                    | GenericSourceConnection::File(_),
                 ..
             } => false,
         }
@@ -1587,13 +1665,18 @@ impl SourceDesc {
             GenericSourceConnection::Kafka(_)
             | GenericSourceConnection::Kinesis(_)
             | GenericSourceConnection::S3(_)
-            | GenericSourceConnection::TestScript(_) => 0,
+            | GenericSourceConnection::Mqtt(_)
+            | GenericSourceConnection::PubSub(_)
+            | GenericSourceConnection::TestScript(_)
+            | GenericSourceConnection::File(_) => 0,
             GenericSourceConnection::LoadGenerator(connection) => {
                 connection.load_generator.views().len()
             }
             GenericSourceConnection::Postgres(connection) => {
                 connection.publication_details.tables.len()
             }
+            GenericSourceConnection::MySql(connection) => connection.details.tables.len(),
+            GenericSourceConnection::SqlServer(connection) => connection.details.tables.len(),
         };
         // Every ingestion produces a main stream plus subsource streams
         subsources + 1
@@ -1614,8 +1697,13 @@ pub enum GenericSourceConnection {
     Kinesis(KinesisSourceConnection),
     S3(S3SourceConnection),
     Postgres(PostgresSourceConnection),
+    MySql(MySqlSourceConnection),
+    Mqtt(MqttSourceConnection),
+    SqlServer(SqlServerSourceConnection),
+    PubSub(PubSubSourceConnection),
     LoadGenerator(LoadGeneratorSourceConnection),
     TestScript(TestScriptSourceConnection),
+    File(FileSourceConnection),
 }
 
 impl GenericSourceConnection {
@@ -1625,8 +1713,12 @@ impl GenericSourceConnection {
             Kafka(KafkaSourceConnection { connection_id, .. })
             | Kinesis(KinesisSourceConnection { connection_id, .. })
             | S3(S3SourceConnection { connection_id, .. })
-            | Postgres(PostgresSourceConnection { connection_id, .. }) => Some(*connection_id),
-            LoadGenerator(_) | TestScript(_) => None,
+            | Postgres(PostgresSourceConnection { connection_id, .. })
+            | MySql(MySqlSourceConnection { connection_id, .. })
+            | Mqtt(MqttSourceConnection { connection_id, .. })
+            | SqlServer(SqlServerSourceConnection { connection_id, .. })
+            | PubSub(PubSubSourceConnection { connection_id, .. }) => Some(*connection_id),
+            LoadGenerator(_) | TestScript(_) | File(_) => None,
         }
     }
 }
@@ -1642,12 +1734,19 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection {
                 GenericSourceConnection::Postgres(postgres) => {
                     Kind::Postgres(postgres.into_proto())
                 }
+                GenericSourceConnection::MySql(mysql) => Kind::Mysql(mysql.into_proto()),
+                GenericSourceConnection::Mqtt(mqtt) => Kind::Mqtt(mqtt.into_proto()),
+                GenericSourceConnection::SqlServer(sql_server) => {
+                    Kind::SqlServer(sql_server.into_proto())
+                }
+                GenericSourceConnection::PubSub(pubsub) => Kind::Pubsub(pubsub.into_proto()),
                 GenericSourceConnection::LoadGenerator(loadgen) => {
                     Kind::Loadgen(loadgen.into_proto())
                 }
                 GenericSourceConnection::TestScript(testscript) => {
                     Kind::Testscript(testscript.into_proto())
                 }
+                GenericSourceConnection::File(file) => Kind::File(file.into_proto()),
             }),
         }
     }
@@ -1662,10 +1761,17 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection {
             Kind::Kinesis(kinesis) => GenericSourceConnection::Kinesis(kinesis.into_rust()?),
             Kind::S3(s3) => GenericSourceConnection::S3(s3.into_rust()?),
             Kind::Postgres(postgres) => GenericSourceConnection::Postgres(postgres.into_rust()?),
+            Kind::Mysql(mysql) => GenericSourceConnection::MySql(mysql.into_rust()?),
+            Kind::Mqtt(mqtt) => GenericSourceConnection::Mqtt(mqtt.into_rust()?),
+            Kind::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_rust()?)
+            }
+            Kind::Pubsub(pubsub) => GenericSourceConnection::PubSub(pubsub.into_rust()?),
             Kind::Loadgen(loadgen) => GenericSourceConnection::LoadGenerator(loadgen.into_rust()?),
             Kind::Testscript(testscript) => {
                 GenericSourceConnection::TestScript(testscript.into_rust()?)
             }
+            Kind::File(file) => GenericSourceConnection::File(file.into_rust()?),
         })
     }
 }
@@ -1732,8 +1838,13 @@ impl GenericSourceConnection {
             Self::Kinesis(_) => vec![],
             Self::S3(_) => vec![],
             Self::Postgres(_) => vec![],
+            Self::MySql(_) => vec![],
+            Self::Mqtt(_) => vec![],
+            Self::SqlServer(_) => vec![],
+            Self::PubSub(_) => vec![],
             Self::LoadGenerator(_) => vec![],
             Self::TestScript(_) => vec![],
+            Self::File(_) => vec![],
         }
     }
 
@@ -1769,8 +1880,13 @@ impl GenericSourceConnection {
             GenericSourceConnection::Kinesis(_)
             | GenericSourceConnection::S3(_)
             | GenericSourceConnection::Postgres(_)
+            | GenericSourceConnection::MySql(_)
+            | GenericSourceConnection::Mqtt(_)
+            | GenericSourceConnection::SqlServer(_)
+            | GenericSourceConnection::PubSub(_)
             | GenericSourceConnection::LoadGenerator(_)
-            | GenericSourceConnection::TestScript(_) => Vec::new(),
+            | GenericSourceConnection::TestScript(_)
+            | GenericSourceConnection::File(_) => Vec::new(),
         }
     }
 
@@ -1781,8 +1897,13 @@ impl GenericSourceConnection {
             GenericSourceConnection::Kinesis(c) => c.name(),
             GenericSourceConnection::S3(c) => c.name(),
             GenericSourceConnection::Postgres(c) => c.name(),
+            GenericSourceConnection::MySql(c) => c.name(),
+            GenericSourceConnection::Mqtt(c) => c.name(),
+            GenericSourceConnection::SqlServer(c) => c.name(),
+            GenericSourceConnection::PubSub(c) => c.name(),
             GenericSourceConnection::LoadGenerator(c) => c.name(),
             GenericSourceConnection::TestScript(c) => c.name(),
+            GenericSourceConnection::File(c) => c.name(),
         }
     }
 
@@ -1799,8 +1920,13 @@ impl GenericSourceConnection {
             }
             GenericSourceConnection::S3(_) => None,
             GenericSourceConnection::Postgres(_) => None,
+            GenericSourceConnection::MySql(_) => None,
+            GenericSourceConnection::Mqtt(_) => None,
+            GenericSourceConnection::SqlServer(_) => None,
+            GenericSourceConnection::PubSub(_) => None,
             GenericSourceConnection::LoadGenerator(_) => None,
             GenericSourceConnection::TestScript(_) => None,
+            GenericSourceConnection::File(_) => None,
         }
     }
 }
@@ -1980,6 +2106,249 @@ impl RustType<ProtoPostgresSourcePublicationDetails> for PostgresSourcePublicati
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: MySqlConnection,
+    /// The cast expressions to convert the incoming string encoded rows to
+    /// their target types, keyed by their position in the source.
+    pub table_casts: HashMap<usize, Vec<MirScalarExpr>>,
+    pub details: MySqlSourceDetails,
+}
+
+impl Arbitrary for MySqlSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<MySqlConnection>(),
+            any::<GlobalId>(),
+            proptest::collection::hash_map(
+                any::<usize>(),
+                proptest::collection::vec(any::<MirScalarExpr>(), 1..4),
+                1..4,
+            ),
+            any::<MySqlSourceDetails>(),
+        )
+            .prop_map(|(connection, connection_id, table_casts, details)| Self {
+                connection,
+                connection_id,
+                table_casts,
+                details,
+            })
+            .boxed()
+    }
+}
+
+impl SourceConnection for MySqlSourceConnection {
+    fn name(&self) -> &'static str {
+        "mysql"
+    }
+}
+
+impl RustType<ProtoMySqlSourceConnection> for MySqlSourceConnection {
+    fn into_proto(&self) -> ProtoMySqlSourceConnection {
+        use proto_my_sql_source_connection::ProtoMySqlTableCast;
+        let mut table_casts = Vec::with_capacity(self.table_casts.len());
+        let mut table_cast_pos = Vec::with_capacity(self.table_casts.len());
+        for (pos, table_cast_cols) in self.table_casts.iter() {
+            table_casts.push(ProtoMySqlTableCast {
+                column_casts: table_cast_cols
+                    .iter()
+                    .cloned()
+                    .map(|cast| cast.into_proto())
+                    .collect(),
+            });
+            table_cast_pos.push(mz_ore::cast::usize_to_u64(*pos));
+        }
+
+        ProtoMySqlSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+            table_casts,
+            table_cast_pos,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlSourceConnection) -> Result<Self, TryFromProtoError> {
+        let mut table_casts = HashMap::new();
+        for (pos, cast) in proto
+            .table_cast_pos
+            .into_iter()
+            .zip_eq(proto.table_casts.into_iter())
+        {
+            let mut column_casts = vec![];
+            for cast in cast.column_casts {
+                column_casts.push(cast.into_rust()?);
+            }
+            table_casts.insert(mz_ore::cast::u64_to_usize(pos), column_casts);
+        }
+
+        Ok(MySqlSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoMySqlSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoMySqlSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoMySqlSourceConnection::details")?,
+            table_casts,
+        })
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlSourceDetails {
+    /// The upstream tables that this source ingests, in output order.
+    pub tables: Vec<mysql::MySqlTableDesc>,
+    /// The server ID with which this source registers as a replica of the
+    /// upstream server. It must be unique among the server's replicas.
+    pub server_id: u32,
+}
+
+impl RustType<ProtoMySqlSourceDetails> for MySqlSourceDetails {
+    fn into_proto(&self) -> ProtoMySqlSourceDetails {
+        ProtoMySqlSourceDetails {
+            tables: self.tables.iter().map(|t| t.into_proto()).collect(),
+            server_id: self.server_id,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlSourceDetails {
+            tables: proto
+                .tables
+                .into_iter()
+                .map(mysql::MySqlTableDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+            server_id: proto.server_id,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: SqlServerConnection,
+    /// The cast expressions to convert the incoming string encoded rows to
+    /// their target types, keyed by their position in the source.
+    pub table_casts: HashMap<usize, Vec<MirScalarExpr>>,
+    pub details: SqlServerSourceDetails,
+}
+
+impl Arbitrary for SqlServerSourceConnection {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<SqlServerConnection>(),
+            any::<GlobalId>(),
+            proptest::collection::hash_map(
+                any::<usize>(),
+                proptest::collection::vec(any::<MirScalarExpr>(), 1..4),
+                1..4,
+            ),
+            any::<SqlServerSourceDetails>(),
+        )
+            .prop_map(|(connection, connection_id, table_casts, details)| Self {
+                connection,
+                connection_id,
+                table_casts,
+                details,
+            })
+            .boxed()
+    }
+}
+
+impl SourceConnection for SqlServerSourceConnection {
+    fn name(&self) -> &'static str {
+        "sql-server"
+    }
+}
+
+impl RustType<ProtoSqlServerSourceConnection> for SqlServerSourceConnection {
+    fn into_proto(&self) -> ProtoSqlServerSourceConnection {
+        use proto_sql_server_source_connection::ProtoSqlServerTableCast;
+        let mut table_casts = Vec::with_capacity(self.table_casts.len());
+        let mut table_cast_pos = Vec::with_capacity(self.table_casts.len());
+        for (pos, table_cast_cols) in self.table_casts.iter() {
+            table_casts.push(ProtoSqlServerTableCast {
+                column_casts: table_cast_cols
+                    .iter()
+                    .cloned()
+                    .map(|cast| cast.into_proto())
+                    .collect(),
+            });
+            table_cast_pos.push(mz_ore::cast::usize_to_u64(*pos));
+        }
+
+        ProtoSqlServerSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+            table_casts,
+            table_cast_pos,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceConnection) -> Result<Self, TryFromProtoError> {
+        let mut table_casts = HashMap::new();
+        for (pos, cast) in proto
+            .table_cast_pos
+            .into_iter()
+            .zip_eq(proto.table_casts.into_iter())
+        {
+            let mut column_casts = vec![];
+            for cast in cast.column_casts {
+                column_casts.push(cast.into_rust()?);
+            }
+            table_casts.insert(mz_ore::cast::u64_to_usize(pos), column_casts);
+        }
+
+        Ok(SqlServerSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoSqlServerSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoSqlServerSourceConnection::details")?,
+            table_casts,
+        })
+    }
+}
+
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerSourceDetails {
+    /// The upstream tables that this source ingests, in output order.
+    pub tables: Vec<sql_server::SqlServerTableDesc>,
+}
+
+impl RustType<ProtoSqlServerSourceDetails> for SqlServerSourceDetails {
+    fn into_proto(&self) -> ProtoSqlServerSourceDetails {
+        ProtoSqlServerSourceDetails {
+            tables: self.tables.iter().map(|t| t.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerSourceDetails {
+            tables: proto
+                .tables
+                .into_iter()
+                .map(sql_server::SqlServerTableDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct LoadGeneratorSourceConnection {
     pub load_generator: LoadGenerator,
@@ -2298,6 +2667,124 @@ impl RustType<ProtoTestScriptSourceConnection> for TestScriptSourceConnection {
     }
 }
 
+/// A source that subscribes to topics on an MQTT broker.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MqttSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: MqttConnection,
+    /// The topic filters to subscribe to, which may contain the `+` and `#`
+    /// wildcards.
+    pub topic_filters: Vec<String>,
+    /// The quality of service level at which to subscribe: 0 (at most once),
+    /// 1 (at least once), or 2 (exactly once).
+    pub qos: u8,
+    /// The ID of the Materialize environment, which makes the source's client
+    /// ID unique across deployments that share a broker.
+    pub environment_id: String,
+}
+
+impl SourceConnection for MqttSourceConnection {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+impl RustType<ProtoMqttSourceConnection> for MqttSourceConnection {
+    fn into_proto(&self) -> ProtoMqttSourceConnection {
+        ProtoMqttSourceConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            topic_filters: self.topic_filters.clone(),
+            qos: self.qos.into_proto(),
+            environment_id: self.environment_id.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMqttSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MqttSourceConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoMqttSourceConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoMqttSourceConnection::connection")?,
+            topic_filters: proto.topic_filters,
+            qos: proto.qos.into_rust()?,
+            environment_id: proto.environment_id,
+        })
+    }
+}
+
+/// A source that pulls messages from a Google Cloud Pub/Sub subscription.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PubSubSourceConnection {
+    pub connection_id: GlobalId,
+    pub connection: GcpConnection,
+    /// The ID of the subscription, within the connection's project.
+    pub subscription: String,
+}
+
+impl SourceConnection for PubSubSourceConnection {
+    fn name(&self) -> &'static str {
+        "pubsub"
+    }
+}
+
+impl RustType<ProtoPubSubSourceConnection> for PubSubSourceConnection {
+    fn into_proto(&self) -> ProtoPubSubSourceConnection {
+        ProtoPubSubSourceConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            subscription: self.subscription.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoPubSubSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PubSubSourceConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPubSubSourceConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPubSubSourceConnection::connection")?,
+            subscription: proto.subscription,
+        })
+    }
+}
+
+/// A source that reads newline-delimited records from a local file.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileSourceConnection {
+    /// The path of the file on the host that runs the source. Glob patterns
+    /// are not expanded.
+    pub path: String,
+    /// Whether to keep reading records that are appended to the file, rather
+    /// than stopping at its end.
+    pub tail: bool,
+}
+
+impl SourceConnection for FileSourceConnection {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+}
+
+impl RustType<ProtoFileSourceConnection> for FileSourceConnection {
+    fn into_proto(&self) -> ProtoFileSourceConnection {
+        ProtoFileSourceConnection {
+            path: self.path.clone(),
+            tail: self.tail,
+        }
+    }
+
+    fn from_proto(proto: ProtoFileSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(FileSourceConnection {
+            path: proto.path,
+            tail: proto.tail,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct S3SourceConnection {
     pub connection_id: GlobalId,
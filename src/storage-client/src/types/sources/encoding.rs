@@ -10,7 +10,7 @@
 //! Types and traits related to the *decoding* of data for sources.
 
 use anyhow::Context;
-use proptest::prelude::{Arbitrary, BoxedStrategy, Strategy};
+use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
@@ -138,6 +138,7 @@ pub enum DataEncodingInner {
     Regex(RegexEncoding),
     Bytes,
     Text,
+    Json,
     RowCodec(RelationDesc),
 }
 
@@ -152,6 +153,7 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
                 DataEncodingInner::Regex(e) => Kind::Regex(e.into_proto()),
                 DataEncodingInner::Bytes => Kind::Bytes(()),
                 DataEncodingInner::Text => Kind::Text(()),
+                DataEncodingInner::Json => Kind::Json(()),
                 DataEncodingInner::RowCodec(e) => Kind::RowCodec(e.into_proto()),
             }),
         }
@@ -169,6 +171,7 @@ impl RustType<ProtoDataEncodingInner> for DataEncodingInner {
             Kind::Regex(e) => DataEncodingInner::Regex(e.into_rust()?),
             Kind::Bytes(()) => DataEncodingInner::Bytes,
             Kind::Text(()) => DataEncodingInner::Text,
+            Kind::Json(()) => DataEncodingInner::Json,
             Kind::RowCodec(e) => DataEncodingInner::RowCodec(e.into_rust()?),
         })
     }
@@ -238,7 +241,11 @@ impl DataEncoding {
                 .fold(RelationDesc::empty(), |desc, (name, ty)| {
                     desc.with_column(name, ty.clone())
                 }),
-            DataEncodingInner::Regex(RegexEncoding { regex }) => regex
+            DataEncodingInner::Regex(RegexEncoding {
+                regex,
+                column_types,
+                on_mismatch: _,
+            }) => regex
                 .capture_names()
                 .enumerate()
                 // The first capture is the entire matched string. This will
@@ -246,13 +253,13 @@ impl DataEncoding {
                 // just surround their entire regex in an explicit capture
                 // group.
                 .skip(1)
-                .fold(RelationDesc::empty(), |desc, (i, name)| {
+                .zip(column_types)
+                .fold(RelationDesc::empty(), |desc, ((i, name), ty)| {
                     let name = match name {
                         None => format!("column{}", i),
                         Some(name) => name.to_owned(),
                     };
-                    let ty = ScalarType::String.nullable(true);
-                    desc.with_column(name, ty)
+                    desc.with_column(name, ty.clone().nullable(true))
                 }),
             DataEncodingInner::Csv(CsvEncoding { columns, .. }) => match columns {
                 ColumnSpec::Count(n) => {
@@ -270,6 +277,9 @@ impl DataEncoding {
             DataEncodingInner::Text => {
                 RelationDesc::empty().with_column("text", ScalarType::String.nullable(false))
             }
+            DataEncodingInner::Json => {
+                RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+            }
             DataEncodingInner::RowCodec(desc) => desc.clone(),
         };
 
@@ -291,6 +301,7 @@ impl DataEncoding {
             DataEncodingInner::Regex { .. } => "Regex",
             DataEncodingInner::Csv(_) => "Csv",
             DataEncodingInner::Text => "Text",
+            DataEncodingInner::Json => "Json",
             DataEncodingInner::RowCodec(_) => "RowCodec",
         }
     }
@@ -302,6 +313,9 @@ pub struct AvroEncoding {
     pub schema: String,
     pub csr_connection: Option<CsrConnection>,
     pub confluent_wire_format: bool,
+    /// Whether to skip, rather than error on, messages whose writer schema
+    /// cannot be resolved against `schema`.
+    pub skip_incompatible_schemas: bool,
 }
 
 impl RustType<ProtoAvroEncoding> for AvroEncoding {
@@ -310,6 +324,7 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: self.schema.clone(),
             csr_connection: self.csr_connection.into_proto(),
             confluent_wire_format: self.confluent_wire_format,
+            skip_incompatible_schemas: self.skip_incompatible_schemas,
         }
     }
 
@@ -318,6 +333,7 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
             schema: proto.schema,
             csr_connection: proto.csr_connection.into_rust()?,
             confluent_wire_format: proto.confluent_wire_format,
+            skip_incompatible_schemas: proto.skip_incompatible_schemas,
         })
     }
 }
@@ -353,6 +369,7 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
 pub struct CsvEncoding {
     pub columns: ColumnSpec,
     pub delimiter: u8,
+    pub quote: u8,
 }
 
 impl RustType<ProtoCsvEncoding> for CsvEncoding {
@@ -360,6 +377,7 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
         ProtoCsvEncoding {
             columns: Some(self.columns.into_proto()),
             delimiter: self.delimiter.into_proto(),
+            quote: self.quote.into_proto(),
         }
     }
 
@@ -369,6 +387,7 @@ impl RustType<ProtoCsvEncoding> for CsvEncoding {
                 .columns
                 .into_rust_if_some("ProtoCsvEncoding::columns")?,
             delimiter: proto.delimiter.into_rust()?,
+            quote: proto.quote.into_rust()?,
         })
     }
 }
@@ -429,6 +448,10 @@ impl ColumnSpec {
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct RegexEncoding {
     pub regex: mz_repr::adt::regex::Regex,
+    /// The type of each capture group, other than the one for the entire
+    /// match, into which its text is parsed.
+    pub column_types: Vec<ScalarType>,
+    pub on_mismatch: RegexMismatchPolicy,
 }
 
 impl Arbitrary for RegexEncoding {
@@ -436,8 +459,12 @@ impl Arbitrary for RegexEncoding {
     type Parameters = ();
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        any_regex()
-            .prop_map(|regex| RegexEncoding { regex })
+        (any_regex(), any::<RegexMismatchPolicy>())
+            .prop_map(|(regex, on_mismatch)| RegexEncoding {
+                column_types: vec![ScalarType::String; regex.captures_len() - 1],
+                regex,
+                on_mismatch,
+            })
             .boxed()
     }
 }
@@ -446,12 +473,54 @@ impl RustType<ProtoRegexEncoding> for RegexEncoding {
     fn into_proto(&self) -> ProtoRegexEncoding {
         ProtoRegexEncoding {
             regex: Some(self.regex.into_proto()),
+            column_types: self.column_types.into_proto(),
+            on_mismatch: Some(self.on_mismatch.into_proto()),
         }
     }
 
     fn from_proto(proto: ProtoRegexEncoding) -> Result<Self, TryFromProtoError> {
         Ok(RegexEncoding {
             regex: proto.regex.into_rust_if_some("ProtoRegexEncoding::regex")?,
+            column_types: proto.column_types.into_rust()?,
+            on_mismatch: proto
+                .on_mismatch
+                .into_rust_if_some("ProtoRegexEncoding::on_mismatch")?,
+        })
+    }
+}
+
+/// What a regex encoding does with values that do not match its regex.
+#[derive(Arbitrary, Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum RegexMismatchPolicy {
+    /// The value is ignored.
+    Skip,
+    /// The value is decoded as a row in which every column is null.
+    Null,
+    /// The value is decoded as an error.
+    Error,
+}
+
+impl RustType<ProtoRegexMismatchPolicy> for RegexMismatchPolicy {
+    fn into_proto(&self) -> ProtoRegexMismatchPolicy {
+        use proto_regex_mismatch_policy::Kind;
+        ProtoRegexMismatchPolicy {
+            kind: Some(match self {
+                RegexMismatchPolicy::Skip => Kind::Skip(()),
+                RegexMismatchPolicy::Null => Kind::Null(()),
+                RegexMismatchPolicy::Error => Kind::Error(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoRegexMismatchPolicy) -> Result<Self, TryFromProtoError> {
+        use proto_regex_mismatch_policy::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoRegexMismatchPolicy::kind"))?;
+        Ok(match kind {
+            Kind::Skip(()) => RegexMismatchPolicy::Skip,
+            Kind::Null(()) => RegexMismatchPolicy::Null,
+            Kind::Error(()) => RegexMismatchPolicy::Error,
         })
     }
 }
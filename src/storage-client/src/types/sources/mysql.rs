@@ -0,0 +1,162 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Descriptions of MySQL objects, and helpers to inspect a MySQL server.
+
+use anyhow::bail;
+use mysql_async::prelude::Queryable;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{RustType, TryFromProtoError};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_client.types.sources.mysql.rs"
+));
+
+/// Schemas that belong to the MySQL server itself, and that are never
+/// replicated.
+const SYSTEM_SCHEMAS: &[&str] = &["mysql", "information_schema", "performance_schema", "sys"];
+
+/// Describes a table in a MySQL database.
+#[derive(Arbitrary, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlTableDesc {
+    /// The name of the schema (i.e. database) that the table belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The description of each column, in order.
+    pub columns: Vec<MySqlColumnDesc>,
+}
+
+impl RustType<ProtoMySqlTableDesc> for MySqlTableDesc {
+    fn into_proto(&self) -> ProtoMySqlTableDesc {
+        ProtoMySqlTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlTableDesc {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(MySqlColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Describes a column in a [`MySqlTableDesc`].
+#[derive(Arbitrary, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MySqlColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The full MySQL type of the column, e.g. `int unsigned` or
+    /// `enum('a','b')`.
+    pub column_type: String,
+    /// Whether the column is nullable.
+    pub nullable: bool,
+}
+
+impl RustType<ProtoMySqlColumnDesc> for MySqlColumnDesc {
+    fn into_proto(&self) -> ProtoMySqlColumnDesc {
+        ProtoMySqlColumnDesc {
+            name: self.name.clone(),
+            column_type: self.column_type.clone(),
+            nullable: self.nullable,
+        }
+    }
+
+    fn from_proto(proto: ProtoMySqlColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(MySqlColumnDesc {
+            name: proto.name,
+            column_type: proto.column_type,
+            nullable: proto.nullable,
+        })
+    }
+}
+
+/// Fetches the description of every user table on the MySQL server, ordered
+/// by schema and table name.
+pub async fn table_info(
+    conn: &mut mysql_async::Conn,
+) -> Result<Vec<MySqlTableDesc>, anyhow::Error> {
+    let query = format!(
+        "SELECT c.table_schema, c.table_name, c.column_name, c.column_type, c.is_nullable
+        FROM information_schema.columns c
+        JOIN information_schema.tables t
+            ON c.table_schema = t.table_schema AND c.table_name = t.table_name
+        WHERE t.table_type = 'BASE TABLE' AND c.table_schema NOT IN ({})
+        ORDER BY c.table_schema, c.table_name, c.ordinal_position",
+        SYSTEM_SCHEMAS
+            .iter()
+            .map(|s| format!("'{}'", s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let rows: Vec<(String, String, String, String, String)> = conn.query(query).await?;
+
+    let mut tables: Vec<MySqlTableDesc> = vec![];
+    for (schema_name, name, column_name, column_type, is_nullable) in rows {
+        let column = MySqlColumnDesc {
+            name: column_name,
+            column_type: column_type.to_lowercase(),
+            nullable: is_nullable == "YES",
+        };
+        match tables.last_mut() {
+            Some(table) if table.schema_name == schema_name && table.name == name => {
+                table.columns.push(column)
+            }
+            _ => tables.push(MySqlTableDesc {
+                schema_name,
+                name,
+                columns: vec![column],
+            }),
+        }
+    }
+    Ok(tables)
+}
+
+/// Verifies that the MySQL server is configured to emit row-based binlog
+/// events with full row images and GTIDs, which the MySQL source requires.
+pub async fn validate_replication_settings(
+    conn: &mut mysql_async::Conn,
+) -> Result<(), anyhow::Error> {
+    let settings: Option<(i64, String, String, String)> = conn
+        .query_first("SELECT @@log_bin, @@binlog_format, @@binlog_row_image, @@gtid_mode")
+        .await?;
+    let (log_bin, binlog_format, binlog_row_image, gtid_mode) = match settings {
+        Some(settings) => settings,
+        None => bail!("unable to read replication settings from MySQL server"),
+    };
+    if log_bin != 1 {
+        bail!("MySQL server must have binary logging enabled (log_bin = ON)");
+    }
+    for (name, actual, expected) in [
+        ("binlog_format", binlog_format, "ROW"),
+        ("binlog_row_image", binlog_row_image, "FULL"),
+        ("gtid_mode", gtid_mode, "ON"),
+    ] {
+        if !actual.eq_ignore_ascii_case(expected) {
+            bail!(
+                "MySQL server must have {} set to {}, but it is set to {}",
+                name,
+                expected,
+                actual
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Descriptions of SQL Server objects, and helpers to inspect a SQL Server
+//! database.
+
+use anyhow::{anyhow, bail};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use tiberius::Client;
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+
+use mz_proto::{RustType, TryFromProtoError};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_client.types.sources.sql_server.rs"
+));
+
+/// Describes a table in a SQL Server database that has change data capture
+/// enabled.
+#[derive(Arbitrary, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerTableDesc {
+    /// The name of the schema that the table belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The name of the capture instance that records the table's changes.
+    pub capture_instance: String,
+    /// The description of each captured column, in order.
+    pub columns: Vec<SqlServerColumnDesc>,
+}
+
+impl RustType<ProtoSqlServerTableDesc> for SqlServerTableDesc {
+    fn into_proto(&self) -> ProtoSqlServerTableDesc {
+        ProtoSqlServerTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            capture_instance: self.capture_instance.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerTableDesc {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            capture_instance: proto.capture_instance,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(SqlServerColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Describes a column in a [`SqlServerTableDesc`].
+#[derive(Arbitrary, Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SqlServerColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The name of the column's system type, e.g. `int` or `nvarchar`.
+    pub data_type: String,
+    /// Whether the column is nullable.
+    pub nullable: bool,
+}
+
+impl RustType<ProtoSqlServerColumnDesc> for SqlServerColumnDesc {
+    fn into_proto(&self) -> ProtoSqlServerColumnDesc {
+        ProtoSqlServerColumnDesc {
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            nullable: self.nullable,
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerColumnDesc {
+            name: proto.name,
+            data_type: proto.data_type,
+            nullable: proto.nullable,
+        })
+    }
+}
+
+/// Fetches the description of every table in the database that has change
+/// data capture enabled, ordered by schema and table name.
+///
+/// A table can have up to two capture instances while its schema is being
+/// migrated; the most recently created one is used.
+pub async fn table_info(
+    client: &mut Client<Compat<TcpStream>>,
+) -> Result<Vec<SqlServerTableDesc>, anyhow::Error> {
+    let query = "SELECT s.name, t.name, ct.capture_instance, cc.column_name, ty.name, c.is_nullable
+        FROM cdc.change_tables ct
+        JOIN sys.tables t ON ct.source_object_id = t.object_id
+        JOIN sys.schemas s ON t.schema_id = s.schema_id
+        JOIN cdc.captured_columns cc ON cc.object_id = ct.object_id
+        JOIN sys.columns c ON c.object_id = t.object_id AND c.name = cc.column_name
+        JOIN sys.types ty ON ty.user_type_id = c.system_type_id
+        WHERE ct.create_date = (
+            SELECT MAX(latest.create_date) FROM cdc.change_tables latest
+            WHERE latest.source_object_id = ct.source_object_id
+        )
+        ORDER BY s.name, t.name, cc.column_ordinal";
+    let rows = client
+        .simple_query(query)
+        .await?
+        .into_first_result()
+        .await?;
+
+    let mut tables: Vec<SqlServerTableDesc> = vec![];
+    for row in rows {
+        let text = |i: usize| -> Result<String, anyhow::Error> {
+            row.try_get::<&str, _>(i)?
+                .map(str::to_owned)
+                .ok_or_else(|| anyhow!("unexpected NULL in SQL Server catalog"))
+        };
+        let schema_name = text(0)?;
+        let name = text(1)?;
+        let capture_instance = text(2)?;
+        let column = SqlServerColumnDesc {
+            name: text(3)?,
+            data_type: text(4)?.to_lowercase(),
+            nullable: row.try_get::<bool, _>(5)?.unwrap_or(true),
+        };
+        match tables.last_mut() {
+            Some(table) if table.schema_name == schema_name && table.name == name => {
+                table.columns.push(column)
+            }
+            _ => tables.push(SqlServerTableDesc {
+                schema_name,
+                name,
+                capture_instance,
+                columns: vec![column],
+            }),
+        }
+    }
+    Ok(tables)
+}
+
+/// Verifies that change data capture is enabled for the database, which the
+/// SQL Server source requires.
+pub async fn validate_cdc_settings(
+    client: &mut Client<Compat<TcpStream>>,
+) -> Result<(), anyhow::Error> {
+    let row = client
+        .simple_query("SELECT is_cdc_enabled FROM sys.databases WHERE name = DB_NAME()")
+        .await?
+        .into_row()
+        .await?;
+    let enabled = match row {
+        Some(row) => row.try_get::<bool, _>(0)?.unwrap_or(false),
+        None => bail!("unable to read change data capture settings from SQL Server"),
+    };
+    if !enabled {
+        bail!(
+            "SQL Server database must have change data capture enabled \
+            (EXEC sys.sp_cdc_enable_db)"
+        );
+    }
+    Ok(())
+}
@@ -8,8 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use tokio::runtime::Handle as TokioHandle;
+use tracing::warn;
 
-use mz_interchange::avro::Decoder;
+use mz_interchange::avro::{Decoder, IncompatibleSchemaError};
 use mz_repr::Row;
 use mz_storage_client::types::connections::CsrClient;
 use mz_storage_client::types::errors::DecodeErrorKind;
@@ -18,6 +19,7 @@ use mz_storage_client::types::errors::DecodeErrorKind;
 pub struct AvroDecoderState {
     tokio_handle: TokioHandle,
     decoder: Decoder<CsrClient>,
+    skip_incompatible_schemas: bool,
     events_success: i64,
 }
 
@@ -27,10 +29,12 @@ impl AvroDecoderState {
         ccsr_client: Option<CsrClient>,
         debug_name: String,
         confluent_wire_format: bool,
+        skip_incompatible_schemas: bool,
     ) -> Result<Self, anyhow::Error> {
         Ok(AvroDecoderState {
             tokio_handle: TokioHandle::current(),
             decoder: Decoder::new(value_schema, ccsr_client, debug_name, confluent_wire_format)?,
+            skip_incompatible_schemas,
             events_success: 0,
         })
     }
@@ -41,6 +45,28 @@ impl AvroDecoderState {
                 self.events_success += 1;
                 Ok(Some(row))
             }
+            Err(err)
+                if self.skip_incompatible_schemas
+                    && err.downcast_ref::<IncompatibleSchemaError>().is_some() =>
+            {
+                warn!("skipping message with incompatible Avro schema: {:#}", err);
+                *bytes = &[];
+                Ok(None)
+            }
+            Err(err) => Err(DecodeErrorKind::Text(format!(
+                "avro deserialization error: {:#}",
+                err
+            ))),
+        }
+    }
+
+    /// Decodes all records of the Avro Object Container File `bytes`.
+    pub fn decode_ocf(&mut self, bytes: &[u8]) -> Result<Vec<Row>, DecodeErrorKind> {
+        match self.decoder.decode_ocf(bytes) {
+            Ok(rows) => {
+                self.events_success += i64::try_from(rows.len()).expect("row count fits in i64");
+                Ok(rows)
+            }
             Err(err) => Err(DecodeErrorKind::Text(format!(
                 "avro deserialization error: {:#}",
                 err
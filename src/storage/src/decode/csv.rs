@@ -32,7 +32,11 @@ impl CsvDecoderState {
     }
 
     pub fn new(format: CsvEncoding) -> Self {
-        let CsvEncoding { columns, delimiter } = format;
+        let CsvEncoding {
+            columns,
+            delimiter,
+            quote,
+        } = format;
         let n_cols = columns.arity();
 
         let header_names = columns.into_header_names();
@@ -44,7 +48,10 @@ impl CsvDecoderState {
             output_cursor: 0,
             ends: vec![0],
             ends_cursor: 1,
-            csv_reader: csv_core::ReaderBuilder::new().delimiter(delimiter).build(),
+            csv_reader: csv_core::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .quote(quote)
+                .build(),
             row_buf: Row::default(),
             events_error: 0,
             events_success: 0,
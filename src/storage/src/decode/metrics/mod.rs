@@ -36,13 +36,14 @@ impl DecodeMetrics {
 
     fn counter_inc(&self, decoder: &DataDecoderInner, success: bool, n: usize) {
         let format_label = match decoder {
-            DataDecoderInner::Avro(_) => "avro",
+            DataDecoderInner::Avro(_) | DataDecoderInner::AvroOcf { .. } => "avro",
             DataDecoderInner::Csv(_) => "csv",
             DataDecoderInner::DelimitedBytes { format, .. }
             | DataDecoderInner::PreDelimited(format) => match format {
                 PreDelimitedFormat::Bytes => "raw",
                 PreDelimitedFormat::Text => "text",
-                PreDelimitedFormat::Regex(..) => "regex",
+                PreDelimitedFormat::Json(..) => "json",
+                PreDelimitedFormat::Regex { .. } => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
             },
         };
@@ -34,12 +34,14 @@ use tracing::error;
 use mz_avro::{AvroDeserializer, GeneralDeserializer};
 use mz_expr::PartitionId;
 use mz_interchange::avro::ConfluentAvroResolver;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::strconv::{self, ParseError};
 use mz_repr::{adt::timestamp::CheckedTimestamp, Datum};
-use mz_repr::{Diff, Row, Timestamp};
+use mz_repr::{Diff, Row, RowPacker, ScalarType, Timestamp};
 use mz_storage_client::types::connections::{ConnectionContext, CsrClient};
 use mz_storage_client::types::errors::{DecodeError, DecodeErrorKind};
 use mz_storage_client::types::sources::encoding::{
-    AvroEncoding, DataEncoding, DataEncodingInner, RegexEncoding,
+    AvroEncoding, DataEncoding, DataEncodingInner, RegexEncoding, RegexMismatchPolicy,
 };
 use mz_storage_client::types::sources::{IncludedColumnSource, MzOffset};
 
@@ -136,7 +138,13 @@ pub fn render_decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
 pub(crate) enum PreDelimitedFormat {
     Bytes,
     Text,
-    Regex(Regex, Row),
+    Json(Row),
+    Regex {
+        regex: Regex,
+        column_types: Vec<ScalarType>,
+        on_mismatch: RegexMismatchPolicy,
+        row_buf: Row,
+    },
     Protobuf(ProtobufDecoderState),
 }
 
@@ -149,19 +157,45 @@ impl PreDelimitedFormat {
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
                 Ok(Some(Row::pack(Some(Datum::String(s)))))
             }
-            PreDelimitedFormat::Regex(regex, row_buf) => {
+            PreDelimitedFormat::Json(row_buf) => {
+                JsonbPacker::new(&mut row_buf.packer())
+                    .pack_slice(bytes)
+                    .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?;
+                Ok(Some(row_buf.clone()))
+            }
+            PreDelimitedFormat::Regex {
+                regex,
+                column_types,
+                on_mismatch,
+                row_buf,
+            } => {
                 let s = std::str::from_utf8(bytes)
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
                 let captures = match regex.captures(s) {
                     Some(captures) => captures,
-                    None => return Ok(None),
+                    None => {
+                        return match on_mismatch {
+                            RegexMismatchPolicy::Skip => Ok(None),
+                            RegexMismatchPolicy::Null => {
+                                row_buf
+                                    .packer()
+                                    .extend(column_types.iter().map(|_| Datum::Null));
+                                Ok(Some(row_buf.clone()))
+                            }
+                            RegexMismatchPolicy::Error => Err(DecodeErrorKind::Text(format!(
+                                "Value does not match regex: {}",
+                                s
+                            ))),
+                        }
+                    }
                 };
-                row_buf.packer().extend(
-                    captures
-                        .iter()
-                        .skip(1)
-                        .map(|c| Datum::from(c.map(|c| c.as_str()))),
-                );
+                let mut packer = row_buf.packer();
+                for (capture, ty) in captures.iter().skip(1).zip(column_types.iter()) {
+                    match capture {
+                        None => packer.push(Datum::Null),
+                        Some(capture) => pack_regex_capture(&mut packer, ty, capture.as_str())?,
+                    }
+                }
                 Ok(Some(row_buf.clone()))
             }
             PreDelimitedFormat::Protobuf(pb) => pb.get_value(bytes).transpose(),
@@ -169,9 +203,59 @@ impl PreDelimitedFormat {
     }
 }
 
+/// Packs the text of a regex capture group as a datum of the type of its
+/// column.
+fn pack_regex_capture(
+    packer: &mut RowPacker,
+    ty: &ScalarType,
+    s: &str,
+) -> Result<(), DecodeErrorKind> {
+    let parse_err = |e: ParseError| DecodeErrorKind::Text(e.to_string());
+    match ty {
+        ScalarType::Bool => packer.push(Datum::from(strconv::parse_bool(s).map_err(parse_err)?)),
+        ScalarType::Int16 => packer.push(Datum::from(strconv::parse_int16(s).map_err(parse_err)?)),
+        ScalarType::Int32 => packer.push(Datum::from(strconv::parse_int32(s).map_err(parse_err)?)),
+        ScalarType::Int64 => packer.push(Datum::from(strconv::parse_int64(s).map_err(parse_err)?)),
+        ScalarType::Float32 => {
+            packer.push(Datum::from(strconv::parse_float32(s).map_err(parse_err)?))
+        }
+        ScalarType::Float64 => {
+            packer.push(Datum::from(strconv::parse_float64(s).map_err(parse_err)?))
+        }
+        ScalarType::Numeric { .. } => packer.push(Datum::Numeric(
+            strconv::parse_numeric(s).map_err(parse_err)?,
+        )),
+        ScalarType::String => packer.push(Datum::String(s)),
+        ScalarType::Jsonb => JsonbPacker::new(packer)
+            .pack_str(s)
+            .map_err(|e| DecodeErrorKind::Text(format!("Failed to decode JSON: {}", e)))?,
+        ScalarType::Date => packer.push(Datum::Date(strconv::parse_date(s).map_err(parse_err)?)),
+        ScalarType::Timestamp => packer.push(Datum::Timestamp(
+            strconv::parse_timestamp(s).map_err(parse_err)?,
+        )),
+        ScalarType::TimestampTz => packer.push(Datum::TimestampTz(
+            strconv::parse_timestamptz(s).map_err(parse_err)?,
+        )),
+        _ => unreachable!(
+            "regex columns of type {:?} are rejected during planning",
+            ty
+        ),
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub(crate) enum DataDecoderInner {
     Avro(AvroDecoderState),
+    /// Avro Object Container Files, which are decoded in full once the end of
+    /// each file is reached.
+    AvroOcf {
+        avro: AvroDecoderState,
+        /// The bytes of the current file.
+        buf: Vec<u8>,
+        /// The records of the current file that have not been returned yet.
+        rows: VecDeque<Row>,
+    },
     DelimitedBytes {
         delimiter: u8,
         format: PreDelimitedFormat,
@@ -201,6 +285,11 @@ impl DataDecoder {
                 format.decode(data)
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes),
+            DataDecoderInner::AvroOcf { buf, .. } => {
+                buf.extend_from_slice(bytes);
+                *bytes = &[];
+                Ok(None)
+            }
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
@@ -213,9 +302,18 @@ impl DataDecoder {
     /// Get the next record if it exists, assuming an EOF has occurred.
     ///
     /// This is distinct from `next` because, for example, a CSV record should be returned even if it
-    /// does not end in a newline.
+    /// does not end in a newline. Callers must call `eof` until it returns `Ok(None)`, as some
+    /// formats can only be decoded once the entire input has been seen.
     pub fn eof(&mut self, bytes: &mut &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
         match &mut self.inner {
+            DataDecoderInner::AvroOcf { avro, buf, rows } => {
+                buf.extend_from_slice(std::mem::take(bytes));
+                if !buf.is_empty() {
+                    let file = std::mem::take(buf);
+                    rows.extend(avro.decode_ocf(&file)?);
+                }
+                Ok(rows.pop_front())
+            }
             DataDecoderInner::Csv(csv) => {
                 let result = csv.decode(bytes);
                 csv.reset_for_new_object();
@@ -259,6 +357,7 @@ fn get_decoder(
             schema,
             csr_connection,
             confluent_wire_format,
+            skip_incompatible_schemas,
         }) => {
             let csr_client = match csr_connection {
                 None => None,
@@ -273,21 +372,38 @@ fn get_decoder(
                 csr_client,
                 debug_name.to_string(),
                 confluent_wire_format,
+                skip_incompatible_schemas,
             )
             .expect("Failed to create avro decoder, even though we validated ccsr client creation in purification.");
-            DataDecoder {
-                inner: DataDecoderInner::Avro(state),
-                metrics,
-            }
+            // Byte streams of Avro data are Object Container Files, which
+            // carry their own schema and delimit their own records.
+            let inner = if is_connection_delimited {
+                DataDecoderInner::Avro(state)
+            } else {
+                DataDecoderInner::AvroOcf {
+                    avro: state,
+                    buf: vec![],
+                    rows: VecDeque::new(),
+                }
+            };
+            DataDecoder { inner, metrics }
         }
         DataEncodingInner::Text
         | DataEncodingInner::Bytes
+        | DataEncodingInner::Json
         | DataEncodingInner::Protobuf(_)
         | DataEncodingInner::Regex(_) => {
             let after_delimiting = match encoding.inner {
-                DataEncodingInner::Regex(RegexEncoding { regex }) => {
-                    PreDelimitedFormat::Regex(regex.0, Default::default())
-                }
+                DataEncodingInner::Regex(RegexEncoding {
+                    regex,
+                    column_types,
+                    on_mismatch,
+                }) => PreDelimitedFormat::Regex {
+                    regex: regex.0,
+                    column_types,
+                    on_mismatch,
+                    row_buf: Default::default(),
+                },
                 DataEncodingInner::Protobuf(encoding) => {
                     PreDelimitedFormat::Protobuf(ProtobufDecoderState::new(encoding).expect(
                         "Failed to create protobuf decoder, even though we validated ccsr \
@@ -296,6 +412,7 @@ fn get_decoder(
                 }
                 DataEncodingInner::Bytes => PreDelimitedFormat::Bytes,
                 DataEncodingInner::Text => PreDelimitedFormat::Text,
+                DataEncodingInner::Json => PreDelimitedFormat::Json(Default::default()),
                 _ => unreachable!(),
             };
             let inner = if is_connection_delimited {
@@ -523,48 +640,54 @@ where
                         Some(data) => data,
                         None => {
                             let data = &mut &value_buf[..];
-                            let mut result = value_decoder.eof(data);
-                            if result.is_ok() && !data.is_empty() {
-                                result = Err(DecodeErrorKind::Text(format!(
-                                    "Saw unexpected EOF with bytes remaining in buffer: {:?}",
-                                    data
-                                )));
-                            }
-                            value_buf.clear();
+                            loop {
+                                let mut result = value_decoder.eof(data);
+                                if result.is_ok() && !data.is_empty() {
+                                    result = Err(DecodeErrorKind::Text(format!(
+                                        "Saw unexpected EOF with bytes remaining in buffer: {:?}",
+                                        data
+                                    )));
+                                    *data = &[];
+                                }
 
-                            match result.transpose() {
-                                None => continue,
-                                Some(value) => {
-                                    if value.is_err() {
-                                        n_errors += 1;
-                                    } else if matches!(&value, Ok(_)) {
-                                        n_successes += 1;
-                                    }
-                                    // `RangeFrom` `Iterator`'s never end
-                                    let position = n_seen.next().unwrap();
-                                    let metadata = to_metadata_row(
-                                        &metadata_items,
-                                        partition.clone(),
-                                        position.into(),
-                                        *upstream_time_millis,
-                                        headers.as_deref(),
-                                    );
-
-                                    session.give(DecodeResult {
-                                        key: None,
-                                        value: Some(value.map(|r| (r, 1)).map_err(|inner| {
-                                            DecodeError {
-                                                kind: inner,
-                                                raw: None,
-                                            }
-                                        })),
-                                        position: position.into(),
-                                        upstream_time_millis: *upstream_time_millis,
-                                        partition: partition.clone(),
-                                        metadata,
-                                    });
+                                let value = match result.transpose() {
+                                    None => break,
+                                    Some(value) => value,
+                                };
+                                let is_err = value.is_err();
+                                if is_err {
+                                    n_errors += 1;
+                                } else {
+                                    n_successes += 1;
+                                }
+                                // `RangeFrom` `Iterator`'s never end
+                                let position = n_seen.next().unwrap();
+                                let metadata = to_metadata_row(
+                                    &metadata_items,
+                                    partition.clone(),
+                                    position.into(),
+                                    *upstream_time_millis,
+                                    headers.as_deref(),
+                                );
+
+                                session.give(DecodeResult {
+                                    key: None,
+                                    value: Some(value.map(|r| (r, 1)).map_err(|inner| {
+                                        DecodeError {
+                                            kind: inner,
+                                            raw: None,
+                                        }
+                                    })),
+                                    position: position.into(),
+                                    upstream_time_millis: *upstream_time_millis,
+                                    partition: partition.clone(),
+                                    metadata,
+                                });
+                                if is_err {
+                                    break;
                                 }
                             }
+                            value_buf.clear();
                             continue;
                         }
                     };
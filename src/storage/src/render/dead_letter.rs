@@ -0,0 +1,303 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Routing of undecodable source messages to a Kafka dead letter topic.
+
+use std::any::Any;
+use std::collections::{BTreeMap, VecDeque};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{StreamExt, TryFutureExt};
+use rdkafka::client::ClientContext;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::{Header, Message, OwnedHeaders, OwnedMessage};
+use rdkafka::producer::{BaseRecord, DeliveryResult, Producer, ProducerContext, ThreadedProducer};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::{Scope, Stream};
+use tracing::warn;
+
+use mz_kafka_util::client::{BrokerRewritingClientContext, MzClientContext};
+use mz_ore::retry::Retry;
+use mz_ore::task;
+use mz_repr::Timestamp;
+use mz_storage_client::types::connections::{ConnectionContext, KafkaConnection};
+use mz_storage_client::types::errors::DecodeError;
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::source::types::{DecodeResult, HealthStatusUpdate};
+use crate::source::HealthStatus;
+
+/// The maximum backoff between attempts to create the producer or to send a
+/// dead letter.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(60);
+
+/// How long to wait for outstanding dead letters to be delivered.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+type DeadLetterProducer = ThreadedProducer<BrokerRewritingClientContext<DeadLetterContext>>;
+
+/// Collects the dead letters whose delivery failed, so that they can be sent
+/// again.
+struct DeadLetterContext {
+    failed: Arc<Mutex<VecDeque<(String, OwnedMessage)>>>,
+}
+
+impl ClientContext for DeadLetterContext {
+    // The shape of the rdkafka *Context traits require us to forward to the `MzClientContext`
+    // implementation.
+    fn log(&self, level: rdkafka::config::RDKafkaLogLevel, fac: &str, log_message: &str) {
+        MzClientContext.log(level, fac, log_message)
+    }
+    fn error(&self, error: KafkaError, reason: &str) {
+        MzClientContext.error(error, reason)
+    }
+}
+
+impl ProducerContext for DeadLetterContext {
+    type DeliveryOpaque = ();
+
+    fn delivery(&self, result: &DeliveryResult, _: Self::DeliveryOpaque) {
+        if let Err((e, msg)) = result {
+            warn!("dead letter delivery error {:?} for {:?}", e, msg);
+            self.failed
+                .lock()
+                .expect("lock poisoned")
+                .push_back((e.to_string(), msg.detach()));
+        }
+    }
+}
+
+/// Writes every result in `input` that failed to decode to `topic`, and passes
+/// through only the results that decoded successfully.
+///
+/// Each dead letter carries the raw bytes that failed to decode as its payload,
+/// and the decoding error and the message's upstream partition and offset as
+/// headers. Messages are delivered at least once: results that are decoded
+/// again after a restart are written again.
+///
+/// Dead letters that cannot be written are retried until they are delivered,
+/// and the returned health stream reports the source as stalled in the
+/// meantime.
+pub(crate) fn render<G>(
+    input: &Stream<G, DecodeResult>,
+    connection: KafkaConnection,
+    topic: String,
+    connection_context: ConnectionContext,
+) -> (
+    Stream<G, DecodeResult>,
+    Stream<G, (usize, HealthStatusUpdate)>,
+    Rc<dyn Any>,
+)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let scope = input.scope();
+    let worker_id = scope.index();
+    let mut builder = AsyncOperatorBuilder::new(format!("DeadLetterTopic({topic})"), scope);
+
+    let mut input = builder.new_input(input, Pipeline);
+    let (mut output, output_stream) = builder.new_output();
+    let (mut health_output, health_stream) = builder.new_output();
+
+    let button = builder.build(move |mut capabilities| async move {
+        let health_cap = capabilities.pop().expect("missing capability");
+        // Passed through results are emitted at the capabilities of their
+        // input, so the initial capability of the output is not needed.
+        drop(capabilities);
+
+        let mut stalled = false;
+        let mut report = |update: Option<String>| {
+            if update.is_some() == stalled {
+                return;
+            }
+            stalled = update.is_some();
+            let update = match update {
+                Some(error) => HealthStatus::StalledWithError(error),
+                None => HealthStatus::Running,
+            };
+            health_output.activate().session(&health_cap).give((
+                worker_id,
+                HealthStatusUpdate {
+                    update,
+                    should_halt: false,
+                },
+            ));
+        };
+
+        let failed = Arc::new(Mutex::new(VecDeque::new()));
+        let retries = Retry::default()
+            .max_tries(usize::MAX)
+            .clamp_backoff(BACKOFF_CLAMP)
+            .into_retry_stream();
+        tokio::pin!(retries);
+        let producer = loop {
+            retries.next().await.expect("infinite stream");
+            let context = DeadLetterContext {
+                failed: Arc::clone(&failed),
+            };
+            match connection
+                .create_with_context(&connection_context, context, &BTreeMap::new())
+                .await
+            {
+                Ok(producer) => break Arc::new(producer),
+                Err(e) => {
+                    warn!("creating Kafka producer for dead letter topic {topic} failed: {e:#}");
+                    report(Some(format!(
+                        "creating Kafka producer for dead letter topic failed: {e:#}"
+                    )));
+                }
+            }
+        };
+        report(None);
+
+        let mut buffer = Vec::new();
+        let mut passed = Vec::new();
+        while let Some(event) = input.next().await {
+            let (cap, data) = match event {
+                Event::Data(cap, data) => (cap.retain(), data),
+                Event::Progress(_) => continue,
+            };
+            data.swap(&mut buffer);
+
+            let mut sent = false;
+            for result in buffer.drain(..) {
+                let errors: Vec<_> = [
+                    result.key.as_ref().and_then(|key| key.as_ref().err()),
+                    result.value.as_ref().and_then(|value| value.as_ref().err()),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if errors.is_empty() {
+                    passed.push(result);
+                    continue;
+                }
+                for error in errors {
+                    send(&producer, record(&topic, error, &result), &mut report).await;
+                    sent = true;
+                }
+            }
+            if sent {
+                flush(&producer, &failed, &mut report).await;
+            }
+
+            output.activate().session(&cap).give_vec(&mut passed);
+        }
+    });
+
+    (
+        output_stream,
+        health_stream,
+        Rc::new(button.press_on_drop()),
+    )
+}
+
+/// Builds the dead letter for the `error` that decoding `result` produced.
+fn record(topic: &str, error: &DecodeError, result: &DecodeResult) -> OwnedMessage {
+    let reason = error.kind.to_string();
+    let partition = result.partition.to_string();
+    let offset = result.position.to_string();
+    let headers = OwnedHeaders::new()
+        .insert(Header {
+            key: "materialize-error",
+            value: Some(reason.as_bytes()),
+        })
+        .insert(Header {
+            key: "materialize-partition",
+            value: Some(partition.as_bytes()),
+        })
+        .insert(Header {
+            key: "materialize-offset",
+            value: Some(offset.as_bytes()),
+        });
+    OwnedMessage::new(
+        error.raw.clone(),
+        None,
+        topic.to_string(),
+        rdkafka::Timestamp::NotAvailable,
+        0,
+        0,
+        Some(headers),
+    )
+}
+
+/// Enqueues `msg` with the producer, retrying until it is accepted.
+async fn send(
+    producer: &DeadLetterProducer,
+    msg: OwnedMessage,
+    report: &mut impl FnMut(Option<String>),
+) {
+    let mut record: BaseRecord<[u8], [u8]> = BaseRecord::to(msg.topic());
+    if let Some(payload) = msg.payload() {
+        record = record.payload(payload);
+    }
+    if let Some(headers) = msg.headers() {
+        record = record.headers(headers.clone());
+    }
+    let tries = Retry::default()
+        .max_tries(usize::MAX)
+        .clamp_backoff(BACKOFF_CLAMP)
+        .into_retry_stream();
+    tokio::pin!(tries);
+    loop {
+        tries.next().await.expect("infinite stream");
+        match producer.send(record) {
+            Ok(()) => {
+                report(None);
+                return;
+            }
+            Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), rec)) => {
+                record = rec;
+            }
+            Err((e, rec)) => {
+                warn!(
+                    "unable to write to dead letter topic {}: {}",
+                    msg.topic(),
+                    e
+                );
+                report(Some(format!("unable to write to dead letter topic: {e}")));
+                record = rec;
+            }
+        }
+    }
+}
+
+/// Waits until all enqueued dead letters are delivered, sending the ones whose
+/// delivery failed again.
+async fn flush(
+    producer: &Arc<DeadLetterProducer>,
+    failed: &Mutex<VecDeque<(String, OwnedMessage)>>,
+    report: &mut impl FnMut(Option<String>),
+) {
+    loop {
+        let flush_producer = Arc::clone(producer);
+        let flushed = task::spawn_blocking(
+            || "dead_letter_flush",
+            move || flush_producer.flush(FLUSH_TIMEOUT),
+        )
+        .unwrap_or_else(|_| Err(KafkaError::Canceled))
+        .await;
+        if let Err(e) = flushed {
+            warn!("flushing dead letters failed: {e}");
+            continue;
+        }
+        let retries: Vec<_> = failed.lock().expect("lock poisoned").drain(..).collect();
+        if retries.is_empty() {
+            return;
+        }
+        for (error, msg) in retries {
+            report(Some(format!(
+                "unable to deliver to dead letter topic: {error}"
+            )));
+            send(producer, msg, report).await;
+        }
+    }
+}
@@ -115,6 +115,7 @@ use mz_storage_client::types::sources::IngestionDescription;
 use crate::source::types::SourcePersistSinkMetrics;
 use crate::storage_state::StorageState;
 
+mod dead_letter;
 mod debezium;
 mod persist_sink;
 pub mod sinks;
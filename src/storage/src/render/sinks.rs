@@ -68,8 +68,17 @@ pub(crate) fn render_sink<G: Scope<Timestamp = Timestamp>>(
     );
     needed_tokens.push(source_token);
 
-    let ok_collection =
-        apply_sink_envelope(sink_id, sink, &sink_render, ok_collection.as_collection());
+    let mut ok_collection = ok_collection.as_collection();
+    // Retain only the columns requested by the `COLUMNS` clause, if any.
+    if let Some(projection) = sink.projection.clone() {
+        let mut datum_vec = mz_repr::DatumVec::new();
+        ok_collection = ok_collection.map(move |row| {
+            let datums = datum_vec.borrow_with(&row);
+            Row::pack(projection.iter().map(|idx| datums[*idx]))
+        });
+    }
+
+    let ok_collection = apply_sink_envelope(sink_id, sink, &sink_render, ok_collection);
 
     let healthchecker_args = HealthcheckerArgs {
         persist_clients: Arc::clone(&storage_state.persist_clients),
@@ -246,5 +255,10 @@ where
 {
     match connection {
         StorageSinkConnection::Kafka(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Object(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Postgres(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Elasticsearch(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Redis(connection) => Box::new(connection.clone()),
+        StorageSinkConnection::Nats(connection) => Box::new(connection.clone()),
     }
 }
@@ -17,12 +17,14 @@ use std::sync::Arc;
 
 use differential_dataflow::{collection, AsCollection, Collection, Hashable};
 use serde::{Deserialize, Serialize};
-use timely::dataflow::operators::{Exchange, Map, OkErr, ToStream};
+use timely::dataflow::operators::{
+    Concatenate, ConnectLoop, Exchange, Feedback, Map, OkErr, ToStream,
+};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 use tokio::runtime::Handle as TokioHandle;
 
-use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker, Timestamp};
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row, RowArena, RowPacker, Timestamp};
 use mz_storage_client::controller::CollectionMetadata;
 use mz_storage_client::source::persist_source;
 use mz_storage_client::types::errors::{DataflowError, DecodeError, EnvelopeError};
@@ -30,7 +32,7 @@ use mz_storage_client::types::sources::{encoding::*, *};
 use mz_timely_util::operator::{CollectionExt, StreamExt};
 
 use crate::decode::{render_decode, render_decode_cdcv2, render_decode_delimited};
-use crate::source::types::{DecodeResult, SourceOutput};
+use crate::source::types::{DecodeResult, HealthStatusUpdate, SourceOutput};
 use crate::source::{self, DelimitedValueSourceConnection, RawSourceCreationConfig};
 
 /// A type-level enum that holds one of two types of sources depending on their message type
@@ -104,12 +106,19 @@ where
             .get(&id)
             .expect("statistics initialized")
             .clone(),
+        rate_limits: description.desc.rate_limits.clone(),
     };
 
     // TODO(petrosagg): put the description as-is in the RawSourceCreationConfig instead of cloning
     // a million fields
     let resumption_calculator = description.clone();
 
+    // Operators downstream of the raw source report their health to it through
+    // this feedback edge, which is connected once all outputs are rendered.
+    // Health updates don't affect the timestamps of any data, so we don't
+    // bother about increasing the timestamp on feeding back using the summary.
+    let (health_handle, downstream_health) = scope.feedback(Timestamp::default());
+
     // Build the _raw_ ok and error sources using `create_raw_source` and the
     // correct `SourceReader` implementations
     let ((ok_sources, err_source), capability) = match connection {
@@ -120,6 +129,7 @@ where
                 connection,
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks: Vec<_> = oks.into_iter().map(SourceType::Delimited).collect();
             ((oks, err), cap)
@@ -131,6 +141,7 @@ where
                 DelimitedValueSourceConnection(connection),
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks = oks.into_iter().map(SourceType::Delimited).collect();
             ((oks, err), cap)
@@ -142,6 +153,7 @@ where
                 connection,
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks = oks.into_iter().map(SourceType::ByteStream).collect();
             ((oks, err), cap)
@@ -153,10 +165,59 @@ where
                 connection,
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks = oks.into_iter().map(SourceType::Row).collect();
             ((oks, err), cap)
         }
+        GenericSourceConnection::MySql(connection) => {
+            let ((oks, err), cap) = source::create_raw_source(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+                downstream_health,
+            );
+            let oks = oks.into_iter().map(SourceType::Row).collect();
+            ((oks, err), cap)
+        }
+        GenericSourceConnection::SqlServer(connection) => {
+            let ((oks, err), cap) = source::create_raw_source(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+                downstream_health,
+            );
+            let oks = oks.into_iter().map(SourceType::Row).collect();
+            ((oks, err), cap)
+        }
+        GenericSourceConnection::Mqtt(connection) => {
+            let ((oks, err), cap) = source::create_raw_source(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+                downstream_health,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
+        GenericSourceConnection::PubSub(connection) => {
+            let ((oks, err), cap) = source::create_raw_source(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+                downstream_health,
+            );
+            let oks = oks.into_iter().map(SourceType::Delimited).collect();
+            ((oks, err), cap)
+        }
         GenericSourceConnection::LoadGenerator(connection) => {
             let ((oks, err), cap) = source::create_raw_source(
                 scope,
@@ -164,6 +225,7 @@ where
                 connection,
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks = oks.into_iter().map(SourceType::Row).collect();
             ((oks, err), cap)
@@ -175,10 +237,42 @@ where
                 connection,
                 storage_state.connection_context.clone(),
                 resumption_calculator,
+                downstream_health,
             );
             let oks: Vec<_> = oks.into_iter().map(SourceType::Delimited).collect();
             ((oks, err), cap)
         }
+        GenericSourceConnection::File(connection) => {
+            let whole_file =
+                source::FileSourceReader::reads_whole_file(&base_source_config.encoding);
+            let ((oks, err), cap) = source::create_raw_source(
+                scope,
+                base_source_config,
+                connection,
+                storage_state.connection_context.clone(),
+                resumption_calculator,
+                downstream_health,
+            );
+            let oks: Vec<_> = if whole_file {
+                // A file that is read as a whole has no keys.
+                oks.into_iter()
+                    .map(|ok| {
+                        SourceType::ByteStream(ok.map(|output| SourceOutput {
+                            key: (),
+                            value: output.value,
+                            position: output.position,
+                            upstream_time_millis: output.upstream_time_millis,
+                            partition: output.partition,
+                            headers: output.headers,
+                            diff: output.diff,
+                        }))
+                    })
+                    .collect()
+            } else {
+                oks.into_iter().map(SourceType::Delimited).collect()
+            };
+            ((oks, err), cap)
+        }
     };
 
     let source_token = Rc::new(capability);
@@ -186,6 +280,7 @@ where
     needed_tokens.push(source_token);
 
     let mut outputs = vec![];
+    let mut health_streams = vec![];
     for ok_source in ok_sources {
         // All sources should push their various error streams into this vector,
         // whose contents will be concatenated and inserted along the collection.
@@ -203,11 +298,15 @@ where
             description.clone(),
             resume_upper.clone(),
             error_collections,
+            &mut health_streams,
             storage_state,
         );
         needed_tokens.extend(extra_tokens);
         outputs.push((ok, err));
     }
+    scope
+        .concatenate(health_streams)
+        .connect_loop(health_handle);
     (outputs, Rc::new(needed_tokens))
 }
 
@@ -230,6 +329,7 @@ fn render_source_stream<G>(
     description: IngestionDescription<CollectionMetadata>,
     resume_upper: Antichain<G::Timestamp>,
     mut error_collections: Vec<Collection<G, DataflowError, Diff>>,
+    health_streams: &mut Vec<Stream<G, (usize, HealthStatusUpdate)>>,
     storage_state: &mut crate::storage_state::StorageState,
 ) -> (
     Collection<G, Row, Diff>,
@@ -242,9 +342,11 @@ where
     let mut needed_tokens: Vec<Rc<dyn Any>> = vec![];
 
     let SourceDesc {
+        connection,
         encoding,
         envelope,
         metadata_columns,
+        mfp,
         ..
     } = description.desc;
     let (stream, errors) = {
@@ -262,6 +364,7 @@ where
                 schema,
                 csr_connection,
                 confluent_wire_format,
+                skip_incompatible_schemas: _,
             } = match value_encoding.inner {
                 DataEncodingInner::Avro(enc) => enc,
                 _ => unreachable!("Attempted to create non-Avro CDCv2 source"),
@@ -325,6 +428,25 @@ where
                 needed_tokens.push(Rc::new(tok));
             }
 
+            let results = match &connection {
+                GenericSourceConnection::Kafka(KafkaSourceConnection {
+                    connection,
+                    dead_letter_topic: Some(topic),
+                    ..
+                }) => {
+                    let (results, health, token) = super::dead_letter::render(
+                        &results,
+                        connection.clone(),
+                        topic.clone(),
+                        storage_state.connection_context.clone(),
+                    );
+                    health_streams.push(health);
+                    needed_tokens.push(token);
+                    results
+                }
+                _ => results,
+            };
+
             // render envelopes
             match &envelope {
                 SourceEnvelope::Debezium(dbz_envelope) => {
@@ -453,8 +575,34 @@ where
 
                     let (stream, errors) = flattened_stream.ok_err(split_ok_err);
 
-                    let errors = errors.as_collection();
-                    (stream.as_collection(), Some(errors))
+                    let mut stream = stream.as_collection();
+                    let mut errors = errors.as_collection();
+
+                    // Apply the source's projection and filter before the
+                    // exchange below, so that discarded columns and rows are
+                    // never shipped to other workers.
+                    if let Some(mfp) = mfp {
+                        let plan = mfp
+                            .into_plan()
+                            .expect("source MFP is valid")
+                            .into_nontemporal()
+                            .expect("source MFP is non-temporal");
+                        let (oks, errs) = stream.flat_map_fallible("SourceMapFilterProject", {
+                            let mut datum_vec = DatumVec::new();
+                            let mut row_builder = Row::default();
+                            move |row| {
+                                let arena = RowArena::new();
+                                let mut datums_local = datum_vec.borrow_with(&row);
+                                plan.evaluate_into(&mut datums_local, &arena, &mut row_builder)
+                                    .map_err(DataflowError::from)
+                                    .transpose()
+                            }
+                        });
+                        stream = oks;
+                        errors = errors.concat(&errs);
+                    }
+
+                    (stream, Some(errors))
                 }
                 SourceEnvelope::CdcV2 => unreachable!(),
             }
@@ -106,6 +106,7 @@ pub fn serve(
                 sink_handles: HashMap::new(),
                 dropped_ids: Vec::new(),
                 source_statistics: HashMap::new(),
+                sink_statistics: HashMap::new(),
             },
         }
         .run()
@@ -0,0 +1,471 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that maintains an index in an Elasticsearch or OpenSearch cluster.
+//!
+//! Once the input frontier passes a timestamp, the updates at that timestamp
+//! are applied to the index with bulk requests, which index the new value of
+//! each updated key as a document, or delete the document of each deleted key.
+//! Once all the updates at the timestamps that a frontier closes are applied,
+//! the last of those timestamps is recorded in the progress index, and a
+//! restarted sink skips the updates at timestamps that it has already applied.
+//!
+//! Bulk requests are not atomic, so readers may observe the index partway
+//! through applying the updates at a timestamp.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use differential_dataflow::{Collection, Hashable};
+use reqwest::{Method, StatusCode};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{info, warn};
+
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_ore::{halt, task};
+use mz_repr::{ColumnName, ColumnType, Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::types::connections::{ConnectionContext, ElasticsearchClient};
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    ElasticsearchSinkConnection, MetadataFilled, SinkAsOf, SinkErrorPolicy, StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatistics, SinkStatus};
+use crate::storage_state::StorageState;
+
+/// The maximum number of documents that a single bulk request indexes or
+/// deletes.
+const MAX_BULK_ACTIONS: usize = 1000;
+
+impl<G> SinkRender<G> for ElasticsearchSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // As with Kafka sinks, only the worker to which the updates are
+        // exchanged ever holds back the write frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = write_index(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            sink.error_policy,
+            Rc::clone(&shared_frontier),
+            storage_state.sink_statistics[&sink_id].clone(),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// Applies a stream of upsert-formatted updates to an Elasticsearch index.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// sink `id`. Updates are applied in ascending timestamp order once the input
+/// frontier has passed their timestamp, and updates that are not beyond the
+/// given [`SinkAsOf`] or that the index already reflects are discarded.
+///
+/// A write that still fails after [`WRITE_ATTEMPTS`] attempts is handled as
+/// the given [`SinkErrorPolicy`] asks.
+fn write_index<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: ElasticsearchSinkConnection,
+    as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("elasticsearch-{}", id);
+    let scope = collection.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), scope.clone());
+
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => Some(
+                Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker"),
+            ),
+            None => None,
+        };
+        let mut s = ElasticsearchSinkState {
+            name,
+            healthchecker,
+            error_policy,
+            statistics,
+        };
+
+        s.update_status(SinkStatus::Starting).await;
+
+        let writer = ElasticsearchWriter::new(&connection, id, &connection_context).await;
+        let writer = s.halt_on_err(writer).await;
+        let gate_ts = writer.latest_applied_ts().await;
+        let gate_ts = s.halt_on_err(gate_ts).await;
+        info!(
+            "{}: initial as_of: {:?}, latest applied timestamp: {:?}",
+            s.name, as_of.frontier, gate_ts
+        );
+
+        s.update_status(SinkStatus::Running).await;
+
+        let mut pending_rows: BTreeMap<Timestamp, Vec<(Row, Option<Row>)>> = BTreeMap::new();
+        let mut vector = Vec::new();
+        while let Some(event) = input.next().await {
+            match event {
+                Event::Data(_, rows) => {
+                    rows.swap(&mut vector);
+                    for ((key, value), time, diff) in vector.drain(..) {
+                        let should_emit = if as_of.strict {
+                            as_of.frontier.less_than(&time)
+                        } else {
+                            as_of.frontier.less_equal(&time)
+                        };
+                        let previously_applied = Some(time) <= gate_ts;
+                        if !should_emit || previously_applied || diff == 0 {
+                            continue;
+                        }
+                        let key = key.expect("elasticsearch sinks always have a key");
+                        pending_rows.entry(time).or_default().push((key, value));
+                    }
+                }
+                Event::Progress(frontier) => {
+                    let mut updates = vec![];
+                    let mut last_ts = None;
+                    while let Some(entry) = pending_rows.first_entry() {
+                        if frontier.less_equal(entry.key()) {
+                            break;
+                        }
+                        let (ts, rows) = entry.remove_entry();
+                        updates.extend(rows);
+                        last_ts = Some(ts);
+                    }
+
+                    if let Some(ts) = last_ts {
+                        let result = Retry::default()
+                            .max_tries(WRITE_ATTEMPTS)
+                            .clamp_backoff(BACKOFF_CLAMP)
+                            .retry_async(|_| writer.apply(&updates, ts))
+                            .await;
+                        if let Err(e) = result {
+                            if !s.handle_write_err(e, updates.len()).await {
+                                return;
+                            }
+                        }
+                    }
+
+                    // Every update before the frontier has been applied, and
+                    // every update at or beyond it is still pending.
+                    *write_frontier.borrow_mut() = frontier;
+                }
+            }
+        }
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+/// The number of times the sink attempts to apply the updates at a timestamp
+/// before it gives up as its [`SinkErrorPolicy`] asks.
+const WRITE_ATTEMPTS: usize = 5;
+
+/// The longest the sink waits between attempts to apply updates.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+struct ElasticsearchSinkState {
+    name: String,
+    healthchecker: Option<Healthchecker>,
+    error_policy: SinkErrorPolicy,
+    statistics: SinkStatistics,
+}
+
+impl ElasticsearchSinkState {
+    async fn update_status(&mut self, status: SinkStatus) {
+        if let Some(hc) = &mut self.healthchecker {
+            hc.update_status(status).await;
+        }
+    }
+
+    /// Report a SinkStatus::Stalled and then halt with the same message.
+    async fn halt_on_err<T>(&mut self, result: Result<T, impl ToString + Debug>) -> T {
+        match result {
+            Ok(t) => t,
+            Err(msg) => {
+                self.update_status(SinkStatus::Stalled(msg.to_string()))
+                    .await;
+                halt!("{}: {msg:?}", self.name)
+            }
+        }
+    }
+
+    /// Handle a failed write of `count` updates as the sink's error policy
+    /// asks, returning whether the sink should keep writing.
+    async fn handle_write_err(&mut self, err: anyhow::Error, count: usize) -> bool {
+        match self.error_policy {
+            SinkErrorPolicy::Retry => self.halt_on_err(Err(err)).await,
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed write: {err:#}", self.name);
+                self.update_status(SinkStatus::Paused(err.to_string()))
+                    .await;
+                false
+            }
+            SinkErrorPolicy::Drop => {
+                warn!(
+                    "{}: dropping {count} updates after failed write: {err:#}",
+                    self.name
+                );
+                self.statistics
+                    .inc_updates_dropped_by(u64::cast_from(count));
+                true
+            }
+        }
+    }
+}
+
+/// Applies updates to the index of a sink, and records its progress.
+///
+/// The client is driven on the Tokio runtime, so every request is issued from
+/// a spawned task.
+struct ElasticsearchWriter {
+    name: String,
+    sink_id: String,
+    client: ElasticsearchClient,
+    index: String,
+    key_columns: Vec<(ColumnName, ColumnType)>,
+    value_columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl ElasticsearchWriter {
+    async fn new(
+        connection: &ElasticsearchSinkConnection,
+        id: GlobalId,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        let client = connection
+            .connection
+            .client(&*connection_context.secrets_reader)
+            .await?;
+        Ok(ElasticsearchWriter {
+            name: format!("elasticsearch-{}", id),
+            sink_id: id.to_string(),
+            client,
+            index: connection.index.clone(),
+            key_columns: column_names_and_types(connection.key_desc_and_indices.0.clone()),
+            value_columns: column_names_and_types(connection.value_desc.clone()),
+        })
+    }
+
+    /// Returns the path of the document in which the sink records its
+    /// progress.
+    fn progress_path(&self) -> String {
+        format!(
+            "{}/_doc/{}",
+            ElasticsearchSinkConnection::PROGRESS_INDEX,
+            self.sink_id
+        )
+    }
+
+    /// Returns the latest timestamp that the index reflects, if the sink has
+    /// ever applied any updates to it.
+    async fn latest_applied_ts(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        let request = self.client.request(Method::GET, &self.progress_path());
+        let (status, body) = self.send(request).await?;
+        if status == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let document = parse_response(status, body)?;
+        let ts = document["_source"]["ts"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("invalid progress document: {}", document))?;
+        Ok(Some(Timestamp::from(ts)))
+    }
+
+    /// Applies `updates`, which hold the new value of each updated key, or
+    /// `None` if the key was deleted, and then records that the index reflects
+    /// the sinked collection as of `ts`.
+    async fn apply(
+        &self,
+        updates: &[(Row, Option<Row>)],
+        ts: Timestamp,
+    ) -> Result<(), anyhow::Error> {
+        for chunk in updates.chunks(MAX_BULK_ACTIONS) {
+            let mut body = String::new();
+            for (key, value) in chunk {
+                let metadata = serde_json::json!({
+                    "_index": self.index,
+                    "_id": self.document_id(key),
+                });
+                match value {
+                    Some(value) => {
+                        let document = encode_datums_as_json(value.iter(), &self.value_columns);
+                        body.push_str(&serde_json::json!({ "index": metadata }).to_string());
+                        body.push('\n');
+                        body.push_str(&document.to_string());
+                        body.push('\n');
+                    }
+                    None => {
+                        body.push_str(&serde_json::json!({ "delete": metadata }).to_string());
+                        body.push('\n');
+                    }
+                }
+            }
+            let request = self
+                .client
+                .request(Method::POST, "_bulk")
+                .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+                .body(body);
+            let (status, body) = self.send(request).await?;
+            check_bulk_response(&parse_response(status, body)?)?;
+        }
+
+        let request = self
+            .client
+            .request(Method::PUT, &self.progress_path())
+            .json(&serde_json::json!({ "ts": u64::from(ts) }));
+        let (status, body) = self.send(request).await?;
+        parse_response(status, body).context("recording sink progress")?;
+        Ok(())
+    }
+
+    /// Sends `request`, and returns the status and body of its response.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<(StatusCode, String), anyhow::Error> {
+        task::spawn(|| self.name.clone(), async move {
+            let response = request.send().await?;
+            let status = response.status();
+            Ok::<_, anyhow::Error>((status, response.text().await?))
+        })
+        .await?
+    }
+
+    /// Returns the ID of the document for `key`: the value of the key column
+    /// if there is only one, and a JSON array of the values of the key columns
+    /// otherwise.
+    fn document_id(&self, key: &Row) -> String {
+        let key = encode_datums_as_json(key.iter(), &self.key_columns);
+        let mut values = self
+            .key_columns
+            .iter()
+            .map(|(name, _)| key[name.as_str()].clone())
+            .collect::<Vec<_>>();
+        if values.len() == 1 {
+            match values.remove(0) {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            }
+        } else {
+            serde_json::Value::Array(values).to_string()
+        }
+    }
+}
+
+/// Parses the JSON body of a response, or returns an error with the body if
+/// the request failed.
+fn parse_response(status: StatusCode, body: String) -> Result<serde_json::Value, anyhow::Error> {
+    if !status.is_success() {
+        return Err(anyhow!("{}: {}", status, body));
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Returns an error describing the first action of a bulk request that failed,
+/// if any did.
+///
+/// Deleting a document that does not exist is not an error, as a key that was
+/// deleted before a restart may be deleted again.
+fn check_bulk_response(response: &serde_json::Value) -> Result<(), anyhow::Error> {
+    if response["errors"].as_bool() != Some(true) {
+        return Ok(());
+    }
+    let items = response["items"].as_array().into_iter().flatten();
+    for item in items {
+        let (action, result) = match item.as_object().and_then(|item| item.iter().next()) {
+            Some(entry) => entry,
+            None => continue,
+        };
+        let not_found = result["status"].as_u64() == Some(404);
+        if result.get("error").is_some() && !(action == "delete" && not_found) {
+            return Err(anyhow!("bulk {} failed: {}", action, result["error"]));
+        }
+    }
+    Ok(())
+}
@@ -106,6 +106,9 @@ pub enum SinkStatus {
     /// Represents a stall in the export process that might get resolved.
     /// Existing data is still available and queryable.
     Stalled(String),
+    /// Represents a sink that stopped writing after a write failed, as its
+    /// error policy asks. Existing data is still available and queryable.
+    Paused(String),
     /// Represents a irrecoverable failure in the pipeline. Data from this collection
     /// is not queryable any longer. The only valid transition from Failed is Dropped.
     Failed(String),
@@ -120,6 +123,7 @@ impl SinkStatus {
             SinkStatus::Starting => "starting",
             SinkStatus::Running => "running",
             SinkStatus::Stalled(_) => "stalled",
+            SinkStatus::Paused(_) => "paused",
             SinkStatus::Failed(_) => "failed",
             SinkStatus::Dropped => "dropped",
         }
@@ -128,6 +132,7 @@ impl SinkStatus {
     fn error(&self) -> Option<&str> {
         match self {
             SinkStatus::Stalled(e) => Some(&*e),
+            SinkStatus::Paused(e) => Some(&*e),
             SinkStatus::Failed(e) => Some(&*e),
             SinkStatus::Setup => None,
             SinkStatus::Starting => None,
@@ -148,7 +153,8 @@ impl SinkStatus {
                 old @ SinkStatus::Setup
                 | old @ SinkStatus::Starting
                 | old @ SinkStatus::Running
-                | old @ SinkStatus::Stalled(_),
+                | old @ SinkStatus::Stalled(_)
+                | old @ SinkStatus::Paused(_),
             ) => old != new_status,
         }
     }
@@ -191,6 +197,10 @@ mod tests {
         SinkStatus::Stalled("".into())
     }
 
+    fn paused() -> SinkStatus {
+        SinkStatus::Paused("".into())
+    }
+
     fn failed() -> SinkStatus {
         SinkStatus::Failed("".into())
     }
@@ -309,6 +319,7 @@ mod tests {
                     SinkStatus::Starting,
                     SinkStatus::Running,
                     stalled(),
+                    paused(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
@@ -320,6 +331,7 @@ mod tests {
                     SinkStatus::Setup,
                     SinkStatus::Running,
                     stalled(),
+                    paused(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
@@ -331,6 +343,7 @@ mod tests {
                     SinkStatus::Setup,
                     SinkStatus::Starting,
                     stalled(),
+                    paused(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
@@ -342,6 +355,19 @@ mod tests {
                     SinkStatus::Setup,
                     SinkStatus::Starting,
                     SinkStatus::Running,
+                    paused(),
+                    failed(),
+                    SinkStatus::Dropped,
+                ],
+                true,
+            ),
+            (
+                Some(paused()),
+                vec![
+                    SinkStatus::Setup,
+                    SinkStatus::Starting,
+                    SinkStatus::Running,
+                    stalled(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
@@ -355,6 +381,7 @@ mod tests {
                     SinkStatus::Starting,
                     SinkStatus::Running,
                     stalled(),
+                    paused(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
@@ -369,6 +396,7 @@ mod tests {
             ),
             (Some(SinkStatus::Running), vec![SinkStatus::Running], false),
             (Some(stalled()), vec![stalled()], false),
+            (Some(paused()), vec![paused()], false),
             (
                 Some(failed()),
                 vec![
@@ -376,6 +404,7 @@ mod tests {
                     SinkStatus::Starting,
                     SinkStatus::Running,
                     stalled(),
+                    paused(),
                     failed(),
                 ],
                 false,
@@ -387,6 +416,7 @@ mod tests {
                     SinkStatus::Starting,
                     SinkStatus::Running,
                     stalled(),
+                    paused(),
                     failed(),
                     SinkStatus::Dropped,
                 ],
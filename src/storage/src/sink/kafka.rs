@@ -44,6 +44,7 @@ use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, error, info, warn};
 
 use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator};
+use mz_interchange::csv::CsvEncoder;
 use mz_interchange::encode::Encode;
 use mz_interchange::json::JsonEncoder;
 use mz_kafka_util::client::{BrokerRewritingClientContext, MzClientContext};
@@ -56,13 +57,13 @@ use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::errors::DataflowError;
 use mz_storage_client::types::sinks::{
-    KafkaSinkConnection, MetadataFilled, PublishedSchemaInfo, SinkAsOf, SinkEnvelope,
-    StorageSinkDesc,
+    KafkaSinkConnection, KafkaSinkCsvFormat, KafkaSinkPartitionStrategy, MetadataFilled,
+    PublishedSchemaInfo, SinkAsOf, SinkEnvelope, SinkErrorPolicy, StorageSinkDesc,
 };
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 
 use crate::render::sinks::{HealthcheckerArgs, SinkRender};
-use crate::sink::{Healthchecker, KafkaBaseMetrics, SinkStatus};
+use crate::sink::{Healthchecker, KafkaBaseMetrics, SinkStatistics, SinkStatus};
 use crate::storage_state::StorageState;
 
 // 30s is a good maximum backoff for network operations. Long enough to reduce
@@ -126,7 +127,9 @@ where
             self.clone(),
             sink.envelope,
             sink.as_of.clone(),
+            sink.error_policy,
             Rc::clone(&shared_frontier),
+            storage_state.sink_statistics[&sink_id].clone(),
             &storage_state.sink_metrics.kafka,
             &storage_state.connection_context,
             healthchecker_args,
@@ -288,6 +291,21 @@ impl KafkaTxProducer {
         .unwrap_or_else(|_| Err(KafkaError::Canceled))
     }
 
+    fn fetch_partitions(
+        &self,
+        topic: String,
+    ) -> impl Future<Output = Result<Vec<i32>, anyhow::Error>> {
+        let self_producer = Arc::clone(&self.inner);
+        let self_timeout = self.timeout;
+        task::spawn_blocking(
+            || format!("fetch_partitions:{}", self.name),
+            move || {
+                mz_kafka_util::client::get_partitions(self_producer.client(), &topic, self_timeout)
+            },
+        )
+        .unwrap_or_else(|e| Err(anyhow!(e)))
+    }
+
     fn abort_transaction(&self) -> impl Future<Output = KafkaResult<()>> {
         let self_producer = Arc::clone(&self.inner);
         let self_timeout = self.timeout;
@@ -371,13 +389,27 @@ struct KafkaSinkState {
     ready_rows: VecDeque<(Timestamp, Vec<EncodedRow>)>,
     retry_manager: Arc<Mutex<KafkaSinkSendRetryManager>>,
 
+    partition_strategy: KafkaSinkPartitionStrategy,
+    /// The partitions of the topic, if the sink rather than the producer
+    /// chooses the partition of each message.
+    partitions: Vec<i32>,
+    /// The index in `partitions` of the partition of the next message.
+    next_partition: Cell<usize>,
+
     progress_topic: String,
     progress_key: String,
+    /// The topic to which the sink reports the timestamps through which
+    /// `topic` is complete, if any.
+    consistency_topic: Option<String>,
     progress_client: Option<Arc<BaseConsumer<BrokerRewritingClientContext<SinkConsumerContext>>>>,
 
     healthchecker: Arc<Mutex<Option<Healthchecker>>>,
     gate_ts: Rc<Cell<Option<Timestamp>>>,
 
+    /// What to do with a message that the producer refuses to send.
+    error_policy: SinkErrorPolicy,
+    statistics: SinkStatistics,
+
     /// Timestamp of the latest progress record that was written out to Kafka.
     latest_progress_ts: Timestamp,
 
@@ -419,6 +451,8 @@ impl KafkaSinkState {
         metrics: &KafkaBaseMetrics,
         connection_context: &ConnectionContext,
         gate_ts: Rc<Cell<Option<Timestamp>>>,
+        error_policy: SinkErrorPolicy,
+        statistics: SinkStatistics,
     ) -> Self {
         let metrics = Arc::new(SinkMetrics::new(
             metrics,
@@ -450,37 +484,43 @@ impl KafkaSinkState {
             });
         }
 
+        let mut producer_options = btreemap! {
+            // Ensure that messages are sinked in order and without
+            // duplicates. Note that this only applies to a single
+            // instance of a producer - in the case of restarts, all
+            // bets are off and full exactly once support is required.
+            "enable.idempotence" => "true".into(),
+            // Increase limits for the Kafka producer's internal
+            // buffering of messages Currently we don't have a great
+            // backpressure mechanism to tell indexes or views to slow
+            // down, so the only thing we can do with a message that we
+            // can't immediately send is to put it in a buffer and
+            // there's no point having buffers within the dataflow layer
+            // and Kafka If the sink starts falling behind and the
+            // buffers start consuming too much memory the best thing to
+            // do is to drop the sink Sets the buffer size to be 16 GB
+            // (note that this setting is in KB)
+            "queue.buffering.max.kbytes" => format!("{}", 16 << 20),
+            // Set the max messages buffered by the producer at any time
+            // to 10MM which is the maximum allowed value.
+            "queue.buffering.max.messages" => format!("{}", 10_000_000),
+            // Make the Kafka producer wait at least 10 ms before
+            // sending out MessageSets TODO(rkhaitan): experiment with
+            // different settings for this value to see if it makes a
+            // big difference.
+            "queue.buffering.max.ms" => format!("{}", 10),
+            "transactional.id" => format!("mz-producer-{sink_id}-{worker_id}"),
+        };
+        if connection.partition_strategy == KafkaSinkPartitionStrategy::Murmur2 {
+            // Partition messages as the Java client does, and choose a random
+            // partition for messages without a key.
+            producer_options.insert("partitioner", "murmur2_random".into());
+        }
         let producer = TokioHandle::current()
             .block_on(connection.connection.create_with_context(
                 connection_context,
                 producer_context,
-                &btreemap! {
-                    // Ensure that messages are sinked in order and without
-                    // duplicates. Note that this only applies to a single
-                    // instance of a producer - in the case of restarts, all
-                    // bets are off and full exactly once support is required.
-                    "enable.idempotence" => "true".into(),
-                    // Increase limits for the Kafka producer's internal
-                    // buffering of messages Currently we don't have a great
-                    // backpressure mechanism to tell indexes or views to slow
-                    // down, so the only thing we can do with a message that we
-                    // can't immediately send is to put it in a buffer and
-                    // there's no point having buffers within the dataflow layer
-                    // and Kafka If the sink starts falling behind and the
-                    // buffers start consuming too much memory the best thing to
-                    // do is to drop the sink Sets the buffer size to be 16 GB
-                    // (note that this setting is in KB)
-                    "queue.buffering.max.kbytes" => format!("{}", 16 << 20),
-                    // Set the max messages buffered by the producer at any time
-                    // to 10MM which is the maximum allowed value.
-                    "queue.buffering.max.messages" => format!("{}", 10_000_000),
-                    // Make the Kafka producer wait at least 10 ms before
-                    // sending out MessageSets TODO(rkhaitan): experiment with
-                    // different settings for this value to see if it makes a
-                    // big difference.
-                    "queue.buffering.max.ms" => format!("{}", 10),
-                    "transactional.id" => format!("mz-producer-{sink_id}-{worker_id}"),
-                },
+                &producer_options,
             ))
             .expect("creating Kafka producer for sink failed");
         let producer = KafkaTxProducer {
@@ -511,17 +551,35 @@ impl KafkaSinkState {
             pending_rows: HashMap::new(),
             ready_rows: VecDeque::new(),
             retry_manager,
+            partition_strategy: connection.partition_strategy,
+            partitions: vec![],
+            next_partition: Cell::new(0),
             progress_topic: connection.progress.topic,
             progress_key: format!("mz-sink-{sink_id}"),
+            consistency_topic: connection.consistency_topic,
             progress_client: Some(Arc::new(progress_client)),
             healthchecker,
             gate_ts,
+            error_policy,
+            statistics,
             latest_progress_ts: Timestamp::minimum(),
             write_frontier,
         }
     }
 
-    async fn send<'a, K, P>(&self, mut record: BaseRecord<'a, K, P>)
+    /// Sends `record`, halting if the producer refuses to send it.
+    async fn send<'a, K, P>(&self, record: BaseRecord<'a, K, P>)
+    where
+        K: ToBytes + ?Sized,
+        P: ToBytes + ?Sized,
+    {
+        let result = self.try_send(record).await;
+        self.halt_on_err(result).await
+    }
+
+    /// Sends `record`, retrying for as long as the producer's queue is full,
+    /// and returns any other error the producer reports.
+    async fn try_send<'a, K, P>(&self, mut record: BaseRecord<'a, K, P>) -> Result<(), String>
     where
         K: ToBytes + ?Sized,
         P: ToBytes + ?Sized,
@@ -537,7 +595,7 @@ impl KafkaSinkState {
                 Ok(()) => {
                     self.metrics.messages_sent_counter.inc();
                     self.retry_manager.lock().await.record_send();
-                    return;
+                    return Ok(());
                 }
                 Err((e, rec)) => {
                     record = *rec;
@@ -551,11 +609,10 @@ impl KafkaSinkState {
                         continue;
                     } else {
                         // We've received an error that is not transient
-                        self.halt_on_err(Err(format!(
+                        return Err(format!(
                             "fatal error while producing message in {}: {e}",
                             self.name
-                        )))
-                        .await
+                        ));
                     }
                 }
             }
@@ -754,6 +811,17 @@ impl KafkaSinkState {
             .await
     }
 
+    /// Returns the partition to which to write the next message, if the sink
+    /// rather than the producer chooses it.
+    fn next_partition(&self) -> Option<i32> {
+        if self.partitions.is_empty() {
+            return None;
+        }
+        let i = self.next_partition.get();
+        self.next_partition.set((i + 1) % self.partitions.len());
+        Some(self.partitions[i])
+    }
+
     async fn send_progress_record(&self, transaction_id: Timestamp) {
         let encoded = serde_json::to_vec(&ProgressRecord {
             timestamp: transaction_id,
@@ -762,7 +830,19 @@ impl KafkaSinkState {
         let record = BaseRecord::to(&self.progress_topic)
             .payload(&encoded)
             .key(&self.progress_key);
-        self.send(record).await
+        self.send(record).await;
+
+        if let Some(consistency_topic) = &self.consistency_topic {
+            let encoded = serde_json::to_vec(&ConsistencyRecord {
+                topic: &self.topic,
+                timestamp: transaction_id,
+            })
+            .expect("serialization to vec cannot fail");
+            let record = BaseRecord::to(consistency_topic)
+                .payload(&encoded)
+                .key(&self.topic);
+            self.send(record).await;
+        }
     }
 
     /// Asserts that the write frontier has not yet advanced beyond `t`.
@@ -904,6 +984,27 @@ impl KafkaSinkState {
             }
         }
     }
+
+    /// Handle a message that the producer refused to send as the sink's
+    /// error policy asks, returning whether the sink should keep writing.
+    ///
+    /// A paused sink leaves its open transaction to be aborted, so none of
+    /// the updates at the timestamp it was writing become visible.
+    async fn handle_send_err(&self, err: String) -> bool {
+        match self.error_policy {
+            SinkErrorPolicy::Retry => self.halt_on_err(Err(err)).await,
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed send: {err}", self.name);
+                self.update_status(SinkStatus::Paused(err)).await;
+                false
+            }
+            SinkErrorPolicy::Drop => {
+                warn!("{}: dropping message after failed send: {err}", self.name);
+                self.statistics.inc_updates_dropped_by(1);
+                true
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -920,7 +1021,9 @@ fn kafka<G>(
     connection: KafkaSinkConnection,
     envelope: Option<SinkEnvelope>,
     as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
     healthchecker_args: HealthcheckerArgs,
@@ -963,21 +1066,40 @@ where
                 name.clone(),
             )
         }
-        None => {
-            let encoder = JsonEncoder::new(
-                key_desc,
-                value_desc,
-                matches!(envelope, Some(SinkEnvelope::Debezium)),
-            );
-            encode_stream(
-                stream,
-                as_of.clone(),
-                Rc::clone(&shared_gate_ts),
-                encoder,
-                connection.fuel,
-                name.clone(),
-            )
-        }
+        None => match &connection.csv_format {
+            Some(KafkaSinkCsvFormat {
+                delimiter,
+                quote,
+                null,
+                header: _,
+            }) => {
+                let encoder =
+                    CsvEncoder::new(key_desc, value_desc, *delimiter, *quote, null.clone());
+                encode_stream(
+                    stream,
+                    as_of.clone(),
+                    Rc::clone(&shared_gate_ts),
+                    encoder,
+                    connection.fuel,
+                    name.clone(),
+                )
+            }
+            None => {
+                let encoder = JsonEncoder::new(
+                    key_desc,
+                    value_desc,
+                    matches!(envelope, Some(SinkEnvelope::Debezium)),
+                );
+                encode_stream(
+                    stream,
+                    as_of.clone(),
+                    Rc::clone(&shared_gate_ts),
+                    encoder,
+                    connection.fuel,
+                    name.clone(),
+                )
+            }
+        },
     };
 
     produce_to_kafka(
@@ -986,8 +1108,10 @@ where
         name,
         connection,
         as_of,
+        error_policy,
         shared_gate_ts,
         write_frontier,
+        statistics,
         metrics,
         connection_context,
         healthchecker_args,
@@ -1005,14 +1129,19 @@ where
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnection`] will be discarded without producing them.
+///
+/// An update that the producer refuses to send is handled as the given
+/// [`SinkErrorPolicy`] asks.
 pub fn produce_to_kafka<G>(
     stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
     id: GlobalId,
     name: String,
     connection: KafkaSinkConnection,
     as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
     shared_gate_ts: Rc<Cell<Option<Timestamp>>>,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
     metrics: &KafkaBaseMetrics,
     connection_context: &ConnectionContext,
     healthchecker_args: HealthcheckerArgs,
@@ -1032,6 +1161,8 @@ where
         metrics,
         connection_context,
         Rc::clone(&shared_gate_ts),
+        error_policy,
+        statistics,
     );
 
     let mut vector = Vec::new();
@@ -1074,6 +1205,11 @@ where
         )
         .await;
 
+        if s.partition_strategy == KafkaSinkPartitionStrategy::RoundRobin {
+            let partitions = s.producer.fetch_partitions(s.topic.clone()).await;
+            s.partitions = s.halt_on_err(partitions).await;
+        }
+
         let latest_ts = s.determine_latest_progress_record().await;
         let latest_ts = s.halt_on_err(latest_ts).await;
         info!(
@@ -1169,6 +1305,10 @@ where
                                 Some(r) => record.key(r),
                                 None => record,
                             };
+                            let record = match s.next_partition() {
+                                Some(partition) => record.partition(partition),
+                                None => record,
+                            };
 
                             let ts_bytes = ts.to_string().into_bytes();
                             let record = record.headers(OwnedHeaders::new().insert(Header {
@@ -1176,7 +1316,11 @@ where
                                 value: Some(&ts_bytes),
                             }));
 
-                            s.send(record).await;
+                            if let Err(e) = s.try_send(record).await {
+                                if !s.handle_send_err(e).await {
+                                    return;
+                                }
+                            }
 
                             // advance to the next repetition of this row, or the next row if all
                             // repetitions are exhausted
@@ -1391,3 +1535,16 @@ where
 struct ProgressRecord {
     timestamp: Timestamp,
 }
+
+/// This struct is emitted to the user-specified consistency topic of a sink, in the same
+/// transaction as its [`ProgressRecord`]. It records that every message in `topic` whose
+/// `materialize-timestamp` header is at most `timestamp` has been committed, so that consumers
+/// can read several sinked topics as of a single timestamp.
+///
+/// Records are keyed by `topic`, so several sinks can share a consistency topic, and it can be
+/// compacted down to the latest record of each topic.
+#[derive(Serialize)]
+struct ConsistencyRecord<'a> {
+    topic: &'a str,
+    timestamp: Timestamp,
+}
@@ -14,6 +14,8 @@ use mz_ore::{
     metrics::{IntCounterVec, MetricsRegistry, UIntGaugeVec},
 };
 
+use crate::sink::statistics::SinkStatisticsMetricsDefinitions;
+
 /// Metrics reported by each kafka sink.
 #[derive(Clone)]
 pub struct KafkaBaseMetrics {
@@ -54,6 +56,7 @@ impl KafkaBaseMetrics {
 #[derive(Clone)]
 pub struct SinkBaseMetrics {
     pub(crate) kafka: KafkaBaseMetrics,
+    pub(crate) sink_statistics: SinkStatisticsMetricsDefinitions,
 }
 
 impl SinkBaseMetrics {
@@ -61,6 +64,7 @@ impl SinkBaseMetrics {
     pub fn register_with(registry: &MetricsRegistry) -> Self {
         Self {
             kafka: KafkaBaseMetrics::register_with(registry),
+            sink_statistics: SinkStatisticsMetricsDefinitions::register_with(registry),
         }
     }
 }
@@ -9,10 +9,17 @@
 
 //! Moving data to external systems
 
+mod elasticsearch;
 mod healthcheck;
 mod kafka;
 mod metrics;
+mod nats;
+mod object;
+mod postgres;
+mod redis;
+mod statistics;
 
 pub use healthcheck::{Healthchecker, SinkStatus};
 pub(crate) use metrics::KafkaBaseMetrics;
 pub use metrics::SinkBaseMetrics;
+pub use statistics::SinkStatistics;
@@ -0,0 +1,508 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that publishes the updates to a collection to a NATS JetStream
+//! subject.
+//!
+//! Once the input frontier passes a timestamp, a message is published for each
+//! update at that timestamp, and the write frontier advances once the stream
+//! has acknowledged every message. Each message records the timestamp of its
+//! update in a header. A restarted sink reads the last message of the subject
+//! from the stream, skips the updates before its timestamp, and publishes the
+//! updates at its timestamp again, as they may only have been published in
+//! part. With deduplication headers, the ID of a message is derived from the
+//! sink, the timestamp, and the position of the update among the updates at
+//! that timestamp, so the stream discards the messages that it already holds,
+//! as long as they are within its duplicate window.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use async_nats::jetstream;
+use async_nats::HeaderMap;
+use bytes::Bytes;
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{info, warn};
+
+use mz_interchange::csv::CsvEncoder;
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_ore::{halt, task};
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    KafkaSinkCsvFormat, MetadataFilled, NatsSinkConnection, SinkAsOf, SinkEnvelope,
+    SinkErrorPolicy, StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatistics, SinkStatus};
+use crate::storage_state::StorageState;
+
+impl<G> SinkRender<G> for NatsSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // As with Kafka sinks, only the worker to which the updates are
+        // exchanged ever holds back the write frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = publish_messages(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.envelope,
+            sink.as_of.clone(),
+            sink.error_policy,
+            Rc::clone(&shared_frontier),
+            storage_state.sink_statistics[&sink_id].clone(),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// Publishes a stream of updates to a NATS JetStream subject.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// sink `id`. Updates are published in ascending timestamp order once the
+/// input frontier has passed their timestamp, and updates that are not beyond
+/// the given [`SinkAsOf`] or that precede the last message of the subject are
+/// discarded.
+///
+/// A publish that still fails after [`PUBLISH_ATTEMPTS`] attempts is handled
+/// as the given [`SinkErrorPolicy`] asks.
+fn publish_messages<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: NatsSinkConnection,
+    envelope: Option<SinkEnvelope>,
+    as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("nats-{}", id);
+    let scope = collection.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), scope.clone());
+
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => Some(
+                Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker"),
+            ),
+            None => None,
+        };
+        let mut s = NatsSinkState {
+            name,
+            healthchecker,
+            error_policy,
+            statistics,
+        };
+
+        s.update_status(SinkStatus::Starting).await;
+
+        let publisher = NatsPublisher::new(&connection, id, envelope, &connection_context).await;
+        let publisher = s.halt_on_err(publisher).await;
+        let gate_ts = publisher.latest_published_ts().await;
+        let gate_ts = s.halt_on_err(gate_ts).await;
+        info!(
+            "{}: initial as_of: {:?}, latest published timestamp: {:?}",
+            s.name, as_of.frontier, gate_ts
+        );
+
+        s.update_status(SinkStatus::Running).await;
+
+        let mut pending_rows: BTreeMap<Timestamp, Vec<(Option<Row>, Option<Row>, usize)>> =
+            BTreeMap::new();
+        let mut vector = Vec::new();
+        while let Some(event) = input.next().await {
+            match event {
+                Event::Data(_, rows) => {
+                    rows.swap(&mut vector);
+                    for ((key, value), time, diff) in vector.drain(..) {
+                        let should_emit = if as_of.strict {
+                            as_of.frontier.less_than(&time)
+                        } else {
+                            as_of.frontier.less_equal(&time)
+                        };
+                        // The updates at the timestamp of the last message
+                        // may only have been published in part.
+                        let previously_published = Some(time) < gate_ts;
+                        if !should_emit || previously_published || diff == 0 {
+                            continue;
+                        }
+                        let count =
+                            usize::try_from(diff).expect("can't sink negative multiplicities");
+                        pending_rows
+                            .entry(time)
+                            .or_default()
+                            .push((key, value, count));
+                    }
+                }
+                Event::Progress(frontier) => {
+                    let mut messages = vec![];
+                    while let Some(entry) = pending_rows.first_entry() {
+                        if frontier.less_equal(entry.key()) {
+                            break;
+                        }
+                        let (ts, rows) = entry.remove_entry();
+                        messages.extend(publisher.encode(ts, rows));
+                    }
+
+                    if !messages.is_empty() {
+                        let result = Retry::default()
+                            .max_tries(PUBLISH_ATTEMPTS)
+                            .clamp_backoff(BACKOFF_CLAMP)
+                            .retry_async(|_| publisher.publish(messages.clone()))
+                            .await;
+                        if let Err(e) = result {
+                            if !s.handle_publish_err(e, messages.len()).await {
+                                return;
+                            }
+                        }
+                    }
+
+                    // Every update before the frontier has been published, and
+                    // every update at or beyond it is still pending.
+                    *write_frontier.borrow_mut() = frontier;
+                }
+            }
+        }
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+/// The number of times the sink attempts to publish the messages for the
+/// updates at a timestamp before it gives up as its [`SinkErrorPolicy`] asks.
+const PUBLISH_ATTEMPTS: usize = 5;
+
+/// The longest the sink waits between attempts to publish messages.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+/// The header with which JetStream deduplicates messages.
+const MSG_ID_HEADER: &str = "Nats-Msg-Id";
+
+/// The header with which JetStream rejects a message that the expected stream
+/// does not capture.
+const EXPECTED_STREAM_HEADER: &str = "Nats-Expected-Stream";
+
+struct NatsSinkState {
+    name: String,
+    healthchecker: Option<Healthchecker>,
+    error_policy: SinkErrorPolicy,
+    statistics: SinkStatistics,
+}
+
+impl NatsSinkState {
+    async fn update_status(&mut self, status: SinkStatus) {
+        if let Some(hc) = &mut self.healthchecker {
+            hc.update_status(status).await;
+        }
+    }
+
+    /// Report a SinkStatus::Stalled and then halt with the same message.
+    async fn halt_on_err<T>(&mut self, result: Result<T, impl ToString + Debug>) -> T {
+        match result {
+            Ok(t) => t,
+            Err(msg) => {
+                self.update_status(SinkStatus::Stalled(msg.to_string()))
+                    .await;
+                halt!("{}: {msg:?}", self.name)
+            }
+        }
+    }
+
+    /// Handle a failed publish of `count` messages as the sink's error policy
+    /// asks, returning whether the sink should keep publishing.
+    async fn handle_publish_err(&mut self, err: anyhow::Error, count: usize) -> bool {
+        match self.error_policy {
+            SinkErrorPolicy::Retry => self.halt_on_err(Err(err)).await,
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed publish: {err:#}", self.name);
+                self.update_status(SinkStatus::Paused(err.to_string()))
+                    .await;
+                false
+            }
+            SinkErrorPolicy::Drop => {
+                warn!(
+                    "{}: dropping {count} messages after failed publish: {err:#}",
+                    self.name
+                );
+                self.statistics
+                    .inc_updates_dropped_by(u64::cast_from(count));
+                true
+            }
+        }
+    }
+}
+
+/// A message, ready to be published: its headers and its payload.
+type Message = (HeaderMap, Bytes);
+
+/// Encodes updates as messages and publishes them to the subject of a sink.
+///
+/// The client is driven on the Tokio runtime, so every request is issued from
+/// a spawned task.
+struct NatsPublisher {
+    name: String,
+    sink_id: String,
+    context: jetstream::Context,
+    stream: String,
+    subject: String,
+    deduplication_headers: bool,
+    encoder: Box<dyn Encode>,
+    /// Encodes the keys of updates as JSON, whatever the format of payloads.
+    key_encoder: Option<JsonEncoder>,
+}
+
+impl NatsPublisher {
+    async fn new(
+        connection: &NatsSinkConnection,
+        id: GlobalId,
+        envelope: Option<SinkEnvelope>,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        let client = connection
+            .connection
+            .connect(&*connection_context.secrets_reader)
+            .await
+            .context("connecting to NATS")?;
+        let key_desc = connection
+            .key_desc_and_indices
+            .as_ref()
+            .map(|(desc, _indices)| desc.clone());
+        let value_desc = connection.value_desc.clone();
+        let encoder: Box<dyn Encode> = match &connection.csv_format {
+            Some(KafkaSinkCsvFormat {
+                delimiter,
+                quote,
+                null,
+                header: _,
+            }) => Box::new(CsvEncoder::new(
+                key_desc.clone(),
+                value_desc.clone(),
+                *delimiter,
+                *quote,
+                null.clone(),
+            )),
+            None => Box::new(JsonEncoder::new(
+                key_desc.clone(),
+                value_desc.clone(),
+                matches!(envelope, Some(SinkEnvelope::Debezium)),
+            )),
+        };
+        let key_encoder =
+            key_desc.map(|key_desc| JsonEncoder::new(Some(key_desc), value_desc, false));
+        Ok(NatsPublisher {
+            name: format!("nats-{}", id),
+            sink_id: id.to_string(),
+            context: jetstream::new(client),
+            stream: connection.stream.clone(),
+            subject: connection.subject.clone(),
+            deduplication_headers: connection.deduplication_headers,
+            encoder,
+            key_encoder,
+        })
+    }
+
+    /// Returns the timestamp of the last message of the subject, if the sink
+    /// has ever published a message to it.
+    async fn latest_published_ts(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        let context = self.context.clone();
+        let stream = self.stream.clone();
+        let subject = self.subject.clone();
+        let message = task::spawn(|| self.name.clone(), async move {
+            let stream = context
+                .get_stream(&stream)
+                .await
+                .map_err(|e| anyhow!("looking up NATS stream {}: {}", stream, e))?;
+            match stream.get_last_raw_message_by_subject(&subject).await {
+                Ok(message) => Ok(Some(message)),
+                // JetStream reports a subject without messages as an error.
+                Err(e) if e.to_string().contains("no message found") => Ok(None),
+                Err(e) => Err(anyhow!(
+                    "reading the last message of NATS subject {}: {}",
+                    subject,
+                    e
+                )),
+            }
+        })
+        .await??;
+
+        let headers = match message.and_then(|message| message.headers) {
+            Some(headers) => base64::decode(headers).context("decoding NATS message headers")?,
+            None => return Ok(None),
+        };
+        match header_value(&headers, NatsSinkConnection::TIMESTAMP_HEADER) {
+            Some(ts) => {
+                let ts = ts
+                    .parse::<u64>()
+                    .with_context(|| format!("parsing NATS message timestamp {}", ts))?;
+                Ok(Some(Timestamp::from(ts)))
+            }
+            // The last message was not published by a sink.
+            None => Ok(None),
+        }
+    }
+
+    /// Encodes the messages for the updates at `ts`, each of which is a key,
+    /// a value, and the value's multiplicity.
+    ///
+    /// The updates are sorted first, so that the ID of each message is the
+    /// same whenever the updates at `ts` are published.
+    fn encode(
+        &self,
+        ts: Timestamp,
+        mut rows: Vec<(Option<Row>, Option<Row>, usize)>,
+    ) -> Vec<Message> {
+        rows.sort();
+        let ts = u64::from(ts).to_string();
+        let mut messages = vec![];
+        for (key, value, count) in rows {
+            let key = match (&self.key_encoder, key) {
+                (Some(encoder), Some(key)) => Some(encoder.encode_key_unchecked(key)),
+                _ => None,
+            };
+            // An upsert without a value deletes its key, and is published as
+            // a message with an empty payload.
+            let payload = match value {
+                Some(value) => Bytes::from(self.encoder.encode_value_unchecked(value)),
+                None => Bytes::new(),
+            };
+            for _ in 0..count {
+                let mut headers = HeaderMap::new();
+                headers.insert(EXPECTED_STREAM_HEADER, self.stream.as_str());
+                headers.insert(NatsSinkConnection::TIMESTAMP_HEADER, ts.as_str());
+                if let Some(key) = &key {
+                    headers.insert(
+                        NatsSinkConnection::KEY_HEADER,
+                        String::from_utf8_lossy(key).as_ref(),
+                    );
+                }
+                if self.deduplication_headers {
+                    let msg_id = format!("{}/{}/{}", self.sink_id, ts, messages.len());
+                    headers.insert(MSG_ID_HEADER, msg_id.as_str());
+                }
+                messages.push((headers, payload.clone()));
+            }
+        }
+        messages
+    }
+
+    /// Publishes `messages` in order, and waits for the stream to acknowledge
+    /// all of them.
+    async fn publish(&self, messages: Vec<Message>) -> Result<(), anyhow::Error> {
+        let context = self.context.clone();
+        let subject = self.subject.clone();
+        task::spawn(|| self.name.clone(), async move {
+            let mut acks = Vec::with_capacity(messages.len());
+            for (headers, payload) in messages {
+                let ack = context
+                    .publish_with_headers(subject.clone(), headers, payload)
+                    .await
+                    .map_err(|e| anyhow!("publishing to NATS subject {}: {}", subject, e))?;
+                acks.push(ack);
+            }
+            for ack in acks {
+                ack.await
+                    .map_err(|e| anyhow!("publishing to NATS subject {}: {}", subject, e))?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?
+    }
+}
+
+/// Returns the value of the header `name` in the encoded `headers` of a
+/// message, which follow the format of HTTP headers.
+fn header_value(headers: &[u8], name: &str) -> Option<String> {
+    String::from_utf8_lossy(headers)
+        .split("\r\n")
+        // The first line holds the version of the format.
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(key, _value)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_key, value)| value.trim().to_string())
+}
@@ -0,0 +1,631 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes the updates to a collection to a sequence of files in a
+//! directory or an object store.
+//!
+//! Updates are appended to an in-progress file once their timestamp is
+//! complete. When the file grows past the sink's maximum file size, or has
+//! been open for longer than its rotation interval, it is completed: a file in
+//! a directory is renamed to its final name, and a file destined for an object
+//! store (S3, Google Cloud Storage or Azure Blob Storage) is uploaded. The
+//! name of a completed file records the range of timestamps that it covers,
+//! which is how a restarted sink determines the updates that it has already
+//! written.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{info, warn};
+
+use mz_interchange::csv::CsvEncoder;
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_ore::cast::CastFrom;
+use mz_ore::{halt, task};
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    KafkaSinkCsvFormat, MetadataFilled, ObjectSinkConnection, ObjectSinkFormat,
+    ObjectStoreConnection, SinkAsOf, SinkErrorPolicy, StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use self::store::RemoteStore;
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatus};
+use crate::storage_state::StorageState;
+
+mod store;
+
+impl<G> SinkRender<G> for ObjectSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // As with Kafka sinks, only the worker to which the updates are
+        // exchanged ever holds back the write frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = write_objects(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            sink.error_policy,
+            Rc::clone(&shared_frontier),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// Writes a stream of Debezium-formatted updates to files.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// sink `id`. Updates are written in ascending timestamp order once the input
+/// frontier has passed their timestamp, and updates that are not beyond the
+/// given [`SinkAsOf`] or that are covered by an already completed file are
+/// discarded.
+///
+/// A failed write leaves the in-progress file in an unknown state, so the sink
+/// never drops updates: it either pauses or halts, as the given
+/// [`SinkErrorPolicy`] asks.
+fn write_objects<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: ObjectSinkConnection,
+    as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("{}-{}", connection.store.name(), id);
+    let scope = collection.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), scope.clone());
+
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => Some(
+                Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker"),
+            ),
+            None => None,
+        };
+        let mut s = ObjectSinkState {
+            name,
+            healthchecker,
+            error_policy,
+        };
+
+        s.update_status(SinkStatus::Starting).await;
+
+        let store = ObjectStore::new(&connection.store, id, &connection_context).await;
+        let store = s.halt_on_err(store).await;
+        let mut writer = ObjectWriter::new(&connection, id, store);
+        // Unlike a Kafka sink, an object sink records no progress while it has
+        // nothing to write, so its as_of may lie beyond the latest completed
+        // file.
+        let gate_ts = writer.latest_completed_ts().await;
+        let gate_ts = s.halt_on_err(gate_ts).await;
+        info!(
+            "{}: initial as_of: {:?}, latest completed file: {:?}",
+            s.name, as_of.frontier, gate_ts
+        );
+
+        s.update_status(SinkStatus::Running).await;
+
+        let mut pending_rows: BTreeMap<Timestamp, Vec<(Row, usize)>> = BTreeMap::new();
+        let mut vector = Vec::new();
+        while let Some(event) = input.next().await {
+            match event {
+                Event::Data(_, rows) => {
+                    rows.swap(&mut vector);
+                    for ((_key, value), time, diff) in vector.drain(..) {
+                        let should_emit = if as_of.strict {
+                            as_of.frontier.less_than(&time)
+                        } else {
+                            as_of.frontier.less_equal(&time)
+                        };
+                        let previously_written = Some(time) <= gate_ts;
+                        if !should_emit || previously_written || diff == 0 {
+                            continue;
+                        }
+                        let count =
+                            usize::try_from(diff).expect("can't sink negative multiplicities");
+                        let value = value.expect("debezium envelope always produces a value");
+                        pending_rows.entry(time).or_default().push((value, count));
+                    }
+                }
+                Event::Progress(frontier) => {
+                    while let Some(entry) = pending_rows.first_entry() {
+                        if frontier.less_equal(entry.key()) {
+                            break;
+                        }
+                        let (ts, rows) = entry.remove_entry();
+                        if let Err(e) = writer.write(ts, rows) {
+                            s.pause_or_halt(e).await;
+                            return;
+                        }
+                    }
+
+                    if writer.should_rotate() {
+                        if let Err(e) = writer.complete().await {
+                            s.pause_or_halt(e).await;
+                            return;
+                        }
+                    }
+
+                    // Updates in the in-progress file are not durable until
+                    // the file is completed, so the write frontier may not pass
+                    // the first timestamp that it holds.
+                    let held_ts = writer.first_ts().or(pending_rows.keys().next().copied());
+                    *write_frontier.borrow_mut() = match held_ts {
+                        Some(ts) if !frontier.less_equal(&ts) => Antichain::from_elem(ts),
+                        _ => frontier,
+                    };
+                }
+            }
+        }
+
+        // The input is exhausted, so the in-progress file holds the last
+        // updates the sink will ever see.
+        if let Err(e) = writer.complete().await {
+            s.pause_or_halt(e).await;
+            return;
+        }
+        *write_frontier.borrow_mut() = Antichain::new();
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+struct ObjectSinkState {
+    name: String,
+    healthchecker: Option<Healthchecker>,
+    error_policy: SinkErrorPolicy,
+}
+
+impl ObjectSinkState {
+    async fn update_status(&mut self, status: SinkStatus) {
+        if let Some(hc) = &mut self.healthchecker {
+            hc.update_status(status).await;
+        }
+    }
+
+    /// Report a SinkStatus::Stalled and then halt with the same message.
+    async fn halt_on_err<T>(&mut self, result: Result<T, impl ToString + Debug>) -> T {
+        match result {
+            Ok(t) => t,
+            Err(msg) => {
+                self.update_status(SinkStatus::Stalled(msg.to_string()))
+                    .await;
+                halt!("{}: {msg:?}", self.name)
+            }
+        }
+    }
+
+    /// Report a SinkStatus::Paused if the sink's error policy asks to pause
+    /// on a failed write, and otherwise halt as [`Self::halt_on_err`] does.
+    /// The caller must stop writing once this returns.
+    async fn pause_or_halt(&mut self, err: anyhow::Error) {
+        match self.error_policy {
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed write: {err:#}", self.name);
+                self.update_status(SinkStatus::Paused(err.to_string()))
+                    .await;
+            }
+            // Dropping the updates of a failed write is rejected when the
+            // sink is planned.
+            SinkErrorPolicy::Retry | SinkErrorPolicy::Drop => self.halt_on_err(Err(err)).await,
+        }
+    }
+}
+
+/// Encodes updates and appends them to the in-progress file of a sink.
+struct ObjectWriter {
+    store: ObjectStore,
+    /// The prefix of the names of the sink's files.
+    name_prefix: String,
+    extension: &'static str,
+    encoder: RecordEncoder,
+    /// The record with which each file begins, if any.
+    header: Option<Vec<u8>>,
+    max_file_size: u64,
+    rotation_interval: Duration,
+    file: Option<InProgressFile>,
+}
+
+struct InProgressFile {
+    writer: BufWriter<File>,
+    first_ts: Timestamp,
+    last_ts: Timestamp,
+    size: u64,
+    opened_at: Instant,
+}
+
+impl ObjectWriter {
+    fn new(connection: &ObjectSinkConnection, id: GlobalId, store: ObjectStore) -> Self {
+        let (encoder, header, extension) = match &connection.format {
+            ObjectSinkFormat::Json => (
+                RecordEncoder::Json(JsonEncoder::new(None, connection.value_desc.clone(), true)),
+                None,
+                "json",
+            ),
+            ObjectSinkFormat::Csv(KafkaSinkCsvFormat {
+                delimiter,
+                quote,
+                null,
+                header,
+            }) => {
+                let desc = RelationDesc::empty()
+                    .with_column("mz_diff", ScalarType::Int64.nullable(false))
+                    .concat(connection.value_desc.clone());
+                let encoder = CsvEncoder::new(None, desc, *delimiter, *quote, null.clone());
+                let header = header.as_ref().map(|names| {
+                    let names: Vec<_> = std::iter::once("mz_diff".to_string())
+                        .chain(names.iter().cloned())
+                        .collect();
+                    encoder.encode_header(&names)
+                });
+                (
+                    RecordEncoder::Csv {
+                        encoder,
+                        row_buf: Row::default(),
+                    },
+                    header,
+                    "csv",
+                )
+            }
+        };
+        ObjectWriter {
+            store,
+            name_prefix: format!("{}-", id),
+            extension,
+            encoder,
+            header,
+            max_file_size: connection.max_file_size,
+            rotation_interval: connection.rotation_interval,
+            file: None,
+        }
+    }
+
+    /// Returns the last timestamp covered by a completed file, if any.
+    async fn latest_completed_ts(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        let names = self.store.list(&self.name_prefix).await?;
+        let latest = names
+            .iter()
+            .filter_map(|name| {
+                let range = name
+                    .strip_prefix(&self.name_prefix)?
+                    .strip_suffix(self.extension)?
+                    .strip_suffix('.')?;
+                let (_first, last) = range.split_once('-')?;
+                last.parse::<u64>().ok()
+            })
+            .max();
+        Ok(latest.map(Timestamp::from))
+    }
+
+    /// Appends the updates at `ts` to the in-progress file, opening a new one
+    /// if necessary.
+    fn write(&mut self, ts: Timestamp, rows: Vec<(Row, usize)>) -> Result<(), anyhow::Error> {
+        if self.file.is_none() {
+            let path = self.store.staging_path(&self.staging_name(ts));
+            let file =
+                File::create(&path).with_context(|| format!("creating {}", path.display()))?;
+            let mut file = InProgressFile {
+                writer: BufWriter::new(file),
+                first_ts: ts,
+                last_ts: ts,
+                size: 0,
+                opened_at: Instant::now(),
+            };
+            if let Some(header) = &self.header {
+                file.writer.write_all(header)?;
+                file.writer.write_all(b"\n")?;
+                file.size += u64::cast_from(header.len()) + 1;
+            }
+            self.file = Some(file);
+        }
+        let file = self.file.as_mut().expect("file opened above");
+        for (row, count) in rows {
+            for record in self.encoder.encode(row) {
+                for _ in 0..count {
+                    file.writer.write_all(&record)?;
+                    file.writer.write_all(b"\n")?;
+                    file.size += u64::cast_from(record.len()) + 1;
+                }
+            }
+        }
+        file.last_ts = ts;
+        Ok(())
+    }
+
+    /// Reports whether the in-progress file should be completed.
+    fn should_rotate(&self) -> bool {
+        match &self.file {
+            Some(file) => {
+                file.size >= self.max_file_size
+                    || file.opened_at.elapsed() >= self.rotation_interval
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the first timestamp in the in-progress file, if any.
+    fn first_ts(&self) -> Option<Timestamp> {
+        self.file.as_ref().map(|file| file.first_ts)
+    }
+
+    /// Completes the in-progress file, if any.
+    async fn complete(&mut self) -> Result<(), anyhow::Error> {
+        let InProgressFile {
+            writer,
+            first_ts,
+            last_ts,
+            ..
+        } = match self.file.take() {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+
+        let name = format!(
+            "{}{:020}-{:020}.{}",
+            self.name_prefix,
+            u64::from(first_ts),
+            u64::from(last_ts),
+            self.extension
+        );
+        info!("completing file {}", name);
+        self.store.complete(self.staging_name(first_ts), name).await
+    }
+
+    fn staging_name(&self, first_ts: Timestamp) -> String {
+        format!(
+            ".{}{:020}.{}.tmp",
+            self.name_prefix,
+            u64::from(first_ts),
+            self.extension
+        )
+    }
+}
+
+/// Encodes the Debezium-formatted updates of a sink as the records of a file.
+enum RecordEncoder {
+    Json(JsonEncoder),
+    /// CSV records cannot nest the `before` and `after` states of an update,
+    /// so an update is instead encoded as a record that retracts the old state
+    /// of the row and one that inserts its new state, each prefixed with the
+    /// change in multiplicity.
+    Csv {
+        encoder: CsvEncoder,
+        row_buf: Row,
+    },
+}
+
+impl RecordEncoder {
+    fn encode(&mut self, row: Row) -> Vec<Vec<u8>> {
+        match self {
+            RecordEncoder::Json(encoder) => vec![encoder.encode_value_unchecked(row)],
+            RecordEncoder::Csv { encoder, row_buf } => {
+                let mut records = vec![];
+                for (datum, diff) in row.iter().zip([-1, 1]) {
+                    if let Datum::List(state) = datum {
+                        let mut packer = row_buf.packer();
+                        packer.push(Datum::Int64(diff));
+                        packer.extend(state.iter());
+                        records.push(encoder.encode_value_unchecked(row_buf.clone()));
+                    }
+                }
+                records
+            }
+        }
+    }
+}
+
+/// The place to which the completed files of a sink are published.
+enum ObjectStore {
+    /// Files are staged in the directory under a hidden name, and completed by
+    /// renaming them.
+    Directory(PathBuf),
+    /// Files are staged in a local temporary directory, and completed by
+    /// uploading them to the remote store.
+    Remote {
+        store: Arc<dyn RemoteStore>,
+        staging_dir: PathBuf,
+    },
+}
+
+impl ObjectStore {
+    /// Connects to the store, discarding any files that a previous
+    /// incarnation of the sink staged but did not complete.
+    async fn new(
+        connection: &ObjectStoreConnection,
+        id: GlobalId,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        match connection {
+            ObjectStoreConnection::Directory { path } => {
+                let dir = PathBuf::from(path);
+                fs::create_dir_all(&dir)
+                    .with_context(|| format!("creating directory {}", dir.display()))?;
+                let staging_prefix = format!(".{}-", id);
+                for entry in fs::read_dir(&dir)? {
+                    let entry = entry?;
+                    let is_staged = entry
+                        .file_name()
+                        .to_str()
+                        .map_or(false, |name| name.starts_with(&staging_prefix));
+                    if is_staged {
+                        fs::remove_file(entry.path())?;
+                    }
+                }
+                Ok(ObjectStore::Directory(dir))
+            }
+            ObjectStoreConnection::S3 { .. }
+            | ObjectStoreConnection::Gcs { .. }
+            | ObjectStoreConnection::AzureBlob { .. } => {
+                let staging_dir = std::env::temp_dir().join(format!("mz-sink-{}", id));
+                if staging_dir.exists() {
+                    fs::remove_dir_all(&staging_dir)?;
+                }
+                fs::create_dir_all(&staging_dir)?;
+                let store = run_on_runtime(
+                    format!("object-sink-connect:{}", id),
+                    store::connect(connection.clone(), id, connection_context.clone()),
+                )
+                .await?;
+                Ok(ObjectStore::Remote { store, staging_dir })
+            }
+        }
+    }
+
+    /// Returns the names of the completed files that start with `name_prefix`.
+    async fn list(&self, name_prefix: &str) -> Result<Vec<String>, anyhow::Error> {
+        match self {
+            ObjectStore::Directory(dir) => {
+                let mut names = vec![];
+                for entry in fs::read_dir(dir)? {
+                    if let Some(name) = entry?.file_name().to_str() {
+                        if name.starts_with(name_prefix) {
+                            names.push(name.to_string());
+                        }
+                    }
+                }
+                Ok(names)
+            }
+            ObjectStore::Remote { store, .. } => {
+                let store = Arc::clone(store);
+                let name_prefix = name_prefix.to_string();
+                run_on_runtime(format!("object-sink-list:{}", name_prefix), async move {
+                    store.list(&name_prefix).await
+                })
+                .await
+            }
+        }
+    }
+
+    /// Returns the path at which the file with the given name is staged.
+    fn staging_path(&self, staging_name: &str) -> PathBuf {
+        match self {
+            ObjectStore::Directory(dir) => dir.join(staging_name),
+            ObjectStore::Remote { staging_dir, .. } => staging_dir.join(staging_name),
+        }
+    }
+
+    /// Publishes a staged file under its final name.
+    async fn complete(&self, staging_name: String, name: String) -> Result<(), anyhow::Error> {
+        let staging_path = self.staging_path(&staging_name);
+        match self {
+            ObjectStore::Directory(dir) => {
+                fs::rename(&staging_path, dir.join(&name))
+                    .with_context(|| format!("renaming {}", staging_path.display()))?;
+                // Make the rename itself durable.
+                File::open(dir)?.sync_all()?;
+                Ok(())
+            }
+            ObjectStore::Remote { store, .. } => {
+                let contents = fs::read(&staging_path)?;
+                let store = Arc::clone(store);
+                run_on_runtime(format!("object-sink-upload:{}", name), async move {
+                    store.put(&name, contents).await
+                })
+                .await?;
+                fs::remove_file(&staging_path)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Runs a future to completion on the Tokio runtime, on which the AWS SDK
+/// and the HTTP clients of the other object stores expect to be driven.
+async fn run_on_runtime<T, F>(name: String, future: F) -> Result<T, anyhow::Error>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, anyhow::Error>> + Send + 'static,
+{
+    task::spawn(|| name, future).await?
+}
@@ -0,0 +1,316 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The remote object stores to which object sinks upload their files.
+
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use aws_sdk_s3::types::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use tokio::sync::Mutex;
+
+use mz_repr::GlobalId;
+use mz_storage_client::types::connections::azure::AzureBlobClient;
+use mz_storage_client::types::connections::gcp::GcsClient;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::sinks::ObjectStoreConnection;
+
+/// A bucket or container of a remote object store, restricted to the objects
+/// whose names start with a sink's prefix.
+///
+/// Names passed to and returned by a store are relative to that prefix.
+#[async_trait]
+pub(super) trait RemoteStore: Send + Sync {
+    /// Returns the names of the objects that start with `name_prefix`.
+    async fn list(&self, name_prefix: &str) -> Result<Vec<String>, anyhow::Error>;
+
+    /// Uploads `contents` as the object `name`, replacing any object of that
+    /// name.
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<(), anyhow::Error>;
+}
+
+/// Connects to the remote store that `connection` describes.
+pub(super) async fn connect(
+    connection: ObjectStoreConnection,
+    id: GlobalId,
+    connection_context: ConnectionContext,
+) -> Result<Arc<dyn RemoteStore>, anyhow::Error> {
+    let secrets_reader = &*connection_context.secrets_reader;
+    let store: Arc<dyn RemoteStore> = match connection {
+        ObjectStoreConnection::Directory { .. } => bail!("a directory is not a remote store"),
+        ObjectStoreConnection::S3 {
+            connection,
+            bucket,
+            prefix,
+            ..
+        } => {
+            let config = connection
+                .load(
+                    connection_context.aws_external_id_prefix.as_ref(),
+                    Some(&id),
+                    secrets_reader,
+                )
+                .await;
+            Arc::new(S3Store {
+                client: S3Client::new(&config),
+                bucket,
+                prefix,
+            })
+        }
+        ObjectStoreConnection::Gcs {
+            connection,
+            bucket,
+            prefix,
+            ..
+        } => Arc::new(GcsStore {
+            client: Mutex::new(connection.gcs_client(secrets_reader).await?),
+            bucket,
+            prefix,
+        }),
+        ObjectStoreConnection::AzureBlob {
+            connection,
+            container,
+            prefix,
+            ..
+        } => Arc::new(AzureBlobStore {
+            client: connection.blob_client(secrets_reader).await?,
+            container,
+            prefix,
+        }),
+    };
+    Ok(store)
+}
+
+struct S3Store {
+    client: S3Client,
+    bucket: String,
+    prefix: String,
+}
+
+#[async_trait]
+impl RemoteStore for S3Store {
+    async fn list(&self, name_prefix: &str) -> Result<Vec<String>, anyhow::Error> {
+        let key_prefix = with_prefix(&self.prefix, name_prefix);
+        let mut names = vec![];
+        let mut continuation_token = None;
+        loop {
+            let response = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&key_prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .with_context(|| format!("listing objects in bucket {}", self.bucket))?;
+            for object in response.contents.unwrap_or_default() {
+                if let Some(name) = object
+                    .key
+                    .as_deref()
+                    .and_then(|key| key.strip_prefix(&self.prefix))
+                {
+                    names.push(name.to_string());
+                }
+            }
+            continuation_token = response.next_continuation_token;
+            if continuation_token.is_none() {
+                return Ok(names);
+            }
+        }
+    }
+
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<(), anyhow::Error> {
+        let key = with_prefix(&self.prefix, name);
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .body(ByteStream::from(contents))
+            .send()
+            .await
+            .with_context(|| format!("uploading {} to bucket {}", key, self.bucket))?;
+        Ok(())
+    }
+}
+
+struct GcsStore {
+    /// The client caches its access token, so it requires exclusive access.
+    client: Mutex<GcsClient>,
+    bucket: String,
+    prefix: String,
+}
+
+#[async_trait]
+impl RemoteStore for GcsStore {
+    async fn list(&self, name_prefix: &str) -> Result<Vec<String>, anyhow::Error> {
+        let object_prefix = with_prefix(&self.prefix, name_prefix);
+        let names = self
+            .client
+            .lock()
+            .await
+            .list_objects(&self.bucket, &object_prefix)
+            .await
+            .with_context(|| format!("listing objects in bucket {}", self.bucket))?;
+        Ok(strip_prefixes(names, &self.prefix))
+    }
+
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<(), anyhow::Error> {
+        let object = with_prefix(&self.prefix, name);
+        self.client
+            .lock()
+            .await
+            .upload_object(&self.bucket, &object, contents)
+            .await
+            .with_context(|| format!("uploading {} to bucket {}", object, self.bucket))
+    }
+}
+
+struct AzureBlobStore {
+    client: AzureBlobClient,
+    container: String,
+    prefix: String,
+}
+
+#[async_trait]
+impl RemoteStore for AzureBlobStore {
+    async fn list(&self, name_prefix: &str) -> Result<Vec<String>, anyhow::Error> {
+        let blob_prefix = with_prefix(&self.prefix, name_prefix);
+        let names = self
+            .client
+            .list_blobs(&self.container, &blob_prefix)
+            .await
+            .with_context(|| format!("listing blobs in container {}", self.container))?;
+        Ok(strip_prefixes(names, &self.prefix))
+    }
+
+    async fn put(&self, name: &str, contents: Vec<u8>) -> Result<(), anyhow::Error> {
+        let blob = with_prefix(&self.prefix, name);
+        self.client
+            .put_blob(&self.container, &blob, contents)
+            .await
+            .with_context(|| format!("uploading {} to container {}", blob, self.container))
+    }
+}
+
+/// Returns the full name of the object `name` under `prefix`.
+fn with_prefix(prefix: &str, name: &str) -> String {
+    format!("{}{}", prefix, name)
+}
+
+/// Returns the names that start with `prefix`, with the prefix removed.
+fn strip_prefixes(names: Vec<String>, prefix: &str) -> Vec<String> {
+    names
+        .iter()
+        .filter_map(|name| name.strip_prefix(prefix))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_secrets::{InMemorySecretsController, SecretsController};
+    use mz_storage_client::types::connections::azure::{AzureBlobConnection, AzureBlobCredentials};
+    use mz_storage_client::types::connections::gcp::GcpConnection;
+
+    use super::*;
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(with_prefix("out/", "u1-1.csv"), "out/u1-1.csv");
+        assert_eq!(with_prefix("", "u1-1.csv"), "u1-1.csv");
+
+        // Only the names under the prefix belong to the sink, and they keep
+        // any further path components.
+        let names = vec![
+            "out/u1-1.csv".to_string(),
+            "outer/u1-2.csv".to_string(),
+            "other/out/u1-3.csv".to_string(),
+            "out/nested/u1-4.csv".to_string(),
+        ];
+        assert_eq!(
+            strip_prefixes(names, "out/"),
+            vec!["u1-1.csv".to_string(), "nested/u1-4.csv".to_string()]
+        );
+
+        let names = vec!["u1-1.csv".to_string()];
+        assert_eq!(strip_prefixes(names.clone(), ""), names);
+    }
+
+    #[tokio::test]
+    async fn test_connect() {
+        let id = GlobalId::User(1);
+        let secrets = InMemorySecretsController::new();
+        secrets
+            .ensure(GlobalId::User(2), b"sv=2021-08-06&sig=x")
+            .await
+            .unwrap();
+        secrets
+            .ensure(GlobalId::User(3), b"not base64!")
+            .await
+            .unwrap();
+        let context = ConnectionContext::for_tests(secrets.reader());
+
+        let err = connect(
+            ObjectStoreConnection::Directory {
+                path: "/tmp".into(),
+            },
+            id,
+            context.clone(),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(err.to_string(), "a directory is not a remote store");
+
+        // Neither store contacts its service until it is used.
+        let azure = |credentials| ObjectStoreConnection::AzureBlob {
+            connection_id: GlobalId::User(4),
+            connection: AzureBlobConnection {
+                account: "acct".into(),
+                credentials,
+                endpoint: None,
+            },
+            container: "lake".into(),
+            prefix: "out/".into(),
+        };
+        connect(
+            azure(AzureBlobCredentials::SasToken(GlobalId::User(2))),
+            id,
+            context.clone(),
+        )
+        .await
+        .unwrap();
+        let gcs = ObjectStoreConnection::Gcs {
+            connection_id: GlobalId::User(4),
+            connection: GcpConnection {
+                project: "proj".into(),
+                credentials: None,
+                endpoint: None,
+            },
+            bucket: "lake".into(),
+            prefix: "out/".into(),
+        };
+        connect(gcs, id, context.clone()).await.unwrap();
+
+        let err = connect(
+            azure(AzureBlobCredentials::AccountKey(GlobalId::User(3))),
+            id,
+            context,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "decoding Azure storage account key from ACCOUNT KEY"
+        );
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that maintains a table in a PostgreSQL database.
+//!
+//! Once the input frontier passes a timestamp, the updates at that timestamp
+//! are applied to the table as upserts and deletes of the rows with the
+//! updated keys. The updates at all of the timestamps that a frontier closes
+//! are applied in a single transaction, which also records the last of those
+//! timestamps in the sink's progress table. A restarted sink skips the updates
+//! at timestamps that its progress table says it has already applied, so the
+//! table always reflects the sinked collection as of some timestamp.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use bytes::BytesMut;
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tokio_postgres::types::{ToSql, Type};
+use tokio_postgres::{Client, Statement};
+use tracing::{info, warn};
+
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_ore::{halt, task};
+use mz_postgres_util::quote_identifier;
+use mz_repr::{Diff, GlobalId, RelationType, Row, Timestamp};
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    postgres_sink_column_type, MetadataFilled, PostgresSinkConnection, SinkAsOf, SinkErrorPolicy,
+    StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatistics, SinkStatus};
+use crate::storage_state::StorageState;
+
+impl<G> SinkRender<G> for PostgresSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // As with Kafka sinks, only the worker to which the updates are
+        // exchanged ever holds back the write frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = write_table(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            sink.error_policy,
+            Rc::clone(&shared_frontier),
+            storage_state.sink_statistics[&sink_id].clone(),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// Applies a stream of upsert-formatted updates to a PostgreSQL table.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// sink `id`. Updates are applied in ascending timestamp order once the input
+/// frontier has passed their timestamp, and updates that are not beyond the
+/// given [`SinkAsOf`] or that the table already reflects are discarded.
+///
+/// A write that still fails after [`WRITE_ATTEMPTS`] attempts is handled as
+/// the given [`SinkErrorPolicy`] asks.
+fn write_table<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: PostgresSinkConnection,
+    as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("postgres-{}", id);
+    let scope = collection.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), scope.clone());
+
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => Some(
+                Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker"),
+            ),
+            None => None,
+        };
+        let mut s = PostgresSinkState {
+            name,
+            healthchecker,
+            error_policy,
+            statistics,
+        };
+
+        s.update_status(SinkStatus::Starting).await;
+
+        let writer = PostgresWriter::new(&connection, id, &connection_context).await;
+        let writer = s.halt_on_err(writer).await;
+        let gate_ts = writer.latest_applied_ts().await;
+        let gate_ts = s.halt_on_err(gate_ts).await;
+        info!(
+            "{}: initial as_of: {:?}, latest applied timestamp: {:?}",
+            s.name, as_of.frontier, gate_ts
+        );
+
+        s.update_status(SinkStatus::Running).await;
+
+        let mut pending_rows: BTreeMap<Timestamp, Vec<(Row, Option<Row>)>> = BTreeMap::new();
+        let mut vector = Vec::new();
+        while let Some(event) = input.next().await {
+            match event {
+                Event::Data(_, rows) => {
+                    rows.swap(&mut vector);
+                    for ((key, value), time, diff) in vector.drain(..) {
+                        let should_emit = if as_of.strict {
+                            as_of.frontier.less_than(&time)
+                        } else {
+                            as_of.frontier.less_equal(&time)
+                        };
+                        let previously_applied = Some(time) <= gate_ts;
+                        if !should_emit || previously_applied || diff == 0 {
+                            continue;
+                        }
+                        let key = key.expect("postgres sinks always have a key");
+                        pending_rows.entry(time).or_default().push((key, value));
+                    }
+                }
+                Event::Progress(frontier) => {
+                    let mut updates = vec![];
+                    let mut last_ts = None;
+                    while let Some(entry) = pending_rows.first_entry() {
+                        if frontier.less_equal(entry.key()) {
+                            break;
+                        }
+                        let (ts, rows) = entry.remove_entry();
+                        updates.extend(rows);
+                        last_ts = Some(ts);
+                    }
+
+                    if let Some(ts) = last_ts {
+                        let result = Retry::default()
+                            .max_tries(WRITE_ATTEMPTS)
+                            .clamp_backoff(BACKOFF_CLAMP)
+                            .retry_async(|_| writer.apply(&updates, ts))
+                            .await;
+                        if let Err(e) = result {
+                            if !s.handle_write_err(e, updates.len()).await {
+                                return;
+                            }
+                        }
+                    }
+
+                    // Every update before the frontier has been applied, and
+                    // every update at or beyond it is still pending.
+                    *write_frontier.borrow_mut() = frontier;
+                }
+            }
+        }
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+/// The number of times the sink attempts to apply the updates at a timestamp
+/// before it gives up as its [`SinkErrorPolicy`] asks.
+const WRITE_ATTEMPTS: usize = 5;
+
+/// The longest the sink waits between attempts to apply updates.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+struct PostgresSinkState {
+    name: String,
+    healthchecker: Option<Healthchecker>,
+    error_policy: SinkErrorPolicy,
+    statistics: SinkStatistics,
+}
+
+impl PostgresSinkState {
+    async fn update_status(&mut self, status: SinkStatus) {
+        if let Some(hc) = &mut self.healthchecker {
+            hc.update_status(status).await;
+        }
+    }
+
+    /// Report a SinkStatus::Stalled and then halt with the same message.
+    async fn halt_on_err<T>(&mut self, result: Result<T, impl ToString + Debug>) -> T {
+        match result {
+            Ok(t) => t,
+            Err(msg) => {
+                self.update_status(SinkStatus::Stalled(msg.to_string()))
+                    .await;
+                halt!("{}: {msg:?}", self.name)
+            }
+        }
+    }
+
+    /// Handle a failed write of `count` updates as the sink's error policy
+    /// asks, returning whether the sink should keep writing.
+    async fn handle_write_err(&mut self, err: anyhow::Error, count: usize) -> bool {
+        match self.error_policy {
+            SinkErrorPolicy::Retry => self.halt_on_err(Err(err)).await,
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed write: {err:#}", self.name);
+                self.update_status(SinkStatus::Paused(err.to_string()))
+                    .await;
+                false
+            }
+            SinkErrorPolicy::Drop => {
+                warn!(
+                    "{}: dropping {count} updates after failed write: {err:#}",
+                    self.name
+                );
+                self.statistics
+                    .inc_updates_dropped_by(u64::cast_from(count));
+                true
+            }
+        }
+    }
+}
+
+/// Applies updates to the table of a sink, and records its progress.
+///
+/// The client is driven on the Tokio runtime, so every query is issued from a
+/// spawned task.
+struct PostgresWriter {
+    name: String,
+    sink_id: String,
+    client: Arc<Client>,
+    key_type: RelationType,
+    value_type: RelationType,
+    /// Inserts a row, or replaces the row with the same key.
+    upsert: Statement,
+    /// Deletes the row with a given key.
+    delete: Statement,
+    /// Reads the latest timestamp that the table reflects.
+    read_progress: Statement,
+    /// Records the latest timestamp that the table reflects.
+    record_progress: Statement,
+}
+
+impl PostgresWriter {
+    async fn new(
+        connection: &PostgresSinkConnection,
+        id: GlobalId,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        let (key_desc, key_indices) = &connection.key_desc_and_indices;
+        let table = connection.qualified_table();
+        let progress_table = connection.qualified_progress_table();
+
+        // Parameters are sent as text and cast to the type of their column, so
+        // that every value is sent in the encoding that PostgreSQL parses.
+        let mut columns = vec![];
+        let mut pg_types = vec![];
+        for (name, typ) in connection.value_desc.iter() {
+            let pg_type = postgres_sink_column_type(&typ.scalar_type)
+                .ok_or_else(|| anyhow!("column {} cannot be sinked to PostgreSQL", name))?;
+            columns.push(quote_identifier(name.as_str()));
+            pg_types.push(pg_type);
+        }
+        let key_columns = key_indices
+            .iter()
+            .map(|i| columns[*i].clone())
+            .collect::<Vec<_>>();
+        let values = pg_types
+            .iter()
+            .enumerate()
+            .map(|(i, pg_type)| format!("${}::{}", i + 1, pg_type))
+            .collect::<Vec<_>>();
+        let updates = columns
+            .iter()
+            .filter(|column| !key_columns.contains(column))
+            .map(|column| format!("{column} = EXCLUDED.{column}"))
+            .collect::<Vec<_>>();
+        let on_conflict = if updates.is_empty() {
+            "DO NOTHING".into()
+        } else {
+            format!("DO UPDATE SET {}", updates.join(", "))
+        };
+        let upsert = format!(
+            "INSERT INTO {table} ({}) VALUES ({}) ON CONFLICT ({}) {on_conflict}",
+            columns.join(", "),
+            values.join(", "),
+            key_columns.join(", "),
+        );
+        let key_matches = key_indices
+            .iter()
+            .enumerate()
+            .map(|(i, idx)| format!("{} = ${}::{}", columns[*idx], i + 1, pg_types[*idx]))
+            .collect::<Vec<_>>();
+        let delete = format!("DELETE FROM {table} WHERE {}", key_matches.join(" AND "));
+        let read_progress = format!("SELECT ts FROM {progress_table} WHERE sink_id = $1");
+        let record_progress = format!(
+            "INSERT INTO {progress_table} (sink_id, ts) VALUES ($1, $2) \
+             ON CONFLICT (sink_id) DO UPDATE SET ts = EXCLUDED.ts"
+        );
+
+        let name = format!("postgres-{}", id);
+        let config = connection
+            .connection
+            .config(&*connection_context.secrets_reader)
+            .await?;
+        let value_params = vec![Type::TEXT; connection.value_desc.arity()];
+        let key_params = vec![Type::TEXT; key_desc.arity()];
+        let (client, statements) = task::spawn(|| name.clone(), async move {
+            let client = config
+                .connect("postgres_sink")
+                .await
+                .context("connecting to PostgreSQL")?;
+            let statements = (
+                client.prepare_typed(&upsert, &value_params).await?,
+                client.prepare_typed(&delete, &key_params).await?,
+                client.prepare_typed(&read_progress, &[Type::TEXT]).await?,
+                client
+                    .prepare_typed(&record_progress, &[Type::TEXT, Type::INT8])
+                    .await?,
+            );
+            Ok::<_, anyhow::Error>((client, statements))
+        })
+        .await??;
+        let (upsert, delete, read_progress, record_progress) = statements;
+
+        Ok(PostgresWriter {
+            name,
+            sink_id: id.to_string(),
+            client: Arc::new(client),
+            key_type: key_desc.typ().clone(),
+            value_type: connection.value_desc.typ().clone(),
+            upsert,
+            delete,
+            read_progress,
+            record_progress,
+        })
+    }
+
+    /// Returns the latest timestamp that the table reflects, if the sink has
+    /// ever applied any updates to it.
+    async fn latest_applied_ts(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        let client = Arc::clone(&self.client);
+        let read_progress = self.read_progress.clone();
+        let sink_id = self.sink_id.clone();
+        let row = task::spawn(|| self.name.clone(), async move {
+            client.query_opt(&read_progress, &[&sink_id]).await
+        })
+        .await??;
+        match row {
+            None => Ok(None),
+            Some(row) => {
+                let ts: i64 = row.get(0);
+                Ok(Some(Timestamp::from(u64::try_from(ts)?)))
+            }
+        }
+    }
+
+    /// Applies `updates`, which hold the new value of each updated key, or
+    /// `None` if the key was deleted, and records that the table reflects the
+    /// sinked collection as of `ts`, all in one transaction.
+    async fn apply(
+        &self,
+        updates: &[(Row, Option<Row>)],
+        ts: Timestamp,
+    ) -> Result<(), anyhow::Error> {
+        let mut statements = Vec::with_capacity(updates.len());
+        for (key, value) in updates {
+            statements.push(match value {
+                Some(value) => (self.upsert.clone(), encode_text(value, &self.value_type)),
+                None => (self.delete.clone(), encode_text(key, &self.key_type)),
+            });
+        }
+        let client = Arc::clone(&self.client);
+        let record_progress = self.record_progress.clone();
+        let sink_id = self.sink_id.clone();
+        let ts = i64::try_from(u64::from(ts))?;
+        task::spawn(|| self.name.clone(), async move {
+            client.batch_execute("BEGIN").await?;
+            for (stmt, params) in &statements {
+                let params = params
+                    .iter()
+                    .map(|param| -> &(dyn ToSql + Sync) { param })
+                    .collect::<Vec<_>>();
+                client.execute(stmt, &params).await?;
+            }
+            client.execute(&record_progress, &[&sink_id, &ts]).await?;
+            client.batch_execute("COMMIT").await
+        })
+        .await??;
+        Ok(())
+    }
+}
+
+/// Encodes the datums of `row` as PostgreSQL text, as parameters of the
+/// sink's statements.
+fn encode_text(row: &Row, typ: &RelationType) -> Vec<Option<String>> {
+    let mut buf = BytesMut::new();
+    mz_pgrepr::values_from_row(row.clone(), typ)
+        .into_iter()
+        .map(|value| {
+            value.map(|value| {
+                buf.clear();
+                value.encode_text(&mut buf);
+                String::from_utf8_lossy(&buf).into_owned()
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,426 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes the value of each key of a collection to a Redis key.
+//!
+//! Once the input frontier passes a timestamp, the updates at that timestamp
+//! are applied to Redis: the value of each updated key is written to its
+//! Redis key, either as a hash or as a JSON string, and the Redis key of each
+//! deleted key is deleted. The updates at all of the timestamps that a frontier
+//! closes are applied in a single `MULTI` transaction, which also records the
+//! last of those timestamps in the progress hash, so readers never observe a
+//! partially applied timestamp, and a restarted sink skips the updates at
+//! timestamps that it has already applied.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::Duration;
+
+use bytes::BytesMut;
+use differential_dataflow::{Collection, Hashable};
+use redis::aio::MultiplexedConnection;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tracing::{info, warn};
+
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_ore::{halt, task};
+use mz_repr::{ColumnName, ColumnType, Diff, GlobalId, RelationType, Row, Timestamp};
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::errors::DataflowError;
+use mz_storage_client::types::sinks::{
+    MetadataFilled, RedisSinkConnection, RedisSinkValueFormat, SinkAsOf, SinkErrorPolicy,
+    StorageSinkDesc,
+};
+use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
+
+use crate::render::sinks::{HealthcheckerArgs, SinkRender};
+use crate::sink::{Healthchecker, SinkStatistics, SinkStatus};
+use crate::storage_state::StorageState;
+
+impl<G> SinkRender<G> for RedisSinkConnection
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+        healthchecker_args: HealthcheckerArgs,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // As with Kafka sinks, only the worker to which the updates are
+        // exchanged ever holds back the write frontier.
+        let peers = sinked_collection.inner.scope().peers();
+        let worker_index = sinked_collection.inner.scope().index();
+        let active_write_worker = (usize::cast_from(sink_id.hashed()) % peers) == worker_index;
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        let token = write_keys(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            sink.error_policy,
+            Rc::clone(&shared_frontier),
+            storage_state.sink_statistics[&sink_id].clone(),
+            &storage_state.connection_context,
+            healthchecker_args,
+        );
+
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        Some(token)
+    }
+}
+
+/// Applies a stream of upsert-formatted updates to Redis keys.
+///
+/// This operator exchanges all updates to a single worker by hashing on the
+/// sink `id`. Updates are applied in ascending timestamp order once the input
+/// frontier has passed their timestamp, and updates that are not beyond the
+/// given [`SinkAsOf`] or that Redis already reflects are discarded.
+///
+/// A write that still fails after [`WRITE_ATTEMPTS`] attempts is handled as
+/// the given [`SinkErrorPolicy`] asks.
+fn write_keys<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connection: RedisSinkConnection,
+    as_of: SinkAsOf,
+    error_policy: SinkErrorPolicy,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+    statistics: SinkStatistics,
+    connection_context: &ConnectionContext,
+    healthchecker_args: HealthcheckerArgs,
+) -> Rc<dyn Any>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("redis-{}", id);
+    let scope = collection.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), scope.clone());
+
+    let hashed_id = id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_input(&collection.inner, Exchange::new(move |_| hashed_id));
+
+    let connection_context = connection_context.clone();
+    let button = builder.build(move |_capabilities| async move {
+        if !is_active_worker {
+            return;
+        }
+
+        let healthchecker = match healthchecker_args.status_shard_id {
+            Some(status_shard_id) => Some(
+                Healthchecker::new(
+                    id,
+                    &healthchecker_args.persist_clients,
+                    healthchecker_args.persist_location.clone(),
+                    status_shard_id,
+                    healthchecker_args.now_fn.clone(),
+                )
+                .await
+                .expect("error initializing healthchecker"),
+            ),
+            None => None,
+        };
+        let mut s = RedisSinkState {
+            name,
+            healthchecker,
+            error_policy,
+            statistics,
+        };
+
+        s.update_status(SinkStatus::Starting).await;
+
+        let writer = RedisWriter::new(&connection, id, &connection_context).await;
+        let writer = s.halt_on_err(writer).await;
+        let gate_ts = writer.latest_applied_ts().await;
+        let gate_ts = s.halt_on_err(gate_ts).await;
+        info!(
+            "{}: initial as_of: {:?}, latest applied timestamp: {:?}",
+            s.name, as_of.frontier, gate_ts
+        );
+
+        s.update_status(SinkStatus::Running).await;
+
+        let mut pending_rows: BTreeMap<Timestamp, Vec<(Row, Option<Row>)>> = BTreeMap::new();
+        let mut vector = Vec::new();
+        while let Some(event) = input.next().await {
+            match event {
+                Event::Data(_, rows) => {
+                    rows.swap(&mut vector);
+                    for ((key, value), time, diff) in vector.drain(..) {
+                        let should_emit = if as_of.strict {
+                            as_of.frontier.less_than(&time)
+                        } else {
+                            as_of.frontier.less_equal(&time)
+                        };
+                        let previously_applied = Some(time) <= gate_ts;
+                        if !should_emit || previously_applied || diff == 0 {
+                            continue;
+                        }
+                        let key = key.expect("redis sinks always have a key");
+                        pending_rows.entry(time).or_default().push((key, value));
+                    }
+                }
+                Event::Progress(frontier) => {
+                    let mut updates = vec![];
+                    let mut last_ts = None;
+                    while let Some(entry) = pending_rows.first_entry() {
+                        if frontier.less_equal(entry.key()) {
+                            break;
+                        }
+                        let (ts, rows) = entry.remove_entry();
+                        updates.extend(rows);
+                        last_ts = Some(ts);
+                    }
+
+                    if let Some(ts) = last_ts {
+                        let result = Retry::default()
+                            .max_tries(WRITE_ATTEMPTS)
+                            .clamp_backoff(BACKOFF_CLAMP)
+                            .retry_async(|_| writer.apply(&updates, ts))
+                            .await;
+                        if let Err(e) = result {
+                            if !s.handle_write_err(e, updates.len()).await {
+                                return;
+                            }
+                        }
+                    }
+
+                    // Every update before the frontier has been applied, and
+                    // every update at or beyond it is still pending.
+                    *write_frontier.borrow_mut() = frontier;
+                }
+            }
+        }
+    });
+
+    Rc::new(button.press_on_drop())
+}
+
+/// The number of times the sink attempts to apply the updates at a timestamp
+/// before it gives up as its [`SinkErrorPolicy`] asks.
+const WRITE_ATTEMPTS: usize = 5;
+
+/// The longest the sink waits between attempts to apply updates.
+const BACKOFF_CLAMP: Duration = Duration::from_secs(30);
+
+struct RedisSinkState {
+    name: String,
+    healthchecker: Option<Healthchecker>,
+    error_policy: SinkErrorPolicy,
+    statistics: SinkStatistics,
+}
+
+impl RedisSinkState {
+    async fn update_status(&mut self, status: SinkStatus) {
+        if let Some(hc) = &mut self.healthchecker {
+            hc.update_status(status).await;
+        }
+    }
+
+    /// Report a SinkStatus::Stalled and then halt with the same message.
+    async fn halt_on_err<T>(&mut self, result: Result<T, impl ToString + Debug>) -> T {
+        match result {
+            Ok(t) => t,
+            Err(msg) => {
+                self.update_status(SinkStatus::Stalled(msg.to_string()))
+                    .await;
+                halt!("{}: {msg:?}", self.name)
+            }
+        }
+    }
+
+    /// Handle a failed write of `count` updates as the sink's error policy
+    /// asks, returning whether the sink should keep writing.
+    async fn handle_write_err(&mut self, err: anyhow::Error, count: usize) -> bool {
+        match self.error_policy {
+            SinkErrorPolicy::Retry => self.halt_on_err(Err(err)).await,
+            SinkErrorPolicy::Pause => {
+                warn!("{}: pausing after failed write: {err:#}", self.name);
+                self.update_status(SinkStatus::Paused(err.to_string()))
+                    .await;
+                false
+            }
+            SinkErrorPolicy::Drop => {
+                warn!(
+                    "{}: dropping {count} updates after failed write: {err:#}",
+                    self.name
+                );
+                self.statistics
+                    .inc_updates_dropped_by(u64::cast_from(count));
+                true
+            }
+        }
+    }
+}
+
+/// Applies updates to the Redis keys of a sink, and records its progress.
+///
+/// The connection is driven on the Tokio runtime, so every command is issued
+/// from a spawned task.
+struct RedisWriter {
+    name: String,
+    sink_id: String,
+    conn: MultiplexedConnection,
+    key_prefix: String,
+    value_format: RedisSinkValueFormat,
+    key_type: RelationType,
+    value_type: RelationType,
+    value_columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl RedisWriter {
+    async fn new(
+        connection: &RedisSinkConnection,
+        id: GlobalId,
+        connection_context: &ConnectionContext,
+    ) -> Result<Self, anyhow::Error> {
+        let conn = connection
+            .connection
+            .connect(&*connection_context.secrets_reader)
+            .await?;
+        Ok(RedisWriter {
+            name: format!("redis-{}", id),
+            sink_id: id.to_string(),
+            conn,
+            key_prefix: connection.key_prefix.clone(),
+            value_format: connection.value_format,
+            key_type: connection.key_desc_and_indices.0.typ().clone(),
+            value_type: connection.value_desc.typ().clone(),
+            value_columns: column_names_and_types(connection.value_desc.clone()),
+        })
+    }
+
+    /// Returns the latest timestamp that Redis reflects, if the sink has ever
+    /// applied any updates to it.
+    async fn latest_applied_ts(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        let mut cmd = redis::cmd("HGET");
+        cmd.arg(RedisSinkConnection::PROGRESS_KEY)
+            .arg(&self.sink_id);
+        let mut conn = self.conn.clone();
+        let ts: Option<u64> = task::spawn(|| self.name.clone(), async move {
+            cmd.query_async::<_, Option<u64>>(&mut conn).await
+        })
+        .await??;
+        Ok(ts.map(Timestamp::from))
+    }
+
+    /// Applies `updates`, which hold the new value of each updated key, or
+    /// `None` if the key was deleted, and records that Redis reflects the
+    /// sinked collection as of `ts`, all in one transaction.
+    async fn apply(
+        &self,
+        updates: &[(Row, Option<Row>)],
+        ts: Timestamp,
+    ) -> Result<(), anyhow::Error> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (key, value) in updates {
+            let key = self.redis_key(key);
+            match (value, self.value_format) {
+                (None, _) => {
+                    pipe.del(&key).ignore();
+                }
+                (Some(value), RedisSinkValueFormat::Hash) => {
+                    // Replace rather than update the hash, so that fields of
+                    // columns that have become null are removed.
+                    pipe.del(&key).ignore();
+                    let fields = self
+                        .value_columns
+                        .iter()
+                        .zip(encode_text(value, &self.value_type))
+                        .filter_map(|((name, _), value)| Some((name.as_str(), value?)))
+                        .collect::<Vec<_>>();
+                    if !fields.is_empty() {
+                        pipe.hset_multiple(&key, &fields).ignore();
+                    }
+                }
+                (Some(value), RedisSinkValueFormat::Json) => {
+                    let value = encode_datums_as_json(value.iter(), &self.value_columns);
+                    pipe.set(&key, value.to_string()).ignore();
+                }
+            }
+        }
+        pipe.hset(
+            RedisSinkConnection::PROGRESS_KEY,
+            &self.sink_id,
+            u64::from(ts),
+        )
+        .ignore();
+
+        let mut conn = self.conn.clone();
+        task::spawn(|| self.name.clone(), async move {
+            pipe.query_async::<_, ()>(&mut conn).await
+        })
+        .await??;
+        Ok(())
+    }
+
+    /// Returns the Redis key for `key`: the key prefix followed by the value
+    /// of the key column if there is only one and it is not null, and by a
+    /// JSON array of the values of the key columns otherwise.
+    fn redis_key(&self, key: &Row) -> String {
+        let values = encode_text(key, &self.key_type);
+        match values.as_slice() {
+            [Some(value)] => format!("{}{}", self.key_prefix, value),
+            _ => format!("{}{}", self.key_prefix, serde_json::Value::from(values)),
+        }
+    }
+}
+
+/// Encodes the datums of `row` in the text format of the PostgreSQL wire
+/// protocol.
+fn encode_text(row: &Row, typ: &RelationType) -> Vec<Option<String>> {
+    let mut buf = BytesMut::new();
+    mz_pgrepr::values_from_row(row.clone(), typ)
+        .into_iter()
+        .map(|value| {
+            value.map(|value| {
+                buf.clear();
+                value.encode_text(&mut buf);
+                String::from_utf8_lossy(&buf).into_owned()
+            })
+        })
+        .collect()
+}
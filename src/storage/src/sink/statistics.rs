@@ -0,0 +1,98 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Helpers for managing sink statistics.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::progress::frontier::Antichain;
+
+use mz_ore::metric;
+use mz_ore::metrics::{CounterVecExt, DeleteOnDropCounter, IntCounterVec, MetricsRegistry};
+use mz_repr::{GlobalId, Timestamp};
+use mz_storage_client::client::SinkStatisticsUpdate;
+use prometheus::core::AtomicU64;
+
+use crate::sink::SinkBaseMetrics;
+
+#[derive(Clone, Debug)]
+pub(crate) struct SinkStatisticsMetricsDefinitions {
+    pub(crate) updates_dropped: IntCounterVec,
+}
+
+impl SinkStatisticsMetricsDefinitions {
+    pub(crate) fn register_with(registry: &MetricsRegistry) -> Self {
+        Self {
+            updates_dropped: registry.register(metric!(
+                name: "mz_sink_updates_dropped",
+                help: "The number of updates the worker discarded because they could not be written, as the sink's error policy asks.",
+                var_labels: ["sink_id", "worker_id"],
+            )),
+        }
+    }
+}
+
+/// Prometheus metrics for user-facing sink metrics.
+pub struct SinkStatisticsMetrics {
+    pub(crate) updates_dropped: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
+}
+
+impl SinkStatisticsMetrics {
+    pub fn new(id: GlobalId, worker_id: usize, metrics: &SinkBaseMetrics) -> SinkStatisticsMetrics {
+        SinkStatisticsMetrics {
+            updates_dropped: metrics
+                .sink_statistics
+                .updates_dropped
+                .get_delete_on_drop_counter(vec![id.to_string(), worker_id.to_string()]),
+        }
+    }
+}
+
+/// A helper struct that makes it easy for sink operators to update user-facing
+/// metrics, with the same caveats as
+/// [`SourceStatistics`](crate::source::statistics::SourceStatistics).
+///
+/// The write frontier of the sink is not tracked here, but read from the
+/// worker's shared sink write frontier whenever a snapshot is taken.
+#[derive(Clone)]
+pub struct SinkStatistics {
+    stats: Rc<RefCell<(SinkStatisticsUpdate, SinkStatisticsMetrics)>>,
+}
+
+impl SinkStatistics {
+    pub fn new(id: GlobalId, worker_id: usize, metrics: &SinkBaseMetrics) -> Self {
+        Self {
+            stats: Rc::new(RefCell::new((
+                SinkStatisticsUpdate {
+                    id,
+                    worker_id,
+                    updates_dropped: 0,
+                    write_frontier: None,
+                },
+                SinkStatisticsMetrics::new(id, worker_id, metrics),
+            ))),
+        }
+    }
+
+    /// Return a snapshot of the stats data, reporting `write_frontier` as the
+    /// write frontier of the sink.
+    pub fn snapshot(&self, write_frontier: &Antichain<Timestamp>) -> SinkStatisticsUpdate {
+        let mut snapshot = self.stats.borrow().0.clone();
+        snapshot.write_frontier = write_frontier.as_option().copied();
+        snapshot
+    }
+
+    /// Increment the `updates_dropped` stat.
+    pub fn inc_updates_dropped_by(&self, value: u64) {
+        let mut cur = self.stats.borrow_mut();
+        cur.0.updates_dropped = cur.0.updates_dropped + value;
+        cur.1.updates_dropped.inc_by(value);
+    }
+}
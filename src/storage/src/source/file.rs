@@ -0,0 +1,275 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that reads newline-delimited records from a local file.
+//!
+//! The offset of each record is its line number, counting from zero, so the
+//! source can resume after a restart by skipping the lines it has already
+//! emitted. The file is expected to only ever be appended to.
+//!
+//! The path names a single file. Glob patterns are not expanded, so a path
+//! that contains `*` or `?` refers to a file with that literal name.
+//!
+//! Files that are decoded as Avro are instead read as a single Avro Object
+//! Container File: the whole file is emitted as one chunk at offset zero,
+//! followed by the end-of-file marker that makes the decoder read it.
+
+use std::time::Duration;
+
+use timely::scheduling::SyncActivator;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::time::sleep;
+
+use mz_expr::PartitionId;
+use mz_repr::GlobalId;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::sources::encoding::{
+    DataEncoding, DataEncodingInner, SourceDataEncoding,
+};
+use mz_storage_client::types::sources::{FileSourceConnection, MzOffset};
+
+use crate::source::commit::LogCommitter;
+use crate::source::source_reader_pipeline::HealthStatus;
+use crate::source::types::SourceConnectionBuilder;
+use crate::source::{SourceMessage, SourceMessageType, SourceReader};
+
+pub struct FileSourceReader {
+    connection: FileSourceConnection,
+    /// Whether this worker is responsible for reading the file.
+    active_read_worker: bool,
+    reported_unconsumed_partitions: bool,
+    /// The file, once it has been opened.
+    file: Option<BufReader<File>>,
+    /// The offset of the next line to be read.
+    next_offset: u64,
+    /// Lines before this offset were emitted before the source restarted.
+    start_offset: u64,
+    /// The line being read, which may not be terminated yet.
+    line: Vec<u8>,
+    /// Whether the end of a file that is not tailed has been reached.
+    done: bool,
+    /// Whether the file is read as a whole, rather than line by line.
+    whole_file: bool,
+    /// Whether the end-of-file marker of a file that is read as a whole
+    /// remains to be emitted.
+    pending_eof: bool,
+}
+
+impl SourceConnectionBuilder for FileSourceConnection {
+    type Reader = FileSourceReader;
+    type OffsetCommitter = LogCommitter;
+
+    fn into_reader(
+        self,
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        _connection_context: ConnectionContext,
+    ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+        let start_offset = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match pid {
+                PartitionId::None => offset,
+                _ => panic!("unexpected partition id type"),
+            })
+            .map(|offset| offset.offset)
+            .max()
+            .unwrap_or(0);
+        Ok((
+            FileSourceReader {
+                connection: self,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+                file: None,
+                next_offset: 0,
+                start_offset,
+                line: Vec::new(),
+                done: false,
+                whole_file: FileSourceReader::reads_whole_file(&encoding),
+                pending_eof: false,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+}
+
+impl FileSourceReader {
+    /// Reports whether a file source with the given encoding reads its file as
+    /// a whole, which is the case for Avro Object Container Files.
+    pub fn reads_whole_file(encoding: &SourceDataEncoding) -> bool {
+        matches!(
+            encoding,
+            SourceDataEncoding::Single(DataEncoding {
+                inner: DataEncodingInner::Avro(_),
+                ..
+            })
+        )
+    }
+
+    /// Returns a message that carries the given value at offset zero, which
+    /// is where a file that is read as a whole is emitted.
+    fn whole_file_message(
+        value: Option<Vec<u8>>,
+        is_final: bool,
+    ) -> SourceMessageType<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
+        let msg = Ok(SourceMessage {
+            output: 0,
+            upstream_time_millis: None,
+            key: None,
+            value,
+            headers: None,
+        });
+        let ts = (PartitionId::None, MzOffset::from(0));
+        if is_final {
+            SourceMessageType::Finalized(msg, ts, ())
+        } else {
+            SourceMessageType::InProgress(msg, ts, ())
+        }
+    }
+
+    /// Returns the message for the line that was just read, unless it was
+    /// already emitted before the source restarted.
+    fn take_line(&mut self) -> Option<SourceMessageType<Option<Vec<u8>>, Option<Vec<u8>>, ()>> {
+        let mut line = std::mem::take(&mut self.line);
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        let offset = self.next_offset;
+        self.next_offset += 1;
+        if offset < self.start_offset {
+            return None;
+        }
+        let msg = Ok(SourceMessage {
+            output: 0,
+            upstream_time_millis: None,
+            key: None,
+            value: Some(line),
+            headers: None,
+        });
+        let ts = (PartitionId::None, MzOffset::from(offset));
+        Some(SourceMessageType::Finalized(msg, ts, ()))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SourceReader for FileSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Time = MzOffset;
+    type Diff = ();
+
+    async fn next(
+        &mut self,
+        timestamp_granularity: Duration,
+    ) -> Option<SourceMessageType<Self::Key, Self::Value, Self::Diff>> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ]));
+            }
+            return std::future::pending().await;
+        }
+
+        loop {
+            if self.pending_eof {
+                self.pending_eof = false;
+                self.done = true;
+                return Some(Self::whole_file_message(None, true));
+            }
+            // A file that is read as a whole is emitted at offset zero, so
+            // any restored offset means it was already emitted in full.
+            if self.done || (self.whole_file && self.start_offset > 0) {
+                return std::future::pending().await;
+            }
+
+            let file = match &mut self.file {
+                Some(file) => file,
+                None => match File::open(&self.connection.path).await {
+                    Ok(file) => {
+                        self.file = Some(BufReader::new(file));
+                        return Some(SourceMessageType::status(HealthStatus::Running));
+                    }
+                    Err(e) => {
+                        // The file may not have been created yet, so retry.
+                        sleep(timestamp_granularity).await;
+                        return Some(SourceMessageType::status(HealthStatus::StalledWithError(
+                            format!("opening {}: {}", self.connection.path, e),
+                        )));
+                    }
+                },
+            };
+
+            if self.whole_file {
+                match file.read_to_end(&mut self.line).await {
+                    Ok(_) => {
+                        // The contents and the end-of-file marker share an
+                        // offset, so that they are never split across a
+                        // restart.
+                        self.pending_eof = true;
+                        let contents = std::mem::take(&mut self.line);
+                        return Some(Self::whole_file_message(Some(contents), false));
+                    }
+                    Err(e) => {
+                        // Start over from the beginning of the file.
+                        self.file = None;
+                        self.line.clear();
+                        sleep(timestamp_granularity).await;
+                        return Some(SourceMessageType::status(HealthStatus::StalledWithError(
+                            format!("reading {}: {}", self.connection.path, e),
+                        )));
+                    }
+                }
+            }
+
+            match file.read_until(b'\n', &mut self.line).await {
+                Ok(0) if self.connection.tail => {
+                    // Wait for more data to be appended, keeping any partial
+                    // line until its end is written.
+                    sleep(timestamp_granularity).await;
+                }
+                Ok(0) => {
+                    self.done = true;
+                    // The final line need not be terminated.
+                    if !self.line.is_empty() {
+                        if let Some(msg) = self.take_line() {
+                            return Some(msg);
+                        }
+                    }
+                }
+                Ok(_) => {
+                    if self.line.last() == Some(&b'\n') {
+                        if let Some(msg) = self.take_line() {
+                            return Some(msg);
+                        }
+                    }
+                }
+                Err(e) => {
+                    sleep(timestamp_granularity).await;
+                    return Some(SourceMessageType::status(HealthStatus::StalledWithError(
+                        format!("reading {}: {}", self.connection.path, e),
+                    )));
+                }
+            }
+        }
+    }
+}
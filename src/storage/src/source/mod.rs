@@ -37,16 +37,21 @@ use crate::source::types::{NextMessage, SourceMessage, SourceReader};
 
 mod commit;
 mod delimited_value_reader;
+mod file;
 pub mod generator;
 pub mod healthcheck;
 mod kafka;
 mod kinesis;
 pub mod metrics;
+mod mqtt;
+mod mysql;
 mod postgres;
+mod pubsub;
 mod reclock;
 mod resumption;
 mod s3;
 mod source_reader_pipeline;
+mod sql_server;
 pub mod statistics;
 // Public for integration testing.
 #[doc(hidden)]
@@ -54,13 +59,19 @@ pub mod testscript;
 pub mod types;
 
 pub use delimited_value_reader::DelimitedValueSourceConnection;
+pub use file::FileSourceReader;
 pub use generator::LoadGeneratorSourceReader;
 pub use kafka::KafkaSourceReader;
 pub use kinesis::KinesisSourceReader;
+pub use mqtt::MqttSourceReader;
+pub use mysql::MySqlSourceReader;
 pub use postgres::PostgresSourceReader;
+pub use pubsub::PubSubSourceReader;
 pub use s3::S3SourceReader;
 pub use source_reader_pipeline::create_raw_source;
+pub use source_reader_pipeline::HealthStatus;
 pub use source_reader_pipeline::RawSourceCreationConfig;
+pub use sql_server::SqlServerSourceReader;
 pub use testscript::TestScriptSourceReader;
 
 /// Returns true if the given source id/worker id is responsible for handling the given
@@ -0,0 +1,303 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that subscribes to topics on an MQTT broker.
+//!
+//! The key of each message is the topic it was published to, and its value is
+//! the message's payload. MQTT brokers do not keep a replayable log of
+//! messages, so the offset of each message is a count of the messages that the
+//! source has emitted. The source connects with a persistent session, so that
+//! the broker queues messages published at QoS 1 or 2 while the source is
+//! disconnected, and delivers them when it reconnects. Messages are only
+//! acknowledged once the offset commit frontier passes them, i.e. once they
+//! have been durably recorded, so that the broker redelivers those that were
+//! received but not recorded when the source restarts.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rumqttc::{
+    AsyncClient, Event, EventLoop, Packet, Publish, QoS, SubscribeFilter, SubscribeReasonCode,
+};
+use timely::scheduling::SyncActivator;
+use tokio::sync::watch;
+use tokio::time::sleep;
+
+use mz_expr::PartitionId;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::sources::encoding::SourceDataEncoding;
+use mz_storage_client::types::sources::{MqttSourceConnection, MzOffset};
+
+use crate::source::commit::LogCommitter;
+use crate::source::source_reader_pipeline::HealthStatus;
+use crate::source::types::{OffsetCommitter, SourceConnectionBuilder};
+use crate::source::{SourceMessage, SourceMessageType, SourceReader};
+
+/// The number of requests that the MQTT client buffers before they are sent.
+const REQUEST_CAPACITY: usize = 10;
+
+/// How often the client pings the broker while no other packets are sent.
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+pub struct MqttSourceReader {
+    connection: MqttSourceConnection,
+    /// The ID with which the client identifies itself, and its session, to the
+    /// broker.
+    client_id: String,
+    secrets_reader: Arc<dyn SecretsReader>,
+    /// Whether this worker is responsible for subscribing to the broker.
+    active_read_worker: bool,
+    reported_unconsumed_partitions: bool,
+    /// The client and its event loop, once they have been created.
+    client: Option<(AsyncClient, EventLoop)>,
+    /// The offset of the next message to be emitted.
+    next_offset: u64,
+    /// Messages that have been emitted but not yet acknowledged, in offset
+    /// order.
+    unacknowledged: VecDeque<(u64, Publish)>,
+    /// Receives the offset commit frontier.
+    frontier_rx: watch::Receiver<u64>,
+}
+
+/// An OffsetCommitter for MQTT, that sends the offset commit frontier to the
+/// reader, which acknowledges the messages below it.
+pub struct MqttOffsetCommitter {
+    logger: LogCommitter,
+    /// Sends the offset commit frontier to the reader, if this worker is
+    /// responsible for subscribing to the broker.
+    frontier_tx: Option<watch::Sender<u64>>,
+}
+
+impl SourceConnectionBuilder for MqttSourceConnection {
+    type Reader = MqttSourceReader;
+    type OffsetCommitter = MqttOffsetCommitter;
+
+    fn into_reader(
+        self,
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+        let next_offset = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match pid {
+                PartitionId::None => offset,
+                _ => panic!("unexpected partition id type"),
+            })
+            .map(|offset| offset.offset)
+            .max()
+            .unwrap_or(0);
+        // The client ID must be stable across restarts for the broker to
+        // resume the source's session.
+        let client_id = format!(
+            "materialize-{}-{}-{}",
+            self.environment_id, self.connection_id, source_id
+        );
+        let (frontier_tx, frontier_rx) = watch::channel(next_offset);
+        Ok((
+            MqttSourceReader {
+                connection: self,
+                client_id,
+                secrets_reader: connection_context.secrets_reader,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+                client: None,
+                next_offset,
+                unacknowledged: VecDeque::new(),
+                frontier_rx,
+            },
+            MqttOffsetCommitter {
+                logger: LogCommitter {
+                    source_id,
+                    worker_id,
+                    worker_count,
+                },
+                frontier_tx: active_read_worker.then_some(frontier_tx),
+            },
+        ))
+    }
+}
+
+impl MqttSourceReader {
+    /// Creates the client and its event loop. The client does not connect to
+    /// the broker until the event loop is first polled.
+    async fn create_client(&self) -> Result<(AsyncClient, EventLoop), anyhow::Error> {
+        let mut options = self
+            .connection
+            .connection
+            .options(self.client_id.clone(), &*self.secrets_reader)
+            .await?;
+        options.set_clean_session(false);
+        options.set_keep_alive(KEEP_ALIVE);
+        options.set_manual_acks(true);
+        Ok(AsyncClient::new(options, REQUEST_CAPACITY))
+    }
+
+    fn stalled(
+        &self,
+        error: impl std::fmt::Display,
+    ) -> SourceMessageType<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
+        SourceMessageType::status(HealthStatus::StalledWithError(format!(
+            "MQTT broker {}:{}: {}",
+            self.connection.connection.host, self.connection.connection.port, error
+        )))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SourceReader for MqttSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Time = MzOffset;
+    type Diff = ();
+
+    async fn next(
+        &mut self,
+        timestamp_granularity: Duration,
+    ) -> Option<SourceMessageType<Self::Key, Self::Value, Self::Diff>> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ]));
+            }
+            return std::future::pending().await;
+        }
+
+        loop {
+            if self.client.is_none() {
+                match self.create_client().await {
+                    Ok(client) => self.client = Some(client),
+                    Err(e) => {
+                        sleep(timestamp_granularity).await;
+                        return Some(self.stalled(e));
+                    }
+                }
+            }
+            let (client, eventloop) = self.client.as_mut().expect("client created");
+            let frontier = *self.frontier_rx.borrow();
+            acknowledge_committed(&mut self.unacknowledged, frontier, client);
+
+            match eventloop.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(ack))) => {
+                    // The broker only remembers the subscriptions, and the
+                    // messages that await acknowledgement, of a session that
+                    // it resumed.
+                    if !ack.session_present {
+                        self.unacknowledged.clear();
+                        let qos = match self.connection.qos {
+                            0 => QoS::AtMostOnce,
+                            1 => QoS::AtLeastOnce,
+                            _ => QoS::ExactlyOnce,
+                        };
+                        let filters = self
+                            .connection
+                            .topic_filters
+                            .iter()
+                            .map(|filter| SubscribeFilter::new(filter.clone(), qos));
+                        if let Err(e) = client.try_subscribe_many(filters) {
+                            return Some(self.stalled(e));
+                        }
+                    }
+                    return Some(SourceMessageType::status(HealthStatus::Running));
+                }
+                Ok(Event::Incoming(Packet::SubAck(ack))) => {
+                    if ack
+                        .return_codes
+                        .iter()
+                        .any(|code| matches!(code, SubscribeReasonCode::Failure))
+                    {
+                        return Some(self.stalled(format!(
+                            "broker rejected subscription to topic filters {}",
+                            self.connection.topic_filters.join(", ")
+                        )));
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    // After a reconnection, the broker redelivers the messages
+                    // that were not acknowledged. Those that were already
+                    // emitted are acknowledged through their new delivery.
+                    if publish.dup && publish.qos != QoS::AtMostOnce {
+                        if let Some((_, pending)) = self
+                            .unacknowledged
+                            .iter_mut()
+                            .find(|(_, pending)| pending.pkid == publish.pkid)
+                        {
+                            *pending = publish;
+                            continue;
+                        }
+                    }
+                    let offset = self.next_offset;
+                    self.next_offset += 1;
+                    let msg = Ok(SourceMessage {
+                        output: 0,
+                        upstream_time_millis: None,
+                        key: Some(publish.topic.clone().into_bytes()),
+                        value: Some(publish.payload.to_vec()),
+                        headers: None,
+                    });
+                    self.unacknowledged.push_back((offset, publish));
+                    let ts = (PartitionId::None, MzOffset::from(offset));
+                    return Some(SourceMessageType::Finalized(msg, ts, ()));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // Polling the event loop again reconnects to the broker.
+                    sleep(timestamp_granularity).await;
+                    return Some(self.stalled(e));
+                }
+            }
+        }
+    }
+}
+
+/// Acknowledges the messages below the offset commit `frontier`, until the
+/// client's request buffer is full. The event loop sends the acknowledgements
+/// when it is next polled.
+fn acknowledge_committed(
+    unacknowledged: &mut VecDeque<(u64, Publish)>,
+    frontier: u64,
+    client: &AsyncClient,
+) {
+    while let Some((offset, publish)) = unacknowledged.front() {
+        if *offset >= frontier || client.try_ack(publish).is_err() {
+            break;
+        }
+        unacknowledged.pop_front();
+    }
+}
+
+#[async_trait::async_trait]
+impl OffsetCommitter for MqttOffsetCommitter {
+    async fn commit_offsets(
+        &self,
+        offsets: HashMap<PartitionId, MzOffset>,
+    ) -> Result<(), anyhow::Error> {
+        if let (Some(tx), Some(frontier)) = (&self.frontier_tx, offsets.get(&PartitionId::None)) {
+            // The reader may have been dropped, in which case there is nothing
+            // left to acknowledge.
+            tx.send_replace(frontier.offset);
+        }
+        self.logger.commit_offsets(offsets).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1067 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that ingests tables from a MySQL server.
+//!
+//! The source takes a consistent snapshot of the tables it ingests and then
+//! tails the server's binary log (binlog), acting as a replica of the server.
+//! Offsets are GTID sequence numbers (`gno`s) of the upstream server: the
+//! snapshot is emitted at the `gno` of the last transaction it includes, and
+//! each replicated transaction is emitted at its own `gno`. The source
+//! therefore requires the server to run with `gtid_mode = ON` and to have
+//! never failed over, i.e. every GTID it has executed carries its own UUID.
+
+use std::collections::HashMap;
+use std::future;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use futures::{FutureExt, StreamExt};
+use mysql_async::prelude::Queryable;
+use mysql_async::{BinlogRequest, Conn, Value};
+use mysql_common::binlog::events::EventData;
+use mysql_common::binlog::value::BinlogValue;
+use mysql_common::packets::Sid;
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{info, warn};
+
+use mz_expr::{MirScalarExpr, PartitionId};
+use mz_ore::display::DisplayExt;
+use mz_ore::task;
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
+use mz_storage_client::types::connections::{ConnectionContext, MySqlConnection};
+use mz_storage_client::types::errors::SourceErrorDetails;
+use mz_storage_client::types::sources::mysql::MySqlTableDesc;
+use mz_storage_client::types::sources::{
+    encoding::SourceDataEncoding, MySqlSourceConnection, MzOffset,
+};
+
+use super::metrics::SourceBaseMetrics;
+use crate::source::commit::LogCommitter;
+use crate::source::source_reader_pipeline::HealthStatus;
+use crate::source::types::{HealthStatusUpdate, SourceConnectionBuilder};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+
+enum ReplicationError {
+    /// This error is definite: this source is permanently wedged.
+    /// Returning a definite error will cause the collection to become un-queryable.
+    Definite(anyhow::Error),
+    /// This error may or may not resolve itself in the future, and
+    /// should be retried instead of being added to the output.
+    Indefinite(anyhow::Error),
+}
+
+macro_rules! try_definite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Definite(err.into())),
+        }
+    };
+}
+macro_rules! try_indefinite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Indefinite(err.into())),
+        }
+    };
+}
+
+// Message used to communicate between `get_next_message` and the tokio task
+enum InternalMessage {
+    Err(SourceReaderError),
+    Status(HealthStatusUpdate),
+    Value {
+        output: usize,
+        value: Row,
+        gno: u64,
+        diff: Diff,
+        end: bool,
+    },
+}
+
+/// Information required to sync data from MySQL
+pub struct MySqlSourceReader {
+    receiver_stream: Receiver<InternalMessage>,
+
+    // MySQL sources support single-threaded ingestion only, so only one of
+    // the `MySqlSourceReader`s will actually produce data.
+    active_read_worker: bool,
+
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+
+    /// The gno we last emitted data at. Used to fabricate timestamps for errors.
+    last_gno: u64,
+}
+
+/// Information about an ingested upstream table
+struct SourceTable {
+    /// The source output index of this table
+    output_index: usize,
+    /// The description of this table
+    desc: MySqlTableDesc,
+    /// The scalar expressions required to cast the text encoded columns into
+    /// the target relational types
+    casts: Vec<MirScalarExpr>,
+}
+
+/// An internal struct held by the spawned tokio task
+struct MySqlTaskInfo {
+    source_id: GlobalId,
+    connection: MySqlConnection,
+    connection_context: ConnectionContext,
+    server_id: u32,
+    /// Whether the initial snapshot has yet to be emitted.
+    needs_snapshot: bool,
+    /// The gno of the last transaction that has been fully emitted.
+    gno: u64,
+    /// The UUID of the upstream server, as found in the GTIDs it assigns.
+    server_uuid: Option<[u8; 16]>,
+    /// A map of the `(schema, table)` name to its information
+    source_tables: HashMap<(String, String), SourceTable>,
+    row_sender: RowSender,
+    sender: Sender<InternalMessage>,
+}
+
+impl SourceConnectionBuilder for MySqlSourceConnection {
+    type Reader = MySqlSourceReader;
+    type OffsetCommitter = LogCommitter;
+
+    fn into_reader(
+        self,
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(50_000);
+
+        // The start offset is the upper of the data already ingested, i.e.
+        // one past the gno of the last emitted transaction.
+        let start_offset = start_offsets
+            .into_iter()
+            .find_map(|(pid, offset)| {
+                if pid == PartitionId::None {
+                    offset
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let gno = start_offset.offset.saturating_sub(1);
+
+        if active_read_worker {
+            let mut source_tables = HashMap::new();
+            for (i, desc) in self.details.tables.iter().enumerate() {
+                let output_index = i + 1;
+                // Only tables referenced by the source have casts.
+                if let Some(casts) = self.table_casts.get(&output_index) {
+                    source_tables.insert(
+                        (desc.schema_name.clone(), desc.name.clone()),
+                        SourceTable {
+                            output_index,
+                            desc: desc.clone(),
+                            casts: casts.to_vec(),
+                        },
+                    );
+                }
+            }
+
+            let task_info = MySqlTaskInfo {
+                source_id,
+                connection: self.connection,
+                connection_context,
+                server_id: self.details.server_id,
+                needs_snapshot: start_offset.offset == 0,
+                gno,
+                server_uuid: None,
+                source_tables,
+                row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
+                sender: dataflow_tx,
+            };
+
+            task::spawn(
+                || format!("mysql_source:{}", source_id),
+                mysql_replication_loop(task_info),
+            );
+        }
+
+        Ok((
+            MySqlSourceReader {
+                receiver_stream: dataflow_rx,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+                last_gno: gno,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+}
+
+impl SourceReader for MySqlSourceReader {
+    type Key = ();
+    type Value = Row;
+    // MySQL can produce deletes that cause retractions
+    type Time = MzOffset;
+    type Diff = Diff;
+
+    fn get_next_message(&mut self) -> NextMessage<Self::Key, Self::Value, Self::Diff> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return NextMessage::Ready(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ]));
+            }
+            return NextMessage::Finished;
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(InternalMessage::Value {
+                output,
+                value,
+                gno,
+                diff,
+                end,
+            })) => {
+                self.last_gno = gno;
+                let msg = SourceMessage {
+                    output,
+                    upstream_time_millis: None,
+                    key: (),
+                    value,
+                    headers: None,
+                };
+                let ts = (PartitionId::None, MzOffset::from(gno));
+                if end {
+                    NextMessage::Ready(SourceMessageType::Finalized(Ok(msg), ts, diff))
+                } else {
+                    NextMessage::Ready(SourceMessageType::InProgress(Ok(msg), ts, diff))
+                }
+            }
+            Some(Some(InternalMessage::Status(update))) => {
+                NextMessage::Ready(SourceMessageType::SourceStatus(update))
+            }
+            Some(Some(InternalMessage::Err(err))) => {
+                // Errors don't belong to a transaction, so they are reported
+                // at the first gno that has not been emitted yet.
+                let non_definite_ts = (PartitionId::None, MzOffset::from(self.last_gno + 1));
+                NextMessage::Ready(SourceMessageType::Finalized(Err(err), non_definite_ts, 1))
+            }
+            None => NextMessage::Pending,
+            Some(None) => NextMessage::Finished,
+        }
+    }
+}
+
+/// Defers to `mysql_replication_loop_inner` and sends errors through the channel if they occur
+async fn mysql_replication_loop(mut task_info: MySqlTaskInfo) {
+    match mysql_replication_loop_inner(&mut task_info).await {
+        Ok(()) => {}
+        Err(e) => {
+            // Drop the send error, as we have no way of communicating back to the
+            // source operator if the channel is gone.
+            let _ = task_info
+                .row_sender
+                .sender
+                .send(InternalMessage::Err(e))
+                .await;
+            task_info
+                .row_sender
+                .activator
+                .activate()
+                .expect("mysql reader activation failed");
+        }
+    }
+}
+
+/// Core logic
+async fn mysql_replication_loop_inner(
+    task_info: &mut MySqlTaskInfo,
+) -> Result<(), SourceReaderError> {
+    if task_info.needs_snapshot {
+        match task_info.produce_snapshot().await {
+            Ok(_) => {
+                info!(
+                    "replication snapshot for source {} succeeded",
+                    &task_info.source_id
+                );
+            }
+            Err(ReplicationError::Indefinite(e)) => {
+                warn!(
+                    "replication snapshot for source {} failed: {}",
+                    &task_info.source_id, e
+                );
+                // If the channel is shutting down, so is the source. As with
+                // Postgres sources, halting discards any partially emitted
+                // snapshot, which the restarted process takes afresh.
+                let _ = task_info
+                    .sender
+                    .send(InternalMessage::Status(HealthStatusUpdate {
+                        update: HealthStatus::StalledWithError(e.to_string_alt()),
+                        should_halt: true,
+                    }))
+                    .await;
+
+                future::pending().await
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Initialization(e.to_string()),
+                })
+            }
+        }
+    }
+
+    loop {
+        match task_info.produce_replication().await {
+            Err(ReplicationError::Indefinite(e)) => {
+                // If the channel is shutting down, so is the source.
+                let _ = task_info
+                    .sender
+                    .send(InternalMessage::Status(HealthStatusUpdate {
+                        update: HealthStatus::StalledWithError(e.to_string()),
+                        should_halt: false,
+                    }))
+                    .await;
+                warn!(
+                    "replication for source {} interrupted, retrying: {}",
+                    task_info.source_id, e
+                );
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                })
+            }
+            Ok(_) => {
+                // shutdown initiated elsewhere
+                return Ok(());
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        info!("resuming replication for source {}", task_info.source_id);
+    }
+}
+
+struct RowMessage {
+    output_index: usize,
+    row: Row,
+    gno: u64,
+    diff: i64,
+}
+
+/// A type that makes it easy to correctly send inserts and deletes.
+///
+/// Note: `RowSender::delete/insert` should be called with the same gno until
+/// `close_gno` is called, which should be called and awaited before dropping
+/// the `RowSender` or moving onto a new gno.
+struct RowSender {
+    sender: Sender<InternalMessage>,
+    activator: SyncActivator,
+    buffered_message: Option<RowMessage>,
+}
+
+impl RowSender {
+    /// Create a new `RowSender`.
+    pub fn new(sender: Sender<InternalMessage>, activator: SyncActivator) -> Self {
+        Self {
+            sender,
+            activator,
+            buffered_message: None,
+        }
+    }
+
+    /// Insert a row at a gno.
+    pub async fn insert(&mut self, output_index: usize, row: Row, gno: u64) {
+        self.push(output_index, row, gno, 1).await
+    }
+
+    /// Delete a row at a gno.
+    pub async fn delete(&mut self, output_index: usize, row: Row, gno: u64) {
+        self.push(output_index, row, gno, -1).await
+    }
+
+    async fn push(&mut self, output_index: usize, row: Row, gno: u64, diff: i64) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.gno, gno);
+            self.send_row(buffered, false).await;
+        }
+        self.buffered_message = Some(RowMessage {
+            output_index,
+            row,
+            gno,
+            diff,
+        });
+    }
+
+    /// Finalize a gno, making sure all messages that may be buffered are
+    /// flushed, and that the last message sent is marked as closing the gno.
+    pub async fn close_gno(&mut self, gno: u64) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.gno, gno);
+            self.send_row(buffered, true).await;
+        }
+    }
+
+    async fn send_row(&self, message: RowMessage, end: bool) {
+        // a closed receiver means the source has been shutdown
+        // (dropped or the process is dying), so just continue on
+        // without activation
+        if let Ok(_) = self
+            .sender
+            .send(InternalMessage::Value {
+                output: message.output_index,
+                value: message.row,
+                gno: message.gno,
+                diff: message.diff,
+                end,
+            })
+            .await
+        {
+            self.activator
+                .activate()
+                .expect("mysql reader activation failed");
+        }
+    }
+}
+
+impl MySqlTaskInfo {
+    async fn connect(&self) -> Result<Conn, anyhow::Error> {
+        self.connection
+            .connect(&*self.connection_context.secrets_reader)
+            .await
+    }
+
+    /// Returns the UUID of the upstream server and the gno of the last
+    /// transaction it has executed.
+    ///
+    /// Errors if the server has executed transactions under any other UUID,
+    /// as the source cannot represent their positions as a single offset.
+    async fn gtid_position(conn: &mut Conn) -> Result<([u8; 16], u64), ReplicationError> {
+        let position: Option<(String, String)> = try_indefinite!(
+            conn.query_first("SELECT @@server_uuid, @@gtid_executed")
+                .await
+        );
+        let (server_uuid, gtid_executed) = try_indefinite!(
+            position.ok_or_else(|| anyhow!("unable to read GTID position from MySQL server"))
+        );
+        let uuid = try_definite!(uuid::Uuid::parse_str(&server_uuid));
+        let gtid_executed = gtid_executed.replace('\n', "");
+        if gtid_executed.is_empty() {
+            return Ok((*uuid.as_bytes(), 0));
+        }
+        let gno = match gtid_executed.split_once(':') {
+            Some((sid, interval)) if sid.eq_ignore_ascii_case(&server_uuid) => {
+                match interval.split_once('-') {
+                    Some(("1", end)) => end.parse().ok(),
+                    None if interval == "1" => Some(1),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match gno {
+            Some(gno) => Ok((*uuid.as_bytes(), gno)),
+            None => Err(ReplicationError::Definite(anyhow!(
+                "MySQL server has executed GTIDs {} which are not a single contiguous \
+                range of its own transactions",
+                gtid_executed
+            ))),
+        }
+    }
+
+    /// Produces the initial snapshot of the ingested tables.
+    ///
+    /// While holding read locks on the tables, this records the GTID position
+    /// of the server and starts a consistent snapshot transaction on a second
+    /// connection, so that the snapshot reflects exactly the transactions up
+    /// to that position.
+    async fn produce_snapshot(&mut self) -> Result<(), ReplicationError> {
+        let mut lock_conn = try_indefinite!(self.connect().await);
+        let mut snapshot_conn = try_indefinite!(self.connect().await);
+
+        // Validate that the tables still have the schema the source expects.
+        let tables = try_indefinite!(
+            mz_storage_client::types::sources::mysql::table_info(&mut snapshot_conn).await
+        );
+        try_definite!(self.validate_tables(tables));
+
+        if !self.source_tables.is_empty() {
+            let lock_query = format!(
+                "LOCK TABLES {}",
+                self.source_tables
+                    .values()
+                    .map(|t| format!(
+                        "{}.{} READ",
+                        quote_identifier(&t.desc.schema_name),
+                        quote_identifier(&t.desc.name)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            try_indefinite!(lock_conn.query_drop(lock_query).await);
+        }
+        let (server_uuid, gno) = Self::gtid_position(&mut lock_conn).await?;
+        try_indefinite!(
+            snapshot_conn
+                .query_drop("SET SESSION time_zone = '+00:00'")
+                .await
+        );
+        try_indefinite!(
+            snapshot_conn
+                .query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+                .await
+        );
+        try_indefinite!(lock_conn.query_drop("UNLOCK TABLES").await);
+        let _ = lock_conn.disconnect().await;
+
+        self.server_uuid = Some(server_uuid);
+        self.gno = gno;
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        for info in self.source_tables.values() {
+            let query = format!(
+                "SELECT * FROM {}.{}",
+                quote_identifier(&info.desc.schema_name),
+                quote_identifier(&info.desc.name)
+            );
+            let mut result = try_indefinite!(snapshot_conn.query_iter(query).await);
+            let mut stream = match try_indefinite!(result.stream::<mysql_async::Row>().await) {
+                Some(stream) => stream,
+                None => continue,
+            };
+            while let Some(row) = stream.next().await {
+                let row = try_indefinite!(row);
+                let values = row.unwrap().into_iter().map(BinlogValue::Value).collect();
+                let row = try_definite!(info.decode_row(values, &mut datum_vec));
+                self.row_sender.insert(info.output_index, row, gno).await;
+            }
+        }
+        try_indefinite!(snapshot_conn.query_drop("COMMIT").await);
+        let _ = snapshot_conn.disconnect().await;
+
+        // close the current `row_sender` context after we are sure we have not errored
+        // out (in the commit).
+        self.row_sender.close_gno(gno).await;
+        self.needs_snapshot = false;
+        Ok(())
+    }
+
+    /// Validates that all ingested tables still exist upstream with the same
+    /// schema.
+    fn validate_tables(&self, tables: Vec<MySqlTableDesc>) -> Result<(), anyhow::Error> {
+        let tables: HashMap<_, _> = tables
+            .into_iter()
+            .map(|t| ((t.schema_name.clone(), t.name.clone()), t))
+            .collect();
+        for (name, info) in self.source_tables.iter() {
+            match tables.get(name) {
+                Some(desc) if desc == &info.desc => {}
+                Some(_) => bail!(
+                    "Schema for table {}.{} differs, recreate Materialize source to use new schema",
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+                None => bail!(
+                    "MySQL server is missing expected table {}.{}",
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams the binlog from the current gno onwards, emitting the changes
+    /// to the ingested tables.
+    async fn produce_replication(&mut self) -> Result<(), ReplicationError> {
+        let mut conn = try_indefinite!(self.connect().await);
+        let (server_uuid, executed_gno) = Self::gtid_position(&mut conn).await?;
+        if let Some(expected) = self.server_uuid {
+            if expected != server_uuid {
+                return Err(ReplicationError::Definite(anyhow!(
+                    "MySQL server UUID changed; recreate the source"
+                )));
+            }
+        }
+        self.server_uuid = Some(server_uuid);
+        if executed_gno < self.gno {
+            return Err(ReplicationError::Definite(anyhow!(
+                "MySQL server has executed fewer transactions ({}) than the source has \
+                ingested ({})",
+                executed_gno,
+                self.gno
+            )));
+        }
+
+        // The transactions the source still needs must not have been purged
+        // from the binlog.
+        let purged: Option<String> =
+            try_indefinite!(conn.query_first("SELECT @@gtid_purged").await);
+        let purged = purged.unwrap_or_default().replace('\n', "");
+        if let Some((_, interval)) = purged.split_once(':') {
+            let purged_gno: u64 = match interval.split_once('-') {
+                Some((_, end)) => try_definite!(end.parse()),
+                None => try_definite!(interval.parse()),
+            };
+            if purged_gno > self.gno {
+                return Err(ReplicationError::Definite(anyhow!(
+                    "MySQL server has purged transactions up to {} from its binlog, \
+                    but the source has only ingested up to {}",
+                    purged_gno,
+                    self.gno
+                )));
+            }
+        }
+
+        let uuid = uuid::Uuid::from_bytes(server_uuid);
+        let mut request = BinlogRequest::new(self.server_id).with_gtid();
+        if self.gno > 0 {
+            let sid: Sid<'static> =
+                try_definite!(format!("{}:1-{}", uuid, self.gno).parse::<Sid>());
+            request = request.with_gtid_set(vec![sid]);
+        }
+        let mut stream = try_indefinite!(conn.get_binlog_stream(request).await);
+
+        // The gno of the transaction being replicated, if any.
+        let mut current_gno = None;
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        while let Some(event) = stream.next().await {
+            let event = try_indefinite!(event);
+            let data = match try_indefinite!(event.read_data()) {
+                Some(data) => data,
+                None => continue,
+            };
+            match data {
+                EventData::GtidEvent(gtid) => {
+                    if gtid.sid() != server_uuid {
+                        return Err(ReplicationError::Definite(anyhow!(
+                            "MySQL server executed a transaction with a foreign GTID"
+                        )));
+                    }
+                    current_gno = Some(gtid.gno());
+                }
+                EventData::RowsEvent(rows_event) => {
+                    let gno = try_definite!(current_gno
+                        .ok_or_else(|| anyhow!("received row event outside of a transaction")));
+                    let tme = try_definite!(stream
+                        .get_tme(rows_event.table_id())
+                        .ok_or_else(|| anyhow!("received row event without table map event")));
+                    let key = (
+                        tme.database_name().into_owned(),
+                        tme.table_name().into_owned(),
+                    );
+                    let info = match self.source_tables.get(&key) {
+                        Some(info) => info,
+                        None => continue,
+                    };
+                    for change in rows_event.rows(tme) {
+                        let (before, after) = try_definite!(change);
+                        if let Some(before) = before {
+                            let row =
+                                try_definite!(info.decode_row(before.unwrap(), &mut datum_vec));
+                            self.row_sender.delete(info.output_index, row, gno).await;
+                        }
+                        if let Some(after) = after {
+                            let row =
+                                try_definite!(info.decode_row(after.unwrap(), &mut datum_vec));
+                            self.row_sender.insert(info.output_index, row, gno).await;
+                        }
+                    }
+                }
+                EventData::XidEvent(_) => {
+                    if let Some(gno) = current_gno.take() {
+                        self.row_sender.close_gno(gno).await;
+                        self.gno = gno;
+                    }
+                }
+                EventData::QueryEvent(query) => {
+                    let text = query.query();
+                    if text.eq_ignore_ascii_case("BEGIN") {
+                        continue;
+                    }
+                    if !text.eq_ignore_ascii_case("COMMIT") {
+                        // Statements other than transaction control are DDL,
+                        // which the source cannot apply to its subsources.
+                        let text = text.to_lowercase();
+                        if let Some(info) = self
+                            .source_tables
+                            .values()
+                            .find(|t| text.contains(&t.desc.name.to_lowercase()))
+                        {
+                            return Err(ReplicationError::Definite(anyhow!(
+                                "MySQL table {}.{} was altered by statement {}; recreate the \
+                                source to ingest its new schema",
+                                info.desc.schema_name,
+                                info.desc.name,
+                                query.query()
+                            )));
+                        }
+                    }
+                    // DDL statements and non-transactional writes are
+                    // committed implicitly.
+                    if let Some(gno) = current_gno.take() {
+                        self.row_sender.close_gno(gno).await;
+                        self.gno = gno;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SourceTable {
+    /// Converts the values of an upstream row into a text encoded row and
+    /// casts it to the target types.
+    fn decode_row(
+        &self,
+        values: Vec<BinlogValue<'static>>,
+        datum_vec: &mut DatumVec,
+    ) -> Result<Row, anyhow::Error> {
+        if values.len() != self.desc.columns.len() {
+            bail!(
+                "MySQL table {}.{} has {} columns but the source expects {}",
+                self.desc.schema_name,
+                self.desc.name,
+                values.len(),
+                self.desc.columns.len()
+            );
+        }
+        let texts = values
+            .into_iter()
+            .zip(self.desc.columns.iter())
+            .map(|(value, column)| value_to_text(&column.column_type, value))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut datums = datum_vec.borrow();
+        datums.extend(texts.iter().map(|text| match text {
+            Some(text) => Datum::String(text),
+            None => Datum::Null,
+        }));
+
+        let arena = mz_repr::RowArena::new();
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        for column_cast in &self.casts {
+            let datum = column_cast.eval(&datums, &arena)?;
+            packer.push(datum);
+        }
+        Ok(row)
+    }
+}
+
+/// Quotes a MySQL identifier.
+fn quote_identifier(ident: &str) -> String {
+    format!("`{}`", ident.replace('`', "``"))
+}
+
+/// Converts a value of a column of MySQL type `column_type` into the text
+/// encoding from which the source casts it to its target type.
+///
+/// Values read from a snapshot arrive as text already, except for binary
+/// strings; values read from the binlog arrive in their binary encoding.
+fn value_to_text(
+    column_type: &str,
+    value: BinlogValue<'static>,
+) -> Result<Option<String>, anyhow::Error> {
+    let base = column_type
+        .split(|c: char| c == '(' || c.is_whitespace())
+        .next()
+        .unwrap_or(column_type);
+    let value = match value {
+        BinlogValue::Value(value) => value,
+        BinlogValue::Jsonb(json) => {
+            let json = serde_json::Value::try_from(json)
+                .map_err(|e| anyhow!("decoding JSON value: {}", e))?;
+            return Ok(Some(json.to_string()));
+        }
+        BinlogValue::JsonDiff(_) => bail!(
+            "received a partial JSON update; set binlog_row_value_options to '' on the MySQL server"
+        ),
+    };
+    let text = match value {
+        Value::NULL => return Ok(None),
+        Value::Bytes(bytes) => match base {
+            "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+                format!("\\x{}", hex::encode(bytes))
+            }
+            "timestamp" => {
+                let text = String::from_utf8(bytes)?;
+                if text.contains('-') {
+                    format!("{}+00", text)
+                } else {
+                    // The binlog encodes timestamps as seconds since the
+                    // Unix epoch, with an optional fractional part.
+                    let (secs, frac) = text.split_once('.').unwrap_or((&text, "0"));
+                    let nanos = format!("{:0<9}", frac).parse()?;
+                    let ts = chrono::NaiveDateTime::from_timestamp_opt(secs.parse()?, nanos)
+                        .ok_or_else(|| anyhow!("invalid timestamp {}", text))?;
+                    format!("{}+00", ts.format("%Y-%m-%d %H:%M:%S%.6f"))
+                }
+            }
+            _ => String::from_utf8(bytes)?,
+        },
+        Value::Int(n) => match base {
+            "enum" => enum_label(column_type, n)?,
+            "set" => set_labels(column_type, n)?,
+            "timestamp" => {
+                let ts = chrono::NaiveDateTime::from_timestamp_opt(n, 0)
+                    .ok_or_else(|| anyhow!("invalid timestamp {}", n))?;
+                format!("{}+00", ts.format("%Y-%m-%d %H:%M:%S"))
+            }
+            _ if column_type.contains("unsigned") => {
+                // The binlog does not record signedness, so unsigned values
+                // that exceed the signed range arrive as negative numbers.
+                let bits = match base {
+                    "tinyint" => 8,
+                    "smallint" => 16,
+                    "mediumint" => 24,
+                    "int" | "integer" => 32,
+                    _ => 64,
+                };
+                let n = u64::from_ne_bytes(n.to_ne_bytes());
+                let n = if bits == 64 { n } else { n & ((1 << bits) - 1) };
+                n.to_string()
+            }
+            _ => n.to_string(),
+        },
+        Value::UInt(n) => n.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Double(f) => f.to_string(),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            let date = format!("{:04}-{:02}-{:02}", year, month, day);
+            match base {
+                "date" => date,
+                _ => {
+                    let datetime = format!(
+                        "{} {:02}:{:02}:{:02}.{:06}",
+                        date, hour, minute, second, micros
+                    );
+                    if base == "timestamp" {
+                        format!("{}+00", datetime)
+                    } else {
+                        datetime
+                    }
+                }
+            }
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => format!(
+            "{}{:02}:{:02}:{:02}.{:06}",
+            if negative { "-" } else { "" },
+            u64::from(days) * 24 + u64::from(hours),
+            minutes,
+            seconds,
+            micros
+        ),
+    };
+    Ok(Some(text))
+}
+
+/// Parses the labels of an `enum` or `set` column type, e.g. `enum('a','b')`.
+fn labels(column_type: &str) -> Result<Vec<String>, anyhow::Error> {
+    let start = column_type
+        .find('(')
+        .ok_or_else(|| anyhow!("invalid column type {}", column_type))?;
+    let mut labels = vec![];
+    let mut chars = column_type[start + 1..].chars().peekable();
+    loop {
+        match chars.next() {
+            Some('\'') => {
+                let mut label = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\'') if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            label.push('\'');
+                        }
+                        Some('\'') => break,
+                        Some(c) => label.push(c),
+                        None => bail!("invalid column type {}", column_type),
+                    }
+                }
+                labels.push(label);
+            }
+            Some(',') => {}
+            Some(')') | None => break,
+            Some(c) => bail!("unexpected character {} in column type {}", c, column_type),
+        }
+    }
+    Ok(labels)
+}
+
+/// Returns the label of the `enum` value with the 1-based index `n`.
+fn enum_label(column_type: &str, n: i64) -> Result<String, anyhow::Error> {
+    // MySQL stores invalid enum values as the index 0, which reads back as
+    // the empty string.
+    if n == 0 {
+        return Ok(String::new());
+    }
+    let labels = labels(column_type)?;
+    usize::try_from(n - 1)
+        .ok()
+        .and_then(|i| labels.into_iter().nth(i))
+        .ok_or_else(|| anyhow!("enum index {} out of range for {}", n, column_type))
+}
+
+/// Returns the comma separated labels of the members of a `set` value, whose
+/// bit `i` indicates the presence of the `i`th label.
+fn set_labels(column_type: &str, n: i64) -> Result<String, anyhow::Error> {
+    let labels = labels(column_type)?;
+    Ok(labels
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| *i < 64 && n & (1 << i) != 0)
+        .map(|(_, label)| label)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(column_type: &str, value: Value) -> Option<String> {
+        value_to_text(column_type, BinlogValue::Value(value)).unwrap()
+    }
+
+    #[test]
+    fn test_value_to_text_integers() {
+        assert_eq!(text("int", Value::Int(-5)), Some("-5".into()));
+        assert_eq!(text("int", Value::UInt(5)), Some("5".into()));
+        // The binlog reports unsigned values as signed integers of the
+        // column's width.
+        assert_eq!(text("tinyint unsigned", Value::Int(-1)), Some("255".into()));
+        assert_eq!(
+            text("smallint(5) unsigned", Value::Int(-1)),
+            Some("65535".into())
+        );
+        assert_eq!(
+            text("mediumint unsigned", Value::Int(-1)),
+            Some("16777215".into())
+        );
+        assert_eq!(
+            text("int unsigned", Value::Int(-1)),
+            Some("4294967295".into())
+        );
+        assert_eq!(
+            text("bigint unsigned", Value::Int(-1)),
+            Some("18446744073709551615".into())
+        );
+        assert_eq!(text("int unsigned", Value::Int(7)), Some("7".into()));
+    }
+
+    #[test]
+    fn test_value_to_text_strings() {
+        assert_eq!(text("varchar(10)", Value::NULL), None);
+        assert_eq!(
+            text("varchar(10)", Value::Bytes(b"hello".to_vec())),
+            Some("hello".into())
+        );
+        assert_eq!(
+            text("varbinary(10)", Value::Bytes(vec![0xde, 0xad])),
+            Some("\\xdead".into())
+        );
+        assert_eq!(text("blob", Value::Bytes(vec![])), Some("\\x".into()));
+        assert!(value_to_text("text", BinlogValue::Value(Value::Bytes(vec![0xff]))).is_err());
+    }
+
+    #[test]
+    fn test_value_to_text_temporal() {
+        assert_eq!(
+            text("date", Value::Date(2020, 1, 2, 0, 0, 0, 0)),
+            Some("2020-01-02".into())
+        );
+        assert_eq!(
+            text("datetime(6)", Value::Date(2020, 1, 2, 3, 4, 5, 6)),
+            Some("2020-01-02 03:04:05.000006".into())
+        );
+        assert_eq!(
+            text("timestamp", Value::Date(2020, 1, 2, 3, 4, 5, 0)),
+            Some("2020-01-02 03:04:05.000000+00".into())
+        );
+        // Timestamps in the binlog are seconds since the Unix epoch.
+        assert_eq!(
+            text("timestamp", Value::Int(86400)),
+            Some("1970-01-02 00:00:00+00".into())
+        );
+        assert_eq!(
+            text("timestamp(3)", Value::Bytes(b"1.5".to_vec())),
+            Some("1970-01-01 00:00:01.500000+00".into())
+        );
+        assert_eq!(
+            text("timestamp", Value::Bytes(b"2020-01-02 03:04:05".to_vec())),
+            Some("2020-01-02 03:04:05+00".into())
+        );
+        assert_eq!(
+            text("time", Value::Time(false, 0, 1, 2, 3, 0)),
+            Some("01:02:03.000000".into())
+        );
+        assert_eq!(
+            text("time", Value::Time(true, 1, 2, 3, 4, 5)),
+            Some("-26:03:04.000005".into())
+        );
+    }
+
+    #[test]
+    fn test_value_to_text_enum_and_set() {
+        let enum_type = "enum('a','it''s','c')";
+        assert_eq!(text(enum_type, Value::Int(1)), Some("a".into()));
+        assert_eq!(text(enum_type, Value::Int(2)), Some("it's".into()));
+        assert_eq!(text(enum_type, Value::Int(0)), Some("".into()));
+        assert!(value_to_text(enum_type, BinlogValue::Value(Value::Int(4))).is_err());
+        // Snapshots read enums as their labels.
+        assert_eq!(
+            text(enum_type, Value::Bytes(b"c".to_vec())),
+            Some("c".into())
+        );
+
+        let set_type = "set('a','b','c')";
+        assert_eq!(text(set_type, Value::Int(0)), Some("".into()));
+        assert_eq!(text(set_type, Value::Int(0b101)), Some("a,c".into()));
+        assert_eq!(text(set_type, Value::Int(0b111)), Some("a,b,c".into()));
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(labels("enum('a','b')").unwrap(), vec!["a", "b"]);
+        assert_eq!(labels("enum('a,b','(c)')").unwrap(), vec!["a,b", "(c)"]);
+        assert_eq!(labels("set('')").unwrap(), vec![""]);
+        assert!(labels("enum").is_err());
+        assert!(labels("enum('a").is_err());
+    }
+}
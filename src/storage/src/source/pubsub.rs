@@ -0,0 +1,375 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that pulls messages from a Google Cloud Pub/Sub subscription.
+//!
+//! The key of each message is its ordering key, and its value is the message's
+//! payload. Pub/Sub does not keep a replayable log of messages, so the offset of
+//! each message is a count of the messages that the source has emitted.
+//! Messages are only acknowledged once the offset commit frontier passes them,
+//! i.e. once they have been durably recorded, so that those that were pulled
+//! but not recorded when the source restarts are redelivered. Until then, a
+//! separate task holds a lease on each message by repeatedly extending its ack
+//! deadline.
+//!
+//! Messages are emitted in the order in which they are pulled, which preserves
+//! the order of the messages that share an ordering key if the subscription has
+//! message ordering enabled.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use timely::scheduling::SyncActivator;
+use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender};
+use tokio::time::{self, sleep};
+use tracing::warn;
+
+use mz_expr::PartitionId;
+use mz_ore::task;
+use mz_repr::GlobalId;
+use mz_secrets::SecretsReader;
+use mz_storage_client::types::connections::gcp::PubSubClient;
+use mz_storage_client::types::connections::ConnectionContext;
+use mz_storage_client::types::sources::encoding::SourceDataEncoding;
+use mz_storage_client::types::sources::{MzOffset, PubSubSourceConnection};
+
+use crate::source::commit::LogCommitter;
+use crate::source::source_reader_pipeline::HealthStatus;
+use crate::source::types::{OffsetCommitter, SourceConnectionBuilder, SourceReaderError};
+use crate::source::{SourceMessage, SourceMessageType, SourceReader};
+
+/// The maximum number of messages to pull at once.
+const MAX_MESSAGES_PER_PULL: usize = 1_000;
+
+/// The maximum number of messages that have been pulled but not yet
+/// acknowledged. The source stops pulling when it reaches this limit.
+const MAX_OUTSTANDING_MESSAGES: usize = 10_000;
+
+/// The maximum number of ack IDs to include in a single request.
+const MAX_ACK_IDS_PER_REQUEST: usize = 1_000;
+
+/// The ack deadline that the source sets on the messages it holds.
+const ACK_DEADLINE: Duration = Duration::from_secs(60);
+
+/// How often the source extends the ack deadlines of the messages it holds.
+const ACK_DEADLINE_EXTENSION_INTERVAL: Duration = Duration::from_secs(20);
+
+/// The offset and ack ID of a message that has been pulled but not yet
+/// acknowledged.
+type Lease = (u64, String);
+
+pub struct PubSubSourceReader {
+    connection: PubSubSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    /// Whether this worker is responsible for pulling from the subscription.
+    active_read_worker: bool,
+    reported_unconsumed_partitions: bool,
+    /// The client, once it has been created.
+    client: Option<PubSubClient>,
+    /// Whether the last attempt to pull from the subscription failed.
+    stalled: bool,
+    /// Messages that have been pulled but not yet emitted.
+    buffer: VecDeque<SourceMessageType<Option<Vec<u8>>, Option<Vec<u8>>, ()>>,
+    /// The offset of the next message to be pulled.
+    next_offset: u64,
+    /// Sends the leases of newly pulled messages to the lease task.
+    lease_tx: UnboundedSender<Vec<Lease>>,
+    /// The number of messages that have been pulled but not yet acknowledged.
+    outstanding: Arc<AtomicUsize>,
+}
+
+/// An OffsetCommitter for Pub/Sub, that sends the offset commit frontier to
+/// the lease task, which acknowledges the messages below it.
+pub struct PubSubOffsetCommitter {
+    logger: LogCommitter,
+    /// Sends the offset commit frontier to the lease task, if this worker is
+    /// responsible for reading from the subscription.
+    tx: Option<Sender<HashMap<PartitionId, MzOffset>>>,
+}
+
+impl SourceConnectionBuilder for PubSubSourceConnection {
+    type Reader = PubSubSourceReader;
+    type OffsetCommitter = PubSubOffsetCommitter;
+
+    fn into_reader(
+        self,
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        _consumer_activator: SyncActivator,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: crate::source::metrics::SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+        let next_offset = restored_offsets
+            .into_iter()
+            .filter_map(|(pid, offset)| match pid {
+                PartitionId::None => offset,
+                _ => panic!("unexpected partition id type"),
+            })
+            .map(|offset| offset.offset)
+            .max()
+            .unwrap_or(0);
+
+        let (lease_tx, lease_rx) = mpsc::unbounded_channel();
+        let (offset_tx, offset_rx) = mpsc::channel(10);
+        let outstanding = Arc::new(AtomicUsize::new(0));
+
+        if active_read_worker {
+            task::spawn(
+                || format!("pubsub_lease_manager:{}", source_id),
+                manage_leases(
+                    self.clone(),
+                    Arc::clone(&connection_context.secrets_reader),
+                    lease_rx,
+                    offset_rx,
+                    Arc::clone(&outstanding),
+                ),
+            );
+        }
+
+        Ok((
+            PubSubSourceReader {
+                connection: self,
+                secrets_reader: connection_context.secrets_reader,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+                client: None,
+                stalled: true,
+                buffer: VecDeque::new(),
+                next_offset,
+                lease_tx,
+                outstanding,
+            },
+            PubSubOffsetCommitter {
+                logger: LogCommitter {
+                    source_id,
+                    worker_id,
+                    worker_count,
+                },
+                tx: active_read_worker.then_some(offset_tx),
+            },
+        ))
+    }
+}
+
+impl PubSubSourceReader {
+    fn stalled(
+        &mut self,
+        error: impl std::fmt::Display,
+    ) -> SourceMessageType<Option<Vec<u8>>, Option<Vec<u8>>, ()> {
+        self.stalled = true;
+        SourceMessageType::status(HealthStatus::StalledWithError(format!(
+            "Pub/Sub subscription {}: {}",
+            self.connection.subscription, error
+        )))
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SourceReader for PubSubSourceReader {
+    type Key = Option<Vec<u8>>;
+    type Value = Option<Vec<u8>>;
+    type Time = MzOffset;
+    type Diff = ();
+
+    async fn next(
+        &mut self,
+        timestamp_granularity: Duration,
+    ) -> Option<SourceMessageType<Self::Key, Self::Value, Self::Diff>> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return Some(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ]));
+            }
+            return std::future::pending().await;
+        }
+
+        loop {
+            if let Some(message) = self.buffer.pop_front() {
+                return Some(message);
+            }
+            if self.outstanding.load(Ordering::SeqCst) >= MAX_OUTSTANDING_MESSAGES {
+                sleep(timestamp_granularity).await;
+                continue;
+            }
+
+            if self.client.is_none() {
+                match self
+                    .connection
+                    .connection
+                    .pubsub_client(&*self.secrets_reader)
+                    .await
+                {
+                    Ok(client) => self.client = Some(client),
+                    Err(e) => {
+                        sleep(timestamp_granularity).await;
+                        return Some(self.stalled(e));
+                    }
+                }
+            }
+            let client = self.client.as_mut().expect("client created");
+
+            let messages = match client
+                .pull(&self.connection.subscription, MAX_MESSAGES_PER_PULL)
+                .await
+            {
+                Ok(messages) => messages,
+                Err(e) => {
+                    sleep(timestamp_granularity).await;
+                    return Some(self.stalled(e));
+                }
+            };
+            if self.stalled {
+                self.stalled = false;
+                self.buffer
+                    .push_back(SourceMessageType::status(HealthStatus::Running));
+            }
+
+            let mut leases = Vec::with_capacity(messages.len());
+            for received in messages {
+                let offset = self.next_offset;
+                self.next_offset += 1;
+                let msg = match received.message.payload() {
+                    Ok(payload) => Ok(SourceMessage {
+                        output: 0,
+                        upstream_time_millis: None,
+                        key: Some(received.message.ordering_key.into_bytes()),
+                        value: Some(payload),
+                        headers: None,
+                    }),
+                    Err(e) => Err(SourceReaderError::other_definite(e)),
+                };
+                leases.push((offset, received.ack_id));
+                let ts = (PartitionId::None, MzOffset::from(offset));
+                self.buffer
+                    .push_back(SourceMessageType::Finalized(msg, ts, ()));
+            }
+            if !leases.is_empty() {
+                self.outstanding.fetch_add(leases.len(), Ordering::SeqCst);
+                // The lease task only exits once this sender is dropped.
+                let _ = self.lease_tx.send(leases);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl OffsetCommitter for PubSubOffsetCommitter {
+    async fn commit_offsets(
+        &self,
+        offsets: HashMap<PartitionId, MzOffset>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(tx) = &self.tx {
+            // The lease task may have shut down along with the reader, in
+            // which case there is nothing left to acknowledge.
+            let _ = tx.send(offsets.clone()).await;
+        }
+        self.logger.commit_offsets(offsets).await?;
+
+        Ok(())
+    }
+}
+
+/// Holds the leases on the messages that the source has pulled, extending
+/// their ack deadlines until the offset commit frontier passes them, at which
+/// point it acknowledges them.
+async fn manage_leases(
+    connection: PubSubSourceConnection,
+    secrets_reader: Arc<dyn SecretsReader>,
+    mut lease_rx: UnboundedReceiver<Vec<Lease>>,
+    mut offset_rx: Receiver<HashMap<PartitionId, MzOffset>>,
+    outstanding: Arc<AtomicUsize>,
+) {
+    let subscription = &connection.subscription;
+    let mut client = None;
+    // The leases on messages that are not yet durably recorded, in offset
+    // order.
+    let mut leases: VecDeque<Lease> = VecDeque::new();
+    // The ack IDs of messages that are durably recorded, but that could not be
+    // acknowledged.
+    let mut unacknowledged: Vec<String> = Vec::new();
+    let mut interval = time::interval(ACK_DEADLINE_EXTENSION_INTERVAL);
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+    loop {
+        // The ack IDs on which to act in this iteration.
+        let mut to_extend = Vec::new();
+        tokio::select! {
+            new_leases = lease_rx.recv() => match new_leases {
+                Some(new_leases) => {
+                    // Messages are delivered with the subscription's ack
+                    // deadline, which may be shorter than ours.
+                    to_extend.extend(new_leases.iter().map(|(_, ack_id)| ack_id.clone()));
+                    leases.extend(new_leases);
+                }
+                None => return,
+            },
+            Some(offsets) = offset_rx.recv() => {
+                if let Some(frontier) = offsets.get(&PartitionId::None) {
+                    while let Some((offset, _)) = leases.front() {
+                        if *offset >= frontier.offset {
+                            break;
+                        }
+                        let (_, ack_id) = leases.pop_front().expect("known to exist");
+                        unacknowledged.push(ack_id);
+                    }
+                }
+            }
+            _ = interval.tick() => {
+                to_extend.extend(leases.iter().map(|(_, ack_id)| ack_id.clone()));
+            }
+        }
+
+        if to_extend.is_empty() && unacknowledged.is_empty() {
+            continue;
+        }
+        if client.is_none() {
+            match connection.connection.pubsub_client(&*secrets_reader).await {
+                Ok(c) => client = Some(c),
+                Err(e) => {
+                    warn!("failed to create Pub/Sub client for {subscription}: {e:#}");
+                    continue;
+                }
+            }
+        }
+        let client = client.as_mut().expect("client created");
+
+        for ack_ids in to_extend.chunks(MAX_ACK_IDS_PER_REQUEST) {
+            if let Err(e) = client
+                .modify_ack_deadline(subscription, ack_ids, ACK_DEADLINE)
+                .await
+            {
+                warn!("failed to extend Pub/Sub ack deadlines for {subscription}: {e:#}");
+            }
+        }
+
+        let mut failed = Vec::new();
+        for ack_ids in unacknowledged.chunks(MAX_ACK_IDS_PER_REQUEST) {
+            match client.acknowledge(subscription, ack_ids).await {
+                Ok(()) => {
+                    outstanding.fetch_sub(ack_ids.len(), Ordering::SeqCst);
+                }
+                Err(e) => {
+                    warn!("failed to acknowledge Pub/Sub messages for {subscription}: {e:#}");
+                    failed.extend_from_slice(ack_ids);
+                }
+            }
+        }
+        unacknowledged = failed;
+    }
+}
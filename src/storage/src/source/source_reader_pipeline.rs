@@ -24,7 +24,7 @@
 
 use std::any::Any;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
 use std::rc::Rc;
@@ -41,7 +41,7 @@ use timely::dataflow::channels::pushers::Tee;
 use timely::dataflow::operators::generic::{
     builder_rc::OperatorBuilder, OperatorInfo, OutputHandle,
 };
-use timely::dataflow::operators::{Broadcast, CapabilitySet, Partition};
+use timely::dataflow::operators::{Broadcast, CapabilitySet, Concat, Map, Partition};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::{Antichain, Timestamp as _};
 use timely::PartialOrder;
@@ -62,7 +62,7 @@ use mz_storage_client::source::util::async_source;
 use mz_storage_client::types::connections::ConnectionContext;
 use mz_storage_client::types::errors::SourceError;
 use mz_storage_client::types::sources::encoding::SourceDataEncoding;
-use mz_storage_client::types::sources::{MzOffset, SourceTimestamp, SourceToken};
+use mz_storage_client::types::sources::{MzOffset, SourceRateLimits, SourceTimestamp, SourceToken};
 use mz_storage_client::util::antichain::{MutableOffsetAntichain, OffsetAntichain};
 use mz_timely_util::builder_async::{Event, OperatorBuilder as AsyncOperatorBuilder};
 use mz_timely_util::operator::StreamExt as _;
@@ -110,6 +110,8 @@ pub struct RawSourceCreationConfig {
     pub persist_clients: Arc<Mutex<PersistClientCache>>,
     /// Place to share statistics updates with storage state.
     pub source_statistics: crate::source::statistics::SourceStatistics,
+    /// Limits on how quickly the source reads from its upstream system.
+    pub rate_limits: SourceRateLimits,
 }
 
 /// A batch of messages from a source reader, along with the batch upper, the
@@ -158,6 +160,10 @@ struct BatchUpperSummary {
 /// (<https://github.com/MaterializeInc/materialize/blob/main/doc/developer/design/20210714_reclocking.md>)
 /// happens.
 ///
+/// Operators rendered downstream of the raw source report their health on
+/// `downstream_health`, which is combined with the health of the source
+/// readers.
+///
 /// See the [`source` module docs](crate::source) for more details about how raw
 /// sources are used.
 pub fn create_raw_source<G, C, R>(
@@ -166,6 +172,7 @@ pub fn create_raw_source<G, C, R>(
     source_connection: C,
     connection_context: ConnectionContext,
     calc: R,
+    downstream_health: Stream<G, (usize, HealthStatusUpdate)>,
 ) -> (
     (
         Vec<
@@ -232,7 +239,7 @@ where
         remap_stream,
     );
 
-    let health_token = health_operator(scope, config, health_stream);
+    let health_token = health_operator(scope, config, health_stream, downstream_health);
 
     let token = Rc::new((source_reader_token, remap_token, resume_token, health_token));
 
@@ -267,6 +274,104 @@ struct SourceReaderOperatorOutput<K, V, D> {
     batch_upper: OffsetAntichain,
 }
 
+/// Throttles a source reader to its worker's share of the source's
+/// [`SourceRateLimits`].
+///
+/// Each limit is divided evenly among the workers that actively read from the
+/// source, i.e., the workers that are responsible for at least one of its
+/// partitions. A source with a single reader is thus throttled to the full
+/// limits.
+struct RateLimiter {
+    /// The limits of the source as a whole.
+    limits: SourceRateLimits,
+    /// The id of the source.
+    id: GlobalId,
+    /// The number of workers of the source.
+    worker_count: usize,
+    /// The partitions of the source that this worker knows about.
+    partitions: BTreeSet<PartitionId>,
+    /// The number of workers responsible for at least one of `partitions`.
+    active_readers: u64,
+    /// The start of the current one second window.
+    window_start: tokio::time::Instant,
+    /// The number of messages read in the current window.
+    window_messages: u64,
+    /// The number of bytes read since the last batch was emitted.
+    bytes_in_flight: u64,
+}
+
+impl RateLimiter {
+    fn new(limits: SourceRateLimits, id: GlobalId, worker_count: usize) -> Self {
+        RateLimiter {
+            limits,
+            id,
+            worker_count,
+            partitions: BTreeSet::new(),
+            active_readers: 1,
+            window_start: tokio::time::Instant::now(),
+            window_messages: 0,
+            bytes_in_flight: 0,
+        }
+    }
+
+    /// Records that the source has a partition `pid`, whether or not this
+    /// worker is responsible for it.
+    fn record_partition(&mut self, pid: &PartitionId) {
+        if self.partitions.contains(pid) {
+            return;
+        }
+        self.partitions.insert(pid.clone());
+        let active_readers = (0..self.worker_count)
+            .filter(|worker_id| {
+                self.partitions.iter().any(|pid| {
+                    crate::source::responsible_for(&self.id, *worker_id, self.worker_count, pid)
+                })
+            })
+            .count();
+        self.active_readers = u64::cast_from(active_readers).max(1);
+    }
+
+    /// Returns this worker's share of `limit`, rounded up so that every
+    /// active reader can make progress.
+    fn share(&self, limit: u64) -> u64 {
+        limit / self.active_readers + u64::from(limit % self.active_readers != 0)
+    }
+
+    /// Records that a message of `bytes` bytes was read.
+    fn record_message(&mut self, bytes: u64) {
+        self.window_messages += 1;
+        self.bytes_in_flight += bytes;
+    }
+
+    /// Records that all messages read so far were emitted downstream.
+    fn record_emission(&mut self) {
+        self.bytes_in_flight = 0;
+    }
+
+    /// Returns the instant at which the reader may resume reading, if it has
+    /// read its share of messages for the current window.
+    fn messages_exhausted_until(&mut self) -> Option<tokio::time::Instant> {
+        let max = self.share(self.limits.max_messages_per_second?);
+        let window_end = self.window_start + Duration::from_secs(1);
+        let now = tokio::time::Instant::now();
+        if now >= window_end {
+            self.window_start = now;
+            self.window_messages = 0;
+            None
+        } else if self.window_messages >= max {
+            Some(window_end)
+        } else {
+            None
+        }
+    }
+
+    /// Reports whether the reader must wait for the next emitted batch before
+    /// reading more messages.
+    fn bytes_exhausted(&self) -> bool {
+        matches!(self.limits.max_bytes_in_flight, Some(max) if self.bytes_in_flight >= self.share(max))
+    }
+}
+
 fn build_source_reader_stream<S>(
     source_reader: S,
     config: RawSourceCreationConfig,
@@ -295,6 +400,7 @@ where
         now: _,
         persist_clients: _,
         source_statistics,
+        rate_limits,
     } = config;
     Box::pin(async_stream::stream!({
         // Most recent batch upper frontier, does not regress.
@@ -327,11 +433,17 @@ where
         let mut untimestamped_messages = HashMap::<_, Vec<_>>::new();
         let mut unconsumed_partitions = Vec::new();
         let mut status_update = None;
+        let mut rate_limiter = RateLimiter::new(rate_limits, id, worker_count);
         loop {
+            // Stop reading while the source is over its rate limits. Batches
+            // are still emitted, so that timestamps keep advancing.
+            let resume_at = rate_limiter.messages_exhausted_until();
+            let throttled = resume_at.is_some() || rate_limiter.bytes_exhausted();
+
             // TODO(guswyn): move lots of this out of the macro so rustfmt works better
             tokio::select! {
                 // N.B. This branch is cancel-safe because `next` only borrows the underlying stream.
-                item = source_stream.next() => {
+                item = source_stream.next(), if !throttled => {
                     match item {
                         Some(message) => {
 
@@ -351,11 +463,15 @@ where
                                           {worker_id}/{worker_count}: \
                                           dropping partition capability for: {:?}",
                                           pids);
+                                    for pid in &pids {
+                                        rate_limiter.record_partition(pid);
+                                    }
                                     unconsumed_partitions.append(&mut pids);
                                 }
                                 SourceMessageType::Finalized(message, ts, diff) | SourceMessageType::InProgress(message, ts, diff) => {
                                     let pid = ts.0.clone();
                                     let offset = ts.1;
+                                    rate_limiter.record_partition(&pid);
                                     // Advance the _overall_ frontier of the
                                     // source if this the final message for that
                                     // offset. We know that we won't see more
@@ -381,11 +497,11 @@ where
                                     }
 
                                     if let Ok(message) = &message {
+                                        let bytes = u64::cast_from(message.key.len().unwrap_or(0))
+                                            + u64::cast_from(message.value.len().unwrap_or(0));
                                         source_statistics.inc_messages_received_by(1);
-                                        source_statistics.inc_bytes_received_by(
-                                            u64::cast_from(message.key.len().unwrap_or(0))
-                                            + u64::cast_from(message.value.len().unwrap_or(0))
-                                        );
+                                        source_statistics.inc_bytes_received_by(bytes);
+                                        rate_limiter.record_message(bytes);
                                     }
 
                                     untimestamped_messages.entry(pid).or_default().push(((message, ts, diff), offset));
@@ -416,6 +532,8 @@ where
                         },
                     }
                 }
+                // The source read its share of messages for this second.
+                _ = tokio::time::sleep_until(resume_at.unwrap_or_else(tokio::time::Instant::now)), if resume_at.is_some() => {}
                 // It's time to emit a batch of messages
                 _ = emission_interval.tick() => {
 
@@ -444,6 +562,7 @@ where
                             batch_upper: batch_upper.clone(),
                         }
                     );
+                    rate_limiter.record_emission();
                 }
             }
         }
@@ -526,6 +645,7 @@ where
         now: now_fn,
         persist_clients: _,
         source_statistics: _,
+        rate_limits: _,
     } = config;
 
     let (stream, capability) = async_source(
@@ -868,6 +988,7 @@ fn health_operator<G>(
     scope: &G,
     config: RawSourceCreationConfig,
     health_stream: Stream<G, (usize, HealthStatusUpdate)>,
+    downstream_health: Stream<G, (usize, HealthStatusUpdate)>,
 ) -> Rc<dyn Any>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -887,7 +1008,14 @@ where
     let chosen_worker_id = usize::cast_from(source_id.hashed()) % worker_count;
     let is_active_worker = chosen_worker_id == healthcheck_worker_id;
 
+    // The health of the source readers and of the operators downstream of them
+    // is tracked separately, so that they don't overwrite each other's status
+    // on the same worker.
+    let health_stream = health_stream
+        .map(|(worker_id, update)| (false, worker_id, update))
+        .concat(&downstream_health.map(|(worker_id, update)| (true, worker_id, update)));
     let mut healths = vec![HealthStatus::Starting; worker_count];
+    let mut downstream_healths = vec![HealthStatus::Starting; worker_count];
 
     let operator_name = format!("healthcheck({})", healthcheck_worker_id);
     let mut health_op = AsyncOperatorBuilder::new(operator_name, scope.clone());
@@ -897,11 +1025,18 @@ where
         Exchange::new(move |_| u64::cast_from(chosen_worker_id)),
     );
 
-    fn overall_status(healths: &[HealthStatus]) -> &HealthStatus {
-        healths.iter().max().unwrap_or(&HealthStatus::Starting)
+    fn overall_status<'a>(
+        healths: &'a [HealthStatus],
+        downstream_healths: &'a [HealthStatus],
+    ) -> &'a HealthStatus {
+        healths
+            .iter()
+            .chain(downstream_healths)
+            .max()
+            .unwrap_or(&HealthStatus::Starting)
     }
 
-    let mut last_reported_status = overall_status(&healths).clone();
+    let mut last_reported_status = overall_status(&healths, &downstream_healths).clone();
 
     let button = health_op.build(move |mut _capabilities| async move {
         let mut buffer = Vec::new();
@@ -929,7 +1064,7 @@ where
             if let Event::Data(_cap, rows) = event {
                 rows.swap(&mut buffer);
                 let mut halt_with = None;
-                for (worker_id, health_event) in buffer.drain(..) {
+                for (downstream, worker_id, health_event) in buffer.drain(..) {
                     if !is_active_worker {
                         warn!("Health messages for source {source_id} passed to an unexpected worker id: {healthcheck_worker_id}")
                     }
@@ -938,10 +1073,14 @@ where
                     if should_halt {
                         halt_with = Some(update.clone());
                     }
-                    healths[worker_id] = update;
+                    if downstream {
+                        downstream_healths[worker_id] = update;
+                    } else {
+                        healths[worker_id] = update;
+                    }
                 }
 
-                let new_status = overall_status(&healths);
+                let new_status = overall_status(&healths, &downstream_healths);
                 if &last_reported_status != new_status {
                     info!("Health transition for source {source_id}: {last_reported_status:?} -> {new_status:?}");
                     if let Some(status_shard) = storage_metadata.status_shard {
@@ -1038,6 +1177,7 @@ where
         now,
         persist_clients,
         source_statistics: _,
+        rate_limits: _,
     } = config;
 
     let chosen_worker = usize::cast_from(id.hashed() % u64::cast_from(worker_count));
@@ -1266,6 +1406,7 @@ where
         now: _,
         persist_clients: _,
         source_statistics: _,
+        rate_limits: _,
     } = config;
 
     let bytes_read_counter = base_metrics.bytes_read.clone();
@@ -1679,3 +1820,33 @@ fn derive_new_compaction_since(
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_share() {
+        let limits = SourceRateLimits {
+            max_messages_per_second: Some(100),
+            max_bytes_in_flight: None,
+        };
+        let id = GlobalId::User(1);
+
+        // A source with a single reader gets the full limit, no matter how
+        // many workers there are.
+        let mut limiter = RateLimiter::new(limits.clone(), id, 4);
+        limiter.record_partition(&PartitionId::None);
+        assert_eq!(limiter.share(100), 100);
+
+        // Partitioned sources divide the limit among the workers responsible
+        // for at least one partition.
+        let mut limiter = RateLimiter::new(limits, id, 4);
+        for pid in 0..256 {
+            limiter.record_partition(&PartitionId::Kafka(pid));
+        }
+        assert_eq!(limiter.active_readers, 4);
+        assert_eq!(limiter.share(100), 25);
+        assert_eq!(limiter.share(101), 26);
+    }
+}
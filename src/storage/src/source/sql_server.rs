@@ -0,0 +1,947 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A source that ingests tables from a SQL Server database using its change
+//! data capture (CDC) feature.
+//!
+//! SQL Server's capture job, which runs as part of the SQL Server Agent, copies
+//! the changes to each table with CDC enabled from the transaction log into a
+//! change table. The source takes a consistent snapshot of the tables it
+//! ingests and then periodically polls their change tables for changes that
+//! committed after the snapshot.
+//!
+//! Offsets are log sequence numbers (LSNs) of the upstream database. An LSN is
+//! a 10 byte value made up of a 4 byte virtual log file (VLF) sequence number,
+//! a 4 byte block offset and a 2 byte slot number, which the source packs into
+//! a 64 bit offset by keeping the low 24 bits of the VLF sequence number and
+//! of the block offset. The source errors if it encounters an LSN that does
+//! not fit, which in practice requires a database whose transaction log has
+//! cycled through more than 16 million VLFs.
+
+use std::collections::HashMap;
+use std::future;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use futures::{FutureExt, StreamExt};
+use tiberius::Client;
+use timely::scheduling::SyncActivator;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio_util::compat::Compat;
+use tracing::{info, warn};
+
+use mz_expr::{MirScalarExpr, PartitionId};
+use mz_ore::display::DisplayExt;
+use mz_ore::task;
+use mz_repr::{Datum, DatumVec, Diff, GlobalId, Row};
+use mz_storage_client::types::connections::{ConnectionContext, SqlServerConnection};
+use mz_storage_client::types::errors::SourceErrorDetails;
+use mz_storage_client::types::sources::sql_server::{SqlServerColumnDesc, SqlServerTableDesc};
+use mz_storage_client::types::sources::{
+    encoding::SourceDataEncoding, MzOffset, SqlServerSourceConnection,
+};
+
+use super::metrics::SourceBaseMetrics;
+use crate::source::commit::LogCommitter;
+use crate::source::source_reader_pipeline::HealthStatus;
+use crate::source::types::{HealthStatusUpdate, SourceConnectionBuilder};
+use crate::source::{
+    NextMessage, SourceMessage, SourceMessageType, SourceReader, SourceReaderError,
+};
+
+/// How often the source polls the change tables for new changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the snapshot waits for the capture job to process the changes
+/// that committed before the snapshot started.
+const CAPTURE_TIMEOUT_SECS: u32 = 60;
+
+/// The `__$operation` of a change that deletes a row.
+const OPERATION_DELETE: i32 = 1;
+/// The `__$operation` of a change that inserts a row.
+const OPERATION_INSERT: i32 = 2;
+/// The `__$operation` of a change that records a row's values before an
+/// update.
+const OPERATION_UPDATE_BEFORE: i32 = 3;
+/// The `__$operation` of a change that records a row's values after an
+/// update.
+const OPERATION_UPDATE_AFTER: i32 = 4;
+
+enum ReplicationError {
+    /// This error is definite: this source is permanently wedged.
+    /// Returning a definite error will cause the collection to become un-queryable.
+    Definite(anyhow::Error),
+    /// This error may or may not resolve itself in the future, and
+    /// should be retried instead of being added to the output.
+    Indefinite(anyhow::Error),
+}
+
+macro_rules! try_definite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Definite(err.into())),
+        }
+    };
+}
+macro_rules! try_indefinite {
+    ($expr:expr $(,)?) => {
+        match $expr {
+            Ok(val) => val,
+            Err(err) => return Err(ReplicationError::Indefinite(err.into())),
+        }
+    };
+}
+
+// Message used to communicate between `get_next_message` and the tokio task
+enum InternalMessage {
+    Err(SourceReaderError),
+    Status(HealthStatusUpdate),
+    Value {
+        output: usize,
+        value: Row,
+        lsn: u64,
+        diff: Diff,
+        end: bool,
+    },
+}
+
+/// Information required to sync data from SQL Server
+pub struct SqlServerSourceReader {
+    receiver_stream: Receiver<InternalMessage>,
+
+    // SQL Server sources support single-threaded ingestion only, so only one
+    // of the `SqlServerSourceReader`s will actually produce data.
+    active_read_worker: bool,
+
+    // The non-active reader (see above `active_read_worker`) has to report back
+    // that is is not consuming from the one [`PartitionId:None`] partition.
+    // Before it can return a [`NextMessage::Finished`]. This is keeping track
+    // of that.
+    reported_unconsumed_partitions: bool,
+
+    /// The LSN we last emitted data at. Used to fabricate timestamps for errors.
+    last_lsn: u64,
+}
+
+/// Information about an ingested upstream table
+struct SourceTable {
+    /// The source output index of this table
+    output_index: usize,
+    /// The description of this table
+    desc: SqlServerTableDesc,
+    /// The scalar expressions required to cast the text encoded columns into
+    /// the target relational types
+    casts: Vec<MirScalarExpr>,
+}
+
+/// A change read from a change table.
+struct Change {
+    lsn: u64,
+    /// The sequence number of the change within its transaction.
+    seqval: Vec<u8>,
+    output_index: usize,
+    row: Row,
+    diff: Diff,
+}
+
+/// An internal struct held by the spawned tokio task
+struct SqlServerTaskInfo {
+    source_id: GlobalId,
+    connection: SqlServerConnection,
+    connection_context: ConnectionContext,
+    /// Whether the initial snapshot has yet to be emitted.
+    needs_snapshot: bool,
+    /// The LSN up to which all changes have been emitted.
+    lsn: u64,
+    /// The ingested tables, keyed by the name of their capture instance.
+    source_tables: HashMap<String, SourceTable>,
+    row_sender: RowSender,
+    sender: Sender<InternalMessage>,
+}
+
+impl SourceConnectionBuilder for SqlServerSourceConnection {
+    type Reader = SqlServerSourceReader;
+    type OffsetCommitter = LogCommitter;
+
+    fn into_reader(
+        self,
+        _source_name: String,
+        source_id: GlobalId,
+        worker_id: usize,
+        worker_count: usize,
+        consumer_activator: SyncActivator,
+        start_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _encoding: SourceDataEncoding,
+        _metrics: SourceBaseMetrics,
+        connection_context: ConnectionContext,
+    ) -> Result<(Self::Reader, Self::OffsetCommitter), anyhow::Error> {
+        let active_read_worker =
+            crate::source::responsible_for(&source_id, worker_id, worker_count, &PartitionId::None);
+
+        let (dataflow_tx, dataflow_rx) = tokio::sync::mpsc::channel(50_000);
+
+        // The start offset is the upper of the data already ingested, i.e.
+        // one past the LSN of the last emitted transaction.
+        let start_offset = start_offsets
+            .into_iter()
+            .find_map(|(pid, offset)| {
+                if pid == PartitionId::None {
+                    offset
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let lsn = start_offset.offset.saturating_sub(1);
+
+        if active_read_worker {
+            let mut source_tables = HashMap::new();
+            for (i, desc) in self.details.tables.iter().enumerate() {
+                let output_index = i + 1;
+                // Only tables referenced by the source have casts.
+                if let Some(casts) = self.table_casts.get(&output_index) {
+                    source_tables.insert(
+                        desc.capture_instance.clone(),
+                        SourceTable {
+                            output_index,
+                            desc: desc.clone(),
+                            casts: casts.to_vec(),
+                        },
+                    );
+                }
+            }
+
+            let task_info = SqlServerTaskInfo {
+                source_id,
+                connection: self.connection,
+                connection_context,
+                needs_snapshot: start_offset.offset == 0,
+                lsn,
+                source_tables,
+                row_sender: RowSender::new(dataflow_tx.clone(), consumer_activator),
+                sender: dataflow_tx,
+            };
+
+            task::spawn(
+                || format!("sql_server_source:{}", source_id),
+                sql_server_replication_loop(task_info),
+            );
+        }
+
+        Ok((
+            SqlServerSourceReader {
+                receiver_stream: dataflow_rx,
+                active_read_worker,
+                reported_unconsumed_partitions: false,
+                last_lsn: lsn,
+            },
+            LogCommitter {
+                source_id,
+                worker_id,
+                worker_count,
+            },
+        ))
+    }
+}
+
+impl SourceReader for SqlServerSourceReader {
+    type Key = ();
+    type Value = Row;
+    // SQL Server can produce deletes that cause retractions
+    type Time = MzOffset;
+    type Diff = Diff;
+
+    fn get_next_message(&mut self) -> NextMessage<Self::Key, Self::Value, Self::Diff> {
+        if !self.active_read_worker {
+            if !self.reported_unconsumed_partitions {
+                self.reported_unconsumed_partitions = true;
+                return NextMessage::Ready(SourceMessageType::DropPartitionCapabilities(vec![
+                    PartitionId::None,
+                ]));
+            }
+            return NextMessage::Finished;
+        }
+
+        match self.receiver_stream.recv().now_or_never() {
+            Some(Some(InternalMessage::Value {
+                output,
+                value,
+                lsn,
+                diff,
+                end,
+            })) => {
+                self.last_lsn = lsn;
+                let msg = SourceMessage {
+                    output,
+                    upstream_time_millis: None,
+                    key: (),
+                    value,
+                    headers: None,
+                };
+                let ts = (PartitionId::None, MzOffset::from(lsn));
+                if end {
+                    NextMessage::Ready(SourceMessageType::Finalized(Ok(msg), ts, diff))
+                } else {
+                    NextMessage::Ready(SourceMessageType::InProgress(Ok(msg), ts, diff))
+                }
+            }
+            Some(Some(InternalMessage::Status(update))) => {
+                NextMessage::Ready(SourceMessageType::SourceStatus(update))
+            }
+            Some(Some(InternalMessage::Err(err))) => {
+                // XXX(petrosagg): we are fabricating a timestamp here!!
+                let non_definite_ts = (PartitionId::None, MzOffset::from(self.last_lsn + 1));
+                NextMessage::Ready(SourceMessageType::Finalized(Err(err), non_definite_ts, 1))
+            }
+            None => NextMessage::Pending,
+            Some(None) => NextMessage::Finished,
+        }
+    }
+}
+
+/// Defers to `sql_server_replication_loop_inner` and sends errors through the
+/// channel if they occur
+async fn sql_server_replication_loop(mut task_info: SqlServerTaskInfo) {
+    match sql_server_replication_loop_inner(&mut task_info).await {
+        Ok(()) => {}
+        Err(e) => {
+            // Drop the send error, as we have no way of communicating back to the
+            // source operator if the channel is gone.
+            let _ = task_info
+                .row_sender
+                .sender
+                .send(InternalMessage::Err(e))
+                .await;
+            task_info
+                .row_sender
+                .activator
+                .activate()
+                .expect("sql server reader activation failed");
+        }
+    }
+}
+
+/// Core logic
+async fn sql_server_replication_loop_inner(
+    task_info: &mut SqlServerTaskInfo,
+) -> Result<(), SourceReaderError> {
+    if task_info.needs_snapshot {
+        match task_info.produce_snapshot().await {
+            Ok(_) => {
+                info!(
+                    "replication snapshot for source {} succeeded",
+                    &task_info.source_id
+                );
+            }
+            Err(ReplicationError::Indefinite(e)) => {
+                warn!(
+                    "replication snapshot for source {} failed: {}",
+                    &task_info.source_id, e
+                );
+                // If the channel is shutting down, so is the source. As with
+                // Postgres sources, halting discards any partially emitted
+                // snapshot, which the restarted process takes afresh.
+                let _ = task_info
+                    .sender
+                    .send(InternalMessage::Status(HealthStatusUpdate {
+                        update: HealthStatus::StalledWithError(e.to_string_alt()),
+                        should_halt: true,
+                    }))
+                    .await;
+
+                future::pending().await
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Initialization(e.to_string()),
+                })
+            }
+        }
+    }
+
+    loop {
+        match task_info.produce_replication().await {
+            Err(ReplicationError::Indefinite(e)) => {
+                // If the channel is shutting down, so is the source.
+                let _ = task_info
+                    .sender
+                    .send(InternalMessage::Status(HealthStatusUpdate {
+                        update: HealthStatus::StalledWithError(e.to_string()),
+                        should_halt: false,
+                    }))
+                    .await;
+                warn!(
+                    "replication for source {} interrupted, retrying: {}",
+                    task_info.source_id, e
+                );
+            }
+            Err(ReplicationError::Definite(e)) => {
+                return Err(SourceReaderError {
+                    inner: SourceErrorDetails::Other(e.to_string()),
+                })
+            }
+            Ok(_) => {
+                // shutdown initiated elsewhere
+                return Ok(());
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        info!("resuming replication for source {}", task_info.source_id);
+    }
+}
+
+struct RowMessage {
+    output_index: usize,
+    row: Row,
+    lsn: u64,
+    diff: i64,
+}
+
+/// A type that makes it easy to correctly send inserts and deletes.
+///
+/// Note: `RowSender::delete/insert` should be called with the same LSN until
+/// `close_lsn` is called, which should be called and awaited before dropping
+/// the `RowSender` or moving onto a new LSN.
+struct RowSender {
+    sender: Sender<InternalMessage>,
+    activator: SyncActivator,
+    buffered_message: Option<RowMessage>,
+}
+
+impl RowSender {
+    /// Create a new `RowSender`.
+    pub fn new(sender: Sender<InternalMessage>, activator: SyncActivator) -> Self {
+        Self {
+            sender,
+            activator,
+            buffered_message: None,
+        }
+    }
+
+    /// Insert a row at an LSN.
+    pub async fn insert(&mut self, output_index: usize, row: Row, lsn: u64) {
+        self.push(output_index, row, lsn, 1).await
+    }
+
+    /// Delete a row at an LSN.
+    pub async fn delete(&mut self, output_index: usize, row: Row, lsn: u64) {
+        self.push(output_index, row, lsn, -1).await
+    }
+
+    async fn push(&mut self, output_index: usize, row: Row, lsn: u64, diff: i64) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.lsn, lsn);
+            self.send_row(buffered, false).await;
+        }
+        self.buffered_message = Some(RowMessage {
+            output_index,
+            row,
+            lsn,
+            diff,
+        });
+    }
+
+    /// Finalize an LSN, making sure all messages that may be buffered are
+    /// flushed, and that the last message sent is marked as closing the LSN.
+    pub async fn close_lsn(&mut self, lsn: u64) {
+        if let Some(buffered) = self.buffered_message.take() {
+            assert_eq!(buffered.lsn, lsn);
+            self.send_row(buffered, true).await;
+        }
+    }
+
+    async fn send_row(&self, message: RowMessage, end: bool) {
+        // a closed receiver means the source has been shutdown
+        // (dropped or the process is dying), so just continue on
+        // without activation
+        if let Ok(_) = self
+            .sender
+            .send(InternalMessage::Value {
+                output: message.output_index,
+                value: message.row,
+                lsn: message.lsn,
+                diff: message.diff,
+                end,
+            })
+            .await
+        {
+            self.activator
+                .activate()
+                .expect("sql server reader activation failed");
+        }
+    }
+}
+
+impl SqlServerTaskInfo {
+    async fn connect(&self) -> Result<Client<Compat<TcpStream>>, anyhow::Error> {
+        self.connection
+            .connect(&*self.connection_context.secrets_reader)
+            .await
+    }
+
+    /// Produces the initial snapshot of the ingested tables.
+    ///
+    /// The snapshot holds shared locks on the tables, which block writers,
+    /// for its duration. Once it holds the locks, it waits for the capture job
+    /// to process the transactions that committed before, so that the highest
+    /// LSN in the change tables reflects exactly the snapshotted data.
+    async fn produce_snapshot(&mut self) -> Result<(), ReplicationError> {
+        let mut client = try_indefinite!(self.connect().await);
+
+        // Validate that the tables still have the schema the source expects.
+        let tables = try_indefinite!(
+            mz_storage_client::types::sources::sql_server::table_info(&mut client).await
+        );
+        try_definite!(self.validate_tables(tables));
+
+        let mut batch = String::from("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE;\n");
+        batch.push_str("BEGIN TRANSACTION;\n");
+        for info in self.source_tables.values() {
+            batch.push_str(&format!(
+                "SELECT TOP 0 1 FROM {}.{} WITH (TABLOCK, HOLDLOCK);\n",
+                quote_identifier(&info.desc.schema_name),
+                quote_identifier(&info.desc.name)
+            ));
+        }
+        // The capture job records the commit time of each transaction it
+        // processes, and periodically records its progress while there are no
+        // changes to capture.
+        batch.push_str(&format!(
+            "DECLARE @locked datetime = GETDATE();
+            WHILE ISNULL(
+                sys.fn_cdc_map_time_to_lsn('smallest greater than or equal', @locked),
+                0x00000000000000000000
+            ) = 0x00000000000000000000
+            BEGIN
+                IF DATEDIFF(second, @locked, GETDATE()) > {}
+                    THROW 50000, 'timed out waiting for the change data capture job; \
+            check that the SQL Server Agent is running', 1;
+                WAITFOR DELAY '00:00:01';
+            END;
+            SELECT sys.fn_cdc_get_max_lsn();",
+            CAPTURE_TIMEOUT_SECS
+        ));
+        // The statements that take the locks return empty results, so the
+        // maximum LSN is in the last result.
+        let results = try_indefinite!(
+            try_indefinite!(client.simple_query(batch).await)
+                .into_results()
+                .await
+        );
+        let max_lsn = try_indefinite!(results
+            .last()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.get::<&[u8], _>(0))
+            .ok_or_else(|| anyhow!("unable to read the maximum LSN from SQL Server")));
+        let lsn = try_definite!(lsn_to_offset(max_lsn));
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        for info in self.source_tables.values() {
+            let query = format!(
+                "SELECT {} FROM {}.{}",
+                info.select_list(),
+                quote_identifier(&info.desc.schema_name),
+                quote_identifier(&info.desc.name)
+            );
+            let mut stream = try_indefinite!(client.simple_query(query).await).into_row_stream();
+            while let Some(row) = stream.next().await {
+                let row = try_indefinite!(row);
+                let row = try_definite!(info.decode_row(&row, 0, &mut datum_vec));
+                self.row_sender.insert(info.output_index, row, lsn).await;
+            }
+        }
+        try_indefinite!(
+            try_indefinite!(client.simple_query("COMMIT").await)
+                .into_results()
+                .await
+        );
+        let _ = client.close().await;
+
+        // close the current `row_sender` context after we are sure we have not errored
+        // out (in the commit).
+        self.row_sender.close_lsn(lsn).await;
+        self.lsn = lsn;
+        self.needs_snapshot = false;
+        Ok(())
+    }
+
+    /// Validates that all ingested tables still have the same capture
+    /// instance and schema upstream.
+    fn validate_tables(&self, tables: Vec<SqlServerTableDesc>) -> Result<(), anyhow::Error> {
+        let tables: HashMap<_, _> = tables
+            .into_iter()
+            .map(|t| (t.capture_instance.clone(), t))
+            .collect();
+        for (capture_instance, info) in self.source_tables.iter() {
+            match tables.get(capture_instance) {
+                Some(desc) if desc == &info.desc => {}
+                Some(_) => bail!(
+                    "Schema for table {}.{} differs, recreate Materialize source to use new schema",
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+                None => bail!(
+                    "SQL Server is missing expected capture instance {} for table {}.{}",
+                    capture_instance,
+                    info.desc.schema_name,
+                    info.desc.name
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Polls the change tables for changes that committed after the current
+    /// LSN, emitting them in commit order.
+    async fn produce_replication(&mut self) -> Result<(), ReplicationError> {
+        let mut client = try_indefinite!(self.connect().await);
+
+        // Scratch space to use while evaluating casts
+        let mut datum_vec = DatumVec::new();
+
+        loop {
+            let row = try_indefinite!(
+                try_indefinite!(client.simple_query("SELECT sys.fn_cdc_get_max_lsn()").await)
+                    .into_row()
+                    .await
+            );
+            let max_lsn = match row.as_ref().and_then(|row| row.get::<&[u8], _>(0)) {
+                Some(max_lsn) => try_definite!(lsn_to_offset(max_lsn)),
+                None => 0,
+            };
+            if max_lsn <= self.lsn {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+            let from_lsn = offset_to_lsn(self.lsn);
+            let to_lsn = offset_to_lsn(max_lsn);
+
+            let mut changes = vec![];
+            for (capture_instance, info) in self.source_tables.iter() {
+                // The changes the source still needs must not have been
+                // removed from the change table by the cleanup job.
+                let row = try_indefinite!(
+                    try_indefinite!(
+                        client
+                            .query("SELECT sys.fn_cdc_get_min_lsn(@P1)", &[capture_instance])
+                            .await
+                    )
+                    .into_row()
+                    .await
+                );
+                let min_lsn = match row.as_ref().and_then(|row| row.get::<&[u8], _>(0)) {
+                    Some(min_lsn) => try_definite!(lsn_to_offset(min_lsn)),
+                    None => 0,
+                };
+                if min_lsn == 0 {
+                    return Err(ReplicationError::Definite(anyhow!(
+                        "SQL Server capture instance {} for table {}.{} no longer exists",
+                        capture_instance,
+                        info.desc.schema_name,
+                        info.desc.name
+                    )));
+                }
+                if min_lsn > self.lsn {
+                    return Err(ReplicationError::Definite(anyhow!(
+                        "SQL Server has removed changes to table {}.{} up to LSN {}, \
+                        but the source has only ingested up to {}",
+                        info.desc.schema_name,
+                        info.desc.name,
+                        min_lsn,
+                        self.lsn
+                    )));
+                }
+
+                let query = format!(
+                    "DECLARE @from binary(10) = sys.fn_cdc_increment_lsn(@P1);
+                    SELECT __$start_lsn, __$seqval, __$operation, {}
+                    FROM cdc.{}(@from, @P2, N'all')
+                    ORDER BY __$start_lsn, __$seqval",
+                    info.select_list(),
+                    quote_identifier(&format!("fn_cdc_get_all_changes_{}", capture_instance))
+                );
+                let mut stream = try_indefinite!(client.query(query, &[&from_lsn, &to_lsn]).await)
+                    .into_row_stream();
+                while let Some(row) = stream.next().await {
+                    let row = try_indefinite!(row);
+                    let lsn = try_definite!(try_definite!(row.try_get::<&[u8], _>(0))
+                        .ok_or_else(|| anyhow!("change without an LSN")));
+                    let lsn = try_definite!(lsn_to_offset(lsn));
+                    let seqval = try_definite!(row.try_get::<&[u8], _>(1)).unwrap_or_default();
+                    let diff = match try_definite!(row.try_get::<i32, _>(2)) {
+                        Some(OPERATION_DELETE | OPERATION_UPDATE_BEFORE) => -1,
+                        Some(OPERATION_INSERT | OPERATION_UPDATE_AFTER) => 1,
+                        operation => {
+                            return Err(ReplicationError::Definite(anyhow!(
+                                "unexpected change operation {:?}",
+                                operation
+                            )))
+                        }
+                    };
+                    let row = try_definite!(info.decode_row(&row, 3, &mut datum_vec));
+                    changes.push(Change {
+                        lsn,
+                        seqval: seqval.to_vec(),
+                        output_index: info.output_index,
+                        row,
+                        diff,
+                    });
+                }
+            }
+
+            // Interleave the changes to all tables in the order in which they
+            // were made.
+            changes.sort_by(|a, b| (a.lsn, &a.seqval).cmp(&(b.lsn, &b.seqval)));
+            let mut current_lsn = None;
+            for change in changes {
+                if let Some(lsn) = current_lsn {
+                    if lsn != change.lsn {
+                        self.row_sender.close_lsn(lsn).await;
+                    }
+                }
+                current_lsn = Some(change.lsn);
+                if change.diff < 0 {
+                    self.row_sender
+                        .delete(change.output_index, change.row, change.lsn)
+                        .await;
+                } else {
+                    self.row_sender
+                        .insert(change.output_index, change.row, change.lsn)
+                        .await;
+                }
+            }
+            if let Some(lsn) = current_lsn {
+                self.row_sender.close_lsn(lsn).await;
+            }
+            self.lsn = max_lsn;
+        }
+    }
+}
+
+impl SourceTable {
+    /// Returns the list of expressions that select the table's columns as text.
+    fn select_list(&self) -> String {
+        self.desc
+            .columns
+            .iter()
+            .map(text_expression)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Decodes the text encoded columns of an upstream row, starting at column
+    /// `offset`, and casts them to the target types.
+    fn decode_row(
+        &self,
+        row: &tiberius::Row,
+        offset: usize,
+        datum_vec: &mut DatumVec,
+    ) -> Result<Row, anyhow::Error> {
+        let mut texts = Vec::with_capacity(self.desc.columns.len());
+        for (i, column) in self.desc.columns.iter().enumerate() {
+            let text = row.try_get::<&str, _>(offset + i)?;
+            texts.push(match text {
+                Some(text) if is_binary(column) => Some(format!("\\x{}", text)),
+                Some(text) => Some(text.to_owned()),
+                None => None,
+            });
+        }
+
+        let mut datums = datum_vec.borrow();
+        datums.extend(texts.iter().map(|text| match text {
+            Some(text) => Datum::String(text),
+            None => Datum::Null,
+        }));
+
+        let arena = mz_repr::RowArena::new();
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        for column_cast in &self.casts {
+            let datum = column_cast.eval(&datums, &arena)?;
+            packer.push(datum);
+        }
+        Ok(row)
+    }
+}
+
+/// Quotes a SQL Server identifier.
+fn quote_identifier(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
+
+/// Reports whether the column holds binary data, which is selected as hex.
+fn is_binary(column: &SqlServerColumnDesc) -> bool {
+    matches!(
+        column.data_type.as_str(),
+        "binary" | "varbinary" | "image" | "timestamp"
+    )
+}
+
+/// Returns an expression that converts the column into the text encoding
+/// from which the source casts it to its target type.
+fn text_expression(column: &SqlServerColumnDesc) -> String {
+    let name = quote_identifier(&column.name);
+    match column.data_type.as_str() {
+        _ if is_binary(column) => format!(
+            "CONVERT(varchar(max), CONVERT(varbinary(max), {}), 2)",
+            name
+        ),
+        "date" => format!("CONVERT(varchar(10), {}, 23)", name),
+        // ODBC canonical style, e.g. `2023-01-31 12:34:56.1234567`.
+        "datetime" | "datetime2" | "smalldatetime" | "datetimeoffset" => {
+            format!("CONVERT(varchar(40), {}, 121)", name)
+        }
+        // Style 3 prints as many digits as required to round trip the value.
+        "float" | "real" => format!("CONVERT(varchar(30), {}, 3)", name),
+        // Style 2 prints all four decimal places, without thousands separators.
+        "money" | "smallmoney" => format!("CONVERT(varchar(30), {}, 2)", name),
+        _ => format!("CAST({} AS nvarchar(max))", name),
+    }
+}
+
+/// Packs a 10 byte LSN into an offset.
+fn lsn_to_offset(lsn: &[u8]) -> Result<u64, anyhow::Error> {
+    let lsn: [u8; 10] = lsn
+        .try_into()
+        .map_err(|_| anyhow!("invalid LSN of {} bytes", lsn.len()))?;
+    let vlf = u32::from_be_bytes([lsn[0], lsn[1], lsn[2], lsn[3]]);
+    let block = u32::from_be_bytes([lsn[4], lsn[5], lsn[6], lsn[7]]);
+    let slot = u16::from_be_bytes([lsn[8], lsn[9]]);
+    if vlf >= 1 << 24 || block >= 1 << 24 {
+        bail!(
+            "LSN {} cannot be represented as an offset",
+            hex::encode(lsn)
+        );
+    }
+    Ok(u64::from(vlf) << 40 | u64::from(block) << 16 | u64::from(slot))
+}
+
+/// Unpacks an offset produced by [`lsn_to_offset`] into a 10 byte LSN.
+fn offset_to_lsn(offset: u64) -> Vec<u8> {
+    let vlf = u32::try_from(offset >> 40).expect("24 bit value");
+    let block = u32::try_from((offset >> 16) & 0xff_ffff).expect("24 bit value");
+    let slot = u16::try_from(offset & 0xffff).expect("16 bit value");
+    let mut lsn = Vec::with_capacity(10);
+    lsn.extend(vlf.to_be_bytes());
+    lsn.extend(block.to_be_bytes());
+    lsn.extend(slot.to_be_bytes());
+    lsn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str) -> SqlServerColumnDesc {
+        SqlServerColumnDesc {
+            name: name.into(),
+            data_type: data_type.into(),
+            nullable: true,
+        }
+    }
+
+    #[test]
+    fn test_lsn_to_offset() {
+        let lsn = hex::decode("0000002a000001f00003").unwrap();
+        let offset = lsn_to_offset(&lsn).unwrap();
+        assert_eq!(offset, 0x2a << 40 | 0x1f0 << 16 | 0x3);
+        assert_eq!(offset_to_lsn(offset), lsn);
+
+        assert_eq!(lsn_to_offset(&[0; 10]).unwrap(), 0);
+        let max = hex::decode("00ffffff00ffffffffff").unwrap();
+        assert_eq!(lsn_to_offset(&max).unwrap(), u64::MAX);
+        assert_eq!(offset_to_lsn(u64::MAX), max);
+    }
+
+    #[test]
+    fn test_lsn_to_offset_preserves_order() {
+        // LSNs compare as big endian byte strings, and so must their offsets.
+        let lsns = [
+            "00000001000000010001",
+            "00000001000000010002",
+            "00000001000000020000",
+            "0000000100ffffffffff",
+            "00000002000000000000",
+            "00000002000000000001",
+        ];
+        let offsets = lsns
+            .iter()
+            .map(|lsn| lsn_to_offset(&hex::decode(lsn).unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        for pair in offsets.windows(2) {
+            assert!(pair[0] < pair[1], "{:?}", offsets);
+        }
+    }
+
+    #[test]
+    fn test_lsn_to_offset_errors() {
+        assert_eq!(
+            lsn_to_offset(&[0; 9]).unwrap_err().to_string(),
+            "invalid LSN of 9 bytes"
+        );
+        assert_eq!(
+            lsn_to_offset(&hex::decode("01000000000000000000").unwrap())
+                .unwrap_err()
+                .to_string(),
+            "LSN 01000000000000000000 cannot be represented as an offset"
+        );
+        assert_eq!(
+            lsn_to_offset(&hex::decode("00000000010000000000").unwrap())
+                .unwrap_err()
+                .to_string(),
+            "LSN 00000000010000000000 cannot be represented as an offset"
+        );
+    }
+
+    #[test]
+    fn test_text_expression() {
+        let cases = [
+            ("int", "CAST([c] AS nvarchar(max))"),
+            ("nvarchar", "CAST([c] AS nvarchar(max))"),
+            ("uniqueidentifier", "CAST([c] AS nvarchar(max))"),
+            (
+                "varbinary",
+                "CONVERT(varchar(max), CONVERT(varbinary(max), [c]), 2)",
+            ),
+            (
+                "timestamp",
+                "CONVERT(varchar(max), CONVERT(varbinary(max), [c]), 2)",
+            ),
+            ("date", "CONVERT(varchar(10), [c], 23)"),
+            ("datetime2", "CONVERT(varchar(40), [c], 121)"),
+            ("datetimeoffset", "CONVERT(varchar(40), [c], 121)"),
+            ("float", "CONVERT(varchar(30), [c], 3)"),
+            ("money", "CONVERT(varchar(30), [c], 2)"),
+        ];
+        for (data_type, expected) in cases {
+            assert_eq!(
+                text_expression(&column("c", data_type)),
+                expected,
+                "{}",
+                data_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("c"), "[c]");
+        assert_eq!(quote_identifier("a b"), "[a b]");
+        assert_eq!(quote_identifier("a]b[c"), "[a]]b[c]");
+        assert_eq!(
+            text_expression(&column("x]", "int")),
+            "CAST([x]]] AS nvarchar(max))"
+        );
+    }
+}
@@ -35,7 +35,7 @@ use mz_storage_client::types::sinks::{MetadataFilled, StorageSinkDesc};
 use mz_storage_client::types::sources::{IngestionDescription, SourceData};
 
 use crate::decode::metrics::DecodeMetrics;
-use crate::sink::SinkBaseMetrics;
+use crate::sink::{SinkBaseMetrics, SinkStatistics};
 use crate::source::metrics::SourceBaseMetrics;
 use crate::source::statistics::SourceStatistics;
 
@@ -103,6 +103,9 @@ pub struct StorageState {
     /// Stats objects shared with operators to allow them to update the metrics
     /// we report in `StatisticsUpdates` responses.
     pub source_statistics: HashMap<GlobalId, SourceStatistics>,
+    /// Stats objects shared with sink operators, reported alongside
+    /// `source_statistics`.
+    pub sink_statistics: HashMap<GlobalId, SinkStatistics>,
 }
 
 /// This maintains an additional read hold on the source data for a sink, alongside
@@ -250,7 +253,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
             if last_stats_time.is_none()
                 || last_stats_time.as_ref().unwrap().elapsed() >= Duration::from_secs(10)
             {
-                self.report_source_statistics(&response_tx);
+                self.report_storage_statistics(&response_tx);
                 last_stats_time = Some(Instant::now());
             }
 
@@ -325,6 +328,15 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         .exports
                         .insert(export.id, export.description.clone());
 
+                    self.storage_state.sink_statistics.insert(
+                        export.id,
+                        SinkStatistics::new(
+                            export.id,
+                            self.storage_state.timely_worker_index,
+                            &self.storage_state.sink_metrics,
+                        ),
+                    );
+
                     self.storage_state.sink_write_frontiers.insert(
                         export.id,
                         Rc::new(RefCell::new(Antichain::from_elem(
@@ -369,6 +381,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         self.storage_state.source_tokens.remove(&id);
                         self.storage_state.sink_tokens.remove(&id);
                         self.storage_state.sink_handles.remove(&id);
+                        self.storage_state.sink_statistics.remove(&id);
                         self.storage_state.dropped_ids.push(id);
                     }
                 }
@@ -426,18 +439,27 @@ impl<'w, A: Allocate> Worker<'w, A> {
         }
     }
 
-    /// Report source statistics back to the controller.
-    pub fn report_source_statistics(&mut self, response_tx: &ResponseSender) {
-        // Check if any observed frontier should advance the reported frontiers.
-        let mut to_send = vec![];
+    /// Report source and sink statistics back to the controller.
+    pub fn report_storage_statistics(&mut self, response_tx: &ResponseSender) {
+        let mut source_stats = vec![];
         for (_, stats) in self.storage_state.source_statistics.iter() {
             if let Some(snapshot) = stats.snapshot() {
-                to_send.push(snapshot);
+                source_stats.push(snapshot);
+            }
+        }
+
+        let mut sink_stats = vec![];
+        for (id, stats) in self.storage_state.sink_statistics.iter() {
+            if let Some(write_frontier) = self.storage_state.sink_write_frontiers.get(id) {
+                sink_stats.push(stats.snapshot(&write_frontier.borrow()));
             }
         }
 
-        if !to_send.is_empty() {
-            self.send_storage_response(response_tx, StorageResponse::StatisticsUpdates(to_send));
+        if !source_stats.is_empty() || !sink_stats.is_empty() {
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::StatisticsUpdates(source_stats, sink_stats),
+            );
         }
     }
 
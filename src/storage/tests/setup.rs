@@ -96,7 +96,7 @@ use mz_storage::DecodeMetrics;
 use mz_storage_client::client::StorageCommand;
 use mz_storage_client::types::sources::{
     encoding::SourceDataEncoding, GenericSourceConnection, SourceData, SourceDesc, SourceEnvelope,
-    TestScriptSourceConnection,
+    SourceRateLimits, TestScriptSourceConnection,
 };
 
 pub fn run_script_source(
@@ -115,6 +115,8 @@ pub fn run_script_source(
         envelope,
         metadata_columns: vec![],
         timestamp_interval,
+        mfp: None,
+        rate_limits: SourceRateLimits::default(),
     };
 
     build_and_run_source(desc, timestamp_interval, move |upper, mut read| {
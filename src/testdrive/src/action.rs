@@ -164,6 +164,7 @@ pub struct State {
     // === Materialize state. ===
     materialize_catalog_postgres_stash: Option<String>,
     materialize_sql_addr: String,
+    materialize_http_addr: String,
     materialize_internal_sql_addr: String,
     materialize_internal_http_addr: String,
     materialize_user: String,
@@ -254,6 +255,10 @@ impl State {
             "testdrive.materialize-sql-addr".into(),
             self.materialize_sql_addr.clone(),
         );
+        self.cmd_vars.insert(
+            "testdrive.materialize-http-addr".into(),
+            self.materialize_http_addr.clone(),
+        );
         self.cmd_vars.insert(
             "testdrive.materialize-internal-sql-addr".into(),
             self.materialize_internal_sql_addr.clone(),
@@ -322,6 +327,10 @@ impl State {
             "testdrive.materialize-sql-addr".into(),
             self.materialize_sql_addr.clone(),
         );
+        self.cmd_vars.insert(
+            "testdrive.materialize-http-addr".into(),
+            self.materialize_http_addr.clone(),
+        );
         self.cmd_vars.insert(
             "testdrive.materialize-internal-sql-addr".into(),
             self.materialize_internal_sql_addr.clone(),
@@ -828,6 +837,7 @@ pub async fn create_state(
 
     let (
         materialize_sql_addr,
+        materialize_http_addr,
         materialize_internal_sql_addr,
         materialize_internal_http_addr,
         materialize_user,
@@ -870,6 +880,11 @@ pub async fn create_state(
             materialize_url.host_str().unwrap(),
             materialize_url.port().unwrap()
         );
+        let materialize_http_addr = format!(
+            "{}:{}",
+            materialize_url.host_str().unwrap(),
+            config.materialize_http_port
+        );
         let materialize_internal_sql_addr = format!(
             "{}:{}",
             materialize_internal_url.host_str().unwrap(),
@@ -882,6 +897,7 @@ pub async fn create_state(
         );
         (
             materialize_sql_addr,
+            materialize_http_addr,
             materialize_internal_sql_addr,
             materialize_internal_http_addr,
             materialize_user,
@@ -976,6 +992,7 @@ pub async fn create_state(
         // === Materialize state. ===
         materialize_catalog_postgres_stash,
         materialize_sql_addr,
+        materialize_http_addr,
         materialize_internal_sql_addr,
         materialize_internal_http_addr,
         materialize_user,
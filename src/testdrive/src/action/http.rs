@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use reqwest::Method;
 
 use crate::action::{ControlFlow, State};
@@ -22,6 +22,15 @@ pub async fn run_request(
     let url = cmd.args.string("url")?;
     let method: Method = cmd.args.parse("method")?;
     let content_type = cmd.args.opt_string("content-type");
+    let header = cmd
+        .args
+        .opt_string("header")
+        .map(|header| match header.split_once(':') {
+            Some((name, value)) => Ok((name.to_string(), value.to_string())),
+            None => Err(anyhow!("header must be of the form NAME:VALUE")),
+        })
+        .transpose()?;
+    let expected_status: Option<u16> = cmd.args.opt_parse("status")?;
     let body = cmd.input.join("\n");
 
     println!("$ http-request {} {}\n{}", method, url, body);
@@ -33,15 +42,26 @@ pub async fn run_request(
     if let Some(value) = &content_type {
         request = request.header(CONTENT_TYPE, value);
     }
+    if let Some((name, value)) = &header {
+        request = request.header(name, value);
+    }
 
     let response = request.send().await?;
     let status = response.status();
 
     println!("{}\n{}", status, response.text().await?);
 
-    if status.is_success() {
-        Ok(ControlFlow::Continue)
-    } else {
-        bail!("http request returned failing status: {}", status)
+    match expected_status {
+        Some(expected) if status.as_u16() != expected => {
+            bail!(
+                "http request returned status {}, expected {}",
+                status,
+                expected
+            )
+        }
+        None if !status.is_success() => {
+            bail!("http request returned failing status: {}", status)
+        }
+        _ => Ok(ControlFlow::Continue),
     }
 }
@@ -288,6 +288,14 @@ impl ColumnKnowledge {
                             &input_knowledge[..],
                             knowledge_stack,
                         )?;
+                        if let Some(filter) = &mut aggregate.filter {
+                            optimize(
+                                filter,
+                                &input_typ.column_types,
+                                &input_knowledge[..],
+                                knowledge_stack,
+                            )?;
+                        }
                         // This could be improved.
                         let knowledge = match aggregate.func {
                             AggregateFunc::MaxInt16
@@ -319,11 +327,15 @@ impl ColumnKnowledge {
                             | AggregateFunc::MinTimestamp
                             | AggregateFunc::MinTimestampTz
                             | AggregateFunc::Any
-                            | AggregateFunc::All => {
+                            | AggregateFunc::All
+                            | AggregateFunc::BoolAnd
+                            | AggregateFunc::BoolOr
+                            | AggregateFunc::BitAnd
+                            | AggregateFunc::BitOr => {
                                 // These methods propagate constant values exactly.
                                 knowledge
                             }
-                            AggregateFunc::Count => DatumKnowledge {
+                            AggregateFunc::Count | AggregateFunc::CountDiffs => DatumKnowledge {
                                 value: None,
                                 nullable: false,
                             },
@@ -335,6 +347,16 @@ impl ColumnKnowledge {
                                 }
                             }
                         };
+                        // A filter may reject all inputs of a group, in which
+                        // case the aggregate produces its default value.
+                        let knowledge = if aggregate.filter.is_some() {
+                            DatumKnowledge {
+                                value: None,
+                                nullable: knowledge.nullable || aggregate.func.default().is_null(),
+                            }
+                        } else {
+                            knowledge
+                        };
                         output.push(knowledge);
                     }
                     Ok(output)
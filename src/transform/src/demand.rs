@@ -241,8 +241,7 @@ impl Demand {
                         // No obvious requirements on aggregate columns.
                         // A "non-empty" requirement, I guess?
                         if *column >= group_key.len() {
-                            new_columns
-                                .extend(aggregates[*column - group_key.len()].expr.support());
+                            new_columns.extend(aggregates[*column - group_key.len()].support());
                         }
                     }
 
@@ -255,6 +254,7 @@ impl Demand {
                                 func: AggregateFunc::Dummy,
                                 expr: MirScalarExpr::literal_ok(Datum::Dummy, typ.scalar_type),
                                 distinct: false,
+                                filter: None,
                             };
                         }
                     }
@@ -96,8 +96,8 @@ impl FoldConstants {
                 for key in group_key.iter_mut() {
                     key.reduce(input_typ);
                 }
-                for aggregate in aggregates.iter_mut() {
-                    aggregate.expr.reduce(input_typ);
+                for expr in aggregates.iter_mut().flat_map(|a| a.exprs_mut()) {
+                    expr.reduce(input_typ);
                 }
 
                 // Guard against evaluating an expression that may contain
@@ -105,7 +105,8 @@ impl FoldConstants {
                 if group_key.iter().any(|e| e.contains_unmaterializable())
                     || aggregates
                         .iter()
-                        .any(|a| a.expr.contains_unmaterializable())
+                        .flat_map(|a| a.exprs())
+                        .any(|e| e.contains_unmaterializable())
                 {
                     return Ok(());
                 }
@@ -469,10 +470,16 @@ impl FoldConstants {
             let val = match aggregates
                 .iter()
                 .map(|agg| {
+                    // Rows rejected by the filter do not contribute to the aggregate.
+                    if let Some(filter) = &agg.filter {
+                        if filter.eval(&datums, &temp_storage)? != Datum::True {
+                            return Ok(None);
+                        }
+                    }
                     row_buf
                         .packer()
                         .extend([agg.expr.eval(&datums, &temp_storage)?]);
-                    Ok::<_, EvalError>(row_buf.clone())
+                    Ok::<_, EvalError>(Some(row_buf.clone()))
                 })
                 .collect::<Result<Vec<_>, _>>()
             {
@@ -498,19 +505,19 @@ impl FoldConstants {
                     let temp_storage = RowArena::new();
                     row_buf.packer().extend(key.into_iter().chain(
                         aggregates.iter().enumerate().map(|(i, agg)| {
-                            if agg.distinct {
-                                agg.func.eval(
-                                    vals.iter()
-                                        .map(|val| val[i].unpack_first())
-                                        .collect::<HashSet<_>>()
-                                        .into_iter(),
-                                    &temp_storage,
-                                )
+                            let mut inputs = vals
+                                .iter()
+                                .filter_map(|val| val[i].as_ref())
+                                .map(|row| row.unpack_first())
+                                .peekable();
+                            if inputs.peek().is_none() {
+                                // All rows of the group were rejected by the filter.
+                                agg.func.default()
+                            } else if agg.distinct {
+                                agg.func
+                                    .eval(inputs.collect::<HashSet<_>>().into_iter(), &temp_storage)
                             } else {
-                                agg.func.eval(
-                                    vals.iter().map(|val| val[i].unpack_first()),
-                                    &temp_storage,
-                                )
+                                agg.func.eval(inputs, &temp_storage)
                             }
                         }),
                     ));
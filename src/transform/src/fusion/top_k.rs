@@ -46,6 +46,7 @@ impl TopK {
             limit,
             offset,
             monotonic,
+            expected_group_size,
         } = relation
         {
             while let MirRelationExpr::TopK {
@@ -55,6 +56,7 @@ impl TopK {
                 limit: inner_limit,
                 offset: inner_offset,
                 monotonic: inner_monotonic,
+                expected_group_size: inner_expected_group_size,
             } = &mut **input
             {
                 // We can fuse two chained TopK operators as long as they share the
@@ -88,6 +90,10 @@ impl TopK {
 
                     *offset += *inner_offset;
                     *monotonic = *inner_monotonic;
+                    // Keep the larger of the two hints, which also keeps a
+                    // hint that only one of the operators has.
+                    *expected_group_size =
+                        std::cmp::max(*expected_group_size, *inner_expected_group_size);
                     **input = inner_input.take_dangerous();
                 } else {
                     break;
@@ -96,3 +102,38 @@ impl TopK {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{GlobalId, RelationType, ScalarType};
+
+    use super::*;
+
+    fn fuse(inner_hint: Option<usize>, outer_hint: Option<usize>) -> Option<usize> {
+        let typ = RelationType::new(vec![ScalarType::Int64.nullable(true)]);
+        let mut relation = MirRelationExpr::global_get(GlobalId::User(0), typ)
+            .top_k(vec![0], vec![], Some(3), 0, inner_hint)
+            .top_k(vec![0], vec![], Some(2), 0, outer_hint);
+        TopK.action(&mut relation);
+        match relation {
+            MirRelationExpr::TopK {
+                input,
+                expected_group_size,
+                ..
+            } => {
+                assert!(matches!(*input, MirRelationExpr::Get { .. }));
+                expected_group_size
+            }
+            _ => panic!("expected a TopK"),
+        }
+    }
+
+    #[test]
+    fn test_fusion_keeps_expected_group_size() {
+        assert_eq!(fuse(None, None), None);
+        assert_eq!(fuse(Some(10), None), Some(10));
+        assert_eq!(fuse(None, Some(100)), Some(100));
+        assert_eq!(fuse(Some(10), Some(100)), Some(100));
+        assert_eq!(fuse(Some(100), Some(10)), Some(100));
+    }
+}
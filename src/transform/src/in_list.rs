@@ -0,0 +1,126 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Replaces long disjunctions of equalities with literals by IN list tests.
+//!
+//! The planner expands `e IN (v1, v2, ...)` into `e = v1 OR e = v2 OR ...`,
+//! whose evaluation takes time linear in the length of the list. This transform
+//! replaces such disjunctions with a single [`IsInList`] test, which looks up
+//! `e` in a sorted list of the values instead.
+//!
+//! The transform must run after the literal constraints of filters have been
+//! turned into index lookups, as that analysis only understands disjunctions of
+//! equalities.
+
+use mz_expr::func::IsInList;
+use mz_expr::visit::Visit;
+use mz_expr::{BinaryFunc, MirRelationExpr, MirScalarExpr, UnaryFunc, VariadicFunc};
+use mz_repr::Datum;
+
+use crate::TransformArgs;
+
+/// The minimum number of equalities with the same expression that are
+/// replaced by an IN list test.
+const MIN_IN_LIST_LEN: usize = 8;
+
+/// Replaces long disjunctions of equalities with literals by IN list tests.
+#[derive(Debug)]
+pub struct InList;
+
+impl crate::Transform for InList {
+    #[tracing::instrument(
+        target = "optimizer"
+        level = "trace",
+        skip_all,
+        fields(path.segment = "in_list")
+    )]
+    fn transform(
+        &self,
+        relation: &mut MirRelationExpr,
+        _: TransformArgs,
+    ) -> Result<(), crate::TransformError> {
+        let result = relation
+            .try_visit_scalars_mut(&mut |s| s.visit_mut_post(&mut Self::action))
+            .map_err(From::from);
+        mz_repr::explain_new::trace_plan(&*relation);
+        result
+    }
+}
+
+impl InList {
+    /// Replaces the equalities with literals in the disjunction `expr`, if
+    /// there are at least [`MIN_IN_LIST_LEN`] of them with the same expression.
+    pub fn action(expr: &mut MirScalarExpr) {
+        let exprs = match expr {
+            MirScalarExpr::CallVariadic {
+                func: VariadicFunc::Or,
+                exprs,
+            } => exprs,
+            _ => return,
+        };
+
+        // Group the disjuncts that compare the same expression to a literal,
+        // in order of their first appearance.
+        let mut groups: Vec<(MirScalarExpr, Vec<usize>)> = Vec::new();
+        for (i, disjunct) in exprs.iter().enumerate() {
+            if let Some((e, _)) = Self::equality_with_literal(disjunct) {
+                match groups.iter().position(|(group, _)| group == e) {
+                    Some(g) => groups[g].1.push(i),
+                    None => groups.push((e.clone(), vec![i])),
+                }
+            }
+        }
+
+        let mut replaced = false;
+        for (e, positions) in groups {
+            if positions.len() < MIN_IN_LIST_LEN {
+                continue;
+            }
+            let values = positions
+                .iter()
+                .map(|i| Self::equality_with_literal(&exprs[*i]).unwrap().1);
+            let test = e.call_unary(UnaryFunc::IsInList(IsInList::new(values)));
+            // Replace the first equality with the test, and the others with a
+            // literal `false`, which is removed below.
+            exprs[positions[0]] = test;
+            for i in &positions[1..] {
+                exprs[*i] = MirScalarExpr::literal_false();
+            }
+            replaced = true;
+        }
+
+        if replaced {
+            exprs.retain(|e| !e.is_literal_false());
+            if exprs.len() == 1 {
+                *expr = exprs.pop().unwrap();
+            }
+        }
+    }
+
+    /// If `expr` is an equality between a non-literal expression and a
+    /// non-null literal, returns the expression and the literal.
+    fn equality_with_literal(expr: &MirScalarExpr) -> Option<(&MirScalarExpr, Datum)> {
+        if let MirScalarExpr::CallBinary {
+            func: BinaryFunc::Eq,
+            expr1,
+            expr2,
+        } = expr
+        {
+            let (e, literal) = match (expr1.as_literal(), expr2.as_literal()) {
+                (None, Some(Ok(literal))) => (&**expr1, literal),
+                (Some(Ok(literal)), None) => (&**expr2, literal),
+                _ => return None,
+            };
+            if !literal.is_null() {
+                return Some((e, literal));
+            }
+        }
+        None
+    }
+}
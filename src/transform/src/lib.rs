@@ -105,12 +105,14 @@ pub mod cse;
 pub mod demand;
 pub mod fold_constants;
 pub mod fusion;
+pub mod in_list;
 pub mod join_implementation;
 pub mod literal_lifting;
 pub mod monotonic;
 pub mod nonnull_requirements;
 pub mod nonnullable;
 pub mod normalize_lets;
+pub mod plan_limits;
 pub mod predicate_pushdown;
 pub mod projection_extraction;
 pub mod projection_lifting;
@@ -169,6 +171,15 @@ pub enum TransformError {
     LetRecUnsupported,
     /// A reference to an apparently unbound identifier.
     IdentifierMissing(mz_expr::LocalId),
+    /// A plan exceeds one of the configured [`plan_limits::PlanLimits`].
+    PlanLimitExceeded {
+        /// The limit that was exceeded.
+        limit: plan_limits::PlanLimit,
+        /// The configured maximum.
+        max: usize,
+        /// The value observed in the plan.
+        actual: usize,
+    },
 }
 
 impl fmt::Display for TransformError {
@@ -179,6 +190,11 @@ impl fmt::Display for TransformError {
             TransformError::IdentifierMissing(i) => {
                 write!(f, "apparently unbound identifier: {:?}", i)
             }
+            TransformError::PlanLimitExceeded { limit, max, actual } => write!(
+                f,
+                "plan exceeds the maximum {} ({} > {})",
+                limit, actual, max
+            ),
         }
     }
 }
@@ -484,6 +500,9 @@ impl Optimizer {
                 ],
             }),
             Box::new(crate::canonicalize_mfp::CanonicalizeMfp),
+            // Must run after the literal constraints of filters have been
+            // turned into index lookups by `CanonicalizeMfp`.
+            Box::new(crate::in_list::InList),
             // Identifies common relation subexpressions.
             Box::new(crate::cse::relation_cse::RelationCSE::new(false)),
             Box::new(crate::fold_constants::FoldConstants { limit: Some(10000) }),
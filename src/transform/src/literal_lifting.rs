@@ -523,9 +523,9 @@ impl LiteralLifting {
                                 }
                             })?;
                         }
-                        // Inline literals into aggregate value selector expressions.
-                        for aggr in aggregates.iter_mut() {
-                            aggr.expr.visit_mut_post(&mut |e| {
+                        // Inline literals into aggregate value selector and filter expressions.
+                        for expr in aggregates.iter_mut().flat_map(|a| a.exprs_mut()) {
+                            expr.visit_mut_post(&mut |e| {
                                 if let MirScalarExpr::Column(c) = e {
                                     if *c >= input_arity {
                                         *e = literals[*c - input_arity].clone();
@@ -615,6 +615,7 @@ impl LiteralLifting {
                     limit: _,
                     offset: _,
                     monotonic: _,
+                    expected_group_size: _,
                 } => {
                     let literals = self.action(input, gets)?;
                     if !literals.is_empty() {
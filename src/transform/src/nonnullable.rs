@@ -82,7 +82,10 @@ impl NonNullable {
                 let contains_isnull_or_count = aggregates
                     .iter()
                     .map::<Result<_, TransformError>, _>(|a| {
-                        let contains_null = scalar_contains_isnull(&(a).expr)?;
+                        let mut contains_null = false;
+                        for expr in a.exprs() {
+                            contains_null |= scalar_contains_isnull(expr)?;
+                        }
                         let matches_count = matches!(&(a).func, AggregateFunc::Count);
                         Ok(contains_null || matches_count)
                     })
@@ -90,7 +93,9 @@ impl NonNullable {
                 if contains_isnull_or_count {
                     let metadata = input.typ();
                     for aggregate in aggregates.iter_mut() {
-                        scalar_nonnullable(&mut aggregate.expr, &metadata)?;
+                        for expr in aggregate.exprs_mut() {
+                            scalar_nonnullable(expr, &metadata)?;
+                        }
                         aggregate_nonnullable(aggregate, &metadata);
                     }
                 }
@@ -18,7 +18,10 @@
 //! 2. Each expression assigns a contiguous block of identifiers.
 //!
 //! The transform may remove some `Let` and `Get` operators, and does not
-//! introduce any new operators.
+//! introduce any new operators. In particular, bindings that are used at most
+//! once are inlined, bindings that are never used are removed, and in
+//! non-recursive expressions bindings whose values are identical to those of
+//! prior bindings are replaced by references to the prior bindings.
 //!
 //! The module also publishes the function `renumber_bindings` which can
 //! be used to renumber bindings in an expression starting from a provided
@@ -117,6 +120,13 @@ impl NormalizeLets {
         // placing all `LetRec` nodes around the root, if not always in a single AST node.
         let_motion::promote_let_rec(relation);
 
+        // Redirect references to bindings whose values repeat those of prior bindings.
+        // This is only correct for non-recursive bindings, and the redirected bindings
+        // are subsequently removed as dead code by inlining.
+        if !was_recursive {
+            deduplication::deduplicate_lets(relation);
+        }
+
         inlining::inline_lets(relation, self.inline_mfp)?;
 
         support::refresh_types(relation)?;
@@ -432,6 +442,57 @@ mod let_motion {
     }
 }
 
+mod deduplication {
+
+    use std::collections::{BTreeMap, HashMap};
+
+    use mz_expr::{Id, LocalId, MirRelationExpr};
+
+    /// Replaces references to bindings whose values are identical to those of a prior binding.
+    ///
+    /// Bindings are visited in order, and references to already deduplicated bindings are
+    /// substituted before each value is compared, so that chains of duplicated bindings are
+    /// also detected. The duplicated bindings are left in place but are no longer referenced.
+    ///
+    /// This is only correct for non-recursive bindings, where the value of each binding does
+    /// not depend on the iteration at which it is observed.
+    pub(super) fn deduplicate_lets(expr: &mut MirRelationExpr) {
+        if let MirRelationExpr::LetRec { ids, values, body } = expr {
+            let mut replacements = BTreeMap::new();
+            // Indexes of retained bindings, by the hash of their values.
+            let mut retained: HashMap<u64, Vec<usize>> = HashMap::new();
+            for index in 0..values.len() {
+                replace_gets(&mut values[index], &replacements);
+                let hash = mz_ore::hash::hash(&values[index]);
+                let candidates = retained.entry(hash).or_default();
+                if let Some(prior) = candidates.iter().find(|i| values[**i] == values[index]) {
+                    replacements.insert(ids[index], ids[*prior]);
+                } else {
+                    candidates.push(index);
+                }
+            }
+            replace_gets(body, &replacements);
+        }
+    }
+
+    /// Substitutes local identifiers in `Get` expressions according to `replacements`.
+    fn replace_gets(expr: &mut MirRelationExpr, replacements: &BTreeMap<LocalId, LocalId>) {
+        if replacements.is_empty() {
+            return;
+        }
+        expr.visit_pre_mut(|expr| {
+            if let MirRelationExpr::Get {
+                id: Id::Local(id), ..
+            } = expr
+            {
+                if let Some(replacement) = replacements.get(&*id) {
+                    *id = *replacement;
+                }
+            }
+        });
+    }
+}
+
 mod inlining {
 
     use std::collections::BTreeMap;
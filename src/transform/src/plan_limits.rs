@@ -0,0 +1,126 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Guards against plans that are too large to render.
+//!
+//! Very large plans can exhaust the resources of the replicas that render
+//! them, or take unreasonably long to build. [`PlanLimits`] inspects optimized
+//! plans and rejects those that exceed the configured limits with a
+//! [`TransformError::PlanLimitExceeded`] error, before they are shipped to
+//! compute.
+
+use std::fmt;
+
+use mz_compute_client::types::dataflows::DataflowDesc;
+use mz_expr::MirRelationExpr;
+
+use crate::TransformError;
+
+/// A limit on the shape of a plan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlanLimit {
+    /// The total number of operators in the plans of a dataflow.
+    Nodes,
+    /// The number of inputs of a single join.
+    JoinInputs,
+    /// The nesting depth of a single plan.
+    Depth,
+}
+
+impl fmt::Display for PlanLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanLimit::Nodes => f.write_str("number of operators"),
+            PlanLimit::JoinInputs => f.write_str("number of join inputs"),
+            PlanLimit::Depth => f.write_str("nesting depth"),
+        }
+    }
+}
+
+/// Configurable limits on the size of optimized plans.
+///
+/// A limit of `None` is not enforced. The default value enforces no limits.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlanLimits {
+    /// The maximum total number of operators in the plans of a dataflow.
+    pub max_nodes: Option<usize>,
+    /// The maximum number of inputs of any join.
+    pub max_join_inputs: Option<usize>,
+    /// The maximum nesting depth of any plan.
+    pub max_depth: Option<usize>,
+}
+
+impl PlanLimits {
+    /// Checks that `expr` does not exceed any of the limits.
+    pub fn check(&self, expr: &MirRelationExpr) -> Result<(), TransformError> {
+        let mut stats = PlanStats::default();
+        stats.observe(expr, 1);
+        self.check_stats(&stats)
+    }
+
+    /// Checks that the plans of the objects built by `dataflow` do not
+    /// exceed any of the limits.
+    ///
+    /// The number of operators is accumulated across all plans of the
+    /// dataflow, as they are rendered together.
+    pub fn check_dataflow(&self, dataflow: &DataflowDesc) -> Result<(), TransformError> {
+        let mut stats = PlanStats::default();
+        for build in &dataflow.objects_to_build {
+            stats.observe(build.plan.as_inner(), 1);
+        }
+        self.check_stats(&stats)
+    }
+
+    fn check_stats(&self, stats: &PlanStats) -> Result<(), TransformError> {
+        let checks = [
+            (PlanLimit::Nodes, self.max_nodes, stats.nodes),
+            (
+                PlanLimit::JoinInputs,
+                self.max_join_inputs,
+                stats.join_inputs,
+            ),
+            (PlanLimit::Depth, self.max_depth, stats.depth),
+        ];
+        for (limit, max, actual) in checks {
+            if let Some(max) = max {
+                if actual > max {
+                    return Err(TransformError::PlanLimitExceeded { limit, max, actual });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Statistics about the shape of one or more plans.
+#[derive(Debug, Default)]
+struct PlanStats {
+    /// The total number of operators.
+    nodes: usize,
+    /// The largest number of inputs of any join.
+    join_inputs: usize,
+    /// The largest nesting depth.
+    depth: usize,
+}
+
+impl PlanStats {
+    /// Accumulates the statistics of `expr`, which is nested at `depth`.
+    fn observe(&mut self, expr: &MirRelationExpr, depth: usize) {
+        mz_ore::stack::maybe_grow(|| {
+            self.nodes += 1;
+            self.depth = std::cmp::max(self.depth, depth);
+            if let MirRelationExpr::Join { inputs, .. } = expr {
+                self.join_inputs = std::cmp::max(self.join_inputs, inputs.len());
+            }
+            for child in expr.children() {
+                self.observe(child, depth + 1);
+            }
+        })
+    }
+}
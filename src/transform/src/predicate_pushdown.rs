@@ -314,6 +314,7 @@ impl PredicatePushdown {
                                             && aggregates[0].func == AggregateFunc::Any
                                         {
                                             push_down.push(aggregates[0].expr.clone());
+                                            push_down.extend(aggregates[0].filter.take());
                                             aggregates[0].expr = MirScalarExpr::literal_ok(
                                                 Datum::True,
                                                 ScalarType::Bool,
@@ -232,7 +232,9 @@ impl ProjectionLifting {
                             key.permute(outputs);
                         }
                         for aggregate in aggregates.iter_mut() {
-                            aggregate.expr.permute(outputs);
+                            for expr in aggregate.exprs_mut() {
+                                expr.permute(outputs);
+                            }
                         }
                         **input = inner.take_dangerous();
                     }
@@ -245,6 +247,7 @@ impl ProjectionLifting {
                     limit,
                     offset,
                     monotonic: _,
+                    expected_group_size,
                 } => {
                     self.action(input, gets)?;
                     if let MirRelationExpr::Project {
@@ -265,6 +268,7 @@ impl ProjectionLifting {
                                 order_key.clone(),
                                 limit.clone(),
                                 offset.clone(),
+                                expected_group_size.clone(),
                             )
                             .project(outputs.clone());
                     }
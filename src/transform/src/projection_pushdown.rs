@@ -267,14 +267,14 @@ impl ProjectionPushdown {
                     } else {
                         // No obvious requirements on aggregate columns.
                         // A "non-empty" requirement, I guess?
-                        columns_to_pushdown.extend(aggregates[index].expr.support())
+                        columns_to_pushdown.extend(aggregates[index].support())
                     }
                 }
 
                 reverse_permute(
                     group_key
                         .iter_mut()
-                        .chain(aggregates.iter_mut().map(|a| &mut a.expr)),
+                        .chain(aggregates.iter_mut().flat_map(|a| a.exprs_mut())),
                     columns_to_pushdown.iter(),
                 );
 
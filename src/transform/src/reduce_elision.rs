@@ -52,10 +52,12 @@ impl ReduceElision {
         } = relation
         {
             let input_type = input.typ();
-            if input_type.keys.iter().any(|keys| {
-                keys.iter()
-                    .all(|k| group_key.contains(&mz_expr::MirScalarExpr::Column(*k)))
-            }) {
+            if aggregates.iter().all(|a| a.supports_on_unique())
+                && input_type.keys.iter().any(|keys| {
+                    keys.iter()
+                        .all(|k| group_key.contains(&mz_expr::MirScalarExpr::Column(*k)))
+                })
+            {
                 let map_scalars = aggregates
                     .iter()
                     .map(|a| a.on_unique(&input_type.column_types))
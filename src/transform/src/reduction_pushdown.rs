@@ -123,8 +123,8 @@ impl ReductionPushdown {
                         }
                     })?;
                 }
-                for agg in aggregates.iter_mut() {
-                    agg.expr.visit_mut_post(&mut |e| {
+                for expr in aggregates.iter_mut().flat_map(|a| a.exprs_mut()) {
+                    expr.visit_mut_post(&mut |e| {
                         if let mz_expr::MirScalarExpr::Column(c) = e {
                             if *c >= arity {
                                 *e = scalars[*c - arity].clone();
@@ -298,8 +298,8 @@ fn try_push_reduce_through_join(
         if let Some(component) =
             lookup_corresponding_component(&agg.expr, &old_join_mapper, &input_component_map)
         {
-            if !agg.distinct {
-                // TODO: support non-distinct aggs.
+            if !agg.distinct || agg.filter.is_some() {
+                // TODO: support non-distinct and filtered aggs.
                 // For more details, see https://github.com/MaterializeInc/materialize/issues/9604
                 return None;
             }
@@ -306,6 +306,9 @@ mod tests {
             "NonNullRequirements" => Ok(Box::new(
                 mz_transform::nonnull_requirements::NonNullRequirements::default(),
             )),
+            "NormalizeLets" => Ok(Box::new(mz_transform::normalize_lets::NormalizeLets::new(
+                false,
+            ))),
             "PredicatePushdown" => Ok(Box::new(
                 mz_transform::predicate_pushdown::PredicatePushdown::default(),
             )),
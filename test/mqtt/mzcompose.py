@@ -0,0 +1,56 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import Materialized, Mosquitto, Testdrive
+
+SERVICES = [
+    Materialized(),
+    Mosquitto(),
+    Testdrive(),
+    Testdrive(name="testdrive_no_reset", no_reset=True),
+]
+
+
+def publish(c: Composition, topic: str, *messages: str) -> None:
+    for message in messages:
+        c.exec(
+            "mosquitto",
+            "mosquitto_pub",
+            "-q",
+            "1",
+            "-t",
+            topic,
+            "-m",
+            message,
+        )
+
+
+def workflow_default(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "mosquitto"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "create-source.td")
+
+    publish(c, "sensors/a", "one", "two")
+    publish(c, "sensors/b", "three")
+    publish(c, "other/c", "ignored")
+    c.run("testdrive_no_reset", "verify-before-restart.td")
+
+    # The broker queues the messages published while the source is down, and
+    # delivers them once the source resumes its session.
+    c.kill("materialized")
+    publish(c, "sensors/a", "four")
+    publish(c, "sensors/b", "five")
+    c.up("materialized")
+    c.wait_for_materialized()
+
+    c.run("testdrive_no_reset", "verify-after-restart.td")
+
+    c.run("testdrive_no_reset", "drop-source.td")
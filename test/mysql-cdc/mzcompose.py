@@ -0,0 +1,50 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+import random
+import string
+
+from materialize.mzcompose import Composition, WorkflowArgumentParser
+from materialize.mzcompose.services import Materialized, MySql, Testdrive
+
+mysql_root_password = "AAbb!@" + "".join(
+    random.choices(string.ascii_uppercase + string.digits, k=10)
+)
+
+SERVICES = [
+    Materialized(),
+    Testdrive(),
+    MySql(
+        mysql_root_password=mysql_root_password,
+        # The MySQL source requires GTIDs; row-based binlogs with full row
+        # images are already the default.
+        additional_args=[
+            "--gtid-mode=ON",
+            "--enforce-gtid-consistency=ON",
+        ],
+    ),
+]
+
+
+def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
+    parser.add_argument(
+        "filter",
+        nargs="*",
+        default=["*.td"],
+        help="limit to only the files matching filter",
+    )
+    args = parser.parse_args()
+
+    c.start_and_wait_for_tcp(services=["materialized", "mysql"])
+    c.wait_for_materialized()
+    c.run(
+        "testdrive",
+        f"--var=mysql-root-password={mysql_root_password}",
+        *args.filter,
+    )
@@ -0,0 +1,34 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose import Composition
+from materialize.mzcompose.services import Materialized, PubSubEmulator, Testdrive
+
+SERVICES = [
+    Materialized(),
+    PubSubEmulator(),
+    Testdrive(),
+    Testdrive(name="testdrive_no_reset", no_reset=True),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["materialized", "pubsub-emulator"])
+    c.wait_for_materialized()
+
+    c.run("testdrive", "pubsub.td")
+
+    # Messages that are published while the source is down are pulled once it
+    # resumes, and those that were acknowledged before are not redelivered.
+    c.kill("materialized")
+    c.run("testdrive_no_reset", "publish-during-restart.td")
+    c.up("materialized")
+    c.wait_for_materialized()
+
+    c.run("testdrive_no_reset", "verify-after-restart.td")
@@ -19,6 +19,10 @@ from materialize.mzcompose.services import (
 
 testdrive_no_reset = Testdrive(name="testdrive_no_reset", no_reset=True)
 
+# File sources read from a path that must survive a restart, so the file
+# source workflow uses a fixed temporary directory.
+FILE_SOURCE_TEMP_DIR = "--temp-dir=/share/tmp/file-source"
+
 SERVICES = [
     Zookeeper(),
     Kafka(auto_create_topics=True),
@@ -160,8 +164,39 @@ def workflow_storage_managed_collections(c: Composition) -> None:
         raise Exception("user shards empty or not equal after restart")
 
 
+def workflow_file_source(c: Composition) -> None:
+    # Start from an empty file.
+    c.rm(
+        "testdrive",
+        "materialized",
+        stop=True,
+        destroy_volumes=True,
+    )
+    c.rm_volumes("mzdata", "tmp", force=True)
+
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    with c.override(Testdrive(entrypoint_extra=[FILE_SOURCE_TEMP_DIR])):
+        c.run("testdrive", "file-source-before-restart.td")
+
+    # Restart mz.
+    c.kill("materialized")
+    c.up("materialized")
+    c.wait_for_materialized()
+
+    with c.override(
+        Testdrive(
+            name="testdrive_no_reset",
+            no_reset=True,
+            entrypoint_extra=[FILE_SOURCE_TEMP_DIR],
+        )
+    ):
+        c.run("testdrive_no_reset", "file-source-after-restart.td")
+
+
 def workflow_default(c: Composition) -> None:
     c.workflow("github-8021")
     c.workflow("audit-log")
     c.workflow("timelines")
     c.workflow("stash")
+    c.workflow("file-source")
@@ -0,0 +1,44 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+import random
+import string
+
+from materialize.mzcompose import Composition, WorkflowArgumentParser
+from materialize.mzcompose.services import Materialized, SqlServer, Testdrive
+
+sa_password = "AAbb!@" + "".join(
+    random.choices(string.ascii_uppercase + string.digits, k=10)
+)
+
+SERVICES = [
+    Materialized(),
+    # The SQL Server Agent's capture job only picks up changes every few
+    # seconds.
+    Testdrive(default_timeout="300s"),
+    SqlServer(sa_password=sa_password),
+]
+
+
+def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
+    parser.add_argument(
+        "filter",
+        nargs="*",
+        default=["*.td"],
+        help="limit to only the files matching filter",
+    )
+    args = parser.parse_args()
+
+    c.start_and_wait_for_tcp(services=["materialized", "sql-server"])
+    c.wait_for_materialized()
+    c.run(
+        "testdrive",
+        f"--var=sa-password={sa_password}",
+        *args.filter,
+    )